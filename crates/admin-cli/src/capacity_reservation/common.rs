@@ -0,0 +1,55 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::forge as forgerpc;
+use prettytable::{Table, row};
+
+/// Produces a table for printing a non-JSON representation of
+/// capacity reservations to standard out.
+pub fn convert_reservations_to_table(reservations: &[forgerpc::CapacityReservation]) -> Box<Table> {
+    let mut table = Box::new(Table::new());
+
+    table.set_titles(row![
+        "Id",
+        "Tenant",
+        "InstanceType",
+        "Count",
+        "InUse",
+        "Available",
+        "Active",
+        "Expires",
+    ]);
+
+    for reservation in reservations {
+        let status = reservation.status.clone().unwrap_or_default();
+        table.add_row(row![
+            reservation.id.map(|id| id.to_string()).unwrap_or_default(),
+            reservation.tenant_organization_id,
+            reservation.instance_type_id,
+            reservation.count,
+            status.in_use,
+            status.instance_type_available,
+            status.active,
+            reservation
+                .expires_at
+                .map(|expires_at| expires_at.to_string())
+                .unwrap_or_else(|| "never".to_string()),
+        ]);
+    }
+
+    table
+}
@@ -0,0 +1,50 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use carbide_uuid::capacity_reservation::CapacityReservationId;
+use chrono::{DateTime, Utc};
+use clap::Parser;
+
+#[derive(Parser, Debug, Clone)]
+pub struct Args {
+    #[clap(
+        short = 'i',
+        long,
+        help = "Optional, unique ID to use when creating the reservation"
+    )]
+    pub id: Option<CapacityReservationId>,
+
+    #[clap(
+        short = 't',
+        long,
+        help = "Tenant organization the machines are reserved for"
+    )]
+    pub tenant_org_id: String,
+
+    #[clap(long, help = "Instance type of the reserved machines")]
+    pub instance_type_id: String,
+
+    #[clap(short = 'c', long, help = "Number of machines to reserve")]
+    pub count: u32,
+
+    #[clap(
+        short = 'e',
+        long,
+        help = "Optional, RFC 3339 timestamp at which the reservation expires"
+    )]
+    pub expires_at: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,54 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::admin_cli::{CarbideCliError, CarbideCliResult, OutputFormat};
+use ::rpc::forge::CapacityReservationCreationRequest;
+
+use super::args::Args;
+use crate::capacity_reservation::common::convert_reservations_to_table;
+use crate::rpc::ApiClient;
+
+/// Create a capacity reservation.
+/// On successful creation, the details of the
+/// new reservation will be displayed.
+pub async fn create(
+    args: Args,
+    output_format: OutputFormat,
+    api_client: &ApiClient,
+) -> CarbideCliResult<()> {
+    let reservation = api_client
+        .0
+        .create_capacity_reservation(CapacityReservationCreationRequest {
+            id: args.id,
+            tenant_organization_id: args.tenant_org_id,
+            instance_type_id: args.instance_type_id,
+            count: args.count,
+            expires_at: args.expires_at.map(Into::into),
+        })
+        .await?;
+
+    if output_format == OutputFormat::Json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&reservation).map_err(CarbideCliError::JsonError)?
+        );
+    } else {
+        convert_reservations_to_table(&[reservation]).printstd();
+    }
+
+    Ok(())
+}
@@ -0,0 +1,182 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// The intent of the tests.rs file is to test the integrity of the
+// command, including things like basic structure parsing, enum
+// translations, and any external input validators that are
+// configured. Specific "categories" are:
+//
+// Command Structure - Baseline debug_assert() of the entire command.
+// Argument Parsing  - Ensure required/optional arg combinations parse correctly.
+
+use clap::{CommandFactory, Parser};
+
+use super::*;
+
+const TEST_RESERVATION_ID: &str = "0d3a8e2c-4b7f-4e34-9a57-3c1f7d9b2a61";
+
+// verify_cmd_structure runs a baseline clap debug_assert()
+// to do basic command configuration checking and validation,
+// ensuring things like unique argument definitions, group
+// configurations, argument references, etc. Things that would
+// otherwise be missed until runtime.
+#[test]
+fn verify_cmd_structure() {
+    Cmd::command().debug_assert();
+}
+
+/////////////////////////////////////////////////////////////////////////////
+// Argument Parsing
+//
+// This section contains tests specific to argument parsing,
+// including testing required arguments, as well as optional
+// flag-specific checking.
+
+// parse_create ensures create parses with the required
+// arguments.
+#[test]
+fn parse_create() {
+    let cmd = Cmd::try_parse_from([
+        "capacity-reservation",
+        "create",
+        "--tenant-org-id",
+        "tenant-a",
+        "--instance-type-id",
+        "type-123",
+        "--count",
+        "4",
+    ])
+    .expect("should parse create");
+
+    match cmd {
+        Cmd::Create(args) => {
+            assert!(args.id.is_none());
+            assert_eq!(args.tenant_org_id, "tenant-a");
+            assert_eq!(args.instance_type_id, "type-123");
+            assert_eq!(args.count, 4);
+            assert!(args.expires_at.is_none());
+        }
+        _ => panic!("expected Create variant"),
+    }
+}
+
+// parse_create_with_expiry ensures create parses an
+// RFC 3339 expiry timestamp.
+#[test]
+fn parse_create_with_expiry() {
+    let cmd = Cmd::try_parse_from([
+        "capacity-reservation",
+        "create",
+        "--id",
+        TEST_RESERVATION_ID,
+        "-t",
+        "tenant-a",
+        "--instance-type-id",
+        "type-123",
+        "-c",
+        "1",
+        "--expires-at",
+        "2030-01-01T00:00:00Z",
+    ])
+    .expect("should parse create with expiry");
+
+    match cmd {
+        Cmd::Create(args) => {
+            assert_eq!(args.id.unwrap().to_string(), TEST_RESERVATION_ID);
+            assert_eq!(
+                args.expires_at.unwrap().to_rfc3339(),
+                "2030-01-01T00:00:00+00:00"
+            );
+        }
+        _ => panic!("expected Create variant"),
+    }
+}
+
+// parse_create_missing_count_fails ensures create
+// fails without a count.
+#[test]
+fn parse_create_missing_count_fails() {
+    let result = Cmd::try_parse_from([
+        "capacity-reservation",
+        "create",
+        "--tenant-org-id",
+        "tenant-a",
+        "--instance-type-id",
+        "type-123",
+    ]);
+    assert!(result.is_err(), "should fail without --count");
+}
+
+// parse_show_no_args ensures show parses with no
+// arguments (all active reservations).
+#[test]
+fn parse_show_no_args() {
+    let cmd = Cmd::try_parse_from(["capacity-reservation", "show"]).expect("should parse show");
+
+    match cmd {
+        Cmd::Show(args) => {
+            assert!(args.tenant_org_id.is_none());
+            assert!(args.instance_type_id.is_none());
+            assert!(!args.all);
+        }
+        _ => panic!("expected Show variant"),
+    }
+}
+
+// parse_show_with_filters ensures show parses with
+// filters and --all.
+#[test]
+fn parse_show_with_filters() {
+    let cmd = Cmd::try_parse_from([
+        "capacity-reservation",
+        "show",
+        "--tenant-org-id",
+        "tenant-a",
+        "--instance-type-id",
+        "type-123",
+        "--all",
+    ])
+    .expect("should parse show with filters");
+
+    match cmd {
+        Cmd::Show(args) => {
+            assert_eq!(args.tenant_org_id, Some("tenant-a".to_string()));
+            assert_eq!(args.instance_type_id, Some("type-123".to_string()));
+            assert!(args.all);
+        }
+        _ => panic!("expected Show variant"),
+    }
+}
+
+// parse_release ensures release parses with required ID.
+#[test]
+fn parse_release() {
+    let cmd = Cmd::try_parse_from([
+        "capacity-reservation",
+        "release",
+        "--id",
+        TEST_RESERVATION_ID,
+    ])
+    .expect("should parse release");
+
+    match cmd {
+        Cmd::Release(args) => {
+            assert_eq!(args.id.to_string(), TEST_RESERVATION_ID);
+        }
+        _ => panic!("expected Release variant"),
+    }
+}
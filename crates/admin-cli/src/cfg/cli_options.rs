@@ -19,14 +19,15 @@ use rpc::admin_cli::OutputFormat;
 
 use crate::cfg::measurement;
 use crate::{
-    bmc_machine, boot_override, credential, devenv, domain, dpa, dpu, dpu_remediation,
-    expected_machines, expected_power_shelf, expected_switch, extension_service, firmware,
-    generate_shell_complete, host, ib_partition, instance, instance_type, inventory, ip, jump,
-    machine, machine_interfaces, machine_validation, managed_host, mlx, network_devices,
+    bmc_machine, boot_override, capacity_reservation, credential, devenv, domain, dpa, dpu,
+    dpu_remediation, expected_machines, expected_power_shelf, expected_switch, extension_service,
+    firmware, generate_shell_complete, host, ib_partition, instance, instance_type,
+    instance_webhook, inventory, ip, jump, machine, machine_interfaces, machine_update_rollout,
+    machine_validation, maintenance_window, managed_host, mlx, network_devices,
     network_security_group, network_segment, nvl_logical_partition, nvl_partition, os_image, ping,
     power_shelf, rack, rack_firmware, redfish, resource_pool, rms, route_server, scout_stream, set,
-    site_explorer, sku, ssh, switch, tenant, tenant_keyset, tpm_ca, trim_table, version, vpc,
-    vpc_peering, vpc_prefix,
+    site_explorer, sku, ssh, ssh_console, switch, tenant, tenant_keyset, tpm_ca, trim_table,
+    version, vpc, vpc_peering, vpc_prefix,
 };
 
 #[derive(Parser, Debug)]
@@ -264,9 +265,19 @@ pub enum CliCommand {
     #[clap(about = "Instance type management", visible_alias = "it", subcommand)]
     InstanceType(instance_type::Cmd),
 
+    #[clap(
+        about = "Capacity reservations of instance types",
+        visible_alias = "cr",
+        subcommand
+    )]
+    CapacityReservation(capacity_reservation::Cmd),
+
     #[clap(about = "SSH Util functions", subcommand)]
     Ssh(ssh::Cmd),
 
+    #[clap(about = "List and replay recorded ssh-console sessions", subcommand)]
+    SshConsole(ssh_console::Cmd),
+
     #[clap(about = "Power Shelf management", subcommand, visible_alias = "ps")]
     PowerShelf(power_shelf::Cmd),
 
@@ -289,6 +300,27 @@ pub enum CliCommand {
     #[clap(about = "Firmware related actions", subcommand)]
     Firmware(firmware::Cmd),
 
+    #[clap(
+        about = "Staged rollout of machine updates",
+        visible_alias = "mur",
+        subcommand
+    )]
+    MachineUpdateRollout(machine_update_rollout::Cmd),
+
+    #[clap(
+        about = "Maintenance windows which restrict when disruptive work starts on hosts",
+        visible_alias = "mw",
+        subcommand
+    )]
+    MaintenanceWindow(maintenance_window::Cmd),
+
+    #[clap(
+        about = "Webhooks which notify tenants about the lifecycle of their instances",
+        visible_alias = "iwh",
+        subcommand
+    )]
+    InstanceWebhook(instance_webhook::Cmd),
+
     #[clap(about = "DPA related handling", subcommand)]
     Dpa(dpa::Cmd),
     #[clap(about = "Trim DB tables", subcommand)]
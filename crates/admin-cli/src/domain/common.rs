@@ -0,0 +1,81 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::admin_cli::{CarbideCliError, CarbideCliResult, OutputFormat};
+use ::rpc::protos::dns as dnsrpc;
+use clap::ValueEnum;
+use prettytable::{Table, row};
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyKind {
+    /// Key signing key
+    Ksk,
+    /// Zone signing key
+    Zsk,
+}
+
+impl From<KeyKind> for dnsrpc::DomainKeyKind {
+    fn from(kind: KeyKind) -> Self {
+        match kind {
+            KeyKind::Ksk => dnsrpc::DomainKeyKind::KeySigning,
+            KeyKind::Zsk => dnsrpc::DomainKeyKind::ZoneSigning,
+        }
+    }
+}
+
+/// Prints the DNSSEC keys of a domain as JSON or as a table.
+pub fn print_keys(
+    keys: &[dnsrpc::DomainKeyInfo],
+    output_format: OutputFormat,
+) -> CarbideCliResult<()> {
+    if output_format == OutputFormat::Json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(keys).map_err(CarbideCliError::JsonError)?
+        );
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.set_titles(row![
+        "Id",
+        "Kind",
+        "Flags",
+        "Active",
+        "Published",
+        "Created"
+    ]);
+    for key in keys {
+        let kind = match key.kind() {
+            dnsrpc::DomainKeyKind::KeySigning => "ksk",
+            dnsrpc::DomainKeyKind::ZoneSigning => "zsk",
+        };
+        table.add_row(row![
+            key.id,
+            kind,
+            key.flags,
+            key.active,
+            key.published,
+            key.created
+                .map(|created| created.to_string())
+                .unwrap_or_default(),
+        ]);
+    }
+    table.printstd();
+
+    Ok(())
+}
@@ -15,6 +15,9 @@
  * limitations under the License.
  */
 
+mod common;
+mod generate_key;
+mod roll_key;
 mod show;
 
 // Cross-module re-exports for jump module
@@ -32,4 +35,16 @@ use crate::cfg::dispatch::Dispatch;
 pub enum Cmd {
     #[clap(about = "Display Domain information")]
     Show(show::Args),
+
+    #[clap(about = "Generate an additional active DNSSEC key for a domain")]
+    GenerateKey(generate_key::Args),
+
+    #[clap(
+        about = "Roll the DNSSEC keys of a domain",
+        long_about = "Generates a new active key and deactivates the previously active keys \
+                      of the same kind, which stay published. Keys deactivated by the \
+                      previous roll are removed, so rolling again after the maximum TTL of \
+                      the zone passed completes the rollover."
+    )]
+    RollKey(roll_key::Args),
 }
@@ -0,0 +1,35 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use carbide_uuid::domain::DomainId;
+use clap::Parser;
+
+use crate::domain::common::KeyKind;
+
+#[derive(Parser, Debug, Clone)]
+pub struct Args {
+    #[clap(help = "The domain to roll the keys of")]
+    pub domain: DomainId,
+
+    #[clap(
+        long,
+        value_enum,
+        default_value = "zsk",
+        help = "Kind of the keys to roll"
+    )]
+    pub kind: KeyKind,
+}
@@ -55,6 +55,7 @@ fn parse_show_no_args() {
             assert!(!args.all);
             assert!(args.domain.is_none());
         }
+        _ => panic!("expected Show variant"),
     }
 }
 
@@ -68,5 +69,41 @@ fn parse_show_with_all_flag() {
         Cmd::Show(args) => {
             assert!(args.all);
         }
+        _ => panic!("expected Show variant"),
+    }
+}
+
+// parse_generate_key ensures generate-key defaults to a
+// zone signing key.
+#[test]
+fn parse_generate_key() {
+    let cmd = Cmd::try_parse_from([
+        "domain",
+        "generate-key",
+        "5b2e1a70-3c1d-4a9e-9f6b-1d2c3b4a5e6f",
+    ])
+    .expect("should parse generate-key");
+
+    match cmd {
+        Cmd::GenerateKey(args) => assert_eq!(args.kind, common::KeyKind::Zsk),
+        _ => panic!("expected GenerateKey variant"),
+    }
+}
+
+// parse_roll_key_ksk ensures roll-key accepts the key kind.
+#[test]
+fn parse_roll_key_ksk() {
+    let cmd = Cmd::try_parse_from([
+        "domain",
+        "roll-key",
+        "5b2e1a70-3c1d-4a9e-9f6b-1d2c3b4a5e6f",
+        "--kind",
+        "ksk",
+    ])
+    .expect("should parse roll-key --kind ksk");
+
+    match cmd {
+        Cmd::RollKey(args) => assert_eq!(args.kind, common::KeyKind::Ksk),
+        _ => panic!("expected RollKey variant"),
     }
 }
@@ -23,6 +23,10 @@ use clap::ValueEnum;
 pub enum ExtensionServiceType {
     #[value(alias = "k8s")]
     KubernetesPod = 0, // Kubernetes pod service type
+    #[value(alias = "ctr")]
+    Container = 1, // Standalone containerd container service type
+    #[value(alias = "systemd")]
+    SystemdUnit = 2, // systemd unit service type
 }
 
 impl From<ExtensionServiceType> for i32 {
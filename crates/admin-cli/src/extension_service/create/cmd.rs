@@ -32,7 +32,7 @@ pub async fn handle_create(
 
     let credential =
         if args.username.is_some() || args.password.is_some() || args.registry_url.is_some() {
-            // This check is for KubernetesPod service credentials, must be modified if we add more service types
+            // This check is for KubernetesPod and Container service credentials, must be modified if we add more service types
             if args.username.is_none() || args.password.is_none() || args.registry_url.is_none() {
                 return Err(CarbideCliError::GenericError(
                     "All of username, password and registry URL are required to create credential"
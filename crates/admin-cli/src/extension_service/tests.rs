@@ -288,5 +288,18 @@ fn extension_service_type_value_enum() {
         ExtensionServiceType::from_str("k8s", false),
         Ok(ExtensionServiceType::KubernetesPod)
     ));
+    assert!(matches!(
+        ExtensionServiceType::from_str("container", false),
+        Ok(ExtensionServiceType::Container)
+    ));
+    assert!(matches!(
+        ExtensionServiceType::from_str("systemd-unit", false),
+        Ok(ExtensionServiceType::SystemdUnit)
+    ));
+    // "systemd" is an alias for SystemdUnit
+    assert!(matches!(
+        ExtensionServiceType::from_str("systemd", false),
+        Ok(ExtensionServiceType::SystemdUnit)
+    ));
     assert!(ExtensionServiceType::from_str("invalid", false).is_err());
 }
@@ -0,0 +1,148 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::admin_cli::{CarbideCliError, CarbideCliResult, OutputFormat};
+use ::rpc::forge as forgerpc;
+use clap::ValueEnum;
+use prettytable::{Table, row};
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventType {
+    Allocated,
+    NetworkConfigSynced,
+    Ready,
+    ReleaseStarted,
+    CleanedUp,
+}
+
+impl From<EventType> for forgerpc::InstanceLifecycleEventType {
+    fn from(event_type: EventType) -> Self {
+        match event_type {
+            EventType::Allocated => forgerpc::InstanceLifecycleEventType::InstanceEventAllocated,
+            EventType::NetworkConfigSynced => {
+                forgerpc::InstanceLifecycleEventType::InstanceEventNetworkConfigSynced
+            }
+            EventType::Ready => forgerpc::InstanceLifecycleEventType::InstanceEventReady,
+            EventType::ReleaseStarted => {
+                forgerpc::InstanceLifecycleEventType::InstanceEventReleaseStarted
+            }
+            EventType::CleanedUp => forgerpc::InstanceLifecycleEventType::InstanceEventCleanedUp,
+        }
+    }
+}
+
+fn event_type_name(event_type: i32) -> &'static str {
+    match forgerpc::InstanceLifecycleEventType::try_from(event_type) {
+        Ok(forgerpc::InstanceLifecycleEventType::InstanceEventAllocated) => "allocated",
+        Ok(forgerpc::InstanceLifecycleEventType::InstanceEventNetworkConfigSynced) => {
+            "network_config_synced"
+        }
+        Ok(forgerpc::InstanceLifecycleEventType::InstanceEventReady) => "ready",
+        Ok(forgerpc::InstanceLifecycleEventType::InstanceEventReleaseStarted) => "release_started",
+        Ok(forgerpc::InstanceLifecycleEventType::InstanceEventCleanedUp) => "cleaned_up",
+        Err(_) => "unknown",
+    }
+}
+
+/// Prints subscriptions as JSON or as a table.
+pub fn print_subscriptions(
+    subscriptions: &[forgerpc::InstanceWebhookSubscription],
+    output_format: OutputFormat,
+) -> CarbideCliResult<()> {
+    if output_format == OutputFormat::Json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(subscriptions).map_err(CarbideCliError::JsonError)?
+        );
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.set_titles(row!["ID", "Tenant", "URL", "Events", "Created"]);
+    for subscription in subscriptions {
+        let events = if subscription.event_types.is_empty() {
+            "all".to_string()
+        } else {
+            subscription
+                .event_types
+                .iter()
+                .map(|event_type| event_type_name(*event_type))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        table.add_row(row![
+            subscription.id,
+            subscription.tenant_organization_id,
+            subscription.url,
+            events,
+            subscription
+                .created
+                .map(|created| created.to_string())
+                .unwrap_or_default(),
+        ]);
+    }
+    table.printstd();
+
+    Ok(())
+}
+
+/// Prints dead letters as JSON or as a table.
+pub fn print_deliveries(
+    deliveries: &[forgerpc::InstanceWebhookDelivery],
+    output_format: OutputFormat,
+) -> CarbideCliResult<()> {
+    if output_format == OutputFormat::Json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(deliveries).map_err(CarbideCliError::JsonError)?
+        );
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.set_titles(row![
+        "ID",
+        "Tenant",
+        "Event",
+        "Instance",
+        "URL",
+        "Attempts",
+        "Last Error",
+        "Dead Lettered"
+    ]);
+    for delivery in deliveries {
+        table.add_row(row![
+            delivery.id,
+            delivery.tenant_organization_id,
+            event_type_name(delivery.event_type),
+            delivery
+                .instance_id
+                .map(|instance_id| instance_id.to_string())
+                .unwrap_or_default(),
+            delivery.url,
+            delivery.attempts,
+            delivery.last_error.clone().unwrap_or_default(),
+            delivery
+                .dead_lettered
+                .map(|dead_lettered| dead_lettered.to_string())
+                .unwrap_or_default(),
+        ]);
+    }
+    table.printstd();
+
+    Ok(())
+}
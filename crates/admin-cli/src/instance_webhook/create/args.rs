@@ -0,0 +1,47 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use clap::Parser;
+
+use crate::instance_webhook::common::EventType;
+
+#[derive(Parser, Debug, Clone)]
+pub struct Args {
+    #[clap(
+        short = 't',
+        long,
+        help = "Tenant organization whose instance events are delivered"
+    )]
+    pub tenant_org_id: String,
+
+    #[clap(short = 'u', long, help = "http or https URL the events are posted to")]
+    pub url: String,
+
+    #[clap(
+        short = 'e',
+        long = "event",
+        value_enum,
+        help = "Event to deliver. Can be repeated. All events are delivered if not set"
+    )]
+    pub events: Vec<EventType>,
+
+    #[clap(
+        long,
+        help = "Key the payloads are signed with. A random secret is generated and shown once if not set"
+    )]
+    pub secret: Option<String>,
+}
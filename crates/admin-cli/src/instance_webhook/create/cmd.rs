@@ -0,0 +1,54 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::admin_cli::{CarbideCliResult, OutputFormat};
+use ::rpc::forge::{InstanceLifecycleEventType, InstanceWebhookSubscription};
+
+use super::args::Args;
+use crate::instance_webhook::common::print_subscriptions;
+use crate::rpc::ApiClient;
+
+/// Create an instance webhook subscription.
+/// On successful creation, the new subscription and its secret are displayed.
+pub async fn create(
+    args: Args,
+    output_format: OutputFormat,
+    api_client: &ApiClient,
+) -> CarbideCliResult<()> {
+    let subscription = api_client
+        .0
+        .create_instance_webhook_subscription(InstanceWebhookSubscription {
+            id: String::new(),
+            tenant_organization_id: args.tenant_org_id,
+            url: args.url,
+            secret: args.secret,
+            event_types: args
+                .events
+                .into_iter()
+                .map(|event_type| InstanceLifecycleEventType::from(event_type) as i32)
+                .collect(),
+            created: None,
+        })
+        .await?;
+
+    if output_format != OutputFormat::Json
+        && let Some(secret) = subscription.secret.as_ref()
+    {
+        println!("Signing secret (shown only once): {secret}");
+    }
+    print_subscriptions(&[subscription], output_format)
+}
@@ -0,0 +1,55 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+mod common;
+mod create;
+mod dead_letters;
+mod delete;
+mod retry;
+mod show;
+
+#[cfg(test)]
+mod tests;
+
+use clap::Parser;
+
+use crate::cfg::dispatch::Dispatch;
+
+#[derive(Parser, Debug, Clone, Dispatch)]
+#[clap(rename_all = "kebab_case")]
+pub enum Cmd {
+    #[clap(
+        about = "Subscribe a tenant webhook to instance lifecycle events",
+        visible_alias = "c"
+    )]
+    Create(create::Args),
+
+    #[clap(about = "Show instance webhook subscriptions", visible_alias = "s")]
+    Show(show::Args),
+
+    #[clap(about = "Delete an instance webhook subscription", visible_alias = "d")]
+    Delete(delete::Args),
+
+    #[clap(
+        about = "Show deliveries which ran out of attempts",
+        visible_alias = "dl"
+    )]
+    DeadLetters(dead_letters::Args),
+
+    #[clap(about = "Deliver dead letters again", visible_alias = "r")]
+    Retry(retry::Args),
+}
@@ -0,0 +1,36 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use clap::{ArgGroup, Parser};
+
+#[derive(Parser, Debug, Clone)]
+#[clap(group(ArgGroup::new("selection").required(true).args(["ids", "all"])))]
+pub struct Args {
+    #[clap(help = "IDs of the dead letters to deliver again")]
+    pub ids: Vec<u64>,
+
+    #[clap(long, help = "Deliver all dead letters again")]
+    pub all: bool,
+
+    #[clap(
+        short = 't',
+        long,
+        requires = "all",
+        help = "Only deliver the dead letters of a tenant organization again"
+    )]
+    pub tenant_org_id: Option<String>,
+}
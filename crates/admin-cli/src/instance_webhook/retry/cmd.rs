@@ -0,0 +1,61 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::admin_cli::{CarbideCliResult, OutputFormat};
+use ::rpc::forge::{InstanceWebhookSearchFilter, RetryInstanceWebhookDeadLettersRequest};
+
+use super::args::Args;
+use crate::rpc::ApiClient;
+
+/// Queue dead letters for delivery again, with a fresh set of attempts.
+pub async fn retry(
+    args: Args,
+    _output_format: OutputFormat,
+    api_client: &ApiClient,
+) -> CarbideCliResult<()> {
+    let delivery_ids = if args.all {
+        api_client
+            .0
+            .find_instance_webhook_dead_letters(InstanceWebhookSearchFilter {
+                tenant_organization_id: args.tenant_org_id,
+            })
+            .await?
+            .deliveries
+            .into_iter()
+            .map(|delivery| delivery.id)
+            .collect()
+    } else {
+        args.ids
+    };
+
+    if delivery_ids.is_empty() {
+        println!("No dead letters to retry.");
+        return Ok(());
+    }
+
+    let response = api_client
+        .0
+        .retry_instance_webhook_dead_letters(RetryInstanceWebhookDeadLettersRequest {
+            delivery_ids,
+        })
+        .await?;
+    println!(
+        "{} dead letters queued for delivery.",
+        response.retried_count
+    );
+    Ok(())
+}
@@ -0,0 +1,177 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// The intent of the tests.rs file is to test the integrity of the
+// command, including things like basic structure parsing, enum
+// translations, and any external input validators that are
+// configured. Specific "categories" are:
+//
+// Command Structure - Baseline debug_assert() of the entire command.
+// Argument Parsing  - Ensure required/optional arg combinations parse correctly.
+// ValueEnum Parsing - Ensure event types parse from their kebab-case names.
+
+use clap::{CommandFactory, Parser};
+
+use super::*;
+
+// verify_cmd_structure runs a baseline clap debug_assert()
+// to do basic command configuration checking and validation,
+// ensuring things like unique argument definitions, group
+// configurations, argument references, etc. Things that would
+// otherwise be missed until runtime.
+#[test]
+fn verify_cmd_structure() {
+    Cmd::command().debug_assert();
+}
+
+/////////////////////////////////////////////////////////////////////////////
+// Argument Parsing
+//
+// This section contains tests specific to argument parsing,
+// including testing required arguments, as well as optional
+// flag-specific checking.
+
+// parse_create ensures create parses a subscription for
+// a subset of the events.
+#[test]
+fn parse_create() {
+    let cmd = Cmd::try_parse_from([
+        "instance-webhook",
+        "create",
+        "--tenant-org-id",
+        "Tenant1",
+        "--url",
+        "https://hooks.example.com/carbide",
+        "--event",
+        "ready",
+        "--event",
+        "network-config-synced",
+    ])
+    .expect("should parse create");
+
+    match cmd {
+        Cmd::Create(args) => {
+            assert_eq!(args.tenant_org_id, "Tenant1");
+            assert_eq!(args.url, "https://hooks.example.com/carbide");
+            assert_eq!(
+                args.events,
+                vec![
+                    common::EventType::Ready,
+                    common::EventType::NetworkConfigSynced
+                ]
+            );
+            assert!(args.secret.is_none());
+        }
+        _ => panic!("expected Create variant"),
+    }
+}
+
+// parse_create_requires_tenant_and_url ensures a subscription
+// needs both a tenant and a URL.
+#[test]
+fn parse_create_requires_tenant_and_url() {
+    assert!(
+        Cmd::try_parse_from([
+            "instance-webhook",
+            "create",
+            "--url",
+            "https://hooks.example.com"
+        ])
+        .is_err()
+    );
+    assert!(
+        Cmd::try_parse_from(["instance-webhook", "create", "--tenant-org-id", "Tenant1"]).is_err()
+    );
+}
+
+// parse_retry ensures retry takes either IDs or --all, and
+// only allows a tenant filter together with --all.
+#[test]
+fn parse_retry() {
+    let cmd = Cmd::try_parse_from(["instance-webhook", "retry", "3", "7"])
+        .expect("should parse retry with IDs");
+    match cmd {
+        Cmd::Retry(args) => {
+            assert_eq!(args.ids, vec![3, 7]);
+            assert!(!args.all);
+        }
+        _ => panic!("expected Retry variant"),
+    }
+
+    let cmd = Cmd::try_parse_from(["instance-webhook", "retry", "--all", "-t", "Tenant1"])
+        .expect("should parse retry --all");
+    assert!(matches!(cmd, Cmd::Retry(args) if args.all && args.tenant_org_id.is_some()));
+
+    assert!(Cmd::try_parse_from(["instance-webhook", "retry"]).is_err());
+    assert!(Cmd::try_parse_from(["instance-webhook", "retry", "3", "--all"]).is_err());
+    assert!(Cmd::try_parse_from(["instance-webhook", "retry", "3", "-t", "Tenant1"]).is_err());
+}
+
+// parse_show_delete_and_dead_letters ensures the remaining
+// subcommands parse.
+#[test]
+fn parse_show_delete_and_dead_letters() {
+    let cmd = Cmd::try_parse_from(["instance-webhook", "show"]).expect("should parse show");
+    assert!(matches!(cmd, Cmd::Show(args) if args.tenant_org_id.is_none()));
+
+    let cmd = Cmd::try_parse_from(["instance-webhook", "dead-letters", "-t", "Tenant1"])
+        .expect("should parse dead-letters");
+    assert!(
+        matches!(cmd, Cmd::DeadLetters(args) if args.tenant_org_id.as_deref() == Some("Tenant1"))
+    );
+
+    let cmd = Cmd::try_parse_from([
+        "instance-webhook",
+        "delete",
+        "7d1f3c9e-52c1-4c38-a6c5-0d2b1c1f8a11",
+    ])
+    .expect("should parse delete");
+    assert!(matches!(cmd, Cmd::Delete(_)));
+    assert!(Cmd::try_parse_from(["instance-webhook", "delete"]).is_err());
+}
+
+/////////////////////////////////////////////////////////////////////////////
+// ValueEnum Parsing
+//
+// This section contains tests for the event type names.
+
+// event_type_translation ensures every event type maps to
+// its RPC counterpart.
+#[test]
+fn event_type_translation() {
+    use ::rpc::forge::InstanceLifecycleEventType;
+
+    assert_eq!(
+        InstanceLifecycleEventType::from(common::EventType::Allocated),
+        InstanceLifecycleEventType::InstanceEventAllocated
+    );
+    assert_eq!(
+        InstanceLifecycleEventType::from(common::EventType::CleanedUp),
+        InstanceLifecycleEventType::InstanceEventCleanedUp
+    );
+    let cmd = Cmd::try_parse_from([
+        "instance-webhook",
+        "create",
+        "-t",
+        "Tenant1",
+        "-u",
+        "http://127.0.0.1/hook",
+        "-e",
+        "deleted",
+    ]);
+    assert!(cmd.is_err(), "unknown event types are rejected");
+}
@@ -0,0 +1,104 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::admin_cli::{CarbideCliResult, OutputFormat};
+use ::rpc::forge as forgerpc;
+use prettytable::{Row, Table, row};
+
+use super::args::Args;
+use crate::rpc::ApiClient;
+
+pub async fn handler_log(
+    args: Args,
+    api_client: &ApiClient,
+    format: OutputFormat,
+) -> CarbideCliResult<()> {
+    let req = forgerpc::StateHandlerOutcomeHistoryRequest {
+        machine_ids: vec![args.machine],
+    };
+    let mut histories = api_client
+        .0
+        .find_state_handler_outcome_history(req)
+        .await?
+        .histories;
+
+    let mut records = histories
+        .remove(&args.machine.to_string())
+        .map(|history| history.records)
+        .unwrap_or_default();
+    if let Some(last) = args.last {
+        records.drain(..records.len().saturating_sub(last));
+    }
+
+    if format == OutputFormat::Json {
+        println!("{}", serde_json::to_string_pretty(&records)?);
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.set_titles(Row::from(vec![
+        "Time",
+        "Last Time",
+        "Count",
+        "Iteration",
+        "Latency",
+        "Outcome",
+        "Message",
+        "Source",
+        "State",
+    ]));
+    for record in records {
+        let (outcome, message, source) = match record.outcome.as_ref() {
+            Some(reason) => (
+                format!("{:?}", reason.outcome()),
+                reason.outcome_msg.clone().unwrap_or_default(),
+                reason
+                    .source_ref
+                    .as_ref()
+                    .map(|src| format!("{}:{}", src.file, src.line))
+                    .unwrap_or("---".to_string()),
+            ),
+            None => ("---".to_string(), String::new(), "---".to_string()),
+        };
+        table.add_row(row![
+            record
+                .time
+                .map(|time| time.to_string())
+                .unwrap_or("---".to_string()),
+            record
+                .last_time
+                .map(|time| time.to_string())
+                .unwrap_or("---".to_string()),
+            record.repeat_count,
+            record
+                .iteration_id
+                .map(|id| id.to_string())
+                .unwrap_or("---".to_string()),
+            record
+                .handler_latency
+                .map(|latency| latency.to_string())
+                .unwrap_or("---".to_string()),
+            outcome,
+            message,
+            source,
+            record.state,
+        ]);
+    }
+    table.printstd();
+
+    Ok(())
+}
@@ -19,6 +19,7 @@ pub mod auto_update;
 pub mod common;
 pub mod dpu_ssh_credentials;
 pub mod force_delete;
+pub mod handler_log;
 pub mod hardware_info;
 pub mod health_override;
 pub mod metadata;
@@ -80,4 +81,6 @@ pub enum Cmd {
     Positions(positions::Args),
     #[clap(subcommand, about = "Update/show NVLink info for an MNNVL machine")]
     NvlinkInfo(nvlink_info::Args),
+    #[clap(about = "Show the recent state handler outcomes for a machine")]
+    HandlerLog(handler_log::Args),
 }
@@ -289,6 +289,37 @@ fn parse_positions() {
     }
 }
 
+// parse_handler_log ensures handler-log parses with
+// a machine and an optional limit.
+#[test]
+fn parse_handler_log() {
+    let cmd = Cmd::try_parse_from([
+        "machine",
+        "handler-log",
+        "--machine",
+        TEST_MACHINE_ID,
+        "--last",
+        "10",
+    ])
+    .expect("should parse handler-log");
+
+    match cmd {
+        Cmd::HandlerLog(args) => {
+            assert_eq!(args.machine.to_string(), TEST_MACHINE_ID);
+            assert_eq!(args.last, Some(10));
+        }
+        _ => panic!("expected HandlerLog variant"),
+    }
+}
+
+// parse_handler_log_missing_machine ensures handler-log
+// requires a machine.
+#[test]
+fn parse_handler_log_missing_machine() {
+    let result = Cmd::try_parse_from(["machine", "handler-log"]);
+    assert!(result.is_err(), "should fail without --machine");
+}
+
 /////////////////////////////////////////////////////////////////////////////
 // ValueEnum Parsing
 //
@@ -0,0 +1,33 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::admin_cli::CarbideCliResult;
+use ::rpc::forge::MachineUpdateRolloutAction;
+
+use super::args::Args;
+use crate::machine_update_rollout::common::apply_action;
+use crate::rpc::ApiClient;
+
+/// Abort the latest machine update rollout.
+pub async fn abort(args: Args, api_client: &ApiClient) -> CarbideCliResult<()> {
+    apply_action(
+        MachineUpdateRolloutAction::RolloutAbort,
+        args.reason,
+        api_client,
+    )
+    .await
+}
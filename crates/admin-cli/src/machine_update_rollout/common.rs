@@ -0,0 +1,106 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::admin_cli::{CarbideCliError, CarbideCliResult, OutputFormat};
+use ::rpc::forge as forgerpc;
+use prettytable::{Table, row};
+
+use crate::rpc::ApiClient;
+
+/// Applies an action to the latest rollout and prints the result.
+pub async fn apply_action(
+    action: forgerpc::MachineUpdateRolloutAction,
+    reason: Option<String>,
+    api_client: &ApiClient,
+) -> CarbideCliResult<()> {
+    let rollout = api_client
+        .0
+        .machine_update_rollout_action(forgerpc::MachineUpdateRolloutActionRequest {
+            action: action as i32,
+            reason,
+        })
+        .await?;
+    println!(
+        "Machine update rollout {} is now {}.",
+        rollout.id,
+        state_name(rollout.state)
+    );
+    Ok(())
+}
+
+/// Prints a rollout as JSON or as a summary followed by a table of its hosts.
+pub fn print_rollout(
+    rollout: &forgerpc::MachineUpdateRollout,
+    output_format: OutputFormat,
+) -> CarbideCliResult<()> {
+    if output_format == OutputFormat::Json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(rollout).map_err(CarbideCliError::JsonError)?
+        );
+        return Ok(());
+    }
+
+    let mut summary = Table::new();
+    summary.add_row(row!["Id", rollout.id]);
+    summary.add_row(row!["State", state_name(rollout.state)]);
+    summary.add_row(row![
+        "Reason",
+        rollout.state_reason.clone().unwrap_or_default()
+    ]);
+    summary.add_row(row![
+        "Wave",
+        format!("{} of {}", rollout.wave + 1, rollout.wave_count)
+    ]);
+    summary.add_row(row![
+        "Soak ends",
+        rollout
+            .soak_ends_at
+            .map(|soak_ends_at| soak_ends_at.to_string())
+            .unwrap_or_default()
+    ]);
+    summary.add_row(row![
+        "Created",
+        rollout
+            .created
+            .map(|created| created.to_string())
+            .unwrap_or_default()
+    ]);
+    summary.printstd();
+
+    let mut hosts = Table::new();
+    hosts.set_titles(row!["Host", "Wave", "Unhealthy"]);
+    for host in &rollout.hosts {
+        let unhealthy = host
+            .machine_id
+            .is_some_and(|id| rollout.unhealthy_machine_ids.contains(&id));
+        hosts.add_row(row![
+            host.machine_id.map(|id| id.to_string()).unwrap_or_default(),
+            host.wave + 1,
+            unhealthy,
+        ]);
+    }
+    hosts.printstd();
+
+    Ok(())
+}
+
+fn state_name(state: i32) -> String {
+    forgerpc::MachineUpdateRolloutState::try_from(state)
+        .map(|state| format!("{state:?}"))
+        .unwrap_or_else(|_| state.to_string())
+}
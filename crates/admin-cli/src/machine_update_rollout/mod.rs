@@ -0,0 +1,48 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+mod abort;
+mod common;
+mod pause;
+mod resume;
+mod show;
+
+#[cfg(test)]
+mod tests;
+
+use clap::Parser;
+
+use crate::cfg::dispatch::Dispatch;
+
+#[derive(Parser, Debug, Clone, Dispatch)]
+#[clap(rename_all = "kebab_case")]
+pub enum Cmd {
+    #[clap(
+        about = "Show the waves and hosts of a machine update rollout",
+        visible_alias = "s"
+    )]
+    Show(show::Args),
+
+    #[clap(about = "Stop starting updates until the rollout is resumed")]
+    Pause(pause::Args),
+
+    #[clap(about = "Resume a paused or halted rollout, or start over after an abort")]
+    Resume(resume::Args),
+
+    #[clap(about = "Abort the rollout")]
+    Abort(abort::Args),
+}
@@ -0,0 +1,38 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::admin_cli::{CarbideCliResult, OutputFormat};
+use ::rpc::forge::MachineUpdateRolloutRequest;
+
+use super::args::Args;
+use crate::machine_update_rollout::common::print_rollout;
+use crate::rpc::ApiClient;
+
+/// Show a machine update rollout, including the hosts each
+/// of its waves updated.
+pub async fn show(
+    args: Args,
+    output_format: OutputFormat,
+    api_client: &ApiClient,
+) -> CarbideCliResult<()> {
+    let rollout = api_client
+        .0
+        .get_machine_update_rollout(MachineUpdateRolloutRequest { id: args.id })
+        .await?;
+
+    print_rollout(&rollout, output_format)
+}
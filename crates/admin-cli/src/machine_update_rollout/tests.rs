@@ -0,0 +1,111 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// The intent of the tests.rs file is to test the integrity of the
+// command, including things like basic structure parsing, enum
+// translations, and any external input validators that are
+// configured. Specific "categories" are:
+//
+// Command Structure - Baseline debug_assert() of the entire command.
+// Argument Parsing  - Ensure required/optional arg combinations parse correctly.
+
+use clap::{CommandFactory, Parser};
+
+use super::*;
+
+// verify_cmd_structure runs a baseline clap debug_assert()
+// to do basic command configuration checking and validation,
+// ensuring things like unique argument definitions, group
+// configurations, argument references, etc. Things that would
+// otherwise be missed until runtime.
+#[test]
+fn verify_cmd_structure() {
+    Cmd::command().debug_assert();
+}
+
+/////////////////////////////////////////////////////////////////////////////
+// Argument Parsing
+//
+// This section contains tests specific to argument parsing,
+// including testing required arguments, as well as optional
+// flag-specific checking.
+
+// parse_show_no_args ensures show parses with no
+// arguments (latest rollout).
+#[test]
+fn parse_show_no_args() {
+    let cmd = Cmd::try_parse_from(["machine-update-rollout", "show"]).expect("should parse show");
+
+    match cmd {
+        Cmd::Show(args) => assert!(args.id.is_none()),
+        _ => panic!("expected Show variant"),
+    }
+}
+
+// parse_show_with_id ensures show parses a rollout ID.
+#[test]
+fn parse_show_with_id() {
+    let cmd = Cmd::try_parse_from(["machine-update-rollout", "show", "--id", "7"])
+        .expect("should parse show with id");
+
+    match cmd {
+        Cmd::Show(args) => assert_eq!(args.id, Some(7)),
+        _ => panic!("expected Show variant"),
+    }
+}
+
+// parse_pause_with_reason ensures pause parses an
+// optional reason.
+#[test]
+fn parse_pause_with_reason() {
+    let cmd = Cmd::try_parse_from([
+        "machine-update-rollout",
+        "pause",
+        "--reason",
+        "investigating canary",
+    ])
+    .expect("should parse pause");
+
+    match cmd {
+        Cmd::Pause(args) => assert_eq!(args.reason, Some("investigating canary".to_string())),
+        _ => panic!("expected Pause variant"),
+    }
+}
+
+// parse_resume_and_abort ensures resume and abort parse
+// without arguments.
+#[test]
+fn parse_resume_and_abort() {
+    let cmd =
+        Cmd::try_parse_from(["machine-update-rollout", "resume"]).expect("should parse resume");
+    assert!(matches!(cmd, Cmd::Resume(args) if args.reason.is_none()));
+
+    let cmd = Cmd::try_parse_from(["machine-update-rollout", "abort", "-r", "bad firmware"])
+        .expect("should parse abort");
+    match cmd {
+        Cmd::Abort(args) => assert_eq!(args.reason, Some("bad firmware".to_string())),
+        _ => panic!("expected Abort variant"),
+    }
+}
+
+// parse_show_invalid_id_fails ensures show fails with
+// a non-numeric ID.
+#[test]
+fn parse_show_invalid_id_fails() {
+    let result = Cmd::try_parse_from(["machine-update-rollout", "show", "--id", "latest"]);
+    assert!(result.is_err(), "should fail with a non-numeric --id");
+}
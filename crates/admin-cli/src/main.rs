@@ -40,6 +40,7 @@ use crate::rpc::ApiClient;
 mod async_write;
 mod bmc_machine;
 mod boot_override;
+mod capacity_reservation;
 mod cfg;
 mod credential;
 mod debug_bundle;
@@ -59,12 +60,15 @@ mod host;
 mod ib_partition;
 mod instance;
 mod instance_type;
+mod instance_webhook;
 mod inventory;
 mod ip;
 mod jump;
 mod machine;
 mod machine_interfaces;
+mod machine_update_rollout;
 mod machine_validation;
+mod maintenance_window;
 mod managed_host;
 mod measurement;
 mod metadata;
@@ -89,6 +93,7 @@ mod set;
 mod site_explorer;
 mod sku;
 mod ssh;
+mod ssh_console;
 mod switch;
 mod tenant;
 mod tenant_keyset;
@@ -200,6 +205,7 @@ async fn main() -> color_eyre::Result<()> {
     match command {
         CliCommand::BmcMachine(cmd) => cmd.dispatch(ctx).await?,
         CliCommand::BootOverride(cmd) => cmd.dispatch(ctx).await?,
+        CliCommand::CapacityReservation(cmd) => cmd.dispatch(ctx).await?,
         CliCommand::Credential(cmd) => cmd.dispatch(ctx).await?,
         CliCommand::DevEnv(cmd) => cmd.dispatch(ctx).await?,
         CliCommand::Domain(cmd) => cmd.dispatch(ctx).await?,
@@ -222,6 +228,9 @@ async fn main() -> color_eyre::Result<()> {
         CliCommand::LogicalPartition(cmd) => cmd.dispatch(ctx).await?,
         CliCommand::Machine(cmd) => cmd.dispatch(ctx).await?,
         CliCommand::MachineInterfaces(cmd) => cmd.dispatch(ctx).await?,
+        CliCommand::MachineUpdateRollout(cmd) => cmd.dispatch(ctx).await?,
+        CliCommand::MaintenanceWindow(cmd) => cmd.dispatch(ctx).await?,
+        CliCommand::InstanceWebhook(cmd) => cmd.dispatch(ctx).await?,
         CliCommand::MachineValidation(cmd) => cmd.dispatch(ctx).await?,
         CliCommand::ManagedHost(cmd) => cmd.dispatch(ctx).await?,
         CliCommand::Measurement(cmd) => cmd.dispatch(ctx).await?,
@@ -239,6 +248,7 @@ async fn main() -> color_eyre::Result<()> {
         CliCommand::ScoutStream(cmd) => cmd.dispatch(ctx).await?,
         CliCommand::Set(cmd) => cmd.dispatch(ctx).await?,
         CliCommand::Ssh(cmd) => cmd.dispatch(ctx).await?,
+        CliCommand::SshConsole(cmd) => cmd.dispatch(ctx).await?,
         CliCommand::SiteExplorer(cmd) => cmd.dispatch(ctx).await?,
         CliCommand::Sku(cmd) => cmd.dispatch(ctx).await?,
         CliCommand::Switch(cmd) => cmd.dispatch(ctx).await?,
@@ -0,0 +1,89 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::admin_cli::{CarbideCliError, CarbideCliResult, OutputFormat};
+use ::rpc::forge as forgerpc;
+use prettytable::{Table, row};
+
+/// Prints windows as JSON or as a table.
+pub fn print_windows(
+    windows: &[forgerpc::MaintenanceWindow],
+    output_format: OutputFormat,
+) -> CarbideCliResult<()> {
+    if output_format == OutputFormat::Json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(windows).map_err(CarbideCliError::JsonError)?
+        );
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.set_titles(row![
+        "Name",
+        "Kind",
+        "Scope",
+        "Schedule",
+        "Duration",
+        "Timezone",
+        "Starts",
+        "Ends",
+        "Active",
+        "Description"
+    ]);
+    for window in windows {
+        let kind = match window.kind() {
+            forgerpc::MaintenanceWindowKind::MaintenanceWindowAllow => "maintenance",
+            forgerpc::MaintenanceWindowKind::MaintenanceWindowBlackout => "blackout",
+        };
+        let scope = match window.scope() {
+            forgerpc::MaintenanceWindowScope::MaintenanceScopeSite => "site".to_string(),
+            forgerpc::MaintenanceWindowScope::MaintenanceScopeRack => {
+                format!("rack {}", window.scope_id.clone().unwrap_or_default())
+            }
+            forgerpc::MaintenanceWindowScope::MaintenanceScopeTenant => {
+                format!("tenant {}", window.scope_id.clone().unwrap_or_default())
+            }
+        };
+        let duration = if window.schedule.is_some() {
+            format!("{}m", window.duration_minutes)
+        } else {
+            String::new()
+        };
+        table.add_row(row![
+            window.name,
+            kind,
+            scope,
+            window.schedule.clone().unwrap_or_default(),
+            duration,
+            window.timezone.clone().unwrap_or_default(),
+            window
+                .starts_at
+                .map(|starts_at| starts_at.to_string())
+                .unwrap_or_default(),
+            window
+                .ends_at
+                .map(|ends_at| ends_at.to_string())
+                .unwrap_or_default(),
+            window.active,
+            window.description,
+        ]);
+    }
+    table.printstd();
+
+    Ok(())
+}
@@ -0,0 +1,98 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use chrono::{DateTime, Utc};
+use clap::{ArgGroup, Parser, ValueEnum};
+
+#[derive(Parser, Debug, Clone)]
+#[clap(group(ArgGroup::new("scope").args(["rack_id", "tenant_org_id"])))]
+pub struct Args {
+    #[clap(help = "Unique name of the window")]
+    pub name: String,
+
+    #[clap(
+        short = 'k',
+        long,
+        value_enum,
+        default_value_t = WindowKind::Maintenance,
+        help = "Whether disruptive work may only start within the window, or never while it is open"
+    )]
+    pub kind: WindowKind,
+
+    #[clap(long, help = "Restrict the window to the hosts of a rack")]
+    pub rack_id: Option<String>,
+
+    #[clap(
+        short = 't',
+        long,
+        help = "Restrict the window to the hosts with instances of a tenant organization"
+    )]
+    pub tenant_org_id: Option<String>,
+
+    #[clap(
+        short = 's',
+        long,
+        requires = "duration",
+        help = "Cron expression (minute hour day-of-month month day-of-week) of when the window opens, e.g. \"0 2 * * 6\""
+    )]
+    pub schedule: Option<String>,
+
+    #[clap(
+        short = 'd',
+        long,
+        help = "How many minutes the window stays open each time the schedule fires"
+    )]
+    pub duration: Option<u32>,
+
+    #[clap(
+        long,
+        help = "IANA timezone the schedule is evaluated in. Defaults to UTC"
+    )]
+    pub timezone: Option<String>,
+
+    #[clap(
+        long,
+        required_unless_present = "schedule",
+        help = "RFC 3339 timestamp before which the window is never open"
+    )]
+    pub starts_at: Option<DateTime<Utc>>,
+
+    #[clap(
+        long,
+        required_unless_present = "schedule",
+        help = "RFC 3339 timestamp from which on the window is never open"
+    )]
+    pub ends_at: Option<DateTime<Utc>>,
+
+    #[clap(long, default_value = "", help = "Description of the window")]
+    pub description: String,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowKind {
+    Maintenance,
+    Blackout,
+}
+
+impl From<WindowKind> for rpc::forge::MaintenanceWindowKind {
+    fn from(kind: WindowKind) -> Self {
+        match kind {
+            WindowKind::Maintenance => rpc::forge::MaintenanceWindowKind::MaintenanceWindowAllow,
+            WindowKind::Blackout => rpc::forge::MaintenanceWindowKind::MaintenanceWindowBlackout,
+        }
+    }
+}
@@ -0,0 +1,57 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::admin_cli::{CarbideCliResult, OutputFormat};
+use ::rpc::forge::{MaintenanceWindow, MaintenanceWindowKind, MaintenanceWindowScope};
+
+use super::args::Args;
+use crate::maintenance_window::common::print_windows;
+use crate::rpc::ApiClient;
+
+/// Create a maintenance window.
+/// On successful creation, the new window is displayed.
+pub async fn create(
+    args: Args,
+    output_format: OutputFormat,
+    api_client: &ApiClient,
+) -> CarbideCliResult<()> {
+    let (scope, scope_id) = match (args.rack_id, args.tenant_org_id) {
+        (Some(rack_id), _) => (MaintenanceWindowScope::MaintenanceScopeRack, Some(rack_id)),
+        (None, Some(tenant)) => (MaintenanceWindowScope::MaintenanceScopeTenant, Some(tenant)),
+        (None, None) => (MaintenanceWindowScope::MaintenanceScopeSite, None),
+    };
+
+    let window = api_client
+        .0
+        .create_maintenance_window(MaintenanceWindow {
+            name: args.name,
+            kind: MaintenanceWindowKind::from(args.kind) as i32,
+            scope: scope as i32,
+            scope_id,
+            schedule: args.schedule,
+            duration_minutes: args.duration.unwrap_or_default(),
+            timezone: args.timezone,
+            starts_at: args.starts_at.map(Into::into),
+            ends_at: args.ends_at.map(Into::into),
+            description: args.description,
+            active: false,
+            created: None,
+        })
+        .await?;
+
+    print_windows(&[window], output_format)
+}
@@ -0,0 +1,43 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::admin_cli::{CarbideCliError, CarbideCliResult, OutputFormat};
+
+use super::args::Args;
+use crate::maintenance_window::common::print_windows;
+use crate::rpc::ApiClient;
+
+/// Show the maintenance windows of the site, and whether
+/// they are currently open.
+pub async fn show(
+    args: Args,
+    output_format: OutputFormat,
+    api_client: &ApiClient,
+) -> CarbideCliResult<()> {
+    let mut windows = api_client.0.find_maintenance_windows(()).await?.windows;
+
+    if let Some(name) = args.name {
+        windows.retain(|window| window.name == name);
+        if windows.is_empty() {
+            return Err(CarbideCliError::GenericError(format!(
+                "Maintenance window {name} not found"
+            )));
+        }
+    }
+
+    print_windows(&windows, output_format)
+}
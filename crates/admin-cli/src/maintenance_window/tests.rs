@@ -0,0 +1,162 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// The intent of the tests.rs file is to test the integrity of the
+// command, including things like basic structure parsing, enum
+// translations, and any external input validators that are
+// configured. Specific "categories" are:
+//
+// Command Structure - Baseline debug_assert() of the entire command.
+// Argument Parsing  - Ensure required/optional arg combinations parse correctly.
+
+use clap::{CommandFactory, Parser};
+
+use super::*;
+
+// verify_cmd_structure runs a baseline clap debug_assert()
+// to do basic command configuration checking and validation,
+// ensuring things like unique argument definitions, group
+// configurations, argument references, etc. Things that would
+// otherwise be missed until runtime.
+#[test]
+fn verify_cmd_structure() {
+    Cmd::command().debug_assert();
+}
+
+/////////////////////////////////////////////////////////////////////////////
+// Argument Parsing
+//
+// This section contains tests specific to argument parsing,
+// including testing required arguments, as well as optional
+// flag-specific checking.
+
+// parse_create_recurring ensures create parses a recurring
+// rack window.
+#[test]
+fn parse_create_recurring() {
+    let cmd = Cmd::try_parse_from([
+        "maintenance-window",
+        "create",
+        "weekend",
+        "--rack-id",
+        "rack-a",
+        "--schedule",
+        "0 2 * * 6",
+        "--duration",
+        "240",
+        "--timezone",
+        "Europe/Berlin",
+    ])
+    .expect("should parse create");
+
+    match cmd {
+        Cmd::Create(args) => {
+            assert_eq!(args.name, "weekend");
+            assert_eq!(args.kind, create::args::WindowKind::Maintenance);
+            assert_eq!(args.rack_id, Some("rack-a".to_string()));
+            assert_eq!(args.schedule, Some("0 2 * * 6".to_string()));
+            assert_eq!(args.duration, Some(240));
+        }
+        _ => panic!("expected Create variant"),
+    }
+}
+
+// parse_create_blackout ensures create parses a one-off
+// blackout with start and end.
+#[test]
+fn parse_create_blackout() {
+    let cmd = Cmd::try_parse_from([
+        "maintenance-window",
+        "create",
+        "freeze",
+        "--kind",
+        "blackout",
+        "--starts-at",
+        "2026-12-20T00:00:00Z",
+        "--ends-at",
+        "2027-01-04T00:00:00Z",
+    ])
+    .expect("should parse create blackout");
+
+    match cmd {
+        Cmd::Create(args) => {
+            assert_eq!(args.kind, create::args::WindowKind::Blackout);
+            assert!(args.starts_at.is_some() && args.ends_at.is_some());
+            assert!(args.schedule.is_none());
+        }
+        _ => panic!("expected Create variant"),
+    }
+}
+
+// parse_create_without_schedule_or_bounds_fails ensures a
+// window needs either a schedule or start and end.
+#[test]
+fn parse_create_without_schedule_or_bounds_fails() {
+    let result = Cmd::try_parse_from(["maintenance-window", "create", "broken"]);
+    assert!(result.is_err(), "should fail without schedule or bounds");
+}
+
+// parse_create_schedule_without_duration_fails ensures a
+// schedule requires a duration.
+#[test]
+fn parse_create_schedule_without_duration_fails() {
+    let result = Cmd::try_parse_from([
+        "maintenance-window",
+        "create",
+        "nightly",
+        "--schedule",
+        "0 2 * * *",
+    ]);
+    assert!(result.is_err(), "should fail without --duration");
+}
+
+// parse_create_rack_and_tenant_fails ensures a window has
+// at most one scope.
+#[test]
+fn parse_create_rack_and_tenant_fails() {
+    let result = Cmd::try_parse_from([
+        "maintenance-window",
+        "create",
+        "nightly",
+        "--schedule",
+        "0 2 * * *",
+        "--duration",
+        "60",
+        "--rack-id",
+        "rack-a",
+        "--tenant-org-id",
+        "tenant",
+    ]);
+    assert!(result.is_err(), "should fail with rack and tenant scope");
+}
+
+// parse_show_and_delete ensures show takes an optional
+// name and delete requires one.
+#[test]
+fn parse_show_and_delete() {
+    let cmd = Cmd::try_parse_from(["maintenance-window", "show"]).expect("should parse show");
+    assert!(matches!(cmd, Cmd::Show(args) if args.name.is_none()));
+
+    let cmd = Cmd::try_parse_from(["maintenance-window", "delete", "weekend"])
+        .expect("should parse delete");
+    match cmd {
+        Cmd::Delete(args) => assert_eq!(args.name, "weekend"),
+        _ => panic!("expected Delete variant"),
+    }
+
+    assert!(Cmd::try_parse_from(["maintenance-window", "delete"]).is_err());
+}
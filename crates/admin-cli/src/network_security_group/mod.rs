@@ -22,6 +22,7 @@ mod delete;
 mod detach;
 mod show;
 mod show_attachments;
+mod simulate;
 mod update;
 
 #[cfg(test)]
@@ -66,4 +67,10 @@ pub enum Cmd {
         visible_alias = "r"
     )]
     Detach(detach::Args),
+
+    #[clap(
+        about = "Evaluate flows against network security group rules and detect rule conflicts",
+        visible_alias = "m"
+    )]
+    Simulate(simulate::Args),
 }
@@ -0,0 +1,96 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::net::{IpAddr, SocketAddr};
+
+use ::rpc::forge::{self as forgerpc};
+use clap::{ArgGroup, Parser};
+
+#[derive(Parser, Debug, Clone)]
+#[clap(group(ArgGroup::new("rule_source").required(true).args(&["id", "rules"])))]
+pub struct Args {
+    #[clap(
+        short = 'i',
+        long,
+        help = "ID of an existing network security group to simulate"
+    )]
+    pub id: Option<String>,
+
+    #[clap(
+        short = 'r',
+        long,
+        help = "JSON array containing a proposed set of network security group rules to simulate"
+    )]
+    pub rules: Option<String>,
+
+    #[clap(
+        short = 'f',
+        long = "flow",
+        value_parser = parse_flow,
+        help = "Flow to evaluate against the rules, as \"<ingress|egress> <tcp|udp|icmp|icmp6> <src_ip>[:port] <dst_ip>[:port]\". Can be repeated. Without flows, only the rule analysis is shown."
+    )]
+    pub flows: Vec<forgerpc::NetworkSecurityGroupFlow>,
+}
+
+/// Parses a flow like `ingress tcp 10.0.0.1:40000 10.0.0.2:22`.
+/// IPv6 endpoints with a port use the bracket notation, e.g. `[2001:db8::1]:22`.
+pub fn parse_flow(arg: &str) -> eyre::Result<forgerpc::NetworkSecurityGroupFlow> {
+    let parts: Vec<&str> = arg.split_whitespace().collect();
+    let [direction, protocol, src, dst] = parts[..] else {
+        return Err(eyre::eyre!(
+            "must be <direction> <protocol> <src_ip>[:port] <dst_ip>[:port]"
+        ));
+    };
+
+    let direction = match direction.to_lowercase().as_str() {
+        "ingress" => forgerpc::NetworkSecurityGroupRuleDirection::NsgRuleDirectionIngress,
+        "egress" => forgerpc::NetworkSecurityGroupRuleDirection::NsgRuleDirectionEgress,
+        _ => return Err(eyre::eyre!("direction must be ingress or egress")),
+    };
+
+    let protocol = match protocol.to_lowercase().as_str() {
+        "tcp" => forgerpc::NetworkSecurityGroupRuleProtocol::NsgRuleProtoTcp,
+        "udp" => forgerpc::NetworkSecurityGroupRuleProtocol::NsgRuleProtoUdp,
+        "icmp" => forgerpc::NetworkSecurityGroupRuleProtocol::NsgRuleProtoIcmp,
+        "icmp6" => forgerpc::NetworkSecurityGroupRuleProtocol::NsgRuleProtoIcmp6,
+        _ => return Err(eyre::eyre!("protocol must be tcp, udp, icmp or icmp6")),
+    };
+
+    let (src_ip, src_port) = parse_endpoint(src)?;
+    let (dst_ip, dst_port) = parse_endpoint(dst)?;
+
+    Ok(forgerpc::NetworkSecurityGroupFlow {
+        direction: direction.into(),
+        protocol: protocol.into(),
+        src_ip,
+        dst_ip,
+        src_port,
+        dst_port,
+    })
+}
+
+fn parse_endpoint(endpoint: &str) -> eyre::Result<(String, Option<u32>)> {
+    if let Ok(addr) = endpoint.parse::<SocketAddr>() {
+        return Ok((addr.ip().to_string(), Some(addr.port().into())));
+    }
+
+    let ip = endpoint
+        .parse::<IpAddr>()
+        .map_err(|_| eyre::eyre!("{endpoint} is not a valid IP address or IP:port"))?;
+
+    Ok((ip.to_string(), None))
+}
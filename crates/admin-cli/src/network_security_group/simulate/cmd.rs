@@ -0,0 +1,126 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::admin_cli::{CarbideCliError, CarbideCliResult, OutputFormat};
+use ::rpc::forge::simulate_network_security_group_request::RuleSource;
+use ::rpc::forge::{self as forgerpc};
+use prettytable::{Table, row};
+
+use super::args::Args;
+use crate::rpc::ApiClient;
+
+/// Evaluate flows against the rules of an existing or
+/// proposed network security group and show any
+/// shadowed, redundant or contradictory rules.
+pub async fn simulate(
+    args: Args,
+    output_format: OutputFormat,
+    api_client: &ApiClient,
+) -> CarbideCliResult<()> {
+    let is_json = output_format == OutputFormat::Json;
+
+    let rule_source = match (args.id, args.rules) {
+        (Some(id), _) => RuleSource::NetworkSecurityGroupId(id),
+        (None, Some(r)) => {
+            RuleSource::NetworkSecurityGroupAttributes(forgerpc::NetworkSecurityGroupAttributes {
+                // Doesn't influence the verdict of new flows.
+                stateful_egress: false,
+                rules: serde_json::from_str(&r)?,
+            })
+        }
+        (None, None) => {
+            return Err(CarbideCliError::GenericError(
+                "either --id or --rules is required".to_string(),
+            ));
+        }
+    };
+
+    let response = api_client
+        .0
+        .simulate_network_security_group(forgerpc::SimulateNetworkSecurityGroupRequest {
+            rule_source: Some(rule_source),
+            flows: args.flows,
+        })
+        .await?;
+
+    if is_json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&response).map_err(CarbideCliError::JsonError)?
+        );
+        return Ok(());
+    }
+
+    if !response.results.is_empty() {
+        let mut results_table = Table::new();
+        results_table.set_titles(row![
+            "Direction",
+            "Protocol",
+            "Source",
+            "Destination",
+            "Action",
+            "Matched Rule"
+        ]);
+
+        let endpoint = |ip: &str, port: Option<u32>| match port {
+            Some(port) if ip.contains(':') => format!("[{ip}]:{port}"),
+            Some(port) => format!("{ip}:{port}"),
+            None => ip.to_string(),
+        };
+
+        for result in &response.results {
+            let Some(flow) = result.flow.as_ref() else {
+                continue;
+            };
+            results_table.add_row(row![
+                flow.direction().as_str_name(),
+                flow.protocol().as_str_name(),
+                endpoint(&flow.src_ip, flow.src_port),
+                endpoint(&flow.dst_ip, flow.dst_port),
+                result.action().as_str_name(),
+                result
+                    .matched_rule_id
+                    .as_deref()
+                    .unwrap_or("<implicit deny>"),
+            ]);
+        }
+
+        println!("Flows:");
+        results_table.printstd();
+    }
+
+    if response.findings.is_empty() {
+        println!("No rule conflicts found");
+    } else {
+        let mut findings_table = Table::new();
+        findings_table.set_titles(row!["Finding", "Rule", "Related Rule", "Details"]);
+
+        for finding in &response.findings {
+            findings_table.add_row(row![
+                finding.r#type().as_str_name(),
+                finding.rule_id,
+                finding.related_rule_id.as_deref().unwrap_or_default(),
+                finding.details,
+            ]);
+        }
+
+        println!("Findings:");
+        findings_table.printstd();
+    }
+
+    Ok(())
+}
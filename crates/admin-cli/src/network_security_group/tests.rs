@@ -232,3 +232,104 @@ fn parse_create_missing_required_fails() {
         "should fail without --tenant-organization-id"
     );
 }
+
+// parse_simulate ensures simulate parses an NSG ID
+// and repeated flows.
+#[test]
+fn parse_simulate() {
+    let cmd = Cmd::try_parse_from([
+        "network-security-group",
+        "simulate",
+        "--id",
+        "nsg-123",
+        "--flow",
+        "ingress tcp 10.0.0.1:40000 10.0.0.2:22",
+        "--flow",
+        "egress icmp6 2001:db8::1 [2001:db8::2]:7",
+    ])
+    .expect("should parse simulate");
+
+    match cmd {
+        Cmd::Simulate(args) => {
+            assert_eq!(args.id.as_deref(), Some("nsg-123"));
+            assert!(args.rules.is_none());
+            assert_eq!(args.flows.len(), 2);
+
+            let ssh = &args.flows[0];
+            assert_eq!(
+                ssh.direction(),
+                ::rpc::forge::NetworkSecurityGroupRuleDirection::NsgRuleDirectionIngress
+            );
+            assert_eq!(
+                ssh.protocol(),
+                ::rpc::forge::NetworkSecurityGroupRuleProtocol::NsgRuleProtoTcp
+            );
+            assert_eq!(ssh.src_ip, "10.0.0.1");
+            assert_eq!(ssh.src_port, Some(40000));
+            assert_eq!(ssh.dst_ip, "10.0.0.2");
+            assert_eq!(ssh.dst_port, Some(22));
+
+            let ping = &args.flows[1];
+            assert_eq!(ping.src_ip, "2001:db8::1");
+            assert_eq!(ping.src_port, None);
+            assert_eq!(ping.dst_ip, "2001:db8::2");
+            assert_eq!(ping.dst_port, Some(7));
+        }
+        _ => panic!("expected Simulate variant"),
+    }
+}
+
+// parse_simulate_with_rules ensures simulate parses
+// proposed rules without flows.
+#[test]
+fn parse_simulate_with_rules() {
+    let cmd = Cmd::try_parse_from(["network-security-group", "simulate", "--rules", "[]"])
+        .expect("should parse simulate with rules");
+
+    match cmd {
+        Cmd::Simulate(args) => {
+            assert!(args.id.is_none());
+            assert_eq!(args.rules.as_deref(), Some("[]"));
+            assert!(args.flows.is_empty());
+        }
+        _ => panic!("expected Simulate variant"),
+    }
+}
+
+// parse_simulate_invalid_fails ensures simulate requires
+// exactly one rule source and well-formed flows.
+#[test]
+fn parse_simulate_invalid_fails() {
+    let result = Cmd::try_parse_from(["network-security-group", "simulate"]);
+    assert!(result.is_err(), "should fail without --id or --rules");
+
+    let result = Cmd::try_parse_from([
+        "network-security-group",
+        "simulate",
+        "--id",
+        "nsg-123",
+        "--rules",
+        "[]",
+    ]);
+    assert!(result.is_err(), "should fail with both --id and --rules");
+
+    let result = Cmd::try_parse_from([
+        "network-security-group",
+        "simulate",
+        "--id",
+        "nsg-123",
+        "--flow",
+        "ingress tcp 10.0.0.1",
+    ]);
+    assert!(result.is_err(), "should fail with an incomplete flow");
+
+    let result = Cmd::try_parse_from([
+        "network-security-group",
+        "simulate",
+        "--id",
+        "nsg-123",
+        "--flow",
+        "ingress sctp 10.0.0.1 10.0.0.2",
+    ]);
+    assert!(result.is_err(), "should fail with an unknown protocol");
+}
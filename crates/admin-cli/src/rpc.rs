@@ -1226,6 +1226,8 @@ impl ApiClient {
             tenant_organization_id: tenant_org.to_string(),
             tenant_keyset_ids: vec![],
             hostname: None,
+            metadata_token_required: None,
+            metadata_token_hop_limit: None,
         };
 
         let instance_config = rpc::InstanceConfig {
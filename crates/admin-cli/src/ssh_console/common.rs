@@ -0,0 +1,107 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::admin_cli::{CarbideCliError, CarbideCliResult};
+use serde::{Deserialize, Serialize};
+
+/// The header line of an asciicast v2 recording written by ssh-console
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RecordingHeader {
+    pub version: u8,
+    pub width: u32,
+    pub height: u32,
+    pub timestamp: i64,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub session_id: Option<String>,
+    #[serde(default)]
+    pub machine_id: Option<String>,
+    #[serde(default)]
+    pub machine_string: Option<String>,
+    #[serde(default)]
+    pub user: Option<String>,
+    #[serde(default)]
+    pub certificate_key_id: Option<String>,
+    #[serde(default)]
+    pub peer_addr: Option<String>,
+}
+
+/// A single event of a recording: seconds since the session started, the event code ("o" for
+/// output, "i" for input, "r" for resize, "m" for marker) and its data.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct RecordingEvent(pub f64, pub String, pub String);
+
+impl RecordingEvent {
+    pub fn time(&self) -> f64 {
+        self.0
+    }
+
+    pub fn code(&self) -> &str {
+        &self.1
+    }
+
+    pub fn data(&self) -> &str {
+        &self.2
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Recording {
+    /// The recording's name, which identifies the session
+    pub name: String,
+    pub header: RecordingHeader,
+    pub events: Vec<RecordingEvent>,
+}
+
+impl Recording {
+    /// Parse an asciicast v2 recording.
+    ///
+    /// Lines which aren't valid events (such as a partial line at the end of a recording which
+    /// was cut short) are skipped with a warning, so that the rest of the recording can still be
+    /// replayed.
+    pub fn parse(name: String, contents: &str) -> CarbideCliResult<Self> {
+        let mut lines = contents.lines();
+        let header = serde_json::from_str(lines.next().ok_or_else(|| {
+            CarbideCliError::GenericError(format!("Session recording {name} is empty"))
+        })?)?;
+        let events = lines
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .filter_map(|(index, line)| match serde_json::from_str(line) {
+                Ok(event) => Some(event),
+                Err(error) => {
+                    // The header is line 1
+                    tracing::warn!(
+                        "Skipping invalid line {} of session recording {name}: {error}",
+                        index + 2
+                    );
+                    None
+                }
+            })
+            .collect();
+        Ok(Self {
+            name,
+            header,
+            events,
+        })
+    }
+
+    pub fn duration_secs(&self) -> f64 {
+        self.events.last().map(RecordingEvent::time).unwrap_or(0.0)
+    }
+}
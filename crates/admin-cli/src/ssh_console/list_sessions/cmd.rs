@@ -0,0 +1,68 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::admin_cli::{CarbideCliResult, OutputFormat};
+use ::rpc::forge::SshConsoleSessionsRequest;
+use prettytable::{Row, Table, row};
+
+use super::args::Args;
+use crate::rpc::ApiClient;
+
+pub async fn list_sessions(
+    args: Args,
+    format: OutputFormat,
+    api_client: &ApiClient,
+) -> CarbideCliResult<()> {
+    let sessions = api_client
+        .0
+        .find_ssh_console_sessions(SshConsoleSessionsRequest {
+            machine_id: Some(args.machine),
+        })
+        .await?
+        .sessions;
+
+    if format == OutputFormat::Json {
+        println!("{}", serde_json::to_string_pretty(&sessions)?);
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.set_titles(Row::from(vec![
+        "Session",
+        "Started",
+        "Duration",
+        "User",
+        "Peer Address",
+        "Input Events",
+    ]));
+    for session in sessions {
+        table.add_row(row![
+            session.name,
+            session
+                .started
+                .map(|started| started.to_string())
+                .unwrap_or("---".to_string()),
+            format!("{:.1}s", session.duration_secs),
+            session.user.as_deref().unwrap_or("---"),
+            session.peer_addr,
+            session.input_events,
+        ]);
+    }
+    table.printstd();
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use carbide_uuid::machine::MachineId;
+use clap::Parser;
+
+#[derive(Parser, Debug, Clone)]
+pub struct Args {
+    #[clap(long, help = "Machine ID the session was recorded for")]
+    pub machine: MachineId,
+    #[clap(
+        long,
+        help = "The session to replay, by name (as shown by list-sessions) or session ID"
+    )]
+    pub session: String,
+    #[clap(
+        long,
+        default_value = "1.0",
+        help = "Playback speed multiplier (e.g. 2.0 plays back twice as fast)"
+    )]
+    pub speed: f64,
+    #[clap(
+        long,
+        default_value = "2.0",
+        help = "Limit pauses between events to this many seconds"
+    )]
+    pub max_idle: f64,
+    #[clap(
+        long,
+        help = "Also print the user's input, annotated with its timestamp, to stderr"
+    )]
+    pub show_input: bool,
+    #[clap(long, help = "Print the whole session immediately, without timing")]
+    pub no_timing: bool,
+}
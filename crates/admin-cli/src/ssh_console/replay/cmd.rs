@@ -0,0 +1,104 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::io::Write;
+use std::time::Duration;
+
+use ::rpc::admin_cli::{CarbideCliError, CarbideCliResult};
+use ::rpc::forge::SshConsoleSessionRecordingRequest;
+
+use super::super::common::Recording;
+use super::args::Args;
+use crate::rpc::ApiClient;
+
+pub async fn replay(args: Args, api_client: &ApiClient) -> CarbideCliResult<()> {
+    if args.speed <= 0.0 || !args.speed.is_finite() {
+        return Err(CarbideCliError::GenericError(
+            "--speed must be a positive number".to_string(),
+        ));
+    }
+    if args.max_idle < 0.0 || !args.max_idle.is_finite() {
+        return Err(CarbideCliError::GenericError(
+            "--max-idle must not be negative".to_string(),
+        ));
+    }
+
+    let response = api_client
+        .0
+        .get_ssh_console_session_recording(SshConsoleSessionRecordingRequest {
+            machine_id: Some(args.machine),
+            session: args.session.clone(),
+        })
+        .await?;
+    let name = response
+        .session
+        .map(|session| session.name)
+        .unwrap_or(args.session);
+    let recording = Recording::parse(name, &response.recording)?;
+
+    eprintln!(
+        "Replaying session {} of {} by {} from {} ({:.1}s)",
+        recording.name,
+        args.machine,
+        recording.header.user.as_deref().unwrap_or("<unknown user>"),
+        recording
+            .header
+            .peer_addr
+            .as_deref()
+            .unwrap_or("<unknown address>"),
+        recording.duration_secs(),
+    );
+
+    let mut stdout = std::io::stdout();
+    let mut previous_time = 0.0;
+    for event in recording.events.iter() {
+        if !args.no_timing {
+            tokio::time::sleep(replay_delay(
+                previous_time,
+                event.time(),
+                args.speed,
+                args.max_idle,
+            ))
+            .await;
+        }
+        previous_time = event.time();
+
+        match event.code() {
+            "o" => {
+                stdout.write_all(event.data().as_bytes())?;
+                stdout.flush()?;
+            }
+            "i" if args.show_input => {
+                eprintln!("[{:.3}s input] {:?}", event.time(), event.data());
+            }
+            "m" => {
+                eprintln!("[{:.3}s] --- {} ---", event.time(), event.data());
+            }
+            _ => {}
+        }
+    }
+
+    eprintln!("\r\n--- End of session {} ---", recording.name);
+    Ok(())
+}
+
+/// How long to wait before playing back an event at `time`, when the previous event was played
+/// back at `previous_time`.
+pub fn replay_delay(previous_time: f64, time: f64, speed: f64, max_idle: f64) -> Duration {
+    let delay = ((time - previous_time).max(0.0)).min(max_idle) / speed;
+    Duration::from_secs_f64(delay)
+}
@@ -0,0 +1,174 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// The intent of the tests.rs file is to test the integrity of the
+// command, including things like basic structure parsing, enum
+// translations, and any external input validators that are
+// configured. Specific "categories" are:
+//
+// Command Structure - Baseline debug_assert() of the entire command.
+// Argument Parsing  - Ensure required/optional arg combinations parse correctly.
+// Recording Parsing - Ensure session recordings are read correctly.
+
+use std::time::Duration;
+
+use clap::{CommandFactory, Parser};
+use common::Recording;
+use replay::cmd::replay_delay;
+
+use super::*;
+
+// Define a basic/working MachineId for testing.
+const TEST_MACHINE_ID: &str = "fm100ht038bg3qsho433vkg684heguv282qaggmrsh2ugn1qk096n2c6hcg";
+
+// verify_cmd_structure runs a baseline clap debug_assert()
+// to do basic command configuration checking and validation,
+// ensuring things like unique argument definitions, group
+// configurations, argument references, etc. Things that would
+// otherwise be missed until runtime.
+#[test]
+fn verify_cmd_structure() {
+    Cmd::command().debug_assert();
+}
+
+/////////////////////////////////////////////////////////////////////////////
+// Argument Parsing
+//
+// This section contains tests specific to argument parsing,
+// including testing required arguments, as well as optional
+// flag-specific checking.
+
+// parse_list_sessions ensures list-sessions parses with a
+// machine.
+#[test]
+fn parse_list_sessions() {
+    let cmd = Cmd::try_parse_from(["ssh-console", "list-sessions", "--machine", TEST_MACHINE_ID])
+        .expect("should parse list-sessions");
+
+    match cmd {
+        Cmd::ListSessions(args) => {
+            assert_eq!(args.machine.to_string(), TEST_MACHINE_ID);
+        }
+        _ => panic!("expected ListSessions variant"),
+    }
+}
+
+// parse_list_sessions_missing_machine ensures list-sessions
+// requires a machine.
+#[test]
+fn parse_list_sessions_missing_machine() {
+    let result = Cmd::try_parse_from(["ssh-console", "list-sessions"]);
+    assert!(result.is_err(), "should fail without --machine");
+}
+
+// parse_replay ensures replay parses with all options.
+#[test]
+fn parse_replay() {
+    let cmd = Cmd::try_parse_from([
+        "ssh-console",
+        "replay",
+        "--machine",
+        TEST_MACHINE_ID,
+        "--session",
+        "20260301T123456Z_a",
+        "--speed",
+        "4",
+        "--max-idle",
+        "0.5",
+        "--show-input",
+    ])
+    .expect("should parse replay");
+
+    match cmd {
+        Cmd::Replay(args) => {
+            assert_eq!(args.session, "20260301T123456Z_a");
+            assert_eq!(args.speed, 4.0);
+            assert_eq!(args.max_idle, 0.5);
+            assert!(args.show_input);
+            assert!(!args.no_timing);
+        }
+        _ => panic!("expected Replay variant"),
+    }
+}
+
+// parse_replay_missing_session ensures replay requires a
+// session.
+#[test]
+fn parse_replay_missing_session() {
+    let result = Cmd::try_parse_from(["ssh-console", "replay", "--machine", TEST_MACHINE_ID]);
+    assert!(result.is_err(), "should fail without --session");
+}
+
+/////////////////////////////////////////////////////////////////////////////
+// Recording Parsing
+//
+// These tests ensure recordings written by ssh-console are
+// read correctly.
+
+// parse_recording ensures the header and events of a
+// recording are parsed, skipping invalid lines, including
+// a partially written one, without dropping what follows.
+#[test]
+fn parse_recording() {
+    let contents = concat!(
+        r#"{"version":2,"width":120,"height":40,"timestamp":1772368496,"title":"machine console","env":{"TERM":"xterm"},"session_id":"4f2c4a36-5d0c-4b7b-9a53-7f6b4b4f0a11","machine_id":"fm100ht038bg3qsho433vkg684heguv282qaggmrsh2ugn1qk096n2c6hcg","machine_string":"fm100ht038bg3qsho433vkg684heguv282qaggmrsh2ugn1qk096n2c6hcg","user":"jdoe","certificate_key_id":"user=jdoe roles=admin","peer_addr":"10.0.0.1:5555"}"#,
+        "\n",
+        r#"[0.25,"o","login: "]"#,
+        "\n",
+        r#"[1.5,"i","root\r"]"#,
+        "\n",
+        r#"not an event"#,
+        "\n",
+        r#"[2.0,"r","100x30"]"#,
+        "\n",
+        r#"[2.5,"o","Pass"#,
+    );
+
+    let recording =
+        Recording::parse("session".to_string(), contents).expect("should parse recording");
+    assert_eq!(recording.header.user.as_deref(), Some("jdoe"));
+    assert_eq!(recording.header.peer_addr.as_deref(), Some("10.0.0.1:5555"));
+    assert_eq!(recording.events.len(), 3);
+    assert_eq!(recording.events[1].code(), "i");
+    assert_eq!(recording.events[1].data(), "root\r");
+    assert_eq!(
+        recording
+            .events
+            .iter()
+            .filter(|event| event.code() == "o")
+            .count(),
+        1
+    );
+    assert_eq!(recording.duration_secs(), 2.0);
+}
+
+// parse_recording_empty ensures an empty recording is
+// rejected.
+#[test]
+fn parse_recording_empty() {
+    assert!(Recording::parse("session".to_string(), "").is_err());
+}
+
+// replay_delay_is_limited ensures pauses are scaled by the
+// playback speed and limited by max_idle.
+#[test]
+fn replay_delay_is_limited() {
+    assert_eq!(replay_delay(1.0, 2.0, 1.0, 2.0), Duration::from_secs(1));
+    assert_eq!(replay_delay(1.0, 2.0, 2.0, 2.0), Duration::from_millis(500));
+    assert_eq!(replay_delay(0.0, 60.0, 1.0, 2.0), Duration::from_secs(2));
+    assert_eq!(replay_delay(2.0, 1.0, 1.0, 2.0), Duration::ZERO);
+}
@@ -39,6 +39,8 @@ pub(crate) fn convert_tenants_to_table(
         "Description",
         "Version",
         "Routing Profile Type",
+        "Metadata Token Required",
+        "Metadata Token Hop Limit",
         "Labels",
     ]);
 
@@ -65,6 +67,11 @@ pub(crate) fn convert_tenants_to_table(
             } else {
                 tenant.routing_profile_type().as_str_name()
             },
+            tenant.metadata_token_required,
+            tenant
+                .metadata_token_hop_limit
+                .map(|hop_limit| hop_limit.to_string())
+                .unwrap_or_else(|| "None".to_string()),
             labels.join(", "),
         ]);
     }
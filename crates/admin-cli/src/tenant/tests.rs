@@ -230,3 +230,27 @@ fn parse_routing_profile_invalid_fails() {
     let result = Cmd::try_parse_from(["tenant", "update", "org-123", "-p", "invalid"]);
     assert!(result.is_err(), "should fail with invalid routing profile");
 }
+
+// parse_update_with_metadata_token_required ensures update parses
+// with --metadata-token-required.
+#[test]
+fn parse_update_with_metadata_token_required() {
+    let cmd = Cmd::try_parse_from([
+        "tenant",
+        "update",
+        "org-123",
+        "--metadata-token-required",
+        "true",
+        "--metadata-token-hop-limit",
+        "1",
+    ])
+    .expect("should parse update with metadata token requirement");
+
+    match cmd {
+        Cmd::Update(args) => {
+            assert_eq!(args.metadata_token_required, Some(true));
+            assert_eq!(args.metadata_token_hop_limit, Some(1));
+        }
+        _ => panic!("expected Update variant"),
+    }
+}
@@ -42,4 +42,16 @@ pub struct Args {
 
     #[clap(short = 'n', long, help = "Organization name of the tenant")]
     pub name: Option<String>,
+
+    #[clap(
+        long,
+        help = "Optional, whether instances of the tenant must obtain a session token before reading instance metadata. The cloud-init NoCloud and OpenStack datasources don't obtain tokens"
+    )]
+    pub metadata_token_required: Option<bool>,
+
+    #[clap(
+        long,
+        help = "Optional, IP hop limit (1-255) of metadata token responses for instances of the tenant, 0 removes the limit"
+    )]
+    pub metadata_token_hop_limit: Option<u32>,
 }
@@ -56,6 +56,8 @@ pub async fn update(
             routing_profile_type: args
                 .routing_profile_type
                 .map(|p| rpc::forge::RoutingProfileType::from(p).into()),
+            metadata_token_required: args.metadata_token_required,
+            metadata_token_hop_limit: args.metadata_token_hop_limit,
         })
         .await?
         .tenant
@@ -0,0 +1,552 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! cloud-init compatible documents served by FMDS
+//!
+//! The tenant network configuration is rendered both as a cloud-init network-config (v2, netplan
+//! format) for the NoCloud and EC2 style layouts, and as an OpenStack `network_data.json`.
+//! Interfaces are matched by their MAC address, so that the host side interface names don't matter.
+
+use std::collections::BTreeMap;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use ::rpc::forge::{self as rpc, ManagedHostNetworkConfigResponse};
+use ipnetwork::IpNetwork;
+use serde::Serialize;
+
+use crate::periodic_config_fetcher::InstanceMetadata;
+
+/// The address and routes of a single tenant interface, as seen from the host
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HostInterface {
+    /// `pf0` for the physical function, `vf<id>` for virtual functions
+    pub id: String,
+    pub mac_address: String,
+    pub mtu: Option<u32>,
+    /// The host address, with the prefix length of the network segment
+    pub address: IpNetwork,
+    pub gateway: IpAddr,
+    /// Whether the default route points at the gateway of this interface.
+    /// Only the first interface carries the default route.
+    pub default_route: bool,
+    /// Prefixes which are reachable via the gateway of this interface
+    pub routes: Vec<IpNetwork>,
+}
+
+/// Derives the host side interface configuration from the network config of an instance
+///
+/// Interfaces whose MAC address has not been reported yet are skipped, since the host
+/// would not be able to match them.
+pub fn host_interfaces(
+    network_config: &ManagedHostNetworkConfigResponse,
+) -> eyre::Result<Vec<HostInterface>> {
+    if network_config.use_admin_network {
+        return Ok(vec![]);
+    }
+
+    let interface_statuses = network_config
+        .instance
+        .as_ref()
+        .and_then(|instance| instance.status.as_ref())
+        .and_then(|status| status.network.as_ref())
+        .map(|network| network.interfaces.as_slice())
+        .unwrap_or_default();
+
+    let mut tenant_interfaces: Vec<&rpc::FlatInterfaceConfig> =
+        network_config.tenant_interfaces.iter().collect();
+    // Physical function first, so that it carries the default route
+    tenant_interfaces.sort_by_key(|iface| {
+        (
+            iface.function_type != rpc::InterfaceFunctionType::Physical as i32,
+            iface.virtual_function_id,
+        )
+    });
+
+    let mut interfaces = Vec::with_capacity(tenant_interfaces.len());
+    for iface in tenant_interfaces {
+        let is_physical = iface.function_type == rpc::InterfaceFunctionType::Physical as i32;
+        let virtual_function_id = if is_physical {
+            None
+        } else {
+            iface.virtual_function_id
+        };
+        let id = match virtual_function_id {
+            Some(vf_id) => format!("vf{vf_id}"),
+            None => "pf0".to_string(),
+        };
+
+        let Some(mac_address) = interface_statuses
+            .iter()
+            .find(|status| status.virtual_function_id == virtual_function_id)
+            .and_then(|status| status.mac_address.clone())
+        else {
+            tracing::debug!(interface = id, "Skipping interface without MAC address");
+            continue;
+        };
+
+        // The gateway is sent in CIDR notation, with the prefix length of the segment
+        let gateway: IpNetwork = iface
+            .gateway
+            .parse()
+            .map_err(|e| eyre::eyre!("Invalid gateway {} for {id}: {e}", iface.gateway))?;
+        let ip: IpAddr = iface
+            .ip
+            .parse()
+            .map_err(|e| eyre::eyre!("Invalid address {} for {id}: {e}", iface.ip))?;
+        let address = IpNetwork::new(ip, gateway.prefix())?;
+
+        let mut routes: Vec<IpNetwork> = vec![];
+        for prefix in iface.vpc_prefixes.iter().chain(&iface.vpc_peer_prefixes) {
+            let prefix: IpNetwork = prefix
+                .parse()
+                .map_err(|e| eyre::eyre!("Invalid VPC prefix {prefix} for {id}: {e}"))?;
+            // Prefixes of other address families and the on-link segment don't need routes
+            if prefix.is_ipv4() != ip.is_ipv4()
+                || (prefix.prefix() >= address.prefix() && address.contains(prefix.ip()))
+                || routes.contains(&prefix)
+            {
+                continue;
+            }
+            routes.push(prefix);
+        }
+
+        interfaces.push(HostInterface {
+            id,
+            mac_address: mac_address.to_lowercase(),
+            mtu: iface.mtu,
+            address,
+            gateway: gateway.ip(),
+            default_route: interfaces.is_empty(),
+            routes,
+        });
+    }
+
+    Ok(interfaces)
+}
+
+/// A cloud-init network-config document in version 2 (netplan) format
+#[derive(Clone, Debug, Serialize)]
+pub struct NetworkConfigV2 {
+    pub version: u8,
+    pub ethernets: BTreeMap<String, EthernetConfig>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct EthernetConfig {
+    #[serde(rename = "match")]
+    pub match_: MatchConfig,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mtu: Option<u32>,
+    pub addresses: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub routes: Vec<RouteConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nameservers: Option<NameserversConfig>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct MatchConfig {
+    pub macaddress: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct RouteConfig {
+    pub to: String,
+    pub via: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct NameserversConfig {
+    pub addresses: Vec<String>,
+}
+
+impl NetworkConfigV2 {
+    pub fn new(interfaces: &[HostInterface], nameservers: &[IpAddr]) -> Self {
+        let ethernets = interfaces
+            .iter()
+            .map(|iface| {
+                let via = iface.gateway.to_string();
+                let mut routes = vec![];
+                if iface.default_route {
+                    routes.push(RouteConfig {
+                        to: default_route(iface.gateway).to_string(),
+                        via: via.clone(),
+                    });
+                }
+                routes.extend(iface.routes.iter().map(|prefix| RouteConfig {
+                    to: prefix.to_string(),
+                    via: via.clone(),
+                }));
+
+                let nameservers: Vec<String> = nameservers
+                    .iter()
+                    .filter(|ns| ns.is_ipv4() == iface.address.is_ipv4())
+                    .map(IpAddr::to_string)
+                    .collect();
+
+                let config = EthernetConfig {
+                    match_: MatchConfig {
+                        macaddress: iface.mac_address.clone(),
+                    },
+                    mtu: iface.mtu,
+                    addresses: vec![iface.address.to_string()],
+                    routes,
+                    nameservers: (iface.default_route && !nameservers.is_empty()).then_some(
+                        NameserversConfig {
+                            addresses: nameservers,
+                        },
+                    ),
+                };
+                (iface.id.clone(), config)
+            })
+            .collect();
+
+        Self {
+            version: 2,
+            ethernets,
+        }
+    }
+}
+
+/// OpenStack `network_data.json`
+#[derive(Clone, Debug, Serialize)]
+pub struct OpenStackNetworkData {
+    pub links: Vec<OpenStackLink>,
+    pub networks: Vec<OpenStackNetwork>,
+    pub services: Vec<OpenStackService>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct OpenStackLink {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub link_type: String,
+    pub ethernet_mac_address: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mtu: Option<u32>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct OpenStackNetwork {
+    pub id: String,
+    pub link: String,
+    #[serde(rename = "type")]
+    pub network_type: String,
+    pub ip_address: String,
+    pub netmask: String,
+    pub routes: Vec<OpenStackRoute>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct OpenStackRoute {
+    pub network: String,
+    pub netmask: String,
+    pub gateway: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct OpenStackService {
+    #[serde(rename = "type")]
+    pub service_type: String,
+    pub address: String,
+}
+
+impl OpenStackNetworkData {
+    pub fn new(interfaces: &[HostInterface], nameservers: &[IpAddr]) -> Self {
+        let links = interfaces
+            .iter()
+            .map(|iface| OpenStackLink {
+                id: iface.id.clone(),
+                link_type: "phy".to_string(),
+                ethernet_mac_address: iface.mac_address.clone(),
+                mtu: iface.mtu,
+            })
+            .collect();
+
+        let networks = interfaces
+            .iter()
+            .enumerate()
+            .map(|(index, iface)| {
+                let gateway = iface.gateway.to_string();
+                let mut routes = vec![];
+                if iface.default_route {
+                    let default = default_route(iface.gateway);
+                    routes.push(OpenStackRoute {
+                        network: default.network().to_string(),
+                        netmask: default.mask().to_string(),
+                        gateway: gateway.clone(),
+                    });
+                }
+                routes.extend(iface.routes.iter().map(|prefix| OpenStackRoute {
+                    network: prefix.network().to_string(),
+                    netmask: prefix.mask().to_string(),
+                    gateway: gateway.clone(),
+                }));
+
+                OpenStackNetwork {
+                    id: format!("network{index}"),
+                    link: iface.id.clone(),
+                    network_type: if iface.address.is_ipv4() {
+                        "ipv4"
+                    } else {
+                        "ipv6"
+                    }
+                    .to_string(),
+                    ip_address: iface.address.ip().to_string(),
+                    netmask: iface.address.mask().to_string(),
+                    routes,
+                }
+            })
+            .collect();
+
+        let services = nameservers
+            .iter()
+            .map(|ns| OpenStackService {
+                service_type: "dns".to_string(),
+                address: ns.to_string(),
+            })
+            .collect();
+
+        Self {
+            links,
+            networks,
+            services,
+        }
+    }
+}
+
+/// OpenStack `meta_data.json`
+#[derive(Clone, Debug, Serialize)]
+pub struct OpenStackMetaData {
+    pub uuid: String,
+    pub name: String,
+    pub hostname: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub availability_zone: Option<String>,
+    pub launch_index: u32,
+    pub meta: BTreeMap<String, String>,
+}
+
+impl From<&InstanceMetadata> for OpenStackMetaData {
+    fn from(metadata: &InstanceMetadata) -> Self {
+        let mut meta = BTreeMap::new();
+        if let Some(machine_id) = &metadata.machine_id {
+            meta.insert("machine_id".to_string(), machine_id.to_string());
+        }
+
+        Self {
+            uuid: metadata
+                .instance_id
+                .as_ref()
+                .map(|id| id.to_string())
+                .unwrap_or_else(|| metadata.hostname.clone()),
+            name: metadata.hostname.clone(),
+            hostname: metadata.hostname.clone(),
+            availability_zone: metadata.sitename.clone(),
+            launch_index: 0,
+            meta,
+        }
+    }
+}
+
+fn default_route(gateway: IpAddr) -> IpNetwork {
+    let unspecified = match gateway {
+        IpAddr::V4(_) => IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+        IpAddr::V6(_) => IpAddr::V6(Ipv6Addr::UNSPECIFIED),
+    };
+    IpNetwork::new(unspecified, 0).expect("a prefix length of 0 is always valid")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tenant_interface(
+        function_type: rpc::InterfaceFunctionType,
+        virtual_function_id: Option<u32>,
+        ip: &str,
+        gateway: &str,
+        vpc_prefixes: &[&str],
+    ) -> rpc::FlatInterfaceConfig {
+        rpc::FlatInterfaceConfig {
+            function_type: function_type as i32,
+            virtual_function_id,
+            ip: ip.to_string(),
+            gateway: gateway.to_string(),
+            vpc_prefixes: vpc_prefixes.iter().map(|p| p.to_string()).collect(),
+            mtu: Some(9000),
+            ..Default::default()
+        }
+    }
+
+    fn interface_status(
+        virtual_function_id: Option<u32>,
+        mac_address: Option<&str>,
+    ) -> rpc::InstanceInterfaceStatus {
+        rpc::InstanceInterfaceStatus {
+            virtual_function_id,
+            mac_address: mac_address.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    fn network_config() -> ManagedHostNetworkConfigResponse {
+        ManagedHostNetworkConfigResponse {
+            tenant_interfaces: vec![
+                tenant_interface(
+                    rpc::InterfaceFunctionType::Virtual,
+                    Some(1),
+                    "10.1.0.5",
+                    "10.1.0.1/24",
+                    &["10.1.0.0/24", "10.200.0.0/16"],
+                ),
+                tenant_interface(
+                    rpc::InterfaceFunctionType::Physical,
+                    None,
+                    "10.0.0.5",
+                    "10.0.0.1/24",
+                    &["10.0.0.0/24"],
+                ),
+                tenant_interface(
+                    rpc::InterfaceFunctionType::Virtual,
+                    Some(2),
+                    "10.2.0.5",
+                    "10.2.0.1/24",
+                    &[],
+                ),
+            ],
+            instance: Some(rpc::Instance {
+                status: Some(rpc::InstanceStatus {
+                    network: Some(rpc::InstanceNetworkStatus {
+                        interfaces: vec![
+                            interface_status(None, Some("AA:BB:CC:00:00:01")),
+                            interface_status(Some(1), Some("aa:bb:cc:00:00:02")),
+                            interface_status(Some(2), None),
+                        ],
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_host_interfaces() {
+        let interfaces = host_interfaces(&network_config()).unwrap();
+        // The VF without a MAC address is skipped, the PF comes first
+        assert_eq!(
+            interfaces,
+            vec![
+                HostInterface {
+                    id: "pf0".to_string(),
+                    mac_address: "aa:bb:cc:00:00:01".to_string(),
+                    mtu: Some(9000),
+                    address: "10.0.0.5/24".parse().unwrap(),
+                    gateway: "10.0.0.1".parse().unwrap(),
+                    default_route: true,
+                    routes: vec![],
+                },
+                HostInterface {
+                    id: "vf1".to_string(),
+                    mac_address: "aa:bb:cc:00:00:02".to_string(),
+                    mtu: Some(9000),
+                    address: "10.1.0.5/24".parse().unwrap(),
+                    gateway: "10.1.0.1".parse().unwrap(),
+                    default_route: false,
+                    routes: vec!["10.200.0.0/16".parse().unwrap()],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_host_interfaces_admin_network() {
+        let network_config = ManagedHostNetworkConfigResponse {
+            use_admin_network: true,
+            ..network_config()
+        };
+        assert!(host_interfaces(&network_config).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_network_config_v2() {
+        let interfaces = host_interfaces(&network_config()).unwrap();
+        let config = NetworkConfigV2::new(&interfaces, &["10.0.0.2".parse().unwrap()]);
+        let expected = r#"version: 2
+ethernets:
+  pf0:
+    match:
+      macaddress: aa:bb:cc:00:00:01
+    mtu: 9000
+    addresses:
+    - 10.0.0.5/24
+    routes:
+    - to: 0.0.0.0/0
+      via: 10.0.0.1
+    nameservers:
+      addresses:
+      - 10.0.0.2
+  vf1:
+    match:
+      macaddress: aa:bb:cc:00:00:02
+    mtu: 9000
+    addresses:
+    - 10.1.0.5/24
+    routes:
+    - to: 10.200.0.0/16
+      via: 10.1.0.1
+"#;
+        assert_eq!(serde_yaml::to_string(&config).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_openstack_network_data() {
+        let interfaces = host_interfaces(&network_config()).unwrap();
+        let data = OpenStackNetworkData::new(&interfaces, &["10.0.0.2".parse().unwrap()]);
+        assert_eq!(
+            serde_json::to_value(&data).unwrap(),
+            serde_json::json!({
+                "links": [
+                    {"id": "pf0", "type": "phy", "ethernet_mac_address": "aa:bb:cc:00:00:01", "mtu": 9000},
+                    {"id": "vf1", "type": "phy", "ethernet_mac_address": "aa:bb:cc:00:00:02", "mtu": 9000},
+                ],
+                "networks": [
+                    {
+                        "id": "network0",
+                        "link": "pf0",
+                        "type": "ipv4",
+                        "ip_address": "10.0.0.5",
+                        "netmask": "255.255.255.0",
+                        "routes": [{"network": "0.0.0.0", "netmask": "0.0.0.0", "gateway": "10.0.0.1"}],
+                    },
+                    {
+                        "id": "network1",
+                        "link": "vf1",
+                        "type": "ipv4",
+                        "ip_address": "10.1.0.5",
+                        "netmask": "255.255.255.0",
+                        "routes": [{"network": "10.200.0.0", "netmask": "255.255.0.0", "gateway": "10.1.0.1"}],
+                    },
+                ],
+                "services": [{"type": "dns", "address": "10.0.0.2"}],
+            })
+        );
+    }
+}
@@ -0,0 +1,375 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Standalone containers, run directly by containerd through the `ctr` CLI rather than by
+//! kubelet as part of a pod.
+
+use std::process::Output;
+use std::time::Duration;
+
+use eyre::WrapErr;
+use tokio::process::Command as TokioCommand;
+
+use crate::pretty_cmd;
+
+const CONTAINERD_ADDRESS: &str = "/run/containerd/containerd.sock";
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(30);
+// Pulling an image can take a while for large images on slow links
+const PULL_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// The status of a container task, as reported by `ctr tasks ls`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TaskStatus {
+    Created,
+    Running,
+    Paused,
+    Stopped,
+    Unknown,
+}
+
+impl TaskStatus {
+    fn parse(status: &str) -> Self {
+        match status {
+            "CREATED" => TaskStatus::Created,
+            "RUNNING" => TaskStatus::Running,
+            "PAUSED" | "PAUSING" => TaskStatus::Paused,
+            "STOPPED" => TaskStatus::Stopped,
+            _ => TaskStatus::Unknown,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TaskStatus::Created => "CREATED",
+            TaskStatus::Running => "RUNNING",
+            TaskStatus::Paused => "PAUSED",
+            TaskStatus::Stopped => "STOPPED",
+            TaskStatus::Unknown => "UNKNOWN",
+        }
+    }
+}
+
+/// The running process of a container
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Task {
+    pub container_id: String,
+    pub status: TaskStatus,
+}
+
+/// A container known to containerd, which may or may not have a task
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Container {
+    pub id: String,
+    pub image: String,
+}
+
+/// A bind mount from the host into a container
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mount {
+    pub source: String,
+    pub destination: String,
+    pub read_only: bool,
+}
+
+/// How to run a container
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RunOptions {
+    pub image: String,
+    /// Arguments passed to the entrypoint of the image
+    pub args: Vec<String>,
+    pub env: Vec<(String, String)>,
+    pub mounts: Vec<Mount>,
+    pub labels: Vec<(String, String)>,
+    pub host_network: bool,
+    pub privileged: bool,
+}
+
+/// Runs `ctr` commands within a containerd namespace
+#[derive(Clone, Debug)]
+pub struct Ctr {
+    namespace: String,
+}
+
+impl Ctr {
+    pub fn new(namespace: &str) -> Self {
+        Self {
+            namespace: namespace.to_string(),
+        }
+    }
+
+    /// Pull an image, optionally authenticating against the registry
+    pub async fn pull(&self, image: &str, credential: Option<(&str, &str)>) -> eyre::Result<()> {
+        let user = credential.map(|(username, password)| format!("{username}:{password}"));
+        let mut args = vec!["images", "pull"];
+        if let Some(user) = user.as_deref() {
+            args.extend(["--user", user]);
+        }
+        args.push(image);
+
+        self.run_ctr(&args, PULL_TIMEOUT).await.map(|_| ())
+    }
+
+    /// Create a container and start its task in the background
+    pub async fn run(&self, container_id: &str, options: &RunOptions) -> eyre::Result<()> {
+        let args = run_args(container_id, options);
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        self.run_ctr(&args, COMMAND_TIMEOUT).await.map(|_| ())
+    }
+
+    /// Start the task of an existing container, e.g. after the DPU rebooted
+    pub async fn start(&self, container_id: &str) -> eyre::Result<()> {
+        self.run_ctr(
+            &["tasks", "start", "--detach", container_id],
+            COMMAND_TIMEOUT,
+        )
+        .await
+        .map(|_| ())
+    }
+
+    /// Delete the stopped task of a container and start a new one
+    pub async fn restart(&self, container_id: &str) -> eyre::Result<()> {
+        let (cmd_str, output) = self
+            .output(&["tasks", "delete", container_id], COMMAND_TIMEOUT)
+            .await?;
+        // ctr exits with the exit status of the deleted task, so only a failure which comes with
+        // an error message is a failure of the command
+        if !output.status.success() && !output.stderr.trim_ascii().is_empty() {
+            return Err(eyre::eyre!(
+                "{cmd_str} failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        self.start(container_id).await
+    }
+
+    /// Kill the task of a container, if it has one, and delete the container and its snapshot
+    pub async fn remove(&self, container_id: &str) -> eyre::Result<()> {
+        if let Err(e) = self
+            .run_ctr(
+                &["tasks", "delete", "--force", container_id],
+                COMMAND_TIMEOUT,
+            )
+            .await
+            && !e.to_string().contains("not found")
+        {
+            return Err(e);
+        }
+        self.run_ctr(&["containers", "delete", container_id], COMMAND_TIMEOUT)
+            .await
+            .map(|_| ())
+    }
+
+    pub async fn containers(&self) -> eyre::Result<Vec<Container>> {
+        let output = self
+            .run_ctr(&["containers", "list"], COMMAND_TIMEOUT)
+            .await?;
+        Ok(parse_containers(&output))
+    }
+
+    pub async fn tasks(&self) -> eyre::Result<Vec<Task>> {
+        let output = self.run_ctr(&["tasks", "list"], COMMAND_TIMEOUT).await?;
+        Ok(parse_tasks(&output))
+    }
+
+    async fn run_ctr(&self, args: &[&str], timeout: Duration) -> eyre::Result<String> {
+        let (cmd_str, output) = self.output(args, timeout).await?;
+
+        if !output.status.success() {
+            return Err(eyre::eyre!(
+                "{cmd_str} failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    /// Run a `ctr` command, returning the command line for logging along with its output
+    async fn output(&self, args: &[&str], timeout: Duration) -> eyre::Result<(String, Output)> {
+        let mut cmd = TokioCommand::new("ctr");
+        cmd.args([
+            "--address",
+            CONTAINERD_ADDRESS,
+            "--namespace",
+            &self.namespace,
+        ])
+        .args(args)
+        .kill_on_drop(true);
+        // Don't log credentials passed to `images pull`
+        let cmd_str = match args.first() {
+            Some(&"images") => format!("ctr {}", args[..2].join(" ")),
+            _ => pretty_cmd(cmd.as_std()),
+        };
+
+        let output = tokio::time::timeout(timeout, cmd.output())
+            .await
+            .wrap_err_with(|| format!("Timeout while running command: {cmd_str}"))?
+            .wrap_err_with(|| format!("Failed to run command: {cmd_str}"))?;
+
+        Ok((cmd_str, output))
+    }
+}
+
+fn run_args(container_id: &str, options: &RunOptions) -> Vec<String> {
+    let mut args = vec!["run".to_string(), "--detach".to_string()];
+    if options.host_network {
+        args.push("--net-host".to_string());
+    }
+    if options.privileged {
+        args.push("--privileged".to_string());
+    }
+    for (name, value) in &options.env {
+        args.extend(["--env".to_string(), format!("{name}={value}")]);
+    }
+    for mount in &options.mounts {
+        let mount_options = if mount.read_only {
+            "rbind:ro"
+        } else {
+            "rbind:rw"
+        };
+        args.extend([
+            "--mount".to_string(),
+            format!(
+                "type=bind,src={},dst={},options={mount_options}",
+                mount.source, mount.destination
+            ),
+        ]);
+    }
+    for (name, value) in &options.labels {
+        args.extend(["--label".to_string(), format!("{name}={value}")]);
+    }
+    args.push(options.image.clone());
+    args.push(container_id.to_string());
+    args.extend(options.args.iter().cloned());
+    args
+}
+
+/// Parses the table printed by `ctr containers list`:
+/// ```text
+/// CONTAINER    IMAGE                               RUNTIME
+/// app          docker.io/library/nginx:1.27        io.containerd.runc.v2
+/// ```
+fn parse_containers(output: &str) -> Vec<Container> {
+    output
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let mut columns = line.split_whitespace();
+            Some(Container {
+                id: columns.next()?.to_string(),
+                image: columns.next()?.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Parses the table printed by `ctr tasks list`:
+/// ```text
+/// TASK    PID      STATUS
+/// app     12345    RUNNING
+/// ```
+fn parse_tasks(output: &str) -> Vec<Task> {
+    output
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let columns: Vec<&str> = line.split_whitespace().collect();
+            match columns.as_slice() {
+                [container_id, _pid, status] => Some(Task {
+                    container_id: container_id.to_string(),
+                    status: TaskStatus::parse(status),
+                }),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tasks() {
+        let output = "TASK                  PID      STATUS\n\
+                      app                   12345    RUNNING\n\
+                      batch                 0        STOPPED\n";
+        assert_eq!(
+            parse_tasks(output),
+            vec![
+                Task {
+                    container_id: "app".to_string(),
+                    status: TaskStatus::Running,
+                },
+                Task {
+                    container_id: "batch".to_string(),
+                    status: TaskStatus::Stopped,
+                },
+            ]
+        );
+        assert!(parse_tasks("TASK    PID    STATUS\n").is_empty());
+    }
+
+    #[test]
+    fn test_parse_containers() {
+        let output = "CONTAINER    IMAGE                           RUNTIME\n\
+                      app          nvcr.io/nvidia/app:1.0          io.containerd.runc.v2\n";
+        assert_eq!(
+            parse_containers(output),
+            vec![Container {
+                id: "app".to_string(),
+                image: "nvcr.io/nvidia/app:1.0".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_run_args() {
+        let options = RunOptions {
+            image: "nvcr.io/nvidia/app:1.0".to_string(),
+            args: vec!["--port".to_string(), "8080".to_string()],
+            env: vec![("LOG_LEVEL".to_string(), "debug".to_string())],
+            mounts: vec![Mount {
+                source: "/var/log/app".to_string(),
+                destination: "/log".to_string(),
+                read_only: true,
+            }],
+            labels: vec![("extservice-version".to_string(), "2".to_string())],
+            host_network: true,
+            privileged: false,
+        };
+
+        assert_eq!(
+            run_args("app", &options),
+            vec![
+                "run",
+                "--detach",
+                "--net-host",
+                "--env",
+                "LOG_LEVEL=debug",
+                "--mount",
+                "type=bind,src=/var/log/app,dst=/log,options=rbind:ro",
+                "--label",
+                "extservice-version=2",
+                "nvcr.io/nvidia/app:1.0",
+                "app",
+                "--port",
+                "8080",
+            ]
+        );
+    }
+}
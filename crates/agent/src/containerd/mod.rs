@@ -19,6 +19,8 @@ pub use command::bash_command::BashCommand;
 
 pub mod container;
 
+pub mod ctr;
+
 pub mod command;
 
 pub mod image;
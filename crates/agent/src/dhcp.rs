@@ -14,7 +14,7 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
-use std::net::Ipv4Addr;
+use std::net::{Ipv4Addr, Ipv6Addr};
 
 use ::rpc::forge as rpc;
 use gtmpl_derive::Gtmpl;
@@ -47,6 +47,7 @@ pub fn build_server_supervisord_config(
 ) -> Result<String, eyre::Report> {
     let params = TmplDHCServerConfigParameters {
         Interfaces: conf.interfaces,
+        Dhcpv6: conf.dhcpv6,
     };
     gtmpl::template(TMPL_SERVER, params).map_err(|e| e.into())
 }
@@ -60,12 +61,14 @@ pub fn build_server_config(
     pxe_ip: Ipv4Addr,
     ntpservers: Vec<Ipv4Addr>,
     nameservers: Vec<Ipv4Addr>,
+    nameservers_ipv6: Vec<Ipv6Addr>,
     loopback_ip: Ipv4Addr,
 ) -> Result<String, eyre::Report> {
     let dhcp_config = utils::models::dhcp::DhcpConfig::from_forge_dhcp_config(
         pxe_ip,
         ntpservers,
         nameservers,
+        nameservers_ipv6,
         loopback_ip,
     )?;
 
@@ -86,6 +89,8 @@ pub fn build_server_host_config(
 
 pub struct DhcpServerSupervisordConfig {
     pub interfaces: Vec<String>,
+    /// Whether the server also serves DHCPv6 on the interfaces
+    pub dhcpv6: bool,
 }
 
 //
@@ -105,4 +110,5 @@ struct TmplDHCRelayConfigParameters {
 #[derive(Clone, Gtmpl)]
 struct TmplDHCServerConfigParameters {
     Interfaces: Vec<String>,
+    Dhcpv6: bool,
 }
@@ -19,7 +19,7 @@ use std::collections::{BTreeMap, HashMap};
 use std::ffi::CStr;
 use std::fs::File;
 use std::io::Read;
-use std::net::{IpAddr, Ipv4Addr};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::time::Duration;
@@ -1037,9 +1037,10 @@ pub async fn reset(
 // 3. Copy host_config file
 // 4. Reload supervisord
 //
-// This is currently scoped to IPv4 only, and there are
-// a few IPv4-specific checks for things like NTP servers,
-// UEFI HTTP/PXE IP, and nameservers below.
+// This is mostly scoped to IPv4, and there are a few IPv4-specific
+// checks for things like NTP servers, UEFI HTTP/PXE IP, and nameservers
+// below. DHCPv6 is only enabled if the interfaces have IPv6 addresses,
+// in which case the IPv6 nameservers are handed out as well.
 fn write_dhcp_v4_server_config(
     dhcp_relay_path: &FPath,
     dhcp_server_path: &DhcpServerPaths,
@@ -1104,6 +1105,16 @@ fn write_dhcp_v4_server_config(
 
     let loopback_ip = mh_nc.loopback_ip.parse()?;
 
+    let dhcpv6 = if nc.use_admin_network {
+        nc.admin_interface
+            .as_ref()
+            .is_some_and(|interface| interface.ipv6.is_some())
+    } else {
+        nc.tenant_interfaces
+            .iter()
+            .any(|interface| interface.ipv6.is_some())
+    };
+
     // Filter to IPv4, since this is specifically for the DHCPv4 server
     // config, and the input ServiceAddresses holds both families.
     // Again, we'll eventually have a specific builder for a DHCPv6
@@ -1117,6 +1128,15 @@ fn write_dhcp_v4_server_config(
         })
         .collect::<Vec<Ipv4Addr>>();
 
+    let nameservers_v6 = service_addrs
+        .nameservers
+        .iter()
+        .filter_map(|x| match x {
+            IpAddr::V6(x) => Some(*x),
+            _ => None,
+        })
+        .collect::<Vec<Ipv6Addr>>();
+
     let ntpservers_v4 = service_addrs
         .ntpservers
         .iter()
@@ -1139,8 +1159,10 @@ fn write_dhcp_v4_server_config(
 
     let mut has_changes = false;
 
-    let next_contents =
-        dhcp::build_server_supervisord_config(dhcp::DhcpServerSupervisordConfig { interfaces })?;
+    let next_contents = dhcp::build_server_supervisord_config(dhcp::DhcpServerSupervisordConfig {
+        interfaces,
+        dhcpv6,
+    })?;
     match write(
         next_contents,
         &dhcp_server_path.server,
@@ -1155,8 +1177,13 @@ fn write_dhcp_v4_server_config(
         Err(err) => tracing::error!("Write DHCP server {}: {err:#}", dhcp_server_path.server),
     }
 
-    let next_contents =
-        dhcp::build_server_config(pxe_ip_v4, ntpservers_v4, nameservers_v4, loopback_ip)?;
+    let next_contents = dhcp::build_server_config(
+        pxe_ip_v4,
+        ntpservers_v4,
+        nameservers_v4,
+        nameservers_v6,
+        loopback_ip,
+    )?;
     match write(
         next_contents,
         &dhcp_server_path.config,
@@ -1699,7 +1726,7 @@ fn cleanup_new_acls(hbn_root: &Path) {
 mod tests {
     use std::fs;
     use std::io::Write;
-    use std::net::{IpAddr, Ipv4Addr};
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
     use std::path::{Path, PathBuf};
     use std::str::FromStr;
 
@@ -2187,6 +2214,8 @@ mod tests {
             network_security_group: None,
             internal_uuid: None,
             mtu: None,
+            ipv6: None,
+            ipv6_prefix: None,
         };
         assert_eq!(admin_interface.svi_ip, None);
 
@@ -2238,6 +2267,8 @@ mod tests {
                 network_security_group: None,
                 internal_uuid: None,
                 mtu: None,
+                ipv6: None,
+                ipv6_prefix: None,
             },
             rpc::FlatInterfaceConfig {
                 function_type: rpc::InterfaceFunctionType::Physical.into(),
@@ -2411,6 +2442,8 @@ mod tests {
                 },
                 internal_uuid: None,
                 mtu: None,
+                ipv6: None,
+                ipv6_prefix: None,
             },
         ];
 
@@ -2436,6 +2469,8 @@ mod tests {
             asn: 4259912557,
             datacenter_asn: 11414,
             site_global_vpc_vni,
+            fmds_token_required: false,
+            fmds_token_hop_limit: None,
             anycast_site_prefixes: vec!["5.255.255.0/24".to_string()],
             tenant_host_asn: Some(65100),
             common_internal_route_target: Some(rpc_common::RouteTarget {
@@ -2519,6 +2554,7 @@ mod tests {
 
             // For NetworkMonitor
             dpu_network_pinger_type: None,
+            dpu_network_monitor_config: None,
 
             // For ETV:
             network_virtualization_type: None,
@@ -2782,6 +2818,10 @@ mod tests {
         assert_eq!(received.renewal_time_secs, expected.renewal_time_secs);
         assert_eq!(received.rebinding_time_secs, expected.rebinding_time_secs);
         assert_eq!(received.carbide_nameservers, expected.carbide_nameservers);
+        assert_eq!(
+            received.carbide_nameservers_ipv6,
+            expected.carbide_nameservers_ipv6
+        );
         assert_eq!(received.carbide_api_url, expected.carbide_api_url);
         assert_eq!(received.carbide_ntpservers, expected.carbide_ntpservers);
         assert_eq!(
@@ -2840,6 +2880,8 @@ mod tests {
             network_security_group: None,
             internal_uuid: None,
             mtu: None,
+            ipv6: None,
+            ipv6_prefix: None,
         };
 
         let mut admin_interface_with_mtu = admin_interface.clone();
@@ -2875,6 +2917,8 @@ mod tests {
                 network_security_group: None,
                 internal_uuid: None,
                 mtu: None,
+                ipv6: None,
+                ipv6_prefix: None,
             },
             rpc::FlatInterfaceConfig {
                 function_type: rpc::InterfaceFunctionType::Physical.into(),
@@ -2899,6 +2943,8 @@ mod tests {
                 network_security_group: None,
                 internal_uuid: None,
                 mtu: None,
+                ipv6: None,
+                ipv6_prefix: None,
             },
         ];
 
@@ -2926,10 +2972,13 @@ mod tests {
             rebinding_time_secs: 432000,
             carbide_api_url: None,
             carbide_dhcp_server: Ipv4Addr::from([10, 217, 5, 39]),
+            carbide_nameservers_ipv6: vec![],
         };
 
         let mut network_config = rpc::ManagedHostNetworkConfigResponse {
             site_global_vpc_vni: None,
+            fmds_token_required: false,
+            fmds_token_hop_limit: None,
             asn: 4259912557,
             datacenter_asn: 11414,
             common_internal_route_target: Some(rpc_common::RouteTarget {
@@ -2977,6 +3026,7 @@ mod tests {
             remote_id: "test".to_string(),
 
             dpu_network_pinger_type: None,
+            dpu_network_monitor_config: None,
 
             network_virtualization_type: None,
             vpc_vni: None,
@@ -3050,6 +3100,7 @@ mod tests {
         }
         let dhcp_contents = super::read_limited(g.path())?;
         assert!(dhcp_contents.contains("vlan1"));
+        assert!(!dhcp_contents.contains("--dhcpv6"));
 
         let dhcp_config_received: DhcpConfig =
             serde_yaml::from_str(&super::read_limited(h.path())?)?;
@@ -3075,10 +3126,17 @@ mod tests {
             dhcp::build_server_host_config(network_config2, &HBNDeviceNames::pre_23())?;
         assert!(host_config_str.contains("mtu: 1500"));
 
+        // DHCPv6 is served once an interface has an IPv6 address
+        network_config.tenant_interfaces[0].ipv6 = Some("2001:db8:5::170".to_string());
+        network_config.tenant_interfaces[0].ipv6_prefix = Some("2001:db8:5::/64".to_string());
+
         let service_addrs = ServiceAddresses {
             pxe_ips: vec![IpAddr::from([10, 0, 0, 1])],
             ntpservers: vec![],
-            nameservers: vec![IpAddr::from([10, 1, 1, 1])],
+            nameservers: vec![
+                IpAddr::from([10, 1, 1, 1]),
+                IpAddr::from_str("2001:db8::53").unwrap(),
+            ],
         };
         match super::write_dhcp_v4_server_config(
             &fp,
@@ -3110,10 +3168,12 @@ mod tests {
             rebinding_time_secs: 432000,
             carbide_api_url: None,
             carbide_dhcp_server: Ipv4Addr::from([10, 217, 5, 39]),
+            carbide_nameservers_ipv6: vec![Ipv6Addr::from_str("2001:db8::53").unwrap()],
         };
         let dhcp_contents = super::read_limited(g.path())?;
         assert!(dhcp_contents.contains("vlan196"));
         assert!(dhcp_contents.contains("vlan185"));
+        assert!(dhcp_contents.contains("--dhcpv6"));
 
         let dhcp_config_received: DhcpConfig =
             serde_yaml::from_str(&super::read_limited(h.path())?)?;
@@ -3142,6 +3202,8 @@ mod tests {
         };
         let network_config = rpc::ManagedHostNetworkConfigResponse {
             site_global_vpc_vni: None,
+            fmds_token_required: false,
+            fmds_token_hop_limit: None,
             asn: 4259912557,
             datacenter_asn: 11414,
             common_internal_route_target: None,
@@ -3162,6 +3224,7 @@ mod tests {
             instance_id: None,
             remote_id: "test".to_string(),
             dpu_network_pinger_type: None,
+            dpu_network_monitor_config: None,
             network_virtualization_type: None,
             vpc_vni: None,
             route_servers: vec![],
@@ -0,0 +1,562 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::HashMap;
+
+use ::rpc::forge as rpc;
+use async_trait::async_trait;
+use eyre::{Result, WrapErr};
+use model::extension_service::container::{ContainerRestartPolicy, ContainerSpec};
+
+use super::service_handler::{
+    CredentialType, ExtensionServiceHandler, ServiceConfig, deployment_name, parse_deployment_name,
+};
+use crate::containerd::ctr::{self, Container, Ctr, TaskStatus};
+use crate::extension_services::dpu_extension_service_observability;
+
+// containerd namespace of the extension service containers, which keeps them apart from the
+// containers managed by kubelet
+const CONTAINER_NAMESPACE: &str = "extservice";
+
+// For identifying the container in ctr
+const CONTAINER_LABEL_ID: &str = "extservice-id";
+const CONTAINER_LABEL_VER: &str = "extservice-version";
+
+// The registry of image references which don't name one
+const DEFAULT_REGISTRY: &str = "docker.io";
+
+/// The containers and tasks of the extension service namespace, as listed once per update
+#[derive(Debug)]
+struct ContainerListing {
+    containers: HashMap<String, Container>,
+    tasks: HashMap<String, TaskStatus>,
+}
+
+/// Handler for CONTAINER extension services, which runs each service as a single container
+/// through containerd
+pub struct ContainerServicesHandler {
+    ctr: Ctr,
+    /// The state of the containers as of the last update, which service statuses are reported
+    /// from. `None` if the containers could not be listed.
+    listing: Option<ContainerListing>,
+    /// Map of (service_id, version) to error message if deployment/teardown hit issues
+    pub service_errors: HashMap<(String, u64), String>,
+}
+
+impl Default for ContainerServicesHandler {
+    fn default() -> Self {
+        Self {
+            ctr: Ctr::new(CONTAINER_NAMESPACE),
+            listing: None,
+            service_errors: HashMap::new(),
+        }
+    }
+}
+
+impl ContainerServicesHandler {
+    fn parse_spec(service: &ServiceConfig) -> Result<ContainerSpec> {
+        serde_yaml::from_str(&service.data).wrap_err("Invalid container spec")
+    }
+
+    fn run_options(service: &ServiceConfig, spec: ContainerSpec) -> ctr::RunOptions {
+        ctr::RunOptions {
+            image: spec.image,
+            args: spec.args,
+            env: spec.env.into_iter().collect(),
+            mounts: spec
+                .mounts
+                .into_iter()
+                .map(|m| ctr::Mount {
+                    source: m.source,
+                    destination: m.destination,
+                    read_only: m.read_only,
+                })
+                .collect(),
+            labels: vec![
+                (CONTAINER_LABEL_ID.to_string(), service.id.to_string()),
+                (
+                    CONTAINER_LABEL_VER.to_string(),
+                    service.version.version_nr().to_string(),
+                ),
+            ],
+            host_network: spec.host_network,
+            privileged: spec.privileged,
+        }
+    }
+
+    /// The registry credential of the service, if its image is hosted by the registry of the
+    /// credential. The host of the registry URL has to match the registry of the image exactly,
+    /// so that the credential is not sent to a different registry.
+    fn registry_credential<'a>(
+        service: &'a ServiceConfig,
+        image: &str,
+    ) -> Option<(&'a str, &'a str)> {
+        let credential = service.credential.as_ref()?;
+        let registry = credential
+            .registry_url
+            .trim_start_matches("https://")
+            .trim_start_matches("http://");
+        let registry = registry.split('/').next().unwrap_or(registry);
+        if !registry.eq_ignore_ascii_case(Self::image_registry(image)) {
+            return None;
+        }
+        match &credential.credential_type {
+            CredentialType::UsernamePassword(up) => Some((&up.username, &up.password)),
+        }
+    }
+
+    /// The registry host of an image reference. References without a registry, e.g.
+    /// `library/nginx:1.27`, are pulled from Docker Hub.
+    fn image_registry(image: &str) -> &str {
+        match image.split_once('/') {
+            Some((host, _)) if host.contains(['.', ':']) || host == "localhost" => host,
+            _ => DEFAULT_REGISTRY,
+        }
+    }
+
+    /// Split an image reference into the image and its tag,
+    /// e.g. `nvcr.io/nvidia/app:1.0` into `nvcr.io/nvidia/app` and `1.0`
+    fn split_image(image: &str) -> (String, String) {
+        match image.rsplit_once(':') {
+            // A colon followed by a path is the port of the registry, not a tag
+            Some((name, tag)) if !tag.contains('/') => (name.to_string(), tag.to_string()),
+            _ => (image.to_string(), String::new()),
+        }
+    }
+
+    async fn deploy(
+        &self,
+        service: &ServiceConfig,
+        spec: ContainerSpec,
+        container_id: &str,
+    ) -> Result<()> {
+        self.ctr
+            .pull(&spec.image, Self::registry_credential(service, &spec.image))
+            .await
+            .wrap_err_with(|| format!("Failed to pull image {}", spec.image))?;
+
+        let options = Self::run_options(service, spec);
+        self.ctr
+            .run(container_id, &options)
+            .await
+            .wrap_err("Failed to run container")
+    }
+
+    async fn list(&self) -> Result<ContainerListing> {
+        let containers = self
+            .ctr
+            .containers()
+            .await?
+            .into_iter()
+            .filter(|c| parse_deployment_name(&c.id).is_some())
+            .map(|c| (c.id.clone(), c))
+            .collect();
+        let tasks = self
+            .ctr
+            .tasks()
+            .await?
+            .into_iter()
+            .map(|task| (task.container_id, task.status))
+            .collect();
+        Ok(ContainerListing { containers, tasks })
+    }
+
+    /// Run containers for new services, start containers without a task (e.g. after a reboot of
+    /// the DPU), restart stopped containers according to their restart policy and remove the
+    /// containers of services which are no longer active.
+    ///
+    /// The listing is updated with the changes, so that services whose container was just run or
+    /// started are reported as pending until the next update.
+    async fn reconcile_containers(
+        &mut self,
+        listing: &mut ContainerListing,
+        new_active: &[ServiceConfig],
+    ) {
+        for service in new_active {
+            let version = service.version.version_nr();
+            let container_id = deployment_name(&service.id, version);
+
+            let result = match Self::parse_spec(service) {
+                Ok(spec) => {
+                    self.reconcile_container(listing, service, spec, &container_id)
+                        .await
+                }
+                Err(e) => Err(e),
+            };
+
+            if let Err(e) = result {
+                self.service_errors
+                    .insert((service.id.to_string(), version), format!("{e:#}"));
+            }
+        }
+
+        let removed: Vec<String> = listing
+            .containers
+            .keys()
+            .filter(|container_id| {
+                !new_active
+                    .iter()
+                    .any(|s| deployment_name(&s.id, s.version.version_nr()) == **container_id)
+            })
+            .cloned()
+            .collect();
+        for container_id in removed {
+            let Some((service_id, version)) = parse_deployment_name(&container_id) else {
+                continue;
+            };
+
+            match self.ctr.remove(&container_id).await {
+                Ok(()) => {
+                    listing.containers.remove(&container_id);
+                    listing.tasks.remove(&container_id);
+                }
+                Err(e) => {
+                    self.service_errors
+                        .insert((service_id.to_string(), version), format!("{e:#}"));
+                }
+            }
+        }
+    }
+
+    async fn reconcile_container(
+        &self,
+        listing: &mut ContainerListing,
+        service: &ServiceConfig,
+        spec: ContainerSpec,
+        container_id: &str,
+    ) -> Result<()> {
+        if !listing.containers.contains_key(container_id) {
+            let image = spec.image.clone();
+            self.deploy(service, spec, container_id).await?;
+            listing.containers.insert(
+                container_id.to_string(),
+                Container {
+                    id: container_id.to_string(),
+                    image,
+                },
+            );
+            return Ok(());
+        }
+
+        match listing.tasks.get(container_id) {
+            None => self
+                .ctr
+                .start(container_id)
+                .await
+                .wrap_err("Failed to start container"),
+            Some(TaskStatus::Stopped) if spec.restart_policy == ContainerRestartPolicy::Always => {
+                self.ctr
+                    .restart(container_id)
+                    .await
+                    .wrap_err("Failed to restart container")?;
+                listing.tasks.remove(container_id);
+                Ok(())
+            }
+            // Containers which stopped without a restart policy are reported as failed
+            Some(_) => Ok(()),
+        }
+    }
+
+    async fn update_services(&mut self, services: &[ServiceConfig]) -> Result<()> {
+        // Clear any previous errors since we are starting a new update
+        self.service_errors.clear();
+
+        let active_services: Vec<ServiceConfig> = services
+            .iter()
+            .filter(|s| s.removed.is_none())
+            .cloned()
+            .collect();
+
+        // The containers are listed once per update, both to reconcile them and to report the
+        // status of the services
+        let mut listing = match self.list().await {
+            Ok(listing) => listing,
+            Err(e) => {
+                self.listing = None;
+                return Err(eyre::eyre!("Failed to list containers: {}", e));
+            }
+        };
+        self.reconcile_containers(&mut listing, &active_services)
+            .await;
+        self.listing = Some(listing);
+
+        dpu_extension_service_observability::reconcile(services)
+            .await
+            .map_err(|e| eyre::eyre!("Failed to reconcile metrics collection: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Determine the status of the service from the state of its container.
+    ///
+    /// Rules:
+    /// - When expected to be deployed:
+    ///     - no container, or a container without task -> PENDING
+    ///     - RUNNING -> RUNNING
+    ///     - STOPPED -> ERROR, as the container is only left stopped without a restart policy
+    ///       or if restarting it failed
+    ///     - CREATED or PAUSED -> PENDING
+    /// - When NOT expected to be deployed:
+    ///     - no container -> TERMINATED
+    ///     - any remaining container -> TERMINATING
+    /// - UNKNOWN task status -> UNKNOWN
+    fn aggregate_status(
+        container_exists: bool,
+        task_status: Option<&TaskStatus>,
+        expected_deploy: bool,
+    ) -> rpc::DpuExtensionServiceDeploymentStatus {
+        use ::rpc::forge::DpuExtensionServiceDeploymentStatus as Status;
+
+        if task_status == Some(&TaskStatus::Unknown) {
+            return Status::DpuExtensionServiceUnknown;
+        }
+
+        match (expected_deploy, container_exists, task_status) {
+            (true, true, Some(TaskStatus::Running)) => Status::DpuExtensionServiceRunning,
+            (true, true, Some(TaskStatus::Stopped)) => Status::DpuExtensionServiceError,
+            (true, _, _) => Status::DpuExtensionServicePending,
+            (false, false, _) => Status::DpuExtensionServiceTerminated,
+            (false, true, _) => Status::DpuExtensionServiceTerminating,
+        }
+    }
+
+    async fn get_container_status(
+        &self,
+        service: &ServiceConfig,
+    ) -> Result<rpc::DpuExtensionServiceStatusObservation> {
+        let expected_deploy = service.removed.is_none();
+        let version = service.version.version_nr();
+        let container_id = deployment_name(&service.id, version);
+
+        let listing = self
+            .listing
+            .as_ref()
+            .ok_or_else(|| eyre::eyre!("The containers of the DPU could not be listed"))?;
+        let container = listing.containers.get(&container_id).cloned();
+        let task_status = listing.tasks.get(&container_id).cloned();
+
+        let state_enum =
+            Self::aggregate_status(container.is_some(), task_status.as_ref(), expected_deploy);
+
+        let status = task_status
+            .as_ref()
+            .map(TaskStatus::as_str)
+            .unwrap_or("NO_TASK");
+        let components = container
+            .map(|c| {
+                let (url, version) = Self::split_image(&c.image);
+                vec![rpc::DpuExtensionServiceComponent {
+                    name: container_id.clone(),
+                    version,
+                    url,
+                    status: status.to_string(),
+                }]
+            })
+            .unwrap_or_default();
+
+        let message = match self.service_errors.get(&(service.id.to_string(), version)) {
+            Some(e) => e.to_string(),
+            None if components.is_empty() => "No container found".to_string(),
+            None => format!("task state: {status}"),
+        };
+
+        Ok(rpc::DpuExtensionServiceStatusObservation {
+            service_id: service.id.to_string(),
+            service_type: service.service_type as i32,
+            service_name: service.id.to_string(),
+            version: service.version.to_string(),
+            removed: service.removed.clone(),
+            state: state_enum as i32,
+            components,
+            message,
+        })
+    }
+}
+
+#[async_trait]
+impl ExtensionServiceHandler for ContainerServicesHandler {
+    async fn update_active_services(&mut self, services: &[ServiceConfig]) -> Result<()> {
+        if let Err(e) = self.update_services(services).await {
+            tracing::error!("Failed to update active container services: {}", e);
+        }
+        Ok(())
+    }
+
+    async fn get_service_status(
+        &self,
+        service: &ServiceConfig,
+    ) -> Result<rpc::DpuExtensionServiceStatusObservation> {
+        match self.get_container_status(service).await {
+            Ok(status) => Ok(status),
+            Err(e) => Ok(rpc::DpuExtensionServiceStatusObservation {
+                service_id: service.id.to_string(),
+                service_type: service.service_type as i32,
+                service_name: service.id.to_string(),
+                version: service.version.to_string(),
+                removed: service.removed.clone(),
+                state: rpc::DpuExtensionServiceDeploymentStatus::DpuExtensionServiceError as i32,
+                components: Vec::new(),
+                message: e.to_string(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::rpc::forge::DpuExtensionServiceDeploymentStatus as Status;
+    use config_version::ConfigVersion;
+
+    use super::*;
+    use crate::extension_services::service_handler::{ServiceCredential, UsernamePassword};
+
+    fn service(data: &str) -> ServiceConfig {
+        ServiceConfig {
+            id: uuid::Uuid::new_v4(),
+            name: "app".to_string(),
+            service_type: rpc::DpuExtensionServiceType::Container,
+            version: ConfigVersion::initial(),
+            removed: None,
+            data: data.to_string(),
+            credential: None,
+            observability: None,
+        }
+    }
+
+    #[test]
+    fn test_container_handler_run_options() {
+        let service = service(
+            "image: nvcr.io/nvidia/app:1.0\nargs: [\"--port\", \"8080\"]\nenv:\n  LOG_LEVEL: debug\nmounts:\n  - source: /var/log/app\n    destination: /log\n    read_only: true\n",
+        );
+        let spec = ContainerServicesHandler::parse_spec(&service).unwrap();
+        let options = ContainerServicesHandler::run_options(&service, spec);
+
+        assert_eq!(options.image, "nvcr.io/nvidia/app:1.0");
+        assert_eq!(options.args, vec!["--port", "8080"]);
+        assert_eq!(
+            options.env,
+            vec![("LOG_LEVEL".to_string(), "debug".to_string())]
+        );
+        assert_eq!(
+            options.mounts,
+            vec![ctr::Mount {
+                source: "/var/log/app".to_string(),
+                destination: "/log".to_string(),
+                read_only: true,
+            }]
+        );
+        assert_eq!(
+            options.labels,
+            vec![
+                (CONTAINER_LABEL_ID.to_string(), service.id.to_string()),
+                (CONTAINER_LABEL_VER.to_string(), "1".to_string()),
+            ]
+        );
+        // Host networking is the default
+        assert!(options.host_network);
+        assert!(!options.privileged);
+
+        // Unknown fields are rejected
+        assert!(
+            ContainerServicesHandler::parse_spec(&service("image: app:1.0\nports: [80]")).is_err()
+        );
+    }
+
+    #[test]
+    fn test_container_handler_registry_credential() {
+        let mut service = service("image: nvcr.io/nvidia/app:1.0");
+        service.credential = Some(ServiceCredential {
+            registry_url: "https://nvcr.io/nvidia".to_string(),
+            credential_type: CredentialType::UsernamePassword(UsernamePassword {
+                username: "user".to_string(),
+                password: "secret".to_string(),
+            }),
+        });
+
+        assert_eq!(
+            ContainerServicesHandler::registry_credential(&service, "nvcr.io/nvidia/app:1.0"),
+            Some(("user", "secret"))
+        );
+        assert_eq!(
+            ContainerServicesHandler::registry_credential(&service, "docker.io/library/nginx:1.27"),
+            None
+        );
+        // The registry has to match exactly, not just be a prefix of the image
+        assert_eq!(
+            ContainerServicesHandler::registry_credential(&service, "nvcr.io.example.com/app:1.0"),
+            None
+        );
+        assert_eq!(
+            ContainerServicesHandler::registry_credential(&service, "nvcr.io:5000/app:1.0"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_container_handler_image_registry() {
+        let registry = ContainerServicesHandler::image_registry;
+
+        assert_eq!(registry("nvcr.io/nvidia/app:1.0"), "nvcr.io");
+        assert_eq!(registry("registry:5000/app"), "registry:5000");
+        assert_eq!(registry("localhost/app"), "localhost");
+        assert_eq!(registry("library/nginx:1.27"), "docker.io");
+        assert_eq!(registry("nginx"), "docker.io");
+    }
+
+    #[test]
+    fn test_container_handler_split_image() {
+        assert_eq!(
+            ContainerServicesHandler::split_image("nvcr.io/nvidia/app:1.0"),
+            ("nvcr.io/nvidia/app".to_string(), "1.0".to_string())
+        );
+        assert_eq!(
+            ContainerServicesHandler::split_image("registry:5000/app"),
+            ("registry:5000/app".to_string(), "".to_string())
+        );
+    }
+
+    #[test]
+    fn test_container_handler_aggregate_status() {
+        let status = ContainerServicesHandler::aggregate_status;
+
+        assert_eq!(
+            status(false, None, true),
+            Status::DpuExtensionServicePending
+        );
+        assert_eq!(status(true, None, true), Status::DpuExtensionServicePending);
+        assert_eq!(
+            status(true, Some(&TaskStatus::Running), true),
+            Status::DpuExtensionServiceRunning
+        );
+        assert_eq!(
+            status(true, Some(&TaskStatus::Stopped), true),
+            Status::DpuExtensionServiceError
+        );
+        assert_eq!(
+            status(true, Some(&TaskStatus::Unknown), true),
+            Status::DpuExtensionServiceUnknown
+        );
+        assert_eq!(
+            status(true, Some(&TaskStatus::Running), false),
+            Status::DpuExtensionServiceTerminating
+        );
+        assert_eq!(
+            status(false, None, false),
+            Status::DpuExtensionServiceTerminated
+        );
+    }
+}
@@ -16,6 +16,7 @@
  */
 
 use std::collections::HashSet;
+use std::path::PathBuf;
 
 use eyre::WrapErr;
 use gtmpl_derive::Gtmpl;
@@ -24,9 +25,16 @@ use rpc::forge as rpc_forge;
 use sha2::{Digest, Sha256};
 use uuid::Uuid;
 
+use super::service_handler::ServiceConfig;
+
 // Path to the OTEL config validator.
 const OTEL_CONTRIB_VALIDATE_BIN: &str = "/etc/otelcol-contrib/otelcol-wrapper-validate";
 
+// Path for extension services OTEL config files
+const OTEL_CONTRIB_DPU_EXT_PATH: &str = "/etc/otelcol-contrib/config-fragments";
+const OTEL_CONTRIB_SERVICE: &str = "otelcol-contrib.service";
+const MAX_OBSERVABILITY_CONFIG_PER_SERVICE: usize = 20;
+
 const TMPL_OTEL: &str = include_str!("../../templates/dpu_extension_service_observability.tmpl");
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -142,6 +150,101 @@ pub fn build(
     })
 }
 
+/// Reconcile the DPU OTEL metrics collection config based, adding/updating config
+/// for new/existing services and removing config for inactive services.
+/// If no config is provided for a service, any existing metrics config will be removed.
+/// Shared by the handlers of all extension service types.
+pub async fn reconcile(services: &[ServiceConfig]) -> eyre::Result<()> {
+    let mut changed = false;
+
+    // Loop through the items in services.
+    for service in services {
+        let config_path = PathBuf::from(format!("{OTEL_CONTRIB_DPU_EXT_PATH}/{}.yaml", service.id));
+        let tmp_path = config_path.with_extension("TMP");
+
+        if let Some(observability) = service.observability.as_ref() {
+            // Check if the service is marked removed or has no metrics config
+            if service.removed.is_some() || observability.configs.is_empty() {
+                // Check if a config file exists
+                if std::fs::exists(config_path.clone())? {
+                    // If so, flag that we're changing something and remove the file.
+                    changed = true;
+                    std::fs::remove_file(config_path)?;
+                }
+            } else if observability.configs.len() > MAX_OBSERVABILITY_CONFIG_PER_SERVICE {
+                tracing::error!(
+                    "number of observability configs for service `{}` exceeds the limit of {MAX_OBSERVABILITY_CONFIG_PER_SERVICE}",
+                    service.id
+                );
+
+                // We protect against this case in the API layer, so this case,
+                // _should_ never be hit, but we need to do whatever we can to
+                // prevent user-config from blocking the rest of our DPU loop.
+                // Config count that exceeds our imposed limit isn't a systemic
+                // failure (nothing is wrong with the DPU), so we should log and
+                // remove the config.  The user will then need to fix their config
+                // to get their metrics again.
+                changed = true;
+                std::fs::remove_file(config_path)?;
+            } else {
+                // If the service is active and has metrics config, loop through
+                // and generate a tmp config file.
+                let contents = build(service.id, service.name.to_owned(), observability)?;
+
+                std::fs::write(&tmp_path, contents.clone())
+                    .wrap_err_with(|| format!("fs::write {}", tmp_path.display()))?;
+
+                // If no config file already exists, move temp to active and mark changed.
+                if !std::fs::exists(config_path.clone())? {
+                    std::fs::rename(tmp_path, config_path).wrap_err("rename")?;
+                    changed = true;
+                } else {
+                    // Read in the current config
+                    let current = std::fs::read_to_string(config_path.clone())
+                        .wrap_err("read current config")?;
+                    // If there was no change, nothing to do so just clean-up.
+                    if contents == current {
+                        std::fs::remove_file(&tmp_path).wrap_err("remove temp metrics config")?;
+                    } else {
+                        // If there was a change, move tmp to current
+                        std::fs::rename(tmp_path, config_path).wrap_err("rename")?;
+                        changed = true;
+                    }
+                }
+            }
+        } else {
+            // Check if a config file exists
+            if std::fs::exists(config_path.clone())? {
+                // If so, flag that we're changing something and remove the file.
+                changed = true;
+                std::fs::remove_file(config_path)?;
+            }
+        }
+    }
+
+    // If there were changes, restart the otel service.
+    if changed {
+        // We intentionally turn validation failure into a non-fatal
+        // event and continue on to give users a strong signal (their
+        // metrics break) in the event that they've crafted config
+        // that passes the validation at our API layer but managed
+        // to be rejected by otel.
+        // The otel service wrapper itself will validate the combined
+        // config (base + config fragments) and ignore all extension
+        // config if validation fails.
+        // We'll still get _our_ base metrics if the user submited bad
+        // config, but the user will lose theirs until they fix their
+        // config.
+        if !validate().await? {
+            tracing::error!("extension service observability configs failed validation")
+        }
+
+        carbide_systemd::unit::restart(OTEL_CONTRIB_SERVICE).await?;
+    }
+
+    Ok(())
+}
+
 // Validate the config
 pub async fn validate() -> eyre::Result<bool> {
     let mut cmd = tokio::process::Command::new(OTEL_CONTRIB_VALIDATE_BIN);
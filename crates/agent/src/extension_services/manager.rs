@@ -18,8 +18,10 @@ use std::collections::HashMap;
 
 use ::rpc::forge::{self as rpc, DpuExtensionServiceType};
 
+use super::container_handler::ContainerServicesHandler;
 use super::k8s_pod_handler::KubernetesPodServicesHandler;
 use super::service_handler::{ExtensionServiceHandler, ServiceConfig};
+use super::systemd_unit_handler::SystemdUnitServicesHandler;
 
 /// Manager for all extension services on the DPU
 ///
@@ -35,6 +37,14 @@ impl Default for ExtensionServiceManager {
             DpuExtensionServiceType::KubernetesPod,
             Box::new(KubernetesPodServicesHandler::default()) as Box<dyn ExtensionServiceHandler>,
         );
+        service_handlers.insert(
+            DpuExtensionServiceType::Container,
+            Box::new(ContainerServicesHandler::default()) as Box<dyn ExtensionServiceHandler>,
+        );
+        service_handlers.insert(
+            DpuExtensionServiceType::SystemdUnit,
+            Box::new(SystemdUnitServicesHandler::default()) as Box<dyn ExtensionServiceHandler>,
+        );
 
         Self { service_handlers }
     }
@@ -14,9 +14,11 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+pub mod container_handler;
 pub mod dpu_extension_service_observability;
 pub mod k8s_pod_handler;
 pub mod manager;
 pub mod service_handler;
+pub mod systemd_unit_handler;
 
 pub use manager::ExtensionServiceManager;
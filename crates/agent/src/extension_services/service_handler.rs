@@ -90,6 +90,25 @@ impl TryFrom<rpc::ManagedHostDpuExtensionServiceConfig> for ServiceConfig {
     }
 }
 
+// Prefix of the names under which services are deployed, e.g. container IDs or unit names
+const DEPLOYMENT_NAME_PREFIX: &str = "extservice";
+
+/// The name under which a version of a service is deployed on the DPU, which allows to
+/// recognize deployments which are no longer desired
+pub fn deployment_name(service_id: &uuid::Uuid, version: u64) -> String {
+    format!("{DEPLOYMENT_NAME_PREFIX}_{service_id}_{version}")
+}
+
+/// Parse the service ID and version from a name generated by `deployment_name`
+pub fn parse_deployment_name(name: &str) -> Option<(uuid::Uuid, u64)> {
+    let rest = name.strip_prefix(&format!("{DEPLOYMENT_NAME_PREFIX}_"))?;
+    let (id_str, ver_str) = rest.split_once('_')?;
+    let version = ver_str.parse::<u64>().ok()?;
+    let service_id = uuid::Uuid::parse_str(id_str).ok()?;
+
+    Some((service_id, version))
+}
+
 /// Trait for handling different types of extension services
 #[async_trait]
 pub trait ExtensionServiceHandler: Send + Sync {
@@ -102,3 +121,23 @@ pub trait ExtensionServiceHandler: Send + Sync {
         service: &ServiceConfig,
     ) -> Result<rpc::DpuExtensionServiceStatusObservation>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deployment_name() {
+        let service_id = uuid::Uuid::new_v4();
+        let name = deployment_name(&service_id, 3);
+        assert_eq!(name, format!("extservice_{service_id}_3"));
+        assert_eq!(parse_deployment_name(&name), Some((service_id, 3)));
+
+        assert_eq!(parse_deployment_name("kubelet"), None);
+        assert_eq!(parse_deployment_name("extservice_not-a-uuid_3"), None);
+        assert_eq!(
+            parse_deployment_name(&format!("extservice_{service_id}_latest")),
+            None
+        );
+    }
+}
@@ -0,0 +1,387 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::{HashMap, HashSet};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use ::rpc::forge as rpc;
+use async_trait::async_trait;
+use carbide_systemd::unit::{self, UnitState};
+use eyre::{Result, WrapErr};
+use tokio::fs;
+
+use super::service_handler::{
+    ExtensionServiceHandler, ServiceConfig, deployment_name, parse_deployment_name,
+};
+use crate::extension_services::dpu_extension_service_observability;
+
+// Units in /run are removed on reboot, which is fine since the agent writes them again as part
+// of the next update
+const SYSTEMD_UNIT_DIR: &str = "/run/systemd/system";
+const SYSTEMD_UNIT_SUFFIX: &str = ".service";
+
+/// Handler for SYSTEMD_UNIT extension services, which installs each service as a transient
+/// systemd service unit on the DPU
+pub struct SystemdUnitServicesHandler {
+    unit_dir: PathBuf,
+    /// Map of (service_id, version) to error message if deployment/teardown hit issues
+    pub service_errors: HashMap<(String, u64), String>,
+}
+
+impl Default for SystemdUnitServicesHandler {
+    fn default() -> Self {
+        Self {
+            unit_dir: PathBuf::from(SYSTEMD_UNIT_DIR),
+            service_errors: HashMap::new(),
+        }
+    }
+}
+
+impl SystemdUnitServicesHandler {
+    fn unit_name(service_id: &uuid::Uuid, version: u64) -> String {
+        format!(
+            "{}{SYSTEMD_UNIT_SUFFIX}",
+            deployment_name(service_id, version)
+        )
+    }
+
+    fn unit_path(&self, unit_name: &str) -> PathBuf {
+        self.unit_dir.join(unit_name)
+    }
+
+    /// Write the unit file of the service, replacing it atomically
+    async fn write_unit(&self, path: &Path, data: &str) -> Result<()> {
+        let temp_path = path.with_extension("tmp");
+        fs::write(&temp_path, data)
+            .await
+            .wrap_err_with(|| format!("Failed to write {}", temp_path.display()))?;
+        fs::rename(&temp_path, path)
+            .await
+            .wrap_err_with(|| format!("Failed to rename {}", temp_path.display()))
+    }
+
+    /// The names of all units in the unit directory which were written by this handler
+    async fn installed_units(&self) -> Result<HashSet<String>> {
+        let mut units = HashSet::new();
+        let mut entries = match fs::read_dir(&self.unit_dir).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(units),
+            Err(e) => return Err(e).wrap_err("Failed to read systemd unit directory"),
+        };
+
+        while let Some(entry) = entries.next_entry().await? {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if let Some(deployment) = name.strip_suffix(SYSTEMD_UNIT_SUFFIX)
+                && parse_deployment_name(deployment).is_some()
+            {
+                units.insert(name);
+            }
+        }
+        Ok(units)
+    }
+
+    /// Write the units of new services, remove the units of services which are no longer active
+    /// and start any unit which isn't running yet. Units which failed, e.g. because systemd gave
+    /// up restarting them, are reset and started again.
+    async fn reconcile_units(&mut self, new_active: &[ServiceConfig]) -> Result<()> {
+        let installed = self.installed_units().await?;
+        let mut changed = false;
+
+        let mut desired = HashMap::new();
+        for service in new_active {
+            let version = service.version.version_nr();
+            let unit_name = Self::unit_name(&service.id, version);
+
+            if !installed.contains(&unit_name) {
+                if let Err(e) = self
+                    .write_unit(&self.unit_path(&unit_name), &service.data)
+                    .await
+                {
+                    self.service_errors
+                        .insert((service.id.to_string(), version), format!("{e:#}"));
+                    continue;
+                }
+                changed = true;
+            }
+            desired.insert(unit_name, (service.id.to_string(), version));
+        }
+
+        for unit_name in installed.difference(&desired.keys().cloned().collect()) {
+            // The unit is removed even if it can't be stopped, which leaves it to systemd to
+            // report it as not-found once reloaded
+            if let Err(e) = unit::stop(unit_name).await {
+                tracing::warn!("Failed to stop systemd unit {}: {}", unit_name, e);
+            }
+            match fs::remove_file(self.unit_path(unit_name)).await {
+                Ok(()) => changed = true,
+                Err(e) if e.kind() == ErrorKind::NotFound => changed = true,
+                Err(e) => {
+                    tracing::error!("Failed to remove systemd unit {}: {}", unit_name, e);
+                }
+            }
+        }
+
+        if changed {
+            unit::daemon_reload().await?;
+        }
+
+        for (unit_name, key) in desired {
+            let result = match unit::state(&unit_name).await {
+                Ok(state) if state.active_state == "inactive" => unit::start(&unit_name).await,
+                Ok(state) if state.active_state == "failed" => {
+                    Self::restart_failed(&unit_name).await
+                }
+                Ok(_) => Ok(()),
+                Err(e) => Err(e),
+            };
+            if let Err(e) = result {
+                self.service_errors.insert(key, format!("{e:#}"));
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn restart_failed(unit_name: &str) -> Result<()> {
+        tracing::info!("Restarting failed systemd unit {}", unit_name);
+        unit::reset_failed(unit_name).await?;
+        unit::start(unit_name).await
+    }
+
+    async fn update_services(&mut self, services: &[ServiceConfig]) -> Result<()> {
+        // Clear any previous errors since we are starting a new update
+        self.service_errors.clear();
+
+        let active_services: Vec<ServiceConfig> = services
+            .iter()
+            .filter(|s| s.removed.is_none())
+            .cloned()
+            .collect();
+
+        self.reconcile_units(&active_services)
+            .await
+            .map_err(|e| eyre::eyre!("Failed to reconcile systemd units: {}", e))?;
+
+        dpu_extension_service_observability::reconcile(services)
+            .await
+            .map_err(|e| eyre::eyre!("Failed to reconcile metrics collection: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Determine the status of the service from the state of its unit.
+    ///
+    /// Rules:
+    /// - When expected to be deployed:
+    ///     - unit not loaded -> PENDING
+    ///     - active -> RUNNING
+    ///     - activating, reloading, inactive or deactivating -> PENDING
+    ///     - failed -> ERROR, until the unit is restarted with the next update
+    /// - When NOT expected to be deployed:
+    ///     - unit not loaded, inactive or failed -> TERMINATED
+    ///     - active, activating, reloading or deactivating -> TERMINATING
+    /// - Any other state -> UNKNOWN
+    fn aggregate_status(
+        state: &UnitState,
+        expected_deploy: bool,
+    ) -> rpc::DpuExtensionServiceDeploymentStatus {
+        use ::rpc::forge::DpuExtensionServiceDeploymentStatus as Status;
+
+        if !state.is_loaded() {
+            return if expected_deploy {
+                Status::DpuExtensionServicePending
+            } else {
+                Status::DpuExtensionServiceTerminated
+            };
+        }
+
+        match (expected_deploy, state.active_state.as_str()) {
+            (true, "active") => Status::DpuExtensionServiceRunning,
+            (true, "activating" | "reloading" | "inactive" | "deactivating") => {
+                Status::DpuExtensionServicePending
+            }
+            (true, "failed") => Status::DpuExtensionServiceError,
+            (false, "inactive" | "failed") => Status::DpuExtensionServiceTerminated,
+            (false, "active" | "activating" | "reloading" | "deactivating") => {
+                Status::DpuExtensionServiceTerminating
+            }
+            _ => Status::DpuExtensionServiceUnknown,
+        }
+    }
+
+    async fn get_unit_status(
+        &self,
+        service: &ServiceConfig,
+    ) -> Result<rpc::DpuExtensionServiceStatusObservation> {
+        let version = service.version.version_nr();
+        let unit_name = Self::unit_name(&service.id, version);
+        let state = unit::state(&unit_name).await?;
+
+        let state_enum = Self::aggregate_status(&state, service.removed.is_none());
+
+        let components = if state.is_loaded() {
+            vec![rpc::DpuExtensionServiceComponent {
+                name: unit_name,
+                version: service.version.to_string(),
+                url: String::new(),
+                status: format!("{} ({})", state.active_state, state.sub_state),
+            }]
+        } else {
+            Vec::new()
+        };
+
+        let message = match self.service_errors.get(&(service.id.to_string(), version)) {
+            Some(e) => e.to_string(),
+            None => format!("unit state: {}", state.load_state),
+        };
+
+        Ok(rpc::DpuExtensionServiceStatusObservation {
+            service_id: service.id.to_string(),
+            service_type: service.service_type as i32,
+            service_name: service.id.to_string(),
+            version: service.version.to_string(),
+            removed: service.removed.clone(),
+            state: state_enum as i32,
+            components,
+            message,
+        })
+    }
+}
+
+#[async_trait]
+impl ExtensionServiceHandler for SystemdUnitServicesHandler {
+    async fn update_active_services(&mut self, services: &[ServiceConfig]) -> Result<()> {
+        if let Err(e) = self.update_services(services).await {
+            tracing::error!("Failed to update active systemd unit services: {}", e);
+        }
+        Ok(())
+    }
+
+    async fn get_service_status(
+        &self,
+        service: &ServiceConfig,
+    ) -> Result<rpc::DpuExtensionServiceStatusObservation> {
+        match self.get_unit_status(service).await {
+            Ok(status) => Ok(status),
+            Err(e) => Ok(rpc::DpuExtensionServiceStatusObservation {
+                service_id: service.id.to_string(),
+                service_type: service.service_type as i32,
+                service_name: service.id.to_string(),
+                version: service.version.to_string(),
+                removed: service.removed.clone(),
+                state: rpc::DpuExtensionServiceDeploymentStatus::DpuExtensionServiceError as i32,
+                components: Vec::new(),
+                message: e.to_string(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::rpc::forge::DpuExtensionServiceDeploymentStatus as Status;
+
+    use super::*;
+
+    fn unit_state(load_state: &str, active_state: &str) -> UnitState {
+        UnitState {
+            load_state: load_state.to_string(),
+            active_state: active_state.to_string(),
+            sub_state: String::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_systemd_unit_handler_installed_units() {
+        let dir = tempfile::tempdir().unwrap();
+        let handler = SystemdUnitServicesHandler {
+            unit_dir: dir.path().to_path_buf(),
+            service_errors: HashMap::new(),
+        };
+
+        // A missing unit directory has no units
+        let missing = SystemdUnitServicesHandler {
+            unit_dir: dir.path().join("missing"),
+            service_errors: HashMap::new(),
+        };
+        assert!(missing.installed_units().await.unwrap().is_empty());
+
+        let service_id = uuid::Uuid::new_v4();
+        let unit_name = SystemdUnitServicesHandler::unit_name(&service_id, 3);
+        handler
+            .write_unit(
+                &handler.unit_path(&unit_name),
+                "[Service]\nExecStart=/bin/true\n",
+            )
+            .await
+            .unwrap();
+        // Units which weren't written by the handler are ignored
+        fs::write(dir.path().join("other.service"), "")
+            .await
+            .unwrap();
+
+        assert_eq!(
+            handler.installed_units().await.unwrap(),
+            HashSet::from([unit_name.clone()])
+        );
+        assert_eq!(
+            fs::read_to_string(handler.unit_path(&unit_name))
+                .await
+                .unwrap(),
+            "[Service]\nExecStart=/bin/true\n"
+        );
+    }
+
+    #[test]
+    fn test_systemd_unit_handler_aggregate_status() {
+        let status = SystemdUnitServicesHandler::aggregate_status;
+
+        assert_eq!(
+            status(&unit_state("not-found", "inactive"), true),
+            Status::DpuExtensionServicePending
+        );
+        assert_eq!(
+            status(&unit_state("loaded", "active"), true),
+            Status::DpuExtensionServiceRunning
+        );
+        assert_eq!(
+            status(&unit_state("loaded", "activating"), true),
+            Status::DpuExtensionServicePending
+        );
+        assert_eq!(
+            status(&unit_state("loaded", "failed"), true),
+            Status::DpuExtensionServiceError
+        );
+        assert_eq!(
+            status(&unit_state("loaded", "active"), false),
+            Status::DpuExtensionServiceTerminating
+        );
+        assert_eq!(
+            status(&unit_state("loaded", "failed"), false),
+            Status::DpuExtensionServiceTerminated
+        );
+        assert_eq!(
+            status(&unit_state("not-found", "inactive"), false),
+            Status::DpuExtensionServiceTerminated
+        );
+        assert_eq!(
+            status(&unit_state("loaded", "maintenance"), true),
+            Status::DpuExtensionServiceUnknown
+        );
+    }
+}
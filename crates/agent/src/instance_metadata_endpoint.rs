@@ -14,15 +14,21 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+use std::net::{IpAddr, SocketAddr};
 use std::sync::Arc;
+use std::time::Duration;
 
 use ::rpc::forge_tls_client::ForgeClientConfig;
-use arc_swap::ArcSwapOption;
+use arc_swap::{ArcSwap, ArcSwapOption};
 use async_trait::async_trait;
-use axum::Router;
-use axum::extract::{Path, State};
-use axum::http::StatusCode;
-use axum::routing::{get, post};
+use axum::extract::connect_info::Connected;
+use axum::extract::{ConnectInfo, Path, Request, State};
+use axum::http::{HeaderMap, StatusCode, header};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post, put};
+use axum::serve::IncomingStream;
+use axum::{Extension, Router};
 use carbide_uuid::machine::MachineId;
 use eyre::eyre;
 use forge_dpu_agent_utils::utils::create_forge_client;
@@ -32,7 +38,15 @@ use governor::{Quota, RateLimiter, clock};
 use mockall::automock;
 use nonzero_ext::nonzero;
 use rpc::forge::ManagedHostNetworkConfigResponse;
-
+use socket2::SockRef;
+use tokio::net::TcpListener;
+
+use crate::cloud_init::{
+    HostInterface, NetworkConfigV2, OpenStackMetaData, OpenStackNetworkData, host_interfaces,
+};
+use crate::instance_metadata_token::{
+    MAX_TOKEN_TTL, MetadataTokens, TOKEN_HEADER, TOKEN_TTL_HEADER,
+};
 use crate::periodic_config_fetcher::InstanceMetadata;
 use crate::util::phone_home;
 
@@ -41,6 +55,10 @@ const HOSTNAME_CATEGORY: &str = "hostname";
 const SITENAME_CATEGORY: &str = "sitename";
 const USER_DATA_CATEGORY: &str = "user-data";
 const META_DATA_CATEGORY: &str = "meta-data";
+const NETWORK_CONFIG_CATEGORY: &str = "network-config";
+const VENDOR_DATA_CATEGORY: &str = "vendor-data";
+const LOCAL_HOSTNAME_CATEGORY: &str = "local-hostname";
+const LOCAL_IPV4_CATEGORY: &str = "local-ipv4";
 const GUID: &str = "guid";
 const IB_PARTITION: &str = "partition";
 const LID: &str = "lid";
@@ -51,6 +69,13 @@ const MACHINE_ID_CATEGORY: &str = "machine-id";
 const INSTANCE_ID_CATEGORY: &str = "instance-id";
 const PHONE_HOME_CATEGORY: &str = "phone_home";
 const ASN_CATEGORY: &str = "asn";
+const OPENSTACK_VERSION: &str = "latest";
+const OPENSTACK_META_DATA: &str = "meta_data.json";
+const OPENSTACK_NETWORK_DATA: &str = "network_data.json";
+const OPENSTACK_USER_DATA: &str = "user_data";
+const OPENSTACK_VENDOR_DATA: &str = "vendor_data.json";
+const TOKEN_PATH: &str = "api/token";
+const FORWARDED_FOR_HEADER: &str = "x-forwarded-for";
 
 #[automock]
 #[async_trait]
@@ -61,12 +86,24 @@ pub trait InstanceMetadataRouterState: Sync + Send {
         Option<Arc<InstanceMetadata>>,
         Option<Arc<ManagedHostNetworkConfigResponse>>,
     );
+    /// Site specific cloud-init vendor-data, as configured for the agent
+    fn vendor_data(&self) -> Option<String>;
+    /// Nameservers announced to the host via DHCP
+    fn nameservers(&self) -> Vec<IpAddr>;
+    /// Whether metadata requests must present a session token
+    fn metadata_token_required(&self) -> bool;
+    /// Hop limit of session token responses, so that tokens can't be obtained from beyond the host
+    fn metadata_token_hop_limit(&self) -> Option<u32>;
     async fn phone_home(&self) -> Result<(), eyre::Error>;
 }
 
 pub struct InstanceMetadataRouterStateImpl {
     latest_instance_data: ArcSwapOption<InstanceMetadata>,
     latest_network_config: ArcSwapOption<ManagedHostNetworkConfigResponse>,
+    nameservers: ArcSwap<Vec<IpAddr>>,
+    vendor_data: Option<String>,
+    default_token_hop_limit: Option<u32>,
+    metadata_tokens: Arc<MetadataTokens>,
     machine_id: MachineId,
     forge_api: String,
     forge_client_config: Arc<ForgeClientConfig>,
@@ -90,6 +127,29 @@ impl InstanceMetadataRouterState for InstanceMetadataRouterStateImpl {
         )
     }
 
+    fn vendor_data(&self) -> Option<String> {
+        self.vendor_data.clone()
+    }
+
+    fn nameservers(&self) -> Vec<IpAddr> {
+        self.nameservers.load().as_ref().clone()
+    }
+
+    fn metadata_token_required(&self) -> bool {
+        self.latest_network_config
+            .load()
+            .as_ref()
+            .is_some_and(|config| config.fmds_token_required)
+    }
+
+    fn metadata_token_hop_limit(&self) -> Option<u32> {
+        self.latest_network_config
+            .load()
+            .as_ref()
+            .and_then(|config| config.fmds_token_hop_limit)
+            .or(self.default_token_hop_limit)
+    }
+
     // Phones home to the site controller.
     async fn phone_home(&self) -> Result<(), eyre::Error> {
         match self.outbound_governor.clone().check() {
@@ -116,10 +176,16 @@ impl InstanceMetadataRouterStateImpl {
         machine_id: MachineId,
         forge_api: String,
         forge_client_config: Arc<ForgeClientConfig>,
+        vendor_data: Option<String>,
+        default_token_hop_limit: Option<u32>,
     ) -> Self {
         Self {
             latest_instance_data: ArcSwapOption::new(None),
             latest_network_config: ArcSwapOption::new(None),
+            nameservers: ArcSwap::from_pointee(vec![]),
+            vendor_data,
+            default_token_hop_limit,
+            metadata_tokens: Arc::new(MetadataTokens::default()),
             machine_id,
             forge_api,
             forge_client_config,
@@ -127,9 +193,15 @@ impl InstanceMetadataRouterStateImpl {
         }
     }
 
-    /// Updates the instance metadata that should be served by FMDS
+    /// Updates the instance metadata that should be served by FMDS.
+    /// Session tokens are invalidated once the host is assigned to a different instance.
     pub fn update_instance_data(&self, instance_data: Option<Arc<InstanceMetadata>>) {
-        self.latest_instance_data.store(instance_data);
+        let instance_id =
+            |data: &Option<Arc<InstanceMetadata>>| data.as_ref().and_then(|data| data.instance_id);
+        let previous = self.latest_instance_data.swap(instance_data.clone());
+        if instance_id(&previous) != instance_id(&instance_data) {
+            self.metadata_tokens.clear();
+        }
     }
 
     pub fn update_network_configuration(
@@ -138,11 +210,93 @@ impl InstanceMetadataRouterStateImpl {
     ) {
         self.latest_network_config.store(network_config);
     }
+
+    /// Updates the nameservers which are part of the served network configuration
+    pub fn update_nameservers(&self, nameservers: Vec<IpAddr>) {
+        self.nameservers.store(Arc::new(nameservers));
+    }
+
+    /// The session tokens issued to the host
+    pub fn metadata_tokens(&self) -> Arc<MetadataTokens> {
+        self.metadata_tokens.clone()
+    }
+}
+
+#[derive(Clone)]
+struct TokenCheckState {
+    metadata_router_state: Arc<dyn InstanceMetadataRouterState>,
+    metadata_tokens: Arc<MetadataTokens>,
+}
+
+/// Connection info of the metadata service, which gives handlers access to the socket of the
+/// connection a request arrived on.
+#[derive(Clone)]
+pub struct MetadataConnection {
+    socket: Option<Arc<socket2::Socket>>,
+    peer: SocketAddr,
+}
+
+impl MetadataConnection {
+    /// Sets the IP TTL, or the IPv6 hop limit, of everything sent on the connection from now on
+    fn set_hop_limit(&self, hop_limit: u32) -> std::io::Result<()> {
+        let Some(socket) = &self.socket else {
+            return Err(std::io::Error::other("connection socket is not available"));
+        };
+        match self.peer {
+            SocketAddr::V4(_) => socket.set_ttl_v4(hop_limit),
+            SocketAddr::V6(peer) => {
+                socket.set_unicast_hops_v6(hop_limit)?;
+                // IPv4 peers of a dual-stack listener are sent IPv4 packets
+                if peer.ip().to_ipv4_mapped().is_some() {
+                    socket.set_ttl_v4(hop_limit)?;
+                }
+                Ok(())
+            }
+        }
+    }
 }
 
-pub fn get_fmds_router(metadata_router_state: Arc<dyn InstanceMetadataRouterState>) -> Router {
-    let user_data_router =
-        Router::new().route(&format!("/{USER_DATA_CATEGORY}"), get(get_userdata));
+impl Connected<IncomingStream<'_, TcpListener>> for MetadataConnection {
+    fn connect_info(stream: IncomingStream<'_, TcpListener>) -> Self {
+        let socket = SockRef::from(stream.io())
+            .try_clone()
+            .inspect_err(|err| tracing::warn!("Failed to duplicate metadata connection: {err}"))
+            .ok()
+            .map(Arc::new);
+        Self {
+            socket,
+            peer: *stream.remote_addr(),
+        }
+    }
+}
+
+/// Serves the metadata service on the given listener. The service has to be served this way,
+/// so that the hop limit of token responses can be applied to the connection.
+pub async fn serve_metadata_service(listener: TcpListener, router: Router) -> std::io::Result<()> {
+    axum::serve(
+        listener,
+        router.into_make_service_with_connect_info::<MetadataConnection>(),
+    )
+    .await
+}
+
+/// Serves the metadata in the layout of the EC2 instance metadata service. Metadata requests are
+/// subject to the session token check, and the token endpoint is part of this layout. The files of
+/// the cloud-init NoCloud datasource are served here as well, but that datasource does not obtain
+/// tokens, so it only works for tenants which don't require them.
+pub fn get_fmds_router(
+    metadata_router_state: Arc<dyn InstanceMetadataRouterState>,
+    metadata_tokens: Arc<MetadataTokens>,
+) -> Router {
+    let user_data_router = Router::new()
+        .route(&format!("/{USER_DATA_CATEGORY}"), get(get_userdata))
+        // Together with `meta-data` and `user-data` these make up the files which the cloud-init
+        // NoCloud datasource expects at its seed URL
+        .route(
+            &format!("/{NETWORK_CONFIG_CATEGORY}"),
+            get(get_network_config),
+        )
+        .route(&format!("/{VENDOR_DATA_CATEGORY}"), get(get_vendor_data));
 
     // TODO add handling for non-supported URIs
     let ib_router = Router::new()
@@ -176,12 +330,159 @@ pub fn get_fmds_router(metadata_router_state: Arc<dyn InstanceMetadataRouterStat
         .route(&format!("/{META_DATA_CATEGORY}"), get(get_metadata_params))
         .nest(&format!("/{META_DATA_CATEGORY}"), service_router);
 
+    let token_check = TokenCheckState {
+        metadata_router_state: metadata_router_state.clone(),
+        metadata_tokens,
+    };
+
+    // The token endpoint itself is not subject to the token check
+    let token_router = Router::new()
+        .route(&format!("/{TOKEN_PATH}"), put(put_token))
+        .with_state(token_check.clone());
+
     Router::new()
         .merge(metadata_router)
         .merge(user_data_router)
+        .route_layer(middleware::from_fn_with_state(
+            token_check,
+            check_metadata_token,
+        ))
+        .with_state(metadata_router_state)
+        .merge(token_router)
+}
+
+/// Serves the metadata in the layout of the OpenStack metadata service, as used by the
+/// cloud-init OpenStack datasource. Expected to be nested under `/openstack`.
+/// Requests are subject to the session token check, but the OpenStack datasource does not obtain
+/// tokens, so it only works for tenants which don't require them.
+pub fn get_openstack_router(
+    metadata_router_state: Arc<dyn InstanceMetadataRouterState>,
+    metadata_tokens: Arc<MetadataTokens>,
+) -> Router {
+    let version_router = Router::new()
+        .route("/", get(get_openstack_files))
+        .route(
+            &format!("/{OPENSTACK_META_DATA}"),
+            get(get_openstack_meta_data),
+        )
+        .route(
+            &format!("/{OPENSTACK_NETWORK_DATA}"),
+            get(get_openstack_network_data),
+        )
+        .route(&format!("/{OPENSTACK_USER_DATA}"), get(get_userdata))
+        .route(
+            &format!("/{OPENSTACK_VENDOR_DATA}"),
+            get(get_openstack_vendor_data),
+        );
+
+    Router::new()
+        .route("/", get(get_openstack_versions))
+        .route(&format!("/{OPENSTACK_VERSION}/"), get(get_openstack_files))
+        .nest(&format!("/{OPENSTACK_VERSION}"), version_router)
+        .route_layer(middleware::from_fn_with_state(
+            TokenCheckState {
+                metadata_router_state: metadata_router_state.clone(),
+                metadata_tokens,
+            },
+            check_metadata_token,
+        ))
         .with_state(metadata_router_state)
 }
 
+/// Issues a session token, valid for the number of seconds requested in the TTL header.
+///
+/// Requests which were forwarded by a proxy are refused, so that a token can't be obtained
+/// through an application on the host which can be tricked into forwarding requests.
+/// If a hop limit applies, it is set on the connection before the token is sent, and the
+/// connection is closed afterwards so that no other response is sent with it.
+async fn put_token(
+    State(token_check): State<TokenCheckState>,
+    connection: Option<Extension<ConnectInfo<MetadataConnection>>>,
+    headers: HeaderMap,
+) -> Response {
+    if headers.contains_key(FORWARDED_FOR_HEADER) {
+        return (
+            StatusCode::FORBIDDEN,
+            "forwarded requests can not obtain a metadata token\n".to_string(),
+        )
+            .into_response();
+    }
+
+    let ttl = headers
+        .get(TOKEN_TTL_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<u64>().ok())
+        .map(Duration::from_secs)
+        .filter(|ttl| !ttl.is_zero() && *ttl <= MAX_TOKEN_TTL);
+
+    let Some(ttl) = ttl else {
+        return (
+            StatusCode::BAD_REQUEST,
+            format!(
+                "{TOKEN_TTL_HEADER} must be between 1 and {} seconds\n",
+                MAX_TOKEN_TTL.as_secs()
+            ),
+        )
+            .into_response();
+    };
+
+    let Some(hop_limit) = token_check.metadata_router_state.metadata_token_hop_limit() else {
+        return (StatusCode::OK, token_check.metadata_tokens.issue(ttl)).into_response();
+    };
+    let result = match &connection {
+        Some(Extension(ConnectInfo(connection))) => connection.set_hop_limit(hop_limit),
+        None => Err(std::io::Error::other("connection info is not available")),
+    };
+    if let Err(err) = result {
+        tracing::error!("Failed to set hop limit {hop_limit} of metadata token response: {err}");
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "failed to issue metadata token\n".to_string(),
+        )
+            .into_response();
+    }
+
+    (
+        StatusCode::OK,
+        [(header::CONNECTION, "close")],
+        token_check.metadata_tokens.issue(ttl),
+    )
+        .into_response()
+}
+
+/// Rejects metadata requests with an invalid token, and requests without a token if the tenant
+/// requires one.
+async fn check_metadata_token(
+    State(token_check): State<TokenCheckState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    match request.headers().get(TOKEN_HEADER) {
+        Some(token) => {
+            let valid = token
+                .to_str()
+                .is_ok_and(|token| token_check.metadata_tokens.validate(token));
+            if !valid {
+                return (
+                    StatusCode::UNAUTHORIZED,
+                    "invalid or expired metadata token\n".to_string(),
+                )
+                    .into_response();
+            }
+        }
+        None if token_check.metadata_router_state.metadata_token_required() => {
+            return (
+                StatusCode::UNAUTHORIZED,
+                "a metadata token is required\n".to_string(),
+            )
+                .into_response();
+        }
+        None => {}
+    }
+
+    next.run(request).await
+}
+
 async fn get_metadata_parameter(
     State(state): State<Arc<dyn InstanceMetadataRouterState>>,
     Path(category): Path<String>,
@@ -204,7 +505,10 @@ fn extract_metadata(
     {
         match category.as_str() {
             PUBLIC_IPV4_CATEGORY => (StatusCode::OK, metadata.address.clone()),
-            HOSTNAME_CATEGORY => (StatusCode::OK, metadata.hostname.clone()),
+            HOSTNAME_CATEGORY | LOCAL_HOSTNAME_CATEGORY => {
+                (StatusCode::OK, metadata.hostname.clone())
+            }
+            LOCAL_IPV4_CATEGORY => (StatusCode::OK, metadata.address.clone()),
             SITENAME_CATEGORY => (
                 StatusCode::OK,
                 metadata.sitename.clone().unwrap_or(String::new()),
@@ -277,6 +581,8 @@ async fn get_metadata_params(
         StatusCode::OK,
         [
             HOSTNAME_CATEGORY,
+            LOCAL_HOSTNAME_CATEGORY,
+            LOCAL_IPV4_CATEGORY,
             SITENAME_CATEGORY,
             MACHINE_ID_CATEGORY,
             INSTANCE_ID_CATEGORY,
@@ -286,6 +592,111 @@ async fn get_metadata_params(
     )
 }
 
+/// Reads the host side view of the network interfaces of the instance
+fn read_host_interfaces(
+    state: &Arc<dyn InstanceMetadataRouterState>,
+) -> Result<Vec<HostInterface>, (StatusCode, String)> {
+    let (Some(_metadata), Some(network_config)) = state.read() else {
+        return Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "metadata currently unavailable".to_string(),
+        ));
+    };
+
+    match host_interfaces(&network_config) {
+        Ok(interfaces) => Ok(interfaces),
+        Err(err) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("network configuration unavailable: {err}"),
+        )),
+    }
+}
+
+async fn get_network_config(
+    State(state): State<Arc<dyn InstanceMetadataRouterState>>,
+) -> (StatusCode, String) {
+    let interfaces = match read_host_interfaces(&state) {
+        Ok(interfaces) => interfaces,
+        Err(response) => return response,
+    };
+
+    let network_config = NetworkConfigV2::new(&interfaces, &state.nameservers());
+    match serde_yaml::to_string(&network_config) {
+        Ok(yaml) => (StatusCode::OK, yaml),
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    }
+}
+
+async fn get_vendor_data(
+    State(state): State<Arc<dyn InstanceMetadataRouterState>>,
+) -> (StatusCode, String) {
+    (StatusCode::OK, state.vendor_data().unwrap_or_default())
+}
+
+async fn get_openstack_versions(
+    State(_state): State<Arc<dyn InstanceMetadataRouterState>>,
+) -> (StatusCode, String) {
+    (StatusCode::OK, OPENSTACK_VERSION.to_string())
+}
+
+async fn get_openstack_files(
+    State(_state): State<Arc<dyn InstanceMetadataRouterState>>,
+) -> (StatusCode, String) {
+    (
+        StatusCode::OK,
+        [
+            OPENSTACK_META_DATA,
+            OPENSTACK_NETWORK_DATA,
+            OPENSTACK_USER_DATA,
+            OPENSTACK_VENDOR_DATA,
+        ]
+        .join("\n"),
+    )
+}
+
+async fn get_openstack_meta_data(
+    State(state): State<Arc<dyn InstanceMetadataRouterState>>,
+) -> (StatusCode, String) {
+    let Some(metadata) = state.read().0 else {
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "metadata currently unavailable".to_string(),
+        );
+    };
+
+    to_json_response(&OpenStackMetaData::from(metadata.as_ref()))
+}
+
+async fn get_openstack_network_data(
+    State(state): State<Arc<dyn InstanceMetadataRouterState>>,
+) -> (StatusCode, String) {
+    match read_host_interfaces(&state) {
+        Ok(interfaces) => to_json_response(&OpenStackNetworkData::new(
+            &interfaces,
+            &state.nameservers(),
+        )),
+        Err(response) => response,
+    }
+}
+
+async fn get_openstack_vendor_data(
+    State(state): State<Arc<dyn InstanceMetadataRouterState>>,
+) -> (StatusCode, String) {
+    // cloud-init picks the vendor-data from the `cloud-init` key
+    let vendor_data = match state.vendor_data() {
+        Some(vendor_data) => serde_json::json!({ "cloud-init": vendor_data }),
+        None => serde_json::json!({}),
+    };
+    to_json_response(&vendor_data)
+}
+
+fn to_json_response<T: serde::Serialize>(value: &T) -> (StatusCode, String) {
+    match serde_json::to_string(value) {
+        Ok(json) => (StatusCode::OK, json),
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    }
+}
+
 async fn get_devices(
     State(state): State<Arc<dyn InstanceMetadataRouterState>>,
 ) -> (StatusCode, String) {
@@ -484,21 +895,89 @@ mod tests {
             .expect_read()
             .times(2)
             .return_const((metadata.clone(), network_config.clone()));
+        mock_router_state
+            .expect_metadata_token_required()
+            .return_const(false);
 
         let arc_mock_router_state = Arc::new(mock_router_state);
 
-        let router = get_fmds_router(arc_mock_router_state);
+        serve(get_fmds_router(
+            arc_mock_router_state,
+            Arc::new(MetadataTokens::default()),
+        ))
+        .await
+    }
+
+    /// Serves the FMDS and OpenStack layouts, for tests of the cloud-init documents
+    async fn setup_cloud_init_server(
+        metadata: InstanceMetadata,
+        network_config: ManagedHostNetworkConfigResponse,
+        vendor_data: Option<String>,
+    ) -> (tokio::task::JoinHandle<()>, u16) {
+        let mut mock_router_state = MockInstanceMetadataRouterState::new();
+        mock_router_state
+            .expect_read()
+            .return_const((Some(Arc::new(metadata)), Some(Arc::new(network_config))));
+        mock_router_state
+            .expect_vendor_data()
+            .return_const(vendor_data);
+        mock_router_state
+            .expect_nameservers()
+            .return_const(vec!["10.0.0.2".parse::<IpAddr>().unwrap()]);
+        mock_router_state
+            .expect_metadata_token_required()
+            .return_const(false);
+
+        let arc_mock_router_state: Arc<dyn InstanceMetadataRouterState> =
+            Arc::new(mock_router_state);
+        let metadata_tokens = Arc::new(MetadataTokens::default());
 
+        serve(
+            get_fmds_router(arc_mock_router_state.clone(), metadata_tokens.clone()).nest(
+                "/openstack",
+                get_openstack_router(arc_mock_router_state, metadata_tokens),
+            ),
+        )
+        .await
+    }
+
+    /// Serves the FMDS and OpenStack layouts of an instance which may require session tokens
+    async fn setup_token_server(
+        token_required: bool,
+        hop_limit: Option<u32>,
+    ) -> (tokio::task::JoinHandle<()>, u16) {
+        let mut mock_router_state = MockInstanceMetadataRouterState::new();
+        mock_router_state.expect_read().return_const((
+            Some(Arc::new(cloud_init_metadata())),
+            Some(Arc::new(cloud_init_network_config())),
+        ));
+        mock_router_state
+            .expect_metadata_token_required()
+            .return_const(token_required);
+        mock_router_state
+            .expect_metadata_token_hop_limit()
+            .return_const(hop_limit);
+
+        let arc_mock_router_state: Arc<dyn InstanceMetadataRouterState> =
+            Arc::new(mock_router_state);
+        let metadata_tokens = Arc::new(MetadataTokens::default());
+
+        serve(
+            get_fmds_router(arc_mock_router_state.clone(), metadata_tokens.clone()).nest(
+                "/openstack",
+                get_openstack_router(arc_mock_router_state, metadata_tokens),
+            ),
+        )
+        .await
+    }
+
+    async fn serve(router: Router) -> (tokio::task::JoinHandle<()>, u16) {
         let addr = std::net::SocketAddr::from(([127, 0, 0, 1], 0));
         let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
         let server_port = listener.local_addr().unwrap().port();
-        let std_listener = listener.into_std().unwrap();
 
         let server = tokio::spawn(async move {
-            axum_server::Server::from_tcp(std_listener)
-                .serve(router.into_make_service())
-                .await
-                .unwrap();
+            serve_metadata_service(listener, router).await.unwrap();
         });
 
         tokio::time::sleep(std::time::Duration::from_millis(100)).await;
@@ -506,6 +985,28 @@ mod tests {
         (server, server_port)
     }
 
+    async fn send_request(
+        port: u16,
+        method: hyper::Method,
+        path: &str,
+        headers: &[(&str, &str)],
+    ) -> (http::StatusCode, String) {
+        let client = hyper_util::client::legacy::Client::builder(TokioExecutor::new()).build_http();
+        let mut builder = hyper::Request::builder()
+            .method(method)
+            .uri(format!("http://127.0.0.1:{port}/{path}"));
+        for (name, value) in headers {
+            builder = builder.header(*name, *value);
+        }
+        let request: hyper::Request<Full<Bytes>> = builder.body("".into()).unwrap();
+
+        let response = client.request(request).await.unwrap();
+        let status = response.status();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+
     async fn send_request_and_check_response(
         port: u16,
         path: &str,
@@ -618,6 +1119,8 @@ mod tests {
 
         let expected_output = [
             HOSTNAME_CATEGORY,
+            LOCAL_HOSTNAME_CATEGORY,
+            LOCAL_IPV4_CATEGORY,
             SITENAME_CATEGORY,
             MACHINE_ID_CATEGORY,
             INSTANCE_ID_CATEGORY,
@@ -1167,4 +1670,279 @@ mod tests {
         .await;
         server.abort();
     }
+
+    fn cloud_init_metadata() -> InstanceMetadata {
+        InstanceMetadata {
+            instance_id: Some(uuid!("67e55044-10b1-426f-9247-bb680e5fe0c8").into()),
+            machine_id: Some(
+                "fm100ht6n80e7do39u8gmt7cvhm89pb32st9ngevgdolu542l1nfa4an0rg"
+                    .parse()
+                    .unwrap(),
+            ),
+            address: "10.0.0.5".to_string(),
+            hostname: "67e55044-10b1-426f-9247-bb680e5fe0c8".to_string(),
+            user_data: "#cloud-config\n".to_string(),
+            ib_devices: None,
+            config_version: "V2-T1666644937962267".parse().unwrap(),
+            network_config_version: "V1-T1666644937952267".parse().unwrap(),
+            sitename: Some("testsite".to_string()),
+            extension_service_version: "V1-T1666644937952267".parse().unwrap(),
+        }
+    }
+
+    fn cloud_init_network_config() -> ManagedHostNetworkConfigResponse {
+        ManagedHostNetworkConfigResponse {
+            tenant_interfaces: vec![rpc::forge::FlatInterfaceConfig {
+                function_type: rpc::forge::InterfaceFunctionType::Physical as i32,
+                ip: "10.0.0.5".to_string(),
+                gateway: "10.0.0.1/24".to_string(),
+                mtu: Some(1500),
+                ..Default::default()
+            }],
+            instance: Some(rpc::forge::Instance {
+                status: Some(rpc::forge::InstanceStatus {
+                    network: Some(rpc::forge::InstanceNetworkStatus {
+                        interfaces: vec![rpc::forge::InstanceInterfaceStatus {
+                            mac_address: Some("aa:bb:cc:00:00:01".to_string()),
+                            ..Default::default()
+                        }],
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_network_config() {
+        let (server, server_port) =
+            setup_cloud_init_server(cloud_init_metadata(), cloud_init_network_config(), None).await;
+
+        let expected = r#"version: 2
+ethernets:
+  pf0:
+    match:
+      macaddress: aa:bb:cc:00:00:01
+    mtu: 1500
+    addresses:
+    - 10.0.0.5/24
+    routes:
+    - to: 0.0.0.0/0
+      via: 10.0.0.1
+    nameservers:
+      addresses:
+      - 10.0.0.2
+"#;
+        send_request_and_check_response(server_port, "network-config", expected, StatusCode::OK)
+            .await;
+        send_request_and_check_response(
+            server_port,
+            "meta-data/local-ipv4",
+            "10.0.0.5",
+            StatusCode::OK,
+        )
+        .await;
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_get_vendor_data() {
+        let (server, server_port) = setup_cloud_init_server(
+            cloud_init_metadata(),
+            cloud_init_network_config(),
+            Some("#cloud-config\ntimezone: UTC\n".to_string()),
+        )
+        .await;
+        send_request_and_check_response(
+            server_port,
+            "vendor-data",
+            "#cloud-config\ntimezone: UTC\n",
+            StatusCode::OK,
+        )
+        .await;
+        send_request_and_check_response(
+            server_port,
+            "openstack/latest/vendor_data.json",
+            r##"{"cloud-init":"#cloud-config\ntimezone: UTC\n"}"##,
+            StatusCode::OK,
+        )
+        .await;
+        server.abort();
+
+        // Without configured vendor-data, empty documents are served
+        let (server, server_port) =
+            setup_cloud_init_server(cloud_init_metadata(), cloud_init_network_config(), None).await;
+        send_request_and_check_response(server_port, "vendor-data", "", StatusCode::OK).await;
+        send_request_and_check_response(
+            server_port,
+            "openstack/latest/vendor_data.json",
+            "{}",
+            StatusCode::OK,
+        )
+        .await;
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_get_openstack_layout() {
+        let (server, server_port) =
+            setup_cloud_init_server(cloud_init_metadata(), cloud_init_network_config(), None).await;
+
+        send_request_and_check_response(server_port, "openstack", "latest", StatusCode::OK).await;
+        send_request_and_check_response(
+            server_port,
+            "openstack/latest/",
+            "meta_data.json\nnetwork_data.json\nuser_data\nvendor_data.json",
+            StatusCode::OK,
+        )
+        .await;
+        send_request_and_check_response(
+            server_port,
+            "openstack/latest/meta_data.json",
+            r#"{"uuid":"67e55044-10b1-426f-9247-bb680e5fe0c8","name":"67e55044-10b1-426f-9247-bb680e5fe0c8","hostname":"67e55044-10b1-426f-9247-bb680e5fe0c8","availability_zone":"testsite","launch_index":0,"meta":{"machine_id":"fm100ht6n80e7do39u8gmt7cvhm89pb32st9ngevgdolu542l1nfa4an0rg"}}"#,
+            StatusCode::OK,
+        )
+        .await;
+        send_request_and_check_response(
+            server_port,
+            "openstack/latest/network_data.json",
+            r#"{"links":[{"id":"pf0","type":"phy","ethernet_mac_address":"aa:bb:cc:00:00:01","mtu":1500}],"networks":[{"id":"network0","link":"pf0","type":"ipv4","ip_address":"10.0.0.5","netmask":"255.255.255.0","routes":[{"network":"0.0.0.0","netmask":"0.0.0.0","gateway":"10.0.0.1"}]}],"services":[{"type":"dns","address":"10.0.0.2"}]}"#,
+            StatusCode::OK,
+        )
+        .await;
+        send_request_and_check_response(
+            server_port,
+            "openstack/latest/user_data",
+            "#cloud-config\n",
+            StatusCode::OK,
+        )
+        .await;
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_metadata_token_flow() {
+        let (server, server_port) = setup_token_server(true, None).await;
+
+        // Without a token, metadata can not be read
+        let (status, _) =
+            send_request(server_port, hyper::Method::GET, "meta-data/hostname", &[]).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        let (status, _) = send_request(
+            server_port,
+            hyper::Method::GET,
+            "openstack/latest/meta_data.json",
+            &[],
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        let (status, token) = send_request(
+            server_port,
+            hyper::Method::PUT,
+            "api/token",
+            &[(TOKEN_TTL_HEADER, "60")],
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, body) = send_request(
+            server_port,
+            hyper::Method::GET,
+            "meta-data/hostname",
+            &[(TOKEN_HEADER, &token)],
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "67e55044-10b1-426f-9247-bb680e5fe0c8");
+
+        // Tokens which were not issued by the service are rejected
+        let (status, _) = send_request(
+            server_port,
+            hyper::Method::GET,
+            "meta-data/hostname",
+            &[(TOKEN_HEADER, "forged")],
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_metadata_token_optional() {
+        let (server, server_port) = setup_token_server(false, None).await;
+
+        let (status, _) =
+            send_request(server_port, hyper::Method::GET, "meta-data/hostname", &[]).await;
+        assert_eq!(status, StatusCode::OK);
+
+        // A token which is presented is still validated
+        let (status, _) = send_request(
+            server_port,
+            hyper::Method::GET,
+            "meta-data/hostname",
+            &[(TOKEN_HEADER, "forged")],
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_metadata_token_request_validation() {
+        let (server, server_port) = setup_token_server(true, None).await;
+
+        for ttl in [None, Some("0"), Some("21601"), Some("soon")] {
+            let headers = ttl
+                .map(|ttl| vec![(TOKEN_TTL_HEADER, ttl)])
+                .unwrap_or_default();
+            let (status, _) =
+                send_request(server_port, hyper::Method::PUT, "api/token", &headers).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST, "ttl {ttl:?}");
+        }
+
+        let (status, _) = send_request(
+            server_port,
+            hyper::Method::PUT,
+            "api/token",
+            &[(TOKEN_TTL_HEADER, "21600"), ("X-Forwarded-For", "10.0.0.9")],
+        )
+        .await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_metadata_token_hop_limit() {
+        let (server, server_port) = setup_token_server(true, Some(1)).await;
+
+        let client = hyper_util::client::legacy::Client::builder(TokioExecutor::new()).build_http();
+        let request: hyper::Request<Full<Bytes>> = hyper::Request::builder()
+            .method(hyper::Method::PUT)
+            .uri(format!("http://127.0.0.1:{server_port}/api/token"))
+            .header(TOKEN_TTL_HEADER, "60")
+            .body("".into())
+            .unwrap();
+        let response = client.request(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        // No other response is sent on a connection with a hop limited TTL
+        assert_eq!(response.headers().get(header::CONNECTION).unwrap(), "close");
+        let token = response.into_body().collect().await.unwrap().to_bytes();
+        let token = String::from_utf8(token.to_vec()).unwrap();
+
+        // Requests on a new connection are served normally
+        let (status, body) = send_request(
+            server_port,
+            hyper::Method::GET,
+            "meta-data/hostname",
+            &[(TOKEN_HEADER, &token)],
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "67e55044-10b1-426f-9247-bb680e5fe0c8");
+        server.abort();
+    }
 }
@@ -0,0 +1,124 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Session tokens for the instance metadata service.
+//!
+//! Tenants can require that the host first obtains a token through `PUT /latest/api/token` and
+//! then presents it with every metadata request. This mirrors IMDSv2 and prevents SSRF-style
+//! attacks where an application on the host can be tricked into issuing a plain GET request.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use data_encoding::BASE64URL_NOPAD;
+use rand::RngCore;
+
+/// Header used to request a token, carrying its lifetime in seconds
+pub const TOKEN_TTL_HEADER: &str = "x-aws-ec2-metadata-token-ttl-seconds";
+/// Header carrying the token with metadata requests
+pub const TOKEN_HEADER: &str = "x-aws-ec2-metadata-token";
+/// Longest lifetime a token can be requested for
+pub const MAX_TOKEN_TTL: Duration = Duration::from_secs(6 * 60 * 60);
+
+// Bounds the memory used by tokens of a host which keeps requesting new ones
+const MAX_TOKENS: usize = 1024;
+const TOKEN_BYTES: usize = 32;
+
+/// The tokens which were issued to the host, and when they expire
+#[derive(Default)]
+pub struct MetadataTokens {
+    tokens: Mutex<HashMap<String, Instant>>,
+}
+
+impl MetadataTokens {
+    /// Issues a new token which is valid for `ttl`
+    pub fn issue(&self, ttl: Duration) -> String {
+        let mut bytes = [0u8; TOKEN_BYTES];
+        rand::rng().fill_bytes(&mut bytes);
+        let token = BASE64URL_NOPAD.encode(&bytes);
+
+        let now = Instant::now();
+        let mut tokens = self.tokens.lock().expect("lock poisoned");
+        tokens.retain(|_, expires| *expires > now);
+        if tokens.len() >= MAX_TOKENS {
+            // Drop the token closest to expiry to make room
+            if let Some(oldest) = tokens
+                .iter()
+                .min_by_key(|(_, expires)| **expires)
+                .map(|(token, _)| token.clone())
+            {
+                tokens.remove(&oldest);
+            }
+        }
+        tokens.insert(token.clone(), now + ttl);
+        token
+    }
+
+    /// Whether the token was issued by this service and has not yet expired
+    pub fn validate(&self, token: &str) -> bool {
+        self.tokens
+            .lock()
+            .expect("lock poisoned")
+            .get(token)
+            .is_some_and(|expires| *expires > Instant::now())
+    }
+
+    /// Invalidates all tokens, e.g. because the host was assigned to a different instance
+    pub fn clear(&self) {
+        self.tokens.lock().expect("lock poisoned").clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_issue_and_validate() {
+        let tokens = MetadataTokens::default();
+        let token = tokens.issue(Duration::from_secs(60));
+        let other = tokens.issue(Duration::from_secs(60));
+
+        assert_ne!(token, other);
+        assert!(tokens.validate(&token));
+        assert!(tokens.validate(&other));
+        assert!(!tokens.validate("not-a-token"));
+
+        tokens.clear();
+        assert!(!tokens.validate(&token));
+    }
+
+    #[test]
+    fn test_expired_token_is_rejected() {
+        let tokens = MetadataTokens::default();
+        let token = tokens.issue(Duration::ZERO);
+        assert!(!tokens.validate(&token));
+    }
+
+    #[test]
+    fn test_token_count_is_bounded() {
+        let tokens = MetadataTokens::default();
+        let first = tokens.issue(Duration::from_secs(1));
+        for _ in 0..MAX_TOKENS {
+            tokens.issue(Duration::from_secs(60));
+        }
+
+        assert!(!tokens.validate(&first));
+        assert_eq!(tokens.tokens.lock().unwrap().len(), MAX_TOKENS);
+    }
+}
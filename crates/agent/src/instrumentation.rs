@@ -24,7 +24,7 @@ use axum::http::header::{CONTENT_LENGTH, CONTENT_TYPE};
 use axum::routing::get;
 use http_body_util::Full;
 use hyper::body::Bytes;
-use hyper::{Request, Response};
+use hyper::{Method, Request, Response};
 use opentelemetry::KeyValue;
 use opentelemetry::metrics::{Counter, Histogram, Meter};
 use prometheus::{Encoder, TextEncoder};
@@ -32,6 +32,8 @@ use tonic::service::AxumBody;
 use tower::ServiceBuilder;
 use tracing::Span;
 
+use crate::instance_metadata_token::TOKEN_HEADER;
+
 pub mod config;
 use carbide_uuid::machine::MachineId;
 pub use config::{get_dpu_agent_meter, get_prometheus_registry};
@@ -39,6 +41,7 @@ pub use config::{get_dpu_agent_meter, get_prometheus_registry};
 pub struct AgentMetricsState {
     meter: Meter,
     http_counter: Counter<u64>,
+    fmds_request_counter: Counter<u64>,
     http_req_latency_histogram: Histogram<f64>,
 }
 
@@ -75,6 +78,12 @@ pub fn create_metrics(meter: Meter) -> Arc<AgentMetricsState> {
         .u64_counter("http_requests")
         .with_description("Total number of HTTP requests made.")
         .build();
+    let fmds_request_counter = meter
+        .u64_counter("fmds_requests")
+        .with_description(
+            "Number of instance metadata requests, by whether they presented a session token",
+        )
+        .build();
     let http_req_latency_histogram: Histogram<f64> = meter
         .f64_histogram("request_latency")
         .with_description("HTTP request latency")
@@ -84,6 +93,7 @@ pub fn create_metrics(meter: Meter) -> Arc<AgentMetricsState> {
     Arc::new(AgentMetricsState {
         meter,
         http_counter,
+        fmds_request_counter,
         http_req_latency_histogram,
     })
 }
@@ -137,7 +147,7 @@ impl NetworkMonitorMetricsState {
             .build();
         let network_loss_percent = meter
             .f64_histogram("forge_dpu_agent_network_loss_percentage")
-            .with_description("Percentage of failed pings out of total 5 pings")
+            .with_description("Percentage of failed pings out of all pings sent in one check")
             .build();
         let network_monitor_error = meter
             .u64_counter("forge_dpu_agent_network_monitor_error")
@@ -273,6 +283,17 @@ impl WithTracingLayer for Router {
         let layer = tower_http::trace::TraceLayer::new_for_http()
             .on_request(move |request: &Request<AxumBody>, _span: &Span| {
                 metrics.http_counter.add(1, &[]);
+                // Token requests themselves are not metadata requests
+                if request.method() != Method::PUT {
+                    let access = if request.headers().contains_key(TOKEN_HEADER) {
+                        "token"
+                    } else {
+                        "tokenless"
+                    };
+                    metrics
+                        .fmds_request_counter
+                        .add(1, &[KeyValue::new("access", access)]);
+                }
                 tracing::info!("started {} {}", request.method(), request.uri().path())
             })
             .on_response(
@@ -40,6 +40,7 @@ pub mod dpu;
 pub mod acl;
 mod acl_rules;
 pub mod agent_platform;
+mod cloud_init;
 mod command_line;
 pub mod containerd;
 mod daemons;
@@ -54,6 +55,7 @@ mod frr;
 mod hbn;
 mod health;
 mod instance_metadata_endpoint;
+mod instance_metadata_token;
 pub mod instrumentation;
 mod interfaces;
 pub mod lldp;
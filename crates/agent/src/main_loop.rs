@@ -113,6 +113,8 @@ pub async fn setup_and_run(
             machine_id,
             forge_api_server.clone(),
             Arc::clone(&forge_client_config),
+            agent_config.metadata_service.vendor_data.clone(),
+            agent_config.metadata_service.hop_limit,
         ),
     );
 
@@ -239,6 +241,7 @@ pub async fn setup_and_run(
             nameservers: vec![IpAddr::from([127, 0, 0, 1])],
         }
     };
+    instance_metadata_state.update_nameservers(service_addrs.nameservers.clone());
 
     let inventory_updater_config = MachineInventoryUpdaterConfig {
         dpu_agent_version: build_version.clone(),
@@ -258,6 +261,12 @@ pub async fn setup_and_run(
         .as_ref()
         .and_then(|response| response.dpu_network_pinger_type.as_ref())
         .and_then(|value| NetworkPingerType::from_str(value).ok());
+    let network_monitor_config = periodic_config_reader
+        .net_conf_read()
+        .as_ref()
+        .and_then(|response| response.dpu_network_monitor_config.as_ref())
+        .map(network_monitor::NetworkMonitorConfig::from)
+        .unwrap_or_default();
 
     let agent_meter = get_dpu_agent_meter();
     let network_monitor_metrics_state =
@@ -270,7 +279,8 @@ pub async fn setup_and_run(
                 machine_id,
                 Some(network_monitor_metrics_state),
                 Arc::from(pinger_type),
-            );
+            )
+            .with_config(network_monitor_config);
             let forge_api_clone = forge_api_server.clone();
             let forge_client_config_clone = Arc::clone(&forge_client_config);
             let network_monitor_handle = tokio::spawn(async move {
@@ -19,7 +19,9 @@ use std::sync::Arc;
 
 use axum::Router;
 
-use crate::instance_metadata_endpoint::{InstanceMetadataRouterStateImpl, get_fmds_router};
+use crate::instance_metadata_endpoint::{
+    InstanceMetadataRouterStateImpl, get_fmds_router, get_openstack_router, serve_metadata_service,
+};
 use crate::instrumentation::{
     AgentMetricsState, WithTracingLayer, get_metrics_router, get_prometheus_registry,
 };
@@ -31,22 +33,29 @@ pub fn spawn_metadata_service(
     state: Arc<InstanceMetadataRouterStateImpl>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let instance_metadata_state = state;
+    let metadata_tokens = instance_metadata_state.metadata_tokens();
 
     let prometheus_registry = get_prometheus_registry();
     // let meter = get_dpu_agent_meter();
     // let metrics_state = create_metrics(meter);
 
-    start_server(
+    start_metadata_server(
         metadata_service_address,
         Router::new()
             .nest(
                 "/latest",
-                get_fmds_router(instance_metadata_state.clone())
+                get_fmds_router(instance_metadata_state.clone(), metadata_tokens.clone())
                     .with_tracing_layer(metrics_state.clone()),
             )
             .nest(
                 "/2009-04-04",
-                get_fmds_router(instance_metadata_state).with_tracing_layer(metrics_state),
+                get_fmds_router(instance_metadata_state.clone(), metadata_tokens.clone())
+                    .with_tracing_layer(metrics_state.clone()),
+            )
+            .nest(
+                "/openstack",
+                get_openstack_router(instance_metadata_state, metadata_tokens)
+                    .with_tracing_layer(metrics_state),
             ),
     )
     .expect("metadata server panicked");
@@ -57,6 +66,26 @@ pub fn spawn_metadata_service(
     )
 }
 
+/// Spawns a background task to run the metadata service listening on given socket, and returns.
+/// The listener is bound right away, so that errors are returned to the caller.
+fn start_metadata_server(
+    address: String,
+    router: Router,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let addr: std::net::SocketAddr = address.parse()?;
+    let listener = std::net::TcpListener::bind(addr)?;
+    listener.set_nonblocking(true)?;
+    let listener = tokio::net::TcpListener::from_std(listener)?;
+
+    tokio::spawn(async move {
+        if let Err(err) = serve_metadata_service(listener, router).await {
+            eprintln!("Error while serving: {err}");
+        }
+    });
+
+    Ok(())
+}
+
 /// Spawns a background task to run an axum server listening on given socket, and returns.
 fn start_server(address: String, router: Router) -> Result<(), Box<dyn std::error::Error>> {
     let addr: std::net::SocketAddr = address.parse()?;
@@ -15,12 +15,12 @@
  * limitations under the License.
  */
 use std::cmp::max;
-use std::collections::HashMap;
-use std::fmt;
+use std::collections::{HashMap, HashSet};
 use std::hash::Hash;
-use std::net::IpAddr;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
 use std::str::FromStr;
 use std::sync::Arc;
+use std::{fmt, io};
 
 use ::rpc::forge::{self as rpc};
 use ::rpc::forge_tls_client::{ApiConfig, ForgeClientConfig, ForgeTlsClient};
@@ -34,6 +34,7 @@ use regex::Regex;
 use serde::Serialize;
 use serde_json::json;
 use surge_ping::{Client, Config, PingIdentifier, PingSequence};
+use tokio::net::{TcpSocket, UdpSocket};
 use tokio::sync::{mpsc, watch};
 use tokio::task;
 use tokio::time::{self, Duration, Instant};
@@ -42,15 +43,177 @@ use tonic::async_trait;
 use crate::hbn;
 use crate::instrumentation::NetworkMonitorMetricsState;
 
-// @TODO: this should be able to be configured
-const MAX_PINGS_PER_DPU: u32 = 5; // Number of pings for each DPU in each check cycle
-const DPU_LIST_FETCH_INTERVAL: u64 = 30 * 60; // Interval in seconds for fetching DPU list from API
+// Defaults used until carbide-api provides a network monitor configuration
+const DEFAULT_PROBE_COUNT: u32 = 5; // Number of probes for each DPU and probe type in each check cycle
+const DEFAULT_PROBE_TIMEOUT: Duration = Duration::from_secs(1);
+const DEFAULT_PEER_REFRESH_INTERVAL: Duration = Duration::from_secs(30 * 60); // Interval for fetching DPU list from API
+const DEFAULT_MAX_PEERS: usize = 64; // Number of peer DPUs probed in each check cycle
+
+const OOB_INTERFACE: &str = "oob_net0";
+const UDP_PROBE_PAYLOAD: &[u8] = b"forge-dpu-agent-probe";
+
+/// Kind of probe sent to peer DPUs
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy, Serialize)]
+pub enum Probe {
+    Icmp,
+    Tcp(u16),
+    Udp(u16),
+}
+
+impl Probe {
+    pub fn protocol(&self) -> rpc::DpuNetworkProbeProtocol {
+        match self {
+            Probe::Icmp => rpc::DpuNetworkProbeProtocol::Icmp,
+            Probe::Tcp(_) => rpc::DpuNetworkProbeProtocol::Tcp,
+            Probe::Udp(_) => rpc::DpuNetworkProbeProtocol::Udp,
+        }
+    }
+
+    pub fn port(&self) -> Option<u16> {
+        match self {
+            Probe::Icmp => None,
+            Probe::Tcp(port) | Probe::Udp(port) => Some(*port),
+        }
+    }
+}
+
+impl fmt::Display for Probe {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Probe::Icmp => write!(f, "icmp"),
+            Probe::Tcp(port) => write!(f, "tcp/{port}"),
+            Probe::Udp(port) => write!(f, "udp/{port}"),
+        }
+    }
+}
+
+impl TryFrom<&rpc::DpuNetworkProbe> for Probe {
+    type Error = eyre::Error;
+
+    fn try_from(probe: &rpc::DpuNetworkProbe) -> Result<Self, Self::Error> {
+        let protocol = rpc::DpuNetworkProbeProtocol::try_from(probe.protocol)
+            .map_err(|_| eyre::eyre!("Unknown probe protocol {}", probe.protocol))?;
+        let port = || -> Result<u16, Self::Error> {
+            let port = probe
+                .port
+                .ok_or_else(|| eyre::eyre!("{protocol:?} probe without port"))?;
+            Ok(u16::try_from(port)?)
+        };
+        Ok(match protocol {
+            rpc::DpuNetworkProbeProtocol::Icmp => Probe::Icmp,
+            rpc::DpuNetworkProbeProtocol::Tcp => Probe::Tcp(port()?),
+            rpc::DpuNetworkProbeProtocol::Udp => Probe::Udp(port()?),
+        })
+    }
+}
+
+/// Network monitor settings, provided by carbide-api as part of the managed host network config
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetworkMonitorConfig {
+    /// Number of probes sent to each peer per check cycle and probe type
+    pub probe_count: u32,
+    pub probe_timeout: Duration,
+    /// How often the list of peer DPUs is fetched
+    pub peer_refresh_interval: Duration,
+    /// Probes sent to the loopback IP of each peer DPU
+    pub probes: Vec<Probe>,
+    /// Whether probes are also sent on the overlay of VPCs shared with the peer DPU
+    pub vpc_overlay_probes: bool,
+    /// Whether results are reported to carbide-api for the reachability matrix
+    pub report_results: bool,
+    /// Maximum number of peers probed per check cycle. Larger sites are probed in windows of
+    /// peers which rotate with every cycle.
+    pub max_peers: usize,
+}
+
+impl Default for NetworkMonitorConfig {
+    fn default() -> Self {
+        Self {
+            probe_count: DEFAULT_PROBE_COUNT,
+            probe_timeout: DEFAULT_PROBE_TIMEOUT,
+            peer_refresh_interval: DEFAULT_PEER_REFRESH_INTERVAL,
+            probes: vec![Probe::Icmp],
+            vpc_overlay_probes: false,
+            report_results: false,
+            max_peers: DEFAULT_MAX_PEERS,
+        }
+    }
+}
+
+impl From<&rpc::DpuNetworkMonitorConfig> for NetworkMonitorConfig {
+    /// Unset values fall back to the defaults. Invalid probes are skipped.
+    fn from(config: &rpc::DpuNetworkMonitorConfig) -> Self {
+        let defaults = Self::default();
+        let mut probes = Vec::new();
+        for probe in config.probes.iter() {
+            match Probe::try_from(probe) {
+                Ok(probe) if !probes.contains(&probe) => probes.push(probe),
+                Ok(_) => {}
+                Err(e) => tracing::warn!("Ignoring invalid network monitor probe: {e}"),
+            }
+        }
+        if probes.is_empty() {
+            probes = defaults.probes;
+        }
+
+        Self {
+            probe_count: match config.probe_count {
+                0 => defaults.probe_count,
+                probe_count => probe_count,
+            },
+            probe_timeout: match config.probe_timeout_ms {
+                0 => defaults.probe_timeout,
+                timeout_ms => Duration::from_millis(timeout_ms.into()),
+            },
+            peer_refresh_interval: match config.peer_refresh_interval_secs {
+                0 => defaults.peer_refresh_interval,
+                interval_secs => Duration::from_secs(interval_secs.into()),
+            },
+            probes,
+            vpc_overlay_probes: config.vpc_overlay_probes,
+            report_results: config.report_results,
+            max_peers: match config.max_peers {
+                0 => defaults.max_peers,
+                max_peers => max_peers as usize,
+            },
+        }
+    }
+}
+
+/// An address of a DPU on the overlay of a VPC
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Serialize)]
+pub struct VpcProbeTarget {
+    pub vpc_id: String,
+    pub vni: u32,
+    pub ip: IpAddr,
+}
+
+impl VpcProbeTarget {
+    /// Name of the VRF of the VPC inside the HBN container
+    pub fn vrf_name(&self) -> String {
+        format!("vpc_{}", self.vni)
+    }
+}
+
+impl TryFrom<rpc::DpuVpcProbeTarget> for VpcProbeTarget {
+    type Error = eyre::Error;
+
+    fn try_from(target: rpc::DpuVpcProbeTarget) -> Result<Self, Self::Error> {
+        Ok(VpcProbeTarget {
+            ip: IpAddr::from_str(&target.address)?,
+            vpc_id: target.vpc_id,
+            vni: target.vni,
+        })
+    }
+}
 
 /// Structure to store peer DPU information
 #[derive(Debug, Eq, PartialEq, Hash, Clone, Serialize)]
 pub struct DpuInfo {
     pub id: MachineId,
     pub ip: IpAddr,
+    /// Addresses of the DPU on the overlay of VPCs, only provided if VPC overlay probes are enabled
+    pub vpc_probe_targets: Vec<VpcProbeTarget>,
 }
 
 impl fmt::Display for DpuInfo {
@@ -64,30 +227,245 @@ impl TryFrom<rpc::DpuInfo> for DpuInfo {
 
     fn try_from(rpc_info: rpc::DpuInfo) -> Result<Self, Self::Error> {
         let ip = IpAddr::from_str(&rpc_info.loopback_ip)?;
+        let vpc_probe_targets = rpc_info
+            .vpc_probe_targets
+            .into_iter()
+            .filter_map(|target| {
+                VpcProbeTarget::try_from(target)
+                    .inspect_err(|e| tracing::warn!("Ignoring invalid VPC probe target: {e}"))
+                    .ok()
+            })
+            .collect();
         // Note: DpuInfo uses a string for machine_id, not a real MachineId, which is wrong.
         Ok(DpuInfo {
             id: rpc_info.id.parse()?,
             ip,
+            vpc_probe_targets,
+        })
+    }
+}
+
+/// A probe sent to a peer DPU, either to its loopback IP on the underlay or to its address on
+/// the overlay of a VPC
+#[derive(Debug, Clone)]
+pub struct ProbeTarget {
+    pub dpu_info: DpuInfo,
+    pub probe: Probe,
+    pub vpc: Option<VpcProbeTarget>,
+}
+
+impl ProbeTarget {
+    pub fn ip(&self) -> IpAddr {
+        self.vpc.as_ref().map_or(self.dpu_info.ip, |vpc| vpc.ip)
+    }
+}
+
+/// Picks the peers probed in a check cycle, at most `max_peers` of them. Peers are ordered by
+/// loopback IP, and the window starts after this DPU's own loopback IP so that DPUs don't all
+/// probe the same peers at once. Every cycle moves the window on by `max_peers`.
+pub fn sample_peers(
+    this_dpu: &DpuInfo,
+    peer_dpus: &[DpuInfo],
+    max_peers: usize,
+    cycle: usize,
+) -> Vec<DpuInfo> {
+    if peer_dpus.len() <= max_peers {
+        return peer_dpus.to_vec();
+    }
+
+    let mut sorted: Vec<&DpuInfo> = peer_dpus.iter().collect();
+    sorted.sort_by_key(|peer| peer.ip);
+    let start = sorted.partition_point(|peer| peer.ip <= this_dpu.ip);
+    let offset = (start + (cycle % sorted.len()) * max_peers) % sorted.len();
+    sorted
+        .into_iter()
+        .cycle()
+        .skip(offset)
+        .take(max_peers)
+        .cloned()
+        .collect()
+}
+
+/// Results to report after a check cycle. Results of peers which weren't probed in this cycle
+/// are carried over from earlier cycles, as long as the peer is still known.
+pub fn merge_results(
+    previous: Vec<DpuPingResult>,
+    results: Vec<DpuPingResult>,
+    peer_dpus: &[DpuInfo],
+) -> Vec<DpuPingResult> {
+    let known_peers: HashSet<MachineId> = peer_dpus.iter().map(|peer| peer.id).collect();
+    let probed_peers: HashSet<MachineId> =
+        results.iter().map(|result| result.dpu_info.id).collect();
+
+    let mut merged: Vec<DpuPingResult> = previous
+        .into_iter()
+        .filter(|result| {
+            known_peers.contains(&result.dpu_info.id) && !probed_peers.contains(&result.dpu_info.id)
         })
+        .collect();
+    merged.extend(results);
+    merged
+}
+
+/// Builds the probes of one check cycle. Overlay probes are only sent to the peers' addresses in
+/// VPCs this DPU has an address in as well, at most once per peer and VPC. These addresses are
+/// the loopbacks of the DPUs in the VRFs of the VPCs, never tenant instances.
+pub fn probe_targets(
+    this_dpu: &DpuInfo,
+    peer_dpus: &[DpuInfo],
+    config: &NetworkMonitorConfig,
+    pinger: &dyn Ping,
+) -> Vec<ProbeTarget> {
+    let local_vpcs: HashSet<&str> = this_dpu
+        .vpc_probe_targets
+        .iter()
+        .map(|target| target.vpc_id.as_str())
+        .collect();
+
+    let mut targets = Vec::new();
+    for peer_dpu in peer_dpus {
+        for probe in config.probes.iter().copied() {
+            if pinger.supports(probe, false) {
+                targets.push(ProbeTarget {
+                    dpu_info: peer_dpu.clone(),
+                    probe,
+                    vpc: None,
+                });
+            }
+        }
+
+        if !config.vpc_overlay_probes {
+            continue;
+        }
+        let mut probed_vpcs = HashSet::new();
+        for vpc in peer_dpu.vpc_probe_targets.iter() {
+            if !local_vpcs.contains(vpc.vpc_id.as_str()) || !probed_vpcs.insert(&vpc.vpc_id) {
+                continue;
+            }
+            for probe in config.probes.iter().copied() {
+                if pinger.supports(probe, true) {
+                    targets.push(ProbeTarget {
+                        dpu_info: peer_dpu.clone(),
+                        probe,
+                        vpc: Some(vpc.clone()),
+                    });
+                }
+            }
+        }
     }
+    targets
+}
+
+/// Latency distribution of the answered probes of one check cycle
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LatencyStats {
+    pub p50: Duration,
+    pub p90: Duration,
+    pub p99: Duration,
+    /// Mean difference between the latencies of consecutive probes, zero for a single probe
+    pub jitter: Duration,
 }
 
-/// Structure to store ping results for one DPU in one cycle
+impl LatencyStats {
+    /// Computes the stats from latencies in the order the probes were sent.
+    /// Returns `None` if no probe was answered.
+    pub fn from_latencies(latencies: &[Duration]) -> Option<Self> {
+        if latencies.is_empty() {
+            return None;
+        }
+
+        let mut sorted = latencies.to_vec();
+        sorted.sort();
+        // Nearest-rank percentile
+        let percentile = |p: usize| sorted[(p * sorted.len()).div_ceil(100).max(1) - 1];
+
+        let jitter = match latencies.len() {
+            1 => Duration::ZERO,
+            n => {
+                latencies
+                    .windows(2)
+                    .map(|pair| pair[0].abs_diff(pair[1]))
+                    .sum::<Duration>()
+                    / (n as u32 - 1)
+            }
+        };
+
+        Some(Self {
+            p50: percentile(50),
+            p90: percentile(90),
+            p99: percentile(99),
+            jitter,
+        })
+    }
+}
+
+/// Structure to store probe results for one DPU and probe type in one cycle
 pub struct DpuPingResult {
     pub dpu_info: DpuInfo,
-    pub success_count: u32, // Number of successful pings, <= MAX_PINGS_PER_DPU
+    pub probe: Probe,
+    pub vpc: Option<VpcProbeTarget>,
+    pub sent: u32,
+    pub success_count: u32, // Number of successful probes, <= sent
     pub average_latency: Option<Duration>, // None if ping not successful, i.e. success_count = 0
+    pub latency_stats: Option<LatencyStats>,
 }
 
 impl DpuPingResult {
+    /// Builds the result from the latencies of the answered probes, in the order they were sent
+    pub fn new(target: ProbeTarget, sent: u32, latencies: &[Duration]) -> Self {
+        let success_count = latencies.len() as u32;
+        Self {
+            dpu_info: target.dpu_info,
+            probe: target.probe,
+            vpc: target.vpc,
+            sent,
+            success_count,
+            average_latency: (success_count > 0)
+                .then(|| latencies.iter().sum::<Duration>() / success_count),
+            latency_stats: LatencyStats::from_latencies(latencies),
+        }
+    }
+
     pub fn loss_percent(&self) -> f64 {
-        let max_pings = MAX_PINGS_PER_DPU as f64;
-        (max_pings - (self.success_count as f64)) / max_pings
+        if self.sent == 0 {
+            return 0.0;
+        }
+        let sent = self.sent as f64;
+        (sent - (self.success_count as f64)) / sent
     }
 
     pub fn reachable(&self) -> bool {
         self.success_count > 0
     }
+
+    pub fn target_ip(&self) -> IpAddr {
+        self.vpc.as_ref().map_or(self.dpu_info.ip, |vpc| vpc.ip)
+    }
+
+    /// Whether this is the ICMP probe to the loopback IP of the peer, which is exported as metrics
+    fn is_underlay_icmp(&self) -> bool {
+        self.probe == Probe::Icmp && self.vpc.is_none()
+    }
+}
+
+impl From<&DpuPingResult> for rpc::DpuReachabilityResult {
+    fn from(result: &DpuPingResult) -> Self {
+        let as_ms = |latency: Duration| latency.as_secs_f64() * 1000.0;
+        let stats = result.latency_stats;
+        rpc::DpuReachabilityResult {
+            peer_dpu_id: result.dpu_info.id.to_string(),
+            protocol: result.probe.protocol().into(),
+            port: result.probe.port().map(u32::from),
+            vpc_id: result.vpc.as_ref().map(|vpc| vpc.vpc_id.clone()),
+            target_ip: result.target_ip().to_string(),
+            sent: result.sent,
+            received: result.success_count,
+            latency_p50_ms: stats.map(|stats| as_ms(stats.p50)),
+            latency_p90_ms: stats.map(|stats| as_ms(stats.p90)),
+            latency_p99_ms: stats.map(|stats| as_ms(stats.p99)),
+            jitter_ms: stats.map(|stats| as_ms(stats.jitter)),
+        }
+    }
 }
 
 /// Network monitor struct handles network connectivity checks
@@ -95,6 +473,8 @@ pub struct NetworkMonitor {
     machine_id: MachineId,                            // DPU id
     metrics: Option<Arc<NetworkMonitorMetricsState>>, // Metrics for monitoring
     pinger: Arc<dyn Ping>,                            // Pinger that help ping DPUs and get results
+    config: NetworkMonitorConfig,
+    cycle: usize, // Number of check cycles run, selects the peers probed in the next one
 }
 
 impl NetworkMonitor {
@@ -107,9 +487,16 @@ impl NetworkMonitor {
             machine_id,
             metrics,
             pinger,
+            config: NetworkMonitorConfig::default(),
+            cycle: 0,
         }
     }
 
+    pub fn with_config(mut self, config: NetworkMonitorConfig) -> Self {
+        self.config = config;
+        self
+    }
+
     /// Runs in a loop to check network connection with peer DPUs and
     /// fetch updated peer dpus from API
     pub async fn run(
@@ -120,7 +507,7 @@ impl NetworkMonitor {
     ) {
         // Initial fetch peer dpu list from API
         let mut peer_dpus = Vec::new();
-        let mut loopback_ip: Option<IpAddr> = None;
+        let mut this_dpu: Option<DpuInfo> = None;
 
         match self
             .find_all_dpu_info(&self.machine_id, forge_api, &client_config)
@@ -128,16 +515,16 @@ impl NetworkMonitor {
         {
             Ok((dpu_info, new_peer_dpus)) => {
                 peer_dpus = new_peer_dpus;
-                loopback_ip = Some(dpu_info.ip);
+                this_dpu = Some(dpu_info);
             }
             Err(e) => {
                 tracing::debug!("Network monitor failed to get dpu info list from API {}", e);
             }
         }
 
-        let mut peer_dpus_fetch_interval =
-            tokio::time::interval(Duration::from_secs(DPU_LIST_FETCH_INTERVAL));
+        let mut peer_dpus_fetch_interval = tokio::time::interval(self.config.peer_refresh_interval);
         let mut next_monitor_time = Instant::now();
+        let mut reported_results = Vec::new();
 
         loop {
             tokio::select! {
@@ -149,64 +536,78 @@ impl NetworkMonitor {
                     match self.find_all_dpu_info(&self.machine_id, forge_api, &client_config).await {
                         Ok((dpu_info, new_peer_dpus)) => {
                             peer_dpus = new_peer_dpus;
-                            loopback_ip = Some(dpu_info.ip);
+                            this_dpu = Some(dpu_info);
                         }
                         Err(e) => {
                             tracing::debug!("Network monitor failed to get dpu info list from API {}", e);
                             peer_dpus = Vec::new();
-                            loopback_ip = None;
+                            this_dpu = None;
                         }
                     }
                 }
                 _ = time::sleep_until(next_monitor_time) => {
                     // Run the monitoring task and dynamically adjust the interval
-                    let elapsed_time = self.run_monitor(&peer_dpus, loopback_ip).await;
-                    let interval = self.set_loop_interval(&elapsed_time);
+                    let start_time = Instant::now();
+                    let results = self.run_monitor(this_dpu.as_ref(), &peer_dpus).await;
+                    if self.config.report_results && !results.is_empty() {
+                        reported_results = merge_results(reported_results, results, &peer_dpus);
+                        self.report_results(forge_api, &client_config, &reported_results).await;
+                    }
+                    let interval = self.set_loop_interval(&start_time.elapsed());
                     next_monitor_time = Instant::now() + interval;
                 }
             }
         }
     }
 
-    /// Run network monitor for all peer_dpus, export results as metrics
-    /// Returns total time elapsed to complete
+    /// Run network monitor for a window of peer_dpus, export results as metrics
+    /// Returns the results of all probes
     pub async fn run_monitor(
         &mut self,
-        peer_dpus: &Vec<DpuInfo>,
-        loopback_ip: Option<IpAddr>,
-    ) -> Duration {
-        let mut elapsed_time = Duration::from_secs(0);
-        if let (Some(ip), false) = (loopback_ip, peer_dpus.is_empty()) {
-            let start_time = Instant::now();
-            match self.monitor_concurrent(peer_dpus, ip).await {
-                Ok(results) => {
-                    // Export metrics for the results
-                    if let Some(metrics) = self.metrics.clone() {
-                        let mut reachable_map = HashMap::new();
-                        for result in results {
-                            reachable_map.insert(result.dpu_info.id, result.reachable());
-                            if let Some(latency) = result.average_latency {
-                                metrics.record_network_latency(
-                                    latency,
-                                    self.machine_id,
-                                    result.dpu_info.id,
-                                );
-                                metrics.record_network_loss_percent(
-                                    result.loss_percent(),
-                                    self.machine_id,
-                                    result.dpu_info.id,
-                                );
-                            }
+        this_dpu: Option<&DpuInfo>,
+        peer_dpus: &[DpuInfo],
+    ) -> Vec<DpuPingResult> {
+        let Some(this_dpu) = this_dpu else {
+            return Vec::new();
+        };
+        if peer_dpus.is_empty() {
+            return Vec::new();
+        }
+
+        let peer_dpus = sample_peers(this_dpu, peer_dpus, self.config.max_peers, self.cycle);
+        self.cycle = self.cycle.wrapping_add(1);
+
+        let targets = probe_targets(this_dpu, &peer_dpus, &self.config, self.pinger.as_ref());
+        match self.monitor_concurrent(&targets, this_dpu.ip).await {
+            Ok(results) => {
+                // Export metrics for the ICMP results on the underlay, the other probes are only
+                // part of the reachability report
+                if let Some(metrics) = self.metrics.clone() {
+                    let mut reachable_map = HashMap::new();
+                    for result in results.iter().filter(|result| result.is_underlay_icmp()) {
+                        reachable_map.insert(result.dpu_info.id, result.reachable());
+                        if let Some(latency) = result.average_latency {
+                            metrics.record_network_latency(
+                                latency,
+                                self.machine_id,
+                                result.dpu_info.id,
+                            );
+                            metrics.record_network_loss_percent(
+                                result.loss_percent(),
+                                self.machine_id,
+                                result.dpu_info.id,
+                            );
                         }
-                        metrics.update_network_reachable_map(reachable_map);
                     }
+                    metrics.update_network_reachable_map(reachable_map);
                 }
-                Err(e) => tracing::error!("Failed to run network check: {}", e),
+                results
+            }
+            Err(e) => {
+                tracing::error!("Failed to run network check: {}", e);
+                Vec::new()
             }
-            elapsed_time = start_time.elapsed();
         }
-
-        elapsed_time
     }
 
     /// Adjust loop period based on check duration, cap to next multiple of 30 seconds
@@ -217,19 +618,20 @@ impl NetworkMonitor {
     /// Handle one time network check request from commandline
     /// Fetches new list from
     pub async fn run_onetime(&mut self, forge_api: &str, client_config: &ForgeClientConfig) {
-        let (loopback_ip, peer_dpus) = match self
+        let (this_dpu, peer_dpus) = match self
             .find_all_dpu_info(&self.machine_id, forge_api, client_config)
             .await
         {
-            Ok((dpu_info, new_peer_dpus)) => (dpu_info.ip, new_peer_dpus),
+            Ok((dpu_info, new_peer_dpus)) => (dpu_info, new_peer_dpus),
             Err(e) => {
                 tracing::error!("Network monitor failed to get dpu info list from API {}", e);
                 return;
             }
         };
 
-        match self.monitor_concurrent(&peer_dpus, loopback_ip).await {
-            Ok(results) => self.format_results(&results, loopback_ip.to_string()),
+        let targets = probe_targets(&this_dpu, &peer_dpus, &self.config, self.pinger.as_ref());
+        match self.monitor_concurrent(&targets, this_dpu.ip).await {
+            Ok(results) => self.format_results(&results, this_dpu.ip.to_string()),
             Err(e) => tracing::error!("Failed to run network check: {}", e),
         }
     }
@@ -238,7 +640,7 @@ impl NetworkMonitor {
     /// Use a channel to handle recording ping results from concurrent ping tasks
     pub async fn monitor_concurrent(
         &self,
-        peer_dpus: &Vec<DpuInfo>,
+        targets: &[ProbeTarget],
         loopback_ip: IpAddr,
     ) -> Result<Vec<DpuPingResult>, eyre::Report> {
         let concurrent_limit = 20; // Important for not overwhelming hbn container exec
@@ -255,12 +657,16 @@ impl NetworkMonitor {
         });
 
         // Concurrent jobs to ping DPUs and get results
-        stream::iter(peer_dpus)
-            .for_each_concurrent(concurrent_limit, |peer_dpu| {
-                let peer_dpu_id = peer_dpu.id;
+        stream::iter(targets)
+            .for_each_concurrent(concurrent_limit, |target| {
+                let peer_dpu_id = target.dpu_info.id;
                 let tx_clone = tx.clone();
                 async move {
-                    match self.pinger.ping_dpu(peer_dpu.clone(), loopback_ip).await {
+                    match self
+                        .pinger
+                        .ping_dpu(target.clone(), loopback_ip, &self.config)
+                        .await
+                    {
                         Ok(ping_result) => {
                             // Send result to the channel
                             if (tx_clone.send(ping_result).await).is_err() {
@@ -293,6 +699,24 @@ impl NetworkMonitor {
         Ok(results)
     }
 
+    /// Sends the results of one check cycle to carbide-api for the reachability matrix
+    async fn report_results(
+        &self,
+        forge_api: &str,
+        client_config: &ForgeClientConfig,
+        results: &[DpuPingResult],
+    ) {
+        let report = rpc::DpuReachabilityReport {
+            dpu_machine_id: Some(self.machine_id),
+            observed_at: Some(Utc::now().into()),
+            results: results.iter().map(Into::into).collect(),
+        };
+        if let Err(e) = record_dpu_reachability_report(forge_api, client_config, report).await {
+            self.record_error_metrics(NetworkMonitorError::ApiRpcCallError, None);
+            tracing::debug!("Network monitor failed to report results to API {:#}", e);
+        }
+    }
+
     /// Format check results into JSON format
     /// Average latency outputed as seconds
     fn format_results(&self, results: &[DpuPingResult], loopback_ip: String) {
@@ -302,6 +726,9 @@ impl NetworkMonitor {
                 let mut json_result = json!({
                     "peer_dpu_id": result.dpu_info.id.clone(),
                     "loopback_ip": result.dpu_info.ip.clone(),
+                    "probe": result.probe.to_string(),
+                    "vpc_id": result.vpc.as_ref().map(|vpc| vpc.vpc_id.clone()),
+                    "target_ip": result.target_ip(),
                     "reachable": result.reachable(),
                     "loss_percent": result.loss_percent(),
                 });
@@ -311,12 +738,24 @@ impl NetworkMonitor {
                 } else {
                     json_result["average_latency"] = json!("N/A".to_string());
                 }
+                if let Some(stats) = result.latency_stats {
+                    json_result["latency_p50"] = json!(stats.p50.as_secs_f64());
+                    json_result["latency_p90"] = json!(stats.p90.as_secs_f64());
+                    json_result["latency_p99"] = json!(stats.p99.as_secs_f64());
+                    json_result["jitter"] = json!(stats.jitter.as_secs_f64());
+                }
                 json_result
             })
             .collect();
 
-        // Sort the result based on peer_dpu_id lexicographically
-        formatted_results.sort_by(|a, b| a["peer_dpu_id"].as_str().cmp(&b["peer_dpu_id"].as_str()));
+        // Sort the result based on peer_dpu_id lexicographically, then on the probe
+        formatted_results.sort_by(|a, b| {
+            a["peer_dpu_id"]
+                .as_str()
+                .cmp(&b["peer_dpu_id"].as_str())
+                .then_with(|| a["vpc_id"].as_str().cmp(&b["vpc_id"].as_str()))
+                .then_with(|| a["probe"].as_str().cmp(&b["probe"].as_str()))
+        });
 
         let final_result = json!({
             "dpu_id": self.machine_id,
@@ -411,14 +850,48 @@ pub(crate) async fn fetch_dpu_info_list(
     Ok(response.into_inner())
 }
 
+/// Sends the results of one check cycle to the API
+async fn record_dpu_reachability_report(
+    forge_api: &str,
+    client_config: &ForgeClientConfig,
+    report: rpc::DpuReachabilityReport,
+) -> Result<(), eyre::Report> {
+    let api_config = ApiConfig::new(forge_api, client_config);
+    let mut client = ForgeTlsClient::retry_build(&api_config)
+        .await
+        .map_err(|err| {
+            eyre::Report::new(err).wrap_err(format!(
+                "Could not connect to Forge API server at {forge_api}"
+            ))
+        })?;
+
+    client
+        .record_dpu_reachability_report(tonic::Request::new(report))
+        .await
+        .map_err(|err| {
+            eyre::Report::new(err)
+                .wrap_err(format!("forge_api: {forge_api}"))
+                .wrap_err("Error while executing the RecordDpuReachabilityReport gRPC call")
+        })?;
+
+    Ok(())
+}
+
 #[async_trait]
 pub trait Ping: Send + Sync {
-    /// Ping a DPU and return the ping result
+    /// Send `config.probe_count` probes to a peer DPU and return the result
     async fn ping_dpu(
         &self,
-        dpu_info: DpuInfo,
+        target: ProbeTarget,
         loopback_ip: IpAddr,
+        config: &NetworkMonitorConfig,
     ) -> Result<DpuPingResult, (NetworkMonitorError, eyre::Report)>;
+
+    /// Whether the pinger can send this kind of probe, on the underlay or on the overlay of a VPC.
+    /// Unsupported probes are skipped.
+    fn supports(&self, _probe: Probe, _overlay: bool) -> bool {
+        true
+    }
 }
 
 #[derive(ValueEnum, Debug, Clone, Copy)]
@@ -469,116 +942,267 @@ impl From<NetworkPingerType> for Arc<dyn Ping> {
     }
 }
 
-/// Pinger that binds to the oob_net0 interface
+/// Pinger that binds to the oob_net0 interface.
+/// The VPC VRFs only exist inside the HBN container, so it only probes the underlay.
 pub struct OobNetBindPinger;
 
-#[async_trait]
-impl Ping for OobNetBindPinger {
-    /// Pings a dpu from oob_net0 interface
-    ///
-    /// # Parameters
-    /// - `dpu_info`: the peer dpu that is pinged
-    /// - `_interface`: not used
-    ///
-    /// # Returns
-    /// - `Ok(DpuPingResult)`: If is successful or if all pings fail with a timeout but no other errors.
-    /// - `Err(eyre::Report)`: If fails with an unexpected error.
-    async fn ping_dpu(
+impl OobNetBindPinger {
+    /// Returns the latencies of the answered pings
+    async fn icmp(
         &self,
-        dpu_info: DpuInfo,
-        _loopback_ip: IpAddr,
-    ) -> Result<DpuPingResult, (NetworkMonitorError, eyre::Report)> {
-        let interface = "oob_net0";
-        let config = Config::builder().interface(interface).build();
-        let client = Client::new(&config).map_err(|e| {
-            let error_message = format!("Unable to build pinger with interface {interface}: {e}");
+        ip: IpAddr,
+        config: &NetworkMonitorConfig,
+    ) -> Result<Vec<Duration>, (NetworkMonitorError, eyre::Report)> {
+        let ping_config = Config::builder().interface(OOB_INTERFACE).build();
+        let client = Client::new(&ping_config).map_err(|e| {
+            let error_message =
+                format!("Unable to build pinger with interface {OOB_INTERFACE}: {e}");
             (
                 NetworkMonitorError::PingInterfaceError,
                 eyre::eyre!(error_message),
             )
         })?;
 
-        // For each IP, ping MAX_PINGS_PER_DPU times
-        let ping_futures = (0..MAX_PINGS_PER_DPU)
+        // For each IP, ping probe_count times
+        let ping_futures = (0..config.probe_count)
             .map(|seq_num| {
                 let client_clone = client.clone();
-                let ip_inner = dpu_info.ip;
+                let timeout = config.probe_timeout;
                 task::spawn(async move {
                     let mut pinger = client_clone
-                        .pinger(ip_inner, PingIdentifier(rand::random()))
+                        .pinger(ip, PingIdentifier(rand::random()))
                         .await;
-                    // Set each ping to have timeout of 1 second
-                    pinger.timeout(Duration::from_secs(1));
-                    pinger
-                        .ping(PingSequence(seq_num.try_into().unwrap()), &[])
-                        .await
+                    pinger.timeout(timeout);
+                    pinger.ping(PingSequence(seq_num as u16), &[]).await
                 })
             })
             .collect::<Vec<_>>();
 
-        // Get averaged result over all pings
         let results = join_all(ping_futures).await;
-        let mut total_duration = Duration::new(0, 0);
-        let mut success_count = 0;
-        for (_packet, duration) in results.into_iter().flatten().flatten() {
-            total_duration += duration;
-            success_count += 1;
-        }
+        Ok(results
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|(_packet, duration)| duration)
+            .collect())
+    }
+}
 
-        let average_latency = (success_count > 0).then(|| total_duration / success_count);
+#[async_trait]
+impl Ping for OobNetBindPinger {
+    /// Probes a dpu from oob_net0 interface
+    ///
+    /// # Parameters
+    /// - `target`: the peer dpu and the probe that is sent
+    /// - `_loopback_ip`: not used
+    /// - `config`: number of probes and their timeout
+    ///
+    /// # Returns
+    /// - `Ok(DpuPingResult)`: If is successful or if all probes fail with a timeout but no other errors.
+    /// - `Err(eyre::Report)`: If fails with an unexpected error.
+    async fn ping_dpu(
+        &self,
+        target: ProbeTarget,
+        _loopback_ip: IpAddr,
+        config: &NetworkMonitorConfig,
+    ) -> Result<DpuPingResult, (NetworkMonitorError, eyre::Report)> {
+        if target.vpc.is_some() {
+            return Err((
+                NetworkMonitorError::ProbeNotSupported,
+                eyre::eyre!("{OOB_INTERFACE} can't send probes on the overlay of a VPC"),
+            ));
+        }
 
-        let ping_result: DpuPingResult = DpuPingResult {
-            dpu_info,
-            success_count,
-            average_latency,
+        let ip = target.ip();
+        let latencies = match target.probe {
+            Probe::Icmp => self.icmp(ip, config).await?,
+            Probe::Tcp(port) => collect_socket_probes(
+                join_all((0..config.probe_count).map(|_| {
+                    tcp_probe(
+                        SocketAddr::new(ip, port),
+                        OOB_INTERFACE,
+                        config.probe_timeout,
+                    )
+                }))
+                .await,
+            )?,
+            Probe::Udp(port) => collect_socket_probes(
+                join_all((0..config.probe_count).map(|_| {
+                    udp_probe(
+                        SocketAddr::new(ip, port),
+                        OOB_INTERFACE,
+                        config.probe_timeout,
+                    )
+                }))
+                .await,
+            )?,
         };
 
-        Ok(ping_result)
+        Ok(DpuPingResult::new(target, config.probe_count, &latencies))
+    }
+
+    fn supports(&self, _probe: Probe, overlay: bool) -> bool {
+        !overlay
+    }
+}
+
+/// Measures the time until a TCP connection to `addr` is established or refused.
+/// A refused connection still proves that the peer is reachable.
+/// Returns `Ok(None)` if the peer did not answer within the timeout.
+async fn tcp_probe(
+    addr: SocketAddr,
+    interface: &str,
+    timeout: Duration,
+) -> io::Result<Option<Duration>> {
+    let socket = match addr {
+        SocketAddr::V4(_) => TcpSocket::new_v4()?,
+        SocketAddr::V6(_) => TcpSocket::new_v6()?,
+    };
+    socket.bind_device(Some(interface.as_bytes()))?;
+
+    let start = Instant::now();
+    match time::timeout(timeout, socket.connect(addr)).await {
+        Ok(Ok(_stream)) => Ok(Some(start.elapsed())),
+        Ok(Err(e)) if e.kind() == io::ErrorKind::ConnectionRefused => Ok(Some(start.elapsed())),
+        Ok(Err(_)) | Err(_) => Ok(None),
     }
 }
 
+/// Measures the time until the peer answers a datagram sent to `addr`, either with a datagram
+/// or with an ICMP port unreachable error.
+/// Returns `Ok(None)` if the peer did not answer within the timeout.
+async fn udp_probe(
+    addr: SocketAddr,
+    interface: &str,
+    timeout: Duration,
+) -> io::Result<Option<Duration>> {
+    let bind_addr = match addr {
+        SocketAddr::V4(_) => SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), 0),
+        SocketAddr::V6(_) => SocketAddr::new(Ipv6Addr::UNSPECIFIED.into(), 0),
+    };
+    let socket = UdpSocket::bind(bind_addr).await?;
+    socket.bind_device(Some(interface.as_bytes()))?;
+    socket.connect(addr).await?;
+
+    let start = Instant::now();
+    socket.send(UDP_PROBE_PAYLOAD).await?;
+    let mut buf = [0u8; 64];
+    match time::timeout(timeout, socket.recv(&mut buf)).await {
+        Ok(Ok(_len)) => Ok(Some(start.elapsed())),
+        Ok(Err(e)) if e.kind() == io::ErrorKind::ConnectionRefused => Ok(Some(start.elapsed())),
+        Ok(Err(_)) | Err(_) => Ok(None),
+    }
+}
+
+/// Returns the latencies of the answered TCP or UDP probes
+fn collect_socket_probes(
+    results: Vec<io::Result<Option<Duration>>>,
+) -> Result<Vec<Duration>, (NetworkMonitorError, eyre::Report)> {
+    let mut latencies = Vec::new();
+    for result in results {
+        match result {
+            Ok(Some(latency)) => latencies.push(latency),
+            Ok(None) => {}
+            Err(e) => {
+                return Err((
+                    NetworkMonitorError::PingError,
+                    eyre::eyre!("Unable to send probe from interface {OOB_INTERFACE}: {e}"),
+                ));
+            }
+        }
+    }
+    Ok(latencies)
+}
+
 /// Pinger that uses crictl to execute ping command inside HBN container
-/// from the loopback interface.
+/// from the loopback interface, or from the VRF of a VPC for overlay probes.
+/// TCP probes are sent with bash, UDP probes are not supported.
 pub struct HbnExecPinger;
+
+impl HbnExecPinger {
+    fn icmp_command(
+        target: &ProbeTarget,
+        loopback_ip: IpAddr,
+        config: &NetworkMonitorConfig,
+    ) -> Vec<String> {
+        // Binding to the VRF device makes ping use the routing table of the VPC
+        let source = match &target.vpc {
+            Some(vpc) => vpc.vrf_name(),
+            None => loopback_ip.to_string(),
+        };
+        vec![
+            "ping".to_string(),
+            "-W".to_string(),
+            // ping only supports whole seconds as timeout
+            config.probe_timeout.as_secs().max(1).to_string(),
+            "-c".to_string(),
+            config.probe_count.to_string(),
+            "-I".to_string(),
+            source,
+            target.ip().to_string(),
+        ]
+    }
+
+    /// Connects with bash's /dev/tcp and prints one `reply <microseconds>` or `lost` line per probe
+    fn tcp_command(target: &ProbeTarget, port: u16, config: &NetworkMonitorConfig) -> Vec<String> {
+        let script = format!(
+            r#"for i in $(seq {count}); do
+  start=$(date +%s%N)
+  out=$(timeout {timeout} bash -c 'exec 3<>/dev/tcp/{ip}/{port}' 2>&1); rc=$?
+  end=$(date +%s%N)
+  if [ $rc -eq 0 ] || echo "$out" | grep -q "Connection refused"; then echo "reply $(( (end - start) / 1000 ))"; else echo lost; fi
+done"#,
+            count = config.probe_count,
+            timeout = config.probe_timeout.as_secs_f64(),
+            ip = target.ip(),
+        );
+        let mut command = Vec::new();
+        if let Some(vpc) = &target.vpc {
+            command.extend(["ip".to_string(), "vrf".to_string(), "exec".to_string()]);
+            command.push(vpc.vrf_name());
+        }
+        command.extend(["bash".to_string(), "-c".to_string(), script]);
+        command
+    }
+}
+
 #[async_trait]
 impl Ping for HbnExecPinger {
-    /// Pings a dpu from loopback interface inside HBN container.
+    /// Probes a dpu from loopback interface inside HBN container.
     ///
     /// # Parameters
-    /// - `dpu_info`: the peer dpu that is pinged
-    /// - `interface`: IP address of loopback interface of HBN container that we are pinging from
+    /// - `target`: the peer dpu and the probe that is sent
+    /// - `loopback_ip`: IP address of loopback interface of HBN container that we are pinging from
+    /// - `config`: number of probes and their timeout
     ///
     /// # Returns
-    /// - `Ok(DpuPingResult)`: If is successful or if all pings fail with a timeout but no other errors.
+    /// - `Ok(DpuPingResult)`: If is successful or if all probes fail with a timeout but no other errors.
     /// - `Err(eyre::Report)`: If fails with an unexpected error.
     async fn ping_dpu(
         &self,
-        dpu_info: DpuInfo,
+        target: ProbeTarget,
         loopback_ip: IpAddr,
+        config: &NetworkMonitorConfig,
     ) -> Result<DpuPingResult, (NetworkMonitorError, eyre::Report)> {
+        let command = match target.probe {
+            Probe::Icmp => Self::icmp_command(&target, loopback_ip, config),
+            Probe::Tcp(port) => Self::tcp_command(&target, port, config),
+            Probe::Udp(_) => {
+                return Err((
+                    NetworkMonitorError::ProbeNotSupported,
+                    eyre::eyre!("UDP probes are not supported from the HBN container"),
+                ));
+            }
+        };
+
         let container_id: String = hbn::get_hbn_container_id()
             .await
             .wrap_err("Failed to get hbn container id")
             .map_err(|e| (NetworkMonitorError::HbnContainerIdNotFound, e))?;
 
-        match hbn::run_in_container(
-            &container_id,
-            &[
-                "ping",
-                "-W",
-                "1",
-                "-c",
-                &MAX_PINGS_PER_DPU.to_string(),
-                "-I",
-                &loopback_ip.to_string(),
-                &dpu_info.ip.to_string(),
-            ],
-            true,
-        )
-        .await
-        {
-            Ok(stdout) => parse_ping_stdout(dpu_info, &stdout)
-                .map_err(|e| (NetworkMonitorError::PingOutputParseError, e)),
+        let command: Vec<&str> = command.iter().map(String::as_str).collect();
+        let stdout = match hbn::run_in_container(&container_id, &command, true).await {
+            Ok(stdout) => stdout,
             Err(err) => {
                 // Ping fail could be 100% loss or error, 100% loss is treated as unreachable but not error
                 let err_string = format!("{err}");
@@ -595,60 +1219,85 @@ impl Ping for HbnExecPinger {
                     )
                 })?;
 
-                let stdout = err_re
+                err_re
                     .captures(&err_string)
-                    .and_then(|caps| caps.get(4).map(|m| m.as_str()))
+                    .and_then(|caps| caps.get(4).map(|m| m.as_str().to_string()))
                     .ok_or_else(|| {
                         (
                             NetworkMonitorError::HbnContainerCommandExecError,
                             eyre::eyre!("Error running ping in container: {}", err),
                         )
-                    })?;
-
-                parse_ping_stdout(dpu_info, stdout)
-                    .map_err(|e| (NetworkMonitorError::PingOutputParseError, e))
+                    })?
             }
-        }
+        };
+
+        let result = match target.probe {
+            Probe::Icmp => parse_ping_stdout(target, &stdout),
+            _ => parse_tcp_probe_stdout(target, &stdout),
+        };
+        result.map_err(|e| (NetworkMonitorError::PingOutputParseError, e))
+    }
+
+    fn supports(&self, probe: Probe, _overlay: bool) -> bool {
+        !matches!(probe, Probe::Udp(_))
     }
 }
 
 /// Parse ping standard output to valid dpu ping result,
-/// including number of successful pings and average latency.
-pub fn parse_ping_stdout(dpu_info: DpuInfo, stdout: &str) -> Result<DpuPingResult, eyre::Report> {
-    let summary_re = Regex::new(r"(\d+) packets transmitted, (\d+) received, (\d+)% packet loss")?;
-    let rtt_re = Regex::new(r"rtt min/avg/max/mdev = [\d\.]+/([\d\.]+)/[\d\.]+/[\d\.]+ ms")?;
-
-    let mut lines_iter = stdout.lines().rev();
-    let rtt_line = lines_iter
-        .next()
-        .ok_or_else(|| eyre::eyre!("Failed to find RTT line"))?;
-    let summary_line = lines_iter
-        .next()
+/// including number of successful pings and the latency of each reply.
+pub fn parse_ping_stdout(target: ProbeTarget, stdout: &str) -> Result<DpuPingResult, eyre::Report> {
+    let summary_re = Regex::new(r"(\d+) packets transmitted, (\d+) received")?;
+    let reply_re = Regex::new(r"icmp_seq=\d+ .*time=([\d\.]+) ms")?;
+
+    let summary = summary_re
+        .captures(stdout)
         .ok_or_else(|| eyre::eyre!("Failed to find summary line"))?;
+    let sent = summary[1]
+        .parse::<u32>()
+        .wrap_err("Failed to parse number of transmitted packets")?;
+    let success_count = summary[2]
+        .parse::<u32>()
+        .wrap_err("Failed to parse number of success packets")?;
+
+    let latencies: Vec<Duration> = reply_re
+        .captures_iter(stdout)
+        .filter_map(|caps| caps[1].parse::<f64>().ok())
+        .map(|latency_ms| Duration::from_micros((latency_ms * 1000.0).round() as u64))
+        .collect();
+    if latencies.len() != success_count as usize {
+        return Err(eyre::eyre!(
+            "Found {} replies, but summary reports {success_count}",
+            latencies.len()
+        ));
+    }
 
-    let success_count = summary_re
-        .captures(summary_line)
-        .and_then(|caps| caps.get(2).and_then(|m| m.as_str().parse::<u32>().ok()))
-        .ok_or_else(|| eyre::eyre!("Failed to parse number of success packets"))?;
+    Ok(DpuPingResult::new(target, sent, &latencies))
+}
 
-    if success_count == 0 {
-        return Ok(DpuPingResult {
-            dpu_info,
-            success_count,
-            average_latency: None,
-        });
+/// Parse the output of the TCP probe script, one `reply <microseconds>` or `lost` line per probe
+pub fn parse_tcp_probe_stdout(
+    target: ProbeTarget,
+    stdout: &str,
+) -> Result<DpuPingResult, eyre::Report> {
+    let mut sent = 0;
+    let mut latencies = Vec::new();
+    for line in stdout
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+    {
+        sent += 1;
+        if line == "lost" {
+            continue;
+        }
+        let micros = line
+            .strip_prefix("reply ")
+            .and_then(|micros| micros.parse::<u64>().ok())
+            .ok_or_else(|| eyre::eyre!("Unexpected TCP probe output: {line}"))?;
+        latencies.push(Duration::from_micros(micros));
     }
 
-    let latency = rtt_re
-        .captures(rtt_line)
-        .and_then(|caps| caps.get(1).and_then(|m| m.as_str().parse::<f64>().ok()))
-        .ok_or_else(|| eyre::eyre!("Failed to average latency"))?;
-
-    Ok(DpuPingResult {
-        dpu_info,
-        success_count,
-        average_latency: Some(Duration::from_secs_f64(latency / 1000.0)),
-    })
+    Ok(DpuPingResult::new(target, sent, &latencies))
 }
 
 #[derive(Debug)]
@@ -660,6 +1309,7 @@ pub enum NetworkMonitorError {
     PingError,
     PingInterfaceError,
     PingOutputParseError,
+    ProbeNotSupported,
     ResultRecordChannelSendError,
     TaskJoinError,
     UnknownError,
@@ -670,3 +1320,246 @@ impl fmt::Display for NetworkMonitorError {
         fmt::Debug::fmt(self, f)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PEER_DPU_ID: &str = "fm100dsjd1vuk6gklgvh0ao8t7r7tk1pt101ub5ck0g3j7lqcm8h3rf1p8g";
+    const THIS_DPU_ID: &str = "fm100dsvstfujf6mis0gpsoi81tadmllicv7rqo4s7gc16gi0t2478672vg";
+
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    fn vpc_target(vpc_id: &str, ip: &str) -> VpcProbeTarget {
+        VpcProbeTarget {
+            vpc_id: vpc_id.to_string(),
+            vni: 1000,
+            ip: ip.parse().unwrap(),
+        }
+    }
+
+    fn dpu(id: &str, ip: &str, vpc_probe_targets: Vec<VpcProbeTarget>) -> DpuInfo {
+        DpuInfo {
+            id: id.parse().unwrap(),
+            ip: ip.parse().unwrap(),
+            vpc_probe_targets,
+        }
+    }
+
+    fn icmp_target() -> ProbeTarget {
+        ProbeTarget {
+            dpu_info: dpu(PEER_DPU_ID, "10.0.0.2", vec![]),
+            probe: Probe::Icmp,
+            vpc: None,
+        }
+    }
+
+    #[test]
+    fn test_latency_stats() {
+        assert_eq!(LatencyStats::from_latencies(&[]), None);
+
+        let stats = LatencyStats::from_latencies(&[ms(1)]).unwrap();
+        assert_eq!(stats.p50, ms(1));
+        assert_eq!(stats.p99, ms(1));
+        assert_eq!(stats.jitter, Duration::ZERO);
+
+        // Jitter uses the order the probes were sent in, percentiles the sorted latencies
+        let latencies: Vec<_> = [4, 2, 6, 8, 10, 1, 3, 5, 7, 9]
+            .into_iter()
+            .map(ms)
+            .collect();
+        let stats = LatencyStats::from_latencies(&latencies).unwrap();
+        assert_eq!(stats.p50, ms(5));
+        assert_eq!(stats.p90, ms(9));
+        assert_eq!(stats.p99, ms(10));
+        // |4-2| + |2-6| + |6-8| + |8-10| + |10-1| + |1-3| + |3-5| + |5-7| + |7-9| = 29
+        assert_eq!(stats.jitter, ms(29) / 9);
+    }
+
+    #[test]
+    fn test_parse_ping_stdout() {
+        let stdout = "PING 10.0.0.2 (10.0.0.2) from 10.0.0.1 : 56(84) bytes of data.
+64 bytes from 10.0.0.2: icmp_seq=1 ttl=64 time=0.210 ms
+64 bytes from 10.0.0.2: icmp_seq=3 ttl=64 time=0.190 ms
+64 bytes from 10.0.0.2: icmp_seq=4 ttl=64 time=0.410 ms
+
+--- 10.0.0.2 ping statistics ---
+5 packets transmitted, 3 received, 40% packet loss, time 4085ms
+rtt min/avg/max/mdev = 0.190/0.270/0.410/0.099 ms
+";
+        let result = parse_ping_stdout(icmp_target(), stdout).unwrap();
+        assert_eq!(result.sent, 5);
+        assert_eq!(result.success_count, 3);
+        assert!((result.loss_percent() - 0.4).abs() < f64::EPSILON);
+        let stats = result.latency_stats.unwrap();
+        assert_eq!(stats.p50, Duration::from_micros(210));
+        assert_eq!(stats.p99, Duration::from_micros(410));
+
+        let stdout = "PING 10.0.0.2 (10.0.0.2) from 10.0.0.1 : 56(84) bytes of data.
+
+--- 10.0.0.2 ping statistics ---
+5 packets transmitted, 0 received, 100% packet loss, time 4100ms
+
+";
+        let result = parse_ping_stdout(icmp_target(), stdout).unwrap();
+        assert!(!result.reachable());
+        assert_eq!(result.average_latency, None);
+        assert!(result.latency_stats.is_none());
+
+        assert!(parse_ping_stdout(icmp_target(), "ping: unknown iface").is_err());
+    }
+
+    #[test]
+    fn test_parse_tcp_probe_stdout() {
+        let result = parse_tcp_probe_stdout(icmp_target(), "reply 250\nlost\nreply 350\n").unwrap();
+        assert_eq!(result.sent, 3);
+        assert_eq!(result.success_count, 2);
+        assert_eq!(result.average_latency, Some(Duration::from_micros(300)));
+        assert_eq!(
+            result.latency_stats.unwrap().jitter,
+            Duration::from_micros(100)
+        );
+
+        assert!(parse_tcp_probe_stdout(icmp_target(), "bash: oops\n").is_err());
+    }
+
+    #[test]
+    fn test_config_from_rpc() {
+        assert_eq!(
+            NetworkMonitorConfig::from(&rpc::DpuNetworkMonitorConfig::default()),
+            NetworkMonitorConfig::default()
+        );
+
+        let config = NetworkMonitorConfig::from(&rpc::DpuNetworkMonitorConfig {
+            probe_count: 10,
+            probe_timeout_ms: 500,
+            peer_refresh_interval_secs: 60,
+            probes: vec![
+                rpc::DpuNetworkProbe {
+                    protocol: rpc::DpuNetworkProbeProtocol::Tcp.into(),
+                    port: Some(179),
+                },
+                // Invalid, missing the port
+                rpc::DpuNetworkProbe {
+                    protocol: rpc::DpuNetworkProbeProtocol::Udp.into(),
+                    port: None,
+                },
+                rpc::DpuNetworkProbe {
+                    protocol: rpc::DpuNetworkProbeProtocol::Icmp.into(),
+                    port: None,
+                },
+            ],
+            vpc_overlay_probes: true,
+            report_results: true,
+            max_peers: 8,
+        });
+        assert_eq!(config.probe_count, 10);
+        assert_eq!(config.probe_timeout, ms(500));
+        assert_eq!(config.peer_refresh_interval, Duration::from_secs(60));
+        assert_eq!(config.probes, vec![Probe::Tcp(179), Probe::Icmp]);
+        assert!(config.vpc_overlay_probes);
+        assert!(config.report_results);
+        assert_eq!(config.max_peers, 8);
+    }
+
+    #[test]
+    fn test_probe_targets() {
+        let this_dpu = dpu(
+            THIS_DPU_ID,
+            "10.0.0.1",
+            vec![vpc_target("vpc-a", "192.168.0.1")],
+        );
+        let peer = dpu(
+            PEER_DPU_ID,
+            "10.0.0.2",
+            vec![
+                vpc_target("vpc-a", "192.168.0.2"),
+                // Only one probe per VPC
+                vpc_target("vpc-a", "192.168.0.3"),
+                // Not shared with this DPU
+                vpc_target("vpc-b", "192.168.1.2"),
+            ],
+        );
+        let mut config = NetworkMonitorConfig {
+            probes: vec![Probe::Icmp, Probe::Udp(4789)],
+            ..Default::default()
+        };
+
+        let targets = probe_targets(&this_dpu, &[peer.clone()], &config, &HbnExecPinger);
+        let probes: Vec<_> = targets.iter().map(|t| (t.probe, t.ip())).collect();
+        assert_eq!(probes, vec![(Probe::Icmp, peer.ip)]);
+
+        config.vpc_overlay_probes = true;
+        let targets = probe_targets(&this_dpu, &[peer.clone()], &config, &HbnExecPinger);
+        let probes: Vec<_> = targets.iter().map(|t| (t.probe, t.ip())).collect();
+        assert_eq!(
+            probes,
+            vec![
+                (Probe::Icmp, peer.ip),
+                (Probe::Icmp, "192.168.0.2".parse().unwrap())
+            ]
+        );
+
+        // The oob_net0 pinger can't reach the overlay
+        let targets = probe_targets(&this_dpu, &[peer.clone()], &config, &OobNetBindPinger);
+        let probes: Vec<_> = targets.iter().map(|t| (t.probe, t.ip())).collect();
+        assert_eq!(
+            probes,
+            vec![(Probe::Icmp, peer.ip), (Probe::Udp(4789), peer.ip)]
+        );
+    }
+
+    #[test]
+    fn test_sample_peers() {
+        let this_dpu = dpu(THIS_DPU_ID, "10.0.0.3", vec![]);
+        let peers: Vec<_> = ["10.0.0.5", "10.0.0.1", "10.0.0.4", "10.0.0.2"]
+            .into_iter()
+            .map(|ip| dpu(PEER_DPU_ID, ip, vec![]))
+            .collect();
+        let sampled_ips = |cycle| -> Vec<String> {
+            sample_peers(&this_dpu, &peers, 3, cycle)
+                .iter()
+                .map(|peer| peer.ip.to_string())
+                .collect()
+        };
+
+        // The window starts after this DPU and rotates with every cycle
+        assert_eq!(sampled_ips(0), vec!["10.0.0.4", "10.0.0.5", "10.0.0.1"]);
+        assert_eq!(sampled_ips(1), vec!["10.0.0.2", "10.0.0.4", "10.0.0.5"]);
+        assert_eq!(sampled_ips(2), vec!["10.0.0.1", "10.0.0.2", "10.0.0.4"]);
+
+        // All peers are probed if there are few enough
+        assert_eq!(sample_peers(&this_dpu, &peers, 4, 1), peers);
+    }
+
+    #[test]
+    fn test_merge_results() {
+        let peer = dpu(PEER_DPU_ID, "10.0.0.2", vec![]);
+        let gone = dpu(THIS_DPU_ID, "10.0.0.3", vec![]);
+        let result = |dpu_info: &DpuInfo, success_count| DpuPingResult {
+            dpu_info: dpu_info.clone(),
+            probe: Probe::Icmp,
+            vpc: None,
+            sent: 5,
+            success_count,
+            average_latency: None,
+            latency_stats: None,
+        };
+
+        // Results of a peer which is no longer known are dropped
+        let merged = merge_results(
+            vec![result(&peer, 5), result(&gone, 5)],
+            vec![],
+            std::slice::from_ref(&peer),
+        );
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged[0].dpu_info.ip, peer.ip);
+
+        // New results replace the ones of earlier cycles
+        let merged = merge_results(merged, vec![result(&peer, 0)], std::slice::from_ref(&peer));
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged[0].success_count, 0);
+    }
+}
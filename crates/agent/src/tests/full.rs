@@ -485,6 +485,8 @@ async fn handle_netconf(AxumState(state): AxumState<Arc<Mutex<State>>>) -> impl
         network_security_group: None,
         internal_uuid: None,
         mtu: None,
+        ipv6: None,
+        ipv6_prefix: None,
     };
     assert_eq!(admin_interface.svi_ip, None);
 
@@ -653,6 +655,8 @@ async fn handle_netconf(AxumState(state): AxumState<Arc<Mutex<State>>>) -> impl
         }),
         internal_uuid: None,
         mtu: None,
+        ipv6: None,
+        ipv6_prefix: None,
     };
 
     let network_security_policy_overrides = vec![
@@ -776,6 +780,8 @@ async fn handle_netconf(AxumState(state): AxumState<Arc<Mutex<State>>>) -> impl
                 tenant_organization_id: "Forge-simulation-tenant".to_string(),
                 hostname: None,
                 tenant_keyset_ids: vec![],
+                metadata_token_required: None,
+                metadata_token_hop_limit: None,
             }),
             os: Some(rpc::forge::OperatingSystem {
                 phone_home_enabled: false,
@@ -845,6 +851,8 @@ async fn handle_netconf(AxumState(state): AxumState<Arc<Mutex<State>>>) -> impl
 
     let netconf = rpc::forge::ManagedHostNetworkConfigResponse {
         site_global_vpc_vni: None,
+        fmds_token_required: false,
+        fmds_token_hop_limit: None,
         asn: 65535,
         datacenter_asn: 11414,
         common_internal_route_target: Some(rpc_common::RouteTarget {
@@ -910,6 +918,7 @@ async fn handle_netconf(AxumState(state): AxumState<Arc<Mutex<State>>>) -> impl
         min_dpu_functioning_links: None,
         is_primary_dpu: true,
         dpu_network_pinger_type: Some("HbnExec".to_string()),
+        dpu_network_monitor_config: None,
         internet_l3_vni: Some(1337),
         stateful_acls_enabled: true,
         instance: Some(instance),
@@ -961,10 +970,12 @@ async fn handle_get_dpu_info_list(
             DpuInfo {
                 id: "fm100dsvstfujf6mis0gpsoi81tadmllicv7rqo4s7gc16gi0t2478672vg".to_string(),
                 loopback_ip: "172.20.0.119".to_string(),
+                vpc_probe_targets: vec![],
             },
             DpuInfo {
                 id: "fm100dsjd1vuk6gklgvh0ao8t7r7tk1pt101ub5ck0g3j7lqcm8h3rf1p8g".to_string(),
                 loopback_ip: "172.20.0.200".to_string(),
+                vpc_probe_targets: vec![],
             },
         ],
     })
@@ -38,7 +38,9 @@ use tonic::async_trait;
 use tracing::info;
 
 use crate::instrumentation::NetworkMonitorMetricsState;
-use crate::network_monitor::{DpuInfo, DpuPingResult, NetworkMonitor, NetworkMonitorError, Ping};
+use crate::network_monitor::{
+    DpuPingResult, NetworkMonitor, NetworkMonitorConfig, NetworkMonitorError, Ping, ProbeTarget,
+};
 use crate::tests::common;
 
 // DPU machine ids for testing purposes
@@ -166,10 +168,12 @@ async fn handle_get_dpu_info_list(
             rpc::DpuInfo {
                 id: DPU_ID.to_string(),
                 loopback_ip: "172.20.0.119".to_string(),
+                vpc_probe_targets: vec![],
             },
             rpc::DpuInfo {
                 id: DEST_DPU_ID.to_string(),
                 loopback_ip: "172.20.0.200".to_string(),
+                vpc_probe_targets: vec![],
             },
         ],
     })
@@ -317,15 +321,13 @@ pub struct MockPinger;
 impl Ping for MockPinger {
     async fn ping_dpu(
         &self,
-        dpu_info: DpuInfo,
-        _interface: IpAddr,
+        target: ProbeTarget,
+        _loopback_ip: IpAddr,
+        config: &NetworkMonitorConfig,
     ) -> Result<DpuPingResult, (NetworkMonitorError, eyre::Report)> {
-        info!("Received ping request for {}", dpu_info);
-        let ping_result = DpuPingResult {
-            dpu_info,
-            success_count: 1,
-            average_latency: Some(Duration::from_millis(1)),
-        };
+        info!("Received ping request for {}", target.dpu_info);
+        let ping_result =
+            DpuPingResult::new(target, config.probe_count, &[Duration::from_millis(1)]);
 
         Ok(ping_result)
     }
@@ -0,0 +1,18 @@
+-- Consecutive identical outcomes of a Machine are collapsed into a single row:
+-- `time` is when the outcome was first recorded, `last_time` when it was last
+-- repeated and `repeat_count` how often it was produced in a row.
+-- Old entries are pruned periodically by the state controller rather than on
+-- every insert.
+CREATE TABLE machine_state_handler_outcome_history (
+    id BIGINT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+    machine_id VARCHAR(256) NOT NULL,
+    state jsonb NOT NULL,
+    outcome jsonb NOT NULL,
+    iteration_id BIGINT,
+    handler_latency_us BIGINT NOT NULL,
+    repeat_count INTEGER NOT NULL DEFAULT 1,
+    time TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_time TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_machine_state_handler_outcome_history_machine_id ON machine_state_handler_outcome_history (machine_id, id);
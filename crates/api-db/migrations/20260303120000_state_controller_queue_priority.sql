@@ -0,0 +1,26 @@
+-- Introduces a priority column for all queued object tables.
+-- Objects with a higher priority are dequeued ahead of the periodically enqueued ones.
+
+ALTER TABLE machine_state_controller_queued_objects
+    ADD COLUMN priority SMALLINT NOT NULL DEFAULT 0;
+
+ALTER TABLE network_segments_controller_queued_objects
+    ADD COLUMN priority SMALLINT NOT NULL DEFAULT 0;
+
+ALTER TABLE ib_partition_controller_queued_objects
+    ADD COLUMN priority SMALLINT NOT NULL DEFAULT 0;
+
+ALTER TABLE dpa_interfaces_controller_queued_objects
+    ADD COLUMN priority SMALLINT NOT NULL DEFAULT 0;
+
+ALTER TABLE power_shelf_controller_queued_objects
+    ADD COLUMN priority SMALLINT NOT NULL DEFAULT 0;
+
+ALTER TABLE switch_controller_queued_objects
+    ADD COLUMN priority SMALLINT NOT NULL DEFAULT 0;
+
+ALTER TABLE rack_controller_queued_objects
+    ADD COLUMN priority SMALLINT NOT NULL DEFAULT 0;
+
+ALTER TABLE attestation_controller_queued_objects
+    ADD COLUMN priority SMALLINT NOT NULL DEFAULT 0;
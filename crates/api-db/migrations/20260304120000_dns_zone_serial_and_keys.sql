@@ -0,0 +1,165 @@
+-- Keeps the SOA serial of a domain in sync with the records served from the
+-- dns_records view, so that secondaries which transfer the zone via AXFR notice
+-- when it changed. Also introduces a table holding DNSSEC keys for domains.
+--
+-- Record changes only mark the domain as changed in `dns_domain_changes`. The
+-- serial is bumped lazily, once for all changes since the last bump, when the
+-- zone is read through `getDomainInfo`, `list` or `getUpdatedMasters`. This
+-- avoids updating (and locking) the `domains` row on every record change.
+
+CREATE TABLE dns_domain_changes (
+    domain_id uuid PRIMARY KEY REFERENCES domains(id) ON DELETE CASCADE,
+    changed TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- The last serial PowerDNS sent a NOTIFY for, see `setNotified`
+ALTER TABLE domains ADD COLUMN notified_serial BIGINT;
+
+-- Bumps the SOA serial of a domain using the same `YYYYMMDDnn` scheme as
+-- `SoaRecord::increment_serial`.
+CREATE OR REPLACE FUNCTION dns_bump_domain_serial(p_domain_id uuid)
+RETURNS void AS
+$body$
+BEGIN
+    UPDATE domains
+    SET soa = jsonb_set(
+        soa,
+        '{serial}',
+        to_jsonb(GREATEST(
+            COALESCE((soa ->> 'serial')::bigint, 0) + 1,
+            to_char(NOW() AT TIME ZONE 'UTC', 'YYYYMMDD01')::bigint
+        ))
+    )
+    WHERE id = p_domain_id AND deleted IS NULL AND soa ? 'serial';
+END;
+$body$
+LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION dns_mark_domain_changed(p_domain_id uuid)
+RETURNS void AS
+$body$
+BEGIN
+    INSERT INTO dns_domain_changes (domain_id) VALUES (p_domain_id)
+        ON CONFLICT (domain_id) DO NOTHING;
+END;
+$body$
+LANGUAGE plpgsql;
+
+-- Bumps the serial of all changed domains, or only of the domain with the given name
+CREATE OR REPLACE FUNCTION dns_apply_domain_changes(p_domain_name text)
+RETURNS void AS
+$body$
+DECLARE
+    v_domain_id uuid;
+BEGIN
+    FOR v_domain_id IN
+        DELETE FROM dns_domain_changes c USING domains d
+            WHERE c.domain_id = d.id AND (p_domain_name IS NULL OR d.name = p_domain_name)
+            RETURNING c.domain_id
+    LOOP
+        PERFORM dns_bump_domain_serial(v_domain_id);
+    END LOOP;
+END;
+$body$
+LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION dns_machine_interfaces_mark_changed()
+RETURNS TRIGGER AS
+$body$
+BEGIN
+    IF TG_OP IN ('UPDATE', 'DELETE') AND OLD.domain_id IS NOT NULL THEN
+        PERFORM dns_mark_domain_changed(OLD.domain_id);
+    END IF;
+    IF TG_OP IN ('INSERT', 'UPDATE') AND NEW.domain_id IS NOT NULL
+        AND (TG_OP = 'INSERT' OR NEW.domain_id IS DISTINCT FROM OLD.domain_id) THEN
+        PERFORM dns_mark_domain_changed(NEW.domain_id);
+    END IF;
+    RETURN NULL;
+END;
+$body$
+LANGUAGE plpgsql;
+
+CREATE TRIGGER t_dns_machine_interfaces_mark_changed
+  AFTER INSERT OR DELETE ON machine_interfaces
+  FOR EACH ROW EXECUTE PROCEDURE dns_machine_interfaces_mark_changed();
+
+CREATE TRIGGER t_dns_machine_interfaces_update_mark_changed
+  AFTER UPDATE OF domain_id, hostname, machine_id, primary_interface ON machine_interfaces
+  FOR EACH ROW
+  WHEN (OLD.domain_id IS DISTINCT FROM NEW.domain_id
+        OR OLD.hostname IS DISTINCT FROM NEW.hostname
+        OR OLD.machine_id IS DISTINCT FROM NEW.machine_id
+        OR OLD.primary_interface IS DISTINCT FROM NEW.primary_interface)
+  EXECUTE PROCEDURE dns_machine_interfaces_mark_changed();
+
+-- Address and record metadata changes are attributed to the domain of the interface they belong to
+CREATE OR REPLACE FUNCTION dns_interface_children_mark_changed()
+RETURNS TRIGGER AS
+$body$
+DECLARE
+    v_domain_id uuid;
+BEGIN
+    IF TG_TABLE_NAME = 'machine_interface_addresses' THEN
+        SELECT domain_id INTO v_domain_id FROM machine_interfaces
+            WHERE id = COALESCE(NEW.interface_id, OLD.interface_id);
+    ELSE
+        SELECT domain_id INTO v_domain_id FROM machine_interfaces
+            WHERE id = COALESCE(NEW.id, OLD.id);
+    END IF;
+    IF v_domain_id IS NOT NULL THEN
+        PERFORM dns_mark_domain_changed(v_domain_id);
+    END IF;
+    RETURN NULL;
+END;
+$body$
+LANGUAGE plpgsql;
+
+CREATE TRIGGER t_dns_machine_interface_addresses_mark_changed
+  AFTER INSERT OR UPDATE OR DELETE ON machine_interface_addresses
+  FOR EACH ROW EXECUTE PROCEDURE dns_interface_children_mark_changed();
+
+CREATE TRIGGER t_dns_record_metadata_mark_changed
+  AFTER INSERT OR UPDATE OR DELETE ON dns_record_metadata
+  FOR EACH ROW EXECUTE PROCEDURE dns_interface_children_mark_changed();
+
+-- BMC records are derived from the machine topology, which is updated frequently.
+-- Only a change of the BMC IP affects the served records.
+CREATE OR REPLACE FUNCTION dns_machine_topologies_mark_changed()
+RETURNS TRIGGER AS
+$body$
+DECLARE
+    v_domain_id uuid;
+BEGIN
+    FOR v_domain_id IN
+        SELECT DISTINCT domain_id FROM machine_interfaces
+            WHERE machine_id = COALESCE(NEW.machine_id, OLD.machine_id) AND domain_id IS NOT NULL
+    LOOP
+        PERFORM dns_mark_domain_changed(v_domain_id);
+    END LOOP;
+    RETURN NULL;
+END;
+$body$
+LANGUAGE plpgsql;
+
+CREATE TRIGGER t_dns_machine_topologies_insert_mark_changed
+  AFTER INSERT OR DELETE ON machine_topologies
+  FOR EACH ROW EXECUTE PROCEDURE dns_machine_topologies_mark_changed();
+
+CREATE TRIGGER t_dns_machine_topologies_update_mark_changed
+  AFTER UPDATE OF topology ON machine_topologies
+  FOR EACH ROW
+  WHEN ((OLD.topology -> 'bmc_info' ->> 'ip') IS DISTINCT FROM (NEW.topology -> 'bmc_info' ->> 'ip'))
+  EXECUTE PROCEDURE dns_machine_topologies_mark_changed();
+
+-- DNSSEC keys served to PowerDNS via `getDomainKeys`.
+-- The private keys are kept in the credential provider, keyed by domain and key ID.
+CREATE TABLE dns_domain_keys (
+    id INTEGER GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+    domain_id uuid NOT NULL REFERENCES domains(id) ON DELETE CASCADE,
+    flags INTEGER NOT NULL,
+    active BOOLEAN NOT NULL DEFAULT TRUE,
+    published BOOLEAN NOT NULL DEFAULT TRUE,
+    created TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_dns_domain_keys_domain_id ON dns_domain_keys (domain_id);
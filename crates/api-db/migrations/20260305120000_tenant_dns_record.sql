@@ -0,0 +1,37 @@
+-- DNS records which tenants define inside the domains of their VPCs.
+-- They are served alongside the records of the dns_records view.
+CREATE TABLE tenant_dns_record (
+    id uuid PRIMARY KEY,
+    vpc_id uuid NOT NULL REFERENCES vpcs(id) ON DELETE CASCADE,
+    domain_id uuid NOT NULL REFERENCES domains(id) ON DELETE CASCADE,
+    q_name VARCHAR(255) NOT NULL,
+    q_type VARCHAR(10) NOT NULL,
+    ttl INTEGER NOT NULL,
+    content TEXT NOT NULL,
+    created TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (q_name, q_type, content)
+);
+
+CREATE INDEX idx_tenant_dns_record_q_name ON tenant_dns_record (q_name);
+CREATE INDEX idx_tenant_dns_record_vpc_id ON tenant_dns_record (vpc_id);
+CREATE INDEX idx_tenant_dns_record_domain_id ON tenant_dns_record (domain_id);
+
+CREATE OR REPLACE FUNCTION dns_tenant_dns_record_bump_serial()
+RETURNS TRIGGER AS
+$body$
+BEGIN
+    IF TG_OP IN ('UPDATE', 'DELETE') THEN
+        PERFORM dns_bump_domain_serial(OLD.domain_id);
+    END IF;
+    IF TG_OP = 'INSERT' OR (TG_OP = 'UPDATE' AND NEW.domain_id IS DISTINCT FROM OLD.domain_id) THEN
+        PERFORM dns_bump_domain_serial(NEW.domain_id);
+    END IF;
+    RETURN NULL;
+END;
+$body$
+LANGUAGE plpgsql;
+
+CREATE TRIGGER t_dns_tenant_dns_record_bump_serial
+  AFTER INSERT OR UPDATE OR DELETE ON tenant_dns_record
+  FOR EACH ROW EXECUTE PROCEDURE dns_tenant_dns_record_bump_serial();
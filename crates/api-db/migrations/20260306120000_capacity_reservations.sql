@@ -0,0 +1,18 @@
+-- Capacity set aside for a tenant on the Machines of an InstanceType.
+-- Reservations hold a number of Machines rather than specific Machines: as long as a reservation
+-- is active, other tenants can only allocate the Machines of the InstanceType which are not
+-- needed to fulfill it.
+CREATE TABLE capacity_reservations (
+    id uuid PRIMARY KEY,
+    tenant_organization_id VARCHAR NOT NULL,
+    instance_type_id VARCHAR(64) NOT NULL REFERENCES instance_types(id),
+    count INTEGER NOT NULL CHECK (count > 0),
+    -- The reservation ends by itself once this time has passed
+    expires_at TIMESTAMPTZ,
+    created TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    released TIMESTAMPTZ
+);
+
+CREATE INDEX idx_capacity_reservations_instance_type_id ON capacity_reservations (instance_type_id)
+    WHERE released IS NULL;
+CREATE INDEX idx_capacity_reservations_tenant_organization_id ON capacity_reservations (tenant_organization_id);
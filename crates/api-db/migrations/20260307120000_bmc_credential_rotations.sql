@@ -0,0 +1,9 @@
+-- Tracks the automatic rotation of BMC root credentials, keyed by the MAC address
+-- of the BMC like the credentials themselves.
+CREATE TABLE bmc_credential_rotations (
+    bmc_mac_address macaddr PRIMARY KEY,
+    rotated_at TIMESTAMPTZ,
+    last_attempt_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    consecutive_failures INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT
+);
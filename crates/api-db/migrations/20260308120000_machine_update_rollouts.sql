@@ -0,0 +1,28 @@
+-- Staged rollouts of machine updates, see MachineUpdateManager.
+-- Only the most recent rollout is ever active.
+CREATE TYPE machine_update_rollout_state AS ENUM (
+    'running',
+    'paused',
+    'halted',
+    'aborted',
+    'completed'
+);
+
+CREATE TABLE machine_update_rollouts (
+    id BIGINT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+    state machine_update_rollout_state NOT NULL DEFAULT 'running',
+    -- Index of the wave updates are currently started for, 0 being the first
+    wave INTEGER NOT NULL DEFAULT 0,
+    -- When all updates of the current wave finished, which starts its soak period
+    wave_completed_at TIMESTAMPTZ,
+    -- Hosts whose update was started by the rollout, with the wave they belong to
+    machines JSONB NOT NULL DEFAULT '[]',
+    -- Hosts which were unhealthy after their update when the rollout got halted
+    unhealthy_machines JSONB NOT NULL DEFAULT '[]',
+    -- Hosts which are no longer considered for halting the rollout, because an operator
+    -- resumed the rollout while they were unhealthy
+    acknowledged_machines JSONB NOT NULL DEFAULT '[]',
+    state_reason TEXT,
+    created TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
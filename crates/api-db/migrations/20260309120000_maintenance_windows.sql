@@ -0,0 +1,29 @@
+-- Maintenance windows restrict when disruptive work, like firmware updates, DPU
+-- reprovisioning and DPU remediations, may start on hosts.
+CREATE TYPE maintenance_window_kind AS ENUM (
+    'maintenance',
+    'blackout'
+);
+
+CREATE TYPE maintenance_window_scope AS ENUM (
+    'site',
+    'rack',
+    'tenant'
+);
+
+CREATE TABLE maintenance_windows (
+    name TEXT PRIMARY KEY,
+    kind maintenance_window_kind NOT NULL,
+    scope maintenance_window_scope NOT NULL,
+    -- Rack ID or tenant organization ID, NULL for site wide windows
+    scope_id TEXT,
+    -- Cron expression of when the window opens, NULL for one-off windows
+    schedule TEXT,
+    duration_minutes INTEGER NOT NULL DEFAULT 0,
+    timezone TEXT NOT NULL DEFAULT 'UTC',
+    starts_at TIMESTAMPTZ,
+    ends_at TIMESTAMPTZ,
+    description TEXT NOT NULL DEFAULT '',
+    created TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK ((scope = 'site') = (scope_id IS NULL))
+);
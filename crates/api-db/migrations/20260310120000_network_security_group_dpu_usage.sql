@@ -0,0 +1,15 @@
+-- Expanded size of the network security groups applied on each DPU, as tracked by
+-- the NetworkSecurityGroupLimitMonitor. Rules referencing VPCs or VPC prefixes grow
+-- with the referenced objects, so the size can change without the group being touched.
+CREATE TABLE network_security_group_dpu_usage (
+    dpu_machine_id VARCHAR(64) NOT NULL,
+    network_security_group_id VARCHAR(64) NOT NULL,
+    expanded_rule_count BIGINT NOT NULL,
+    -- Why the group can't be propagated to the DPU, e.g. because it exceeds the limits
+    error TEXT,
+    updated TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (dpu_machine_id, network_security_group_id)
+);
+
+CREATE INDEX idx_network_security_group_dpu_usage_nsg_id
+    ON network_security_group_dpu_usage (network_security_group_id);
@@ -0,0 +1,38 @@
+-- Webhooks which notify tenants about the lifecycle of their instances.
+CREATE TYPE instance_lifecycle_event_type AS ENUM (
+    'allocated',
+    'network_config_synced',
+    'ready',
+    'release_started',
+    'cleaned_up'
+);
+
+CREATE TABLE instance_webhook_subscriptions (
+    id uuid PRIMARY KEY,
+    tenant_organization_id TEXT NOT NULL,
+    url TEXT NOT NULL,
+    -- Key the payloads are signed with
+    secret TEXT NOT NULL,
+    -- Events delivered to the subscription, all events if empty
+    event_types instance_lifecycle_event_type[] NOT NULL DEFAULT '{}',
+    created TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_instance_webhook_subscriptions_tenant
+    ON instance_webhook_subscriptions (tenant_organization_id);
+
+-- Events waiting to be delivered to a subscription. Rows are removed once the event was
+-- delivered, and are kept as dead letters once they run out of attempts.
+CREATE TABLE instance_webhook_deliveries (
+    id BIGINT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+    subscription_id uuid NOT NULL REFERENCES instance_webhook_subscriptions(id) ON DELETE CASCADE,
+    payload JSONB NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_error TEXT,
+    dead_lettered TIMESTAMPTZ,
+    created TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_instance_webhook_deliveries_pending
+    ON instance_webhook_deliveries (next_attempt_at) WHERE dead_lettered IS NULL;
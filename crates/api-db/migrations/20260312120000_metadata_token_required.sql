@@ -0,0 +1,4 @@
+-- Session tokens for the instance metadata service (FMDS) can be required per tenant,
+-- and overridden per instance. A NULL value on the instance inherits the tenant setting.
+ALTER TABLE tenants ADD COLUMN metadata_token_required BOOLEAN NOT NULL DEFAULT false;
+ALTER TABLE instances ADD COLUMN metadata_token_required BOOLEAN;
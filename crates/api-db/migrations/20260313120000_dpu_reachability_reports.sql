@@ -0,0 +1,13 @@
+-- The latest results each DPU reported for the probes its network monitor sends to peer DPUs.
+-- Together they make up the DPU reachability matrix.
+CREATE TABLE dpu_reachability_reports (
+    dpu_id VARCHAR PRIMARY KEY,
+    observed_at TIMESTAMPTZ NOT NULL,
+    results JSONB NOT NULL,
+    updated TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT fk_dpu_reachability_reports_dpu_id
+        FOREIGN KEY (dpu_id)
+        REFERENCES machines(id)
+        ON DELETE CASCADE
+);
@@ -0,0 +1,23 @@
+-- Records of the storage erasure performed during the cleanup of a machine.
+-- A pending report is opened when an instance is released and completed once scout
+-- reports the cleanup. Reports are kept after the machine or instance is deleted, since
+-- they serve as proof of sanitization.
+CREATE TABLE machine_erasure_reports (
+    id uuid PRIMARY KEY,
+    machine_id VARCHAR NOT NULL,
+    instance_id uuid,
+    tenant_organization_id TEXT,
+    released_at TIMESTAMPTZ,
+    completed_at TIMESTAMPTZ,
+    records JSONB NOT NULL DEFAULT '[]',
+    -- The completed report as JWT
+    signed_report TEXT,
+    created TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_machine_erasure_reports_machine_id ON machine_erasure_reports (machine_id);
+CREATE INDEX idx_machine_erasure_reports_instance_id ON machine_erasure_reports (instance_id);
+
+-- At most one pending report per machine
+CREATE UNIQUE INDEX idx_machine_erasure_reports_pending
+    ON machine_erasure_reports (machine_id) WHERE completed_at IS NULL;
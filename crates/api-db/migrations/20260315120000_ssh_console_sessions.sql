@@ -0,0 +1,19 @@
+-- Recordings of the frontend console sessions ssh-console opened to machines, in asciicast v2
+-- format. ssh-console uploads a recording when its session ends. Recordings contain everything
+-- the user typed, including passwords, in plaintext.
+CREATE TABLE ssh_console_sessions (
+    session_id uuid PRIMARY KEY,
+    machine_id VARCHAR NOT NULL,
+    name TEXT NOT NULL,
+    started TIMESTAMPTZ NOT NULL,
+    user_name TEXT,
+    certificate_key_id TEXT,
+    peer_addr TEXT NOT NULL,
+    duration_secs DOUBLE PRECISION NOT NULL,
+    input_events INTEGER NOT NULL,
+    output_events INTEGER NOT NULL,
+    recording TEXT NOT NULL,
+    created TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_ssh_console_sessions_machine_id ON ssh_console_sessions (machine_id, started);
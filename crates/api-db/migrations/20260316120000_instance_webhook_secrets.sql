@@ -0,0 +1,6 @@
+-- The secrets of instance webhook subscriptions are kept in the credential provider
+ALTER TABLE instance_webhook_subscriptions DROP COLUMN secret;
+
+-- Dead letters are pruned once they are older than the retention period
+CREATE INDEX idx_instance_webhook_deliveries_dead_lettered
+    ON instance_webhook_deliveries (dead_lettered) WHERE dead_lettered IS NOT NULL;
@@ -0,0 +1,5 @@
+-- The IP hop limit of FMDS token responses can be set per tenant, and overridden per instance.
+-- A NULL value on the instance inherits the tenant setting, a NULL value on the tenant leaves
+-- the choice to the DPU agent.
+ALTER TABLE tenants ADD COLUMN metadata_token_hop_limit INTEGER;
+ALTER TABLE instances ADD COLUMN metadata_token_hop_limit INTEGER;
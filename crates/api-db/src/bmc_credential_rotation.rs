@@ -0,0 +1,86 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use mac_address::MacAddress;
+use model::bmc_credential_rotation::BmcCredentialRotation;
+use sqlx::PgConnection;
+
+use crate::{DatabaseError, DatabaseResult};
+
+pub async fn find_all(txn: &mut PgConnection) -> DatabaseResult<Vec<BmcCredentialRotation>> {
+    let query = "SELECT * FROM bmc_credential_rotations";
+    sqlx::query_as(query)
+        .fetch_all(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+pub async fn find_by_mac_address(
+    txn: &mut PgConnection,
+    bmc_mac_address: MacAddress,
+) -> DatabaseResult<Option<BmcCredentialRotation>> {
+    let query = "SELECT * FROM bmc_credential_rotations WHERE bmc_mac_address = $1";
+    sqlx::query_as(query)
+        .bind(bmc_mac_address)
+        .fetch_optional(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+/// Records a successful rotation of the credentials of a BMC
+pub async fn record_success(
+    txn: &mut PgConnection,
+    bmc_mac_address: MacAddress,
+) -> DatabaseResult<BmcCredentialRotation> {
+    let query = r#"INSERT INTO bmc_credential_rotations
+            (bmc_mac_address, rotated_at, last_attempt_at, consecutive_failures, last_error)
+        VALUES ($1, NOW(), NOW(), 0, NULL)
+        ON CONFLICT (bmc_mac_address) DO UPDATE SET
+            rotated_at = NOW(),
+            last_attempt_at = NOW(),
+            consecutive_failures = 0,
+            last_error = NULL
+        RETURNING *"#;
+    sqlx::query_as(query)
+        .bind(bmc_mac_address)
+        .fetch_one(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+/// Records a failed attempt to rotate the credentials of a BMC.
+/// The time of the last successful rotation is kept.
+pub async fn record_failure(
+    txn: &mut PgConnection,
+    bmc_mac_address: MacAddress,
+    error: &str,
+) -> DatabaseResult<BmcCredentialRotation> {
+    let query = r#"INSERT INTO bmc_credential_rotations
+            (bmc_mac_address, rotated_at, last_attempt_at, consecutive_failures, last_error)
+        VALUES ($1, NULL, NOW(), 1, $2)
+        ON CONFLICT (bmc_mac_address) DO UPDATE SET
+            last_attempt_at = NOW(),
+            consecutive_failures = bmc_credential_rotations.consecutive_failures + 1,
+            last_error = EXCLUDED.last_error
+        RETURNING *"#;
+    sqlx::query_as(query)
+        .bind(bmc_mac_address)
+        .bind(error)
+        .fetch_one(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))
+}
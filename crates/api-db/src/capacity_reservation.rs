@@ -0,0 +1,216 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::HashMap;
+
+use carbide_uuid::capacity_reservation::CapacityReservationId;
+use carbide_uuid::instance_type::InstanceTypeId;
+use carbide_uuid::machine::MachineType;
+use model::capacity_reservation::{CapacityReservation, NewCapacityReservation};
+use model::tenant::TenantOrganizationId;
+use sqlx::postgres::PgRow;
+use sqlx::{FromRow, PgConnection, Row};
+
+use crate::{DatabaseError, DatabaseResult};
+
+/// SQL condition which selects reservations that are neither released nor expired
+const ACTIVE: &str = "released IS NULL AND (expires_at IS NULL OR expires_at > NOW())";
+
+#[derive(Debug, Clone)]
+pub struct DbCapacityReservation(pub CapacityReservation);
+
+impl<'r> FromRow<'r, PgRow> for DbCapacityReservation {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let tenant_organization_id: String = row.try_get("tenant_organization_id")?;
+        let count: i32 = row.try_get("count")?;
+
+        Ok(DbCapacityReservation(CapacityReservation {
+            id: row.try_get("id")?,
+            tenant_organization_id: tenant_organization_id
+                .parse()
+                .map_err(|e| sqlx::Error::Decode(Box::new(e)))?,
+            instance_type_id: row.try_get("instance_type_id")?,
+            count: count as u32,
+            expires_at: row.try_get("expires_at")?,
+            created: row.try_get("created")?,
+            released: row.try_get("released")?,
+        }))
+    }
+}
+
+pub async fn create(
+    txn: &mut PgConnection,
+    value: &NewCapacityReservation,
+) -> DatabaseResult<CapacityReservation> {
+    if value.count == 0 || value.count > i32::MAX as u32 {
+        return Err(DatabaseError::InvalidArgument(format!(
+            "invalid count: {}",
+            value.count
+        )));
+    }
+
+    let query = "INSERT INTO capacity_reservations
+            (id, tenant_organization_id, instance_type_id, count, expires_at)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT DO NOTHING
+        RETURNING *";
+    sqlx::query_as::<_, DbCapacityReservation>(query)
+        .bind(value.id)
+        .bind(value.tenant_organization_id.as_str())
+        .bind(&value.instance_type_id)
+        .bind(value.count as i32)
+        .bind(value.expires_at)
+        .fetch_optional(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?
+        .map(|reservation| reservation.0)
+        .ok_or_else(|| DatabaseError::AlreadyFoundError {
+            kind: "CapacityReservation",
+            id: value.id.to_string(),
+        })
+}
+
+/// Finds reservations, optionally restricted to a tenant and/or InstanceType.
+/// Released and expired reservations are only returned if `include_inactive` is set.
+pub async fn find(
+    txn: &mut PgConnection,
+    tenant_organization_id: Option<&TenantOrganizationId>,
+    instance_type_id: Option<&InstanceTypeId>,
+    include_inactive: bool,
+) -> DatabaseResult<Vec<CapacityReservation>> {
+    let mut builder = sqlx::QueryBuilder::new("SELECT * FROM capacity_reservations WHERE TRUE");
+    if let Some(tenant_organization_id) = tenant_organization_id {
+        builder.push(" AND tenant_organization_id = ");
+        builder.push_bind(tenant_organization_id.as_str());
+    }
+    if let Some(instance_type_id) = instance_type_id {
+        builder.push(" AND instance_type_id = ");
+        builder.push_bind(instance_type_id);
+    }
+    if !include_inactive {
+        builder.push(" AND ");
+        builder.push(ACTIVE);
+    }
+    builder.push(" ORDER BY created");
+
+    builder
+        .build_query_as::<DbCapacityReservation>()
+        .fetch_all(txn)
+        .await
+        .map(|reservations| reservations.into_iter().map(|r| r.0).collect())
+        .map_err(|e| DatabaseError::query(builder.sql(), e))
+}
+
+/// Finds the active reservations for the given InstanceTypes.
+///
+/// With `for_update`, the reservations are locked until the end of the transaction. Allocations
+/// which have to respect the reservations of an InstanceType are thereby serialized.
+pub async fn find_active_by_instance_type_ids(
+    txn: &mut PgConnection,
+    instance_type_ids: &[InstanceTypeId],
+    for_update: bool,
+) -> DatabaseResult<Vec<CapacityReservation>> {
+    let mut builder = sqlx::QueryBuilder::new(
+        "SELECT * FROM capacity_reservations WHERE instance_type_id = ANY(",
+    );
+    builder.push_bind(instance_type_ids);
+    builder.push(") AND ");
+    builder.push(ACTIVE);
+    builder.push(" ORDER BY created");
+    if for_update {
+        builder.push(" FOR UPDATE");
+    }
+
+    builder
+        .build_query_as::<DbCapacityReservation>()
+        .fetch_all(txn)
+        .await
+        .map(|reservations| reservations.into_iter().map(|r| r.0).collect())
+        .map_err(|e| DatabaseError::query(builder.sql(), e))
+}
+
+/// Releases an active reservation
+pub async fn release(
+    txn: &mut PgConnection,
+    id: CapacityReservationId,
+) -> DatabaseResult<CapacityReservation> {
+    let query = "UPDATE capacity_reservations SET released = NOW()
+        WHERE id = $1 AND released IS NULL
+        RETURNING *";
+    sqlx::query_as::<_, DbCapacityReservation>(query)
+        .bind(id)
+        .fetch_optional(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?
+        .map(|reservation| reservation.0)
+        .ok_or_else(|| DatabaseError::NotFoundError {
+            kind: "CapacityReservation",
+            id: id.to_string(),
+        })
+}
+
+/// Counts the Ready host Machines without an instance for each of the given InstanceTypes.
+/// InstanceTypes without such Machines are omitted.
+pub async fn count_available_machines(
+    txn: &mut PgConnection,
+    instance_type_ids: &[InstanceTypeId],
+) -> DatabaseResult<HashMap<InstanceTypeId, u32>> {
+    let query = "SELECT m.instance_type_id, COUNT(*) FROM machines m
+        WHERE m.instance_type_id = ANY($1)
+            AND starts_with(m.id, $2)
+            AND m.controller_state->>'state' = 'ready'
+            AND NOT EXISTS (
+                SELECT 1 FROM instances i WHERE i.machine_id = m.id AND i.deleted IS NULL
+            )
+        GROUP BY m.instance_type_id";
+    let counts: Vec<(InstanceTypeId, i64)> = sqlx::query_as(query)
+        .bind(instance_type_ids)
+        .bind(MachineType::Host.id_prefix())
+        .fetch_all(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+
+    Ok(counts
+        .into_iter()
+        .map(|(instance_type_id, count)| (instance_type_id, count as u32))
+        .collect())
+}
+
+/// Counts the instances of each tenant on Machines of the given InstanceTypes
+pub async fn count_instances_by_tenant(
+    txn: &mut PgConnection,
+    instance_type_ids: &[InstanceTypeId],
+) -> DatabaseResult<HashMap<(InstanceTypeId, TenantOrganizationId), u32>> {
+    let query = "SELECT m.instance_type_id, i.tenant_org, COUNT(*) FROM instances i
+        JOIN machines m ON m.id = i.machine_id
+        WHERE m.instance_type_id = ANY($1) AND i.deleted IS NULL
+        GROUP BY m.instance_type_id, i.tenant_org";
+    let counts: Vec<(InstanceTypeId, String, i64)> = sqlx::query_as(query)
+        .bind(instance_type_ids)
+        .fetch_all(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+
+    Ok(counts
+        .into_iter()
+        .filter_map(|(instance_type_id, tenant_org, count)| {
+            // Instances of unknown tenants can't be counted against any reservation
+            let tenant_org = tenant_org.parse().ok()?;
+            Some(((instance_type_id, tenant_org), count as u32))
+        })
+        .collect())
+}
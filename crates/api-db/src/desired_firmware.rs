@@ -15,7 +15,7 @@
  * limitations under the License.
  */
 
-use model::firmware::{DesiredFirmwareVersions, Firmware};
+use model::firmware::{DesiredFirmware, DesiredFirmwareVersions, Firmware};
 use sqlx::PgConnection;
 
 use super::DatabaseError;
@@ -37,6 +37,16 @@ pub async fn snapshot_desired_firmware(
     Ok(())
 }
 
+/// find_all returns all entries of the desired_firmware table
+pub async fn find_all(txn: &mut PgConnection) -> Result<Vec<DesiredFirmware>, DatabaseError> {
+    let query =
+        "SELECT vendor, model, versions, explicit_update_start_needed FROM desired_firmware";
+    sqlx::query_as(query)
+        .fetch_all(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
 async fn snapshot_desired_firmware_for_model(
     txn: &mut PgConnection,
     model: &Firmware,
@@ -20,7 +20,7 @@ use std::str::FromStr;
 use carbide_uuid::domain::DomainId;
 use chrono::{DateTime, Utc};
 use hickory_proto::rr::Name;
-use model::dns::{Domain, NewDomain, SoaSnapshot};
+use model::dns::{Domain, DomainInfo, NewDomain, SoaSnapshot};
 use sqlx::{FromRow, PgConnection};
 
 use super::super::{ColumnInfo, FilterableQueryBuilder, ObjectColumnFilter};
@@ -216,6 +216,79 @@ pub async fn update(value: &mut Domain, txn: &mut PgConnection) -> Result<Domain
         .map_err(|e| DatabaseError::query(query, e))
 }
 
+/// Bumps the serial of domains whose records changed since the last bump,
+/// either of all domains or only of the domain with the given name.
+///
+/// Record changes only mark the domain as changed, so that a burst of changes
+/// results in a single serial bump once the zone is read.
+pub async fn apply_pending_serial_bumps(
+    txn: &mut PgConnection,
+    name: Option<&str>,
+) -> Result<(), DatabaseError> {
+    let query = "SELECT dns_apply_domain_changes($1)";
+    sqlx::query(query)
+        .bind(name)
+        .execute(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+    Ok(())
+}
+
+#[derive(FromRow)]
+struct DbUpdatedMaster {
+    id: DomainId,
+    name: String,
+    soa: sqlx::types::Json<Option<dns_record::SoaRecord>>,
+    notified_serial: Option<i64>,
+}
+
+/// Returns the domains whose serial differs from the last serial PowerDNS
+/// sent a NOTIFY for.
+pub async fn find_updated_masters(
+    txn: impl DbReader<'_>,
+) -> Result<Vec<DomainInfo>, DatabaseError> {
+    let query = "SELECT id, name, soa, notified_serial FROM domains
+        WHERE deleted IS NULL AND soa ? 'serial'
+            AND notified_serial IS DISTINCT FROM (soa ->> 'serial')::bigint
+        ORDER BY name";
+    let domains = sqlx::query_as::<_, DbUpdatedMaster>(query)
+        .fetch_all(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+
+    Ok(domains
+        .into_iter()
+        .filter_map(|domain| {
+            let soa = domain.soa.0?;
+            Some(DomainInfo {
+                id: domain.id,
+                zone: domain.name + ".",
+                kind: "master".to_string(),
+                serial: soa.serial,
+                last_check: None,
+                notified_serial: domain.notified_serial.map(|serial| serial as u32),
+                masters: vec![],
+            })
+        })
+        .collect())
+}
+
+/// Records the serial PowerDNS sent a NOTIFY for
+pub async fn set_notified(
+    txn: &mut PgConnection,
+    id: DomainId,
+    serial: u32,
+) -> Result<(), DatabaseError> {
+    let query = "UPDATE domains SET notified_serial = $2 WHERE id = $1";
+    sqlx::query(query)
+        .bind(id)
+        .bind(serial as i64)
+        .execute(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+    Ok(())
+}
+
 #[cfg(test)]
 #[test]
 fn test_generate_domain_serial_format() {
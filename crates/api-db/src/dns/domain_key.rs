@@ -0,0 +1,142 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use carbide_uuid::domain::DomainId;
+use chrono::{DateTime, Utc};
+use model::dns::DomainKeyInfo;
+use sqlx::postgres::PgRow;
+use sqlx::{Error, FromRow, PgConnection, Row};
+
+use crate::DatabaseError;
+use crate::db_read::DbReader;
+
+#[derive(Debug, Clone)]
+pub struct DbDomainKey {
+    pub id: i32,
+    pub domain_id: DomainId,
+    pub flags: i32,
+    pub active: bool,
+    pub published: bool,
+    pub created: DateTime<Utc>,
+}
+
+impl<'r> FromRow<'r, PgRow> for DbDomainKey {
+    fn from_row(row: &'r PgRow) -> Result<Self, Error> {
+        Ok(DbDomainKey {
+            id: row.try_get("id")?,
+            domain_id: row.try_get("domain_id")?,
+            flags: row.try_get("flags")?,
+            active: row.try_get("active")?,
+            published: row.try_get("published")?,
+            created: row.try_get("created")?,
+        })
+    }
+}
+
+impl From<DbDomainKey> for DomainKeyInfo {
+    fn from(key: DbDomainKey) -> Self {
+        DomainKeyInfo {
+            id: key.id as u32,
+            domain_id: key.domain_id,
+            flags: key.flags as u32,
+            active: key.active,
+            published: key.published,
+            created: key.created,
+        }
+    }
+}
+
+/// Returns the DNSSEC keys of the domain with the given name, oldest first
+pub async fn find_by_domain_name(
+    txn: impl DbReader<'_>,
+    domain_name: &str,
+) -> Result<Vec<DomainKeyInfo>, DatabaseError> {
+    let domain_name = crate::dns::normalize_domain(domain_name);
+    let query = "SELECT k.id, k.domain_id, k.flags, k.active, k.published, k.created
+        FROM dns_domain_keys k
+        JOIN domains d ON d.id = k.domain_id
+        WHERE d.name = $1 AND d.deleted IS NULL
+        ORDER BY k.id ASC";
+    sqlx::query_as::<_, DbDomainKey>(query)
+        .bind(domain_name)
+        .fetch_all(txn)
+        .await
+        .map(|keys| keys.into_iter().map(DomainKeyInfo::from).collect())
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+/// Returns the DNSSEC keys of a domain, oldest first
+pub async fn find_by_domain_id(
+    txn: impl DbReader<'_>,
+    domain_id: DomainId,
+) -> Result<Vec<DomainKeyInfo>, DatabaseError> {
+    let query = "SELECT id, domain_id, flags, active, published, created
+        FROM dns_domain_keys
+        WHERE domain_id = $1
+        ORDER BY id ASC";
+    sqlx::query_as::<_, DbDomainKey>(query)
+        .bind(domain_id)
+        .fetch_all(txn)
+        .await
+        .map(|keys| keys.into_iter().map(DomainKeyInfo::from).collect())
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+/// Stores a new DNSSEC key for a domain. The private key has to be stored in
+/// the credential provider under the returned key.
+pub async fn persist(
+    txn: &mut PgConnection,
+    domain_id: DomainId,
+    flags: u32,
+    active: bool,
+    published: bool,
+) -> Result<DomainKeyInfo, DatabaseError> {
+    let query = "INSERT INTO dns_domain_keys (domain_id, flags, active, published)
+        VALUES ($1, $2, $3, $4)
+        RETURNING id, domain_id, flags, active, published, created";
+    sqlx::query_as::<_, DbDomainKey>(query)
+        .bind(domain_id)
+        .bind(flags as i32)
+        .bind(active)
+        .bind(published)
+        .fetch_one(txn)
+        .await
+        .map(DomainKeyInfo::from)
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+/// Marks the given keys as inactive. Inactive keys stay published.
+pub async fn deactivate(txn: &mut PgConnection, key_ids: &[u32]) -> Result<(), DatabaseError> {
+    let query = "UPDATE dns_domain_keys SET active = false WHERE id = ANY($1)";
+    let key_ids: Vec<i32> = key_ids.iter().map(|id| *id as i32).collect();
+    sqlx::query(query)
+        .bind(key_ids)
+        .execute(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+    Ok(())
+}
+
+pub async fn delete(txn: &mut PgConnection, key_ids: &[u32]) -> Result<(), DatabaseError> {
+    let query = "DELETE FROM dns_domain_keys WHERE id = ANY($1)";
+    let key_ids: Vec<i32> = key_ids.iter().map(|id| *id as i32).collect();
+    sqlx::query(query)
+        .bind(key_ids)
+        .execute(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+    Ok(())
+}
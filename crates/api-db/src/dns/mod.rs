@@ -16,8 +16,10 @@
  */
 
 pub mod domain;
+pub mod domain_key;
 pub mod domain_metadata;
 pub mod resource_record;
+pub mod tenant_record;
 
 pub fn normalize_domain(name: &str) -> String {
     let normalize_domain = name.trim_end_matches('.').to_lowercase();
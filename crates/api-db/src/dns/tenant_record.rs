@@ -0,0 +1,387 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::str::FromStr;
+
+use carbide_uuid::domain::DomainId;
+use carbide_uuid::tenant_dns_record::TenantDnsRecordId;
+use carbide_uuid::vpc::VpcId;
+use dns_record::DnsResourceRecordType;
+use hickory_proto::rr::Name;
+use model::dns::{NewTenantDnsRecord, TenantDnsRecord};
+use sqlx::postgres::PgRow;
+use sqlx::{FromRow, PgConnection, Row};
+
+use crate::db_read::DbReader;
+use crate::{DatabaseError, DatabaseResult};
+
+/// Maximum length of a single TXT character string
+const MAX_TXT_STRING_LEN: usize = 255;
+
+#[derive(Debug, Clone)]
+pub struct DbTenantDnsRecord(pub TenantDnsRecord);
+
+impl<'r> FromRow<'r, PgRow> for DbTenantDnsRecord {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let q_type: String = row.try_get("q_type")?;
+        let qtype =
+            DnsResourceRecordType::try_from(q_type).map_err(|e| sqlx::Error::Decode(e.into()))?;
+        let ttl: i32 = row.try_get("ttl")?;
+
+        Ok(DbTenantDnsRecord(TenantDnsRecord {
+            id: row.try_get("id")?,
+            vpc_id: row.try_get("vpc_id")?,
+            domain_id: row.try_get("domain_id")?,
+            qname: row.try_get("q_name")?,
+            qtype,
+            ttl: ttl as u32,
+            content: row.try_get("content")?,
+            created: row.try_get("created")?,
+            updated: row.try_get("updated")?,
+        }))
+    }
+}
+
+/// Validates the record data for the given record type
+pub fn validate_content(qtype: DnsResourceRecordType, content: &str) -> DatabaseResult<()> {
+    let invalid = |reason: &str| {
+        Err(DatabaseError::InvalidArgument(format!(
+            "invalid {qtype} record content \"{content}\": {reason}"
+        )))
+    };
+
+    match qtype {
+        DnsResourceRecordType::A => {
+            if Ipv4Addr::from_str(content).is_err() {
+                return invalid("expected an IPv4 address");
+            }
+        }
+        DnsResourceRecordType::AAAA => {
+            if Ipv6Addr::from_str(content).is_err() {
+                return invalid("expected an IPv6 address");
+            }
+        }
+        DnsResourceRecordType::CNAME => {
+            if !is_valid_target(content) {
+                return invalid("expected a domain name");
+            }
+        }
+        DnsResourceRecordType::TXT => {
+            if content.is_empty() || content.len() > MAX_TXT_STRING_LEN {
+                return invalid("expected between 1 and 255 characters");
+            }
+            if content.chars().any(|c| c.is_control()) {
+                return invalid("control characters are not allowed");
+            }
+        }
+        DnsResourceRecordType::SRV => {
+            let fields: Vec<&str> = content.split_whitespace().collect();
+            let [priority, weight, port, target] = fields.as_slice() else {
+                return invalid("expected `priority weight port target`");
+            };
+            if [priority, weight, port]
+                .iter()
+                .any(|field| field.parse::<u16>().is_err())
+            {
+                return invalid("priority, weight and port must be between 0 and 65535");
+            }
+            if !is_valid_target(target) {
+                return invalid("expected a domain name as target");
+            }
+        }
+        _ => {
+            return Err(DatabaseError::InvalidArgument(format!(
+                "record type {qtype} can not be created by tenants"
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+fn is_valid_target(name: &str) -> bool {
+    !name.is_empty() && Name::from_str(name).is_ok()
+}
+
+/// Returns the domain which a record with the given name belongs to.
+///
+/// The domain has to be the subdomain of one of the network segments of the VPC.
+/// If several domains match, the most specific one is used. Records at the apex
+/// of a domain are rejected, as that is where the SOA record of the zone lives.
+pub async fn find_owning_domain(
+    txn: &mut PgConnection,
+    vpc_id: VpcId,
+    qname: &str,
+) -> DatabaseResult<DomainId> {
+    let query = "SELECT DISTINCT d.id, d.name FROM domains d
+        JOIN network_segments ns ON ns.subdomain_id = d.id
+        WHERE ns.vpc_id = $1 AND ns.deleted IS NULL AND d.deleted IS NULL";
+    let domains: Vec<(DomainId, String)> = sqlx::query_as(query)
+        .bind(vpc_id)
+        .fetch_all(&mut *txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+
+    let name = qname.trim_end_matches('.');
+    if domains.iter().any(|(_, domain)| domain == name) {
+        return Err(DatabaseError::InvalidArgument(format!(
+            "records can not be created at the apex of domain {name}"
+        )));
+    }
+
+    domains
+        .into_iter()
+        .filter(|(_, domain)| name.ends_with(&format!(".{domain}")))
+        .max_by_key(|(_, domain)| domain.len())
+        .map(|(id, _)| id)
+        .ok_or_else(|| {
+            DatabaseError::InvalidArgument(format!(
+                "{qname} is not inside a domain of VPC {vpc_id}"
+            ))
+        })
+}
+
+/// Checks that a new record does not clash with generated records or with the
+/// CNAME rules of existing tenant records.
+async fn check_conflicts(txn: &mut PgConnection, value: &NewTenantDnsRecord) -> DatabaseResult<()> {
+    let query = "SELECT COUNT(*) FROM dns_records WHERE q_name = $1";
+    let (generated,): (i64,) = sqlx::query_as(query)
+        .bind(&value.qname)
+        .fetch_one(&mut *txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+    if generated > 0 {
+        return Err(DatabaseError::AlreadyFoundError {
+            kind: "DNS record",
+            id: value.qname.clone(),
+        });
+    }
+
+    // A CNAME can not coexist with any other record of the same name
+    let query = "SELECT q_type FROM tenant_dns_record WHERE q_name = $1";
+    let existing: Vec<(String,)> = sqlx::query_as(query)
+        .bind(&value.qname)
+        .fetch_all(&mut *txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+    let is_cname = value.qtype == DnsResourceRecordType::CNAME;
+    if existing
+        .iter()
+        .any(|(q_type,)| is_cname || q_type == "CNAME")
+    {
+        return Err(DatabaseError::InvalidArgument(format!(
+            "a CNAME record for {} can not coexist with other records",
+            value.qname
+        )));
+    }
+
+    Ok(())
+}
+
+pub async fn create(
+    txn: &mut PgConnection,
+    value: NewTenantDnsRecord,
+) -> DatabaseResult<TenantDnsRecord> {
+    Name::from_str(&value.qname).map_err(|_| {
+        DatabaseError::InvalidArgument(format!("invalid record name: {}", value.qname))
+    })?;
+    validate_content(value.qtype, &value.content)?;
+    if value.ttl > i32::MAX as u32 {
+        return Err(DatabaseError::InvalidArgument(format!(
+            "invalid ttl: {}",
+            value.ttl
+        )));
+    }
+
+    let domain_id = find_owning_domain(txn, value.vpc_id, &value.qname).await?;
+    check_conflicts(txn, &value).await?;
+
+    let query =
+        "INSERT INTO tenant_dns_record (id, vpc_id, domain_id, q_name, q_type, ttl, content)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        ON CONFLICT DO NOTHING
+        RETURNING *";
+    sqlx::query_as::<_, DbTenantDnsRecord>(query)
+        .bind(value.id)
+        .bind(value.vpc_id)
+        .bind(domain_id)
+        .bind(&value.qname)
+        .bind(value.qtype.to_string())
+        .bind(value.ttl as i32)
+        .bind(&value.content)
+        .fetch_optional(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?
+        .map(|record| record.0)
+        .ok_or_else(|| DatabaseError::AlreadyFoundError {
+            kind: "TenantDnsRecord",
+            id: format!(
+                "id={} or {} {} {}",
+                value.id, value.qname, value.qtype, value.content
+            ),
+        })
+}
+
+pub async fn find_ids(
+    txn: &mut PgConnection,
+    vpc_id: Option<VpcId>,
+) -> DatabaseResult<Vec<TenantDnsRecordId>> {
+    let mut builder = sqlx::QueryBuilder::new("SELECT id FROM tenant_dns_record");
+    if let Some(vpc_id) = vpc_id {
+        builder.push(" WHERE vpc_id = ");
+        builder.push_bind(vpc_id);
+    }
+
+    let query = builder.build_query_as();
+    let ids: Vec<TenantDnsRecordId> = query
+        .fetch_all(txn)
+        .await
+        .map_err(|e| DatabaseError::new("tenant_dns_record::find_ids", e))?;
+
+    Ok(ids)
+}
+
+pub async fn find_by_ids(
+    txn: &mut PgConnection,
+    ids: &[TenantDnsRecordId],
+) -> DatabaseResult<Vec<TenantDnsRecord>> {
+    let query = "SELECT * FROM tenant_dns_record WHERE id=ANY($1)";
+    sqlx::query_as::<_, DbTenantDnsRecord>(query)
+        .bind(ids)
+        .fetch_all(txn)
+        .await
+        .map(|records| records.into_iter().map(|r| r.0).collect())
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+/// Finds the tenant records with the given fully qualified name
+pub async fn find_by_qname(
+    txn: impl DbReader<'_>,
+    qname: &str,
+) -> DatabaseResult<Vec<TenantDnsRecord>> {
+    let query = "SELECT * FROM tenant_dns_record WHERE q_name = $1";
+    sqlx::query_as::<_, DbTenantDnsRecord>(query)
+        .bind(qname)
+        .fetch_all(txn)
+        .await
+        .map(|records| records.into_iter().map(|r| r.0).collect())
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+/// Finds all tenant records inside the domain with the given name
+pub async fn find_by_domain_name(
+    txn: impl DbReader<'_>,
+    domain_name: &str,
+) -> DatabaseResult<Vec<TenantDnsRecord>> {
+    let domain_name = crate::dns::normalize_domain(domain_name);
+    let query = "SELECT r.* FROM tenant_dns_record r
+        JOIN domains d ON d.id = r.domain_id
+        WHERE d.name = $1 AND d.deleted IS NULL
+        ORDER BY r.q_name, r.q_type";
+    sqlx::query_as::<_, DbTenantDnsRecord>(query)
+        .bind(domain_name)
+        .fetch_all(txn)
+        .await
+        .map(|records| records.into_iter().map(|r| r.0).collect())
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+/// Updates the TTL and data of a record. Name and type of a record are immutable.
+pub async fn update(
+    txn: &mut PgConnection,
+    id: TenantDnsRecordId,
+    ttl: Option<u32>,
+    content: &str,
+) -> DatabaseResult<TenantDnsRecord> {
+    let existing = find_by_ids(txn, &[id])
+        .await?
+        .into_iter()
+        .next()
+        .ok_or_else(|| DatabaseError::NotFoundError {
+            kind: "TenantDnsRecord",
+            id: id.to_string(),
+        })?;
+    let content = content.trim();
+    validate_content(existing.qtype, content)?;
+    let ttl = ttl.unwrap_or(existing.ttl);
+    if ttl > i32::MAX as u32 {
+        return Err(DatabaseError::InvalidArgument(format!(
+            "invalid ttl: {ttl}"
+        )));
+    }
+
+    let query =
+        "UPDATE tenant_dns_record SET ttl=$1, content=$2, updated=NOW() WHERE id=$3 RETURNING *";
+    sqlx::query_as::<_, DbTenantDnsRecord>(query)
+        .bind(ttl as i32)
+        .bind(content)
+        .bind(id)
+        .fetch_one(txn)
+        .await
+        .map(|record| record.0)
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+pub async fn delete(
+    txn: &mut PgConnection,
+    id: TenantDnsRecordId,
+) -> DatabaseResult<TenantDnsRecord> {
+    let query = "DELETE FROM tenant_dns_record WHERE id=$1 RETURNING *";
+    sqlx::query_as::<_, DbTenantDnsRecord>(query)
+        .bind(id)
+        .fetch_optional(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?
+        .map(|record| record.0)
+        .ok_or_else(|| DatabaseError::NotFoundError {
+            kind: "TenantDnsRecord",
+            id: id.to_string(),
+        })
+}
+
+pub async fn delete_by_vpc_id(txn: &mut PgConnection, vpc_id: VpcId) -> DatabaseResult<()> {
+    let query = "DELETE FROM tenant_dns_record WHERE vpc_id=$1";
+    sqlx::query(query)
+        .bind(vpc_id)
+        .execute(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_content() {
+        use DnsResourceRecordType::*;
+
+        assert!(validate_content(A, "192.0.2.1").is_ok());
+        assert!(validate_content(A, "fd00::1").is_err());
+        assert!(validate_content(AAAA, "fd00::1").is_ok());
+        assert!(validate_content(AAAA, "192.0.2.1").is_err());
+        assert!(validate_content(CNAME, "lb.example.com.").is_ok());
+        assert!(validate_content(CNAME, "").is_err());
+        assert!(validate_content(TXT, "v=spf1 -all").is_ok());
+        assert!(validate_content(TXT, &"x".repeat(256)).is_err());
+        assert!(validate_content(SRV, "10 5 5060 sip.example.com.").is_ok());
+        assert!(validate_content(SRV, "10 5 sip.example.com.").is_err());
+        assert!(validate_content(SRV, "10 5 70000 sip.example.com.").is_err());
+        assert!(validate_content(SOA, "ns1.example.com.").is_err());
+    }
+}
@@ -0,0 +1,82 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use carbide_uuid::machine::MachineId;
+use model::dpu_reachability::{DpuReachabilityReport, DpuReachabilityResult};
+use sqlx::postgres::PgRow;
+use sqlx::types::Json;
+use sqlx::{FromRow, PgConnection, Row};
+
+use crate::db_read::DbReader;
+use crate::{DatabaseError, DatabaseResult};
+
+#[derive(Debug, Clone)]
+pub struct DbDpuReachabilityReport(pub DpuReachabilityReport);
+
+impl<'r> FromRow<'r, PgRow> for DbDpuReachabilityReport {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let results: Json<Vec<DpuReachabilityResult>> = row.try_get("results")?;
+
+        Ok(DbDpuReachabilityReport(DpuReachabilityReport {
+            dpu_id: row.try_get("dpu_id")?,
+            observed_at: row.try_get("observed_at")?,
+            results: results.0,
+        }))
+    }
+}
+
+/// Store the report of a DPU, replacing its previous report
+pub async fn upsert(txn: &mut PgConnection, report: &DpuReachabilityReport) -> DatabaseResult<()> {
+    let query = "INSERT INTO dpu_reachability_reports (dpu_id, observed_at, results)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (dpu_id) DO UPDATE SET
+            observed_at = EXCLUDED.observed_at,
+            results = EXCLUDED.results,
+            updated = NOW()
+        WHERE dpu_reachability_reports.observed_at <= EXCLUDED.observed_at";
+
+    sqlx::query(query)
+        .bind(report.dpu_id)
+        .bind(report.observed_at)
+        .bind(Json(&report.results))
+        .execute(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+
+    Ok(())
+}
+
+/// The latest reports of up to `limit` DPUs, ordered by DPU id and starting after `after_dpu_id`
+pub async fn find_page(
+    txn: impl DbReader<'_>,
+    after_dpu_id: Option<&MachineId>,
+    limit: u32,
+) -> DatabaseResult<Vec<DpuReachabilityReport>> {
+    let query = "SELECT dpu_id, observed_at, results FROM dpu_reachability_reports
+        WHERE $1::varchar IS NULL OR dpu_id > $1
+        ORDER BY dpu_id
+        LIMIT $2";
+
+    let reports: Vec<DbDpuReachabilityReport> = sqlx::query_as(query)
+        .bind(after_dpu_id)
+        .bind(i64::from(limit))
+        .fetch_all(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+
+    Ok(reports.into_iter().map(|report| report.0).collect())
+}
@@ -16,9 +16,12 @@
  */
 use std::collections::{BTreeMap, HashMap};
 
+use carbide_uuid::machine::MachineId;
+use carbide_uuid::rack::RackId;
 use itertools::Itertools;
 use mac_address::MacAddress;
 use model::expected_machine::{ExpectedMachine, ExpectedMachineData, LinkedExpectedMachine};
+use model::machine::Machine;
 use sqlx::PgConnection;
 use uuid::Uuid;
 
@@ -82,6 +85,27 @@ pub async fn find_many_by_bmc_mac_address(
         .collect()
 }
 
+/// Returns the rack of each of the given hosts, as recorded in the expected machine of its BMC.
+/// Hosts without an expected machine or without a rack are left out.
+pub async fn find_rack_ids_of_hosts<'a>(
+    txn: &mut PgConnection,
+    hosts: impl IntoIterator<Item = &'a Machine>,
+) -> DatabaseResult<HashMap<MachineId, RackId>> {
+    let bmc_macs = hosts
+        .into_iter()
+        .filter_map(|host| Some((host.id, host.bmc_info.mac?)))
+        .collect::<HashMap<_, _>>();
+    let expected_machines =
+        find_many_by_bmc_mac_address(txn, &bmc_macs.values().copied().collect::<Vec<_>>()).await?;
+
+    Ok(bmc_macs
+        .into_iter()
+        .filter_map(|(machine_id, mac)| {
+            Some((machine_id, expected_machines.get(&mac)?.data.rack_id?))
+        })
+        .collect())
+}
+
 // the expected machines table needs host mac addresses to control dhcp vending of ip's
 // since the carbide dhcp server in some cases is not authoritative on a large network.
 // search in the host_nics field before vending an ip.
@@ -297,6 +297,7 @@ pub async fn update_phone_home_last_contact(
 /// Updates updateable configurations of an instance
 /// - OS
 /// - Keyset IDs
+/// - Metadata token requirement and hop limit
 /// - Metadata
 /// - Security Group
 ///
@@ -328,7 +329,8 @@ pub async fn update_config(
     let query = "UPDATE instances SET config_version=$1,
             os_ipxe_script=$2, os_user_data=$3, os_always_boot_with_ipxe=$4, os_phone_home_enabled=$5,
             os_image_id=$6, keyset_ids=$7,
-            name=$8, description=$9, labels=$10::json, network_security_group_id=$13
+            name=$8, description=$9, labels=$10::json, network_security_group_id=$13,
+            metadata_token_required=$14, metadata_token_hop_limit=$15
             WHERE id=$11 AND config_version=$12
             RETURNING id";
     let query_result: Result<(InstanceId,), _> = sqlx::query_as(query)
@@ -345,6 +347,13 @@ pub async fn update_config(
         .bind(instance_id)
         .bind(expected_version)
         .bind(config.network_security_group_id)
+        .bind(config.tenant.metadata_token_required)
+        .bind(
+            config
+                .tenant
+                .metadata_token_hop_limit
+                .map(|hop_limit| hop_limit as i32),
+        )
         .fetch_one(txn)
         .await;
 
@@ -552,7 +561,9 @@ pub async fn batch_persist<'a>(
                         extension_services_config,
                         extension_services_config_version,
                         nvlink_config,
-                        nvlink_config_version
+                        nvlink_config_version,
+                        metadata_token_required,
+                        metadata_token_hop_limit
                     )
                     SELECT 
                             vals.id, vals.machine_id, vals.os_user_data, vals.os_ipxe_script, 
@@ -564,7 +575,8 @@ pub async fn batch_persist<'a>(
                             vals.network_security_group_id, true,
                             m.instance_type_id, vals.extension_services_config::json, 
                             vals.extension_services_config_version, vals.nvlink_config::json, 
-                            vals.nvlink_config_version
+                            vals.nvlink_config_version, vals.metadata_token_required,
+                            vals.metadata_token_hop_limit
                     FROM (VALUES ";
 
     let mut qb = sqlx::QueryBuilder::new(query);
@@ -640,6 +652,16 @@ pub async fn batch_persist<'a>(
             .push_bind_unseparated(serde_json::to_string(&value.config.nvlink).unwrap_or_default());
         separated.push_unseparated(",");
         separated.push_bind_unseparated(value.nvlink_config_version);
+        separated.push_unseparated(",");
+        separated.push_bind_unseparated(value.config.tenant.metadata_token_required);
+        separated.push_unseparated(",");
+        separated.push_bind_unseparated(
+            value
+                .config
+                .tenant
+                .metadata_token_hop_limit
+                .map(|hop_limit| hop_limit as i32),
+        );
         separated.push_unseparated(")");
     }
 
@@ -648,7 +670,8 @@ pub async fn batch_persist<'a>(
                        ib_config, ib_config_version, keyset_ids, os_phone_home_enabled, name, 
                        description, labels, config_version, hostname, network_security_group_id,
                        instance_type_id, extension_services_config, extension_services_config_version,
-                       nvlink_config, nvlink_config_version)
+                       nvlink_config, nvlink_config_version, metadata_token_required,
+                       metadata_token_hop_limit)
             INNER JOIN machines m ON m.id = vals.machine_id 
                 AND (vals.instance_type_id IS NULL OR m.instance_type_id = vals.instance_type_id)");
 
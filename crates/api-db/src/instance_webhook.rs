@@ -0,0 +1,257 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use chrono::{DateTime, Utc};
+use model::instance_webhook::{
+    InstanceLifecycleEvent, InstanceWebhookDelivery, InstanceWebhookSubscription,
+};
+use model::tenant::TenantOrganizationId;
+use sqlx::postgres::PgRow;
+use sqlx::types::Json;
+use sqlx::{FromRow, PgConnection, Row};
+use uuid::Uuid;
+
+use crate::{DatabaseError, DatabaseResult};
+
+#[derive(Debug, Clone)]
+pub struct DbInstanceWebhookSubscription(pub InstanceWebhookSubscription);
+
+impl<'r> FromRow<'r, PgRow> for DbInstanceWebhookSubscription {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let tenant_organization_id: String = row.try_get("tenant_organization_id")?;
+        let tenant_organization_id = TenantOrganizationId::try_from(tenant_organization_id)
+            .map_err(|e| sqlx::Error::Decode(e.into()))?;
+
+        Ok(DbInstanceWebhookSubscription(InstanceWebhookSubscription {
+            id: row.try_get("id")?,
+            tenant_organization_id,
+            url: row.try_get("url")?,
+            secret: None,
+            event_types: row.try_get("event_types")?,
+            created: row.try_get("created")?,
+        }))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DbInstanceWebhookDelivery(pub InstanceWebhookDelivery);
+
+impl<'r> FromRow<'r, PgRow> for DbInstanceWebhookDelivery {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let event: Json<InstanceLifecycleEvent> = row.try_get("payload")?;
+        let attempts: i32 = row.try_get("attempts")?;
+
+        Ok(DbInstanceWebhookDelivery(InstanceWebhookDelivery {
+            id: row.try_get("id")?,
+            subscription_id: row.try_get("subscription_id")?,
+            url: row.try_get("url")?,
+            event: event.0,
+            attempts: attempts.max(0) as u32,
+            next_attempt_at: row.try_get("next_attempt_at")?,
+            last_error: row.try_get("last_error")?,
+            dead_lettered: row.try_get("dead_lettered")?,
+        }))
+    }
+}
+
+const DELIVERY_COLUMNS: &str = "d.id, d.subscription_id, s.url, d.payload, d.attempts,
+    d.next_attempt_at, d.last_error, d.dead_lettered";
+
+/// Persists a subscription. Its secret isn't part of the row, it is kept in the credential
+/// provider instead.
+pub async fn create_subscription(
+    txn: &mut PgConnection,
+    subscription: &InstanceWebhookSubscription,
+) -> DatabaseResult<InstanceWebhookSubscription> {
+    let query = "INSERT INTO instance_webhook_subscriptions
+            (id, tenant_organization_id, url, event_types)
+        VALUES ($1, $2, $3, $4)
+        RETURNING *";
+    sqlx::query_as::<_, DbInstanceWebhookSubscription>(query)
+        .bind(subscription.id)
+        .bind(subscription.tenant_organization_id.as_str())
+        .bind(&subscription.url)
+        .bind(&subscription.event_types)
+        .fetch_one(txn)
+        .await
+        .map(|subscription| subscription.0)
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+/// Returns the subscriptions of a tenant, or of all tenants
+pub async fn find_subscriptions(
+    txn: &mut PgConnection,
+    tenant_organization_id: Option<&TenantOrganizationId>,
+) -> DatabaseResult<Vec<InstanceWebhookSubscription>> {
+    let query = "SELECT * FROM instance_webhook_subscriptions
+        WHERE $1::text IS NULL OR tenant_organization_id = $1
+        ORDER BY tenant_organization_id, created";
+    sqlx::query_as::<_, DbInstanceWebhookSubscription>(query)
+        .bind(tenant_organization_id.map(|tenant| tenant.as_str()))
+        .fetch_all(txn)
+        .await
+        .map(|subscriptions| subscriptions.into_iter().map(|s| s.0).collect())
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+/// Deletes a subscription together with its pending deliveries and dead letters,
+/// returning an error if it doesn't exist
+pub async fn delete_subscription(txn: &mut PgConnection, id: Uuid) -> DatabaseResult<()> {
+    let query = "DELETE FROM instance_webhook_subscriptions WHERE id = $1";
+    let result = sqlx::query(query)
+        .bind(id)
+        .execute(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+    if result.rows_affected() == 0 {
+        return Err(DatabaseError::NotFoundError {
+            kind: "InstanceWebhookSubscription",
+            id: id.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Queues an event for delivery to all subscriptions of the tenant of the instance which
+/// match the event type. Returns the number of deliveries.
+///
+/// This should be called in the transaction which persists the change the event is about, so
+/// that events are neither lost nor sent for changes which were rolled back.
+pub async fn enqueue_event(
+    txn: &mut PgConnection,
+    event: &InstanceLifecycleEvent,
+) -> DatabaseResult<u64> {
+    let query = "INSERT INTO instance_webhook_deliveries (subscription_id, payload)
+        SELECT id, $3 FROM instance_webhook_subscriptions
+        WHERE tenant_organization_id = $1
+            AND (cardinality(event_types) = 0 OR $2 = ANY(event_types))";
+    sqlx::query(query)
+        .bind(event.tenant_organization_id.as_str())
+        .bind(event.event_type)
+        .bind(Json(event))
+        .execute(txn)
+        .await
+        .map(|result| result.rows_affected())
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+/// Returns the deliveries which are due for their next attempt, oldest first
+pub async fn find_due_deliveries(
+    txn: &mut PgConnection,
+    now: DateTime<Utc>,
+    limit: u32,
+) -> DatabaseResult<Vec<InstanceWebhookDelivery>> {
+    let query = format!(
+        "SELECT {DELIVERY_COLUMNS} FROM instance_webhook_deliveries d
+            JOIN instance_webhook_subscriptions s ON s.id = d.subscription_id
+        WHERE d.dead_lettered IS NULL AND d.next_attempt_at <= $1
+        ORDER BY d.next_attempt_at, d.id
+        LIMIT $2"
+    );
+    sqlx::query_as::<_, DbInstanceWebhookDelivery>(&query)
+        .bind(now)
+        .bind(limit as i64)
+        .fetch_all(txn)
+        .await
+        .map(|deliveries| deliveries.into_iter().map(|d| d.0).collect())
+        .map_err(|e| DatabaseError::query(&query, e))
+}
+
+/// Returns the deliveries which ran out of attempts, for a tenant or all tenants
+pub async fn find_dead_letters(
+    txn: &mut PgConnection,
+    tenant_organization_id: Option<&TenantOrganizationId>,
+) -> DatabaseResult<Vec<InstanceWebhookDelivery>> {
+    let query = format!(
+        "SELECT {DELIVERY_COLUMNS} FROM instance_webhook_deliveries d
+            JOIN instance_webhook_subscriptions s ON s.id = d.subscription_id
+        WHERE d.dead_lettered IS NOT NULL
+            AND ($1::text IS NULL OR s.tenant_organization_id = $1)
+        ORDER BY d.id"
+    );
+    sqlx::query_as::<_, DbInstanceWebhookDelivery>(&query)
+        .bind(tenant_organization_id.map(|tenant| tenant.as_str()))
+        .fetch_all(txn)
+        .await
+        .map(|deliveries| deliveries.into_iter().map(|d| d.0).collect())
+        .map_err(|e| DatabaseError::query(&query, e))
+}
+
+/// Removes a delivery once the subscriber accepted it
+pub async fn mark_delivered(txn: &mut PgConnection, id: i64) -> DatabaseResult<()> {
+    let query = "DELETE FROM instance_webhook_deliveries WHERE id = $1";
+    sqlx::query(query)
+        .bind(id)
+        .execute(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+    Ok(())
+}
+
+/// Records a failed attempt of a delivery. The delivery is retried at `next_attempt_at`,
+/// or moved to the dead letters if that is not set.
+pub async fn mark_failed(
+    txn: &mut PgConnection,
+    id: i64,
+    error: &str,
+    next_attempt_at: Option<DateTime<Utc>>,
+) -> DatabaseResult<()> {
+    let query = "UPDATE instance_webhook_deliveries
+        SET attempts = attempts + 1,
+            last_error = $2,
+            next_attempt_at = COALESCE($3, next_attempt_at),
+            dead_lettered = CASE WHEN $3 IS NULL THEN NOW() END
+        WHERE id = $1";
+    sqlx::query(query)
+        .bind(id)
+        .bind(error)
+        .bind(next_attempt_at)
+        .execute(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+    Ok(())
+}
+
+/// Removes the dead letters which ran out of attempts before `older_than`.
+/// Returns the number of removed dead letters.
+pub async fn prune_dead_letters(
+    txn: &mut PgConnection,
+    older_than: DateTime<Utc>,
+) -> DatabaseResult<u64> {
+    let query = "DELETE FROM instance_webhook_deliveries
+        WHERE dead_lettered IS NOT NULL AND dead_lettered < $1";
+    sqlx::query(query)
+        .bind(older_than)
+        .execute(txn)
+        .await
+        .map(|result| result.rows_affected())
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+/// Queues dead letters for delivery again, with a fresh set of attempts.
+/// Returns the number of dead letters which were found.
+pub async fn retry_dead_letters(txn: &mut PgConnection, ids: &[i64]) -> DatabaseResult<u64> {
+    let query = "UPDATE instance_webhook_deliveries
+        SET attempts = 0, next_attempt_at = NOW(), dead_lettered = NULL
+        WHERE id = ANY($1) AND dead_lettered IS NOT NULL";
+    sqlx::query(query)
+        .bind(ids)
+        .execute(txn)
+        .await
+        .map(|result| result.rows_affected())
+        .map_err(|e| DatabaseError::query(query, e))
+}
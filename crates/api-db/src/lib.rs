@@ -20,7 +20,9 @@
 #![allow(unknown_lints)]
 
 pub mod attestation;
+pub mod bmc_credential_rotation;
 pub mod bmc_metadata;
+pub mod capacity_reservation;
 pub mod carbide_version;
 pub mod db_read;
 pub mod desired_firmware;
@@ -31,6 +33,7 @@ pub mod dpa_interface;
 pub mod dpa_interface_state_history;
 pub mod dpu_agent_upgrade_policy;
 pub mod dpu_machine_update;
+pub mod dpu_reachability;
 pub mod dpu_remediation;
 pub mod expected_machine;
 pub mod expected_power_shelf;
@@ -44,18 +47,23 @@ pub mod instance;
 pub mod instance_address;
 pub mod instance_network_config;
 pub mod instance_type;
+pub mod instance_webhook;
 pub mod ip_allocator;
 pub mod machine;
 pub mod machine_boot_override;
+pub mod machine_erasure_report;
 pub mod machine_health_history;
 pub mod machine_interface;
 pub mod machine_interface_address;
+pub mod machine_state_handler_outcome_history;
 pub mod machine_state_history;
 pub mod machine_topology;
+pub mod machine_update_rollout;
 pub mod machine_validation;
 pub mod machine_validation_config;
 pub mod machine_validation_result;
 pub mod machine_validation_suites;
+pub mod maintenance_window;
 pub mod managed_host;
 pub mod measured_boot;
 pub mod migrations;
@@ -80,6 +88,7 @@ pub mod resource_pool;
 pub mod route_servers;
 pub mod site_exploration_report;
 pub mod sku;
+pub mod ssh_console_session;
 pub mod switch;
 pub mod switch_state_history;
 pub mod tenant;
@@ -23,7 +23,7 @@ use std::net::IpAddr;
 use std::ops::Deref;
 use std::str::FromStr;
 
-use ::rpc::forge::DpuInfo;
+use ::rpc::forge::{DpuInfo, DpuVpcProbeTarget};
 use carbide_uuid::instance_type::InstanceTypeId;
 use carbide_uuid::machine::{MachineId, MachineType};
 use chrono::prelude::*;
@@ -340,7 +340,8 @@ pub async fn find_ids_by_instance_type_id(
     builder.push_bind(instance_type_id);
 
     if for_update {
-        builder.push(" FOR UPDATE ");
+        // Lock in a consistent order, so that concurrent callers don't deadlock
+        builder.push(" ORDER BY id FOR UPDATE ");
     }
 
     builder
@@ -1169,11 +1170,17 @@ pub async fn try_sync_stable_id_with_current_machine_id_for_host(
         };
     }
 
-    // Update the machine state and heatlh history to account for the rename
+    // Update the machine state, heatlh and state handler outcome history to account for the rename
     crate::machine_state_history::update_machine_ids(txn, current_machine_id, stable_machine_id)
         .await?;
     crate::machine_health_history::update_machine_ids(txn, current_machine_id, stable_machine_id)
         .await?;
+    crate::machine_state_handler_outcome_history::update_machine_ids(
+        txn,
+        current_machine_id,
+        stable_machine_id,
+    )
+    .await?;
 
     // Table machine_interfaces has a FK ON UPDATE CASCADE so machine_interfaces.machine_id will
     // also change.
@@ -1838,12 +1845,57 @@ pub async fn find_dpu_ids_and_loopback_ips(
         .await
         .map_err(|e| DatabaseError::query(query, e))?
         .into_iter()
-        .map(|(id, loopback_ip)| DpuInfo { id, loopback_ip })
+        .map(|(id, loopback_ip)| DpuInfo {
+            id,
+            loopback_ip,
+            vpc_probe_targets: Vec::new(),
+        })
         .collect();
 
     Ok(dpu_infos)
 }
 
+/// Find the targets of probes on the overlay of VPCs, as pairs of DPU id and target
+///
+/// The targets are the loopback addresses the DPUs have in the VRF of each FNN VPC, so that
+/// probes are answered by the DPU itself rather than by a tenant instance. VPCs of other
+/// virtualization types have no addresses of the DPUs and are not probed.
+pub async fn find_dpu_vpc_probe_targets(
+    txn: &mut PgConnection,
+) -> Result<Vec<(String, DpuVpcProbeTarget)>, DatabaseError> {
+    let query = "
+        SELECT
+            l.dpu_id,
+            v.id AS vpc_id,
+            (v.status->>'vni')::bigint AS vni,
+            host(l.loopback_ip) AS address
+        FROM vpc_dpu_loopbacks l
+        INNER JOIN vpcs v ON v.id = l.vpc_id AND v.deleted IS NULL
+        WHERE l.loopback_ip IS NOT NULL
+            AND v.status->>'vni' IS NOT NULL
+            AND family(l.loopback_ip) = 4
+        ORDER BY l.dpu_id, v.id";
+
+    let targets = sqlx::query_as::<_, (String, uuid::Uuid, i64, String)>(query)
+        .fetch_all(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?
+        .into_iter()
+        .map(|(dpu_id, vpc_id, vni, address)| {
+            (
+                dpu_id,
+                DpuVpcProbeTarget {
+                    vpc_id: vpc_id.to_string(),
+                    vni: vni as u32,
+                    address,
+                },
+            )
+        })
+        .collect();
+
+    Ok(targets)
+}
+
 /// Allocate a value from the loopback IP resource pool.
 ///
 /// If the pool exists but is empty or has en error, return that.
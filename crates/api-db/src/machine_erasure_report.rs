@@ -0,0 +1,133 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use carbide_uuid::instance::InstanceId;
+use carbide_uuid::machine::MachineId;
+use model::machine_erasure_report::{MachineErasureReport, StorageErasureRecord};
+use model::tenant::TenantOrganizationId;
+use sqlx::postgres::PgRow;
+use sqlx::types::Json;
+use sqlx::{FromRow, PgConnection, Row};
+
+use crate::db_read::DbReader;
+use crate::{DatabaseError, DatabaseResult};
+
+#[derive(Debug, Clone)]
+pub struct DbMachineErasureReport(pub MachineErasureReport);
+
+impl<'r> FromRow<'r, PgRow> for DbMachineErasureReport {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let tenant_organization_id: Option<String> = row.try_get("tenant_organization_id")?;
+        let tenant_organization_id = tenant_organization_id
+            .map(TenantOrganizationId::try_from)
+            .transpose()
+            .map_err(|e| sqlx::Error::Decode(e.into()))?;
+        let records: Json<Vec<StorageErasureRecord>> = row.try_get("records")?;
+
+        Ok(DbMachineErasureReport(MachineErasureReport {
+            id: row.try_get("id")?,
+            machine_id: row.try_get("machine_id")?,
+            instance_id: row.try_get("instance_id")?,
+            tenant_organization_id,
+            released_at: row.try_get("released_at")?,
+            completed_at: row.try_get("completed_at")?,
+            records: records.0,
+            signed_report: row.try_get("signed_report")?,
+        }))
+    }
+}
+
+/// Opens the pending report of a machine whose instance is released.
+/// Replaces the instance of a pending report which was never completed.
+pub async fn open(txn: &mut PgConnection, report: &MachineErasureReport) -> DatabaseResult<()> {
+    let query = "INSERT INTO machine_erasure_reports
+            (id, machine_id, instance_id, tenant_organization_id, released_at)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (machine_id) WHERE completed_at IS NULL DO UPDATE SET
+            instance_id = EXCLUDED.instance_id,
+            tenant_organization_id = EXCLUDED.tenant_organization_id,
+            released_at = EXCLUDED.released_at";
+    sqlx::query(query)
+        .bind(report.id)
+        .bind(report.machine_id)
+        .bind(report.instance_id)
+        .bind(report.tenant_organization_id.as_ref().map(|id| id.as_str()))
+        .bind(report.released_at)
+        .execute(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+    Ok(())
+}
+
+/// Returns the report which waits for the cleanup of the machine to complete
+pub async fn find_pending(
+    txn: &mut PgConnection,
+    machine_id: &MachineId,
+) -> DatabaseResult<Option<MachineErasureReport>> {
+    let query =
+        "SELECT * FROM machine_erasure_reports WHERE machine_id = $1 AND completed_at IS NULL";
+    sqlx::query_as::<_, DbMachineErasureReport>(query)
+        .bind(machine_id)
+        .fetch_optional(txn)
+        .await
+        .map(|report| report.map(|report| report.0))
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+/// Stores the records of a report, which is completed if `completed_at` is set
+pub async fn save(txn: &mut PgConnection, report: &MachineErasureReport) -> DatabaseResult<()> {
+    let query = "INSERT INTO machine_erasure_reports
+            (id, machine_id, instance_id, tenant_organization_id, released_at,
+             completed_at, records, signed_report)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        ON CONFLICT (id) DO UPDATE SET
+            completed_at = EXCLUDED.completed_at,
+            records = EXCLUDED.records,
+            signed_report = EXCLUDED.signed_report";
+    sqlx::query(query)
+        .bind(report.id)
+        .bind(report.machine_id)
+        .bind(report.instance_id)
+        .bind(report.tenant_organization_id.as_ref().map(|id| id.as_str()))
+        .bind(report.released_at)
+        .bind(report.completed_at)
+        .bind(Json(&report.records))
+        .bind(&report.signed_report)
+        .execute(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+    Ok(())
+}
+
+/// Returns the reports of a machine and/or instance, newest first
+pub async fn find(
+    txn: impl DbReader<'_>,
+    machine_id: Option<&MachineId>,
+    instance_id: Option<InstanceId>,
+) -> DatabaseResult<Vec<MachineErasureReport>> {
+    let query = "SELECT * FROM machine_erasure_reports
+        WHERE ($1::varchar IS NULL OR machine_id = $1)
+            AND ($2::uuid IS NULL OR instance_id = $2)
+        ORDER BY created DESC";
+    sqlx::query_as::<_, DbMachineErasureReport>(query)
+        .bind(machine_id)
+        .bind(instance_id)
+        .fetch_all(txn)
+        .await
+        .map(|reports| reports.into_iter().map(|report| report.0).collect())
+        .map_err(|e| DatabaseError::query(query, e))
+}
@@ -40,6 +40,19 @@ pub async fn find_ipv4_for_interface(
         .map_err(|e| DatabaseError::query(query, e))
 }
 
+pub async fn find_ipv6_for_interface(
+    txn: &mut PgConnection,
+    interface_id: MachineInterfaceId,
+) -> Result<Option<MachineInterfaceAddress>, DatabaseError> {
+    let query =
+        "SELECT * FROM machine_interface_addresses WHERE interface_id = $1 AND family(address) = 6";
+    sqlx::query_as(query)
+        .bind(interface_id)
+        .fetch_optional(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
 pub async fn find_by_address(
     txn: &mut PgConnection,
     address: IpAddr,
@@ -0,0 +1,195 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use carbide_uuid::machine::MachineId;
+use chrono::{DateTime, Utc};
+use model::controller_outcome::PersistentStateHandlerOutcome;
+use model::machine::{MachineStateHandlerOutcomeRecord, ManagedHostState};
+use sqlx::postgres::PgRow;
+use sqlx::{FromRow, PgConnection, Row};
+
+use crate::DatabaseError;
+
+/// A single state handler outcome recorded for a Machine
+#[derive(Debug, Clone)]
+struct DbMachineStateHandlerOutcomeRecord {
+    /// The ID of the machine the state handler acted upon
+    pub machine_id: MachineId,
+
+    /// The state the Machine was in when the state handler was invoked
+    pub state: String,
+
+    /// The outcome of the state handler invocation
+    pub outcome: PersistentStateHandlerOutcome,
+
+    /// The state controller iteration during which the handler was invoked
+    pub iteration_id: Option<i64>,
+
+    /// How long the state handler took, in microseconds
+    pub handler_latency_us: i64,
+
+    /// How often the state handler produced this outcome in a row
+    pub repeat_count: i32,
+
+    /// The time when the outcome was first recorded
+    pub time: DateTime<Utc>,
+
+    /// The time when the outcome was last recorded
+    pub last_time: DateTime<Utc>,
+}
+
+impl<'r> FromRow<'r, PgRow> for DbMachineStateHandlerOutcomeRecord {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        Ok(DbMachineStateHandlerOutcomeRecord {
+            machine_id: row.try_get("machine_id")?,
+            state: row.try_get("state")?,
+            outcome: row
+                .try_get::<sqlx::types::Json<PersistentStateHandlerOutcome>, _>("outcome")?
+                .0,
+            iteration_id: row.try_get("iteration_id")?,
+            handler_latency_us: row.try_get("handler_latency_us")?,
+            repeat_count: row.try_get("repeat_count")?,
+            time: row.try_get("time")?,
+            last_time: row.try_get("last_time")?,
+        })
+    }
+}
+
+impl From<DbMachineStateHandlerOutcomeRecord> for MachineStateHandlerOutcomeRecord {
+    fn from(record: DbMachineStateHandlerOutcomeRecord) -> Self {
+        Self {
+            state: record.state,
+            outcome: record.outcome,
+            iteration_id: record.iteration_id,
+            handler_latency: std::time::Duration::from_micros(
+                record.handler_latency_us.max(0) as u64
+            ),
+            repeat_count: record.repeat_count.max(1) as u32,
+            time: record.time,
+            last_time: record.last_time,
+        }
+    }
+}
+
+/// Retrieve the state handler outcome history for a list of Machines
+///
+/// It returns a [HashMap][std::collections::HashMap] keyed by the machine ID and
+/// the outcomes the state handler produced for the Machine, starting with the
+/// oldest.
+pub async fn find_by_machine_ids(
+    txn: &mut PgConnection,
+    ids: &[MachineId],
+) -> Result<
+    std::collections::HashMap<MachineId, Vec<MachineStateHandlerOutcomeRecord>>,
+    DatabaseError,
+> {
+    let query = "SELECT machine_id, state::TEXT, outcome, iteration_id, handler_latency_us,
+            repeat_count, time, last_time
+        FROM machine_state_handler_outcome_history
+        WHERE machine_id=ANY($1)
+        ORDER BY id ASC";
+    let str_ids: Vec<String> = ids.iter().map(|id| id.to_string()).collect();
+    let query_results = sqlx::query_as::<_, DbMachineStateHandlerOutcomeRecord>(query)
+        .bind(str_ids)
+        .fetch_all(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+
+    let mut histories = std::collections::HashMap::new();
+    for result in query_results.into_iter() {
+        let records: &mut Vec<MachineStateHandlerOutcomeRecord> =
+            histories.entry(result.machine_id).or_default();
+        records.push(result.into());
+    }
+    Ok(histories)
+}
+
+/// Store the outcome of a state handler invocation for a Machine
+///
+/// If the most recent entry of the Machine has the same state and outcome,
+/// that entry is updated instead of adding a new one. Old entries are removed
+/// by [prune].
+pub async fn persist(
+    txn: &mut PgConnection,
+    machine_id: &MachineId,
+    state: &ManagedHostState,
+    outcome: &PersistentStateHandlerOutcome,
+    iteration_id: Option<i64>,
+    handler_latency: std::time::Duration,
+) -> Result<(), DatabaseError> {
+    let query = "WITH latest AS (
+            SELECT id, state, outcome FROM machine_state_handler_outcome_history
+            WHERE machine_id=$1
+            ORDER BY id DESC LIMIT 1
+        ), repeated AS (
+            UPDATE machine_state_handler_outcome_history h
+            SET repeat_count=h.repeat_count + 1, iteration_id=$4, handler_latency_us=$5,
+                last_time=NOW()
+            FROM latest
+            WHERE h.id=latest.id AND latest.state=$2 AND latest.outcome=$3
+            RETURNING h.id
+        )
+        INSERT INTO machine_state_handler_outcome_history
+        (machine_id, state, outcome, iteration_id, handler_latency_us)
+        SELECT $1, $2, $3, $4, $5
+        WHERE NOT EXISTS (SELECT 1 FROM repeated)";
+    sqlx::query(query)
+        .bind(machine_id)
+        .bind(sqlx::types::Json(state))
+        .bind(sqlx::types::Json(outcome))
+        .bind(iteration_id)
+        .bind(handler_latency.as_micros().min(i64::MAX as u128) as i64)
+        .execute(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+    Ok(())
+}
+
+/// Removes all but the most recent `keep` outcome entries of every Machine
+///
+/// Returns the number of deleted entries.
+pub async fn prune(txn: &mut PgConnection, keep: u32) -> Result<u64, DatabaseError> {
+    let query = "DELETE FROM machine_state_handler_outcome_history h
+        USING (
+            SELECT id, ROW_NUMBER() OVER (PARTITION BY machine_id ORDER BY id DESC) AS position
+            FROM machine_state_handler_outcome_history
+        ) ranked
+        WHERE h.id=ranked.id AND ranked.position > $1";
+    sqlx::query(query)
+        .bind(i64::from(keep))
+        .execute(txn)
+        .await
+        .map(|result| result.rows_affected())
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+/// Renames all outcome entries using one Machine ID into using another Machine ID
+pub async fn update_machine_ids(
+    txn: &mut PgConnection,
+    old_machine_id: &MachineId,
+    new_machine_id: &MachineId,
+) -> Result<(), DatabaseError> {
+    let query =
+        "UPDATE machine_state_handler_outcome_history SET machine_id=$1 WHERE machine_id=$2";
+    sqlx::query(query)
+        .bind(new_machine_id)
+        .bind(old_machine_id)
+        .execute(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+
+    Ok(())
+}
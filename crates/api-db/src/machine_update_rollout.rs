@@ -0,0 +1,144 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use carbide_uuid::machine::MachineId;
+use model::machine_update_rollout::{MachineUpdateRollout, RolloutMachine};
+use sqlx::postgres::PgRow;
+use sqlx::{FromRow, PgConnection, Row};
+
+use crate::{DatabaseError, DatabaseResult};
+
+#[derive(Debug, Clone)]
+pub struct DbMachineUpdateRollout(pub MachineUpdateRollout);
+
+impl<'r> FromRow<'r, PgRow> for DbMachineUpdateRollout {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let wave: i32 = row.try_get("wave")?;
+        let machines: sqlx::types::Json<Vec<RolloutMachine>> = row.try_get("machines")?;
+        let unhealthy_machines: sqlx::types::Json<Vec<MachineId>> =
+            row.try_get("unhealthy_machines")?;
+        let acknowledged_machines: sqlx::types::Json<Vec<MachineId>> =
+            row.try_get("acknowledged_machines")?;
+
+        Ok(DbMachineUpdateRollout(MachineUpdateRollout {
+            id: Some(row.try_get("id")?),
+            state: row.try_get("state")?,
+            wave: wave as u32,
+            wave_completed_at: row.try_get("wave_completed_at")?,
+            machines: machines.0,
+            unhealthy_machines: unhealthy_machines.0,
+            acknowledged_machines: acknowledged_machines.0,
+            state_reason: row.try_get("state_reason")?,
+            created: row.try_get("created")?,
+            updated: row.try_get("updated")?,
+        }))
+    }
+}
+
+/// Returns the most recent rollout. Only this rollout can be active.
+/// The row is locked until the end of the transaction if `for_update` is set.
+pub async fn find_latest(
+    txn: &mut PgConnection,
+    for_update: bool,
+) -> DatabaseResult<Option<MachineUpdateRollout>> {
+    let query = if for_update {
+        "SELECT * FROM machine_update_rollouts ORDER BY id DESC LIMIT 1 FOR UPDATE"
+    } else {
+        "SELECT * FROM machine_update_rollouts ORDER BY id DESC LIMIT 1"
+    };
+    sqlx::query_as::<_, DbMachineUpdateRollout>(query)
+        .fetch_optional(txn)
+        .await
+        .map(|rollout| rollout.map(|rollout| rollout.0))
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+pub async fn find_by_id(
+    txn: &mut PgConnection,
+    id: i64,
+) -> DatabaseResult<Option<MachineUpdateRollout>> {
+    let query = "SELECT * FROM machine_update_rollouts WHERE id = $1";
+    sqlx::query_as::<_, DbMachineUpdateRollout>(query)
+        .bind(id)
+        .fetch_optional(txn)
+        .await
+        .map(|rollout| rollout.map(|rollout| rollout.0))
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+pub async fn create(
+    txn: &mut PgConnection,
+    rollout: &MachineUpdateRollout,
+) -> DatabaseResult<MachineUpdateRollout> {
+    let query = "INSERT INTO machine_update_rollouts
+            (state, wave, wave_completed_at, machines, unhealthy_machines, acknowledged_machines, state_reason)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING *";
+    sqlx::query_as::<_, DbMachineUpdateRollout>(query)
+        .bind(rollout.state)
+        .bind(rollout.wave as i32)
+        .bind(rollout.wave_completed_at)
+        .bind(sqlx::types::Json(&rollout.machines))
+        .bind(sqlx::types::Json(&rollout.unhealthy_machines))
+        .bind(sqlx::types::Json(&rollout.acknowledged_machines))
+        .bind(&rollout.state_reason)
+        .fetch_one(txn)
+        .await
+        .map(|rollout| rollout.0)
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+pub async fn update(
+    txn: &mut PgConnection,
+    id: i64,
+    rollout: &MachineUpdateRollout,
+) -> DatabaseResult<MachineUpdateRollout> {
+    let query = "UPDATE machine_update_rollouts SET
+            state = $2, wave = $3, wave_completed_at = $4, machines = $5,
+            unhealthy_machines = $6, acknowledged_machines = $7, state_reason = $8,
+            updated = NOW()
+        WHERE id = $1
+        RETURNING *";
+    sqlx::query_as::<_, DbMachineUpdateRollout>(query)
+        .bind(id)
+        .bind(rollout.state)
+        .bind(rollout.wave as i32)
+        .bind(rollout.wave_completed_at)
+        .bind(sqlx::types::Json(&rollout.machines))
+        .bind(sqlx::types::Json(&rollout.unhealthy_machines))
+        .bind(sqlx::types::Json(&rollout.acknowledged_machines))
+        .bind(&rollout.state_reason)
+        .fetch_optional(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?
+        .map(|rollout| rollout.0)
+        .ok_or_else(|| DatabaseError::NotFoundError {
+            kind: "MachineUpdateRollout",
+            id: id.to_string(),
+        })
+}
+
+/// Stores a rollout. A rollout without ID is inserted, otherwise the existing one is updated.
+pub async fn save(
+    txn: &mut PgConnection,
+    rollout: &MachineUpdateRollout,
+) -> DatabaseResult<MachineUpdateRollout> {
+    match rollout.id {
+        None => create(txn, rollout).await,
+        Some(id) => update(txn, id, rollout).await,
+    }
+}
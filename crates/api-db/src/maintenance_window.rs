@@ -0,0 +1,146 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use chrono::Duration;
+use chrono_tz::Tz;
+use model::maintenance_window::{CronSchedule, MaintenanceWindow, MaintenanceWindowScope};
+use sqlx::postgres::PgRow;
+use sqlx::{FromRow, PgConnection, Row};
+
+use crate::{DatabaseError, DatabaseResult};
+
+#[derive(Debug, Clone, Copy, sqlx::Type)]
+#[sqlx(type_name = "maintenance_window_scope")]
+#[sqlx(rename_all = "lowercase")]
+enum DbMaintenanceWindowScope {
+    Site,
+    Rack,
+    Tenant,
+}
+
+#[derive(Debug, Clone)]
+pub struct DbMaintenanceWindow(pub MaintenanceWindow);
+
+impl<'r> FromRow<'r, PgRow> for DbMaintenanceWindow {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let scope: DbMaintenanceWindowScope = row.try_get("scope")?;
+        let scope_id: Option<String> = row.try_get("scope_id")?;
+        let scope = match (scope, scope_id) {
+            (DbMaintenanceWindowScope::Site, _) => MaintenanceWindowScope::Site,
+            (DbMaintenanceWindowScope::Rack, Some(rack_id)) => {
+                MaintenanceWindowScope::Rack(rack_id)
+            }
+            (DbMaintenanceWindowScope::Tenant, Some(tenant)) => {
+                MaintenanceWindowScope::Tenant(tenant)
+            }
+            (scope, None) => {
+                return Err(sqlx::Error::Decode(
+                    format!("missing scope_id for maintenance window scope {scope:?}").into(),
+                ));
+            }
+        };
+        let schedule: Option<String> = row.try_get("schedule")?;
+        let schedule = schedule
+            .map(|schedule| schedule.parse::<CronSchedule>())
+            .transpose()
+            .map_err(|e| sqlx::Error::Decode(e.into()))?;
+        let timezone: String = row.try_get("timezone")?;
+        let timezone = timezone
+            .parse::<Tz>()
+            .map_err(|e| sqlx::Error::Decode(e.to_string().into()))?;
+        let duration_minutes: i32 = row.try_get("duration_minutes")?;
+
+        Ok(DbMaintenanceWindow(MaintenanceWindow {
+            name: row.try_get("name")?,
+            kind: row.try_get("kind")?,
+            scope,
+            schedule,
+            duration: Duration::minutes(duration_minutes.into()),
+            timezone,
+            starts_at: row.try_get("starts_at")?,
+            ends_at: row.try_get("ends_at")?,
+            description: row.try_get("description")?,
+            created: row.try_get("created")?,
+        }))
+    }
+}
+
+pub async fn create(
+    txn: &mut PgConnection,
+    window: &MaintenanceWindow,
+) -> DatabaseResult<MaintenanceWindow> {
+    let (scope, scope_id) = match &window.scope {
+        MaintenanceWindowScope::Site => (DbMaintenanceWindowScope::Site, None),
+        MaintenanceWindowScope::Rack(rack_id) => (DbMaintenanceWindowScope::Rack, Some(rack_id)),
+        MaintenanceWindowScope::Tenant(tenant) => (DbMaintenanceWindowScope::Tenant, Some(tenant)),
+    };
+    let query = "INSERT INTO maintenance_windows
+            (name, kind, scope, scope_id, schedule, duration_minutes, timezone, starts_at, ends_at, description)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+        ON CONFLICT DO NOTHING
+        RETURNING *";
+    sqlx::query_as::<_, DbMaintenanceWindow>(query)
+        .bind(&window.name)
+        .bind(window.kind)
+        .bind(scope)
+        .bind(scope_id)
+        .bind(
+            window
+                .schedule
+                .as_ref()
+                .map(|schedule| schedule.to_string()),
+        )
+        .bind(window.duration.num_minutes() as i32)
+        .bind(window.timezone.name())
+        .bind(window.starts_at)
+        .bind(window.ends_at)
+        .bind(&window.description)
+        .fetch_optional(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?
+        .map(|window| window.0)
+        .ok_or_else(|| DatabaseError::AlreadyFoundError {
+            kind: "MaintenanceWindow",
+            id: window.name.clone(),
+        })
+}
+
+pub async fn find_all(txn: &mut PgConnection) -> DatabaseResult<Vec<MaintenanceWindow>> {
+    let query = "SELECT * FROM maintenance_windows ORDER BY name";
+    sqlx::query_as::<_, DbMaintenanceWindow>(query)
+        .fetch_all(txn)
+        .await
+        .map(|windows| windows.into_iter().map(|window| window.0).collect())
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+/// Deletes a window, returning an error if it doesn't exist
+pub async fn delete(txn: &mut PgConnection, name: &str) -> DatabaseResult<()> {
+    let query = "DELETE FROM maintenance_windows WHERE name = $1";
+    let result = sqlx::query(query)
+        .bind(name)
+        .execute(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+    if result.rows_affected() == 0 {
+        return Err(DatabaseError::NotFoundError {
+            kind: "MaintenanceWindow",
+            id: name.to_string(),
+        });
+    }
+    Ok(())
+}
@@ -15,18 +15,21 @@
  * limitations under the License.
  */
 use carbide_uuid::instance::InstanceId;
+use carbide_uuid::machine::MachineId;
 use carbide_uuid::network_security_group::NetworkSecurityGroupId;
-use carbide_uuid::vpc::VpcId;
+use carbide_uuid::vpc::{VpcId, VpcPrefixId};
 use config_version::ConfigVersion;
+use ipnetwork::IpNetwork;
 use model::metadata::Metadata;
 use model::network_security_group::{
-    NetworkSecurityGroup, NetworkSecurityGroupAttachments,
+    NetworkSecurityGroup, NetworkSecurityGroupAttachments, NetworkSecurityGroupDpuUsage,
     NetworkSecurityGroupPropagationObjectStatus, NetworkSecurityGroupRule,
+    NetworkSecurityGroupRuleNet, NetworkSecurityGroupRuleNetPrefixes,
 };
 use model::tenant::TenantOrganizationId;
 use sqlx::{PgConnection, Postgres};
 
-use crate::DatabaseError;
+use crate::{BIND_LIMIT, DatabaseError};
 
 /// Creates a new NetworkSecurityGroup DB record.  It enforces a unique `name` by
 /// only creating if there is no active record found with the same name.
@@ -125,6 +128,49 @@ pub async fn find_ids(
         .map_err(|err| DatabaseError::query(builder.sql(), err))
 }
 
+/// Queries the DB for the IDs of non-deleted NetworkSecurityGroups
+/// with rules referencing a VPC as source or destination.
+///
+/// * `txn`    - A reference to a currently open database transaction
+/// * `vpc_id` - The ID of the referenced VPC
+pub async fn find_ids_referencing_vpc(
+    txn: &mut PgConnection,
+    vpc_id: &VpcId,
+) -> Result<Vec<NetworkSecurityGroupId>, DatabaseError> {
+    find_ids_referencing_net(txn, &NetworkSecurityGroupRuleNet::VpcId(*vpc_id)).await
+}
+
+/// Queries the DB for the IDs of non-deleted NetworkSecurityGroups
+/// with rules referencing a VPC prefix as source or destination.
+///
+/// * `txn`           - A reference to a currently open database transaction
+/// * `vpc_prefix_id` - The ID of the referenced VPC prefix
+pub async fn find_ids_referencing_vpc_prefix(
+    txn: &mut PgConnection,
+    vpc_prefix_id: &VpcPrefixId,
+) -> Result<Vec<NetworkSecurityGroupId>, DatabaseError> {
+    find_ids_referencing_net(
+        txn,
+        &NetworkSecurityGroupRuleNet::VpcPrefixId(*vpc_prefix_id),
+    )
+    .await
+}
+
+async fn find_ids_referencing_net(
+    txn: &mut PgConnection,
+    net: &NetworkSecurityGroupRuleNet,
+) -> Result<Vec<NetworkSecurityGroupId>, DatabaseError> {
+    let query = "SELECT id FROM network_security_groups
+        WHERE deleted IS NULL AND (rules @> $1::jsonb OR rules @> $2::jsonb)";
+
+    sqlx::query_as(query)
+        .bind(sqlx::types::Json(serde_json::json!([{ "src_net": net }])))
+        .bind(sqlx::types::Json(serde_json::json!([{ "dst_net": net }])))
+        .fetch_all(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
 /// Queries the DB for non-deleted NetworkSecurityGroup records
 /// based on the supplied list of IDs
 ///
@@ -252,10 +298,14 @@ pub async fn get_propagation_status(
         sum(interfaces_expected)::INT4 as interfaces_expected,
         sum(interfaces_applied)::INT4 as interfaces_applied,
         COALESCE(json_agg(distinct instance_id) FILTER (WHERE interfaces_expected != interfaces_applied), '[]') as unpropagated_instance_ids,
-        COALESCE(json_agg(distinct instance_id) FILTER (WHERE instance_id IS NOT  NULL), '[]') as related_instance_ids
+        COALESCE(json_agg(distinct instance_id) FILTER (WHERE instance_id IS NOT  NULL), '[]') as related_instance_ids,
+        max(propagation_error) as propagation_error
         FROM (
             SELECT
                 v.id as vpc_id, i.id as instance_id,
+                /* Set if the NSG can't be propagated to the DPUs, e.g. because it exceeds limits. */
+                (SELECT string_agg(DISTINCT u.error, '; ') FROM network_security_group_dpu_usage u
+                    WHERE u.network_security_group_id = nsg.id AND u.error IS NOT NULL) as propagation_error,
                 /*
                 * Get the number of interfaces associated with the instance
                 * that do not have NSGs on the interface.
@@ -303,13 +353,14 @@ pub async fn get_propagation_status(
         vpc_query_builder.push_bind(tenant_organization_id.map(|t| t.to_string()));
     }
 
-    vpc_query_builder.push(" GROUP BY v.id, i.id) as prop_stats GROUP BY vpc_id");
+    vpc_query_builder.push(" GROUP BY v.id, i.id, nsg.id) as prop_stats GROUP BY vpc_id");
 
     let mut instance_query_builder = sqlx::QueryBuilder::new("
         SELECT
         instance_id::text as id,
         interfaces_expected,
         interfaces_applied,
+        propagation_error,
 
         /* Provide a list of instances related to the object that don't have the correct NSG details. */    
         COALESCE(json_agg(distinct instance_id) FILTER (WHERE interfaces_expected != interfaces_applied), '[]') as unpropagated_instance_ids,
@@ -319,6 +370,9 @@ pub async fn get_propagation_status(
         FROM (
             SELECT
                 i.id as instance_id,
+                /* Set if the NSG can't be propagated to the DPUs, e.g. because it exceeds limits. */
+                (SELECT string_agg(DISTINCT u.error, '; ') FROM network_security_group_dpu_usage u
+                    WHERE u.network_security_group_id = nsg.id AND u.error IS NOT NULL) as propagation_error,
                 /*
                 * Get the number of interfaces associated with the instance
                 * that do not have NSGs on the interface.
@@ -366,7 +420,7 @@ pub async fn get_propagation_status(
     }
 
     instance_query_builder.push(
-        " GROUP BY i.id) as prop_stats GROUP BY instance_id,interfaces_expected,interfaces_applied",
+        " GROUP BY i.id, nsg.id) as prop_stats GROUP BY instance_id,interfaces_expected,interfaces_applied,propagation_error",
     );
 
     let vpcs = vpc_query_builder
@@ -499,3 +553,152 @@ pub async fn soft_delete(
         Err(e) => Err(DatabaseError::query(query, e)),
     }
 }
+
+/// Queries the DB for the prefixes of the VPCs and VPC prefixes
+/// referenced by NetworkSecurityGroup rules.  Objects which don't
+/// exist, or don't belong to the tenant org, are not part of the result.
+///
+/// * `txn`                    - A reference to an active DB transaction
+/// * `vpc_ids`                - The referenced VPCs
+/// * `vpc_prefix_ids`         - The referenced VPC prefixes
+/// * `tenant_organization_id` - Optional TenantOrganizationId containing the tenant
+///   org the referenced objects must belong to.
+///
+pub async fn find_rule_net_prefixes(
+    txn: &mut PgConnection,
+    vpc_ids: &[VpcId],
+    vpc_prefix_ids: &[VpcPrefixId],
+    tenant_organization_id: Option<&TenantOrganizationId>,
+) -> Result<NetworkSecurityGroupRuleNetPrefixes, DatabaseError> {
+    let mut prefixes = NetworkSecurityGroupRuleNetPrefixes::default();
+    let tenant_organization_id = tenant_organization_id.map(|t| t.to_string());
+
+    if !vpc_ids.is_empty() {
+        // Segment prefixes which are part of a VPC prefix are covered by the VPC prefix.
+        let query = "SELECT v.id, p.prefix FROM vpcs v
+            LEFT JOIN (
+                SELECT vpc_id, prefix FROM network_vpc_prefixes
+                UNION ALL
+                SELECT ns.vpc_id, np.prefix FROM network_prefixes np
+                    INNER JOIN network_segments ns ON np.segment_id = ns.id
+                    WHERE np.vpc_prefix_id IS NULL AND ns.deleted IS NULL
+            ) p ON p.vpc_id = v.id
+            WHERE v.deleted IS NULL AND v.id = ANY($1)
+            AND ($2::varchar IS NULL OR v.organization_id = $2)";
+
+        let rows: Vec<(VpcId, Option<IpNetwork>)> = sqlx::query_as(query)
+            .bind(vpc_ids)
+            .bind(&tenant_organization_id)
+            .fetch_all(&mut *txn)
+            .await
+            .map_err(|e| DatabaseError::query(query, e))?;
+
+        for (vpc_id, prefix) in rows {
+            let vpc_prefixes = prefixes.vpcs.entry(vpc_id).or_default();
+            vpc_prefixes.extend(prefix);
+        }
+    }
+
+    if !vpc_prefix_ids.is_empty() {
+        let query = "SELECT p.id, p.prefix FROM network_vpc_prefixes p
+            INNER JOIN vpcs v ON v.id = p.vpc_id
+            WHERE v.deleted IS NULL AND p.id = ANY($1)
+            AND ($2::varchar IS NULL OR v.organization_id = $2)";
+
+        let rows: Vec<(VpcPrefixId, IpNetwork)> = sqlx::query_as(query)
+            .bind(vpc_prefix_ids)
+            .bind(&tenant_organization_id)
+            .fetch_all(&mut *txn)
+            .await
+            .map_err(|e| DatabaseError::query(query, e))?;
+
+        prefixes.vpc_prefixes.extend(rows);
+    }
+
+    Ok(prefixes)
+}
+
+/// Queries the DB for the NetworkSecurityGroups applied on each DPU,
+/// either directly by an instance or through the VPC of an interface.
+///
+/// * `txn` - A reference to an active DB transaction
+///
+pub async fn find_dpu_assignments(
+    txn: &mut PgConnection,
+) -> Result<Vec<(MachineId, NetworkSecurityGroupId)>, DatabaseError> {
+    // Instance NSGs take precedence over VPC NSGs, like they do when
+    // the network config of the DPU is built.
+    let query = "SELECT DISTINCT
+            mi.attached_dpu_machine_id,
+            COALESCE(i.network_security_group_id, v.network_security_group_id)
+        FROM instances i
+        JOIN jsonb_array_elements(i.network_config #>'{interfaces}') ifc ON TRUE
+        JOIN machine_interfaces mi ON mi.machine_id = i.machine_id
+        JOIN network_segments ns ON ns.id = (ifc->>'network_segment_id')::uuid
+        JOIN vpcs v ON v.id = ns.vpc_id
+        WHERE i.deleted IS NULL
+        AND mi.attached_dpu_machine_id IS NOT NULL
+        AND COALESCE(i.network_security_group_id, v.network_security_group_id) IS NOT NULL";
+
+    sqlx::query_as(query)
+        .fetch_all(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+/// Queries the DB for the tracked expanded size of the
+/// NetworkSecurityGroups applied on each DPU.  Entries of DPUs
+/// which don't exist anymore are skipped.
+///
+/// * `txn` - A reference to an active DB transaction
+///
+pub async fn find_dpu_usage(
+    txn: &mut PgConnection,
+) -> Result<Vec<NetworkSecurityGroupDpuUsage>, DatabaseError> {
+    let query = "SELECT u.* FROM network_security_group_dpu_usage u
+        INNER JOIN machines m ON m.id = u.dpu_machine_id";
+
+    sqlx::query_as(query)
+        .fetch_all(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+/// Replaces the tracked expanded size of the NetworkSecurityGroups
+/// applied on each DPU.
+///
+/// * `txn`   - A reference to an active DB transaction
+/// * `usage` - The current usage of all DPUs
+///
+pub async fn replace_dpu_usage(
+    txn: &mut PgConnection,
+    usage: &[NetworkSecurityGroupDpuUsage],
+) -> Result<(), DatabaseError> {
+    let query = "DELETE FROM network_security_group_dpu_usage";
+    sqlx::query(query)
+        .execute(&mut *txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+
+    // Divide the bind limit by the number of parameters we're inserting in each tuple
+    for chunk in usage.chunks(BIND_LIMIT / 4) {
+        let mut builder = sqlx::QueryBuilder::new(
+            "INSERT INTO network_security_group_dpu_usage
+                (dpu_machine_id, network_security_group_id, expanded_rule_count, error) ",
+        );
+        builder.push_values(chunk, |mut b, u| {
+            b.push_bind(u.dpu_machine_id)
+                .push_bind(&u.network_security_group_id)
+                .push_bind(i64::try_from(u.expanded_rule_count).unwrap_or(i64::MAX))
+                .push_bind(&u.error);
+        });
+
+        builder
+            .build()
+            .execute(&mut *txn)
+            .await
+            .map_err(|e| DatabaseError::query(builder.sql(), e))?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,145 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use carbide_uuid::machine::MachineId;
+use model::ssh_console_session::SshConsoleSession;
+use sqlx::postgres::PgRow;
+use sqlx::{FromRow, PgConnection, Row};
+
+use crate::db_read::DbReader;
+use crate::{DatabaseError, DatabaseResult};
+
+/// All columns except the recording itself
+const SESSION_COLUMNS: &str = "session_id, machine_id, name, started, user_name,
+    certificate_key_id, peer_addr, duration_secs, input_events, output_events";
+
+#[derive(Debug, Clone)]
+pub struct DbSshConsoleSession(pub SshConsoleSession);
+
+impl<'r> FromRow<'r, PgRow> for DbSshConsoleSession {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let input_events: i32 = row.try_get("input_events")?;
+        let output_events: i32 = row.try_get("output_events")?;
+
+        Ok(DbSshConsoleSession(SshConsoleSession {
+            session_id: row.try_get("session_id")?,
+            machine_id: row.try_get("machine_id")?,
+            name: row.try_get("name")?,
+            started: row.try_get("started")?,
+            user: row.try_get("user_name")?,
+            certificate_key_id: row.try_get("certificate_key_id")?,
+            peer_addr: row.try_get("peer_addr")?,
+            duration_secs: row.try_get("duration_secs")?,
+            input_events: input_events.max(0) as u32,
+            output_events: output_events.max(0) as u32,
+        }))
+    }
+}
+
+/// Stores the recording of a session, replacing any earlier recording of the same session
+pub async fn save(
+    txn: &mut PgConnection,
+    session: &SshConsoleSession,
+    recording: &str,
+) -> DatabaseResult<()> {
+    let query = "INSERT INTO ssh_console_sessions
+            (session_id, machine_id, name, started, user_name, certificate_key_id, peer_addr,
+             duration_secs, input_events, output_events, recording)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+        ON CONFLICT (session_id) DO UPDATE SET
+            duration_secs = EXCLUDED.duration_secs,
+            input_events = EXCLUDED.input_events,
+            output_events = EXCLUDED.output_events,
+            recording = EXCLUDED.recording";
+    sqlx::query(query)
+        .bind(session.session_id)
+        .bind(session.machine_id)
+        .bind(&session.name)
+        .bind(session.started)
+        .bind(&session.user)
+        .bind(&session.certificate_key_id)
+        .bind(&session.peer_addr)
+        .bind(session.duration_secs)
+        .bind(i32::try_from(session.input_events).unwrap_or(i32::MAX))
+        .bind(i32::try_from(session.output_events).unwrap_or(i32::MAX))
+        .bind(recording)
+        .execute(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+    Ok(())
+}
+
+/// Deletes all but the newest `keep` sessions of a machine, returning how many were deleted
+pub async fn prune(
+    txn: &mut PgConnection,
+    machine_id: &MachineId,
+    keep: usize,
+) -> DatabaseResult<u64> {
+    let query = "DELETE FROM ssh_console_sessions WHERE session_id IN (
+            SELECT session_id FROM ssh_console_sessions
+            WHERE machine_id = $1
+            ORDER BY started DESC
+            OFFSET $2
+        )";
+    sqlx::query(query)
+        .bind(machine_id)
+        .bind(i64::try_from(keep).unwrap_or(i64::MAX))
+        .execute(txn)
+        .await
+        .map(|result| result.rows_affected())
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+/// Returns the sessions of a machine, oldest first
+pub async fn find(
+    txn: impl DbReader<'_>,
+    machine_id: &MachineId,
+) -> DatabaseResult<Vec<SshConsoleSession>> {
+    let query = format!(
+        "SELECT {SESSION_COLUMNS} FROM ssh_console_sessions WHERE machine_id = $1 ORDER BY started"
+    );
+    sqlx::query_as::<_, DbSshConsoleSession>(&query)
+        .bind(machine_id)
+        .fetch_all(txn)
+        .await
+        .map(|sessions| sessions.into_iter().map(|session| session.0).collect())
+        .map_err(|e| DatabaseError::query(&query, e))
+}
+
+/// Returns a session of a machine and its recording, by session ID or name
+pub async fn find_recording(
+    txn: impl DbReader<'_>,
+    machine_id: &MachineId,
+    session: &str,
+) -> DatabaseResult<Option<(SshConsoleSession, String)>> {
+    let query = "SELECT * FROM ssh_console_sessions
+        WHERE machine_id = $1 AND (session_id::text = $2 OR name = $2)";
+    sqlx::query(query)
+        .bind(machine_id)
+        .bind(session)
+        .fetch_optional(txn)
+        .await
+        .and_then(|row| {
+            row.map(|row| {
+                let session = DbSshConsoleSession::from_row(&row)?.0;
+                let recording: String = row.try_get("recording")?;
+                Ok((session, recording))
+            })
+            .transpose()
+        })
+        .map_err(|e| DatabaseError::query(query, e))
+}
@@ -31,16 +31,20 @@ pub async fn create_and_persist(
     organization_id: String,
     metadata: Metadata,
     routing_profile_type: Option<RoutingProfileType>,
+    metadata_token_required: bool,
+    metadata_token_hop_limit: Option<u32>,
     txn: &mut PgConnection,
 ) -> Result<Tenant, DatabaseError> {
     let version = ConfigVersion::initial();
-    let query = "INSERT INTO tenants (organization_id, organization_name, version, routing_profile_type) VALUES ($1, $2, $3, $4) RETURNING *";
+    let query = "INSERT INTO tenants (organization_id, organization_name, version, routing_profile_type, metadata_token_required, metadata_token_hop_limit) VALUES ($1, $2, $3, $4, $5, $6) RETURNING *";
 
     sqlx::query_as(query)
         .bind(organization_id)
         .bind(metadata.name)
         .bind(version)
         .bind(routing_profile_type.map(|p| p.to_string()))
+        .bind(metadata_token_required)
+        .bind(metadata_token_hop_limit.map(|hop_limit| hop_limit as i32))
         .fetch_one(txn)
         .await
         .map_err(|e| DatabaseError::query(query, e))
@@ -73,6 +77,8 @@ pub async fn update(
     metadata: Metadata,
     expected_version: ConfigVersion,
     routing_profile_type: Option<RoutingProfileType>,
+    metadata_token_required: bool,
+    metadata_token_hop_limit: Option<u32>,
     txn: &mut PgConnection,
 ) -> DatabaseResult<Tenant> {
     let next_version = expected_version.increment();
@@ -81,7 +87,9 @@ pub async fn update(
             SET
                 version=$1,
                 organization_name=$2,
-                routing_profile_type=$3
+                routing_profile_type=$3,
+                metadata_token_required=$6,
+                metadata_token_hop_limit=$7
             WHERE
                 organization_id=$4
                 AND
@@ -94,6 +102,8 @@ pub async fn update(
         .bind(routing_profile_type.map(|p| p.to_string()))
         .bind(organization_id)
         .bind(expected_version)
+        .bind(metadata_token_required)
+        .bind(metadata_token_hop_limit.map(|hop_limit| hop_limit as i32))
         .fetch_one(txn)
         .await
         .map_err(|err| match err {
@@ -0,0 +1,79 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use chrono::{DateTime, Utc};
+use lazy_static::lazy_static;
+use mac_address::MacAddress;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+lazy_static! {
+    pub static ref BMC_CREDENTIAL_ROTATION_HEALTH_PROBE_ID: health_report::HealthProbeId =
+        "BmcCredentialRotationFailed".parse().unwrap();
+}
+
+/// The name of the Health Override which is placed on Machines whose BMC credentials
+/// could not be rotated
+pub const BMC_CREDENTIAL_ROTATION_HEALTH_REPORT_SOURCE: &str = "bmc-credential-rotation";
+
+/// Tracks when the root credentials of a BMC were last rotated
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, FromRow)]
+pub struct BmcCredentialRotation {
+    pub bmc_mac_address: MacAddress,
+    /// When the credentials were last rotated successfully.
+    /// `None` if they were never rotated.
+    pub rotated_at: Option<DateTime<Utc>>,
+    /// When the last rotation was attempted, independent of its outcome
+    pub last_attempt_at: DateTime<Utc>,
+    /// The number of failed rotation attempts since the last successful rotation
+    pub consecutive_failures: i32,
+    /// The error of the last rotation attempt, if it failed
+    pub last_error: Option<String>,
+}
+
+impl BmcCredentialRotation {
+    /// Whether the last rotation attempt failed
+    pub fn is_failed(&self) -> bool {
+        self.consecutive_failures > 0
+    }
+
+    /// The age of the current credentials, if they were ever rotated
+    pub fn credential_age(&self, now: DateTime<Utc>) -> Option<chrono::Duration> {
+        self.rotated_at.map(|rotated_at| now - rotated_at)
+    }
+}
+
+/// Creates the Health override report for a Machine whose BMC credentials could not be rotated
+pub fn create_rotation_failed_health_report(
+    bmc_mac_address: MacAddress,
+    error: &str,
+) -> health_report::HealthReport {
+    health_report::HealthReport {
+        source: BMC_CREDENTIAL_ROTATION_HEALTH_REPORT_SOURCE.to_string(),
+        observed_at: Some(Utc::now()),
+        successes: vec![],
+        alerts: vec![health_report::HealthProbeAlert {
+            id: BMC_CREDENTIAL_ROTATION_HEALTH_PROBE_ID.clone(),
+            target: Some(bmc_mac_address.to_string()),
+            in_alert_since: Some(Utc::now()),
+            message: format!("Rotating the BMC root credentials failed: {error}"),
+            tenant_message: None,
+            // The Machine is still fully usable with the old credentials
+            classifications: vec![],
+        }],
+    }
+}
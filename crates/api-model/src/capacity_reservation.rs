@@ -0,0 +1,207 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::rpc::errors::RpcDataConversionError;
+use carbide_uuid::capacity_reservation::CapacityReservationId;
+use carbide_uuid::instance_type::InstanceTypeId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::tenant::TenantOrganizationId;
+
+/// A number of Machines of an InstanceType which are set aside for a tenant
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CapacityReservation {
+    pub id: CapacityReservationId,
+    pub tenant_organization_id: TenantOrganizationId,
+    pub instance_type_id: InstanceTypeId,
+    pub count: u32,
+    /// The reservation ends by itself once this time has passed
+    pub expires_at: Option<DateTime<Utc>>,
+    pub created: DateTime<Utc>,
+    pub released: Option<DateTime<Utc>>,
+}
+
+impl CapacityReservation {
+    /// Whether the reservation is neither released nor expired at the given time
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.released.is_none() && self.expires_at.is_none_or(|expires_at| expires_at > now)
+    }
+
+    pub fn into_rpc(
+        self,
+        now: DateTime<Utc>,
+        in_use: u32,
+        instance_type_available: u32,
+    ) -> rpc::forge::CapacityReservation {
+        rpc::forge::CapacityReservation {
+            status: Some(rpc::forge::CapacityReservationStatus {
+                active: self.is_active(now),
+                in_use,
+                instance_type_available,
+            }),
+            id: Some(self.id),
+            tenant_organization_id: self.tenant_organization_id.to_string(),
+            instance_type_id: self.instance_type_id.to_string(),
+            count: self.count,
+            expires_at: self.expires_at.map(Into::into),
+            created: Some(self.created.into()),
+            released: self.released.map(Into::into),
+        }
+    }
+}
+
+/// Splits the instances a tenant has on an InstanceType over the tenant's reservations for it.
+/// The oldest reservations are filled first.
+///
+/// `reservations` must all belong to the same tenant and InstanceType. The result holds the
+/// number of instances counted against each reservation, in the order of `reservations`.
+pub fn attribute_usage(reservations: &[&CapacityReservation], in_use: u32) -> Vec<u32> {
+    let mut order: Vec<usize> = (0..reservations.len()).collect();
+    order.sort_by_key(|&i| reservations[i].created);
+
+    let mut remaining = in_use;
+    let mut usage = vec![0; reservations.len()];
+    for i in order {
+        let used = remaining.min(reservations[i].count);
+        usage[i] = used;
+        remaining -= used;
+    }
+    usage
+}
+
+/// A capacity reservation which has not yet been stored
+#[derive(Clone, Debug)]
+pub struct NewCapacityReservation {
+    pub id: CapacityReservationId,
+    pub tenant_organization_id: TenantOrganizationId,
+    pub instance_type_id: InstanceTypeId,
+    pub count: u32,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl TryFrom<rpc::forge::CapacityReservationCreationRequest> for NewCapacityReservation {
+    type Error = RpcDataConversionError;
+
+    fn try_from(
+        request: rpc::forge::CapacityReservationCreationRequest,
+    ) -> Result<Self, Self::Error> {
+        let tenant_organization_id = request
+            .tenant_organization_id
+            .parse::<TenantOrganizationId>()
+            .map_err(|_| {
+                RpcDataConversionError::InvalidTenantOrg(request.tenant_organization_id)
+            })?;
+
+        let instance_type_id = request
+            .instance_type_id
+            .parse::<InstanceTypeId>()
+            .map_err(|_| RpcDataConversionError::InvalidInstanceTypeId(request.instance_type_id))?;
+
+        if request.count == 0 {
+            return Err(RpcDataConversionError::InvalidValue(
+                "count".to_string(),
+                "0".to_string(),
+            ));
+        }
+
+        let expires_at = request
+            .expires_at
+            .map(|expires_at| {
+                DateTime::<Utc>::try_from(expires_at)
+                    .map_err(|_| RpcDataConversionError::InvalidTimestamp(expires_at.to_string()))
+            })
+            .transpose()?;
+
+        Ok(NewCapacityReservation {
+            id: request.id.unwrap_or_else(|| uuid::Uuid::new_v4().into()),
+            tenant_organization_id,
+            instance_type_id,
+            count: request.count,
+            expires_at,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use super::*;
+
+    fn reservation(count: u32, created: DateTime<Utc>) -> CapacityReservation {
+        CapacityReservation {
+            id: uuid::Uuid::new_v4().into(),
+            tenant_organization_id: "tenant-a".parse().unwrap(),
+            instance_type_id: "gpu-large".parse().unwrap(),
+            count,
+            expires_at: None,
+            created,
+            released: None,
+        }
+    }
+
+    #[test]
+    fn test_is_active() {
+        let now = Utc::now();
+        let mut r = reservation(1, now);
+        assert!(r.is_active(now));
+
+        r.expires_at = Some(now + Duration::hours(1));
+        assert!(r.is_active(now));
+        assert!(!r.is_active(now + Duration::hours(2)));
+
+        r.expires_at = None;
+        r.released = Some(now);
+        assert!(!r.is_active(now));
+    }
+
+    #[test]
+    fn test_attribute_usage_fills_oldest_first() {
+        let now = Utc::now();
+        let newer = reservation(3, now);
+        let older = reservation(2, now - Duration::days(1));
+
+        assert_eq!(attribute_usage(&[&newer, &older], 0), vec![0, 0]);
+        assert_eq!(attribute_usage(&[&newer, &older], 1), vec![0, 1]);
+        assert_eq!(attribute_usage(&[&newer, &older], 4), vec![2, 2]);
+        // Instances beyond the reserved count are not attributed to any reservation
+        assert_eq!(attribute_usage(&[&newer, &older], 10), vec![3, 2]);
+    }
+
+    #[test]
+    fn test_new_reservation_validation() {
+        let request = rpc::forge::CapacityReservationCreationRequest {
+            id: None,
+            tenant_organization_id: "tenant-a".to_string(),
+            instance_type_id: "gpu-large".to_string(),
+            count: 4,
+            expires_at: None,
+        };
+        let reservation = NewCapacityReservation::try_from(request.clone()).unwrap();
+        assert_eq!(reservation.count, 4);
+        assert_eq!(reservation.tenant_organization_id.as_str(), "tenant-a");
+
+        let mut zero = request.clone();
+        zero.count = 0;
+        assert!(NewCapacityReservation::try_from(zero).is_err());
+
+        let mut bad_tenant = request;
+        bad_tenant.tenant_organization_id = "tenant a".to_string();
+        assert!(NewCapacityReservation::try_from(bad_tenant).is_err());
+    }
+}
@@ -0,0 +1,196 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::fmt;
+use std::str::FromStr;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use carbide_uuid::domain::DomainId;
+use chrono::{DateTime, Utc};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+/// A DNSSEC key of a domain, in the shape the PowerDNS remote backend expects
+/// as the result of `getDomainKeys`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DomainKey {
+    pub id: u32,
+    pub flags: u32,
+    pub active: bool,
+    pub published: bool,
+    /// The private key in BIND private-key format
+    pub content: String,
+}
+
+impl From<DomainKey> for rpc::protos::dns::DomainKey {
+    fn from(key: DomainKey) -> Self {
+        rpc::protos::dns::DomainKey {
+            id: key.id,
+            flags: key.flags,
+            active: key.active,
+            published: key.published,
+            content: key.content,
+        }
+    }
+}
+
+/// Whether a DNSSEC key signs the DNSKEY set (KSK) or the rest of the zone (ZSK)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DomainKeyKind {
+    KeySigning,
+    ZoneSigning,
+}
+
+impl DomainKeyKind {
+    /// DNSKEY flags of the kind: the zone key bit, plus the SEP bit for KSKs
+    pub fn flags(self) -> u32 {
+        match self {
+            DomainKeyKind::KeySigning => 257,
+            DomainKeyKind::ZoneSigning => 256,
+        }
+    }
+
+    pub fn from_flags(flags: u32) -> Self {
+        if flags & 1 == 1 {
+            DomainKeyKind::KeySigning
+        } else {
+            DomainKeyKind::ZoneSigning
+        }
+    }
+}
+
+impl fmt::Display for DomainKeyKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DomainKeyKind::KeySigning => write!(f, "ksk"),
+            DomainKeyKind::ZoneSigning => write!(f, "zsk"),
+        }
+    }
+}
+
+impl FromStr for DomainKeyKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "ksk" => Ok(DomainKeyKind::KeySigning),
+            "zsk" => Ok(DomainKeyKind::ZoneSigning),
+            _ => Err(format!("invalid domain key kind: {s}")),
+        }
+    }
+}
+
+impl From<rpc::protos::dns::DomainKeyKind> for DomainKeyKind {
+    fn from(kind: rpc::protos::dns::DomainKeyKind) -> Self {
+        match kind {
+            rpc::protos::dns::DomainKeyKind::KeySigning => DomainKeyKind::KeySigning,
+            rpc::protos::dns::DomainKeyKind::ZoneSigning => DomainKeyKind::ZoneSigning,
+        }
+    }
+}
+
+impl From<DomainKeyKind> for rpc::protos::dns::DomainKeyKind {
+    fn from(kind: DomainKeyKind) -> Self {
+        match kind {
+            DomainKeyKind::KeySigning => rpc::protos::dns::DomainKeyKind::KeySigning,
+            DomainKeyKind::ZoneSigning => rpc::protos::dns::DomainKeyKind::ZoneSigning,
+        }
+    }
+}
+
+/// A DNSSEC key of a domain without its private key, which is kept in the
+/// credential provider.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DomainKeyInfo {
+    pub id: u32,
+    pub domain_id: DomainId,
+    pub flags: u32,
+    pub active: bool,
+    pub published: bool,
+    pub created: DateTime<Utc>,
+}
+
+impl DomainKeyInfo {
+    pub fn kind(&self) -> DomainKeyKind {
+        DomainKeyKind::from_flags(self.flags)
+    }
+
+    pub fn with_content(self, content: String) -> DomainKey {
+        DomainKey {
+            id: self.id,
+            flags: self.flags,
+            active: self.active,
+            published: self.published,
+            content,
+        }
+    }
+}
+
+impl From<DomainKeyInfo> for rpc::protos::dns::DomainKeyInfo {
+    fn from(key: DomainKeyInfo) -> Self {
+        rpc::protos::dns::DomainKeyInfo {
+            id: key.id,
+            domain_id: Some(key.domain_id),
+            kind: rpc::protos::dns::DomainKeyKind::from(key.kind()) as i32,
+            flags: key.flags,
+            active: key.active,
+            published: key.published,
+            created: Some(key.created.into()),
+        }
+    }
+}
+
+/// Generates a new Ed25519 (DNSSEC algorithm 15) private key in the BIND
+/// private-key format which PowerDNS imports.
+pub fn generate_ed25519_private_key() -> String {
+    // The thread RNG is a CSPRNG seeded from the operating system
+    let mut seed = [0u8; 32];
+    rand::rng().fill_bytes(&mut seed);
+    format!(
+        "Private-key-format: v1.2\nAlgorithm: 15 (ED25519)\nPrivateKey: {}\n",
+        BASE64_STANDARD.encode(seed)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_domain_key_kind_flags() {
+        assert_eq!(DomainKeyKind::KeySigning.flags(), 257);
+        assert_eq!(DomainKeyKind::ZoneSigning.flags(), 256);
+        assert_eq!(DomainKeyKind::from_flags(257), DomainKeyKind::KeySigning);
+        assert_eq!(DomainKeyKind::from_flags(256), DomainKeyKind::ZoneSigning);
+        assert_eq!(
+            "KSK".parse::<DomainKeyKind>(),
+            Ok(DomainKeyKind::KeySigning)
+        );
+        assert!("csk".parse::<DomainKeyKind>().is_err());
+    }
+
+    #[test]
+    fn test_generate_ed25519_private_key() {
+        let key = generate_ed25519_private_key();
+        let mut lines = key.lines();
+        assert_eq!(lines.next(), Some("Private-key-format: v1.2"));
+        assert_eq!(lines.next(), Some("Algorithm: 15 (ED25519)"));
+        let private_key = lines.next().unwrap().strip_prefix("PrivateKey: ").unwrap();
+        assert_eq!(BASE64_STANDARD.decode(private_key).unwrap().len(), 32);
+        assert_ne!(key, generate_ed25519_private_key());
+    }
+}
@@ -21,14 +21,18 @@ use dns_record::SoaRecord;
 use serde::{Deserialize, Serialize};
 
 pub mod domain_info;
+pub mod domain_key;
 pub mod metadata;
 pub mod resource_record;
 pub mod snapshot;
+pub mod tenant_record;
 
 pub use domain_info::DomainInfo;
+pub use domain_key::{DomainKey, DomainKeyInfo, DomainKeyKind};
 pub use metadata::DomainMetadata;
 pub use resource_record::ResourceRecord;
 pub use snapshot::SoaSnapshot;
+pub use tenant_record::{NewTenantDnsRecord, TenantDnsRecord};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Domain {
@@ -0,0 +1,203 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use ::rpc::errors::RpcDataConversionError;
+use carbide_uuid::domain::DomainId;
+use carbide_uuid::tenant_dns_record::TenantDnsRecordId;
+use carbide_uuid::vpc::VpcId;
+use chrono::{DateTime, Utc};
+use dns_record::{DnsResourceRecordReply, DnsResourceRecordType};
+use serde::{Deserialize, Serialize};
+
+/// The TTL of tenant records which are created without an explicit TTL
+pub const DEFAULT_TENANT_RECORD_TTL: u32 = 300;
+
+/// A DNS record defined by a tenant inside the domain of one of its VPCs
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TenantDnsRecord {
+    pub id: TenantDnsRecordId,
+    pub vpc_id: VpcId,
+    pub domain_id: DomainId,
+    /// Fully qualified name of the record, including the trailing dot
+    pub qname: String,
+    pub qtype: DnsResourceRecordType,
+    pub ttl: u32,
+    /// Record data as entered by the tenant
+    pub content: String,
+    pub created: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+}
+
+impl TenantDnsRecord {
+    /// Returns the record data in the presentation format PowerDNS expects.
+    ///
+    /// TXT data is stored unquoted and gets quoted here.
+    pub fn pdns_content(&self) -> String {
+        match self.qtype {
+            DnsResourceRecordType::TXT => format!(
+                "\"{}\"",
+                self.content.replace('\\', "\\\\").replace('"', "\\\"")
+            ),
+            _ => self.content.clone(),
+        }
+    }
+}
+
+impl From<TenantDnsRecord> for DnsResourceRecordReply {
+    fn from(record: TenantDnsRecord) -> Self {
+        Self {
+            qtype: record.qtype.to_string(),
+            content: record.pdns_content(),
+            qname: record.qname,
+            ttl: record.ttl,
+            domain_id: Some(record.domain_id.to_string()),
+            scope_mask: None,
+            auth: None,
+        }
+    }
+}
+
+impl From<TenantDnsRecord> for rpc::protos::dns::TenantDnsRecord {
+    fn from(record: TenantDnsRecord) -> Self {
+        rpc::protos::dns::TenantDnsRecord {
+            id: Some(record.id),
+            vpc_id: Some(record.vpc_id),
+            domain_id: Some(record.domain_id),
+            qname: record.qname,
+            qtype: record.qtype.to_string(),
+            ttl: record.ttl,
+            content: record.content,
+            created: Some(record.created.into()),
+            updated: Some(record.updated.into()),
+        }
+    }
+}
+
+/// A tenant-defined DNS record which has not yet been stored
+#[derive(Clone, Debug)]
+pub struct NewTenantDnsRecord {
+    pub id: TenantDnsRecordId,
+    pub vpc_id: VpcId,
+    /// Fully qualified, lowercase name of the record, including the trailing dot
+    pub qname: String,
+    pub qtype: DnsResourceRecordType,
+    pub ttl: u32,
+    pub content: String,
+}
+
+impl TryFrom<rpc::protos::dns::TenantDnsRecordCreationRequest> for NewTenantDnsRecord {
+    type Error = RpcDataConversionError;
+
+    fn try_from(
+        request: rpc::protos::dns::TenantDnsRecordCreationRequest,
+    ) -> Result<Self, Self::Error> {
+        let vpc_id = request
+            .vpc_id
+            .ok_or(RpcDataConversionError::MissingArgument("vpc_id"))?;
+
+        let qtype =
+            DnsResourceRecordType::try_from(request.qtype.to_uppercase()).map_err(|_| {
+                RpcDataConversionError::InvalidValue("qtype".to_string(), request.qtype)
+            })?;
+        if !is_tenant_record_type(qtype) {
+            return Err(RpcDataConversionError::InvalidArgument(format!(
+                "record type {qtype} can not be created by tenants"
+            )));
+        }
+
+        if request.qname.trim().is_empty() {
+            return Err(RpcDataConversionError::MissingArgument("qname"));
+        }
+        let qname = format!(
+            "{}.",
+            request.qname.trim().trim_end_matches('.').to_lowercase()
+        );
+
+        Ok(NewTenantDnsRecord {
+            id: request.id.unwrap_or_else(|| uuid::Uuid::new_v4().into()),
+            vpc_id,
+            qname,
+            qtype,
+            ttl: request.ttl.unwrap_or(DEFAULT_TENANT_RECORD_TTL),
+            content: request.content.trim().to_string(),
+        })
+    }
+}
+
+/// Returns whether tenants may create records of the given type
+pub fn is_tenant_record_type(qtype: DnsResourceRecordType) -> bool {
+    matches!(
+        qtype,
+        DnsResourceRecordType::A
+            | DnsResourceRecordType::AAAA
+            | DnsResourceRecordType::CNAME
+            | DnsResourceRecordType::TXT
+            | DnsResourceRecordType::SRV
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn creation_request(
+        qname: &str,
+        qtype: &str,
+    ) -> rpc::protos::dns::TenantDnsRecordCreationRequest {
+        rpc::protos::dns::TenantDnsRecordCreationRequest {
+            id: None,
+            vpc_id: Some(uuid::Uuid::new_v4().into()),
+            qname: qname.to_string(),
+            qtype: qtype.to_string(),
+            ttl: None,
+            content: " 192.0.2.10 ".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_new_tenant_record_is_normalized() {
+        let record =
+            NewTenantDnsRecord::try_from(creation_request("API.Cluster.example.com", "a")).unwrap();
+        assert_eq!(record.qname, "api.cluster.example.com.");
+        assert_eq!(record.qtype, DnsResourceRecordType::A);
+        assert_eq!(record.ttl, DEFAULT_TENANT_RECORD_TTL);
+        assert_eq!(record.content, "192.0.2.10");
+    }
+
+    #[test]
+    fn test_new_tenant_record_rejects_reserved_types() {
+        assert!(NewTenantDnsRecord::try_from(creation_request("example.com.", "SOA")).is_err());
+        assert!(NewTenantDnsRecord::try_from(creation_request("example.com.", "NS")).is_err());
+        assert!(NewTenantDnsRecord::try_from(creation_request("example.com.", "BOGUS")).is_err());
+        assert!(NewTenantDnsRecord::try_from(creation_request("", "A")).is_err());
+    }
+
+    #[test]
+    fn test_txt_content_is_quoted_for_pdns() {
+        let record = TenantDnsRecord {
+            id: uuid::Uuid::new_v4().into(),
+            vpc_id: uuid::Uuid::new_v4().into(),
+            domain_id: uuid::Uuid::new_v4().into(),
+            qname: "svc.example.com.".to_string(),
+            qtype: DnsResourceRecordType::TXT,
+            ttl: 300,
+            content: r#"v=1 "quoted""#.to_string(),
+            created: Utc::now(),
+            updated: Utc::now(),
+        };
+        assert_eq!(record.pdns_content(), r#""v=1 \"quoted\"""#);
+    }
+}
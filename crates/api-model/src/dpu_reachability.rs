@@ -0,0 +1,668 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! The DPU-to-DPU reachability matrix
+//!
+//! The network monitor of every DPU probes its peer DPUs on the underlay, and optionally on the
+//! overlay of the VPCs it shares with them, and reports the results of each check cycle. The
+//! latest report of every DPU makes up the matrix. Looking at both directions of each path
+//! helps to tell a broken DPU apart from a broken link or switch in between.
+
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Display, Formatter};
+use std::net::IpAddr;
+
+use carbide_uuid::machine::MachineId;
+use chrono::{DateTime, Utc};
+use rpc::errors::RpcDataConversionError;
+use serde::{Deserialize, Serialize};
+
+/// The group of DPUs whose switch is unknown
+pub const UNKNOWN_GROUP: &str = "unknown";
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProbeProtocol {
+    Icmp,
+    Tcp,
+    Udp,
+}
+
+impl Display for ProbeProtocol {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            ProbeProtocol::Icmp => "icmp",
+            ProbeProtocol::Tcp => "tcp",
+            ProbeProtocol::Udp => "udp",
+        };
+        f.write_str(s)
+    }
+}
+
+impl From<rpc::forge::DpuNetworkProbeProtocol> for ProbeProtocol {
+    fn from(protocol: rpc::forge::DpuNetworkProbeProtocol) -> Self {
+        match protocol {
+            rpc::forge::DpuNetworkProbeProtocol::Icmp => ProbeProtocol::Icmp,
+            rpc::forge::DpuNetworkProbeProtocol::Tcp => ProbeProtocol::Tcp,
+            rpc::forge::DpuNetworkProbeProtocol::Udp => ProbeProtocol::Udp,
+        }
+    }
+}
+
+impl From<ProbeProtocol> for rpc::forge::DpuNetworkProbeProtocol {
+    fn from(protocol: ProbeProtocol) -> Self {
+        match protocol {
+            ProbeProtocol::Icmp => rpc::forge::DpuNetworkProbeProtocol::Icmp,
+            ProbeProtocol::Tcp => rpc::forge::DpuNetworkProbeProtocol::Tcp,
+            ProbeProtocol::Udp => rpc::forge::DpuNetworkProbeProtocol::Udp,
+        }
+    }
+}
+
+/// The kind of probe a result belongs to. Results of both directions of a path are only
+/// compared if they are of the same kind.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct ProbeKind {
+    pub protocol: ProbeProtocol,
+    pub port: Option<u16>,
+    /// The VPC whose overlay the probe was sent on, the underlay if not set
+    pub vpc_id: Option<String>,
+}
+
+impl Display for ProbeKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.protocol)?;
+        if let Some(port) = self.port {
+            write!(f, "/{port}")?;
+        }
+        match &self.vpc_id {
+            Some(vpc_id) => write!(f, " (VPC {vpc_id})"),
+            None => write!(f, " (underlay)"),
+        }
+    }
+}
+
+/// Whether probes on a path are answered
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PathHealth {
+    Up,
+    /// Some of the probes were lost
+    Degraded,
+    /// All probes were lost
+    Down,
+}
+
+impl Display for PathHealth {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            PathHealth::Up => "up",
+            PathHealth::Degraded => "degraded",
+            PathHealth::Down => "down",
+        };
+        f.write_str(s)
+    }
+}
+
+/// The results of one kind of probe sent from a DPU to a peer DPU
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DpuReachabilityResult {
+    pub peer_dpu_id: MachineId,
+    pub probe: ProbeKind,
+    pub target_ip: IpAddr,
+    pub sent: u32,
+    pub received: u32,
+    pub latency_p50_ms: Option<f64>,
+    pub latency_p90_ms: Option<f64>,
+    pub latency_p99_ms: Option<f64>,
+    pub jitter_ms: Option<f64>,
+}
+
+impl DpuReachabilityResult {
+    /// The share of lost probes, between 0 and 1
+    pub fn loss(&self) -> f64 {
+        if self.sent == 0 {
+            return 0.0;
+        }
+        f64::from(self.sent - self.received) / f64::from(self.sent)
+    }
+
+    pub fn health(&self) -> PathHealth {
+        if self.received == 0 {
+            PathHealth::Down
+        } else if self.received < self.sent {
+            PathHealth::Degraded
+        } else {
+            PathHealth::Up
+        }
+    }
+}
+
+/// The results of a check cycle of the network monitor of a DPU
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DpuReachabilityReport {
+    pub dpu_id: MachineId,
+    pub observed_at: DateTime<Utc>,
+    pub results: Vec<DpuReachabilityResult>,
+}
+
+impl DpuReachabilityReport {
+    /// Only keep the results of probes on the overlay of the given VPC
+    pub fn retain_vpc(&mut self, vpc_id: &str) {
+        self.results
+            .retain(|result| result.probe.vpc_id.as_deref() == Some(vpc_id));
+    }
+}
+
+impl TryFrom<rpc::forge::DpuReachabilityResult> for DpuReachabilityResult {
+    type Error = RpcDataConversionError;
+
+    fn try_from(result: rpc::forge::DpuReachabilityResult) -> Result<Self, Self::Error> {
+        let peer_dpu_id = result
+            .peer_dpu_id
+            .parse()
+            .map_err(|_| RpcDataConversionError::InvalidMachineId(result.peer_dpu_id.clone()))?;
+        let protocol = rpc::forge::DpuNetworkProbeProtocol::try_from(result.protocol)
+            .map_err(|_| {
+                RpcDataConversionError::InvalidValue(
+                    "protocol".to_string(),
+                    result.protocol.to_string(),
+                )
+            })?
+            .into();
+        let port = result
+            .port
+            .map(|port| {
+                u16::try_from(port).map_err(|_| {
+                    RpcDataConversionError::InvalidValue("port".to_string(), port.to_string())
+                })
+            })
+            .transpose()?;
+        if port.is_none() && protocol != ProbeProtocol::Icmp {
+            return Err(RpcDataConversionError::InvalidArgument(format!(
+                "{protocol} probe of peer {peer_dpu_id} is missing the port"
+            )));
+        }
+        let target_ip = result
+            .target_ip
+            .parse()
+            .map_err(|_| RpcDataConversionError::InvalidIpAddress(result.target_ip.clone()))?;
+        if result.received > result.sent {
+            return Err(RpcDataConversionError::InvalidArgument(format!(
+                "{} probes of peer {peer_dpu_id} received, but only {} sent",
+                result.received, result.sent
+            )));
+        }
+
+        Ok(DpuReachabilityResult {
+            peer_dpu_id,
+            probe: ProbeKind {
+                protocol,
+                port,
+                vpc_id: result.vpc_id,
+            },
+            target_ip,
+            sent: result.sent,
+            received: result.received,
+            latency_p50_ms: result.latency_p50_ms,
+            latency_p90_ms: result.latency_p90_ms,
+            latency_p99_ms: result.latency_p99_ms,
+            jitter_ms: result.jitter_ms,
+        })
+    }
+}
+
+impl From<DpuReachabilityResult> for rpc::forge::DpuReachabilityResult {
+    fn from(result: DpuReachabilityResult) -> Self {
+        rpc::forge::DpuReachabilityResult {
+            peer_dpu_id: result.peer_dpu_id.to_string(),
+            protocol: rpc::forge::DpuNetworkProbeProtocol::from(result.probe.protocol) as i32,
+            port: result.probe.port.map(u32::from),
+            vpc_id: result.probe.vpc_id,
+            target_ip: result.target_ip.to_string(),
+            sent: result.sent,
+            received: result.received,
+            latency_p50_ms: result.latency_p50_ms,
+            latency_p90_ms: result.latency_p90_ms,
+            latency_p99_ms: result.latency_p99_ms,
+            jitter_ms: result.jitter_ms,
+        }
+    }
+}
+
+impl TryFrom<rpc::forge::DpuReachabilityReport> for DpuReachabilityReport {
+    type Error = RpcDataConversionError;
+
+    fn try_from(report: rpc::forge::DpuReachabilityReport) -> Result<Self, Self::Error> {
+        let dpu_id = report
+            .dpu_machine_id
+            .ok_or(RpcDataConversionError::MissingArgument("dpu_machine_id"))?;
+        let observed_at = report
+            .observed_at
+            .ok_or(RpcDataConversionError::MissingArgument("observed_at"))?;
+        let observed_at = DateTime::<Utc>::try_from(observed_at)
+            .map_err(|_| RpcDataConversionError::InvalidTimestamp(observed_at.to_string()))?;
+
+        Ok(DpuReachabilityReport {
+            dpu_id,
+            observed_at,
+            results: report
+                .results
+                .into_iter()
+                .map(DpuReachabilityResult::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<DpuReachabilityReport> for rpc::forge::DpuReachabilityReport {
+    fn from(report: DpuReachabilityReport) -> Self {
+        rpc::forge::DpuReachabilityReport {
+            dpu_machine_id: Some(report.dpu_id),
+            observed_at: Some(report.observed_at.into()),
+            results: report.results.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// A problem on a path between two DPUs
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ReachabilityFinding {
+    /// The path works better in one direction than in the other. This points to a problem with
+    /// the DPU or switch on one end, e.g. a filter or a broken transmit path, rather than the
+    /// fabric in between.
+    Asymmetric {
+        source: MachineId,
+        destination: MachineId,
+        probe: ProbeKind,
+        forward: PathHealth,
+        reverse: PathHealth,
+    },
+    /// Some, but not all probes were answered
+    PartialLoss {
+        source: MachineId,
+        destination: MachineId,
+        probe: ProbeKind,
+        loss: f64,
+    },
+    /// No probes were answered in either direction, or in the only direction which was measured
+    Unreachable {
+        source: MachineId,
+        destination: MachineId,
+        probe: ProbeKind,
+    },
+}
+
+impl ReachabilityFinding {
+    pub fn source(&self) -> &MachineId {
+        match self {
+            ReachabilityFinding::Asymmetric { source, .. }
+            | ReachabilityFinding::PartialLoss { source, .. }
+            | ReachabilityFinding::Unreachable { source, .. } => source,
+        }
+    }
+
+    pub fn destination(&self) -> &MachineId {
+        match self {
+            ReachabilityFinding::Asymmetric { destination, .. }
+            | ReachabilityFinding::PartialLoss { destination, .. }
+            | ReachabilityFinding::Unreachable { destination, .. } => destination,
+        }
+    }
+}
+
+impl Display for ReachabilityFinding {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReachabilityFinding::Asymmetric {
+                source,
+                destination,
+                probe,
+                forward,
+                reverse,
+            } => write!(
+                f,
+                "{probe}: {source} -> {destination} is {forward}, but {destination} -> {source} is {reverse}"
+            ),
+            ReachabilityFinding::PartialLoss {
+                source,
+                destination,
+                probe,
+                loss,
+            } => write!(
+                f,
+                "{probe}: {source} -> {destination} lost {:.0}% of probes",
+                loss * 100.0
+            ),
+            ReachabilityFinding::Unreachable {
+                source,
+                destination,
+                probe,
+            } => write!(
+                f,
+                "{probe}: {source} and {destination} can't reach each other"
+            ),
+        }
+    }
+}
+
+type PathKey<'a> = (&'a MachineId, &'a MachineId, &'a ProbeKind);
+
+/// Find asymmetric paths, paths with partial loss and unreachable peers in the latest reports
+/// of all DPUs. Every problem is only reported once, even if both ends of a path report it.
+pub fn analyze(reports: &[DpuReachabilityReport]) -> Vec<ReachabilityFinding> {
+    let paths: BTreeMap<PathKey, &DpuReachabilityResult> = reports
+        .iter()
+        .flat_map(|report| {
+            report
+                .results
+                .iter()
+                .map(move |result| ((&report.dpu_id, &result.peer_dpu_id, &result.probe), result))
+        })
+        .collect();
+
+    let mut findings = Vec::new();
+    for (&(source, destination, probe), result) in paths.iter() {
+        let forward = result.health();
+        let reverse = paths
+            .get(&(destination, source, probe))
+            .map(|result| result.health());
+
+        match (forward, reverse) {
+            (PathHealth::Up, _) => {}
+            // Reported from the end which is worse off
+            (forward, Some(reverse)) if forward > reverse => {
+                findings.push(ReachabilityFinding::Asymmetric {
+                    source: *source,
+                    destination: *destination,
+                    probe: probe.clone(),
+                    forward,
+                    reverse,
+                });
+            }
+            (_, Some(reverse)) if forward < reverse => {}
+            (PathHealth::Degraded, _) => findings.push(ReachabilityFinding::PartialLoss {
+                source: *source,
+                destination: *destination,
+                probe: probe.clone(),
+                loss: result.loss(),
+            }),
+            (PathHealth::Down, reverse) => {
+                // Reported once for both directions
+                if reverse.is_none() || source.cmp(destination) == Ordering::Less {
+                    findings.push(ReachabilityFinding::Unreachable {
+                        source: *source,
+                        destination: *destination,
+                        probe: probe.clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    findings
+}
+
+/// Health of the paths between two groups of DPUs, e.g. the DPUs connected to the same ToR
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct GroupReachability {
+    pub source_group: String,
+    pub destination_group: String,
+    pub paths: usize,
+    pub degraded: usize,
+    pub down: usize,
+}
+
+impl GroupReachability {
+    /// Whether all measured paths between the groups are down. This points to a problem with the
+    /// switch of one of the groups or the links between them, rather than single DPUs.
+    pub fn all_down(&self) -> bool {
+        self.paths > 0 && self.down == self.paths
+    }
+}
+
+/// Summarize the paths of all reports by the groups of the DPUs on both ends.
+/// DPUs without group are summarized as [`UNKNOWN_GROUP`].
+pub fn summarize_by_group(
+    reports: &[DpuReachabilityReport],
+    group_of: &HashMap<MachineId, String>,
+) -> Vec<GroupReachability> {
+    let group = |dpu_id: &MachineId| {
+        group_of
+            .get(dpu_id)
+            .cloned()
+            .unwrap_or_else(|| UNKNOWN_GROUP.to_string())
+    };
+
+    let mut summaries: BTreeMap<(String, String), GroupReachability> = BTreeMap::new();
+    for report in reports {
+        let source_group = group(&report.dpu_id);
+        for result in &report.results {
+            let destination_group = group(&result.peer_dpu_id);
+            let summary = summaries
+                .entry((source_group.clone(), destination_group.clone()))
+                .or_insert_with(|| GroupReachability {
+                    source_group: source_group.clone(),
+                    destination_group,
+                    ..Default::default()
+                });
+            summary.paths += 1;
+            match result.health() {
+                PathHealth::Up => {}
+                PathHealth::Degraded => summary.degraded += 1,
+                PathHealth::Down => summary.down += 1,
+            }
+        }
+    }
+
+    summaries.into_values().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DPU_A: &str = "fm100dsbiu5ckus880v8407u0mkcensa39cule26im5gnpvmuufckacguc0";
+    const DPU_B: &str = "fm100dsg4ekcb4sdi6hkqn0iojhj18okrr8vct64luh8957lfe8e69vme20";
+    const DPU_C: &str = "fm100dsvstfujf6mis0gpsoi81tadmllicv7rqo4s7gc16gi0t2478672vg";
+
+    fn dpu(id: &str) -> MachineId {
+        id.parse().unwrap()
+    }
+
+    fn icmp() -> ProbeKind {
+        ProbeKind {
+            protocol: ProbeProtocol::Icmp,
+            port: None,
+            vpc_id: None,
+        }
+    }
+
+    fn result(peer: &str, sent: u32, received: u32) -> DpuReachabilityResult {
+        DpuReachabilityResult {
+            peer_dpu_id: dpu(peer),
+            probe: icmp(),
+            target_ip: "172.20.0.1".parse().unwrap(),
+            sent,
+            received,
+            latency_p50_ms: (received > 0).then_some(0.2),
+            latency_p90_ms: (received > 0).then_some(0.3),
+            latency_p99_ms: (received > 0).then_some(0.5),
+            jitter_ms: (received > 0).then_some(0.05),
+        }
+    }
+
+    fn report(dpu_id: &str, results: Vec<DpuReachabilityResult>) -> DpuReachabilityReport {
+        DpuReachabilityReport {
+            dpu_id: dpu(dpu_id),
+            observed_at: Utc::now(),
+            results,
+        }
+    }
+
+    #[test]
+    fn test_analyze_healthy_matrix() {
+        let reports = vec![
+            report(DPU_A, vec![result(DPU_B, 5, 5)]),
+            report(DPU_B, vec![result(DPU_A, 5, 5)]),
+        ];
+        assert!(analyze(&reports).is_empty());
+    }
+
+    #[test]
+    fn test_analyze_asymmetric_path() {
+        let reports = vec![
+            report(DPU_A, vec![result(DPU_B, 5, 0), result(DPU_C, 5, 5)]),
+            report(DPU_B, vec![result(DPU_A, 5, 5)]),
+            report(DPU_C, vec![result(DPU_A, 5, 5)]),
+        ];
+        assert_eq!(
+            analyze(&reports),
+            vec![ReachabilityFinding::Asymmetric {
+                source: dpu(DPU_A),
+                destination: dpu(DPU_B),
+                probe: icmp(),
+                forward: PathHealth::Down,
+                reverse: PathHealth::Up,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_analyze_partial_loss_and_unreachable() {
+        let reports = vec![
+            report(DPU_A, vec![result(DPU_B, 4, 3), result(DPU_C, 5, 0)]),
+            report(DPU_B, vec![result(DPU_A, 4, 2)]),
+            report(DPU_C, vec![result(DPU_A, 5, 0)]),
+        ];
+        let findings = analyze(&reports);
+
+        assert_eq!(findings.len(), 3);
+        assert!(findings.contains(&ReachabilityFinding::PartialLoss {
+            source: dpu(DPU_A),
+            destination: dpu(DPU_B),
+            probe: icmp(),
+            loss: 0.25,
+        }));
+        assert!(findings.contains(&ReachabilityFinding::PartialLoss {
+            source: dpu(DPU_B),
+            destination: dpu(DPU_A),
+            probe: icmp(),
+            loss: 0.5,
+        }));
+        // Unreachable peers are only reported once
+        assert_eq!(
+            findings
+                .iter()
+                .filter(|f| matches!(f, ReachabilityFinding::Unreachable { .. }))
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_analyze_compares_same_probe_kind() {
+        let mut udp = result(DPU_A, 5, 5);
+        udp.probe = ProbeKind {
+            protocol: ProbeProtocol::Udp,
+            port: Some(4789),
+            vpc_id: None,
+        };
+        let reports = vec![
+            report(DPU_A, vec![result(DPU_B, 5, 0)]),
+            report(DPU_B, vec![udp]),
+        ];
+        // Without an ICMP result for the reverse path, the path is unreachable rather than
+        // asymmetric
+        assert_eq!(
+            analyze(&reports),
+            vec![ReachabilityFinding::Unreachable {
+                source: dpu(DPU_A),
+                destination: dpu(DPU_B),
+                probe: icmp(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_summarize_by_group() {
+        let reports = vec![
+            report(DPU_A, vec![result(DPU_B, 5, 0), result(DPU_C, 5, 5)]),
+            report(DPU_B, vec![result(DPU_A, 5, 0), result(DPU_C, 5, 3)]),
+        ];
+        let groups = HashMap::from([
+            (dpu(DPU_A), "tor-1".to_string()),
+            (dpu(DPU_B), "tor-2".to_string()),
+        ]);
+
+        let summaries = summarize_by_group(&reports, &groups);
+        assert_eq!(
+            summaries,
+            vec![
+                GroupReachability {
+                    source_group: "tor-1".to_string(),
+                    destination_group: "tor-2".to_string(),
+                    paths: 1,
+                    degraded: 0,
+                    down: 1,
+                },
+                GroupReachability {
+                    source_group: "tor-1".to_string(),
+                    destination_group: UNKNOWN_GROUP.to_string(),
+                    paths: 1,
+                    degraded: 0,
+                    down: 0,
+                },
+                GroupReachability {
+                    source_group: "tor-2".to_string(),
+                    destination_group: "tor-1".to_string(),
+                    paths: 1,
+                    degraded: 0,
+                    down: 1,
+                },
+                GroupReachability {
+                    source_group: "tor-2".to_string(),
+                    destination_group: UNKNOWN_GROUP.to_string(),
+                    paths: 1,
+                    degraded: 1,
+                    down: 0,
+                },
+            ]
+        );
+        assert!(summaries[0].all_down());
+        assert!(!summaries[3].all_down());
+    }
+
+    #[test]
+    fn test_report_rpc_round_trip() {
+        let report = report(DPU_A, vec![result(DPU_B, 5, 4)]);
+        let rpc_report = rpc::forge::DpuReachabilityReport::from(report.clone());
+        assert_eq!(DpuReachabilityReport::try_from(rpc_report).unwrap(), report);
+
+        // TCP probes need a port
+        let mut rpc_result = rpc::forge::DpuReachabilityResult::from(result(DPU_B, 5, 5));
+        rpc_result.protocol = rpc::forge::DpuNetworkProbeProtocol::Tcp as i32;
+        assert!(DpuReachabilityResult::try_from(rpc_result.clone()).is_err());
+        rpc_result.port = Some(179);
+        assert!(DpuReachabilityResult::try_from(rpc_result.clone()).is_ok());
+
+        rpc_result.received = 6;
+        assert!(DpuReachabilityResult::try_from(rpc_result).is_err());
+    }
+}
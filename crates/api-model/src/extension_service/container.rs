@@ -0,0 +1,151 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! The container spec of CONTAINER extension services. The spec is validated by carbide-api and
+//! interpreted by the DPU agent, which runs the container through containerd.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// The container spec of a CONTAINER extension service
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ContainerSpec {
+    pub image: String,
+    /// Arguments passed to the entrypoint of the image
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    #[serde(default)]
+    pub mounts: Vec<ContainerMountSpec>,
+    /// Containers run without a network namespace of their own unless disabled, as the DPU
+    /// doesn't configure container networking outside of kubelet
+    #[serde(default = "default_host_network")]
+    pub host_network: bool,
+    #[serde(default)]
+    pub privileged: bool,
+    #[serde(default)]
+    pub restart_policy: ContainerRestartPolicy,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ContainerMountSpec {
+    pub source: String,
+    pub destination: String,
+    #[serde(default)]
+    pub read_only: bool,
+}
+
+/// Whether the DPU agent starts a container again once it stopped
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContainerRestartPolicy {
+    /// The container is started again whenever it stopped, whatever its exit status
+    #[default]
+    Always,
+    /// A stopped container is left stopped and reported as failed
+    Never,
+}
+
+fn default_host_network() -> bool {
+    true
+}
+
+impl ContainerSpec {
+    /// Checks the parts of the spec which deserialization doesn't: the image must not be empty,
+    /// mounts must use absolute paths and environment variable names must not contain `=`.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.image.trim().is_empty() {
+            return Err("Container spec missing required field: image".to_string());
+        }
+
+        if let Some(name) = self
+            .env
+            .keys()
+            .find(|name| name.is_empty() || name.contains('='))
+        {
+            return Err(format!(
+                "Invalid environment variable name in container spec: \"{name}\""
+            ));
+        }
+
+        for mount in &self.mounts {
+            for path in [&mount.source, &mount.destination] {
+                if !path.starts_with('/') || path.contains(',') {
+                    return Err(format!(
+                        "Container spec mounts must use absolute paths without commas: \"{path}\""
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(image: &str) -> ContainerSpec {
+        ContainerSpec {
+            image: image.to_string(),
+            args: Vec::new(),
+            env: BTreeMap::new(),
+            mounts: Vec::new(),
+            host_network: true,
+            privileged: false,
+            restart_policy: ContainerRestartPolicy::default(),
+        }
+    }
+
+    #[test]
+    fn test_container_spec_defaults() {
+        let spec: ContainerSpec = serde_json::from_str(r#"{"image": "app:1.0"}"#).unwrap();
+        assert_eq!(spec, self::spec("app:1.0"));
+
+        let spec: ContainerSpec =
+            serde_json::from_str(r#"{"image": "app:1.0", "restart_policy": "never"}"#).unwrap();
+        assert_eq!(spec.restart_policy, ContainerRestartPolicy::Never);
+
+        assert!(
+            serde_json::from_str::<ContainerSpec>(r#"{"image": "app:1.0", "ports": [80]}"#)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_container_spec_validate() {
+        assert!(spec("app:1.0").validate().is_ok());
+        assert!(spec(" ").validate().is_err());
+
+        let mut env = spec("app:1.0");
+        env.env.insert("A=B".to_string(), "c".to_string());
+        assert!(env.validate().is_err());
+
+        let mut mounts = spec("app:1.0");
+        mounts.mounts.push(ContainerMountSpec {
+            source: "relative".to_string(),
+            destination: "/data".to_string(),
+            read_only: false,
+        });
+        assert!(mounts.validate().is_err());
+    }
+}
@@ -28,6 +28,8 @@ use sqlx::{FromRow, Row};
 
 use super::tenant::TenantOrganizationId;
 
+pub mod container;
+
 const MAX_OBSERVABILITY_CONFIG_NAME: usize = 64;
 const MAX_OBSERVABILITY_PROPERTY_LEN: usize = 128;
 
@@ -37,12 +39,16 @@ static LOG_PATH_BAD_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"[^a-zA-Z0-9\-\_\
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum ExtensionServiceType {
     KubernetesPod,
+    Container,
+    SystemdUnit,
 }
 
 impl std::fmt::Display for ExtensionServiceType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             ExtensionServiceType::KubernetesPod => write!(f, "kubernetes_pod"),
+            ExtensionServiceType::Container => write!(f, "container"),
+            ExtensionServiceType::SystemdUnit => write!(f, "systemd_unit"),
         }
     }
 }
@@ -57,6 +63,8 @@ impl std::str::FromStr for ExtensionServiceType {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s.to_lowercase().as_str() {
             "kubernetes_pod" => Ok(ExtensionServiceType::KubernetesPod),
+            "container" => Ok(ExtensionServiceType::Container),
+            "systemd_unit" => Ok(ExtensionServiceType::SystemdUnit),
             _ => Err(InvalidExtensionServiceTypeError(s.to_string())),
         }
     }
@@ -66,6 +74,8 @@ impl From<ExtensionServiceType> for rpc::DpuExtensionServiceType {
     fn from(service_type: ExtensionServiceType) -> Self {
         match service_type {
             ExtensionServiceType::KubernetesPod => rpc::DpuExtensionServiceType::KubernetesPod,
+            ExtensionServiceType::Container => rpc::DpuExtensionServiceType::Container,
+            ExtensionServiceType::SystemdUnit => rpc::DpuExtensionServiceType::SystemdUnit,
         }
     }
 }
@@ -74,6 +84,8 @@ impl From<rpc::DpuExtensionServiceType> for ExtensionServiceType {
     fn from(service_type: rpc::DpuExtensionServiceType) -> Self {
         match service_type {
             rpc::DpuExtensionServiceType::KubernetesPod => ExtensionServiceType::KubernetesPod,
+            rpc::DpuExtensionServiceType::Container => ExtensionServiceType::Container,
+            rpc::DpuExtensionServiceType::SystemdUnit => ExtensionServiceType::SystemdUnit,
         }
     }
 }
@@ -422,6 +434,22 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_service_type_round_trip() {
+        for service_type in [
+            ExtensionServiceType::KubernetesPod,
+            ExtensionServiceType::Container,
+            ExtensionServiceType::SystemdUnit,
+        ] {
+            let parsed: ExtensionServiceType = service_type.to_string().parse().unwrap();
+            assert_eq!(parsed, service_type);
+
+            let rpc_type = rpc::DpuExtensionServiceType::from(service_type.clone());
+            assert_eq!(ExtensionServiceType::from(rpc_type), service_type);
+        }
+        assert!("docker".parse::<ExtensionServiceType>().is_err());
+    }
+
     #[test]
     fn test_observability_config_from_rpc() {
         // Try a bad name
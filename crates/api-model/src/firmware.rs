@@ -21,6 +21,8 @@ use std::path::PathBuf;
 
 use regex::Regex;
 use serde::{Deserialize, Serialize};
+use sqlx::Row;
+use sqlx::postgres::PgRow;
 
 use crate::site_explorer::EndpointExplorationReport;
 
@@ -49,6 +51,29 @@ impl From<Firmware> for DesiredFirmwareVersions {
     }
 }
 
+/// An entry of the desired_firmware table
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DesiredFirmware {
+    /// The vendor, in PascalCase as reported by site explorer
+    pub vendor: String,
+    pub model: String,
+    pub versions: DesiredFirmwareVersions,
+    pub explicit_update_start_needed: bool,
+}
+
+impl<'r> sqlx::FromRow<'r, PgRow> for DesiredFirmware {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let versions: sqlx::types::Json<DesiredFirmwareVersions> = row.try_get("versions")?;
+
+        Ok(DesiredFirmware {
+            vendor: row.try_get("vendor")?,
+            model: row.try_get("model")?,
+            versions: versions.0,
+            explicit_update_start_needed: row.try_get("explicit_update_start_needed")?,
+        })
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize, Default)]
 pub struct Firmware {
     pub vendor: bmc_vendor::BMCVendor,
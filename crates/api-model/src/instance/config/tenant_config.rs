@@ -23,7 +23,7 @@ use regex::Regex;
 use serde::{Deserialize, Serialize};
 
 use crate::ConfigValidationError;
-use crate::tenant::TenantOrganizationId;
+use crate::tenant::{TenantOrganizationId, is_valid_metadata_token_hop_limit};
 
 const MAX_KEYSET_IDS: usize = 10;
 
@@ -35,6 +35,16 @@ pub struct TenantConfig {
     pub tenant_keyset_ids: Vec<String>,
 
     pub hostname: Option<String>,
+
+    /// Whether FMDS requires session tokens for this instance.
+    /// `None` inherits the setting of the tenant.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata_token_required: Option<bool>,
+
+    /// The IP hop limit of FMDS token responses for this instance.
+    /// `None` inherits the setting of the tenant.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata_token_hop_limit: Option<u32>,
 }
 
 pub static HOSTNAME_RE: Lazy<Regex> =
@@ -59,6 +69,8 @@ impl TryFrom<rpc::forge::TenantConfig> for TenantConfig {
             .map_err(|_| RpcDataConversionError::InvalidTenantOrg(config.tenant_organization_id))?,
             tenant_keyset_ids: config.tenant_keyset_ids,
             hostname: truncated_hostname,
+            metadata_token_required: config.metadata_token_required,
+            metadata_token_hop_limit: config.metadata_token_hop_limit,
         })
     }
 }
@@ -71,6 +83,8 @@ impl TryFrom<TenantConfig> for rpc::forge::TenantConfig {
             tenant_organization_id: config.tenant_organization_id.to_string(),
             tenant_keyset_ids: config.tenant_keyset_ids,
             hostname: config.hostname,
+            metadata_token_required: config.metadata_token_required,
+            metadata_token_hop_limit: config.metadata_token_hop_limit,
         })
     }
 }
@@ -96,6 +110,14 @@ impl TenantConfig {
                 ));
         }
 
+        if let Some(hop_limit) = self.metadata_token_hop_limit
+            && !is_valid_metadata_token_hop_limit(hop_limit)
+        {
+            return Err(ConfigValidationError::InvalidValue(format!(
+                "Metadata token hop limit {hop_limit} is not between 1 and 255"
+            )));
+        }
+
         // check to see if we are over the max IDs or not
         if self.tenant_keyset_ids.len() > MAX_KEYSET_IDS {
             return Err(ConfigValidationError::TenantKeysetIdsOverMax(
@@ -127,6 +149,8 @@ mod tests {
             tenant_organization_id: TenantOrganizationId::try_from("TenantA".to_string()).unwrap(),
             tenant_keyset_ids: vec![],
             hostname: Some("test-instance".to_string()),
+            metadata_token_required: None,
+            metadata_token_hop_limit: None,
         };
 
         let serialized = serde_json::to_string(&config).unwrap();
@@ -151,6 +175,8 @@ mod tests {
                 "a".to_string(),
             ],
             hostname: Some("test-instance".to_string()),
+            metadata_token_required: None,
+            metadata_token_hop_limit: None,
         };
 
         assert!(matches!(
@@ -165,8 +191,30 @@ mod tests {
             tenant_organization_id: TenantOrganizationId::try_from("TenantA".to_string()).unwrap(),
             tenant_keyset_ids: vec!["a".to_string(), "b".to_string(), "c".to_string()],
             hostname: Some("test-instance".to_string()),
+            metadata_token_required: None,
+            metadata_token_hop_limit: None,
         };
 
         config.validate().unwrap()
     }
+
+    #[test]
+    fn validate_tenant_config_metadata_token_hop_limit() {
+        let mut config = TenantConfig {
+            tenant_organization_id: TenantOrganizationId::try_from("TenantA".to_string()).unwrap(),
+            tenant_keyset_ids: vec![],
+            hostname: None,
+            metadata_token_required: Some(true),
+            metadata_token_hop_limit: Some(1),
+        };
+        config.validate().unwrap();
+
+        for hop_limit in [0, 256] {
+            config.metadata_token_hop_limit = Some(hop_limit);
+            assert!(matches!(
+                config.validate(),
+                Err(ConfigValidationError::InvalidValue(_))
+            ));
+        }
+    }
 }
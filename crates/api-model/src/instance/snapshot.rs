@@ -167,6 +167,10 @@ pub struct InstanceSnapshotPgJson {
     tenant_org: Option<String>,
     keyset_ids: Vec<String>,
     hostname: Option<String>,
+    #[serde(default)]
+    metadata_token_required: Option<bool>,
+    #[serde(default)]
+    metadata_token_hop_limit: Option<u32>,
     os_user_data: Option<String>,
     os_ipxe_script: String,
     os_always_boot_with_ipxe: bool,
@@ -223,6 +227,8 @@ impl TryFrom<InstanceSnapshotPgJson> for InstanceSnapshot {
                 tenant_organization_id,
                 tenant_keyset_ids: value.keyset_ids,
                 hostname: value.hostname,
+                metadata_token_required: value.metadata_token_required,
+                metadata_token_hop_limit: value.metadata_token_hop_limit,
             },
             os,
             network: value.network_config,
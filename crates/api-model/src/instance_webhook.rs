@@ -0,0 +1,536 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Webhooks which notify tenants about the lifecycle of their instances
+//!
+//! Tenants register subscriptions with the URL events are posted to. Every event is stored as
+//! a delivery for each matching subscription of the tenant of the instance, in the same
+//! transaction as the change that caused it. Deliveries are retried with exponential backoff,
+//! and end up in the dead-letter list once they run out of attempts.
+//!
+//! Subscriptions may only post to `https` URLs which resolve to public addresses, so that
+//! tenants can't use webhooks to reach services inside the site.
+
+use std::fmt::{Display, Formatter};
+use std::net::IpAddr;
+use std::str::FromStr;
+use std::time::Duration;
+
+use carbide_uuid::instance::InstanceId;
+use carbide_uuid::machine::MachineId;
+use chrono::{DateTime, Utc};
+use ring::hmac;
+use ring::rand::{SecureRandom, SystemRandom};
+use rpc::errors::RpcDataConversionError;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::instance::snapshot::InstanceSnapshot;
+use crate::tenant::TenantOrganizationId;
+
+/// The header carrying the signature of the payload, in the form `sha256=<hex>`
+pub const SIGNATURE_HEADER: &str = "X-Carbide-Signature-256";
+/// The header carrying the unix timestamp which is part of the signed content
+pub const TIMESTAMP_HEADER: &str = "X-Carbide-Webhook-Timestamp";
+/// The header carrying the event type of the payload
+pub const EVENT_HEADER: &str = "X-Carbide-Event";
+/// The header carrying the ID of the delivery, which stays the same across retries
+pub const DELIVERY_HEADER: &str = "X-Carbide-Delivery";
+
+/// The shortest secret accepted for a subscription
+pub const MIN_SECRET_LENGTH: usize = 16;
+
+/// The instance lifecycle events tenants can subscribe to
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, sqlx::Type, Serialize, Deserialize)]
+#[sqlx(type_name = "instance_lifecycle_event_type")]
+#[sqlx(rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum InstanceLifecycleEventType {
+    /// The instance was allocated on a host
+    Allocated,
+    /// The DPUs of the host applied the network config of the instance
+    NetworkConfigSynced,
+    /// The host was rebooted into the tenant OS and the instance is ready for use
+    Ready,
+    /// The tenant released the instance
+    ReleaseStarted,
+    /// The instance was removed and its resources were released
+    CleanedUp,
+}
+
+impl InstanceLifecycleEventType {
+    pub const ALL: [InstanceLifecycleEventType; 5] = [
+        InstanceLifecycleEventType::Allocated,
+        InstanceLifecycleEventType::NetworkConfigSynced,
+        InstanceLifecycleEventType::Ready,
+        InstanceLifecycleEventType::ReleaseStarted,
+        InstanceLifecycleEventType::CleanedUp,
+    ];
+}
+
+impl Display for InstanceLifecycleEventType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let string = match self {
+            InstanceLifecycleEventType::Allocated => "allocated",
+            InstanceLifecycleEventType::NetworkConfigSynced => "network_config_synced",
+            InstanceLifecycleEventType::Ready => "ready",
+            InstanceLifecycleEventType::ReleaseStarted => "release_started",
+            InstanceLifecycleEventType::CleanedUp => "cleaned_up",
+        };
+        write!(f, "{string}")
+    }
+}
+
+impl FromStr for InstanceLifecycleEventType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        InstanceLifecycleEventType::ALL
+            .into_iter()
+            .find(|event_type| event_type.to_string() == s)
+            .ok_or_else(|| format!("unknown instance lifecycle event type \"{s}\""))
+    }
+}
+
+impl From<rpc::forge::InstanceLifecycleEventType> for InstanceLifecycleEventType {
+    fn from(event_type: rpc::forge::InstanceLifecycleEventType) -> Self {
+        use rpc::forge::InstanceLifecycleEventType as Rpc;
+        match event_type {
+            Rpc::InstanceEventAllocated => InstanceLifecycleEventType::Allocated,
+            Rpc::InstanceEventNetworkConfigSynced => {
+                InstanceLifecycleEventType::NetworkConfigSynced
+            }
+            Rpc::InstanceEventReady => InstanceLifecycleEventType::Ready,
+            Rpc::InstanceEventReleaseStarted => InstanceLifecycleEventType::ReleaseStarted,
+            Rpc::InstanceEventCleanedUp => InstanceLifecycleEventType::CleanedUp,
+        }
+    }
+}
+
+impl From<InstanceLifecycleEventType> for rpc::forge::InstanceLifecycleEventType {
+    fn from(event_type: InstanceLifecycleEventType) -> Self {
+        use rpc::forge::InstanceLifecycleEventType as Rpc;
+        match event_type {
+            InstanceLifecycleEventType::Allocated => Rpc::InstanceEventAllocated,
+            InstanceLifecycleEventType::NetworkConfigSynced => {
+                Rpc::InstanceEventNetworkConfigSynced
+            }
+            InstanceLifecycleEventType::Ready => Rpc::InstanceEventReady,
+            InstanceLifecycleEventType::ReleaseStarted => Rpc::InstanceEventReleaseStarted,
+            InstanceLifecycleEventType::CleanedUp => Rpc::InstanceEventCleanedUp,
+        }
+    }
+}
+
+/// The payload posted to the subscribers of an event
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InstanceLifecycleEvent {
+    pub event_type: InstanceLifecycleEventType,
+    pub instance_id: InstanceId,
+    pub machine_id: MachineId,
+    pub tenant_organization_id: TenantOrganizationId,
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl InstanceLifecycleEvent {
+    /// An event about the given instance which occurred now
+    pub fn for_instance(
+        event_type: InstanceLifecycleEventType,
+        instance: &InstanceSnapshot,
+    ) -> Self {
+        InstanceLifecycleEvent {
+            event_type,
+            instance_id: instance.id,
+            machine_id: instance.machine_id,
+            tenant_organization_id: instance.config.tenant.tenant_organization_id.clone(),
+            occurred_at: Utc::now(),
+        }
+    }
+}
+
+/// A webhook registered by a tenant
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstanceWebhookSubscription {
+    pub id: Uuid,
+    pub tenant_organization_id: TenantOrganizationId,
+    /// The `https` URL events are posted to, see [check_webhook_url]
+    pub url: String,
+    /// The key payloads are signed with. It is kept in the credential provider, and is only
+    /// set right after the subscription was created.
+    pub secret: Option<String>,
+    /// The events delivered to the subscription. All events if empty.
+    pub event_types: Vec<InstanceLifecycleEventType>,
+    pub created: DateTime<Utc>,
+}
+
+impl InstanceWebhookSubscription {
+    /// Whether events of the given type are delivered to the subscription
+    pub fn matches(&self, event_type: InstanceLifecycleEventType) -> bool {
+        self.event_types.is_empty() || self.event_types.contains(&event_type)
+    }
+}
+
+impl From<InstanceWebhookSubscription> for rpc::forge::InstanceWebhookSubscription {
+    fn from(subscription: InstanceWebhookSubscription) -> Self {
+        rpc::forge::InstanceWebhookSubscription {
+            id: subscription.id.to_string(),
+            tenant_organization_id: subscription.tenant_organization_id.to_string(),
+            url: subscription.url,
+            secret: subscription.secret,
+            event_types: subscription
+                .event_types
+                .into_iter()
+                .map(|event_type| rpc::forge::InstanceLifecycleEventType::from(event_type) as i32)
+                .collect(),
+            created: Some(subscription.created.into()),
+        }
+    }
+}
+
+impl TryFrom<rpc::forge::InstanceWebhookSubscription> for InstanceWebhookSubscription {
+    type Error = RpcDataConversionError;
+
+    fn try_from(
+        subscription: rpc::forge::InstanceWebhookSubscription,
+    ) -> Result<Self, Self::Error> {
+        let tenant_organization_id =
+            TenantOrganizationId::try_from(subscription.tenant_organization_id.clone()).map_err(
+                |_| RpcDataConversionError::InvalidTenantOrg(subscription.tenant_organization_id),
+            )?;
+
+        let url = subscription.url.trim().to_string();
+        if !(url.starts_with("https://") || url.starts_with("http://")) {
+            return Err(RpcDataConversionError::InvalidValue(
+                "url".to_string(),
+                subscription.url,
+            ));
+        }
+
+        let secret = match subscription.secret {
+            Some(secret) if secret.len() < MIN_SECRET_LENGTH => {
+                return Err(RpcDataConversionError::InvalidArgument(format!(
+                    "secret must be at least {MIN_SECRET_LENGTH} characters long"
+                )));
+            }
+            Some(secret) => secret,
+            None => generate_secret(),
+        };
+
+        let mut event_types = Vec::with_capacity(subscription.event_types.len());
+        for event_type in subscription.event_types {
+            let event_type = rpc::forge::InstanceLifecycleEventType::try_from(event_type)
+                .map_err(|_| {
+                    RpcDataConversionError::InvalidValue(
+                        "event_types".to_string(),
+                        event_type.to_string(),
+                    )
+                })?
+                .into();
+            if !event_types.contains(&event_type) {
+                event_types.push(event_type);
+            }
+        }
+
+        Ok(InstanceWebhookSubscription {
+            id: Uuid::new_v4(),
+            tenant_organization_id,
+            url,
+            secret: Some(secret),
+            event_types,
+            created: Utc::now(),
+        })
+    }
+}
+
+/// Checks the parts of a subscription URL which don't require name resolution: the URL has
+/// to use `https`, and its host must not be `localhost` or a forbidden address
+pub fn check_webhook_url(url: &str) -> Result<url::Url, String> {
+    let url = url::Url::parse(url).map_err(|e| format!("invalid URL: {e}"))?;
+    if url.scheme() != "https" {
+        return Err(format!("URL must use https, not {}", url.scheme()));
+    }
+    match url.host() {
+        None => return Err("URL has no host".to_string()),
+        Some(url::Host::Ipv4(ip)) => check_webhook_address(ip.into())?,
+        Some(url::Host::Ipv6(ip)) => check_webhook_address(ip.into())?,
+        Some(url::Host::Domain(domain)) => {
+            let domain = domain.trim_end_matches('.').to_ascii_lowercase();
+            if domain == "localhost" || domain.ends_with(".localhost") {
+                return Err(format!("URL must not point to {domain}"));
+            }
+        }
+    }
+    Ok(url)
+}
+
+/// Checks a subscription URL like [check_webhook_url], and additionally resolves its host.
+/// The URL is rejected if any of the addresses is forbidden.
+pub async fn validate_webhook_target(url: &str) -> Result<(), String> {
+    let url = check_webhook_url(url)?;
+    let Some(url::Host::Domain(host)) = url.host() else {
+        return Ok(());
+    };
+    let port = url.port_or_known_default().unwrap_or(443);
+    let addresses: Vec<_> = tokio::net::lookup_host((host, port))
+        .await
+        .map_err(|e| format!("failed to resolve {host}: {e}"))?
+        .collect();
+    if addresses.is_empty() {
+        return Err(format!("{host} does not resolve to any address"));
+    }
+    for address in addresses {
+        check_webhook_address(address.ip())
+            .map_err(|e| format!("{host} resolves to a forbidden address: {e}"))?;
+    }
+    Ok(())
+}
+
+/// Rejects the addresses events must not be posted to: loopback, private, link-local (which
+/// includes the instance metadata service), shared, multicast and unspecified addresses
+pub fn check_webhook_address(ip: IpAddr) -> Result<(), String> {
+    let forbidden = match ip {
+        IpAddr::V4(ip) => {
+            let [first, second, ..] = ip.octets();
+            ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                || ip.is_multicast()
+                || ip.is_documentation()
+                // "This network", 0.0.0.0/8
+                || first == 0
+                // Shared address space, 100.64.0.0/10, which is used by some metadata services
+                || (first == 100 && (second & 0xc0) == 64)
+        }
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => return check_webhook_address(ip.into()),
+            None => {
+                ip.is_loopback()
+                    || ip.is_unspecified()
+                    || ip.is_multicast()
+                    || ip.is_unique_local()
+                    || ip.is_unicast_link_local()
+            }
+        },
+    };
+    if forbidden {
+        return Err(format!("{ip} is not a public address"));
+    }
+    Ok(())
+}
+
+/// Generates a random secret for subscriptions which were created without one
+pub fn generate_secret() -> String {
+    let mut bytes = [0u8; 32];
+    SystemRandom::new()
+        .fill(&mut bytes)
+        .expect("system random number generator failed");
+    to_hex(&bytes)
+}
+
+/// Signs a payload with the secret of a subscription.
+///
+/// The signature is the hex encoded HMAC-SHA256 of `<timestamp>.<payload>`, so that receivers
+/// can reject replayed deliveries by checking the timestamp.
+pub fn sign_payload(secret: &str, timestamp: i64, payload: &[u8]) -> String {
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+    let mut context = hmac::Context::with_key(&key);
+    context.update(timestamp.to_string().as_bytes());
+    context.update(b".");
+    context.update(payload);
+    format!("sha256={}", to_hex(context.sign().as_ref()))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// How long to wait before the next attempt of a delivery which failed `attempts` times.
+/// The delay doubles with every failed attempt, up to `max`.
+pub fn retry_backoff(attempts: u32, initial: Duration, max: Duration) -> Duration {
+    let factor = 1u32
+        .checked_shl(attempts.saturating_sub(1))
+        .unwrap_or(u32::MAX);
+    initial.saturating_mul(factor).min(max)
+}
+
+/// An event waiting to be delivered to a subscription, or which ran out of attempts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstanceWebhookDelivery {
+    pub id: i64,
+    pub subscription_id: Uuid,
+    /// The URL of the subscription
+    pub url: String,
+    pub event: InstanceLifecycleEvent,
+    pub attempts: u32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_error: Option<String>,
+    /// When the delivery ran out of attempts
+    pub dead_lettered: Option<DateTime<Utc>>,
+}
+
+impl From<InstanceWebhookDelivery> for rpc::forge::InstanceWebhookDelivery {
+    fn from(delivery: InstanceWebhookDelivery) -> Self {
+        rpc::forge::InstanceWebhookDelivery {
+            id: delivery.id as u64,
+            subscription_id: delivery.subscription_id.to_string(),
+            url: delivery.url,
+            tenant_organization_id: delivery.event.tenant_organization_id.to_string(),
+            event_type: rpc::forge::InstanceLifecycleEventType::from(delivery.event.event_type)
+                as i32,
+            instance_id: Some(delivery.event.instance_id),
+            machine_id: Some(delivery.event.machine_id),
+            occurred_at: Some(delivery.event.occurred_at.into()),
+            attempts: delivery.attempts,
+            last_error: delivery.last_error,
+            dead_lettered: delivery.dead_lettered.map(Into::into),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_type_round_trip() {
+        for event_type in InstanceLifecycleEventType::ALL {
+            assert_eq!(
+                event_type.to_string().parse::<InstanceLifecycleEventType>(),
+                Ok(event_type)
+            );
+            assert_eq!(
+                serde_json::to_string(&event_type).unwrap(),
+                format!("\"{event_type}\"")
+            );
+            assert_eq!(
+                InstanceLifecycleEventType::from(rpc::forge::InstanceLifecycleEventType::from(
+                    event_type
+                )),
+                event_type
+            );
+        }
+        assert!("deleted".parse::<InstanceLifecycleEventType>().is_err());
+    }
+
+    #[test]
+    fn test_sign_payload() {
+        assert_eq!(
+            sign_payload("topsecret", 1767225600, br#"{"event_type":"ready"}"#),
+            "sha256=a4fbd6170f0edd1b8bf1c048b46c979b7293036458cb4e14b921653890f54e67"
+        );
+        // The timestamp is part of the signed content
+        assert_ne!(
+            sign_payload("topsecret", 1767225601, br#"{"event_type":"ready"}"#),
+            sign_payload("topsecret", 1767225600, br#"{"event_type":"ready"}"#)
+        );
+    }
+
+    #[test]
+    fn test_generate_secret() {
+        let secret = generate_secret();
+        assert_eq!(secret.len(), 64);
+        assert!(secret.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_ne!(secret, generate_secret());
+    }
+
+    #[test]
+    fn test_retry_backoff() {
+        let initial = Duration::from_secs(10);
+        let max = Duration::from_secs(600);
+        assert_eq!(retry_backoff(1, initial, max), Duration::from_secs(10));
+        assert_eq!(retry_backoff(2, initial, max), Duration::from_secs(20));
+        assert_eq!(retry_backoff(4, initial, max), Duration::from_secs(80));
+        assert_eq!(retry_backoff(7, initial, max), max);
+        assert_eq!(retry_backoff(100, initial, max), max);
+    }
+
+    #[test]
+    fn test_subscription_from_rpc() {
+        let rpc_subscription = rpc::forge::InstanceWebhookSubscription {
+            tenant_organization_id: "Tenant1".to_string(),
+            url: "https://hooks.example.com/carbide".to_string(),
+            event_types: vec![
+                rpc::forge::InstanceLifecycleEventType::InstanceEventReady as i32,
+                rpc::forge::InstanceLifecycleEventType::InstanceEventReady as i32,
+            ],
+            ..Default::default()
+        };
+        let subscription = InstanceWebhookSubscription::try_from(rpc_subscription.clone()).unwrap();
+        assert_eq!(
+            subscription.event_types,
+            vec![InstanceLifecycleEventType::Ready]
+        );
+        assert!(subscription.matches(InstanceLifecycleEventType::Ready));
+        assert!(!subscription.matches(InstanceLifecycleEventType::Allocated));
+        // A secret is generated if none is given
+        assert_eq!(subscription.secret.as_ref().map(String::len), Some(64));
+
+        for invalid in [
+            rpc::forge::InstanceWebhookSubscription {
+                url: "ftp://hooks.example.com".to_string(),
+                ..rpc_subscription.clone()
+            },
+            rpc::forge::InstanceWebhookSubscription {
+                tenant_organization_id: "not a tenant".to_string(),
+                ..rpc_subscription.clone()
+            },
+            rpc::forge::InstanceWebhookSubscription {
+                secret: Some("short".to_string()),
+                ..rpc_subscription.clone()
+            },
+            rpc::forge::InstanceWebhookSubscription {
+                event_types: vec![42],
+                ..rpc_subscription.clone()
+            },
+        ] {
+            assert!(InstanceWebhookSubscription::try_from(invalid).is_err());
+        }
+    }
+
+    #[test]
+    fn test_check_webhook_url() {
+        for url in [
+            "https://hooks.example.com/carbide",
+            "https://hooks.example.com:8443",
+            "https://8.8.8.8/hook",
+            "https://[2001:4860::1]/hook",
+        ] {
+            assert!(check_webhook_url(url).is_ok(), "{url}");
+        }
+        for url in [
+            "http://hooks.example.com/carbide",
+            "not a url",
+            "https://localhost/hook",
+            "https://api.LOCALHOST./hook",
+            "https://127.0.0.1/hook",
+            "https://10.1.2.3/hook",
+            "https://172.20.0.1/hook",
+            "https://192.168.1.1/hook",
+            "https://169.254.169.254/latest/meta-data",
+            "https://100.100.100.200/hook",
+            "https://0.0.0.0/hook",
+            "https://[::1]/hook",
+            "https://[fd00:ec2::254]/hook",
+            "https://[fe80::1]/hook",
+            "https://[::ffff:10.0.0.1]/hook",
+        ] {
+            assert!(check_webhook_url(url).is_err(), "{url}");
+        }
+    }
+}
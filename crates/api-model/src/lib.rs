@@ -35,13 +35,16 @@ use serde::{Deserialize, Serialize};
 
 pub mod address_selection_strategy;
 pub mod attestation;
+pub mod bmc_credential_rotation;
 pub mod bmc_info;
+pub mod capacity_reservation;
 pub mod controller_outcome;
 pub mod dhcp_entry;
 pub mod dhcp_record;
 pub mod dns;
 pub mod dpa_interface;
 pub mod dpu_machine_update;
+pub mod dpu_reachability;
 pub mod dpu_remediation;
 pub mod errors;
 pub mod expected_machine;
@@ -56,11 +59,15 @@ pub mod ib_partition;
 pub mod instance;
 pub mod instance_address;
 pub mod instance_type;
+pub mod instance_webhook;
 pub mod machine;
 pub mod machine_boot_override;
+pub mod machine_erasure_report;
 pub mod machine_interface_address;
 pub mod machine_update_module;
+pub mod machine_update_rollout;
 pub mod machine_validation;
+pub mod maintenance_window;
 pub mod metadata;
 pub mod network_devices;
 pub mod network_prefix;
@@ -79,6 +86,7 @@ pub mod resource_pool;
 pub mod route_server;
 pub mod site_explorer;
 pub mod sku;
+pub mod ssh_console_session;
 pub mod storage;
 pub mod switch;
 pub mod tenant;
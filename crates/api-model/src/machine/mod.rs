@@ -1507,6 +1507,7 @@ pub enum PowerDrainState {
 pub enum FailureCause {
     NoError,
     NVMECleanFailed { err: String },
+    SataSasCleanFailed { err: String },
     Discovery { err: String },
     Reprovisioning { err: String },
     MachineValidation { err: String },
@@ -1991,6 +1992,7 @@ impl Display for FailureCause {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             FailureCause::NVMECleanFailed { .. } => write!(f, "NVMECleanFailed"),
+            FailureCause::SataSasCleanFailed { .. } => write!(f, "SataSasCleanFailed"),
             FailureCause::NoError => write!(f, "NoError"),
             FailureCause::Discovery { .. } => write!(f, "Discovery"),
             FailureCause::Reprovisioning { .. } => write!(f, "Reprovisioning"),
@@ -2367,6 +2369,47 @@ impl From<MachineHealthHistoryRecord> for rpc::forge::MachineHealthHistoryRecord
     }
 }
 
+/// The result of a single state handler invocation for a Machine
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MachineStateHandlerOutcomeRecord {
+    /// The state the Machine was in when the state handler was invoked
+    pub state: String,
+
+    /// The outcome of the state handler invocation
+    pub outcome: PersistentStateHandlerOutcome,
+
+    /// The state controller iteration during which the handler was invoked
+    pub iteration_id: Option<i64>,
+
+    /// How long the state handler took to produce the outcome
+    pub handler_latency: std::time::Duration,
+
+    /// How often the state handler produced this outcome in a row
+    pub repeat_count: u32,
+
+    /// The time when the outcome was first recorded
+    pub time: DateTime<Utc>,
+
+    /// The time when the outcome was last recorded
+    pub last_time: DateTime<Utc>,
+}
+
+impl From<MachineStateHandlerOutcomeRecord> for rpc::forge::StateHandlerOutcomeHistoryRecord {
+    fn from(
+        record: MachineStateHandlerOutcomeRecord,
+    ) -> rpc::forge::StateHandlerOutcomeHistoryRecord {
+        rpc::forge::StateHandlerOutcomeHistoryRecord {
+            state: record.state,
+            outcome: Some(record.outcome.into()),
+            iteration_id: record.iteration_id,
+            handler_latency: Some(record.handler_latency.into()),
+            repeat_count: record.repeat_count,
+            time: Some(record.time.into()),
+            last_time: Some(record.last_time.into()),
+        }
+    }
+}
+
 /// Returns the SLA for the current state
 pub fn state_sla(state: &ManagedHostState, state_version: &ConfigVersion) -> StateSla {
     let time_in_state = chrono::Utc::now()
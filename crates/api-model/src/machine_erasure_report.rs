@@ -0,0 +1,352 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Records of the storage erasure performed by scout during the cleanup of a machine
+//!
+//! A report is opened when an instance is released, so that the erasure can be attributed to the
+//! tenant which used the machine. It is completed with the per-device records scout sends along
+//! with the cleanup result, and signed so that tenants can hand it on as proof of sanitization.
+
+use carbide_uuid::instance::InstanceId;
+use carbide_uuid::machine::MachineId;
+use chrono::{DateTime, Utc};
+use rpc::errors::RpcDataConversionError;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::tenant::TenantOrganizationId;
+
+/// Issuer of signed erasure reports
+pub const SIGNED_REPORT_ISSUER: &str = "carbide-api";
+
+/// How a device was erased
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErasureMethod {
+    Unspecified,
+    NvmeCryptoErase,
+    NvmeFormat,
+    NvmeSanitize,
+    NvmeNamespaceRecreate,
+    AtaSecureErase,
+    AtaEnhancedSecureErase,
+    ScsiSanitize,
+}
+
+impl From<rpc::forge::StorageErasureMethod> for ErasureMethod {
+    fn from(method: rpc::forge::StorageErasureMethod) -> Self {
+        use rpc::forge::StorageErasureMethod as Rpc;
+        match method {
+            Rpc::Unspecified => ErasureMethod::Unspecified,
+            Rpc::NvmeCryptoErase => ErasureMethod::NvmeCryptoErase,
+            Rpc::NvmeFormat => ErasureMethod::NvmeFormat,
+            Rpc::NvmeSanitize => ErasureMethod::NvmeSanitize,
+            Rpc::NvmeNamespaceRecreate => ErasureMethod::NvmeNamespaceRecreate,
+            Rpc::AtaSecureErase => ErasureMethod::AtaSecureErase,
+            Rpc::AtaEnhancedSecureErase => ErasureMethod::AtaEnhancedSecureErase,
+            Rpc::ScsiSanitize => ErasureMethod::ScsiSanitize,
+        }
+    }
+}
+
+impl From<ErasureMethod> for rpc::forge::StorageErasureMethod {
+    fn from(method: ErasureMethod) -> Self {
+        use rpc::forge::StorageErasureMethod as Rpc;
+        match method {
+            ErasureMethod::Unspecified => Rpc::Unspecified,
+            ErasureMethod::NvmeCryptoErase => Rpc::NvmeCryptoErase,
+            ErasureMethod::NvmeFormat => Rpc::NvmeFormat,
+            ErasureMethod::NvmeSanitize => Rpc::NvmeSanitize,
+            ErasureMethod::NvmeNamespaceRecreate => Rpc::NvmeNamespaceRecreate,
+            ErasureMethod::AtaSecureErase => Rpc::AtaSecureErase,
+            ErasureMethod::AtaEnhancedSecureErase => Rpc::AtaEnhancedSecureErase,
+            ErasureMethod::ScsiSanitize => Rpc::ScsiSanitize,
+        }
+    }
+}
+
+/// A block of a device which was read back after the erasure
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerificationSample {
+    pub offset_bytes: u64,
+    pub length_bytes: u32,
+    /// Whether the block only contained zeroes
+    pub zeroed: bool,
+    /// Whether the block differs from its content before the erasure
+    pub changed: bool,
+}
+
+/// The erasure of a single NVMe, SATA or SAS device
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageErasureRecord {
+    /// Device path, e.g. /dev/nvme0 or /dev/sda
+    pub device: String,
+    pub serial_number: String,
+    pub model: String,
+    pub firmware_version: String,
+    pub capacity_bytes: u64,
+    pub method: ErasureMethod,
+    pub success: bool,
+    pub error: Option<String>,
+    pub verification_samples: Vec<VerificationSample>,
+    pub started_at: DateTime<Utc>,
+    pub completed_at: DateTime<Utc>,
+}
+
+impl TryFrom<rpc::forge::StorageErasureRecord> for StorageErasureRecord {
+    type Error = RpcDataConversionError;
+
+    fn try_from(record: rpc::forge::StorageErasureRecord) -> Result<Self, Self::Error> {
+        let timestamp = |timestamp: Option<rpc::Timestamp>, field: &'static str| {
+            let timestamp = timestamp.ok_or(RpcDataConversionError::MissingArgument(field))?;
+            DateTime::<Utc>::try_from(timestamp)
+                .map_err(|_| RpcDataConversionError::InvalidTimestamp(timestamp.to_string()))
+        };
+        let method = rpc::forge::StorageErasureMethod::try_from(record.method)
+            .map_err(|_| {
+                RpcDataConversionError::InvalidValue(
+                    "method".to_string(),
+                    record.method.to_string(),
+                )
+            })?
+            .into();
+        if record.device.is_empty() {
+            return Err(RpcDataConversionError::MissingArgument("device"));
+        }
+
+        Ok(StorageErasureRecord {
+            started_at: timestamp(record.started_at, "started_at")?,
+            completed_at: timestamp(record.completed_at, "completed_at")?,
+            device: record.device,
+            serial_number: record.serial_number,
+            model: record.model,
+            firmware_version: record.firmware_version,
+            capacity_bytes: record.capacity_bytes,
+            method,
+            success: record.success,
+            error: record.error,
+            verification_samples: record
+                .verification_samples
+                .into_iter()
+                .map(|sample| VerificationSample {
+                    offset_bytes: sample.offset_bytes,
+                    length_bytes: sample.length_bytes,
+                    zeroed: sample.zeroed,
+                    changed: sample.changed,
+                })
+                .collect(),
+        })
+    }
+}
+
+impl From<StorageErasureRecord> for rpc::forge::StorageErasureRecord {
+    fn from(record: StorageErasureRecord) -> Self {
+        rpc::forge::StorageErasureRecord {
+            device: record.device,
+            serial_number: record.serial_number,
+            model: record.model,
+            firmware_version: record.firmware_version,
+            capacity_bytes: record.capacity_bytes,
+            method: rpc::forge::StorageErasureMethod::from(record.method) as i32,
+            success: record.success,
+            error: record.error,
+            verification_samples: record
+                .verification_samples
+                .into_iter()
+                .map(|sample| rpc::forge::StorageErasureVerificationSample {
+                    offset_bytes: sample.offset_bytes,
+                    length_bytes: sample.length_bytes,
+                    zeroed: sample.zeroed,
+                    changed: sample.changed,
+                })
+                .collect(),
+            started_at: Some(record.started_at.into()),
+            completed_at: Some(record.completed_at.into()),
+        }
+    }
+}
+
+/// The storage erasure of one cleanup of a machine
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MachineErasureReport {
+    pub id: Uuid,
+    pub machine_id: MachineId,
+    /// The released instance, if the cleanup was part of an instance release
+    pub instance_id: Option<InstanceId>,
+    pub tenant_organization_id: Option<TenantOrganizationId>,
+    pub released_at: Option<DateTime<Utc>>,
+    /// `None` while the cleanup is pending
+    pub completed_at: Option<DateTime<Utc>>,
+    pub records: Vec<StorageErasureRecord>,
+    /// The completed report as JWT, if a signing key is configured
+    pub signed_report: Option<String>,
+}
+
+impl MachineErasureReport {
+    /// A report which is opened when the instance on the machine is released
+    pub fn for_release(
+        machine_id: MachineId,
+        instance_id: InstanceId,
+        tenant_organization_id: TenantOrganizationId,
+    ) -> Self {
+        MachineErasureReport {
+            id: Uuid::new_v4(),
+            machine_id,
+            instance_id: Some(instance_id),
+            tenant_organization_id: Some(tenant_organization_id),
+            released_at: Some(Utc::now()),
+            completed_at: None,
+            records: Vec::new(),
+            signed_report: None,
+        }
+    }
+
+    /// A report for a cleanup which isn't part of an instance release, e.g. during ingestion
+    pub fn without_release(machine_id: MachineId) -> Self {
+        MachineErasureReport {
+            id: Uuid::new_v4(),
+            machine_id,
+            instance_id: None,
+            tenant_organization_id: None,
+            released_at: None,
+            completed_at: None,
+            records: Vec::new(),
+            signed_report: None,
+        }
+    }
+
+    /// Whether the cleanup completed and every device was erased successfully.
+    /// A report without any records proves nothing, e.g. if scout skipped the cleanup.
+    pub fn complete(&self) -> bool {
+        self.completed_at.is_some()
+            && !self.records.is_empty()
+            && self.records.iter().all(|record| record.success)
+    }
+
+    /// The claims of the signed report
+    pub fn claims(&self) -> serde_json::Value {
+        serde_json::json!({
+            "iss": SIGNED_REPORT_ISSUER,
+            "jti": self.id.to_string(),
+            "sub": self.machine_id.to_string(),
+            "iat": self.completed_at.unwrap_or_else(Utc::now).timestamp(),
+            "instance_id": self.instance_id.map(|id| id.to_string()),
+            "tenant_organization_id": self.tenant_organization_id.as_ref().map(|id| id.to_string()),
+            "released_at": self.released_at,
+            "completed_at": self.completed_at,
+            "complete": self.complete(),
+            "records": self.records,
+        })
+    }
+}
+
+impl From<MachineErasureReport> for rpc::forge::MachineErasureReport {
+    fn from(report: MachineErasureReport) -> Self {
+        let complete = report.complete();
+        rpc::forge::MachineErasureReport {
+            id: report.id.to_string(),
+            machine_id: Some(report.machine_id),
+            instance_id: report.instance_id,
+            tenant_organization_id: report.tenant_organization_id.map(|id| id.to_string()),
+            released_at: report.released_at.map(Into::into),
+            completed_at: report.completed_at.map(Into::into),
+            records: report.records.into_iter().map(Into::into).collect(),
+            complete,
+            signed_report: report.signed_report,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MACHINE_ID: &str = "fm100htv4fu8fpktl0e0qrg4dl58g2bc2g7naq0l6c15ruc22po1i5rfsq0";
+
+    fn rpc_record() -> rpc::forge::StorageErasureRecord {
+        rpc::forge::StorageErasureRecord {
+            device: "/dev/nvme0".to_string(),
+            serial_number: "S5XANA0R123456".to_string(),
+            model: "SAMSUNG MZQL23T8HCLS".to_string(),
+            firmware_version: "GDC5502Q".to_string(),
+            capacity_bytes: 3_840_755_982_336,
+            method: rpc::forge::StorageErasureMethod::NvmeCryptoErase as i32,
+            success: true,
+            error: None,
+            verification_samples: vec![rpc::forge::StorageErasureVerificationSample {
+                offset_bytes: 0,
+                length_bytes: 4096,
+                zeroed: false,
+                changed: true,
+            }],
+            started_at: Some(Utc::now().into()),
+            completed_at: Some(Utc::now().into()),
+        }
+    }
+
+    #[test]
+    fn test_record_rpc_roundtrip() {
+        let record = StorageErasureRecord::try_from(rpc_record()).unwrap();
+        assert_eq!(record.method, ErasureMethod::NvmeCryptoErase);
+        assert_eq!(record.verification_samples.len(), 1);
+
+        let roundtrip =
+            StorageErasureRecord::try_from(rpc::forge::StorageErasureRecord::from(record.clone()))
+                .unwrap();
+        assert_eq!(roundtrip, record);
+    }
+
+    #[test]
+    fn test_invalid_records() {
+        let mut record = rpc_record();
+        record.started_at = None;
+        assert!(StorageErasureRecord::try_from(record).is_err());
+
+        let mut record = rpc_record();
+        record.method = 1000;
+        assert!(StorageErasureRecord::try_from(record).is_err());
+
+        let mut record = rpc_record();
+        record.device = String::new();
+        assert!(StorageErasureRecord::try_from(record).is_err());
+    }
+
+    #[test]
+    fn test_report_complete() {
+        let mut report = MachineErasureReport::without_release(MACHINE_ID.parse().unwrap());
+        assert!(!report.complete());
+
+        report.completed_at = Some(Utc::now());
+        assert!(!report.complete());
+        report
+            .records
+            .push(StorageErasureRecord::try_from(rpc_record()).unwrap());
+        assert!(report.complete());
+
+        let mut failed = StorageErasureRecord::try_from(rpc_record()).unwrap();
+        failed.success = false;
+        failed.error = Some("drive is frozen".to_string());
+        report.records.push(failed);
+        assert!(!report.complete());
+
+        let claims = report.claims();
+        assert_eq!(claims["sub"], MACHINE_ID);
+        assert_eq!(claims["complete"], false);
+        assert_eq!(claims["records"][0]["method"], "nvme_crypto_erase");
+        assert_eq!(claims["instance_id"], serde_json::Value::Null);
+    }
+}
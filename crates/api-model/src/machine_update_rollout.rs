@@ -0,0 +1,316 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Staged rollouts of machine updates
+
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+
+use carbide_uuid::machine::MachineId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Runtime state of a [MachineUpdateRollout]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, sqlx::Type, Serialize, Deserialize)]
+#[sqlx(type_name = "machine_update_rollout_state")]
+#[sqlx(rename_all = "lowercase")]
+pub enum MachineUpdateRolloutState {
+    /// Updates are started as the current wave allows
+    Running,
+    /// No updates are started until an operator resumes the rollout
+    Paused,
+    /// Too many updated hosts became unhealthy. No updates are started until an operator
+    /// resumes the rollout.
+    Halted,
+    /// Stopped by an operator. No updates are started until an operator resumes, which starts a
+    /// new rollout with the first wave.
+    Aborted,
+    /// All waves are done, or there were no more updates to start
+    Completed,
+}
+
+impl Display for MachineUpdateRolloutState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let string = match self {
+            MachineUpdateRolloutState::Running => "running",
+            MachineUpdateRolloutState::Paused => "paused",
+            MachineUpdateRolloutState::Halted => "halted",
+            MachineUpdateRolloutState::Aborted => "aborted",
+            MachineUpdateRolloutState::Completed => "completed",
+        };
+        write!(f, "{string}")
+    }
+}
+
+impl From<MachineUpdateRolloutState> for rpc::forge::MachineUpdateRolloutState {
+    fn from(state: MachineUpdateRolloutState) -> Self {
+        match state {
+            MachineUpdateRolloutState::Running => {
+                rpc::forge::MachineUpdateRolloutState::RolloutRunning
+            }
+            MachineUpdateRolloutState::Paused => {
+                rpc::forge::MachineUpdateRolloutState::RolloutPaused
+            }
+            MachineUpdateRolloutState::Halted => {
+                rpc::forge::MachineUpdateRolloutState::RolloutHalted
+            }
+            MachineUpdateRolloutState::Aborted => {
+                rpc::forge::MachineUpdateRolloutState::RolloutAborted
+            }
+            MachineUpdateRolloutState::Completed => {
+                rpc::forge::MachineUpdateRolloutState::RolloutCompleted
+            }
+        }
+    }
+}
+
+/// A host whose update was started by a rollout
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RolloutMachine {
+    pub machine_id: MachineId,
+    pub wave: u32,
+}
+
+/// A staged rollout of machine updates. Updates are started in waves, each wave allowing updates
+/// for more hosts than the one before, with a soak period in between.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MachineUpdateRollout {
+    /// Not assigned until the rollout is stored
+    pub id: Option<i64>,
+    pub state: MachineUpdateRolloutState,
+    pub wave: u32,
+    /// When all updates of the current wave finished, which starts its soak period
+    pub wave_completed_at: Option<DateTime<Utc>>,
+    pub machines: Vec<RolloutMachine>,
+    /// Hosts which were unhealthy after their update when the rollout got halted
+    pub unhealthy_machines: Vec<MachineId>,
+    /// Hosts which are no longer considered for halting the rollout, because an operator
+    /// resumed the rollout while they were unhealthy
+    pub acknowledged_machines: Vec<MachineId>,
+    pub state_reason: Option<String>,
+    pub created: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+}
+
+/// An operator request to change the state of a rollout
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MachineUpdateRolloutAction {
+    Pause,
+    Resume,
+    Abort,
+}
+
+impl From<rpc::forge::MachineUpdateRolloutAction> for MachineUpdateRolloutAction {
+    fn from(action: rpc::forge::MachineUpdateRolloutAction) -> Self {
+        match action {
+            rpc::forge::MachineUpdateRolloutAction::RolloutPause => {
+                MachineUpdateRolloutAction::Pause
+            }
+            rpc::forge::MachineUpdateRolloutAction::RolloutResume => {
+                MachineUpdateRolloutAction::Resume
+            }
+            rpc::forge::MachineUpdateRolloutAction::RolloutAbort => {
+                MachineUpdateRolloutAction::Abort
+            }
+        }
+    }
+}
+
+impl MachineUpdateRollout {
+    /// A rollout starting with the first wave, which is not stored yet
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self {
+            id: None,
+            state: MachineUpdateRolloutState::Running,
+            wave: 0,
+            wave_completed_at: None,
+            machines: vec![],
+            unhealthy_machines: vec![],
+            acknowledged_machines: vec![],
+            state_reason: None,
+            created: now,
+            updated: now,
+        }
+    }
+
+    /// Whether the rollout still controls machine updates. Once it is completed, the next update
+    /// starts a new rollout.
+    pub fn is_active(&self) -> bool {
+        self.state != MachineUpdateRolloutState::Completed
+    }
+
+    pub fn machine_ids(&self) -> HashSet<MachineId> {
+        self.machines.iter().map(|m| m.machine_id).collect()
+    }
+
+    /// Records hosts whose update was started in the current wave
+    pub fn add_machines(&mut self, machine_ids: impl IntoIterator<Item = MachineId>) {
+        let mut known = self.machine_ids();
+        for machine_id in machine_ids {
+            if known.insert(machine_id) {
+                self.machines.push(RolloutMachine {
+                    machine_id,
+                    wave: self.wave,
+                });
+            }
+        }
+    }
+
+    /// Stops the rollout because the given hosts are unhealthy after their update
+    pub fn halt(&mut self, unhealthy_machines: Vec<MachineId>, reason: String) {
+        self.state = MachineUpdateRolloutState::Halted;
+        self.unhealthy_machines = unhealthy_machines;
+        self.state_reason = Some(reason);
+    }
+
+    /// Applies an operator action. Fails if the action is not possible in the current state.
+    pub fn apply(
+        &mut self,
+        action: MachineUpdateRolloutAction,
+        reason: Option<String>,
+    ) -> Result<(), String> {
+        use MachineUpdateRolloutState::*;
+
+        match (action, self.state) {
+            (MachineUpdateRolloutAction::Pause, Running) => {
+                self.state = Paused;
+            }
+            (MachineUpdateRolloutAction::Resume, Paused) => {
+                self.state = Running;
+            }
+            (MachineUpdateRolloutAction::Resume, Halted) => {
+                // The operator decided that the hosts which caused the halt are fine to keep going
+                let unhealthy = std::mem::take(&mut self.unhealthy_machines);
+                self.acknowledged_machines.extend(unhealthy);
+                self.state = Running;
+            }
+            (MachineUpdateRolloutAction::Abort, Running | Paused | Halted) => {
+                self.state = Aborted;
+            }
+            (action, state) => {
+                return Err(format!("Can not {action:?} a rollout which is {state}"));
+            }
+        }
+        self.state_reason = reason;
+        Ok(())
+    }
+
+    /// Converts the rollout into its RPC representation. `wave_count` and `soak_ends_at`
+    /// depend on the rollout policy.
+    pub fn into_rpc(
+        self,
+        wave_count: u32,
+        soak_ends_at: Option<DateTime<Utc>>,
+    ) -> rpc::forge::MachineUpdateRollout {
+        rpc::forge::MachineUpdateRollout {
+            id: self.id.unwrap_or_default(),
+            state: rpc::forge::MachineUpdateRolloutState::from(self.state) as i32,
+            wave: self.wave,
+            wave_count,
+            hosts: self
+                .machines
+                .into_iter()
+                .map(|m| rpc::forge::MachineUpdateRolloutHost {
+                    machine_id: Some(m.machine_id),
+                    wave: m.wave,
+                })
+                .collect(),
+            soak_ends_at: soak_ends_at.map(Into::into),
+            unhealthy_machine_ids: self.unhealthy_machines,
+            state_reason: self.state_reason,
+            created: Some(self.created.into()),
+            updated: Some(self.updated.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use carbide_uuid::machine::{MachineIdSource, MachineType};
+
+    use super::*;
+
+    fn machine_id(i: u8) -> MachineId {
+        MachineId::new(
+            MachineIdSource::ProductBoardChassisSerial,
+            [i; 32],
+            MachineType::Host,
+        )
+    }
+
+    #[test]
+    fn test_add_machines_records_wave_once() {
+        let mut rollout = MachineUpdateRollout::new(Utc::now());
+        rollout.add_machines([machine_id(0)]);
+        rollout.wave = 1;
+        rollout.add_machines([machine_id(0), machine_id(1)]);
+        assert_eq!(
+            rollout.machines,
+            vec![
+                RolloutMachine {
+                    machine_id: machine_id(0),
+                    wave: 0
+                },
+                RolloutMachine {
+                    machine_id: machine_id(1),
+                    wave: 1
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_actions() {
+        let mut rollout = MachineUpdateRollout::new(Utc::now());
+        assert!(
+            rollout
+                .apply(MachineUpdateRolloutAction::Resume, None)
+                .is_err()
+        );
+        rollout
+            .apply(
+                MachineUpdateRolloutAction::Pause,
+                Some("maintenance".into()),
+            )
+            .unwrap();
+        assert_eq!(rollout.state, MachineUpdateRolloutState::Paused);
+        assert_eq!(rollout.state_reason.as_deref(), Some("maintenance"));
+        rollout
+            .apply(MachineUpdateRolloutAction::Resume, None)
+            .unwrap();
+        assert_eq!(rollout.state, MachineUpdateRolloutState::Running);
+
+        rollout.halt(vec![machine_id(2)], "unhealthy".into());
+        assert!(rollout.is_active());
+        rollout
+            .apply(MachineUpdateRolloutAction::Resume, None)
+            .unwrap();
+        assert_eq!(rollout.state, MachineUpdateRolloutState::Running);
+        assert!(rollout.unhealthy_machines.is_empty());
+        assert_eq!(rollout.acknowledged_machines, vec![machine_id(2)]);
+
+        rollout
+            .apply(MachineUpdateRolloutAction::Abort, None)
+            .unwrap();
+        assert!(rollout.is_active());
+        assert!(
+            rollout
+                .apply(MachineUpdateRolloutAction::Resume, None)
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,651 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Maintenance windows, which restrict when disruptive work like firmware updates,
+//! DPU reprovisioning and DPU remediations may start on a host
+
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use chrono::{DateTime, Datelike, Duration, NaiveDateTime, Timelike, Utc};
+use chrono_tz::Tz;
+use rpc::errors::RpcDataConversionError;
+use serde::{Deserialize, Serialize};
+
+/// The longest a single occurrence of a window can stay open
+pub const MAX_WINDOW_DURATION_MINUTES: u32 = 7 * 24 * 60;
+
+/// Whether a window allows or prevents disruptive work
+#[derive(Debug, Copy, Clone, PartialEq, Eq, sqlx::Type, Serialize, Deserialize)]
+#[sqlx(type_name = "maintenance_window_kind")]
+#[sqlx(rename_all = "lowercase")]
+pub enum MaintenanceWindowKind {
+    /// Disruptive work may only start while one of the maintenance windows which apply to a
+    /// host is open
+    Maintenance,
+    /// No disruptive work starts while the window is open, even within a maintenance window
+    Blackout,
+}
+
+impl Display for MaintenanceWindowKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let string = match self {
+            MaintenanceWindowKind::Maintenance => "maintenance",
+            MaintenanceWindowKind::Blackout => "blackout",
+        };
+        write!(f, "{string}")
+    }
+}
+
+impl From<rpc::forge::MaintenanceWindowKind> for MaintenanceWindowKind {
+    fn from(kind: rpc::forge::MaintenanceWindowKind) -> Self {
+        match kind {
+            rpc::forge::MaintenanceWindowKind::MaintenanceWindowAllow => {
+                MaintenanceWindowKind::Maintenance
+            }
+            rpc::forge::MaintenanceWindowKind::MaintenanceWindowBlackout => {
+                MaintenanceWindowKind::Blackout
+            }
+        }
+    }
+}
+
+impl From<MaintenanceWindowKind> for rpc::forge::MaintenanceWindowKind {
+    fn from(kind: MaintenanceWindowKind) -> Self {
+        match kind {
+            MaintenanceWindowKind::Maintenance => {
+                rpc::forge::MaintenanceWindowKind::MaintenanceWindowAllow
+            }
+            MaintenanceWindowKind::Blackout => {
+                rpc::forge::MaintenanceWindowKind::MaintenanceWindowBlackout
+            }
+        }
+    }
+}
+
+/// The hosts a window applies to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MaintenanceWindowScope {
+    Site,
+    Rack(String),
+    Tenant(String),
+}
+
+impl MaintenanceWindowScope {
+    fn applies_to(&self, host: &HostMaintenanceScope) -> bool {
+        match self {
+            MaintenanceWindowScope::Site => true,
+            MaintenanceWindowScope::Rack(rack_id) => host.rack_id.as_ref() == Some(rack_id),
+            MaintenanceWindowScope::Tenant(tenant) => {
+                host.tenant_organization_id.as_ref() == Some(tenant)
+            }
+        }
+    }
+}
+
+impl Display for MaintenanceWindowScope {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MaintenanceWindowScope::Site => write!(f, "site"),
+            MaintenanceWindowScope::Rack(rack_id) => write!(f, "rack {rack_id}"),
+            MaintenanceWindowScope::Tenant(tenant) => write!(f, "tenant {tenant}"),
+        }
+    }
+}
+
+/// What is needed to find the windows which apply to a host
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HostMaintenanceScope {
+    pub rack_id: Option<String>,
+    /// The tenant of the instance on the host, if any
+    pub tenant_organization_id: Option<String>,
+}
+
+/// A cron expression with the fields minute, hour, day of month, month and day of week.
+///
+/// Fields support `*`, single values, ranges (`1-5`), lists (`1,3,5`) and steps (`*/15`, `8-18/2`).
+/// Like with cron, a time matches if either the day of month or the day of week matches, if
+/// both of them are restricted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    expression: String,
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    day_of_month_restricted: bool,
+    day_of_week_restricted: bool,
+}
+
+impl CronSchedule {
+    /// Whether the schedule fires at the minute of the given time
+    pub fn matches(&self, time: NaiveDateTime) -> bool {
+        let bit = |set: u64, value: u32| set & (1 << value) != 0;
+
+        if !bit(self.minutes, time.minute())
+            || !bit(self.hours, time.hour())
+            || !bit(self.months, time.month())
+        {
+            return false;
+        }
+
+        let day_of_month = bit(self.days_of_month, time.day());
+        let day_of_week = bit(self.days_of_week, time.weekday().num_days_from_sunday());
+        match (self.day_of_month_restricted, self.day_of_week_restricted) {
+            (true, true) => day_of_month || day_of_week,
+            _ => day_of_month && day_of_week,
+        }
+    }
+}
+
+impl FromStr for CronSchedule {
+    type Err = String;
+
+    fn from_str(expression: &str) -> Result<Self, Self::Err> {
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        let [minute, hour, day_of_month, month, day_of_week] = fields[..] else {
+            return Err(format!(
+                "expected 5 fields in cron expression \"{expression}\", found {}",
+                fields.len()
+            ));
+        };
+
+        let mut days_of_week = parse_cron_field(day_of_week, 0, 7)?;
+        // Both 0 and 7 are Sunday
+        if days_of_week & (1 << 7) != 0 {
+            days_of_week |= 1;
+        }
+
+        Ok(CronSchedule {
+            expression: fields.join(" "),
+            minutes: parse_cron_field(minute, 0, 59)?,
+            hours: parse_cron_field(hour, 0, 23)?,
+            days_of_month: parse_cron_field(day_of_month, 1, 31)?,
+            months: parse_cron_field(month, 1, 12)?,
+            days_of_week,
+            day_of_month_restricted: day_of_month != "*",
+            day_of_week_restricted: day_of_week != "*",
+        })
+    }
+}
+
+impl Display for CronSchedule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.expression)
+    }
+}
+
+/// Parses a single cron field into a bit set of the values it matches
+fn parse_cron_field(field: &str, min: u32, max: u32) -> Result<u64, String> {
+    let parse_value = |value: &str| {
+        value
+            .parse::<u32>()
+            .ok()
+            .filter(|value| (min..=max).contains(value))
+            .ok_or_else(|| format!("invalid value \"{value}\" in cron field \"{field}\""))
+    };
+
+    let mut set = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step = step
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|step| *step > 0)
+                    .ok_or_else(|| format!("invalid step \"{step}\" in cron field \"{field}\""))?;
+                (range, Some(step))
+            }
+            None => (part, None),
+        };
+
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (parse_value(start)?, parse_value(end)?)
+        } else {
+            let start = parse_value(range)?;
+            // `5/15` means every 15 starting at 5
+            (start, if step.is_some() { max } else { start })
+        };
+        if start > end {
+            return Err(format!(
+                "invalid range \"{range}\" in cron field \"{field}\""
+            ));
+        }
+
+        for value in (start..=end).step_by(step.unwrap_or(1) as usize) {
+            set |= 1 << value;
+        }
+    }
+    Ok(set)
+}
+
+/// A window in which disruptive work may, or for blackouts may not, start on the hosts in its
+/// scope. A window either recurs according to its schedule, or is open once from `starts_at`
+/// until `ends_at`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MaintenanceWindow {
+    pub name: String,
+    pub kind: MaintenanceWindowKind,
+    pub scope: MaintenanceWindowScope,
+    /// When the window opens. Not set for one-off windows.
+    pub schedule: Option<CronSchedule>,
+    /// How long the window stays open each time the schedule fires
+    pub duration: Duration,
+    /// The timezone the schedule is evaluated in
+    pub timezone: Tz,
+    /// The window is never open before this time
+    pub starts_at: Option<DateTime<Utc>>,
+    /// The window is never open from this time on
+    pub ends_at: Option<DateTime<Utc>>,
+    pub description: String,
+    pub created: DateTime<Utc>,
+}
+
+impl MaintenanceWindow {
+    /// Whether the window is open at the given time
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        if self.starts_at.is_some_and(|starts_at| now < starts_at)
+            || self.ends_at.is_some_and(|ends_at| now >= ends_at)
+        {
+            return false;
+        }
+        let Some(schedule) = self.schedule.as_ref() else {
+            return true;
+        };
+
+        // Look for the schedule firing within the duration of the window before now
+        let local = now.with_timezone(&self.timezone).naive_local();
+        let Some(minute) = local.with_second(0).and_then(|t| t.with_nanosecond(0)) else {
+            return false;
+        };
+        let into_minute = local - minute;
+        let mut opened_at = minute;
+        while (minute - opened_at) + into_minute < self.duration {
+            if schedule.matches(opened_at) {
+                return true;
+            }
+            opened_at -= Duration::minutes(1);
+        }
+        false
+    }
+
+    pub fn into_rpc(self, now: DateTime<Utc>) -> rpc::forge::MaintenanceWindow {
+        let active = self.is_active(now);
+        let (scope, scope_id) = match self.scope {
+            MaintenanceWindowScope::Site => (
+                rpc::forge::MaintenanceWindowScope::MaintenanceScopeSite,
+                None,
+            ),
+            MaintenanceWindowScope::Rack(rack_id) => (
+                rpc::forge::MaintenanceWindowScope::MaintenanceScopeRack,
+                Some(rack_id),
+            ),
+            MaintenanceWindowScope::Tenant(tenant) => (
+                rpc::forge::MaintenanceWindowScope::MaintenanceScopeTenant,
+                Some(tenant),
+            ),
+        };
+        rpc::forge::MaintenanceWindow {
+            name: self.name,
+            kind: rpc::forge::MaintenanceWindowKind::from(self.kind) as i32,
+            scope: scope as i32,
+            scope_id,
+            schedule: self.schedule.map(|schedule| schedule.to_string()),
+            duration_minutes: self.duration.num_minutes() as u32,
+            timezone: Some(self.timezone.name().to_string()),
+            starts_at: self.starts_at.map(Into::into),
+            ends_at: self.ends_at.map(Into::into),
+            description: self.description,
+            active,
+            created: Some(self.created.into()),
+        }
+    }
+}
+
+impl TryFrom<rpc::forge::MaintenanceWindow> for MaintenanceWindow {
+    type Error = RpcDataConversionError;
+
+    fn try_from(window: rpc::forge::MaintenanceWindow) -> Result<Self, Self::Error> {
+        if window.name.trim().is_empty() {
+            return Err(RpcDataConversionError::InvalidValue(
+                "name".to_string(),
+                window.name,
+            ));
+        }
+
+        let kind = MaintenanceWindowKind::from(window.kind());
+        let scope = match (window.scope(), window.scope_id) {
+            (rpc::forge::MaintenanceWindowScope::MaintenanceScopeSite, None) => {
+                MaintenanceWindowScope::Site
+            }
+            (rpc::forge::MaintenanceWindowScope::MaintenanceScopeRack, Some(rack_id)) => {
+                MaintenanceWindowScope::Rack(rack_id)
+            }
+            (rpc::forge::MaintenanceWindowScope::MaintenanceScopeTenant, Some(tenant)) => {
+                MaintenanceWindowScope::Tenant(tenant)
+            }
+            (scope, scope_id) => {
+                return Err(RpcDataConversionError::InvalidValue(
+                    "scope_id".to_string(),
+                    format!("{scope_id:?} for {scope:?}"),
+                ));
+            }
+        };
+
+        let schedule = window
+            .schedule
+            .map(|schedule| {
+                schedule
+                    .parse::<CronSchedule>()
+                    .map_err(|e| RpcDataConversionError::InvalidValue("schedule".to_string(), e))
+            })
+            .transpose()?;
+        let timezone = match window.timezone {
+            Some(timezone) => timezone.parse::<Tz>().map_err(|_| {
+                RpcDataConversionError::InvalidValue("timezone".to_string(), timezone)
+            })?,
+            None => Tz::UTC,
+        };
+        let timestamp = |timestamp: Option<rpc::Timestamp>| {
+            timestamp
+                .map(|timestamp| {
+                    DateTime::<Utc>::try_from(timestamp).map_err(|_| {
+                        RpcDataConversionError::InvalidTimestamp(timestamp.to_string())
+                    })
+                })
+                .transpose()
+        };
+        let starts_at = timestamp(window.starts_at)?;
+        let ends_at = timestamp(window.ends_at)?;
+
+        if schedule.is_some() {
+            if window.duration_minutes == 0 || window.duration_minutes > MAX_WINDOW_DURATION_MINUTES
+            {
+                return Err(RpcDataConversionError::InvalidValue(
+                    "duration_minutes".to_string(),
+                    window.duration_minutes.to_string(),
+                ));
+            }
+        } else if starts_at.is_none() || ends_at.is_none() {
+            return Err(RpcDataConversionError::MissingArgument(
+                "starts_at and ends_at are required for windows without schedule",
+            ));
+        }
+        if let (Some(starts_at), Some(ends_at)) = (starts_at, ends_at)
+            && starts_at >= ends_at
+        {
+            return Err(RpcDataConversionError::InvalidValue(
+                "ends_at".to_string(),
+                ends_at.to_string(),
+            ));
+        }
+
+        Ok(MaintenanceWindow {
+            name: window.name,
+            kind,
+            scope,
+            schedule,
+            duration: Duration::minutes(window.duration_minutes.into()),
+            timezone,
+            starts_at,
+            ends_at,
+            description: window.description,
+            created: Utc::now(),
+        })
+    }
+}
+
+/// The maintenance windows of a site, evaluated at a point in time
+#[derive(Debug, Clone, Default)]
+pub struct MaintenanceSchedule {
+    /// Each window, and whether it is open
+    windows: Vec<(MaintenanceWindow, bool)>,
+}
+
+impl MaintenanceSchedule {
+    pub fn new(windows: Vec<MaintenanceWindow>, now: DateTime<Utc>) -> Self {
+        Self {
+            windows: windows
+                .into_iter()
+                .map(|window| {
+                    let active = window.is_active(now);
+                    (window, active)
+                })
+                .collect(),
+        }
+    }
+
+    /// Whether no windows are defined, so that disruptive work can start anytime
+    pub fn is_unrestricted(&self) -> bool {
+        self.windows.is_empty()
+    }
+
+    /// Whether any window is scoped to racks
+    pub fn has_rack_windows(&self) -> bool {
+        self.windows
+            .iter()
+            .any(|(window, _)| matches!(window.scope, MaintenanceWindowScope::Rack(_)))
+    }
+
+    /// Whether any window is scoped to tenants
+    pub fn has_tenant_windows(&self) -> bool {
+        self.windows
+            .iter()
+            .any(|(window, _)| matches!(window.scope, MaintenanceWindowScope::Tenant(_)))
+    }
+
+    /// Whether disruptive work may start on a host now. This is the case if no blackout applies
+    /// to the host, and either no maintenance windows apply to it or one of them is open.
+    pub fn allows(&self, host: &HostMaintenanceScope) -> bool {
+        let applicable = self
+            .windows
+            .iter()
+            .filter(|(window, _)| window.scope.applies_to(host));
+        let mut maintenance_windows = 0;
+        let mut in_maintenance_window = false;
+        for (window, active) in applicable {
+            match window.kind {
+                MaintenanceWindowKind::Blackout if *active => return false,
+                MaintenanceWindowKind::Blackout => {}
+                MaintenanceWindowKind::Maintenance => {
+                    maintenance_windows += 1;
+                    in_maintenance_window |= *active;
+                }
+            }
+        }
+        maintenance_windows == 0 || in_maintenance_window
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn time(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    fn window(kind: MaintenanceWindowKind, scope: MaintenanceWindowScope) -> MaintenanceWindow {
+        MaintenanceWindow {
+            name: "test".to_string(),
+            kind,
+            scope,
+            // Saturdays and Sundays from 02:00 for 4 hours
+            schedule: Some("0 2 * * 6,0".parse().unwrap()),
+            duration: Duration::hours(4),
+            timezone: Tz::UTC,
+            starts_at: None,
+            ends_at: None,
+            description: String::new(),
+            created: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_parse_cron_schedule() {
+        let schedule: CronSchedule = "*/15 8-18/2 1,15 * 1-5".parse().unwrap();
+        assert_eq!(schedule.to_string(), "*/15 8-18/2 1,15 * 1-5");
+        // 2026-03-02 is a Monday
+        let monday = Utc
+            .with_ymd_and_hms(2026, 3, 2, 10, 45, 0)
+            .unwrap()
+            .naive_utc();
+        assert!(schedule.matches(monday));
+        assert!(!schedule.matches(monday.with_minute(44).unwrap()));
+        assert!(!schedule.matches(monday.with_hour(9).unwrap()));
+        // Day of month or day of week
+        let sunday_the_first = Utc
+            .with_ymd_and_hms(2026, 3, 1, 10, 0, 0)
+            .unwrap()
+            .naive_utc();
+        assert!(schedule.matches(sunday_the_first));
+        let sunday = Utc
+            .with_ymd_and_hms(2026, 3, 8, 10, 0, 0)
+            .unwrap()
+            .naive_utc();
+        assert!(!schedule.matches(sunday));
+
+        // 7 is Sunday as well
+        let schedule: CronSchedule = "0 0 * * 7".parse().unwrap();
+        assert!(schedule.matches(sunday.with_hour(0).unwrap()));
+
+        for invalid in [
+            "* * * *",
+            "60 * * * *",
+            "* * 0 * *",
+            "*/0 * * * *",
+            "5-1 * * * *",
+        ] {
+            assert!(invalid.parse::<CronSchedule>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_recurring_window_is_active() {
+        let mut window = window(
+            MaintenanceWindowKind::Maintenance,
+            MaintenanceWindowScope::Site,
+        );
+        // 2026-03-07 is a Saturday
+        assert!(!window.is_active(time("2026-03-07T01:59:59Z")));
+        assert!(window.is_active(time("2026-03-07T02:00:00Z")));
+        assert!(window.is_active(time("2026-03-07T05:59:59Z")));
+        assert!(!window.is_active(time("2026-03-07T06:00:00Z")));
+        assert!(!window.is_active(time("2026-03-09T03:00:00Z")));
+
+        // The schedule is evaluated in the timezone of the window
+        window.timezone = "Europe/Berlin".parse().unwrap();
+        assert!(window.is_active(time("2026-03-07T01:30:00Z")));
+        assert!(!window.is_active(time("2026-03-07T05:30:00Z")));
+
+        window.ends_at = Some(time("2026-03-07T01:00:00Z"));
+        assert!(!window.is_active(time("2026-03-07T01:30:00Z")));
+    }
+
+    #[test]
+    fn test_schedule_allows() {
+        let saturday = time("2026-03-07T03:00:00Z");
+        let monday = time("2026-03-09T03:00:00Z");
+        let rack_a = HostMaintenanceScope {
+            rack_id: Some("rack-a".to_string()),
+            tenant_organization_id: None,
+        };
+        let rack_b = HostMaintenanceScope {
+            rack_id: Some("rack-b".to_string()),
+            tenant_organization_id: Some("tenant".to_string()),
+        };
+
+        assert!(MaintenanceSchedule::new(vec![], monday).allows(&rack_a));
+
+        let windows = vec![window(
+            MaintenanceWindowKind::Maintenance,
+            MaintenanceWindowScope::Rack("rack-a".to_string()),
+        )];
+        // Windows only restrict the hosts they apply to
+        assert!(!MaintenanceSchedule::new(windows.clone(), monday).allows(&rack_a));
+        assert!(MaintenanceSchedule::new(windows.clone(), monday).allows(&rack_b));
+        assert!(MaintenanceSchedule::new(windows, saturday).allows(&rack_a));
+
+        // A tenant blackout wins over an open site-wide window
+        let mut blackout = window(
+            MaintenanceWindowKind::Blackout,
+            MaintenanceWindowScope::Tenant("tenant".to_string()),
+        );
+        blackout.schedule = None;
+        blackout.starts_at = Some(time("2026-03-07T00:00:00Z"));
+        blackout.ends_at = Some(time("2026-03-08T00:00:00Z"));
+        let windows = vec![
+            window(
+                MaintenanceWindowKind::Maintenance,
+                MaintenanceWindowScope::Site,
+            ),
+            blackout,
+        ];
+        let schedule = MaintenanceSchedule::new(windows, saturday);
+        assert!(schedule.allows(&rack_a));
+        assert!(!schedule.allows(&rack_b));
+        assert!(schedule.has_tenant_windows());
+        assert!(!schedule.has_rack_windows());
+    }
+
+    #[test]
+    fn test_from_rpc() {
+        let request = rpc::forge::MaintenanceWindow {
+            name: "weekend".to_string(),
+            kind: rpc::forge::MaintenanceWindowKind::MaintenanceWindowAllow as i32,
+            scope: rpc::forge::MaintenanceWindowScope::MaintenanceScopeRack as i32,
+            scope_id: Some("rack-a".to_string()),
+            schedule: Some("0 2 * * 6".to_string()),
+            duration_minutes: 240,
+            timezone: Some("America/Los_Angeles".to_string()),
+            ..Default::default()
+        };
+        let window = MaintenanceWindow::try_from(request.clone()).unwrap();
+        assert_eq!(
+            window.scope,
+            MaintenanceWindowScope::Rack("rack-a".to_string())
+        );
+        assert_eq!(window.duration, Duration::hours(4));
+        assert_eq!(window.timezone, chrono_tz::America::Los_Angeles);
+
+        for invalid in [
+            rpc::forge::MaintenanceWindow {
+                scope_id: None,
+                ..request.clone()
+            },
+            rpc::forge::MaintenanceWindow {
+                timezone: Some("Mars/Olympus_Mons".to_string()),
+                ..request.clone()
+            },
+            rpc::forge::MaintenanceWindow {
+                duration_minutes: 0,
+                ..request.clone()
+            },
+            rpc::forge::MaintenanceWindow {
+                schedule: None,
+                ..request.clone()
+            },
+        ] {
+            assert!(MaintenanceWindow::try_from(invalid).is_err());
+        }
+    }
+}
@@ -0,0 +1,789 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Offline evaluation of network security group rules, to check the effect of a rule set
+//! before it propagates to the DPUs.
+//!
+//! Rules are evaluated the way the DPU applies them: the rules of a direction and IP version
+//! are ordered by priority, the first matching rule decides, and traffic no rule matches is
+//! denied. Return traffic which is permitted by connection tracking for stateful egress rules
+//! is not simulated. VPC and VPC prefix references are evaluated with the prefixes they expand to.
+
+use std::fmt;
+use std::net::IpAddr;
+
+use ::rpc::errors::RpcDataConversionError;
+use ::rpc::forge as rpc;
+use ipnetwork::IpNetwork;
+
+use super::{
+    NetworkSecurityGroupRule, NetworkSecurityGroupRuleAction, NetworkSecurityGroupRuleDirection,
+    NetworkSecurityGroupRuleNet, NetworkSecurityGroupRuleNetPrefixes,
+    NetworkSecurityGroupRuleProtocol,
+};
+
+/* ********************************** */
+/*      NetworkSecurityGroupFlow      */
+/* ********************************** */
+
+/// NetworkSecurityGroupFlow describes a single flow of
+/// traffic to evaluate against a set of rules.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetworkSecurityGroupFlow {
+    pub direction: NetworkSecurityGroupRuleDirection,
+    pub protocol: NetworkSecurityGroupRuleProtocol,
+    pub src_ip: IpAddr,
+    pub dst_ip: IpAddr,
+    pub src_port: Option<u32>,
+    pub dst_port: Option<u32>,
+}
+
+impl TryFrom<rpc::NetworkSecurityGroupFlow> for NetworkSecurityGroupFlow {
+    type Error = RpcDataConversionError;
+
+    fn try_from(flow: rpc::NetworkSecurityGroupFlow) -> Result<Self, Self::Error> {
+        let protocol: NetworkSecurityGroupRuleProtocol = flow.protocol().try_into()?;
+        if protocol == NetworkSecurityGroupRuleProtocol::Any {
+            return Err(RpcDataConversionError::InvalidValue(
+                "protocol".to_string(),
+                "a flow must have a specific protocol".to_string(),
+            ));
+        }
+
+        let src_ip = flow
+            .src_ip
+            .parse::<IpAddr>()
+            .map_err(|_| RpcDataConversionError::InvalidIpAddress(flow.src_ip.clone()))?;
+        let dst_ip = flow
+            .dst_ip
+            .parse::<IpAddr>()
+            .map_err(|_| RpcDataConversionError::InvalidIpAddress(flow.dst_ip.clone()))?;
+        if src_ip.is_ipv6() != dst_ip.is_ipv6() {
+            return Err(RpcDataConversionError::InvalidValue(
+                "dst_ip".to_string(),
+                "IP version of source and destination do not match".to_string(),
+            ));
+        }
+
+        let has_ports = matches!(
+            protocol,
+            NetworkSecurityGroupRuleProtocol::Tcp | NetworkSecurityGroupRuleProtocol::Udp
+        );
+        if !has_ports && (flow.src_port.is_some() || flow.dst_port.is_some()) {
+            return Err(RpcDataConversionError::InvalidValue(
+                "protocol".to_string(),
+                format!("ports cannot be specified for `{protocol}` flows"),
+            ));
+        }
+
+        Ok(NetworkSecurityGroupFlow {
+            direction: flow.direction().try_into()?,
+            protocol,
+            src_ip,
+            dst_ip,
+            src_port: flow.src_port,
+            dst_port: flow.dst_port,
+        })
+    }
+}
+
+impl fmt::Display for NetworkSecurityGroupFlow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let endpoint = |ip: &IpAddr, port: Option<u32>| match port {
+            Some(port) if ip.is_ipv6() => format!("[{ip}]:{port}"),
+            Some(port) => format!("{ip}:{port}"),
+            None => ip.to_string(),
+        };
+        write!(
+            f,
+            "{} {} {} -> {}",
+            self.direction,
+            self.protocol,
+            endpoint(&self.src_ip, self.src_port),
+            endpoint(&self.dst_ip, self.dst_port)
+        )
+    }
+}
+
+/// The verdict for a flow and the rule which decided it.
+/// `rule_id` is not set if no rule matched and the flow is denied by default.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetworkSecurityGroupFlowVerdict {
+    pub action: NetworkSecurityGroupRuleAction,
+    pub rule_id: Option<String>,
+}
+
+/// Returns the rules which apply to traffic of a direction and IP version,
+/// in the order in which they are evaluated.
+fn ordered_rules(
+    rules: &[NetworkSecurityGroupRule],
+    direction: &NetworkSecurityGroupRuleDirection,
+    ipv6: bool,
+) -> Vec<&NetworkSecurityGroupRule> {
+    let mut ordered: Vec<&NetworkSecurityGroupRule> = rules
+        .iter()
+        .filter(|rule| rule.direction == *direction && rule.ipv6 == ipv6)
+        .collect();
+    // Stable, like the sort applied by the DPU agent
+    ordered.sort_by_key(|rule| rule.priority);
+    ordered
+}
+
+/// Evaluates a flow against a set of rules.
+pub fn simulate_flow(
+    rules: &[NetworkSecurityGroupRule],
+    prefixes: &NetworkSecurityGroupRuleNetPrefixes,
+    flow: &NetworkSecurityGroupFlow,
+) -> NetworkSecurityGroupFlowVerdict {
+    ordered_rules(rules, &flow.direction, flow.src_ip.is_ipv6())
+        .into_iter()
+        .find(|rule| rule_matches_flow(rule, prefixes, flow))
+        .map(|rule| NetworkSecurityGroupFlowVerdict {
+            action: rule.action.clone(),
+            rule_id: rule.id.clone(),
+        })
+        .unwrap_or(NetworkSecurityGroupFlowVerdict {
+            action: NetworkSecurityGroupRuleAction::Deny,
+            rule_id: None,
+        })
+}
+
+fn rule_matches_flow(
+    rule: &NetworkSecurityGroupRule,
+    prefixes: &NetworkSecurityGroupRuleNetPrefixes,
+    flow: &NetworkSecurityGroupFlow,
+) -> bool {
+    let port_matches = |start: Option<u32>, end: Option<u32>, port: Option<u32>| match (start, end)
+    {
+        (Some(start), Some(end)) => port.is_some_and(|port| (start..=end).contains(&port)),
+        _ => true,
+    };
+
+    (rule.protocol == NetworkSecurityGroupRuleProtocol::Any || rule.protocol == flow.protocol)
+        && net_contains_ip(prefixes, &rule.src_net, flow.src_ip)
+        && net_contains_ip(prefixes, &rule.dst_net, flow.dst_ip)
+        && port_matches(rule.src_port_start, rule.src_port_end, flow.src_port)
+        && port_matches(rule.dst_port_start, rule.dst_port_end, flow.dst_port)
+}
+
+fn net_contains_ip(
+    prefixes: &NetworkSecurityGroupRuleNetPrefixes,
+    net: &NetworkSecurityGroupRuleNet,
+    ip: IpAddr,
+) -> bool {
+    prefixes
+        .resolve(net, ip.is_ipv6())
+        .iter()
+        .any(|prefix| prefix.contains(ip))
+}
+
+/* ********************************** */
+/*        Rule conflict analysis      */
+/* ********************************** */
+
+/// The kind of problem found with a rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NetworkSecurityGroupRuleFindingType {
+    /// The rule never matches, because a rule evaluated before it
+    /// with a different action matches all of its traffic.
+    Shadowed,
+    /// Removing the rule doesn't change any verdict.
+    Redundant,
+    /// The rule overlaps a rule of the same priority with a different action,
+    /// so that the verdict depends on the order the rules were defined in.
+    Contradictory,
+}
+
+impl fmt::Display for NetworkSecurityGroupRuleFindingType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NetworkSecurityGroupRuleFindingType::Shadowed => write!(f, "SHADOWED"),
+            NetworkSecurityGroupRuleFindingType::Redundant => write!(f, "REDUNDANT"),
+            NetworkSecurityGroupRuleFindingType::Contradictory => write!(f, "CONTRADICTORY"),
+        }
+    }
+}
+
+impl From<NetworkSecurityGroupRuleFindingType> for rpc::NetworkSecurityGroupRuleFindingType {
+    fn from(t: NetworkSecurityGroupRuleFindingType) -> Self {
+        match t {
+            NetworkSecurityGroupRuleFindingType::Shadowed => {
+                rpc::NetworkSecurityGroupRuleFindingType::NsgFindingShadowed
+            }
+            NetworkSecurityGroupRuleFindingType::Redundant => {
+                rpc::NetworkSecurityGroupRuleFindingType::NsgFindingRedundant
+            }
+            NetworkSecurityGroupRuleFindingType::Contradictory => {
+                rpc::NetworkSecurityGroupRuleFindingType::NsgFindingContradictory
+            }
+        }
+    }
+}
+
+/// NetworkSecurityGroupRuleFinding describes a problem with a rule
+/// found by [analyze_rules].
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetworkSecurityGroupRuleFinding {
+    pub finding_type: NetworkSecurityGroupRuleFindingType,
+    pub rule_id: String,
+    /// The rule which causes the finding, if any
+    pub related_rule_id: Option<String>,
+    pub details: String,
+}
+
+impl From<NetworkSecurityGroupRuleFinding> for rpc::NetworkSecurityGroupRuleFinding {
+    fn from(finding: NetworkSecurityGroupRuleFinding) -> Self {
+        rpc::NetworkSecurityGroupRuleFinding {
+            r#type: rpc::NetworkSecurityGroupRuleFindingType::from(finding.finding_type).into(),
+            rule_id: finding.rule_id,
+            related_rule_id: finding.related_rule_id,
+            details: finding.details,
+        }
+    }
+}
+
+/// Detects shadowed, redundant and contradictory rules.
+///
+/// A rule is reported at most once, with the first problem found for it.
+pub fn analyze_rules(
+    rules: &[NetworkSecurityGroupRule],
+    prefixes: &NetworkSecurityGroupRuleNetPrefixes,
+) -> Vec<NetworkSecurityGroupRuleFinding> {
+    let mut findings = vec![];
+
+    for direction in [
+        NetworkSecurityGroupRuleDirection::Ingress,
+        NetworkSecurityGroupRuleDirection::Egress,
+    ] {
+        for ipv6 in [false, true] {
+            let ordered = ordered_rules(rules, &direction, ipv6);
+            for (index, rule) in ordered.iter().enumerate() {
+                if let Some(finding) =
+                    analyze_rule(rule, &ordered[..index], &ordered[index + 1..], prefixes)
+                {
+                    findings.push(finding);
+                }
+            }
+        }
+    }
+
+    findings
+}
+
+/// Checks a rule against the rules evaluated before and after it
+fn analyze_rule(
+    rule: &NetworkSecurityGroupRule,
+    before: &[&NetworkSecurityGroupRule],
+    after: &[&NetworkSecurityGroupRule],
+    prefixes: &NetworkSecurityGroupRuleNetPrefixes,
+) -> Option<NetworkSecurityGroupRuleFinding> {
+    let finding = |finding_type, related: Option<&NetworkSecurityGroupRule>, details| {
+        NetworkSecurityGroupRuleFinding {
+            finding_type,
+            rule_id: rule_name(rule),
+            related_rule_id: related.map(rule_name),
+            details,
+        }
+    };
+
+    // Only the first rule which covers this one matters, since it takes all of its traffic
+    if let Some(covering) = before
+        .iter()
+        .find(|other| rule_covers(prefixes, other, rule))
+    {
+        return Some(if covering.action == rule.action {
+            finding(
+                NetworkSecurityGroupRuleFindingType::Redundant,
+                Some(covering),
+                format!(
+                    "all traffic matching the rule is already {} by rule `{}`",
+                    action_verb(&rule.action),
+                    rule_name(covering)
+                ),
+            )
+        } else {
+            finding(
+                NetworkSecurityGroupRuleFindingType::Shadowed,
+                Some(covering),
+                format!(
+                    "the rule never matches because rule `{}` {} all of its traffic first",
+                    rule_name(covering),
+                    action_verb(&covering.action)
+                ),
+            )
+        });
+    }
+
+    if let Some(conflicting) = before.iter().chain(after.iter()).find(|other| {
+        other.priority == rule.priority
+            && other.action != rule.action
+            && rules_overlap(prefixes, rule, other)
+    }) {
+        return Some(finding(
+            NetworkSecurityGroupRuleFindingType::Contradictory,
+            Some(conflicting),
+            format!(
+                "the rule overlaps rule `{}` of the same priority {} with a different action",
+                rule_name(conflicting),
+                rule.priority
+            ),
+        ));
+    }
+
+    // A deny rule which no later permit rule overlaps only denies what is denied by default
+    if rule.action == NetworkSecurityGroupRuleAction::Deny
+        && !after.iter().any(|other| {
+            other.action == NetworkSecurityGroupRuleAction::Permit
+                && rules_overlap(prefixes, rule, other)
+        })
+    {
+        return Some(finding(
+            NetworkSecurityGroupRuleFindingType::Redundant,
+            None,
+            "the rule only denies traffic which no other rule permits, and which is denied by default"
+                .to_string(),
+        ));
+    }
+
+    None
+}
+
+fn rule_name(rule: &NetworkSecurityGroupRule) -> String {
+    rule.id.clone().unwrap_or_default()
+}
+
+fn action_verb(action: &NetworkSecurityGroupRuleAction) -> &'static str {
+    match action {
+        NetworkSecurityGroupRuleAction::Deny => "denied",
+        NetworkSecurityGroupRuleAction::Permit => "permitted",
+    }
+}
+
+/// Whether `outer` matches all the traffic `inner` matches
+fn rule_covers(
+    prefixes: &NetworkSecurityGroupRuleNetPrefixes,
+    outer: &NetworkSecurityGroupRule,
+    inner: &NetworkSecurityGroupRule,
+) -> bool {
+    (outer.protocol == NetworkSecurityGroupRuleProtocol::Any || outer.protocol == inner.protocol)
+        && net_covers(prefixes, &outer.src_net, &inner.src_net, inner.ipv6)
+        && net_covers(prefixes, &outer.dst_net, &inner.dst_net, inner.ipv6)
+        && port_range_covers(
+            (outer.src_port_start, outer.src_port_end),
+            (inner.src_port_start, inner.src_port_end),
+        )
+        && port_range_covers(
+            (outer.dst_port_start, outer.dst_port_end),
+            (inner.dst_port_start, inner.dst_port_end),
+        )
+}
+
+/// Whether any traffic is matched by both rules
+fn rules_overlap(
+    prefixes: &NetworkSecurityGroupRuleNetPrefixes,
+    a: &NetworkSecurityGroupRule,
+    b: &NetworkSecurityGroupRule,
+) -> bool {
+    (a.protocol == NetworkSecurityGroupRuleProtocol::Any
+        || b.protocol == NetworkSecurityGroupRuleProtocol::Any
+        || a.protocol == b.protocol)
+        && nets_overlap(prefixes, &a.src_net, &b.src_net, a.ipv6)
+        && nets_overlap(prefixes, &a.dst_net, &b.dst_net, a.ipv6)
+        && port_ranges_overlap(
+            (a.src_port_start, a.src_port_end),
+            (b.src_port_start, b.src_port_end),
+        )
+        && port_ranges_overlap(
+            (a.dst_port_start, a.dst_port_end),
+            (b.dst_port_start, b.dst_port_end),
+        )
+}
+
+/// Whether every prefix `inner` expands to is part of a prefix `outer` expands to
+fn net_covers(
+    prefixes: &NetworkSecurityGroupRuleNetPrefixes,
+    outer: &NetworkSecurityGroupRuleNet,
+    inner: &NetworkSecurityGroupRuleNet,
+    ipv6: bool,
+) -> bool {
+    if outer == inner {
+        return true;
+    }
+
+    let outer = prefixes.resolve(outer, ipv6);
+    prefixes
+        .resolve(inner, ipv6)
+        .iter()
+        .all(|inner| outer.iter().any(|outer| prefix_covers(outer, inner)))
+}
+
+fn nets_overlap(
+    prefixes: &NetworkSecurityGroupRuleNetPrefixes,
+    a: &NetworkSecurityGroupRuleNet,
+    b: &NetworkSecurityGroupRuleNet,
+    ipv6: bool,
+) -> bool {
+    if a == b {
+        return true;
+    }
+
+    let b = prefixes.resolve(b, ipv6);
+    // Two prefixes overlap exactly if one contains the other
+    prefixes
+        .resolve(a, ipv6)
+        .iter()
+        .any(|a| b.iter().any(|b| prefix_covers(a, b) || prefix_covers(b, a)))
+}
+
+fn prefix_covers(outer: &IpNetwork, inner: &IpNetwork) -> bool {
+    outer.is_ipv6() == inner.is_ipv6()
+        && outer.prefix() <= inner.prefix()
+        && outer.contains(inner.network())
+}
+
+/// Port ranges are given as (start, end). A missing range matches all ports.
+fn port_range_covers(outer: (Option<u32>, Option<u32>), inner: (Option<u32>, Option<u32>)) -> bool {
+    match (outer, inner) {
+        ((Some(outer_start), Some(outer_end)), (Some(inner_start), Some(inner_end))) => {
+            outer_start <= inner_start && inner_end <= outer_end
+        }
+        ((Some(_), Some(_)), _) => false,
+        _ => true,
+    }
+}
+
+fn port_ranges_overlap(a: (Option<u32>, Option<u32>), b: (Option<u32>, Option<u32>)) -> bool {
+    match (a, b) {
+        ((Some(a_start), Some(a_end)), (Some(b_start), Some(b_end))) => {
+            a_start <= b_end && b_start <= a_end
+        }
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use carbide_uuid::vpc::{VpcId, VpcPrefixId};
+
+    use super::NetworkSecurityGroupRuleAction::{Deny, Permit};
+    use super::NetworkSecurityGroupRuleProtocol::{Any, Icmp, Tcp, Udp};
+    use super::*;
+
+    fn rule(
+        id: &str,
+        priority: u32,
+        action: NetworkSecurityGroupRuleAction,
+        protocol: NetworkSecurityGroupRuleProtocol,
+        src: &str,
+        dst: &str,
+        dst_ports: Option<(u32, u32)>,
+    ) -> NetworkSecurityGroupRule {
+        NetworkSecurityGroupRule {
+            id: Some(id.to_string()),
+            src_net: NetworkSecurityGroupRuleNet::Prefix(src.parse().unwrap()),
+            dst_net: NetworkSecurityGroupRuleNet::Prefix(dst.parse().unwrap()),
+            direction: NetworkSecurityGroupRuleDirection::Ingress,
+            ipv6: false,
+            src_port_start: None,
+            src_port_end: None,
+            dst_port_start: dst_ports.map(|(start, _)| start),
+            dst_port_end: dst_ports.map(|(_, end)| end),
+            protocol,
+            action,
+            priority,
+        }
+    }
+
+    fn flow(
+        protocol: NetworkSecurityGroupRuleProtocol,
+        src: &str,
+        dst: &str,
+        dst_port: Option<u32>,
+    ) -> NetworkSecurityGroupFlow {
+        NetworkSecurityGroupFlow {
+            direction: NetworkSecurityGroupRuleDirection::Ingress,
+            protocol,
+            src_ip: src.parse().unwrap(),
+            dst_ip: dst.parse().unwrap(),
+            src_port: None,
+            dst_port,
+        }
+    }
+
+    #[test]
+    fn test_simulate_flow() {
+        let rules = vec![
+            rule(
+                "permit-https",
+                10,
+                Permit,
+                Tcp,
+                "0.0.0.0/0",
+                "10.0.0.0/24",
+                Some((443, 443)),
+            ),
+            rule(
+                "deny-bad-net",
+                5,
+                Deny,
+                Any,
+                "192.168.66.0/24",
+                "0.0.0.0/0",
+                None,
+            ),
+            rule(
+                "permit-icmp",
+                20,
+                Permit,
+                Icmp,
+                "10.0.0.0/8",
+                "10.0.0.0/8",
+                None,
+            ),
+        ];
+        let prefixes = NetworkSecurityGroupRuleNetPrefixes::default();
+
+        let verdict = simulate_flow(
+            &rules,
+            &prefixes,
+            &flow(Tcp, "172.16.0.1", "10.0.0.5", Some(443)),
+        );
+        assert_eq!(verdict.action, Permit);
+        assert_eq!(verdict.rule_id.as_deref(), Some("permit-https"));
+
+        // Lower priority values are evaluated first
+        let verdict = simulate_flow(
+            &rules,
+            &prefixes,
+            &flow(Tcp, "192.168.66.1", "10.0.0.5", Some(443)),
+        );
+        assert_eq!(verdict.action, Deny);
+        assert_eq!(verdict.rule_id.as_deref(), Some("deny-bad-net"));
+
+        // Unmatched traffic is denied by default
+        for unmatched in [
+            flow(Tcp, "172.16.0.1", "10.0.0.5", Some(80)),
+            flow(Udp, "172.16.0.1", "10.0.0.5", Some(443)),
+            flow(Icmp, "172.16.0.1", "10.0.0.5", None),
+        ] {
+            let verdict = simulate_flow(&rules, &prefixes, &unmatched);
+            assert_eq!(verdict.action, Deny);
+            assert_eq!(verdict.rule_id, None);
+        }
+
+        // Rules only apply to their direction
+        let mut egress = flow(Icmp, "10.0.0.1", "10.0.0.5", None);
+        egress.direction = NetworkSecurityGroupRuleDirection::Egress;
+        assert_eq!(simulate_flow(&rules, &prefixes, &egress).rule_id, None);
+        egress.direction = NetworkSecurityGroupRuleDirection::Ingress;
+        assert_eq!(
+            simulate_flow(&rules, &prefixes, &egress).rule_id.as_deref(),
+            Some("permit-icmp")
+        );
+    }
+
+    #[test]
+    fn test_analyze_rules() {
+        let rules = vec![
+            rule(
+                "permit-web",
+                10,
+                Permit,
+                Tcp,
+                "0.0.0.0/0",
+                "10.0.0.0/24",
+                Some((80, 443)),
+            ),
+            // Covered by permit-web with the same action
+            rule(
+                "permit-https",
+                20,
+                Permit,
+                Tcp,
+                "172.16.0.0/16",
+                "10.0.0.5/32",
+                Some((443, 443)),
+            ),
+            // Covered by permit-web with a different action
+            rule(
+                "deny-http",
+                30,
+                Deny,
+                Tcp,
+                "172.16.0.0/16",
+                "10.0.0.0/24",
+                Some((80, 80)),
+            ),
+            // Same priority as permit-dns with a different action
+            rule(
+                "deny-dns",
+                40,
+                Deny,
+                Udp,
+                "172.16.0.0/16",
+                "10.0.0.0/24",
+                Some((53, 53)),
+            ),
+            rule(
+                "permit-dns",
+                40,
+                Permit,
+                Any,
+                "172.16.0.0/12",
+                "10.0.0.0/24",
+                None,
+            ),
+            // Only denies what is denied by default
+            rule("deny-rest", 50, Deny, Any, "0.0.0.0/0", "0.0.0.0/0", None),
+        ];
+        let prefixes = NetworkSecurityGroupRuleNetPrefixes::default();
+
+        let findings: Vec<(String, NetworkSecurityGroupRuleFindingType, Option<String>)> =
+            analyze_rules(&rules, &prefixes)
+                .into_iter()
+                .map(|f| (f.rule_id, f.finding_type, f.related_rule_id))
+                .collect();
+
+        assert_eq!(
+            findings,
+            vec![
+                (
+                    "permit-https".to_string(),
+                    NetworkSecurityGroupRuleFindingType::Redundant,
+                    Some("permit-web".to_string())
+                ),
+                (
+                    "deny-http".to_string(),
+                    NetworkSecurityGroupRuleFindingType::Shadowed,
+                    Some("permit-web".to_string())
+                ),
+                (
+                    "deny-dns".to_string(),
+                    NetworkSecurityGroupRuleFindingType::Contradictory,
+                    Some("permit-dns".to_string())
+                ),
+                (
+                    "permit-dns".to_string(),
+                    NetworkSecurityGroupRuleFindingType::Contradictory,
+                    Some("deny-dns".to_string())
+                ),
+                (
+                    "deny-rest".to_string(),
+                    NetworkSecurityGroupRuleFindingType::Redundant,
+                    None
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_vpc_references() {
+        let vpc_id: VpcId = "60d92a18-e56b-11ef-8ecd-ef90f290abf4".parse().unwrap();
+        let vpc_prefix_id: VpcPrefixId = "7ed78230-e56b-11ef-a601-f77e6a6c73d3".parse().unwrap();
+        let prefixes = NetworkSecurityGroupRuleNetPrefixes {
+            vpcs: HashMap::from([(
+                vpc_id,
+                vec![
+                    "10.0.0.0/24".parse().unwrap(),
+                    "10.2.0.0/24".parse().unwrap(),
+                ],
+            )]),
+            vpc_prefixes: HashMap::from([(vpc_prefix_id, "10.2.0.0/24".parse().unwrap())]),
+        };
+
+        let mut permit_vpc = rule(
+            "permit-vpc",
+            10,
+            Permit,
+            Any,
+            "0.0.0.0/0",
+            "0.0.0.0/0",
+            None,
+        );
+        permit_vpc.src_net = NetworkSecurityGroupRuleNet::VpcId(vpc_id);
+        let mut deny_vpc_prefix = rule(
+            "deny-vpc-prefix",
+            20,
+            Deny,
+            Tcp,
+            "0.0.0.0/0",
+            "0.0.0.0/0",
+            None,
+        );
+        deny_vpc_prefix.src_net = NetworkSecurityGroupRuleNet::VpcPrefixId(vpc_prefix_id);
+        let rules = vec![permit_vpc, deny_vpc_prefix];
+
+        // Every prefix of the VPC matches
+        for src in ["10.0.0.1", "10.2.0.1"] {
+            let verdict =
+                simulate_flow(&rules, &prefixes, &flow(Tcp, src, "192.168.0.1", Some(22)));
+            assert_eq!(verdict.rule_id.as_deref(), Some("permit-vpc"));
+        }
+        let verdict = simulate_flow(
+            &rules,
+            &prefixes,
+            &flow(Tcp, "10.1.0.1", "192.168.0.1", Some(22)),
+        );
+        assert_eq!(verdict.rule_id, None);
+
+        // The VPC prefix is part of the VPC, so the deny rule never matches
+        let findings = analyze_rules(&rules, &prefixes);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].rule_id, "deny-vpc-prefix");
+        assert_eq!(
+            findings[0].finding_type,
+            NetworkSecurityGroupRuleFindingType::Shadowed
+        );
+    }
+
+    #[test]
+    fn test_flow_from_rpc() {
+        let request = rpc::NetworkSecurityGroupFlow {
+            direction: rpc::NetworkSecurityGroupRuleDirection::NsgRuleDirectionIngress.into(),
+            protocol: rpc::NetworkSecurityGroupRuleProtocol::NsgRuleProtoTcp.into(),
+            src_ip: "2001:db8::1".to_string(),
+            dst_ip: "2001:db8::2".to_string(),
+            src_port: None,
+            dst_port: Some(22),
+        };
+        let flow = NetworkSecurityGroupFlow::try_from(request.clone()).unwrap();
+        assert_eq!(
+            flow.to_string(),
+            "INGRESS TCP 2001:db8::1 -> [2001:db8::2]:22"
+        );
+
+        for invalid in [
+            rpc::NetworkSecurityGroupFlow {
+                protocol: rpc::NetworkSecurityGroupRuleProtocol::NsgRuleProtoAny.into(),
+                dst_port: None,
+                ..request.clone()
+            },
+            rpc::NetworkSecurityGroupFlow {
+                dst_ip: "10.0.0.1".to_string(),
+                ..request.clone()
+            },
+            rpc::NetworkSecurityGroupFlow {
+                protocol: rpc::NetworkSecurityGroupRuleProtocol::NsgRuleProtoIcmp6.into(),
+                ..request.clone()
+            },
+        ] {
+            assert!(NetworkSecurityGroupFlow::try_from(invalid).is_err());
+        }
+    }
+}
@@ -14,17 +14,22 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+
+pub mod analysis;
+
 use std::collections::HashMap;
 use std::fmt;
 
 use ::rpc::errors::RpcDataConversionError;
 use ::rpc::forge as rpc;
 use carbide_uuid::instance::InstanceId;
+use carbide_uuid::machine::MachineId;
 use carbide_uuid::network_security_group::NetworkSecurityGroupId;
-use carbide_uuid::vpc::VpcId;
+use carbide_uuid::vpc::{VpcId, VpcPrefixId};
 use chrono::prelude::*;
 use config_version::ConfigVersion;
 use ipnetwork;
+use lazy_static::lazy_static;
 use serde::{Deserialize, Serialize};
 use sqlx::Row;
 use sqlx::postgres::PgRow;
@@ -371,29 +376,49 @@ impl TryFrom<rpc::NetworkSecurityGroupRuleAction> for NetworkSecurityGroupRuleAc
 /// network traffic. It can be either an explicit prefix
 /// or defined by an object ID.
 ///
+/// Object references are expanded to the prefixes of the
+/// referenced object when rules are sent to a DPU, so the
+/// expanded size of a group can grow without the group being
+/// touched. Prefix additions that would push an attached group
+/// over its limit are rejected, and groups which end up over
+/// the limit anyway are reported instead of being propagated.
+/// References are restricted to objects of the tenant owning
+/// the group, so that nobody else controls what a rule allows.
+///
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 pub enum NetworkSecurityGroupRuleNet {
     Prefix(ipnetwork::IpNetwork),
-    // Not yet supported.  We hide this in the proto spec.
-    // Implementation wouldn't be hard, but it would be hard
-    // to manage operationally because this would allow users
-    // to create a rule that does not exceed any limits but
-    // later silently exceeds limits by adding more prefixes
-    // to a VPC without ever touching the NSG.  It would be
-    // dangerous/irresponsible to simply allow the overflow
-    // (the behavior on the DPU could be undefined), or trim
-    // out anything beyond our limits.
-    // We either need some job checking the state of things
-    // and alerting, or the DPU needs to alert when limits
-    // are exceeded, but the damage is already done by the
-    // time something is alerting.
-    // This also perhaps opens a security issue unless we
-    // restrict the prefixes that a tenant is allowed to add
-    // to a VPC.  Otherwise, If I allow (VPC ID 123), then
-    // it means I allow whatever random prefixes the owner
-    // of that VPC attaches.  It implicitly hands off part of
-    // "my" ACL control over to the other side.
-    // VpcId(VpcId),
+    /// All prefixes of a VPC, including the prefixes of its
+    /// segments which are not part of a VPC prefix.
+    VpcId(VpcId),
+    VpcPrefixId(VpcPrefixId),
+}
+
+impl NetworkSecurityGroupRuleNet {
+    /// Whether the net refers to an object and needs to be expanded
+    pub fn is_reference(&self) -> bool {
+        !matches!(self, NetworkSecurityGroupRuleNet::Prefix(_))
+    }
+}
+
+impl fmt::Display for NetworkSecurityGroupRuleNet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NetworkSecurityGroupRuleNet::Prefix(p) => write!(f, "{p}"),
+            NetworkSecurityGroupRuleNet::VpcId(id) => write!(f, "vpc:{id}"),
+            NetworkSecurityGroupRuleNet::VpcPrefixId(id) => write!(f, "vpc_prefix:{id}"),
+        }
+    }
+}
+
+fn parse_vpc_id(id: &str) -> Result<VpcId, RpcDataConversionError> {
+    id.parse::<VpcId>()
+        .map_err(|_| RpcDataConversionError::InvalidVpcId(id.to_string()))
+}
+
+fn parse_vpc_prefix_id(id: &str) -> Result<VpcPrefixId, RpcDataConversionError> {
+    id.parse::<VpcPrefixId>()
+        .map_err(|_| RpcDataConversionError::InvalidUuid("vpc_prefix_id", id.to_string()))
 }
 
 impl TryFrom<rpc::network_security_group_rule_attributes::SourceNet>
@@ -411,6 +436,12 @@ impl TryFrom<rpc::network_security_group_rule_attributes::SourceNet>
                         .map_err(|e| RpcDataConversionError::InvalidIpAddress(e.to_string()))?,
                 ))
             }
+            rpc::network_security_group_rule_attributes::SourceNet::SrcVpcId(id) => {
+                Ok(NetworkSecurityGroupRuleNet::VpcId(parse_vpc_id(&id)?))
+            }
+            rpc::network_security_group_rule_attributes::SourceNet::SrcVpcPrefixId(id) => Ok(
+                NetworkSecurityGroupRuleNet::VpcPrefixId(parse_vpc_prefix_id(&id)?),
+            ),
         }
     }
 }
@@ -430,6 +461,12 @@ impl TryFrom<rpc::network_security_group_rule_attributes::DestinationNet>
                         .map_err(|e| RpcDataConversionError::InvalidIpAddress(e.to_string()))?,
                 ))
             }
+            rpc::network_security_group_rule_attributes::DestinationNet::DstVpcId(id) => {
+                Ok(NetworkSecurityGroupRuleNet::VpcId(parse_vpc_id(&id)?))
+            }
+            rpc::network_security_group_rule_attributes::DestinationNet::DstVpcPrefixId(id) => Ok(
+                NetworkSecurityGroupRuleNet::VpcPrefixId(parse_vpc_prefix_id(&id)?),
+            ),
         }
     }
 }
@@ -444,6 +481,14 @@ impl TryFrom<NetworkSecurityGroupRuleNet>
             NetworkSecurityGroupRuleNet::Prefix(p) => Ok(
                 rpc::network_security_group_rule_attributes::SourceNet::SrcPrefix(p.to_string()),
             ),
+            NetworkSecurityGroupRuleNet::VpcId(id) => Ok(
+                rpc::network_security_group_rule_attributes::SourceNet::SrcVpcId(id.to_string()),
+            ),
+            NetworkSecurityGroupRuleNet::VpcPrefixId(id) => Ok(
+                rpc::network_security_group_rule_attributes::SourceNet::SrcVpcPrefixId(
+                    id.to_string(),
+                ),
+            ),
         }
     }
 }
@@ -460,9 +505,119 @@ impl TryFrom<NetworkSecurityGroupRuleNet>
                     p.to_string(),
                 ),
             ),
+            NetworkSecurityGroupRuleNet::VpcId(id) => Ok(
+                rpc::network_security_group_rule_attributes::DestinationNet::DstVpcId(
+                    id.to_string(),
+                ),
+            ),
+            NetworkSecurityGroupRuleNet::VpcPrefixId(id) => Ok(
+                rpc::network_security_group_rule_attributes::DestinationNet::DstVpcPrefixId(
+                    id.to_string(),
+                ),
+            ),
         }
     }
 }
+
+/* ************************************** */
+/*   NetworkSecurityGroupRuleNetPrefixes  */
+/* ************************************** */
+
+/// NetworkSecurityGroupRuleNetPrefixes holds the prefixes
+/// of the objects referenced by a set of rules, which
+/// are needed to expand the rules.
+///
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NetworkSecurityGroupRuleNetPrefixes {
+    pub vpcs: HashMap<VpcId, Vec<ipnetwork::IpNetwork>>,
+    pub vpc_prefixes: HashMap<VpcPrefixId, ipnetwork::IpNetwork>,
+}
+
+impl NetworkSecurityGroupRuleNetPrefixes {
+    /// The VPCs and VPC prefixes referenced by a set of rules
+    pub fn references(rules: &[NetworkSecurityGroupRule]) -> (Vec<VpcId>, Vec<VpcPrefixId>) {
+        let mut vpc_ids = vec![];
+        let mut vpc_prefix_ids = vec![];
+
+        for net in rules.iter().flat_map(|r| [&r.src_net, &r.dst_net]) {
+            match net {
+                NetworkSecurityGroupRuleNet::Prefix(_) => {}
+                NetworkSecurityGroupRuleNet::VpcId(id) => vpc_ids.push(*id),
+                NetworkSecurityGroupRuleNet::VpcPrefixId(id) => vpc_prefix_ids.push(*id),
+            }
+        }
+
+        vpc_ids.sort();
+        vpc_ids.dedup();
+        vpc_prefix_ids.sort();
+        vpc_prefix_ids.dedup();
+
+        (vpc_ids, vpc_prefix_ids)
+    }
+
+    /// Expands a net to the prefixes of the given IP version.
+    /// References to objects which no longer exist expand to nothing.
+    pub fn resolve(
+        &self,
+        net: &NetworkSecurityGroupRuleNet,
+        ipv6: bool,
+    ) -> Vec<ipnetwork::IpNetwork> {
+        let prefixes = match net {
+            NetworkSecurityGroupRuleNet::Prefix(p) => return vec![*p],
+            NetworkSecurityGroupRuleNet::VpcId(id) => {
+                self.vpcs.get(id).cloned().unwrap_or_default()
+            }
+            NetworkSecurityGroupRuleNet::VpcPrefixId(id) => {
+                self.vpc_prefixes.get(id).copied().into_iter().collect()
+            }
+        };
+
+        prefixes
+            .into_iter()
+            .filter(|p| p.is_ipv6() == ipv6)
+            .collect()
+    }
+
+    /// The number of ACL entries a DPU needs for a rule
+    /// (src port range * dst port range * src prefix list * dst prefix list)
+    pub fn expanded_rule_size(&self, rule: &NetworkSecurityGroupRule) -> u64 {
+        // Negative ranges are caught when we convert from rpc to internal struct.
+        let port_range_size = |start: Option<u32>, end: Option<u32>| {
+            u64::from(end.unwrap_or_default() - start.unwrap_or_default()) + 1
+        };
+
+        port_range_size(rule.src_port_start, rule.src_port_end)
+            .saturating_mul(port_range_size(rule.dst_port_start, rule.dst_port_end))
+            .saturating_mul(self.resolve(&rule.src_net, rule.ipv6).len() as u64)
+            .saturating_mul(self.resolve(&rule.dst_net, rule.ipv6).len() as u64)
+    }
+
+    /// The number of ACL entries a DPU needs for a set of rules
+    pub fn expanded_size(&self, rules: &[NetworkSecurityGroupRule]) -> u64 {
+        rules
+            .iter()
+            .map(|rule| self.expanded_rule_size(rule))
+            .fold(0, u64::saturating_add)
+    }
+
+    /// Describes the references of a set of rules to objects which don't exist (anymore).
+    pub fn dangling_references(&self, rules: &[NetworkSecurityGroupRule]) -> Vec<String> {
+        let (vpc_ids, vpc_prefix_ids) = Self::references(rules);
+
+        vpc_ids
+            .into_iter()
+            .filter(|id| !self.vpcs.contains_key(id))
+            .map(|id| NetworkSecurityGroupRuleNet::VpcId(id).to_string())
+            .chain(
+                vpc_prefix_ids
+                    .into_iter()
+                    .filter(|id| !self.vpc_prefixes.contains_key(id))
+                    .map(|id| NetworkSecurityGroupRuleNet::VpcPrefixId(id).to_string()),
+            )
+            .collect()
+    }
+}
+
 /* ********************************** */
 /*       NetworkSecurityGroupRule     */
 /* ********************************** */
@@ -596,23 +751,24 @@ impl TryFrom<rpc::NetworkSecurityGroupRuleAttributes> for NetworkSecurityGroupRu
 
         // If prefix is used for src or dst, IP version must match rule ipv6 value.
         // This also implicitly ensures that src and dst are the same IP version.
-        match (&converted_rule.src_net, &converted_rule.dst_net) {
-            (NetworkSecurityGroupRuleNet::Prefix(s), NetworkSecurityGroupRuleNet::Prefix(d)) => {
-                if s.is_ipv6() != converted_rule.ipv6 {
-                    return Err(RpcDataConversionError::InvalidValue(
-                        "src_prefix".to_string(),
-                        "IP version of prefix does not match IP version of rule".to_string(),
-                    ));
-                }
+        // Object references are expanded to the prefixes matching the IP version of the rule.
+        if let NetworkSecurityGroupRuleNet::Prefix(s) = &converted_rule.src_net
+            && s.is_ipv6() != converted_rule.ipv6
+        {
+            return Err(RpcDataConversionError::InvalidValue(
+                "src_prefix".to_string(),
+                "IP version of prefix does not match IP version of rule".to_string(),
+            ));
+        }
 
-                if d.is_ipv6() != converted_rule.ipv6 {
-                    return Err(RpcDataConversionError::InvalidValue(
-                        "dst_prefix".to_string(),
-                        "IP version of prefix does not match IP version of rule".to_string(),
-                    ));
-                }
-            }
-        };
+        if let NetworkSecurityGroupRuleNet::Prefix(d) = &converted_rule.dst_net
+            && d.is_ipv6() != converted_rule.ipv6
+        {
+            return Err(RpcDataConversionError::InvalidValue(
+                "dst_prefix".to_string(),
+                "IP version of prefix does not match IP version of rule".to_string(),
+            ));
+        }
 
         Ok(converted_rule)
     }
@@ -757,6 +913,9 @@ pub struct NetworkSecurityGroupPropagationObjectStatus {
     pub interfaces_applied: u32,
     pub related_instance_ids: Vec<InstanceId>,
     pub unpropagated_instance_ids: Vec<InstanceId>,
+    /// Set if the NSG can't be propagated to some of the DPUs of the object,
+    /// e.g. because its expanded rule set exceeds the limits.
+    pub propagation_error: Option<String>,
 }
 
 impl From<NetworkSecurityGroupPropagationObjectStatus>
@@ -764,7 +923,12 @@ impl From<NetworkSecurityGroupPropagationObjectStatus>
 {
     fn from(status: NetworkSecurityGroupPropagationObjectStatus) -> Self {
         let (status_type, details) = {
-            if status.interfaces_applied == status.interfaces_expected {
+            if let Some(error) = status.propagation_error {
+                (
+                    rpc::NetworkSecurityGroupPropagationStatus::NsgPropStatusError,
+                    Some(error),
+                )
+            } else if status.interfaces_applied == status.interfaces_expected {
                 (
                     rpc::NetworkSecurityGroupPropagationStatus::NsgPropStatusFull,
                     None,
@@ -807,6 +971,80 @@ impl From<NetworkSecurityGroupPropagationObjectStatus>
     }
 }
 
+/* ******************************************* */
+/*        NetworkSecurityGroupDpuUsage         */
+/* ******************************************* */
+
+/// NetworkSecurityGroupDpuUsage holds the expanded size
+/// of a network security group applied on a DPU, and why
+/// it can't be propagated to the DPU, if that is the case.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct NetworkSecurityGroupDpuUsage {
+    pub dpu_machine_id: MachineId,
+    pub network_security_group_id: NetworkSecurityGroupId,
+    pub expanded_rule_count: u64,
+    pub error: Option<String>,
+}
+
+impl<'r> sqlx::FromRow<'r, PgRow> for NetworkSecurityGroupDpuUsage {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let expanded_rule_count: i64 = row.try_get("expanded_rule_count")?;
+
+        Ok(NetworkSecurityGroupDpuUsage {
+            dpu_machine_id: row.try_get("dpu_machine_id")?,
+            network_security_group_id: row.try_get("network_security_group_id")?,
+            expanded_rule_count: expanded_rule_count
+                .try_into()
+                .map_err(|e| sqlx::Error::Decode(Box::new(e)))?,
+            error: row.try_get("error")?,
+        })
+    }
+}
+
+lazy_static! {
+    pub static ref NETWORK_SECURITY_GROUP_LIMITS_HEALTH_PROBE_ID: health_report::HealthProbeId =
+        "NetworkSecurityGroupLimitsExceeded".parse().unwrap();
+}
+
+/// The name of the Health Override which is placed on DPUs whose
+/// network security group can't be propagated
+pub const NETWORK_SECURITY_GROUP_LIMITS_HEALTH_REPORT_SOURCE: &str =
+    "network-security-group-limits";
+
+/// Creates the Health override report for a DPU with network security groups
+/// which can't be propagated.  Usages without an error are skipped.
+pub fn create_limits_exceeded_health_report(
+    usages: &[&NetworkSecurityGroupDpuUsage],
+) -> health_report::HealthReport {
+    health_report::HealthReport {
+        source: NETWORK_SECURITY_GROUP_LIMITS_HEALTH_REPORT_SOURCE.to_string(),
+        observed_at: Some(Utc::now()),
+        successes: vec![],
+        alerts: usages
+            .iter()
+            .filter_map(|usage| {
+                let error = usage.error.as_ref()?;
+                Some(health_report::HealthProbeAlert {
+                    id: NETWORK_SECURITY_GROUP_LIMITS_HEALTH_PROBE_ID.clone(),
+                    target: Some(usage.network_security_group_id.to_string()),
+                    in_alert_since: Some(Utc::now()),
+                    message: format!(
+                        "Network security group {} can't be applied: {error}",
+                        usage.network_security_group_id
+                    ),
+                    tenant_message: Some(format!(
+                        "Network security group {} can't be applied and its previous rules remain in effect: {error}",
+                        usage.network_security_group_id
+                    )),
+                    // The DPU keeps enforcing the previous rules of the group
+                    classifications: vec![],
+                })
+            })
+            .collect(),
+    }
+}
+
 /* ******************************************* */
 /*    NetworkSecurityGroupStatusObservation    */
 /* ******************************************* */
@@ -871,6 +1109,7 @@ impl<'r> sqlx::FromRow<'r, PgRow> for NetworkSecurityGroupPropagationObjectStatu
                 .map_err(|e| sqlx::Error::Decode(Box::new(e)))?,
             related_instance_ids: related_instance_ids.0,
             unpropagated_instance_ids: unpropagated_instance_ids.0,
+            propagation_error: row.try_get("propagation_error")?,
         })
     }
 }
@@ -935,6 +1174,7 @@ mod tests {
             interfaces_applied: 0,
             unpropagated_instance_ids: vec![],
             related_instance_ids: vec![],
+            propagation_error: None,
         };
 
         assert_eq!(
@@ -962,6 +1202,7 @@ mod tests {
                 "fb02b51c-3f18-46b8-b2f1-bc4a6e9b2f3d".parse().unwrap(),
             ],
             unpropagated_instance_ids: vec![],
+            propagation_error: None,
         };
 
         assert_eq!(
@@ -992,6 +1233,7 @@ mod tests {
             unpropagated_instance_ids: vec![
                 "fb02b51c-3f18-46b8-b2f1-bc4a6e9b2f3d".parse().unwrap(),
             ],
+            propagation_error: None,
         };
 
         assert_eq!(
@@ -1020,6 +1262,7 @@ mod tests {
                 "200f1043-1653-426d-bd0e-97f5b06bdb3f".parse().unwrap(),
                 "fb02b51c-3f18-46b8-b2f1-bc4a6e9b2f3d".parse().unwrap(),
             ],
+            propagation_error: None,
         };
 
         assert_eq!(
@@ -1048,6 +1291,30 @@ mod tests {
                 "200f1043-1653-426d-bd0e-97f5b06bdb3f".parse().unwrap(),
                 "fb02b51c-3f18-46b8-b2f1-bc4a6e9b2f3d".parse().unwrap(),
             ],
+            propagation_error: None,
+        };
+
+        assert_eq!(
+            req_type,
+            rpc::NetworkSecurityGroupPropagationObjectStatus::from(status)
+        );
+
+        // Error, regardless of how far propagation got
+        let req_type = rpc::NetworkSecurityGroupPropagationObjectStatus {
+            id: "any_id".to_string(),
+            status: rpc::NetworkSecurityGroupPropagationStatus::NsgPropStatusError.into(),
+            details: Some("expanded rule set exceeds the limit".to_string()),
+            related_instance_ids: vec![],
+            unpropagated_instance_ids: vec![],
+        };
+
+        let status = NetworkSecurityGroupPropagationObjectStatus {
+            id: "any_id".to_string(),
+            interfaces_expected: 0,
+            interfaces_applied: 0,
+            related_instance_ids: vec![],
+            unpropagated_instance_ids: vec![],
+            propagation_error: Some("expanded rule set exceeds the limit".to_string()),
         };
 
         assert_eq!(
@@ -1344,4 +1611,89 @@ mod tests {
 
         assert_eq!(req_type, rpc::NetworkSecurityGroupAttachments::from(status));
     }
+
+    #[test]
+    fn test_rule_net_references_expansion() {
+        let vpc_id: VpcId = "60d92a18-e56b-11ef-8ecd-ef90f290abf4".parse().unwrap();
+        let other_vpc_id: VpcId = "6570b208-e56b-11ef-a659-f38dea668523".parse().unwrap();
+        let vpc_prefix_id: VpcPrefixId = "7ed78230-e56b-11ef-a601-f77e6a6c73d3".parse().unwrap();
+
+        // References survive the round trip through rpc.
+        let req = rpc::NetworkSecurityGroupRuleAttributes {
+            id: Some("anything".to_string()),
+            direction: rpc::NetworkSecurityGroupRuleDirection::NsgRuleDirectionIngress.into(),
+            ipv6: false,
+            src_port_start: Some(80),
+            src_port_end: Some(81),
+            dst_port_start: None,
+            dst_port_end: None,
+            protocol: rpc::NetworkSecurityGroupRuleProtocol::NsgRuleProtoTcp.into(),
+            action: rpc::NetworkSecurityGroupRuleAction::NsgRuleActionPermit.into(),
+            priority: 9001,
+            source_net: Some(
+                rpc::network_security_group_rule_attributes::SourceNet::SrcVpcId(
+                    vpc_id.to_string(),
+                ),
+            ),
+            destination_net: Some(
+                rpc::network_security_group_rule_attributes::DestinationNet::DstVpcPrefixId(
+                    vpc_prefix_id.to_string(),
+                ),
+            ),
+        };
+        let rule = NetworkSecurityGroupRule::try_from(req.clone()).unwrap();
+        assert_eq!(rule.src_net, NetworkSecurityGroupRuleNet::VpcId(vpc_id));
+        assert_eq!(
+            rule.dst_net,
+            NetworkSecurityGroupRuleNet::VpcPrefixId(vpc_prefix_id)
+        );
+        assert_eq!(
+            rpc::NetworkSecurityGroupRuleAttributes::try_from(rule.clone()).unwrap(),
+            req
+        );
+
+        // Bad references are rejected
+        let mut bad_req = req.clone();
+        bad_req.source_net = Some(
+            rpc::network_security_group_rule_attributes::SourceNet::SrcVpcId(
+                "not-a-vpc".to_string(),
+            ),
+        );
+        NetworkSecurityGroupRule::try_from(bad_req).unwrap_err();
+
+        let mut other_rule = rule.clone();
+        other_rule.src_net = NetworkSecurityGroupRuleNet::VpcId(other_vpc_id);
+        let rules = vec![rule.clone(), other_rule];
+
+        assert_eq!(
+            NetworkSecurityGroupRuleNetPrefixes::references(&rules),
+            (vec![vpc_id, other_vpc_id], vec![vpc_prefix_id])
+        );
+
+        let prefixes = NetworkSecurityGroupRuleNetPrefixes {
+            vpcs: HashMap::from([(
+                vpc_id,
+                vec![
+                    "10.0.0.0/24".parse().unwrap(),
+                    "10.0.1.0/24".parse().unwrap(),
+                    "2001:db8::/64".parse().unwrap(),
+                ],
+            )]),
+            vpc_prefixes: HashMap::from([(vpc_prefix_id, "10.1.0.0/16".parse().unwrap())]),
+        };
+
+        // Only the prefixes matching the IP version of the rule are used.
+        assert_eq!(prefixes.resolve(&rule.src_net, false).len(), 2);
+        assert_eq!(prefixes.resolve(&rule.src_net, true).len(), 1);
+        assert!(prefixes.resolve(&rule.dst_net, true).is_empty());
+
+        // 2 src ports * 1 dst port * 2 src prefixes * 1 dst prefix
+        assert_eq!(prefixes.expanded_rule_size(&rule), 4);
+        // The second rule references a VPC we don't know about, which expands to nothing
+        assert_eq!(prefixes.expanded_size(&rules), 4);
+        assert_eq!(
+            prefixes.dangling_references(&rules),
+            vec![format!("vpc:{other_vpc_id}")]
+        );
+    }
 }
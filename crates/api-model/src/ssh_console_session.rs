@@ -0,0 +1,157 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Recordings of frontend console sessions opened through ssh-console.
+//!
+//! Recordings are in asciicast v2 format and include everything the user typed, so passwords
+//! entered on the console are stored in plaintext. Access to them is restricted to admins.
+
+use carbide_uuid::machine::MachineId;
+use chrono::{DateTime, Utc};
+use rpc::errors::RpcDataConversionError;
+use uuid::Uuid;
+
+/// A recorded ssh-console session, without its recording
+#[derive(Debug, Clone, PartialEq)]
+pub struct SshConsoleSession {
+    pub session_id: Uuid,
+    pub machine_id: MachineId,
+    /// The recording's name, made of the session start time and session ID
+    pub name: String,
+    pub started: DateTime<Utc>,
+    pub user: Option<String>,
+    pub certificate_key_id: Option<String>,
+    pub peer_addr: String,
+    pub duration_secs: f64,
+    pub input_events: u32,
+    pub output_events: u32,
+}
+
+impl SshConsoleSession {
+    /// Whether `session` refers to this session, either by session ID or by name
+    pub fn matches(&self, session: &str) -> bool {
+        self.name == session || self.session_id.to_string() == session
+    }
+}
+
+impl TryFrom<rpc::forge::SshConsoleSession> for SshConsoleSession {
+    type Error = RpcDataConversionError;
+
+    fn try_from(session: rpc::forge::SshConsoleSession) -> Result<Self, Self::Error> {
+        let session_id = Uuid::try_parse(&session.session_id).map_err(|_| {
+            RpcDataConversionError::InvalidUuid("session_id", session.session_id.clone())
+        })?;
+        let machine_id = session
+            .machine_id
+            .ok_or(RpcDataConversionError::MissingArgument("machine_id"))?;
+        let started = session
+            .started
+            .ok_or(RpcDataConversionError::MissingArgument("started"))?;
+        let started = DateTime::<Utc>::try_from(started)
+            .map_err(|_| RpcDataConversionError::InvalidTimestamp(started.to_string()))?;
+        if session.name.is_empty() {
+            return Err(RpcDataConversionError::MissingArgument("name"));
+        }
+        if !session.duration_secs.is_finite() || session.duration_secs < 0.0 {
+            return Err(RpcDataConversionError::InvalidValue(
+                "duration_secs".to_string(),
+                session.duration_secs.to_string(),
+            ));
+        }
+
+        Ok(SshConsoleSession {
+            session_id,
+            machine_id,
+            name: session.name,
+            started,
+            user: session.user,
+            certificate_key_id: session.certificate_key_id,
+            peer_addr: session.peer_addr,
+            duration_secs: session.duration_secs,
+            input_events: session.input_events,
+            output_events: session.output_events,
+        })
+    }
+}
+
+impl From<SshConsoleSession> for rpc::forge::SshConsoleSession {
+    fn from(session: SshConsoleSession) -> Self {
+        rpc::forge::SshConsoleSession {
+            session_id: session.session_id.to_string(),
+            machine_id: Some(session.machine_id),
+            name: session.name,
+            started: Some(session.started.into()),
+            user: session.user,
+            certificate_key_id: session.certificate_key_id,
+            peer_addr: session.peer_addr,
+            duration_secs: session.duration_secs,
+            input_events: session.input_events,
+            output_events: session.output_events,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MACHINE_ID: &str = "fm100htv4fu8fpktl0e0qrg4dl58g2bc2g7naq0l6c15ruc22po1i5rfsq0";
+
+    fn rpc_session() -> rpc::forge::SshConsoleSession {
+        rpc::forge::SshConsoleSession {
+            session_id: "9b3c6f4e-2a0d-4d43-9a8e-0f5a2c1e7d11".to_string(),
+            machine_id: Some(MACHINE_ID.parse().unwrap()),
+            name: "20260301T123456Z_9b3c6f4e-2a0d-4d43-9a8e-0f5a2c1e7d11".to_string(),
+            started: Some(
+                DateTime::parse_from_rfc3339("2026-03-01T12:34:56Z")
+                    .unwrap()
+                    .with_timezone(&Utc)
+                    .into(),
+            ),
+            user: Some("alice".to_string()),
+            certificate_key_id: None,
+            peer_addr: "10.0.0.1:51234".to_string(),
+            duration_secs: 12.5,
+            input_events: 3,
+            output_events: 40,
+        }
+    }
+
+    #[test]
+    fn test_session_rpc_roundtrip() {
+        let session = SshConsoleSession::try_from(rpc_session()).unwrap();
+        assert!(session.matches("9b3c6f4e-2a0d-4d43-9a8e-0f5a2c1e7d11"));
+        assert!(session.matches("20260301T123456Z_9b3c6f4e-2a0d-4d43-9a8e-0f5a2c1e7d11"));
+        assert!(!session.matches("20260301T123456Z"));
+        assert_eq!(rpc::forge::SshConsoleSession::from(session), rpc_session());
+    }
+
+    #[test]
+    fn test_invalid_sessions() {
+        let mut session = rpc_session();
+        session.session_id = "not-a-uuid".to_string();
+        assert!(SshConsoleSession::try_from(session).is_err());
+
+        let mut session = rpc_session();
+        session.machine_id = None;
+        assert!(SshConsoleSession::try_from(session).is_err());
+
+        let mut session = rpc_session();
+        session.duration_secs = f64::NAN;
+        assert!(SshConsoleSession::try_from(session).is_err());
+    }
+}
@@ -40,10 +40,19 @@ pub enum TenantError {
 pub struct Tenant {
     pub organization_id: TenantOrganizationId,
     pub routing_profile_type: Option<RoutingProfileType>,
+    /// Whether FMDS requires session tokens for the instances of the tenant
+    pub metadata_token_required: bool,
+    /// The IP hop limit of FMDS token responses for the instances of the tenant
+    pub metadata_token_hop_limit: Option<u32>,
     pub metadata: Metadata,
     pub version: ConfigVersion,
 }
 
+/// Whether a hop limit of FMDS token responses is valid, i.e. between 1 and 255
+pub fn is_valid_metadata_token_hop_limit(hop_limit: u32) -> bool {
+    (1..=255).contains(&hop_limit)
+}
+
 impl TryFrom<Tenant> for rpc::forge::Tenant {
     type Error = RpcDataConversionError;
 
@@ -56,6 +65,8 @@ impl TryFrom<Tenant> for rpc::forge::Tenant {
                 .routing_profile_type
                 .map(rpc_forge::RoutingProfileType::from)
                 .map(|t| t.into()),
+            metadata_token_required: src.metadata_token_required,
+            metadata_token_hop_limit: src.metadata_token_hop_limit,
         })
     }
 }
@@ -82,6 +93,8 @@ impl TryFrom<rpc::forge::Tenant> for Tenant {
             organization_id,
             metadata: metadata.try_into()?,
             routing_profile_type,
+            metadata_token_required: src.metadata_token_required,
+            metadata_token_hop_limit: src.metadata_token_hop_limit,
             version,
         })
     }
@@ -530,6 +543,10 @@ impl<'r> sqlx::FromRow<'r, PgRow> for Tenant {
                 .map(|p| p.parse::<RoutingProfileType>())
                 .transpose()
                 .map_err(|e| sqlx::Error::Decode(Box::new(e)))?,
+            metadata_token_required: row.try_get("metadata_token_required")?,
+            metadata_token_hop_limit: row
+                .try_get::<Option<i32>, _>("metadata_token_hop_limit")?
+                .map(|hop_limit| hop_limit as u32),
             organization_id: organization_id
                 .try_into()
                 .map_err(|e| sqlx::Error::Decode(Box::new(e)))?,
@@ -26,8 +26,14 @@ pub use ::rpc::forge as rpc;
 use ::rpc::forge::{RemoveSkuRequest, SkuIdList};
 use ::rpc::protos::dns::{
     CreateDomainRequest, DnsResourceRecordLookupRequest, DnsResourceRecordLookupResponse, Domain,
-    DomainDeletionRequest, DomainDeletionResult, DomainList, DomainMetadataRequest,
-    DomainMetadataResponse, DomainSearchQuery, GetAllDomainsRequest, GetAllDomainsResponse,
+    DomainDeletionRequest, DomainDeletionResult, DomainKeyRequest, DomainKeysResponse, DomainList,
+    DomainMetadataRequest, DomainMetadataResponse, DomainSearchQuery, GetAllDomainsRequest,
+    GetAllDomainsResponse, GetAllRecordsForDomainRequest, GetAllRecordsForDomainResponse,
+    GetDomainInfoRequest, GetDomainInfoResponse, GetDomainKeysRequest, GetDomainKeysResponse,
+    GetUpdatedMastersRequest, GetUpdatedMastersResponse, SetNotifiedRequest, SetNotifiedResponse,
+    TenantDnsRecord, TenantDnsRecordCreationRequest, TenantDnsRecordDeletionRequest,
+    TenantDnsRecordDeletionResult, TenantDnsRecordIdList, TenantDnsRecordList,
+    TenantDnsRecordSearchFilter, TenantDnsRecordUpdateRequest, TenantDnsRecordsByIdsRequest,
     UpdateDomainRequest,
 };
 use ::rpc::protos::{measured_boot as measured_boot_pb, mlx_device as mlx_device_pb};
@@ -378,6 +384,13 @@ impl Forge for Api {
         crate::handlers::instance::batch_allocate(self, request).await
     }
 
+    async fn allocate_instances_by_instance_type(
+        &self,
+        request: Request<rpc::InstanceTypeAllocationRequest>,
+    ) -> Result<Response<rpc::BatchInstanceAllocationResponse>, Status> {
+        crate::handlers::instance::allocate_by_instance_type(self, request).await
+    }
+
     async fn find_instance_ids(
         &self,
         request: Request<rpc::InstanceSearchFilter>,
@@ -525,6 +538,90 @@ impl Forge for Api {
         crate::handlers::dns::get_all_domains(self, request).await
     }
 
+    async fn get_all_records_for_domain(
+        &self,
+        request: Request<GetAllRecordsForDomainRequest>,
+    ) -> Result<Response<GetAllRecordsForDomainResponse>, tonic::Status> {
+        crate::handlers::dns::get_all_records_for_domain(self, request).await
+    }
+
+    async fn get_domain_info(
+        &self,
+        request: Request<GetDomainInfoRequest>,
+    ) -> Result<Response<GetDomainInfoResponse>, tonic::Status> {
+        crate::handlers::dns::get_domain_info(self, request).await
+    }
+
+    async fn get_domain_keys(
+        &self,
+        request: Request<GetDomainKeysRequest>,
+    ) -> Result<Response<GetDomainKeysResponse>, tonic::Status> {
+        crate::handlers::dns::get_domain_keys(self, request).await
+    }
+
+    async fn generate_domain_key(
+        &self,
+        request: Request<DomainKeyRequest>,
+    ) -> Result<Response<DomainKeysResponse>, tonic::Status> {
+        crate::handlers::dns::generate_domain_key(self, request).await
+    }
+
+    async fn roll_domain_key(
+        &self,
+        request: Request<DomainKeyRequest>,
+    ) -> Result<Response<DomainKeysResponse>, tonic::Status> {
+        crate::handlers::dns::roll_domain_key(self, request).await
+    }
+
+    async fn get_updated_masters(
+        &self,
+        request: Request<GetUpdatedMastersRequest>,
+    ) -> Result<Response<GetUpdatedMastersResponse>, tonic::Status> {
+        crate::handlers::dns::get_updated_masters(self, request).await
+    }
+
+    async fn set_notified(
+        &self,
+        request: Request<SetNotifiedRequest>,
+    ) -> Result<Response<SetNotifiedResponse>, tonic::Status> {
+        crate::handlers::dns::set_notified(self, request).await
+    }
+
+    async fn create_tenant_dns_record(
+        &self,
+        request: Request<TenantDnsRecordCreationRequest>,
+    ) -> Result<Response<TenantDnsRecord>, Status> {
+        crate::handlers::tenant_dns_record::create(self, request).await
+    }
+
+    async fn find_tenant_dns_record_ids(
+        &self,
+        request: Request<TenantDnsRecordSearchFilter>,
+    ) -> Result<Response<TenantDnsRecordIdList>, Status> {
+        crate::handlers::tenant_dns_record::find_ids(self, request).await
+    }
+
+    async fn find_tenant_dns_records_by_ids(
+        &self,
+        request: Request<TenantDnsRecordsByIdsRequest>,
+    ) -> Result<Response<TenantDnsRecordList>, Status> {
+        crate::handlers::tenant_dns_record::find_by_ids(self, request).await
+    }
+
+    async fn update_tenant_dns_record(
+        &self,
+        request: Request<TenantDnsRecordUpdateRequest>,
+    ) -> Result<Response<TenantDnsRecord>, Status> {
+        crate::handlers::tenant_dns_record::update(self, request).await
+    }
+
+    async fn delete_tenant_dns_record(
+        &self,
+        request: Request<TenantDnsRecordDeletionRequest>,
+    ) -> Result<Response<TenantDnsRecordDeletionResult>, Status> {
+        crate::handlers::tenant_dns_record::delete(self, request).await
+    }
+
     async fn lookup_record(
         &self,
         request: Request<DnsResourceRecordLookupRequest>,
@@ -631,6 +728,27 @@ impl Forge for Api {
         crate::handlers::tenant_keyset::validate_public_key(self, request).await
     }
 
+    async fn record_ssh_console_session(
+        &self,
+        request: Request<rpc::SshConsoleSessionRecording>,
+    ) -> Result<Response<()>, Status> {
+        crate::handlers::ssh_console_session::record(self, request).await
+    }
+
+    async fn find_ssh_console_sessions(
+        &self,
+        request: Request<rpc::SshConsoleSessionsRequest>,
+    ) -> Result<Response<rpc::SshConsoleSessionList>, Status> {
+        crate::handlers::ssh_console_session::find(self, request).await
+    }
+
+    async fn get_ssh_console_session_recording(
+        &self,
+        request: Request<rpc::SshConsoleSessionRecordingRequest>,
+    ) -> Result<Response<rpc::SshConsoleSessionRecording>, Status> {
+        crate::handlers::ssh_console_session::get_recording(self, request).await
+    }
+
     async fn renew_machine_certificate(
         &self,
         request: Request<rpc::MachineCertificateRenewRequest>,
@@ -662,6 +780,13 @@ impl Forge for Api {
         crate::handlers::machine_scout::cleanup_machine_completed(self, request).await
     }
 
+    async fn get_machine_erasure_reports(
+        &self,
+        request: Request<rpc::MachineErasureReportsRequest>,
+    ) -> Result<Response<rpc::MachineErasureReportList>, Status> {
+        crate::handlers::machine_erasure_report::get_reports(self, request).await
+    }
+
     // Invoked by forge-scout whenever a certain Machine can not be properly acted on
     async fn report_forge_scout_error(
         &self,
@@ -803,6 +928,13 @@ impl Forge for Api {
         }
     }
 
+    async fn find_state_handler_outcome_history(
+        &self,
+        request: Request<rpc::StateHandlerOutcomeHistoryRequest>,
+    ) -> Result<Response<rpc::StateHandlerOutcomeHistories>, Status> {
+        crate::handlers::machine::find_state_handler_outcome_history(self, request).await
+    }
+
     async fn find_interfaces(
         &self,
         request: Request<rpc::InterfaceSearchQuery>,
@@ -1134,6 +1266,20 @@ impl Forge for Api {
         crate::handlers::machine::get_dpu_info_list(self, request).await
     }
 
+    async fn record_dpu_reachability_report(
+        &self,
+        request: Request<rpc::DpuReachabilityReport>,
+    ) -> Result<Response<()>, Status> {
+        crate::handlers::dpu_reachability::record_report(self, request).await
+    }
+
+    async fn get_dpu_reachability_matrix(
+        &self,
+        request: Request<rpc::DpuReachabilityMatrixRequest>,
+    ) -> Result<Response<rpc::DpuReachabilityMatrix>, Status> {
+        crate::handlers::dpu_reachability::get_matrix(self, request).await
+    }
+
     async fn get_machine_boot_override(
         &self,
         request: Request<MachineInterfaceId>,
@@ -2135,6 +2281,27 @@ impl Forge for Api {
         crate::handlers::instance_type::remove_machine_association(self, request).await
     }
 
+    async fn create_capacity_reservation(
+        &self,
+        request: Request<rpc::CapacityReservationCreationRequest>,
+    ) -> Result<Response<rpc::CapacityReservation>, Status> {
+        crate::handlers::capacity_reservation::create(self, request).await
+    }
+
+    async fn find_capacity_reservations(
+        &self,
+        request: Request<rpc::CapacityReservationSearchFilter>,
+    ) -> Result<Response<rpc::CapacityReservationList>, Status> {
+        crate::handlers::capacity_reservation::find(self, request).await
+    }
+
+    async fn release_capacity_reservation(
+        &self,
+        request: Request<rpc::CapacityReservationReleaseRequest>,
+    ) -> Result<Response<rpc::CapacityReservationReleaseResult>, Status> {
+        crate::handlers::capacity_reservation::release(self, request).await
+    }
+
     async fn redfish_browse(
         &self,
         request: Request<rpc::RedfishBrowseRequest>,
@@ -2232,6 +2399,13 @@ impl Forge for Api {
         crate::handlers::network_security_group::get_attachments(self, request).await
     }
 
+    async fn simulate_network_security_group(
+        &self,
+        request: Request<rpc::SimulateNetworkSecurityGroupRequest>,
+    ) -> Result<Response<rpc::SimulateNetworkSecurityGroupResponse>, Status> {
+        crate::handlers::network_security_group::simulate(self, request).await
+    }
+
     async fn get_desired_firmware_versions(
         &self,
         request: Request<rpc::GetDesiredFirmwareVersionsRequest>,
@@ -2489,6 +2663,76 @@ impl Forge for Api {
         crate::handlers::firmware::list_host_firmware(self, request)
     }
 
+    async fn get_machine_update_rollout(
+        &self,
+        request: Request<rpc::MachineUpdateRolloutRequest>,
+    ) -> Result<Response<rpc::MachineUpdateRollout>, Status> {
+        crate::handlers::machine_update_rollout::get(self, request).await
+    }
+
+    async fn machine_update_rollout_action(
+        &self,
+        request: Request<rpc::MachineUpdateRolloutActionRequest>,
+    ) -> Result<Response<rpc::MachineUpdateRollout>, Status> {
+        crate::handlers::machine_update_rollout::action(self, request).await
+    }
+
+    async fn create_maintenance_window(
+        &self,
+        request: Request<rpc::MaintenanceWindow>,
+    ) -> Result<Response<rpc::MaintenanceWindow>, Status> {
+        crate::handlers::maintenance_window::create(self, request).await
+    }
+
+    async fn find_maintenance_windows(
+        &self,
+        request: Request<()>,
+    ) -> Result<Response<rpc::MaintenanceWindowList>, Status> {
+        crate::handlers::maintenance_window::find(self, request).await
+    }
+
+    async fn delete_maintenance_window(
+        &self,
+        request: Request<rpc::DeleteMaintenanceWindowRequest>,
+    ) -> Result<Response<()>, Status> {
+        crate::handlers::maintenance_window::delete(self, request).await
+    }
+
+    async fn create_instance_webhook_subscription(
+        &self,
+        request: Request<rpc::InstanceWebhookSubscription>,
+    ) -> Result<Response<rpc::InstanceWebhookSubscription>, Status> {
+        crate::handlers::instance_webhook::create_subscription(self, request).await
+    }
+
+    async fn find_instance_webhook_subscriptions(
+        &self,
+        request: Request<rpc::InstanceWebhookSearchFilter>,
+    ) -> Result<Response<rpc::InstanceWebhookSubscriptionList>, Status> {
+        crate::handlers::instance_webhook::find_subscriptions(self, request).await
+    }
+
+    async fn delete_instance_webhook_subscription(
+        &self,
+        request: Request<rpc::DeleteInstanceWebhookSubscriptionRequest>,
+    ) -> Result<Response<()>, Status> {
+        crate::handlers::instance_webhook::delete_subscription(self, request).await
+    }
+
+    async fn find_instance_webhook_dead_letters(
+        &self,
+        request: Request<rpc::InstanceWebhookSearchFilter>,
+    ) -> Result<Response<rpc::InstanceWebhookDeliveryList>, Status> {
+        crate::handlers::instance_webhook::find_dead_letters(self, request).await
+    }
+
+    async fn retry_instance_webhook_dead_letters(
+        &self,
+        request: Request<rpc::RetryInstanceWebhookDeadLettersRequest>,
+    ) -> Result<Response<rpc::RetryInstanceWebhookDeadLettersResponse>, Status> {
+        crate::handlers::instance_webhook::retry_dead_letters(self, request).await
+    }
+
     // Scout is telling Carbide the mlx device configuration in its machine
     async fn publish_mlx_device_report(
         &self,
@@ -112,6 +112,10 @@ impl InternalRBACRules {
             "AllocateInstances",
             vec![ForgeAdminCLI, Machineatron, SiteAgent],
         );
+        x.perm(
+            "AllocateInstancesByInstanceType",
+            vec![ForgeAdminCLI, SiteAgent],
+        );
         x.perm("ReleaseInstance", vec![ForgeAdminCLI, SiteAgent]);
         x.perm("UpdateInstanceOperatingSystem", vec![SiteAgent]);
         x.perm("UpdateInstanceConfig", vec![ForgeAdminCLI, SiteAgent]);
@@ -154,6 +158,18 @@ impl InternalRBACRules {
         x.perm("LookupRecordLegacy", vec![Dns]);
         x.perm("GetAllDomainMetadata", vec![Dns]);
         x.perm("GetAllDomains", vec![Dns]);
+        x.perm("GetAllRecordsForDomain", vec![Dns]);
+        x.perm("GetDomainInfo", vec![Dns]);
+        x.perm("GetDomainKeys", vec![Dns]);
+        x.perm("GenerateDomainKey", vec![ForgeAdminCLI]);
+        x.perm("RollDomainKey", vec![ForgeAdminCLI]);
+        x.perm("GetUpdatedMasters", vec![Dns]);
+        x.perm("SetNotified", vec![Dns]);
+        x.perm("CreateTenantDnsRecord", vec![ForgeAdminCLI, SiteAgent]);
+        x.perm("FindTenantDnsRecordIds", vec![ForgeAdminCLI, SiteAgent]);
+        x.perm("FindTenantDnsRecordsByIds", vec![ForgeAdminCLI, SiteAgent]);
+        x.perm("UpdateTenantDnsRecord", vec![ForgeAdminCLI, SiteAgent]);
+        x.perm("DeleteTenantDnsRecord", vec![ForgeAdminCLI, SiteAgent]);
         x.perm("InvokeInstancePower", vec![ForgeAdminCLI, SiteAgent]);
         x.perm("ForgeAgentControl", vec![Machineatron, Scout]);
         x.perm("DiscoverMachine", vec![Anonymous]);
@@ -193,6 +209,7 @@ impl InternalRBACRules {
         x.perm("FindMachineIdsByBmcIps", vec![ForgeAdminCLI, Rla]);
         x.perm("FindMachineHealthHistories", vec![ForgeAdminCLI, SiteAgent]);
         x.perm("FindMachineStateHistories", vec![ForgeAdminCLI, SiteAgent]);
+        x.perm("FindStateHandlerOutcomeHistory", vec![ForgeAdminCLI]);
         x.perm("IdentifyUuid", vec![ForgeAdminCLI]);
         x.perm("IdentifyMac", vec![ForgeAdminCLI]);
         x.perm("IdentifySerial", vec![ForgeAdminCLI, Machineatron, Rla]);
@@ -211,6 +228,9 @@ impl InternalRBACRules {
         x.perm("UpdateTenantKeyset", vec![SiteAgent]);
         x.perm("DeleteTenantKeyset", vec![SiteAgent]);
         x.perm("ValidateTenantPublicKey", vec![SiteAgent, Ssh, SshRs]);
+        x.perm("RecordSshConsoleSession", vec![SshRs]);
+        x.perm("FindSshConsoleSessions", vec![ForgeAdminCLI]);
+        x.perm("GetSshConsoleSessionRecording", vec![ForgeAdminCLI]);
         x.perm("GetDpuSSHCredential", vec![ForgeAdminCLI]);
         x.perm("GetAllManagedHostNetworkStatus", vec![ForgeAdminCLI]);
         x.perm(
@@ -241,6 +261,9 @@ impl InternalRBACRules {
             vec![ForgeAdminCLI, Rla],
         );
         x.perm("GetDpuInfoList", vec![Agent]);
+        x.perm("RecordDpuReachabilityReport", vec![Agent]);
+        x.perm("GetDpuReachabilityMatrix", vec![ForgeAdminCLI]);
+        x.perm("GetMachineErasureReports", vec![ForgeAdminCLI, SiteAgent]);
         x.perm("GetMachineBootOverride", vec![ForgeAdminCLI]);
         x.perm("SetMachineBootOverride", vec![ForgeAdminCLI]);
         x.perm("ClearMachineBootOverride", vec![ForgeAdminCLI]);
@@ -450,6 +473,9 @@ impl InternalRBACRules {
             "RemoveMachineInstanceTypeAssociation",
             vec![ForgeAdminCLI, SiteAgent],
         );
+        x.perm("CreateCapacityReservation", vec![ForgeAdminCLI, SiteAgent]);
+        x.perm("FindCapacityReservations", vec![ForgeAdminCLI, SiteAgent]);
+        x.perm("ReleaseCapacityReservation", vec![ForgeAdminCLI, SiteAgent]);
         x.perm("RedfishBrowse", vec![ForgeAdminCLI]);
         x.perm("UfmBrowse", vec![ForgeAdminCLI]);
         x.perm("NmxmBrowse", vec![ForgeAdminCLI]);
@@ -473,6 +499,10 @@ impl InternalRBACRules {
             "GetNetworkSecurityGroupAttachments",
             vec![ForgeAdminCLI, SiteAgent],
         );
+        x.perm(
+            "SimulateNetworkSecurityGroup",
+            vec![ForgeAdminCLI, SiteAgent],
+        );
         x.perm(
             "GetDesiredFirmwareVersions",
             vec![ForgeAdminCLI, Machineatron],
@@ -512,6 +542,31 @@ impl InternalRBACRules {
         x.perm("DeleteBmcUser", vec![ForgeAdminCLI]);
         x.perm("SetFirmwareUpdateTimeWindow", vec![ForgeAdminCLI, Rla]);
         x.perm("ListHostFirmware", vec![ForgeAdminCLI, Rla]);
+        x.perm("GetMachineUpdateRollout", vec![ForgeAdminCLI]);
+        x.perm("MachineUpdateRolloutAction", vec![ForgeAdminCLI]);
+        x.perm("CreateMaintenanceWindow", vec![ForgeAdminCLI]);
+        x.perm("FindMaintenanceWindows", vec![ForgeAdminCLI]);
+        x.perm("DeleteMaintenanceWindow", vec![ForgeAdminCLI]);
+        x.perm(
+            "CreateInstanceWebhookSubscription",
+            vec![ForgeAdminCLI, SiteAgent],
+        );
+        x.perm(
+            "FindInstanceWebhookSubscriptions",
+            vec![ForgeAdminCLI, SiteAgent],
+        );
+        x.perm(
+            "DeleteInstanceWebhookSubscription",
+            vec![ForgeAdminCLI, SiteAgent],
+        );
+        x.perm(
+            "FindInstanceWebhookDeadLetters",
+            vec![ForgeAdminCLI, SiteAgent],
+        );
+        x.perm(
+            "RetryInstanceWebhookDeadLetters",
+            vec![ForgeAdminCLI, SiteAgent],
+        );
         x.perm("EnableInfiniteBoot", vec![ForgeAdminCLI]);
         x.perm("IsInfiniteBootEnabled", vec![ForgeAdminCLI]);
         x.perm("Lockdown", vec![ForgeAdminCLI]);
@@ -0,0 +1,307 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use bmc_vendor::BMCVendor;
+use chrono::{DateTime, Utc};
+use model::bmc_credential_rotation::BmcCredentialRotation;
+use opentelemetry::KeyValue;
+use opentelemetry::metrics::{Counter, Histogram, Meter};
+
+use crate::logging::metrics_utils::SharedMetricsHolder;
+
+/// Metrics that are gathered in a single `BmcCredentialRotator` run
+#[derive(Clone, Debug)]
+pub struct BmcCredentialRotationMetrics {
+    /// When we started recording these metrics
+    pub recording_started_at: std::time::Instant,
+    /// The state of the credentials of all BMCs, by vendor
+    pub vendors: HashMap<BMCVendor, VendorCredentialMetrics>,
+    /// The amount of rotations performed in this run, by vendor and outcome
+    pub rotations: HashMap<(BMCVendor, RotationStatus), usize>,
+}
+
+/// The state of the credentials of the BMCs of a single vendor
+#[derive(Clone, Debug, Default)]
+pub struct VendorCredentialMetrics {
+    /// The amount of BMCs that are considered for rotation
+    pub num_bmcs: usize,
+    /// The amount of BMCs whose credentials were never rotated
+    pub num_never_rotated: usize,
+    /// The amount of BMCs whose credentials are older than the rotation interval
+    pub num_overdue: usize,
+    /// The amount of BMCs where the last rotation attempt failed
+    pub num_failed: usize,
+    /// The age of the oldest rotated credentials
+    pub max_credential_age: Option<chrono::Duration>,
+}
+
+impl BmcCredentialRotationMetrics {
+    pub fn new() -> Self {
+        Self {
+            recording_started_at: std::time::Instant::now(),
+            vendors: HashMap::new(),
+            rotations: HashMap::new(),
+        }
+    }
+
+    /// Records the state of the credentials of a single BMC.
+    /// `rotation_interval` is `None` if rotation is disabled for the vendor.
+    pub fn record_candidate(
+        &mut self,
+        vendor: BMCVendor,
+        last_rotation: Option<&BmcCredentialRotation>,
+        rotation_interval: Option<chrono::Duration>,
+        now: DateTime<Utc>,
+    ) {
+        let metrics = self.vendors.entry(vendor).or_default();
+        metrics.num_bmcs += 1;
+
+        if last_rotation.is_some_and(|rotation| rotation.is_failed()) {
+            metrics.num_failed += 1;
+        }
+        match last_rotation.and_then(|rotation| rotation.credential_age(now)) {
+            Some(age) => {
+                if rotation_interval.is_some_and(|interval| age > interval) {
+                    metrics.num_overdue += 1;
+                }
+                metrics.max_credential_age = metrics.max_credential_age.max(Some(age));
+            }
+            None => metrics.num_never_rotated += 1,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum RotationStatus {
+    Ok,
+    Error,
+}
+
+impl From<RotationStatus> for opentelemetry::Value {
+    fn from(value: RotationStatus) -> Self {
+        let str_value = match value {
+            RotationStatus::Ok => "ok",
+            RotationStatus::Error => "error",
+        };
+
+        Self::from(str_value)
+    }
+}
+
+/// Instruments that are used by `BmcCredentialRotator`
+pub struct BmcCredentialRotationInstruments {
+    pub iteration_latency: Histogram<f64>,
+    pub rotations: Counter<u64>,
+}
+
+impl BmcCredentialRotationInstruments {
+    pub fn new(
+        meter: Meter,
+        shared_metrics: SharedMetricsHolder<BmcCredentialRotationMetrics>,
+    ) -> Self {
+        let iteration_latency = meter
+            .f64_histogram("carbide_bmc_credential_rotation_iteration_latency")
+            .with_description("The time it took to perform one BMC credential rotation iteration")
+            .with_unit("ms")
+            .build();
+
+        let rotations = meter
+            .u64_counter("carbide_bmc_credential_rotations")
+            .with_description("The amount of BMC credential rotations that have been attempted")
+            .build();
+
+        {
+            let metrics = shared_metrics.clone();
+            meter
+                .u64_observable_gauge("carbide_bmc_credentials_count")
+                .with_description("The amount of BMCs whose credentials are managed by rotation")
+                .with_callback(move |o| {
+                    metrics.if_available(|metrics, attrs| {
+                        for (vendor, metrics) in metrics.vendors.iter() {
+                            o.observe(metrics.num_bmcs as u64, &vendor_attrs(attrs, *vendor));
+                        }
+                    })
+                })
+                .build();
+        }
+
+        {
+            let metrics = shared_metrics.clone();
+            meter
+                .u64_observable_gauge("carbide_bmc_credentials_never_rotated_count")
+                .with_description("The amount of BMCs whose credentials were never rotated")
+                .with_callback(move |o| {
+                    metrics.if_available(|metrics, attrs| {
+                        for (vendor, metrics) in metrics.vendors.iter() {
+                            o.observe(
+                                metrics.num_never_rotated as u64,
+                                &vendor_attrs(attrs, *vendor),
+                            );
+                        }
+                    })
+                })
+                .build();
+        }
+
+        {
+            let metrics = shared_metrics.clone();
+            meter
+                .u64_observable_gauge("carbide_bmc_credentials_overdue_count")
+                .with_description(
+                    "The amount of BMCs whose credentials are older than the rotation interval",
+                )
+                .with_callback(move |o| {
+                    metrics.if_available(|metrics, attrs| {
+                        for (vendor, metrics) in metrics.vendors.iter() {
+                            o.observe(metrics.num_overdue as u64, &vendor_attrs(attrs, *vendor));
+                        }
+                    })
+                })
+                .build();
+        }
+
+        {
+            let metrics = shared_metrics.clone();
+            meter
+                .u64_observable_gauge("carbide_bmc_credential_rotation_failed_count")
+                .with_description(
+                    "The amount of BMCs where the last credential rotation attempt failed",
+                )
+                .with_callback(move |o| {
+                    metrics.if_available(|metrics, attrs| {
+                        for (vendor, metrics) in metrics.vendors.iter() {
+                            o.observe(metrics.num_failed as u64, &vendor_attrs(attrs, *vendor));
+                        }
+                    })
+                })
+                .build();
+        }
+
+        {
+            let metrics = shared_metrics;
+            meter
+                .u64_observable_gauge("carbide_bmc_credential_max_age_seconds")
+                .with_description("The age of the oldest rotated BMC credentials")
+                .with_unit("s")
+                .with_callback(move |o| {
+                    metrics.if_available(|metrics, attrs| {
+                        for (vendor, metrics) in metrics.vendors.iter() {
+                            if let Some(age) = metrics.max_credential_age {
+                                o.observe(
+                                    age.num_seconds().max(0) as u64,
+                                    &vendor_attrs(attrs, *vendor),
+                                );
+                            }
+                        }
+                    })
+                })
+                .build();
+        }
+
+        Self {
+            iteration_latency,
+            rotations,
+        }
+    }
+
+    fn emit_counters_and_histograms(&self, metrics: &BmcCredentialRotationMetrics) {
+        self.iteration_latency.record(
+            1000.0 * metrics.recording_started_at.elapsed().as_secs_f64(),
+            &[],
+        );
+
+        for (&(vendor, status), &count) in metrics.rotations.iter() {
+            self.rotations.add(
+                count as u64,
+                &[
+                    KeyValue::new("vendor", vendor.to_string()),
+                    KeyValue::new("status", status),
+                ],
+            );
+        }
+    }
+}
+
+fn vendor_attrs(attrs: &[KeyValue], vendor: BMCVendor) -> Vec<KeyValue> {
+    [attrs, &[KeyValue::new("vendor", vendor.to_string())]].concat()
+}
+
+/// Stores Metric data shared between the `BmcCredentialRotator` and the OpenTelemetry background task
+pub struct MetricHolder {
+    instruments: BmcCredentialRotationInstruments,
+    last_iteration_metrics: SharedMetricsHolder<BmcCredentialRotationMetrics>,
+}
+
+impl MetricHolder {
+    pub fn new(meter: Meter, hold_period: Duration) -> Self {
+        let last_iteration_metrics = SharedMetricsHolder::with_hold_period(hold_period);
+        let instruments =
+            BmcCredentialRotationInstruments::new(meter, last_iteration_metrics.clone());
+        Self {
+            instruments,
+            last_iteration_metrics,
+        }
+    }
+
+    /// Updates the most recent metrics
+    pub fn update_metrics(&self, metrics: BmcCredentialRotationMetrics) {
+        self.instruments.emit_counters_and_histograms(&metrics);
+        self.last_iteration_metrics.update(metrics);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+    use mac_address::MacAddress;
+
+    use super::*;
+
+    #[test]
+    fn test_record_candidate() {
+        let now = Utc::now();
+        let rotation = |age_days: i64, consecutive_failures: i32| BmcCredentialRotation {
+            bmc_mac_address: MacAddress::new([0, 1, 2, 3, 4, 5]),
+            rotated_at: Some(now - Duration::days(age_days)),
+            last_attempt_at: now,
+            consecutive_failures,
+            last_error: None,
+        };
+        let interval = Some(Duration::days(30));
+
+        let mut metrics = BmcCredentialRotationMetrics::new();
+        metrics.record_candidate(BMCVendor::Dell, None, interval, now);
+        metrics.record_candidate(BMCVendor::Dell, Some(&rotation(10, 0)), interval, now);
+        metrics.record_candidate(BMCVendor::Dell, Some(&rotation(40, 1)), interval, now);
+        metrics.record_candidate(BMCVendor::Hpe, Some(&rotation(40, 0)), None, now);
+
+        let dell = &metrics.vendors[&BMCVendor::Dell];
+        assert_eq!(dell.num_bmcs, 3);
+        assert_eq!(dell.num_never_rotated, 1);
+        assert_eq!(dell.num_overdue, 1);
+        assert_eq!(dell.num_failed, 1);
+        assert_eq!(dell.max_credential_age, Some(Duration::days(40)));
+
+        // Rotation is disabled for the vendor, so nothing is overdue
+        let hpe = &metrics.vendors[&BMCVendor::Hpe];
+        assert_eq!(hpe.num_bmcs, 1);
+        assert_eq!(hpe.num_overdue, 0);
+    }
+}
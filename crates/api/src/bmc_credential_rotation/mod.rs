@@ -0,0 +1,422 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Periodic rotation of BMC root credentials
+//!
+//! BMC root passwords are set once when site explorer ingests a BMC. The `BmcCredentialRotator`
+//! changes them again once they are older than the configured rotation interval, which can be
+//! overridden per BMC vendor. Machines whose BMC credentials could not be rotated get a health
+//! alert, which is removed again after the next successful rotation.
+
+mod metrics;
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use bmc_vendor::BMCVendor;
+use chrono::{DateTime, Utc};
+use db::Transaction;
+use db::work_lock_manager::WorkLockManagerHandle;
+use health_report::OverrideMode;
+use mac_address::MacAddress;
+use metrics::{BmcCredentialRotationMetrics, RotationStatus};
+use model::bmc_credential_rotation::{
+    BMC_CREDENTIAL_ROTATION_HEALTH_REPORT_SOURCE, BmcCredentialRotation,
+    create_rotation_failed_health_report,
+};
+use model::machine::MachineInterfaceSnapshot;
+use model::site_explorer::{EndpointType, ExploredEndpoint, PreingestionState};
+use sqlx::PgPool;
+use tokio::sync::oneshot;
+
+use crate::CarbideResult;
+use crate::cfg::file::{BmcCredentialRotationConfig, CarbideConfig};
+use crate::site_explorer::EndpointExplorer;
+
+/// A BMC whose credentials are considered for rotation
+#[derive(Debug, Clone)]
+struct RotationCandidate {
+    address: SocketAddr,
+    vendor: BMCVendor,
+    interface: MachineInterfaceSnapshot,
+    last_rotation: Option<BmcCredentialRotation>,
+}
+
+impl RotationCandidate {
+    fn bmc_mac_address(&self) -> MacAddress {
+        self.interface.mac_address
+    }
+}
+
+/// `BmcCredentialRotator` periodically rotates the root credentials of ingested BMCs
+///
+/// Config from [BmcCredentialRotationConfig]:
+/// * `rotation_interval` and `vendors` determine when the credentials of a BMC are due
+/// * `retry_interval` how long to wait before a failed rotation is retried
+/// * `rotations_per_run` the maximum number of BMCs rotated in a single run
+pub struct BmcCredentialRotator {
+    db_pool: PgPool,
+    config: BmcCredentialRotationConfig,
+    bmc_target_port: u16,
+    endpoint_explorer: Arc<dyn EndpointExplorer>,
+    metric_holder: Arc<metrics::MetricHolder>,
+    work_lock_manager_handle: WorkLockManagerHandle,
+}
+
+impl BmcCredentialRotator {
+    const ITERATION_WORK_KEY: &'static str = "BmcCredentialRotator::run_single_iteration";
+
+    pub fn new(
+        db_pool: PgPool,
+        config: Arc<CarbideConfig>,
+        meter: opentelemetry::metrics::Meter,
+        endpoint_explorer: Arc<dyn EndpointExplorer>,
+        work_lock_manager_handle: WorkLockManagerHandle,
+    ) -> Self {
+        let rotation_config = config.bmc_credential_rotation.clone();
+
+        // Hold metrics a bit longer than the run interval, so there is continuity in emitting them
+        let hold_period = rotation_config
+            .run_interval
+            .saturating_add(std::time::Duration::from_secs(60));
+
+        BmcCredentialRotator {
+            db_pool,
+            config: rotation_config,
+            bmc_target_port: config.site_explorer.override_target_port.unwrap_or(443),
+            endpoint_explorer,
+            metric_holder: Arc::new(metrics::MetricHolder::new(meter, hold_period)),
+            work_lock_manager_handle,
+        }
+    }
+
+    /// Start the BmcCredentialRotator and return a [sending channel](tokio::sync::oneshot::Sender) that will stop the BmcCredentialRotator when dropped.
+    pub fn start(self) -> eyre::Result<oneshot::Sender<i32>> {
+        let (stop_sender, stop_receiver) = oneshot::channel();
+
+        if self.config.enabled {
+            tokio::task::Builder::new()
+                .name("bmc_credential_rotator")
+                .spawn(async move { self.run(stop_receiver).await })?;
+        } else {
+            tracing::info!("BMC credential rotation is disabled");
+        }
+
+        Ok(stop_sender)
+    }
+
+    async fn run(&self, mut stop_receiver: oneshot::Receiver<i32>) {
+        loop {
+            if let Err(e) = self.run_single_iteration().await {
+                tracing::warn!("BmcCredentialRotator error: {}", e);
+            }
+
+            tokio::select! {
+                _ = tokio::time::sleep(self.config.run_interval) => {},
+                _ = &mut stop_receiver => {
+                    tracing::info!("BmcCredentialRotator stop was requested");
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Rotates the credentials of BMCs that are due and returns the number of rotation attempts
+    pub async fn run_single_iteration(&self) -> CarbideResult<usize> {
+        let _lock = match self
+            .work_lock_manager_handle
+            .try_acquire_lock(Self::ITERATION_WORK_KEY.into())
+            .await
+        {
+            Ok(lock) => lock,
+            Err(e) => {
+                tracing::warn!(
+                    "BmcCredentialRotator failed to acquire work lock: Another instance of carbide running? {e}"
+                );
+                return Ok(0);
+            }
+        };
+
+        let mut metrics = BmcCredentialRotationMetrics::new();
+        let mut candidates = self.load_candidates().await?;
+        let now = Utc::now();
+
+        let mut due: Vec<&mut RotationCandidate> = candidates
+            .iter_mut()
+            .filter(|candidate| {
+                rotation_due(
+                    &self.config,
+                    candidate.vendor,
+                    candidate.last_rotation.as_ref(),
+                    now,
+                )
+            })
+            .collect();
+        // Rotate the oldest credentials first. Credentials that were never rotated are the oldest.
+        due.sort_by_key(|candidate| {
+            candidate
+                .last_rotation
+                .as_ref()
+                .and_then(|rotation| rotation.rotated_at)
+        });
+
+        let mut num_attempts = 0;
+        for candidate in due {
+            if num_attempts >= self.config.rotations_per_run as usize {
+                break;
+            }
+            // BMCs without stored credentials have not been ingested by site explorer yet
+            if !self
+                .endpoint_explorer
+                .have_credentials(&candidate.interface)
+                .await
+            {
+                continue;
+            }
+
+            num_attempts += 1;
+            let result = self
+                .endpoint_explorer
+                .rotate_bmc_root_credentials(candidate.address, &candidate.interface)
+                .await;
+            let status = match &result {
+                Ok(()) => RotationStatus::Ok,
+                Err(_) => RotationStatus::Error,
+            };
+            *metrics
+                .rotations
+                .entry((candidate.vendor, status))
+                .or_default() += 1;
+
+            match self.record_outcome(candidate, result.err()).await {
+                Ok(rotation) => candidate.last_rotation = Some(rotation),
+                Err(e) => {
+                    tracing::error!(address = %candidate.address, error = %e, "failed to record the outcome of a BMC credential rotation");
+                }
+            }
+        }
+
+        for candidate in candidates.iter() {
+            metrics.record_candidate(
+                candidate.vendor,
+                candidate.last_rotation.as_ref(),
+                self.config.rotation_interval_for(candidate.vendor),
+                now,
+            );
+        }
+        self.metric_holder.update_metrics(metrics);
+
+        Ok(num_attempts)
+    }
+
+    /// Loads all ingested BMCs together with the state of their last rotation
+    async fn load_candidates(&self) -> CarbideResult<Vec<RotationCandidate>> {
+        let mut txn = Transaction::begin(&self.db_pool).await?;
+        let explored_endpoints = db::explored_endpoints::find_all(&mut txn).await?;
+        let interfaces = db::machine_interface::find_all(&mut txn).await?;
+        let rotations = db::bmc_credential_rotation::find_all(&mut txn).await?;
+        txn.commit().await?;
+
+        let interfaces_by_ip: HashMap<_, _> = interfaces
+            .into_iter()
+            .flat_map(|interface| {
+                interface
+                    .addresses
+                    .clone()
+                    .into_iter()
+                    .map(move |address| (address, interface.clone()))
+            })
+            .collect();
+        let mut rotations: HashMap<_, _> = rotations
+            .into_iter()
+            .map(|rotation| (rotation.bmc_mac_address, rotation))
+            .collect();
+
+        Ok(explored_endpoints
+            .into_iter()
+            .filter(is_rotatable)
+            .filter_map(|endpoint| {
+                let interface = interfaces_by_ip.get(&endpoint.address)?.clone();
+                Some(RotationCandidate {
+                    address: SocketAddr::new(endpoint.address, self.bmc_target_port),
+                    vendor: endpoint.report.vendor?,
+                    last_rotation: rotations.remove(&interface.mac_address),
+                    interface,
+                })
+            })
+            .collect())
+    }
+
+    /// Stores the outcome of a rotation, and raises or clears the health alert
+    /// of the Machine that the BMC belongs to
+    async fn record_outcome(
+        &self,
+        candidate: &RotationCandidate,
+        error: Option<model::site_explorer::EndpointExplorationError>,
+    ) -> CarbideResult<BmcCredentialRotation> {
+        let bmc_mac_address = candidate.bmc_mac_address();
+        let mut txn = Transaction::begin(&self.db_pool).await?;
+        let machine_id = db::machine_topology::find_machine_id_by_bmc_ip(
+            &mut txn,
+            &candidate.address.ip().to_string(),
+        )
+        .await?;
+
+        let rotation = match error {
+            None => {
+                tracing::info!(address = %candidate.address, %bmc_mac_address, vendor = %candidate.vendor, "rotated BMC root credentials");
+                if let Some(machine_id) = &machine_id {
+                    db::machine::remove_health_report_override(
+                        &mut txn,
+                        machine_id,
+                        OverrideMode::Merge,
+                        BMC_CREDENTIAL_ROTATION_HEALTH_REPORT_SOURCE,
+                    )
+                    .await?;
+                }
+                db::bmc_credential_rotation::record_success(&mut txn, bmc_mac_address).await?
+            }
+            Some(error) => {
+                let error = error.to_string();
+                tracing::warn!(address = %candidate.address, %bmc_mac_address, vendor = %candidate.vendor, %error, "failed to rotate BMC root credentials");
+                if let Some(machine_id) = &machine_id {
+                    db::machine::insert_health_report_override(
+                        &mut txn,
+                        machine_id,
+                        OverrideMode::Merge,
+                        &create_rotation_failed_health_report(bmc_mac_address, &error),
+                        false,
+                    )
+                    .await?;
+                }
+                db::bmc_credential_rotation::record_failure(&mut txn, bmc_mac_address, &error)
+                    .await?
+            }
+        };
+
+        txn.commit().await?;
+        Ok(rotation)
+    }
+}
+
+/// Only BMCs which site explorer has fully ingested are rotated
+fn is_rotatable(endpoint: &ExploredEndpoint) -> bool {
+    endpoint.report.endpoint_type == EndpointType::Bmc
+        && endpoint.report.last_exploration_error.is_none()
+        && endpoint.report.vendor.is_some()
+        && endpoint.preingestion_state == PreingestionState::Complete
+}
+
+/// Returns whether the credentials of a BMC of the given vendor need to be rotated
+fn rotation_due(
+    config: &BmcCredentialRotationConfig,
+    vendor: BMCVendor,
+    last_rotation: Option<&BmcCredentialRotation>,
+    now: DateTime<Utc>,
+) -> bool {
+    let Some(rotation_interval) = config.rotation_interval_for(vendor) else {
+        return false;
+    };
+    let Some(last_rotation) = last_rotation else {
+        return true;
+    };
+
+    if last_rotation.is_failed() && last_rotation.last_attempt_at + config.retry_interval > now {
+        return false;
+    }
+    last_rotation
+        .rotated_at
+        .is_none_or(|rotated_at| rotated_at + rotation_interval <= now)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use super::*;
+    use crate::cfg::file::BmcVendorCredentialRotationConfig;
+
+    fn rotation(
+        rotated_at: Option<DateTime<Utc>>,
+        last_attempt_at: DateTime<Utc>,
+        consecutive_failures: i32,
+    ) -> BmcCredentialRotation {
+        BmcCredentialRotation {
+            bmc_mac_address: MacAddress::new([0, 1, 2, 3, 4, 5]),
+            rotated_at,
+            last_attempt_at,
+            consecutive_failures,
+            last_error: (consecutive_failures > 0).then(|| "error".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_rotation_due() {
+        let config = BmcCredentialRotationConfig {
+            rotation_interval: Duration::days(90),
+            retry_interval: Duration::hours(6),
+            ..Default::default()
+        };
+        let now = Utc::now();
+
+        // Never rotated
+        assert!(rotation_due(&config, BMCVendor::Dell, None, now));
+
+        let recent = rotation(Some(now - Duration::days(10)), now - Duration::days(10), 0);
+        assert!(!rotation_due(&config, BMCVendor::Dell, Some(&recent), now));
+
+        let old = rotation(Some(now - Duration::days(91)), now - Duration::days(91), 0);
+        assert!(rotation_due(&config, BMCVendor::Dell, Some(&old), now));
+
+        // Failed recently, wait for the retry interval
+        let failed = rotation(Some(now - Duration::days(91)), now - Duration::hours(1), 1);
+        assert!(!rotation_due(&config, BMCVendor::Dell, Some(&failed), now));
+        let failed = rotation(None, now - Duration::hours(7), 2);
+        assert!(rotation_due(&config, BMCVendor::Dell, Some(&failed), now));
+    }
+
+    #[test]
+    fn test_rotation_due_vendor_overrides() {
+        let config = BmcCredentialRotationConfig {
+            rotation_interval: Duration::days(90),
+            vendors: HashMap::from([
+                (
+                    BMCVendor::Dell,
+                    BmcVendorCredentialRotationConfig {
+                        enabled: true,
+                        rotation_interval: Some(Duration::days(30)),
+                    },
+                ),
+                (
+                    BMCVendor::Liteon,
+                    BmcVendorCredentialRotationConfig {
+                        enabled: false,
+                        rotation_interval: None,
+                    },
+                ),
+            ]),
+            ..Default::default()
+        };
+        let now = Utc::now();
+        let last = rotation(Some(now - Duration::days(45)), now - Duration::days(45), 0);
+
+        assert!(rotation_due(&config, BMCVendor::Dell, Some(&last), now));
+        assert!(!rotation_due(&config, BMCVendor::Hpe, Some(&last), now));
+        assert!(!rotation_due(&config, BMCVendor::Liteon, None, now));
+    }
+}
@@ -36,6 +36,9 @@ pub enum Command {
 
     #[clap(about = "Run the API service")]
     Run(Box<Daemon>),
+
+    #[clap(about = "Copies credentials from one credential backend to another")]
+    MigrateCredentials(MigrateCredentials),
 }
 
 #[derive(Parser)]
@@ -63,6 +66,24 @@ pub struct Migrate {
     pub datastore: String,
 }
 
+#[derive(Parser)]
+pub struct MigrateCredentials {
+    /// Path to a TOML file describing the backend to copy credentials from, in the
+    /// format of the `[secrets.credentials]` section of the carbide-api configuration.
+    /// E.g. `backend = "vault"`. Vault settings are taken from the `VAULT_*`
+    /// environment variables.
+    #[clap(long)]
+    pub source: String,
+    /// Path to a TOML file describing the backend to copy credentials to, in the
+    /// same format as `source`.
+    #[clap(long)]
+    pub target: String,
+    #[clap(long, help = "Replace credentials which already exist in the target")]
+    pub overwrite: bool,
+    #[clap(long, help = "Only report which credentials would be copied")]
+    pub dry_run: bool,
+}
+
 impl Options {
     pub fn load() -> Self {
         Self::parse()
@@ -29,7 +29,10 @@ use std::{fmt, fs};
 use arc_swap::ArcSwap;
 use bmc_vendor::BMCVendor;
 use chrono::Duration;
-use duration_str::{deserialize_duration, deserialize_duration_chrono};
+use duration_str::{
+    deserialize_duration, deserialize_duration_chrono, deserialize_option_duration_chrono,
+};
+use forge_secrets::config::SecretsConfig;
 use ipnetwork::{IpNetwork, Ipv4Network};
 use itertools::Itertools;
 use libmlx::firmware::config::FirmwareFlasherProfile;
@@ -151,6 +154,10 @@ pub struct CarbideConfig {
     #[serde(default)]
     pub dpu_network_monitor_pinger_type: Option<String>,
 
+    /// Probes the network monitor of each DPU sends to its peers
+    #[serde(default)]
+    pub dpu_network_monitor: DpuNetworkMonitorConfig,
+
     /// TLS related configuration
     pub tls: Option<TlsConfig>,
 
@@ -276,6 +283,12 @@ pub struct CarbideConfig {
     #[serde(default)]
     pub network_security_group: NetworkSecurityGroupConfig,
 
+    #[serde(default)]
+    pub instance_webhooks: InstanceWebhookConfig,
+
+    #[serde(default)]
+    pub erasure_reports: ErasureReportConfig,
+
     /// The minimum number of functioning links on a dpu for it to be considered healthy
     /// if not present, all links must be functional.
     #[serde(default)]
@@ -300,6 +313,10 @@ pub struct CarbideConfig {
     #[serde(default)]
     pub measured_boot_collector: MeasuredBootMetricsCollectorConfig,
 
+    /// CapacityReservationMetricsCollector related configuration
+    #[serde(default)]
+    pub capacity_reservation_metrics: CapacityReservationMetricsConfig,
+
     /// Machine Validation config to api server
     #[serde(default)]
     pub machine_validation_config: MachineValidationConfig,
@@ -465,6 +482,15 @@ pub struct CarbideConfig {
     /// ```
     #[serde(default)]
     pub supernic_firmware_profiles: HashMap<String, HashMap<String, FirmwareFlasherProfile>>,
+
+    /// Where credentials are stored and certificates are issued. Defaults to Vault for both,
+    /// see [`SecretsConfig`] for the alternatives.
+    #[serde(default)]
+    pub secrets: SecretsConfig,
+
+    /// Periodic rotation of BMC root credentials
+    #[serde(default)]
+    pub bmc_credential_rotation: BmcCredentialRotationConfig,
 }
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
@@ -763,6 +789,16 @@ where
     serializer.serialize_str(&format!("{}s", d.as_secs()))
 }
 
+fn as_option_duration<S>(d: &Option<Duration>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match d {
+        Some(d) => as_duration(d, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
 /// MachineStateController related config.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct MachineStateControllerConfig {
@@ -1449,6 +1485,119 @@ impl SiteExplorerConfig {
     }
 }
 
+/// BMC credential rotation related configuration
+///
+/// ```toml
+/// [bmc_credential_rotation]
+/// enabled = true
+/// rotation_interval = "90d"
+///
+/// [bmc_credential_rotation.vendors.Dell]
+/// rotation_interval = "30d"
+///
+/// [bmc_credential_rotation.vendors.Liteon]
+/// enabled = false
+/// ```
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct BmcCredentialRotationConfig {
+    /// Whether BMC root credentials are rotated automatically.
+    /// Defaults to false.
+    #[serde(default)]
+    pub enabled: bool,
+    /// The interval at which the rotation controller checks for credentials that are due.
+    /// Defaults to 10 Minutes if not specified.
+    #[serde(
+        default = "BmcCredentialRotationConfig::default_run_interval",
+        deserialize_with = "deserialize_duration",
+        serialize_with = "as_std_duration"
+    )]
+    pub run_interval: std::time::Duration,
+    /// How long credentials are used before they get rotated.
+    /// Defaults to 90 days.
+    #[serde(
+        default = "BmcCredentialRotationConfig::default_rotation_interval",
+        deserialize_with = "deserialize_duration_chrono",
+        serialize_with = "as_duration"
+    )]
+    pub rotation_interval: Duration,
+    /// How long to wait before retrying a rotation that failed.
+    /// Defaults to 6 hours.
+    #[serde(
+        default = "BmcCredentialRotationConfig::default_retry_interval",
+        deserialize_with = "deserialize_duration_chrono",
+        serialize_with = "as_duration"
+    )]
+    pub retry_interval: Duration,
+    /// How many BMCs are rotated in a single run.
+    /// Default is 5.
+    #[serde(default = "BmcCredentialRotationConfig::default_rotations_per_run")]
+    pub rotations_per_run: u64,
+    /// Per BMC vendor overrides of the schedule
+    #[serde(default)]
+    pub vendors: HashMap<BMCVendor, BmcVendorCredentialRotationConfig>,
+}
+
+impl BmcCredentialRotationConfig {
+    pub const fn default_run_interval() -> std::time::Duration {
+        std::time::Duration::from_secs(600)
+    }
+
+    pub const fn default_rotation_interval() -> Duration {
+        Duration::days(90)
+    }
+
+    pub const fn default_retry_interval() -> Duration {
+        Duration::hours(6)
+    }
+
+    pub const fn default_rotations_per_run() -> u64 {
+        5
+    }
+
+    /// Returns how long credentials of BMCs of the given vendor are used before they
+    /// get rotated, or `None` if they should not be rotated
+    pub fn rotation_interval_for(&self, vendor: BMCVendor) -> Option<Duration> {
+        match self.vendors.get(&vendor) {
+            Some(vendor_config) if !vendor_config.enabled => None,
+            Some(vendor_config) => Some(
+                vendor_config
+                    .rotation_interval
+                    .unwrap_or(self.rotation_interval),
+            ),
+            None => Some(self.rotation_interval),
+        }
+    }
+}
+
+impl Default for BmcCredentialRotationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            run_interval: Self::default_run_interval(),
+            rotation_interval: Self::default_rotation_interval(),
+            retry_interval: Self::default_retry_interval(),
+            rotations_per_run: Self::default_rotations_per_run(),
+            vendors: HashMap::new(),
+        }
+    }
+}
+
+/// BMC credential rotation settings for a single BMC vendor
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct BmcVendorCredentialRotationConfig {
+    /// Whether credentials of BMCs of this vendor are rotated.
+    /// Defaults to true.
+    #[serde(default = "default_to_true")]
+    pub enabled: bool,
+    /// Overrides `rotation_interval` for this vendor
+    #[serde(
+        default,
+        deserialize_with = "deserialize_option_duration_chrono",
+        serialize_with = "as_option_duration"
+    )]
+    pub rotation_interval: Option<Duration>,
+}
+
 impl DpaConfig {
     pub const fn default_hb_interval() -> chrono::Duration {
         Duration::minutes(2)
@@ -1814,8 +1963,28 @@ pub struct NetworkSecurityGroupConfig {
     pub stateful_acls_enabled: bool,
 
     /// A set of NSG rules that will be inserted before any user-defined rules.
+    /// VPC and VPC prefix references in these rules resolve for objects of any tenant.
     #[serde(default)]
     pub policy_overrides: Vec<NetworkSecurityGroupRule>,
+
+    /// Whether the NetworkSecurityGroupLimitMonitor periodically checks the
+    /// expanded size of the groups applied to DPUs.
+    #[serde(default = "default_to_true")]
+    pub limit_monitor_enabled: bool,
+    /// How often the expanded size of the groups applied to DPUs is checked.
+    /// Defaults to 5 minutes if not specified.
+    #[serde(
+        default = "NetworkSecurityGroupConfig::default_limit_monitor_run_interval",
+        deserialize_with = "deserialize_duration",
+        serialize_with = "as_std_duration"
+    )]
+    pub limit_monitor_run_interval: std::time::Duration,
+}
+
+impl NetworkSecurityGroupConfig {
+    pub const fn default_limit_monitor_run_interval() -> std::time::Duration {
+        std::time::Duration::from_secs(300)
+    }
 }
 
 impl Default for NetworkSecurityGroupConfig {
@@ -1824,6 +1993,273 @@ impl Default for NetworkSecurityGroupConfig {
             max_network_security_group_size: default_max_network_security_group_size(),
             stateful_acls_enabled: default_to_true(),
             policy_overrides: vec![],
+            limit_monitor_enabled: default_to_true(),
+            limit_monitor_run_interval: Self::default_limit_monitor_run_interval(),
+        }
+    }
+}
+
+/// Delivery of the instance lifecycle webhooks registered by tenants
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct InstanceWebhookConfig {
+    /// Whether the InstanceWebhookDispatcher delivers queued events.
+    /// Events are queued for the subscriptions of tenants either way.
+    #[serde(default = "default_to_true")]
+    pub enabled: bool,
+    /// How often queued events are delivered.
+    /// Defaults to 10 seconds if not specified.
+    #[serde(
+        default = "InstanceWebhookConfig::default_run_interval",
+        deserialize_with = "deserialize_duration",
+        serialize_with = "as_std_duration"
+    )]
+    pub run_interval: std::time::Duration,
+    /// How often delivery of an event is attempted before it is moved to the dead letters
+    #[serde(default = "InstanceWebhookConfig::default_max_attempts")]
+    pub max_attempts: u32,
+    /// How long to wait after the first failed attempt. The delay doubles with every
+    /// further failed attempt, up to `max_backoff`.
+    #[serde(
+        default = "InstanceWebhookConfig::default_initial_backoff",
+        deserialize_with = "deserialize_duration",
+        serialize_with = "as_std_duration"
+    )]
+    pub initial_backoff: std::time::Duration,
+    #[serde(
+        default = "InstanceWebhookConfig::default_max_backoff",
+        deserialize_with = "deserialize_duration",
+        serialize_with = "as_std_duration"
+    )]
+    pub max_backoff: std::time::Duration,
+    /// How long to wait for a subscriber to accept an event
+    #[serde(
+        default = "InstanceWebhookConfig::default_request_timeout",
+        deserialize_with = "deserialize_duration",
+        serialize_with = "as_std_duration"
+    )]
+    pub request_timeout: std::time::Duration,
+    /// The maximum number of events delivered in a single run
+    #[serde(default = "InstanceWebhookConfig::default_max_deliveries_per_run")]
+    pub max_deliveries_per_run: u32,
+    /// The maximum number of subscriptions events are delivered to at the same time
+    #[serde(default = "InstanceWebhookConfig::default_max_concurrent_subscriptions")]
+    pub max_concurrent_subscriptions: usize,
+    /// The maximum number of events delivered to a single subscription at the same time.
+    /// Events of a subscription are delivered in order if this is 1, which is the default.
+    #[serde(default = "InstanceWebhookConfig::default_max_concurrent_deliveries_per_subscription")]
+    pub max_concurrent_deliveries_per_subscription: usize,
+    /// How long dead letters are kept before they are removed.
+    /// Defaults to 7 days if not specified.
+    #[serde(
+        default = "InstanceWebhookConfig::default_dead_letter_retention",
+        deserialize_with = "deserialize_duration",
+        serialize_with = "as_std_duration"
+    )]
+    pub dead_letter_retention: std::time::Duration,
+    /// Whether subscriptions may post to `http` URLs and to loopback, private and link-local
+    /// addresses. This lets tenants reach services inside the site, and is only meant for
+    /// development and tests.
+    #[serde(default)]
+    pub allow_insecure_targets: bool,
+}
+
+impl InstanceWebhookConfig {
+    pub const fn default_run_interval() -> std::time::Duration {
+        std::time::Duration::from_secs(10)
+    }
+
+    pub const fn default_max_attempts() -> u32 {
+        8
+    }
+
+    pub const fn default_initial_backoff() -> std::time::Duration {
+        std::time::Duration::from_secs(30)
+    }
+
+    pub const fn default_max_backoff() -> std::time::Duration {
+        std::time::Duration::from_secs(3600)
+    }
+
+    pub const fn default_request_timeout() -> std::time::Duration {
+        std::time::Duration::from_secs(10)
+    }
+
+    pub const fn default_max_deliveries_per_run() -> u32 {
+        100
+    }
+
+    pub const fn default_max_concurrent_subscriptions() -> usize {
+        16
+    }
+
+    pub const fn default_max_concurrent_deliveries_per_subscription() -> usize {
+        1
+    }
+
+    pub const fn default_dead_letter_retention() -> std::time::Duration {
+        std::time::Duration::from_secs(7 * 24 * 60 * 60)
+    }
+}
+
+impl Default for InstanceWebhookConfig {
+    fn default() -> Self {
+        InstanceWebhookConfig {
+            enabled: default_to_true(),
+            run_interval: Self::default_run_interval(),
+            max_attempts: Self::default_max_attempts(),
+            initial_backoff: Self::default_initial_backoff(),
+            max_backoff: Self::default_max_backoff(),
+            request_timeout: Self::default_request_timeout(),
+            max_deliveries_per_run: Self::default_max_deliveries_per_run(),
+            max_concurrent_subscriptions: Self::default_max_concurrent_subscriptions(),
+            max_concurrent_deliveries_per_subscription:
+                Self::default_max_concurrent_deliveries_per_subscription(),
+            dead_letter_retention: Self::default_dead_letter_retention(),
+            allow_insecure_targets: false,
+        }
+    }
+}
+
+/// Certificate-of-erasure reports recorded when the storage of a host is wiped
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ErasureReportConfig {
+    /// PEM encoded EC P-256 private key which signs completed reports.
+    /// Reports are stored unsigned if not specified.
+    #[serde(default)]
+    pub signing_key_path: Option<PathBuf>,
+    /// The `kid` header of signed reports, which lets verifiers pick the matching public key
+    #[serde(default = "ErasureReportConfig::default_signing_key_id")]
+    pub signing_key_id: String,
+}
+
+impl ErasureReportConfig {
+    pub fn default_signing_key_id() -> String {
+        "erasure-report".to_string()
+    }
+}
+
+impl Default for ErasureReportConfig {
+    fn default() -> Self {
+        ErasureReportConfig {
+            signing_key_path: None,
+            signing_key_id: Self::default_signing_key_id(),
+        }
+    }
+}
+
+/// Probes sent by the network monitor of each DPU to its peer DPUs, which make up the DPU
+/// reachability matrix
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct DpuNetworkMonitorConfig {
+    /// Number of probes sent to each peer per check cycle and probe type
+    #[serde(default = "DpuNetworkMonitorConfig::default_probe_count")]
+    pub probe_count: u32,
+    #[serde(
+        default = "DpuNetworkMonitorConfig::default_probe_timeout",
+        deserialize_with = "deserialize_duration",
+        serialize_with = "as_std_duration"
+    )]
+    pub probe_timeout: std::time::Duration,
+    /// How often each DPU fetches the list of its peers
+    #[serde(
+        default = "DpuNetworkMonitorConfig::default_peer_refresh_interval",
+        deserialize_with = "deserialize_duration",
+        serialize_with = "as_std_duration"
+    )]
+    pub peer_refresh_interval: std::time::Duration,
+    /// Ports TCP probes are sent to, in addition to ICMP echo requests
+    #[serde(default)]
+    pub tcp_ports: Vec<u16>,
+    /// Ports UDP probes are sent to, in addition to ICMP echo requests
+    #[serde(default)]
+    pub udp_ports: Vec<u16>,
+    /// Whether the probes are also sent on the overlay of each VPC shared by two DPUs. Only FNN
+    /// VPCs are probed, as DPUs only have an address of their own in the VRF of those.
+    #[serde(default)]
+    pub vpc_overlay_probes: bool,
+    /// Whether DPUs report their results for the reachability matrix
+    #[serde(default = "default_to_true")]
+    pub report_results: bool,
+    /// Maximum number of peers each DPU probes per check cycle. Sites with more DPUs have their
+    /// peers probed in turn over several cycles.
+    #[serde(default = "DpuNetworkMonitorConfig::default_max_peers")]
+    pub max_peers: u32,
+}
+
+impl DpuNetworkMonitorConfig {
+    pub const fn default_probe_count() -> u32 {
+        5
+    }
+
+    pub const fn default_probe_timeout() -> std::time::Duration {
+        std::time::Duration::from_secs(1)
+    }
+
+    pub const fn default_peer_refresh_interval() -> std::time::Duration {
+        std::time::Duration::from_secs(30 * 60)
+    }
+
+    pub const fn default_max_peers() -> u32 {
+        64
+    }
+}
+
+impl Default for DpuNetworkMonitorConfig {
+    fn default() -> Self {
+        DpuNetworkMonitorConfig {
+            probe_count: Self::default_probe_count(),
+            probe_timeout: Self::default_probe_timeout(),
+            peer_refresh_interval: Self::default_peer_refresh_interval(),
+            tcp_ports: Vec::new(),
+            udp_ports: Vec::new(),
+            vpc_overlay_probes: false,
+            report_results: default_to_true(),
+            max_peers: Self::default_max_peers(),
+        }
+    }
+}
+
+impl From<&DpuNetworkMonitorConfig> for rpc::forge::DpuNetworkMonitorConfig {
+    fn from(config: &DpuNetworkMonitorConfig) -> Self {
+        let port_probes = |protocol: rpc::forge::DpuNetworkProbeProtocol, ports: &[u16]| {
+            ports
+                .iter()
+                .map(|port| rpc::forge::DpuNetworkProbe {
+                    protocol: protocol as i32,
+                    port: Some(u32::from(*port)),
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let mut probes = vec![rpc::forge::DpuNetworkProbe {
+            protocol: rpc::forge::DpuNetworkProbeProtocol::Icmp as i32,
+            port: None,
+        }];
+        probes.extend(port_probes(
+            rpc::forge::DpuNetworkProbeProtocol::Tcp,
+            &config.tcp_ports,
+        ));
+        probes.extend(port_probes(
+            rpc::forge::DpuNetworkProbeProtocol::Udp,
+            &config.udp_ports,
+        ));
+
+        rpc::forge::DpuNetworkMonitorConfig {
+            probe_count: config.probe_count,
+            probe_timeout_ms: config
+                .probe_timeout
+                .as_millis()
+                .try_into()
+                .unwrap_or(u32::MAX),
+            peer_refresh_interval_secs: config
+                .peer_refresh_interval
+                .as_secs()
+                .try_into()
+                .unwrap_or(u32::MAX),
+            probes,
+            vpc_overlay_probes: config.vpc_overlay_probes,
+            report_results: config.report_results,
+            max_peers: config.max_peers,
         }
     }
 }
@@ -1866,6 +2302,9 @@ pub struct FirmwareGlobal {
     pub hgx_bmc_gpu_reboot_delay: Duration,
     #[serde(default)]
     pub requires_manual_upgrade: bool,
+    /// Storage of downloaded firmware artifacts
+    #[serde(default)]
+    pub cache: FirmwareCacheConfig,
 }
 
 impl FirmwareGlobal {
@@ -1884,6 +2323,7 @@ impl FirmwareGlobal {
             no_reset_retries: false,
             hgx_bmc_gpu_reboot_delay: FirmwareGlobal::hgx_bmc_gpu_reboot_delay_default(),
             requires_manual_upgrade: false,
+            cache: FirmwareCacheConfig::default(),
         }
     }
 
@@ -1903,6 +2343,87 @@ pub struct MachineUpdater {
     /// The maximum percentage of machines that have in-progress updates running.  This prevents
     /// too many machines from being put into maintenance at any given time.  If both values are given, the lesser will be used.
     pub max_concurrent_machine_updates_percent: Option<i32>,
+    /// Stage machine updates in waves.  If not set, updates are started site-wide up to the limits above.
+    #[serde(default)]
+    pub rollout: Option<MachineUpdateRolloutPolicy>,
+}
+
+/// How machine updates are rolled out across the site.  A rollout starts with a canary wave of a
+/// few hosts, followed by waves which each cover a larger percentage of all hosts.  After all
+/// updates of a wave finished, the next wave only starts once the soak period has passed.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct MachineUpdateRolloutPolicy {
+    /// The number of hosts updated in the first wave.  No canary wave is used if 0.
+    #[serde(default)]
+    pub canary_count: u32,
+    /// The percentage of all hosts which may have been updated by the end of each following wave.
+    /// A last wave covering all hosts is added if the last percentage is below 100.
+    #[serde(default)]
+    pub wave_percentages: Vec<u32>,
+    /// How long to wait after all updates of a wave finished before starting the next wave.
+    /// Defaults to 1 hour.
+    #[serde(
+        default = "MachineUpdateRolloutPolicy::soak_period_default",
+        deserialize_with = "deserialize_duration_chrono",
+        serialize_with = "as_duration"
+    )]
+    pub soak_period: Duration,
+    /// The maximum number of hosts within a rack which are updated at the same time.
+    #[serde(default)]
+    pub max_concurrent_updates_per_rack: Option<u32>,
+    /// The maximum number of hosts of a SKU which are updated at the same time.
+    #[serde(default)]
+    pub max_concurrent_updates_per_sku: Option<u32>,
+    /// Halt the rollout once more than this percentage of the hosts it updated are unhealthy.
+    #[serde(default)]
+    pub halt_unhealthy_percent: Option<u32>,
+}
+
+impl MachineUpdateRolloutPolicy {
+    pub fn soak_period_default() -> Duration {
+        Duration::hours(1)
+    }
+
+    /// The percentage of hosts each wave after the canary wave allows, ending with 100
+    fn percentages(&self) -> Vec<u32> {
+        let mut percentages: Vec<u32> = self
+            .wave_percentages
+            .iter()
+            .map(|percent| (*percent).min(100))
+            .collect();
+        if percentages.last().is_none_or(|last| *last < 100) {
+            percentages.push(100);
+        }
+        percentages
+    }
+
+    pub fn wave_count(&self) -> u32 {
+        let canary = u32::from(self.canary_count > 0);
+        canary + self.percentages().len() as u32
+    }
+
+    /// The number of hosts which may have been updated by the rollout once the given wave is done
+    pub fn wave_target(&self, wave: u32, total_hosts: usize) -> usize {
+        let canary = self.canary_count as usize;
+        let mut target = 0;
+        let mut stages = (self.canary_count > 0).then_some(canary).into_iter().chain(
+            self.percentages()
+                .into_iter()
+                .map(|percent| (percent as usize * total_hosts).div_ceil(100)),
+        );
+        for _ in 0..=wave {
+            match stages.next() {
+                // Later waves never allow less than earlier ones
+                Some(stage_target) => target = target.max(stage_target),
+                None => return total_hosts.max(target),
+            }
+        }
+        target
+    }
+
+    pub fn is_last_wave(&self, wave: u32) -> bool {
+        wave + 1 >= self.wave_count()
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
@@ -1951,6 +2472,79 @@ impl Default for FirmwareGlobal {
             no_reset_retries: false,
             hgx_bmc_gpu_reboot_delay: FirmwareGlobal::hgx_bmc_gpu_reboot_delay_default(),
             requires_manual_upgrade: false,
+            cache: FirmwareCacheConfig::default(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct FirmwareCacheConfig {
+    /// Where downloaded firmware artifacts are stored, addressed by their SHA-256 digest.
+    /// The files referenced by the firmware configs are linked to the stored artifacts.
+    #[serde(default = "FirmwareCacheConfig::directory_default")]
+    pub directory: PathBuf,
+    /// The disk space artifacts may use. Artifacts which are not referenced by any desired
+    /// firmware get evicted, least recently used first, once the quota is exceeded.
+    /// Default is 100 GiB.
+    #[serde(default = "FirmwareCacheConfig::max_size_bytes_default")]
+    pub max_size_bytes: u64,
+    /// How many artifacts are downloaded at the same time.
+    /// Default is 4.
+    #[serde(default = "FirmwareCacheConfig::max_concurrent_downloads_default")]
+    pub max_concurrent_downloads: usize,
+    /// How often a download is attempted before giving up until the next request.
+    /// Interrupted downloads are resumed where they stopped.
+    /// Default is 5.
+    #[serde(default = "FirmwareCacheConfig::download_attempts_default")]
+    pub download_attempts: u32,
+    /// The delay before the first retry of a download, doubled for every further retry.
+    /// Defaults to 5 seconds.
+    #[serde(
+        default = "FirmwareCacheConfig::retry_backoff_default",
+        deserialize_with = "deserialize_duration",
+        serialize_with = "as_std_duration"
+    )]
+    pub retry_backoff: std::time::Duration,
+    /// The interval at which artifacts exceeding the quota are evicted.
+    /// Defaults to 1 hour.
+    #[serde(
+        default = "FirmwareCacheConfig::gc_interval_default",
+        deserialize_with = "deserialize_duration",
+        serialize_with = "as_std_duration"
+    )]
+    pub gc_interval: std::time::Duration,
+}
+
+impl FirmwareCacheConfig {
+    pub fn directory_default() -> PathBuf {
+        PathBuf::from("/opt/carbide/firmware-cache")
+    }
+    pub const fn max_size_bytes_default() -> u64 {
+        100 * 1024 * 1024 * 1024
+    }
+    pub const fn max_concurrent_downloads_default() -> usize {
+        4
+    }
+    pub const fn download_attempts_default() -> u32 {
+        5
+    }
+    pub const fn retry_backoff_default() -> std::time::Duration {
+        std::time::Duration::from_secs(5)
+    }
+    pub const fn gc_interval_default() -> std::time::Duration {
+        std::time::Duration::from_secs(3600)
+    }
+}
+
+impl Default for FirmwareCacheConfig {
+    fn default() -> Self {
+        Self {
+            directory: Self::directory_default(),
+            max_size_bytes: Self::max_size_bytes_default(),
+            max_concurrent_downloads: Self::max_concurrent_downloads_default(),
+            download_attempts: Self::download_attempts_default(),
+            retry_backoff: Self::retry_backoff_default(),
+            gc_interval: Self::gc_interval_default(),
         }
     }
 }
@@ -2210,6 +2804,39 @@ impl MeasuredBootMetricsCollectorConfig {
     }
 }
 
+/// CapacityReservationMetricsCollector related configuration
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct CapacityReservationMetricsConfig {
+    /// enabled controls whether the utilization of capacity
+    /// reservations is exported as metrics.
+    #[serde(default = "default_to_true")]
+    pub enabled: bool,
+    /// run_interval is the interval at which the collector polls
+    /// for the latest data, in seconds.
+    /// Defaults to 60 if not specified.
+    #[serde(
+        default = "CapacityReservationMetricsConfig::default_run_interval",
+        deserialize_with = "deserialize_duration",
+        serialize_with = "as_std_duration"
+    )]
+    pub run_interval: std::time::Duration,
+}
+
+impl Default for CapacityReservationMetricsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            run_interval: Self::default_run_interval(),
+        }
+    }
+}
+
+impl CapacityReservationMetricsConfig {
+    const fn default_run_interval() -> std::time::Duration {
+        std::time::Duration::from_secs(60)
+    }
+}
+
 /// Settings related to an IB fabric
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
 pub struct IbFabricDefinition {
@@ -2884,6 +3511,10 @@ mod tests {
         );
         assert_eq!(config.max_find_by_ids, default_max_find_by_ids());
         assert_eq!(config.dpu_network_monitor_pinger_type, None);
+        assert_eq!(
+            config.dpu_network_monitor,
+            DpuNetworkMonitorConfig::default()
+        );
         assert_eq!(config.measured_boot_collector, {
             MeasuredBootMetricsCollectorConfig {
                 enabled: false,
@@ -3028,6 +3659,17 @@ mod tests {
             config.dpu_network_monitor_pinger_type,
             Some("OobNetBind".to_string())
         );
+        assert_eq!(
+            config.dpu_network_monitor,
+            DpuNetworkMonitorConfig {
+                probe_count: 10,
+                probe_timeout: std::time::Duration::from_millis(500),
+                tcp_ports: vec![179],
+                vpc_overlay_probes: true,
+                max_peers: 16,
+                ..Default::default()
+            }
+        );
     }
 
     #[test]
@@ -3200,6 +3842,15 @@ mod tests {
         }
         assert_eq!(config.firmware_global.max_uploads, 3);
         assert_eq!(config.firmware_global.run_interval, Duration::seconds(20));
+        assert_eq!(
+            config.firmware_global.cache,
+            FirmwareCacheConfig {
+                directory: PathBuf::from("/var/cache/carbide/firmware"),
+                max_size_bytes: 1024 * 1024 * 1024,
+                retry_backoff: std::time::Duration::from_secs(10),
+                ..Default::default()
+            }
+        );
         assert_eq!(config.max_find_by_ids, 75);
         assert_eq!(config.dpu_network_monitor_pinger_type, None);
         assert_eq!(
@@ -3750,6 +4401,44 @@ max_partition_per_tenant = 3
         Ok(())
     }
 
+    #[test]
+    fn test_rollout_wave_targets() {
+        let policy: MachineUpdateRolloutPolicy = Figment::new()
+            .merge(Toml::string(
+                r#"
+canary_count = 2
+wave_percentages = [10, 50]
+soak_period = "30m"
+max_concurrent_updates_per_rack = 1
+"#,
+            ))
+            .extract()
+            .unwrap();
+        assert_eq!(policy.soak_period, Duration::minutes(30));
+        assert_eq!(policy.max_concurrent_updates_per_sku, None);
+
+        // Canary, 10%, 50% and an implicit 100% wave
+        assert_eq!(policy.wave_count(), 4);
+        let targets: Vec<usize> = (0..5).map(|wave| policy.wave_target(wave, 95)).collect();
+        assert_eq!(targets, vec![2, 10, 48, 95, 95]);
+        assert!(policy.is_last_wave(3));
+        assert!(!policy.is_last_wave(2));
+
+        // The canary wave can be larger than the following percentage
+        assert_eq!(policy.wave_target(1, 5), 2);
+
+        let policy = MachineUpdateRolloutPolicy {
+            canary_count: 0,
+            wave_percentages: vec![],
+            soak_period: MachineUpdateRolloutPolicy::soak_period_default(),
+            max_concurrent_updates_per_rack: None,
+            max_concurrent_updates_per_sku: None,
+            halt_unhealthy_percent: None,
+        };
+        assert_eq!(policy.wave_count(), 1);
+        assert_eq!(policy.wave_target(0, 7), 7);
+    }
+
     #[test]
     fn deserialize_dpa_config() {
         let toml = r#"
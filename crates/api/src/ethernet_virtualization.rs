@@ -17,6 +17,8 @@
 use ::rpc::forge as rpc;
 use carbide_uuid::instance::InstanceId;
 use carbide_uuid::machine::{MachineId, MachineInterfaceId};
+use carbide_uuid::network_security_group::NetworkSecurityGroupId;
+use carbide_uuid::vpc::VpcId;
 use db::vpc::{self};
 use db::vpc_peering::get_prefixes_by_vpcs;
 use db::{self, ObjectColumnFilter, network_security_group};
@@ -24,10 +26,11 @@ use forge_network::virtualization::{VpcVirtualizationType, get_svi_ip};
 use ipnetwork::{IpNetwork, Ipv4Network};
 use model::instance::config::network::{InstanceInterfaceConfig, InterfaceFunctionId};
 use model::network_security_group::{
-    NetworkSecurityGroup, NetworkSecurityGroupRule, NetworkSecurityGroupRuleNet,
+    NetworkSecurityGroup, NetworkSecurityGroupRule, NetworkSecurityGroupRuleNetPrefixes,
 };
 use model::network_segment::NetworkSegment;
 use model::resource_pool::common::CommonPools;
+use model::tenant::TenantOrganizationId;
 use sqlx::PgConnection;
 use tonic::Status;
 
@@ -134,6 +137,20 @@ pub async fn admin_network(
 
     let address = db::machine_interface_address::find_ipv4_for_interface(txn, interface.id).await?;
 
+    // An IPv6 address is only handed out when the admin segment is dual-stack.
+    let ipv6 = match admin_segment
+        .prefixes
+        .iter()
+        .find(|prefix| prefix.prefix.is_ipv6())
+    {
+        Some(v6_prefix) => {
+            db::machine_interface_address::find_ipv6_for_interface(txn, interface.id)
+                .await?
+                .map(|address| (address.address.to_string(), v6_prefix.prefix.to_string()))
+        }
+        None => None,
+    };
+
     // On the admin network, the interface_prefix is always
     // just going to be a /32 derived from the machine interface
     // address.
@@ -234,6 +251,8 @@ pub async fn admin_network(
         network_security_group: None,
         internal_uuid: None,
         mtu: u32::try_from(admin_segment.mtu).ok(),
+        ipv6_prefix: ipv6.as_ref().map(|(_, prefix)| prefix.clone()),
+        ipv6: ipv6.map(|(address, _)| address),
     };
     Ok((cfg, interface.id))
 }
@@ -252,6 +271,7 @@ pub async fn tenant_network(
     segment: &NetworkSegment,
     vpc_peering_policy_on_existing: Option<VpcPeeringPolicy>,
     booturl: &Option<String>,
+    max_network_security_group_size: usize,
 ) -> Result<rpc::FlatInterfaceConfig, tonic::Status> {
     // Any stretchable segment is treated as L2 segment by FNN.
     let is_l2_segment = segment.can_stretch.unwrap_or(true);
@@ -292,6 +312,17 @@ pub async fn tenant_network(
         .get(&v4_prefix.id)
         .unwrap_or(&default_prefix);
 
+    let v6_prefix = segment
+        .prefixes
+        .iter()
+        .find(|prefix| prefix.prefix.is_ipv6());
+    let ipv6 = v6_prefix.and_then(|v6_prefix| {
+        iface
+            .ip_addrs
+            .get(&v6_prefix.id)
+            .map(|address| (address.to_string(), v6_prefix.prefix.to_string()))
+    });
+
     let vpc_prefixes: Vec<String> = match segment.vpc_id {
         Some(vpc_id) => {
             let vpc_prefixes = db::vpc_prefix::find_by_vpc(txn, vpc_id)
@@ -439,6 +470,35 @@ pub async fn tenant_network(
         _ => None,
     };
 
+    // Expand object references in the rules.  A group which would exceed the
+    // limits after expansion is left out of this interface only, so that the
+    // rest of the DPU configuration still gets updated.  The
+    // NetworkSecurityGroupLimitMonitor reports the problem on the group and
+    // raises a health alert on the DPU.
+    let network_security_group_details = match network_security_group_details {
+        Some((source, nsg)) => {
+            let prefixes =
+                load_rule_net_prefixes(txn, &nsg.rules, Some(&nsg.tenant_organization_id)).await?;
+
+            match network_security_group_limit_error(
+                &nsg.rules,
+                &prefixes,
+                max_network_security_group_size as u64,
+            ) {
+                Some(error) => {
+                    tracing::warn!(
+                        network_security_group_id = %nsg.id,
+                        %instance_id,
+                        "Not applying network security group to interface: {error}"
+                    );
+                    None
+                }
+                None => Some((source, nsg, prefixes)),
+            }
+        }
+        None => None,
+    };
+
     Ok(rpc::FlatInterfaceConfig {
         function_type: rpc_ft.into(),
         virtual_function_id: match iface.function_id {
@@ -463,7 +523,7 @@ pub async fn tenant_network(
         vpc_peer_prefixes,
         vpc_peer_vnis,
         network_security_group: network_security_group_details
-            .map(|(source, nsg)| {
+            .map(|(source, nsg, prefixes)| {
                 Ok(
                         rpc::FlatInterfaceNetworkSecurityGroupConfig {
                             id: nsg.id.to_string(),
@@ -473,7 +533,7 @@ pub async fn tenant_network(
                             rules:
                                 nsg.rules
                                     .into_iter()
-                                    .map(resolve_security_group_rule)
+                                    .map(|rule| resolve_security_group_rule(rule, &prefixes))
                                     .collect::<Result<
                                         Vec<rpc::ResolvedNetworkSecurityGroupRule>,
                                         CarbideError,
@@ -489,26 +549,124 @@ pub async fn tenant_network(
             })?,
         internal_uuid: Some(iface.internal_uuid.into()),
         mtu: u32::try_from(segment.mtu).ok(),
+        ipv6_prefix: ipv6.as_ref().map(|(_, prefix)| prefix.clone()),
+        ipv6: ipv6.map(|(address, _)| address),
     })
 }
 
+/// Loads the prefixes of the VPCs and VPC prefixes referenced by a set of rules.
+/// If a tenant org is given, only references to objects of that tenant are resolved.
+pub async fn load_rule_net_prefixes(
+    txn: &mut PgConnection,
+    rules: &[NetworkSecurityGroupRule],
+    tenant_organization_id: Option<&TenantOrganizationId>,
+) -> Result<NetworkSecurityGroupRuleNetPrefixes, CarbideError> {
+    let (vpc_ids, vpc_prefix_ids) = NetworkSecurityGroupRuleNetPrefixes::references(rules);
+    if vpc_ids.is_empty() && vpc_prefix_ids.is_empty() {
+        return Ok(NetworkSecurityGroupRuleNetPrefixes::default());
+    }
+
+    Ok(network_security_group::find_rule_net_prefixes(
+        txn,
+        &vpc_ids,
+        &vpc_prefix_ids,
+        tenant_organization_id,
+    )
+    .await?)
+}
+
+/// Checks that the network security groups referencing a VPC still fit the limit once the VPC
+/// gains `new_prefixes`, either through a new VPC prefix or a new segment outside of VPC prefixes.
+pub async fn validate_vpc_growth(
+    txn: &mut PgConnection,
+    vpc_id: &VpcId,
+    new_prefixes: &[IpNetwork],
+    limit: u64,
+) -> Result<(), CarbideError> {
+    if new_prefixes.is_empty() {
+        return Ok(());
+    }
+    let nsg_ids = network_security_group::find_ids_referencing_vpc(txn, vpc_id).await?;
+    if nsg_ids.is_empty() {
+        return Ok(());
+    }
+
+    for nsg in network_security_group::find_by_ids(txn, &nsg_ids, None, false).await? {
+        let mut prefixes =
+            load_rule_net_prefixes(txn, &nsg.rules, Some(&nsg.tenant_organization_id)).await?;
+
+        // Groups of other tenants can't resolve the VPC anyway.
+        let Some(vpc_prefixes) = prefixes.vpcs.get_mut(vpc_id) else {
+            continue;
+        };
+        vpc_prefixes.extend_from_slice(new_prefixes);
+
+        let expanded_size = prefixes.expanded_size(&nsg.rules);
+        if expanded_size > limit {
+            return Err(CarbideError::InvalidArgument(format!(
+                "Adding {prefixes} to VPC {vpc_id} would expand network security group \
+                {nsg_id} to {expanded_size} rules, which exceeds the maximum of {limit}",
+                prefixes = itertools::join(new_prefixes, ", "),
+                nsg_id = nsg.id,
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Rejects the deletion of an object which rules of network security groups still refer to.
+/// The rules would otherwise silently lose the object's prefixes.
+pub fn validate_unreferenced(
+    kind: &str,
+    id: impl std::fmt::Display,
+    nsg_ids: &[NetworkSecurityGroupId],
+) -> Result<(), CarbideError> {
+    if nsg_ids.is_empty() {
+        return Ok(());
+    }
+    Err(CarbideError::FailedPrecondition(format!(
+        "{kind} {id} is referenced by the rules of network security groups {}",
+        itertools::join(nsg_ids, ", ")
+    )))
+}
+
+/// Returns why a set of rules can't be applied to a DPU, if it exceeds the limit after expansion.
+/// Only rules with object references can grow after their size was validated on create/update.
+pub fn network_security_group_limit_error(
+    rules: &[NetworkSecurityGroupRule],
+    prefixes: &NetworkSecurityGroupRuleNetPrefixes,
+    limit: u64,
+) -> Option<String> {
+    if !rules
+        .iter()
+        .any(|rule| rule.src_net.is_reference() || rule.dst_net.is_reference())
+    {
+        return None;
+    }
+
+    let expanded_size = prefixes.expanded_size(rules);
+    (expanded_size > limit)
+        .then(|| format!("expands to {expanded_size} rules, which exceeds the maximum of {limit}"))
+}
+
 pub fn resolve_security_group_rule(
     rule: NetworkSecurityGroupRule,
+    prefixes: &NetworkSecurityGroupRuleNetPrefixes,
 ) -> Result<rpc::ResolvedNetworkSecurityGroupRule, CarbideError> {
     Ok(rpc::ResolvedNetworkSecurityGroupRule {
-        // When we decide to allow object references,
-        // they would be resolved to their actual prefix
-        // lists and stored here.
-        src_prefixes: match rule.src_net {
-            NetworkSecurityGroupRuleNet::Prefix(ref p) => {
-                vec![p.to_string()]
-            }
-        },
-        dst_prefixes: match rule.dst_net {
-            NetworkSecurityGroupRuleNet::Prefix(ref p) => {
-                vec![p.to_string()]
-            }
-        },
+        // Object references are resolved to their actual
+        // prefix lists, matching the IP version of the rule.
+        src_prefixes: prefixes
+            .resolve(&rule.src_net, rule.ipv6)
+            .iter()
+            .map(|p| p.to_string())
+            .collect(),
+        dst_prefixes: prefixes
+            .resolve(&rule.dst_net, rule.ipv6)
+            .iter()
+            .map(|p| p.to_string())
+            .collect(),
         rule: Some(rule.try_into()?),
     })
 }
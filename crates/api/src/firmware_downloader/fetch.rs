@@ -0,0 +1,228 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Downloads of firmware artifacts, retried with backoff and resumed where they stopped
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use eyre::{Report, WrapErr, eyre};
+use futures_util::StreamExt;
+use reqwest::{Client, StatusCode, header};
+use tokio::fs::{File, OpenOptions};
+use tokio::io::AsyncWriteExt;
+
+use super::metrics::FirmwareCacheMetrics;
+
+/// Retries are never delayed longer than this, regardless of how often they failed
+const MAX_RETRY_BACKOFF: Duration = Duration::from_secs(300);
+
+#[derive(Debug, Clone)]
+pub struct FetchOptions {
+    pub attempts: u32,
+    pub retry_backoff: Duration,
+    /// For testing only, wait the given amount of time then write an empty file
+    pub fake_sleep: Option<Duration>,
+}
+
+/// Downloads url into the partial file, continuing the download a previous attempt left in it
+pub async fn fetch(
+    client: &Client,
+    url: &str,
+    partial: &Path,
+    options: &FetchOptions,
+    metrics: &FirmwareCacheMetrics,
+) -> Result<(), Report> {
+    let attempts = options.attempts.max(1);
+    let mut backoff = options.retry_backoff;
+    let mut attempt = 1;
+    loop {
+        match fetch_once(client, url, partial, options.fake_sleep, metrics).await {
+            Ok(()) => return Ok(()),
+            Err(e) if attempt >= attempts => {
+                return Err(e.wrap_err(format!("Giving up after {attempt} attempts")));
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "Attempt {attempt} of {attempts} to download {url} failed, retrying in {backoff:?}: {e:#}"
+                );
+                tokio::time::sleep(backoff).await;
+                backoff = backoff.saturating_mul(2).min(MAX_RETRY_BACKOFF);
+                attempt += 1;
+            }
+        }
+    }
+}
+
+async fn fetch_once(
+    client: &Client,
+    url: &str,
+    partial: &Path,
+    fake_sleep: Option<Duration>,
+    metrics: &FirmwareCacheMetrics,
+) -> Result<(), Report> {
+    if let Some(dirname) = partial.parent() {
+        tokio::fs::create_dir_all(dirname)
+            .await
+            .wrap_err(format!("Could not create {dirname:?}"))?;
+    }
+
+    if let Some(duration) = fake_sleep {
+        File::create(partial)
+            .await
+            .wrap_err(format!("Unable to create file {partial:?}"))?;
+        tokio::time::sleep(duration).await;
+        return Ok(());
+    }
+
+    if let Some(src_filename) = url.strip_prefix("file:/") {
+        // Just copies a local file, for testing. Leaves the second / for the root
+        let mut src_file = File::open(src_filename)
+            .await
+            .wrap_err(format!("FirmwareDownloader could not open source {url}"))?;
+        let mut dst_file = File::create(partial)
+            .await
+            .wrap_err(format!("Unable to create file {partial:?}"))?;
+        let copied = tokio::io::copy(&mut src_file, &mut dst_file)
+            .await
+            .map_err(|e| eyre!("FirmwareDownloader had problems saving file from {url}: {e}"))?;
+        metrics.bytes_fetched.fetch_add(copied, Ordering::Relaxed);
+        return Ok(());
+    }
+
+    // A partial download is only resumed if the server still has the same version of the file,
+    // which If-Range checks against the validator of the response the download started with
+    let validator_path = validator_path(partial);
+    let validator = tokio::fs::read_to_string(&validator_path).await.ok();
+    let offset = match validator {
+        Some(_) => tokio::fs::metadata(partial)
+            .await
+            .map(|metadata| metadata.len())
+            .unwrap_or_default(),
+        None => 0,
+    };
+    let mut request = client.get(url);
+    if let Some(validator) = validator.filter(|_| offset > 0) {
+        request = request
+            .header(header::RANGE, format!("bytes={offset}-"))
+            .header(header::IF_RANGE, validator);
+    }
+    let res = request.send().await.wrap_err(format!(
+        "FirmwareDownloader got error trying to download {url}"
+    ))?;
+
+    let mut dst_file = match res.status() {
+        StatusCode::PARTIAL_CONTENT if offset > 0 => {
+            tracing::info!("Resuming download of {url} at byte {offset}");
+            OpenOptions::new()
+                .append(true)
+                .open(partial)
+                .await
+                .wrap_err(format!("Unable to open file {partial:?}"))?
+        }
+        StatusCode::RANGE_NOT_SATISFIABLE if offset > 0 => {
+            // What we have doesn't fit to what the server has (anymore), start over
+            let _ = tokio::fs::remove_file(partial).await;
+            let _ = tokio::fs::remove_file(&validator_path).await;
+            return Err(eyre!(
+                "FirmwareDownloader could not resume download of {url} at byte {offset}"
+            ));
+        }
+        // Also covers servers which ignore the range, or send everything because the file changed
+        status if status.is_success() => {
+            let file = File::create(partial)
+                .await
+                .wrap_err(format!("Unable to create file {partial:?}"))?;
+            match response_validator(res.headers()) {
+                Some(validator) => tokio::fs::write(&validator_path, validator)
+                    .await
+                    .wrap_err(format!("Unable to create file {validator_path:?}"))?,
+                None => {
+                    // Without a validator the download can't be resumed safely
+                    let _ = tokio::fs::remove_file(&validator_path).await;
+                }
+            }
+            file
+        }
+        status => {
+            return Err(eyre!(
+                "FirmwareDownloader got non-success status trying to download {url}: {status}"
+            ));
+        }
+    };
+
+    let mut body = res.bytes_stream();
+    while let Some(segment) = body.next().await {
+        let segment =
+            segment.map_err(|e| eyre!("FirmwareDownloader had problems downloading {url}: {e}"))?;
+        dst_file.write_all(&segment).await.wrap_err(format!(
+            "FirmwareDownloader had problems saving file from {url}"
+        ))?;
+        metrics
+            .bytes_fetched
+            .fetch_add(segment.len() as u64, Ordering::Relaxed);
+    }
+    dst_file.flush().await.wrap_err(format!(
+        "FirmwareDownloader had problems saving file from {url}"
+    ))?;
+    let _ = tokio::fs::remove_file(&validator_path).await;
+
+    // Success
+    Ok(())
+}
+
+/// Where the validator of the response a partial download started with is kept
+fn validator_path(partial: &Path) -> PathBuf {
+    let mut path = partial.as_os_str().to_owned();
+    path.push(".validator");
+    PathBuf::from(path)
+}
+
+/// Returns the strong ETag of a response or, lacking one, its Last-Modified date. Weak ETags
+/// can't be used with If-Range.
+fn response_validator(headers: &header::HeaderMap) -> Option<String> {
+    let header_value =
+        |name: header::HeaderName| headers.get(name).and_then(|value| value.to_str().ok());
+    header_value(header::ETAG)
+        .filter(|etag| !etag.starts_with("W/"))
+        .or_else(|| header_value(header::LAST_MODIFIED))
+        .map(str::to_owned)
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::header::{ETAG, HeaderMap, HeaderValue, LAST_MODIFIED};
+
+    use super::*;
+
+    #[test]
+    fn test_response_validator() {
+        let last_modified = "Wed, 21 Oct 2015 07:28:00 GMT";
+        let mut headers = HeaderMap::new();
+        assert_eq!(response_validator(&headers), None);
+
+        headers.insert(LAST_MODIFIED, HeaderValue::from_static(last_modified));
+        assert_eq!(response_validator(&headers).as_deref(), Some(last_modified));
+
+        headers.insert(ETAG, HeaderValue::from_static("W/\"weak\""));
+        assert_eq!(response_validator(&headers).as_deref(), Some(last_modified));
+
+        headers.insert(ETAG, HeaderValue::from_static("\"strong\""));
+        assert_eq!(response_validator(&headers).as_deref(), Some("\"strong\""));
+    }
+}
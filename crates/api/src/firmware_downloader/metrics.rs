@@ -0,0 +1,123 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Metrics of the firmware artifact cache
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use opentelemetry::metrics::Meter;
+
+/// Counters of the firmware artifact cache. They are tracked even when no meter is available,
+/// and exported as observable instruments once registered with a meter.
+#[derive(Debug, Default)]
+pub struct FirmwareCacheMetrics {
+    /// Requests for firmware served from local storage
+    pub cache_hits: AtomicU64,
+    /// Requests for firmware which required a download
+    pub cache_misses: AtomicU64,
+    /// Bytes of firmware fetched from remote URLs
+    pub bytes_fetched: AtomicU64,
+    /// Firmware files or stored artifacts which did not match their checksum
+    pub verification_failures: AtomicU64,
+    /// Artifacts removed by garbage collection
+    pub evictions: AtomicU64,
+    /// Bytes used by all stored artifacts
+    pub stored_bytes: AtomicU64,
+}
+
+impl FirmwareCacheMetrics {
+    pub fn increment(counter: &AtomicU64) {
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn register(self: &Arc<Self>, meter: &Meter) {
+        {
+            let metrics = self.clone();
+            meter
+                .u64_observable_counter("carbide_firmware_cache_hits")
+                .with_description(
+                    "The amount of firmware requests served from the local artifact cache",
+                )
+                .with_callback(move |observer| {
+                    observer.observe(metrics.cache_hits.load(Ordering::Relaxed), &[]);
+                })
+                .build();
+        }
+
+        {
+            let metrics = self.clone();
+            meter
+                .u64_observable_counter("carbide_firmware_cache_misses")
+                .with_description("The amount of firmware requests which required a download")
+                .with_callback(move |observer| {
+                    observer.observe(metrics.cache_misses.load(Ordering::Relaxed), &[]);
+                })
+                .build();
+        }
+
+        {
+            let metrics = self.clone();
+            meter
+                .u64_observable_counter("carbide_firmware_cache_fetched_bytes")
+                .with_description("The amount of firmware bytes downloaded from remote URLs")
+                .with_unit("By")
+                .with_callback(move |observer| {
+                    observer.observe(metrics.bytes_fetched.load(Ordering::Relaxed), &[]);
+                })
+                .build();
+        }
+
+        {
+            let metrics = self.clone();
+            meter
+                .u64_observable_counter("carbide_firmware_cache_verification_failures")
+                .with_description(
+                    "The amount of firmware files and cached artifacts which did not match their checksum",
+                )
+                .with_callback(move |observer| {
+                    observer.observe(metrics.verification_failures.load(Ordering::Relaxed), &[]);
+                })
+                .build();
+        }
+
+        {
+            let metrics = self.clone();
+            meter
+                .u64_observable_counter("carbide_firmware_cache_evictions")
+                .with_description(
+                    "The amount of firmware artifacts removed from the cache to stay within its quota",
+                )
+                .with_callback(move |observer| {
+                    observer.observe(metrics.evictions.load(Ordering::Relaxed), &[]);
+                })
+                .build();
+        }
+
+        {
+            let metrics = self.clone();
+            meter
+                .u64_observable_gauge("carbide_firmware_cache_size_bytes")
+                .with_description("The disk space used by cached firmware artifacts")
+                .with_unit("By")
+                .with_callback(move |observer| {
+                    observer.observe(metrics.stored_bytes.load(Ordering::Relaxed), &[]);
+                })
+                .build();
+        }
+    }
+}
@@ -15,30 +15,88 @@
  * limitations under the License.
  */
 
-// Coordinates downloading firmware in the background with multiple possible requestors
-
-use std::collections::HashSet;
-use std::path::Path;
+// Coordinates downloading firmware in the background with multiple possible requestors.
+//
+// Downloads end up in a content addressed artifact store (see store.rs), and the firmware files
+// referenced by the firmware configs are linked to the stored artifacts. Files are hashed and
+// verified against their configured checksum, and verified again whenever their inode, length or
+// modification time changed, so that a file which changed on disk is never handed out based on an
+// earlier verification. Artifacts not referenced by any desired firmware are evicted, least
+// recently used first, once the store exceeds its quota.
+
+mod fetch;
+mod metrics;
+mod store;
+
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
 
+use db::Transaction;
 use eyre::{Report, WrapErr, eyre};
-use futures_util::StreamExt;
+use fetch::FetchOptions;
+use metrics::FirmwareCacheMetrics;
+use model::firmware::FirmwareEntry;
+use opentelemetry::metrics::Meter;
 use reqwest::Client;
-use tokio::fs::File;
+use sqlx::PgPool;
+use store::{ArtifactStore, ExpectedChecksum, FileDigests, GcReport, ReferencedArtifacts};
+use tokio::sync::{Semaphore, oneshot};
+
+use crate::CarbideResult;
+use crate::cfg::file::{FirmwareCacheConfig, FirmwareConfig};
 
 #[derive(Clone, Debug)]
 pub struct FirmwareDownloader {
     // Actual structure wrapped in an Arc so that we can clone the FirmwareDownloader and have the clones all point to one instance.
     actual: Arc<Mutex<FirmwareDownloaderActual>>,
+    cache: Arc<FirmwareCache>,
 }
 
 #[derive(Debug)]
 struct FirmwareDownloaderActual {
-    downloading: HashSet<String>,
+    /// Files which are currently downloaded, linked or verified
+    downloading: HashSet<PathBuf>,
+    /// Files which were downloaded, linked or verified, with the checksum they matched. An entry
+    /// stays valid as long as the fingerprint of the file doesn't change.
+    verified: HashMap<PathBuf, (FileFingerprint, ExpectedChecksum)>,
+    /// Files which failed verification and have no URL to fetch a replacement from
+    rejected: HashMap<PathBuf, (FileFingerprint, ExpectedChecksum)>,
     client: Option<Client>,
 }
 
+#[derive(Debug)]
+struct FirmwareCache {
+    store: ArtifactStore,
+    config: FirmwareCacheConfig,
+    download_limiter: Semaphore,
+    metrics: Arc<FirmwareCacheMetrics>,
+}
+
+/// Identifies the content of a file without reading it, so that a file is only hashed again once
+/// it was replaced or modified.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct FileFingerprint {
+    inode: u64,
+    len: u64,
+    modified: Option<SystemTime>,
+}
+
+impl FileFingerprint {
+    /// Returns None if the file doesn't exist. Follows links to stored artifacts.
+    fn of(path: &Path) -> Option<Self> {
+        let metadata = std::fs::metadata(path).ok()?;
+        Some(Self {
+            inode: metadata.ino(),
+            len: metadata.len(),
+            modified: metadata.modified().ok(),
+        })
+    }
+}
+
 impl Default for FirmwareDownloader {
     fn default() -> Self {
         Self::new()
@@ -46,18 +104,46 @@ impl Default for FirmwareDownloader {
 }
 
 impl FirmwareDownloader {
+    /// Creates a downloader keeping its artifacts in the temporary directory, without exporting metrics
     pub fn new() -> FirmwareDownloader {
+        let config = FirmwareCacheConfig {
+            directory: std::env::temp_dir().join("carbide-firmware-cache"),
+            ..Default::default()
+        };
+        Self::with_store(config, Arc::new(FirmwareCacheMetrics::default()))
+    }
+
+    pub fn with_config(config: &FirmwareCacheConfig, meter: &Meter) -> FirmwareDownloader {
+        let metrics = Arc::new(FirmwareCacheMetrics::default());
+        metrics.register(meter);
+        Self::with_store(config.clone(), metrics)
+    }
+
+    fn with_store(config: FirmwareCacheConfig, metrics: Arc<FirmwareCacheMetrics>) -> Self {
+        let store = ArtifactStore::open(&config.directory);
+        metrics
+            .stored_bytes
+            .store(store.stored_bytes(), std::sync::atomic::Ordering::Relaxed);
         FirmwareDownloader {
             actual: Arc::new(Mutex::new(FirmwareDownloaderActual {
                 downloading: HashSet::new(),
+                verified: HashMap::new(),
+                rejected: HashMap::new(),
                 client: None, // Not created until we actually need it
             })),
+            cache: Arc::new(FirmwareCache {
+                store,
+                download_limiter: Semaphore::new(config.max_concurrent_downloads.max(1)),
+                config,
+                metrics,
+            }),
         }
     }
 
-    /// available will return true if the given file is present, otherwise it will return false after starting a download in the background.
+    /// available will return true if the given file is present and matches its checksum, otherwise it will return false
+    /// after starting a download or verification in the background.
     /// Anything trying to check the same file while it is downloading will get the exact same result, but will not start a new download.
-    /// It provides no guarantee that the checksum matches other than on the initial download.
+    /// Files are verified again once they changed; files without a checksum are trusted as is.
     pub fn available(&self, filename: &Path, url: &str, checksum: &str) -> bool {
         self.available_actual(filename, url, checksum, None)
     }
@@ -70,185 +156,366 @@ impl FirmwareDownloader {
         checksum: &str,
         fake_sleep: Option<Duration>,
     ) -> bool {
-        if filename.exists() {
-            return true;
-        }
+        let expected = ExpectedChecksum::parse(checksum);
 
-        if url.is_empty() {
-            tracing::error!("Firmware with file not present has no URL: {filename:?}");
+        let mut state = self.actual.lock().unwrap();
+        if state.downloading.contains(filename) {
+            // We are already working on this
             return false;
         }
 
-        let filename_string = filename.to_str().unwrap().to_string();
+        if let Some(fingerprint) = FileFingerprint::of(filename) {
+            let Some(expected) = expected else {
+                // No validation requested
+                drop(state);
+                self.record_hit(url);
+                return true;
+            };
+            let verdict = (fingerprint, expected);
+            if state.verified.get(filename) == Some(&verdict) {
+                // Verified, and unchanged since
+                drop(state);
+                self.record_hit(url);
+                return true;
+            }
+            if state.rejected.get(filename) == Some(&verdict) {
+                // Already failed verification, and there is nothing to replace it with
+                return false;
+            }
+            let (_, expected) = verdict;
+            state.downloading.insert(filename.to_path_buf());
+            drop(state);
+            // The fingerprint is taken before hashing, so that changes while hashing are noticed
+            self.spawn_job(
+                filename,
+                Some((Some(fingerprint), expected.clone())),
+                self.clone()
+                    .verify_file(filename.to_path_buf(), url.to_owned(), expected),
+            );
+            return false;
+        }
 
-        let mut state = self.actual.lock().unwrap();
-        if state.downloading.contains(&filename_string) {
-            // We are already downloading this
+        // The artifact might be stored already, under its checksum or from an earlier download of the URL
+        let stored = expected
+            .as_ref()
+            .and_then(|expected| expected.sha256())
+            .filter(|digest| self.cache.store.contains(digest))
+            .map(str::to_owned)
+            .or_else(|| self.cache.store.digest_for_url(url));
+        if let Some(digest) = stored {
+            state.downloading.insert(filename.to_path_buf());
+            drop(state);
+            self.spawn_job(
+                filename,
+                expected.clone().map(|expected| (None, expected)),
+                self.clone()
+                    .link_stored(filename.to_path_buf(), url.to_owned(), digest, expected),
+            );
             return false;
         }
 
-        // Slight timing hole, recheck for the file
-        if filename.exists() {
-            return true;
+        if url.is_empty() {
+            tracing::error!("Firmware with file not present has no URL: {filename:?}");
+            return false;
         }
 
-        state.downloading.insert(filename_string.clone());
-        if state.client.is_none() {
-            state.client = Some(Client::new());
+        state.downloading.insert(filename.to_path_buf());
+        let client = state.client.get_or_insert_with(Client::new).clone();
+        drop(state);
+        FirmwareCacheMetrics::increment(&self.cache.metrics.cache_misses);
+        self.spawn_job(
+            filename,
+            expected.clone().map(|expected| (None, expected)),
+            self.clone().download(
+                filename.to_path_buf(),
+                url.to_owned(),
+                expected,
+                client,
+                fake_sleep,
+            ),
+        );
+        false
+    }
+
+    fn record_hit(&self, url: &str) {
+        FirmwareCacheMetrics::increment(&self.cache.metrics.cache_hits);
+        if let Some(digest) = self.cache.store.digest_for_url(url) {
+            self.cache.store.touch(&digest);
         }
+    }
 
+    /// Runs a job for filename in the background, and marks the file as verified once it succeeded.
+    /// Every job hashes the file it produces or checks. `expected` holds the fingerprint of the file
+    /// from before it was hashed, if it existed, otherwise the file is fingerprinted afterwards.
+    fn spawn_job(
+        &self,
+        filename: &Path,
+        expected: Option<(Option<FileFingerprint>, ExpectedChecksum)>,
+        job: impl Future<Output = Result<(), Report>> + Send + 'static,
+    ) {
         let filename = filename.to_path_buf();
-        let url = url.to_owned();
-        let client = state.client.clone().unwrap();
         let actual = self.actual.clone();
-        let checksum = checksum.to_owned();
         tokio::spawn(async move {
-            let dst_filename = format!("{filename_string}.download");
-            match download(&filename, &url, &dst_filename, client, fake_sleep).await {
-                Err(e) => {
-                    tracing::error!("FirmwareDownloader failed: {e}");
-                    let _ = std::fs::remove_file(dst_filename);
-                    actual
-                        .lock()
-                        .unwrap()
-                        .clear_download_state(&filename_string);
-                }
-                Ok(_) => {
-                    tracing::info!("Completed download of {url} to {filename_string}");
-                    if let Err(e) = verify_checksum(&dst_filename, &checksum) {
-                        tracing::error!("FirmwareDownloader checksum for {url} failed: {e}");
-                        let _ = std::fs::remove_file(dst_filename);
-                        actual
-                            .lock()
-                            .unwrap()
-                            .clear_download_state(&filename_string);
-                        return;
-                    }
-                    if let Err(e) = std::fs::rename(&dst_filename, &filename) {
-                        tracing::error!("FirmwareDownloader rename failed: {e}");
-                        let _ = std::fs::remove_file(dst_filename);
-                        actual
-                            .lock()
-                            .unwrap()
-                            .clear_download_state(&filename_string);
-                        return;
+            let result = job.await;
+            let mut state = actual.lock().unwrap();
+            state.downloading.remove(&filename);
+            match result {
+                Ok(()) => {
+                    state.rejected.remove(&filename);
+                    if let Some((fingerprint, expected)) = expected
+                        && let Some(fingerprint) =
+                            fingerprint.or_else(|| FileFingerprint::of(&filename))
+                    {
+                        state.verified.insert(filename, (fingerprint, expected));
                     }
-
-                    actual
-                        .lock()
-                        .unwrap()
-                        .clear_download_state(&filename_string);
                 }
-            };
+                Err(e) => tracing::error!("FirmwareDownloader failed for {filename:?}: {e:#}"),
+            }
         });
-        false
     }
-}
 
-impl FirmwareDownloaderActual {
-    fn clear_download_state(&mut self, filename: &String) {
-        self.downloading.remove(filename);
+    /// Verifies a file which is present and about to be used
+    async fn verify_file(
+        self,
+        filename: PathBuf,
+        url: String,
+        expected: ExpectedChecksum,
+    ) -> Result<(), Report> {
+        let digests = file_digests(&filename).await?;
+        let Err(e) = expected.verify(&digests) else {
+            return Ok(());
+        };
+        FirmwareCacheMetrics::increment(&self.cache.metrics.verification_failures);
+
+        if url.is_empty() {
+            // Keep the file, maybe somebody is about to replace it, but don't check it again until it changed
+            if let Some(fingerprint) = FileFingerprint::of(&filename) {
+                self.actual
+                    .lock()
+                    .unwrap()
+                    .rejected
+                    .insert(filename.clone(), (fingerprint, expected));
+            }
+        } else {
+            // Drop the file, and the stored artifact it is linked to, so that the next request fetches it again
+            if let Ok(target) = std::fs::read_link(&filename)
+                && let Some(digest) = self.cache.store.digest_for_path(&target)
+            {
+                self.cache.store.remove(&digest);
+                self.update_stored_bytes();
+            }
+            std::fs::remove_file(&filename).wrap_err(format!("Could not remove {filename:?}"))?;
+        }
+        Err(e.wrap_err(format!("Verification of {filename:?} failed")))
     }
-}
 
-async fn download(
-    filename: &Path,
-    url: &String,
-    dst_filename: &String,
-    client: Client,
-    fake_sleep: Option<Duration>,
-) -> Result<(), Report> {
-    // Actual downloader.  We aren't able to return errors to callers here, we just print to the log, and will retry on the next request.
-    let dirname = match Path::parent(filename) {
-        Some(x) => x.to_string_lossy().to_string(),
-        None => {
+    /// Links filename to an artifact which is in the store already
+    async fn link_stored(
+        self,
+        filename: PathBuf,
+        url: String,
+        digest: String,
+        expected: Option<ExpectedChecksum>,
+    ) -> Result<(), Report> {
+        let store = &self.cache.store;
+        let path = store.artifact_path(&digest);
+        let digests = file_digests(&path).await?;
+        if digests.sha256 != digest {
+            FirmwareCacheMetrics::increment(&self.cache.metrics.verification_failures);
+            store.remove(&digest);
+            self.update_stored_bytes();
             return Err(eyre!(
-                "Could not find dirname of {}",
-                filename.to_string_lossy()
+                "Stored firmware artifact {digest} is corrupted, it will be downloaded again"
             ));
         }
-    };
-
-    let _ = std::fs::create_dir_all(dirname);
-    let mut dst_file = File::create(&dst_filename)
-        .await
-        .wrap_err(format!("Unable to create file {dst_filename}"))?;
+        if let Some(expected) = expected
+            && let Err(e) = expected.verify(&digests)
+        {
+            // The artifact is fine, but it's not what's expected from this URL (anymore)
+            FirmwareCacheMetrics::increment(&self.cache.metrics.verification_failures);
+            store.forget_url(&url);
+            return Err(e.wrap_err(format!("Stored firmware from {url} is outdated")));
+        }
 
-    if let Some(duration) = fake_sleep {
-        // For testing only, wait a given amount of time then write an empty file
-        tokio::time::sleep(duration).await;
-        return Ok(());
+        link_artifact(&path, &filename)?;
+        store.touch(&digest);
+        FirmwareCacheMetrics::increment(&self.cache.metrics.cache_hits);
+        tracing::info!("Linked stored firmware artifact {digest} to {filename:?}");
+        Ok(())
     }
 
-    if url.starts_with("file://") {
-        // Just copies a local file, for testing
-        let src_filename = url.strip_prefix("file:/").unwrap(); // Leave the second / for the root
-        let mut src_file = File::open(src_filename)
-            .await
-            .wrap_err(format!("FirmwareDownloader could not open source {url}"))?;
-        return tokio::io::copy(&mut src_file, &mut dst_file)
+    // Actual downloader.  We aren't able to return errors to callers here, we just print to the log, and will retry on the next request.
+    async fn download(
+        self,
+        filename: PathBuf,
+        url: String,
+        expected: Option<ExpectedChecksum>,
+        client: Client,
+        fake_sleep: Option<Duration>,
+    ) -> Result<(), Report> {
+        let cache = &self.cache;
+        let _permit = cache
+            .download_limiter
+            .acquire()
             .await
-            .map(|_| ())
-            .map_err(|e| eyre!("FirmwareDownloader had problems saving file from {url}: {e}"));
+            .map_err(|e| eyre!("FirmwareDownloader download limiter closed: {e}"))?;
+
+        let partial = cache.store.partial_path(&url);
+        let options = FetchOptions {
+            attempts: cache.config.download_attempts,
+            retry_backoff: cache.config.retry_backoff,
+            fake_sleep,
+        };
+        fetch::fetch(&client, &url, &partial, &options, &cache.metrics).await?;
+        tracing::info!("Completed download of {url} for {filename:?}");
+
+        let digests = file_digests(&partial).await?;
+        if let Some(expected) = expected
+            && let Err(e) = expected.verify(&digests)
+        {
+            FirmwareCacheMetrics::increment(&cache.metrics.verification_failures);
+            // Not worth resuming
+            let _ = std::fs::remove_file(&partial);
+            return Err(e.wrap_err(format!("FirmwareDownloader checksum for {url} failed")));
+        }
+
+        let size = std::fs::metadata(&partial)?.len();
+        let path = cache
+            .store
+            .insert(&url, &partial, &digests.sha256, size)
+            .wrap_err(format!("Could not store download of {url}"))?;
+        self.update_stored_bytes();
+        link_artifact(&path, &filename)?;
+        Ok(())
     }
 
-    let res = client.get(url).send().await.wrap_err(format!(
-        "FirmwareDownloader got error trying to download {url}"
-    ))?;
-    if !res.status().is_success() {
-        return Err(eyre!(
-            "FirmwareDownloader got non-success status trying to download {url}: {}",
-            res.status()
-        ));
+    fn update_stored_bytes(&self) {
+        self.cache.metrics.stored_bytes.store(
+            self.cache.store.stored_bytes(),
+            std::sync::atomic::Ordering::Relaxed,
+        );
     }
-    let mut body = res.bytes_stream();
-    while let Some(segment) = body.next().await {
-        match segment {
-            Err(e) => {
-                return Err(eyre!(
-                    "FirmwareDownloader had problems downloading {url}: {e}"
-                ));
-            }
-            Ok(segment) => {
-                tokio::io::copy(&mut segment.as_ref(), &mut dst_file)
-                    .await
-                    .wrap_err(format!(
-                        "FirmwareDownloader had problems saving file from {url}"
-                    ))?;
-            }
-        }
+
+    /// Evicts artifacts which are not referenced until the store is within its quota
+    fn collect_garbage(&self, referenced: &ReferencedArtifacts) -> GcReport {
+        let report = self
+            .cache
+            .store
+            .collect_garbage(self.cache.config.max_size_bytes, referenced);
+        let metrics = &self.cache.metrics;
+        metrics
+            .evictions
+            .fetch_add(report.evicted as u64, std::sync::atomic::Ordering::Relaxed);
+        metrics
+            .stored_bytes
+            .store(report.stored_bytes, std::sync::atomic::Ordering::Relaxed);
+        report
     }
 
-    // Success
-    Ok(())
+    /// Starts evicting artifacts which are not referenced by any desired firmware periodically
+    pub fn start_garbage_collection(
+        &self,
+        db_pool: PgPool,
+        firmware_config: Arc<FirmwareConfig>,
+    ) -> eyre::Result<oneshot::Sender<i32>> {
+        let (stop_sender, mut stop_receiver) = oneshot::channel();
+        let downloader = self.clone();
+
+        tokio::task::Builder::new()
+            .name("firmware_cache_gc")
+            .spawn(async move {
+                loop {
+                    match find_referenced_artifacts(&db_pool, &firmware_config).await {
+                        Ok(referenced) => {
+                            let report = downloader.collect_garbage(&referenced);
+                            tracing::debug!("Firmware cache garbage collection: {report:?}");
+                        }
+                        Err(e) => tracing::warn!("Firmware cache garbage collection error: {e}"),
+                    }
+
+                    tokio::select! {
+                        _ = tokio::time::sleep(downloader.cache.config.gc_interval) => {},
+                        _ = &mut stop_receiver => {
+                            tracing::info!("Firmware cache garbage collection stop was requested");
+                            return;
+                        }
+                    }
+                }
+            })?;
+
+        Ok(stop_sender)
+    }
 }
 
-/// verify_checks checks if the given filename uses the given checksum.  This is not meant to be security,
-/// it's to check against download corruption or retrieving the wrong thing (such as if the vendor changed the URL).
-/// We expect the hardware vendor to have done their own signing to ensure that firmware is not compromised.
-fn verify_checksum(filename: &String, checksum: &String) -> Result<(), Report> {
-    if checksum.is_empty() {
-        // No validation requested
-        return Ok(());
+/// Finds the firmware referenced by the desired_firmware table. Only the artifacts of the
+/// versions we want to install are protected from eviction.
+async fn find_referenced_artifacts(
+    db_pool: &PgPool,
+    firmware_config: &FirmwareConfig,
+) -> CarbideResult<ReferencedArtifacts> {
+    let mut txn = Transaction::begin(db_pool).await?;
+    let desired_firmware = db::desired_firmware::find_all(&mut txn).await?;
+    txn.commit().await?;
+
+    let models = firmware_config.map();
+    let mut referenced = ReferencedArtifacts::default();
+    for desired in desired_firmware {
+        let Some(firmware) = models.values().find(|firmware| {
+            firmware.vendor.to_pascalcase() == desired.vendor && firmware.model == desired.model
+        }) else {
+            continue;
+        };
+        for (component_type, version) in &desired.versions.versions {
+            let Some(component) = firmware.components.get(component_type) else {
+                continue;
+            };
+            component
+                .known_firmware
+                .iter()
+                .filter(|entry| &entry.version == version)
+                .for_each(|entry| add_referenced_entry(&mut referenced, entry));
+        }
     }
-    // md5 doesn't support async, must use the standard
-    let mut file = std::fs::File::open(filename)?;
+    Ok(referenced)
+}
 
-    let mut context = md5::Context::new();
-    std::io::copy(&mut file, &mut context)?;
+fn add_referenced_entry(referenced: &mut ReferencedArtifacts, entry: &FirmwareEntry) {
+    if let Some(url) = &entry.url {
+        referenced.urls.insert(url.clone());
+    }
+    if let Some(digest) = entry
+        .checksum
+        .as_deref()
+        .and_then(ExpectedChecksum::parse)
+        .and_then(|expected| expected.sha256().map(str::to_owned))
+    {
+        referenced.digests.insert(digest);
+    }
+}
 
-    let checksum_actual = format!("{:x}", context.compute());
+async fn file_digests(path: &Path) -> Result<FileDigests, Report> {
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        FileDigests::of(&path).wrap_err(format!("Could not read {path:?}"))
+    })
+    .await?
+}
 
-    if &checksum_actual != checksum {
-        return Err(eyre!(
-            "Checksum mismatch: Expected {checksum} downloaded {checksum_actual}"
-        ));
+/// Makes filename a link to a stored artifact, replacing whatever was there before
+fn link_artifact(artifact: &Path, filename: &Path) -> Result<(), Report> {
+    if let Some(dirname) = filename.parent() {
+        let _ = std::fs::create_dir_all(dirname);
     }
-    Ok(())
+    if filename.symlink_metadata().is_ok() {
+        std::fs::remove_file(filename).wrap_err(format!("Could not replace {filename:?}"))?;
+    }
+    std::os::unix::fs::symlink(artifact, filename)
+        .wrap_err(format!("Could not link {filename:?} to {artifact:?}"))
 }
 
 #[cfg(test)]
 mod tests {
+    use tokio::fs::File;
     use tokio::io::AsyncWriteExt;
 
     use super::*;
@@ -309,4 +576,132 @@ mod tests {
             }
         }
     }
+
+    async fn wait_available(
+        downloader: &FirmwareDownloader,
+        filename: &Path,
+        url: &str,
+        checksum: &str,
+    ) {
+        for _ in 0..1000 {
+            if downloader.available(filename, url, checksum) {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("{filename:?} did not become available");
+    }
+
+    #[tokio::test]
+    async fn test_stored_artifacts_are_reused_and_reverified() {
+        let dir = temp_dir::TempDir::new().unwrap();
+        let src = dir.path().join("src.bin");
+        std::fs::write(&src, b"firmware").unwrap();
+        let url = format!("file:/{}", src.display());
+        let checksum = format!("{:x}", md5::compute(b"firmware"));
+        let downloader = FirmwareDownloader::with_store(
+            FirmwareCacheConfig {
+                directory: dir.path().join("cache"),
+                ..Default::default()
+            },
+            Arc::new(FirmwareCacheMetrics::default()),
+        );
+        let metrics = downloader.cache.metrics.clone();
+        let count = |counter: &std::sync::atomic::AtomicU64| {
+            counter.load(std::sync::atomic::Ordering::Relaxed)
+        };
+
+        let filename = dir.path().join("firmware/a.bin");
+        wait_available(&downloader, &filename, &url, &checksum).await;
+        assert_eq!(std::fs::read(&filename).unwrap(), b"firmware");
+        assert!(std::fs::symlink_metadata(&filename).unwrap().is_symlink());
+        assert_eq!(count(&metrics.cache_misses), 1);
+        assert_eq!(count(&metrics.bytes_fetched), 8);
+        assert_eq!(count(&metrics.stored_bytes), 8);
+
+        // A second file from the same URL is linked to the stored artifact
+        let other = dir.path().join("firmware/b.bin");
+        wait_available(&downloader, &other, &url, &checksum).await;
+        assert_eq!(std::fs::read(&other).unwrap(), b"firmware");
+        assert_eq!(count(&metrics.cache_misses), 1);
+        assert_eq!(count(&metrics.bytes_fetched), 8);
+        assert!(count(&metrics.cache_hits) >= 2);
+
+        // Corrupt the stored artifact. It gets verified again before the next use, and fetched again.
+        std::fs::write(&filename, b"corrupted").unwrap();
+        assert!(!downloader.available(&filename, &url, &checksum));
+        wait_available(&downloader, &filename, &url, &checksum).await;
+        assert_eq!(std::fs::read(&filename).unwrap(), b"firmware");
+        assert_eq!(count(&metrics.verification_failures), 1);
+        assert_eq!(count(&metrics.cache_misses), 2);
+        assert_eq!(count(&metrics.bytes_fetched), 16);
+    }
+
+    #[tokio::test]
+    async fn test_checksum_mismatch_without_url() {
+        let dir = temp_dir::TempDir::new().unwrap();
+        let filename = dir.path().join("firmware.bin");
+        std::fs::write(&filename, b"firmware").unwrap();
+        let downloader = FirmwareDownloader::with_store(
+            FirmwareCacheConfig {
+                directory: dir.path().join("cache"),
+                ..Default::default()
+            },
+            Arc::new(FirmwareCacheMetrics::default()),
+        );
+
+        let wrong = format!("sha256:{}", "0".repeat(64));
+        for _ in 0..10 {
+            assert!(!downloader.available(&filename, "", &wrong));
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        // The file is kept, but only verified once as long as it doesn't change
+        assert!(filename.exists());
+        assert_eq!(
+            downloader
+                .cache
+                .metrics
+                .verification_failures
+                .load(std::sync::atomic::Ordering::Relaxed),
+            1
+        );
+
+        let checksum = format!("{:x}", md5::compute(b"firmware"));
+        wait_available(&downloader, &filename, "", &checksum).await;
+    }
+
+    #[tokio::test]
+    async fn test_files_are_verified_again_once_changed() {
+        let dir = temp_dir::TempDir::new().unwrap();
+        let filename = dir.path().join("firmware.bin");
+        std::fs::write(&filename, b"firmware").unwrap();
+        let downloader = FirmwareDownloader::with_store(
+            FirmwareCacheConfig {
+                directory: dir.path().join("cache"),
+                ..Default::default()
+            },
+            Arc::new(FirmwareCacheMetrics::default()),
+        );
+        let checksum = format!("{:x}", md5::compute(b"firmware"));
+        wait_available(&downloader, &filename, "", &checksum).await;
+
+        // The verification is reused by every use as long as the file doesn't change
+        for _ in 0..10 {
+            assert!(downloader.available(&filename, "", &checksum));
+        }
+
+        std::fs::write(&filename, b"modified firmware").unwrap();
+        for _ in 0..10 {
+            assert!(!downloader.available(&filename, "", &checksum));
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(
+            downloader
+                .cache
+                .metrics
+                .verification_failures
+                .load(std::sync::atomic::Ordering::Relaxed),
+            1
+        );
+    }
 }
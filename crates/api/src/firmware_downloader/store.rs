@@ -0,0 +1,431 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Content addressed storage of firmware artifacts
+//!
+//! Artifacts are stored as `<root>/sha256/<digest>`. An index kept in `<root>/index.json`
+//! remembers the URL each artifact was downloaded from and when it was last used, which
+//! garbage collection uses to evict the least recently used artifacts first.
+
+use std::collections::{HashMap, HashSet};
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use chrono::{DateTime, Utc};
+use eyre::{Report, eyre};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+const INDEX_FILE: &str = "index.json";
+const ARTIFACT_DIR: &str = "sha256";
+const PARTIAL_DIR: &str = "partial";
+
+#[derive(Debug)]
+pub struct ArtifactStore {
+    root: PathBuf,
+    index: Mutex<StoreIndex>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct StoreIndex {
+    /// Stored artifacts by their SHA-256 digest
+    artifacts: HashMap<String, ArtifactEntry>,
+    /// The digest of the artifact last downloaded from a URL
+    urls: HashMap<String, String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct ArtifactEntry {
+    size: u64,
+    last_used: DateTime<Utc>,
+}
+
+/// The artifacts which must be kept regardless of the quota, by URL or by digest
+#[derive(Debug, Default)]
+pub struct ReferencedArtifacts {
+    pub urls: HashSet<String>,
+    pub digests: HashSet<String>,
+}
+
+/// The outcome of a garbage collection run
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct GcReport {
+    pub evicted: usize,
+    pub freed_bytes: u64,
+    pub stored_bytes: u64,
+}
+
+impl ArtifactStore {
+    /// Opens the store below the given directory, picking up the index of a previous run
+    pub fn open(root: &Path) -> Self {
+        let mut index = match std::fs::read(root.join(INDEX_FILE)) {
+            Ok(content) => serde_json::from_slice(&content).unwrap_or_else(|e| {
+                tracing::warn!("Ignoring unreadable firmware cache index in {root:?}: {e}");
+                StoreIndex::default()
+            }),
+            Err(_) => StoreIndex::default(),
+        };
+        // Forget about artifacts which were removed by hand
+        index
+            .artifacts
+            .retain(|digest, _| root.join(ARTIFACT_DIR).join(digest).is_file());
+        let artifacts = &index.artifacts;
+        index
+            .urls
+            .retain(|_, digest| artifacts.contains_key(digest));
+
+        Self {
+            root: root.to_path_buf(),
+            index: Mutex::new(index),
+        }
+    }
+
+    pub fn artifact_path(&self, digest: &str) -> PathBuf {
+        self.root.join(ARTIFACT_DIR).join(digest)
+    }
+
+    /// Where a download of the URL is written to until it is complete. Keeping it at a stable
+    /// location allows resuming interrupted downloads.
+    pub fn partial_path(&self, url: &str) -> PathBuf {
+        self.root
+            .join(PARTIAL_DIR)
+            .join(hex::encode(Sha256::digest(url.as_bytes())))
+    }
+
+    /// Returns the digest of the stored artifact a path points to
+    pub fn digest_for_path(&self, path: &Path) -> Option<String> {
+        if path.parent()? != self.root.join(ARTIFACT_DIR) {
+            return None;
+        }
+        let digest = path.file_name()?.to_str()?;
+        self.contains(digest).then(|| digest.to_string())
+    }
+
+    /// Returns the digest of the stored artifact that was downloaded from the URL
+    pub fn digest_for_url(&self, url: &str) -> Option<String> {
+        self.index.lock().unwrap().urls.get(url).cloned()
+    }
+
+    pub fn contains(&self, digest: &str) -> bool {
+        self.index.lock().unwrap().artifacts.contains_key(digest)
+    }
+
+    /// Moves a verified download into the store and returns the path of the artifact
+    pub fn insert(&self, url: &str, source: &Path, digest: &str, size: u64) -> io::Result<PathBuf> {
+        let path = self.artifact_path(digest);
+        std::fs::create_dir_all(self.root.join(ARTIFACT_DIR))?;
+        std::fs::rename(source, &path)?;
+
+        let mut index = self.index.lock().unwrap();
+        index.artifacts.insert(
+            digest.to_string(),
+            ArtifactEntry {
+                size,
+                last_used: Utc::now(),
+            },
+        );
+        index.urls.insert(url.to_string(), digest.to_string());
+        self.save(&index);
+        Ok(path)
+    }
+
+    /// Records that an artifact was used, so that it is evicted after artifacts used earlier
+    pub fn touch(&self, digest: &str) {
+        if let Some(entry) = self.index.lock().unwrap().artifacts.get_mut(digest) {
+            entry.last_used = Utc::now();
+        }
+    }
+
+    /// Stops serving the artifact for the URL, without removing the artifact itself
+    pub fn forget_url(&self, url: &str) {
+        let mut index = self.index.lock().unwrap();
+        if index.urls.remove(url).is_some() {
+            self.save(&index);
+        }
+    }
+
+    /// Removes an artifact, e.g. because it no longer matches its digest
+    pub fn remove(&self, digest: &str) {
+        let mut index = self.index.lock().unwrap();
+        Self::remove_locked(&mut index, &self.artifact_path(digest), digest);
+        self.save(&index);
+    }
+
+    fn remove_locked(index: &mut StoreIndex, path: &Path, digest: &str) -> u64 {
+        if let Err(e) = std::fs::remove_file(path)
+            && e.kind() != io::ErrorKind::NotFound
+        {
+            tracing::warn!("Failed to remove firmware artifact {path:?}: {e}");
+        }
+        index.urls.retain(|_, d| d != digest);
+        index
+            .artifacts
+            .remove(digest)
+            .map(|entry| entry.size)
+            .unwrap_or_default()
+    }
+
+    pub fn stored_bytes(&self) -> u64 {
+        let index = self.index.lock().unwrap();
+        index.artifacts.values().map(|entry| entry.size).sum()
+    }
+
+    /// Evicts artifacts which are not referenced, least recently used first, until the stored
+    /// artifacts fit into `max_bytes`. Referenced artifacts are kept even if they exceed it.
+    pub fn collect_garbage(&self, max_bytes: u64, referenced: &ReferencedArtifacts) -> GcReport {
+        let mut index = self.index.lock().unwrap();
+        let mut report = GcReport {
+            stored_bytes: index.artifacts.values().map(|entry| entry.size).sum(),
+            ..Default::default()
+        };
+
+        let referenced_digests: HashSet<&String> = index
+            .urls
+            .iter()
+            .filter(|(url, _)| referenced.urls.contains(*url))
+            .map(|(_, digest)| digest)
+            .chain(referenced.digests.iter())
+            .collect();
+        let mut candidates: Vec<(String, DateTime<Utc>)> = index
+            .artifacts
+            .iter()
+            .filter(|(digest, _)| !referenced_digests.contains(digest))
+            .map(|(digest, entry)| (digest.clone(), entry.last_used))
+            .collect();
+        candidates.sort_by_key(|(_, last_used)| *last_used);
+
+        for (digest, _) in candidates {
+            if report.stored_bytes <= max_bytes {
+                break;
+            }
+            let size = Self::remove_locked(&mut index, &self.artifact_path(&digest), &digest);
+            tracing::info!("Evicted firmware artifact {digest} ({size} bytes) from the cache");
+            report.evicted += 1;
+            report.freed_bytes += size;
+            report.stored_bytes -= size;
+        }
+
+        // Also persists the last use of artifacts, which isn't saved on every use
+        self.save(&index);
+        report
+    }
+
+    fn save(&self, index: &StoreIndex) {
+        let result = serde_json::to_vec(index)
+            .map_err(io::Error::other)
+            .and_then(|content| {
+                std::fs::create_dir_all(&self.root)?;
+                // Written next to the index and renamed, so that the index is never seen half written
+                let tmp = self
+                    .root
+                    .join(format!("{INDEX_FILE}.{}", uuid::Uuid::new_v4()));
+                std::fs::write(&tmp, content)?;
+                std::fs::rename(&tmp, self.root.join(INDEX_FILE))
+            });
+        if let Err(e) = result {
+            tracing::warn!(
+                "Failed to save firmware cache index in {:?}: {e}",
+                self.root
+            );
+        }
+    }
+}
+
+/// The digests of a file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileDigests {
+    pub sha256: String,
+    pub md5: String,
+}
+
+impl FileDigests {
+    /// Computes the digests of a file in a single pass. Blocking.
+    pub fn of(path: &Path) -> io::Result<Self> {
+        let mut file = std::fs::File::open(path)?;
+        let mut sha256 = Sha256::new();
+        let mut md5 = md5::Context::new();
+        let mut buffer = vec![0; 1024 * 1024];
+        loop {
+            let read = file.read(&mut buffer)?;
+            if read == 0 {
+                break;
+            }
+            sha256.update(&buffer[..read]);
+            md5.consume(&buffer[..read]);
+        }
+        Ok(Self {
+            sha256: hex::encode(sha256.finalize()),
+            md5: format!("{:x}", md5.compute()),
+        })
+    }
+}
+
+/// The checksum configured for a firmware file. This is not meant to be security, it's to
+/// check against download corruption or retrieving the wrong thing (such as if the vendor
+/// changed the URL). We expect the hardware vendor to have done their own signing to ensure
+/// that firmware is not compromised.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExpectedChecksum {
+    Md5(String),
+    Sha256(String),
+}
+
+impl ExpectedChecksum {
+    /// Checksums are MD5 unless they are prefixed with `sha256:` or have the length of a
+    /// SHA-256 digest. Returns None if no checksum is configured.
+    pub fn parse(checksum: &str) -> Option<Self> {
+        let checksum = checksum.trim().to_lowercase();
+        if checksum.is_empty() {
+            return None;
+        }
+        if let Some(digest) = checksum.strip_prefix("sha256:") {
+            return Some(Self::Sha256(digest.to_string()));
+        }
+        if checksum.len() == 64 {
+            return Some(Self::Sha256(checksum));
+        }
+        Some(Self::Md5(checksum))
+    }
+
+    pub fn sha256(&self) -> Option<&str> {
+        match self {
+            Self::Sha256(digest) => Some(digest),
+            Self::Md5(_) => None,
+        }
+    }
+
+    pub fn verify(&self, digests: &FileDigests) -> Result<(), Report> {
+        let (expected, actual) = match self {
+            Self::Md5(expected) => (expected, &digests.md5),
+            Self::Sha256(expected) => (expected, &digests.sha256),
+        };
+        if expected != actual {
+            return Err(eyre!(
+                "Checksum mismatch: Expected {expected} downloaded {actual}"
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_artifact(store: &ArtifactStore, url: &str, content: &[u8]) -> String {
+        let source = store.partial_path(url);
+        std::fs::create_dir_all(source.parent().unwrap()).unwrap();
+        std::fs::write(&source, content).unwrap();
+        let digests = FileDigests::of(&source).unwrap();
+        store
+            .insert(url, &source, &digests.sha256, content.len() as u64)
+            .unwrap();
+        digests.sha256
+    }
+
+    #[test]
+    fn test_parse_checksum() {
+        assert_eq!(ExpectedChecksum::parse(""), None);
+        assert_eq!(
+            ExpectedChecksum::parse("A08232EF8A758330F8698442550157F7"),
+            Some(ExpectedChecksum::Md5(
+                "a08232ef8a758330f8698442550157f7".to_string()
+            ))
+        );
+        let sha256 = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+        assert_eq!(
+            ExpectedChecksum::parse(sha256),
+            Some(ExpectedChecksum::Sha256(sha256.to_string()))
+        );
+        assert_eq!(
+            ExpectedChecksum::parse(&format!("sha256:{sha256}")),
+            Some(ExpectedChecksum::Sha256(sha256.to_string()))
+        );
+    }
+
+    #[test]
+    fn test_index_survives_reopen() {
+        let dir = temp_dir::TempDir::new().unwrap();
+        let store = ArtifactStore::open(dir.path());
+        let digest = write_artifact(&store, "https://example.com/fw.bin", b"firmware");
+        assert_eq!(
+            digest,
+            hex::encode(Sha256::digest(b"firmware")),
+            "artifacts are addressed by their content"
+        );
+
+        let store = ArtifactStore::open(dir.path());
+        assert_eq!(
+            store.digest_for_url("https://example.com/fw.bin"),
+            Some(digest.clone())
+        );
+        assert_eq!(store.stored_bytes(), 8);
+        assert_eq!(
+            store.digest_for_path(&store.artifact_path(&digest)),
+            Some(digest.clone())
+        );
+
+        // Artifacts deleted behind the back of the store are forgotten
+        std::fs::remove_file(store.artifact_path(&digest)).unwrap();
+        let store = ArtifactStore::open(dir.path());
+        assert_eq!(store.digest_for_url("https://example.com/fw.bin"), None);
+        assert_eq!(store.stored_bytes(), 0);
+    }
+
+    #[test]
+    fn test_collect_garbage_evicts_unreferenced_lru() {
+        let dir = temp_dir::TempDir::new().unwrap();
+        let store = ArtifactStore::open(dir.path());
+        let oldest = write_artifact(&store, "https://example.com/a", &[1; 100]);
+        let referenced = write_artifact(&store, "https://example.com/b", &[2; 100]);
+        let newest = write_artifact(&store, "https://example.com/c", &[3; 100]);
+        let pinned = write_artifact(&store, "https://example.com/d", &[4; 100]);
+        store.touch(&newest);
+
+        let referenced_artifacts = ReferencedArtifacts {
+            urls: HashSet::from(["https://example.com/b".to_string()]),
+            digests: HashSet::from([pinned.clone()]),
+        };
+
+        // Within the quota, nothing is evicted
+        let report = store.collect_garbage(400, &referenced_artifacts);
+        assert_eq!(report.evicted, 0);
+        assert_eq!(report.stored_bytes, 400);
+
+        let report = store.collect_garbage(300, &referenced_artifacts);
+        assert_eq!(
+            report,
+            GcReport {
+                evicted: 1,
+                freed_bytes: 100,
+                stored_bytes: 300,
+            }
+        );
+        assert!(!store.contains(&oldest));
+        assert!(!store.artifact_path(&oldest).exists());
+        assert_eq!(store.digest_for_url("https://example.com/a"), None);
+
+        // Referenced artifacts are kept even if the quota can't be met
+        let report = store.collect_garbage(0, &referenced_artifacts);
+        assert_eq!(report.evicted, 1);
+        assert_eq!(report.stored_bytes, 200);
+        assert!(!store.contains(&newest));
+        assert!(store.contains(&referenced));
+        assert!(store.contains(&pinned));
+    }
+}
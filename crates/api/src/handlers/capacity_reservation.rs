@@ -0,0 +1,206 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::HashMap;
+
+use ::db::capacity_reservation as db;
+use ::rpc::forge as rpc;
+use chrono::Utc;
+use itertools::Itertools;
+use model::capacity_reservation::{CapacityReservation, NewCapacityReservation, attribute_usage};
+use sqlx::PgConnection;
+use tonic::{Request, Response, Status};
+
+use crate::api::{Api, log_request_data};
+use crate::instance::reservation::InstanceTypeCapacity;
+use crate::{CarbideError, CarbideResult};
+
+pub async fn create(
+    api: &Api,
+    request: Request<rpc::CapacityReservationCreationRequest>,
+) -> Result<Response<rpc::CapacityReservation>, Status> {
+    log_request_data(&request);
+
+    let new_reservation =
+        NewCapacityReservation::try_from(request.into_inner()).map_err(CarbideError::from)?;
+
+    if new_reservation
+        .expires_at
+        .is_some_and(|expires_at| expires_at <= Utc::now())
+    {
+        return Err(
+            CarbideError::InvalidArgument("expires_at must be in the future".to_string()).into(),
+        );
+    }
+
+    let mut txn = api.txn_begin().await?;
+
+    if ::db::instance_type::find_by_ids(
+        &mut txn,
+        std::slice::from_ref(&new_reservation.instance_type_id),
+        false,
+    )
+    .await?
+    .is_empty()
+    {
+        return Err(CarbideError::NotFoundError {
+            kind: "InstanceType",
+            id: new_reservation.instance_type_id.to_string(),
+        }
+        .into());
+    }
+
+    // Lock the Machines of the InstanceType, so that allocations can't take them while the
+    // reservation is validated
+    ::db::machine::find_ids_by_instance_type_id(&mut txn, &new_reservation.instance_type_id, true)
+        .await?;
+
+    let capacity = InstanceTypeCapacity::load(
+        &mut txn,
+        std::slice::from_ref(&new_reservation.instance_type_id),
+        true,
+    )
+    .await?
+    .remove(&new_reservation.instance_type_id)
+    .unwrap_or_default();
+    capacity.check_reservation(
+        &new_reservation.instance_type_id,
+        &new_reservation.tenant_organization_id,
+        new_reservation.count,
+    )?;
+
+    let reservation = db::create(&mut txn, &new_reservation).await?;
+
+    let mut reservations = with_status(&mut txn, vec![reservation]).await?;
+
+    txn.commit().await?;
+
+    Ok(Response::new(reservations.remove(0)))
+}
+
+pub async fn find(
+    api: &Api,
+    request: Request<rpc::CapacityReservationSearchFilter>,
+) -> Result<Response<rpc::CapacityReservationList>, Status> {
+    log_request_data(&request);
+
+    let rpc::CapacityReservationSearchFilter {
+        tenant_organization_id,
+        instance_type_id,
+        include_inactive,
+    } = request.into_inner();
+
+    let tenant_organization_id = tenant_organization_id
+        .map(|id| {
+            id.parse()
+                .map_err(|_| CarbideError::InvalidArgument(format!("invalid tenant: {id}")))
+        })
+        .transpose()?;
+    let instance_type_id = instance_type_id
+        .map(|id| {
+            id.parse()
+                .map_err(|_| CarbideError::InvalidArgument(format!("invalid instance type: {id}")))
+        })
+        .transpose()?;
+
+    let mut txn = api.txn_begin().await?;
+
+    let reservations = db::find(
+        &mut txn,
+        tenant_organization_id.as_ref(),
+        instance_type_id.as_ref(),
+        include_inactive,
+    )
+    .await?;
+
+    let reservations = with_status(&mut txn, reservations).await?;
+
+    txn.commit().await?;
+
+    Ok(Response::new(rpc::CapacityReservationList { reservations }))
+}
+
+pub async fn release(
+    api: &Api,
+    request: Request<rpc::CapacityReservationReleaseRequest>,
+) -> Result<Response<rpc::CapacityReservationReleaseResult>, Status> {
+    log_request_data(&request);
+
+    let rpc::CapacityReservationReleaseRequest { id } = request.into_inner();
+
+    let id = id.ok_or_else(|| CarbideError::MissingArgument("id cannot be null"))?;
+
+    let mut txn = api.txn_begin().await?;
+
+    let reservation = db::release(&mut txn, id).await?;
+
+    txn.commit().await?;
+
+    tracing::info!(
+        reservation_id = %reservation.id,
+        tenant_organization_id = %reservation.tenant_organization_id,
+        instance_type_id = %reservation.instance_type_id,
+        "Released capacity reservation"
+    );
+
+    Ok(Response::new(rpc::CapacityReservationReleaseResult {}))
+}
+
+/// Converts reservations into their RPC representation, including their utilization.
+///
+/// The instances of a tenant on an InstanceType are counted against its active reservations for
+/// the InstanceType, oldest first. Inactive reservations are reported without usage.
+async fn with_status(
+    txn: &mut PgConnection,
+    reservations: Vec<CapacityReservation>,
+) -> CarbideResult<Vec<rpc::CapacityReservation>> {
+    let now = Utc::now();
+    let instance_type_ids: Vec<_> = reservations
+        .iter()
+        .map(|r| r.instance_type_id.clone())
+        .unique()
+        .collect();
+    let capacities = InstanceTypeCapacity::load(&mut *txn, &instance_type_ids, false).await?;
+
+    let mut usage = HashMap::new();
+    let active_groups = reservations
+        .iter()
+        .filter(|r| r.is_active(now))
+        .into_group_map_by(|r| (r.instance_type_id.clone(), r.tenant_organization_id.clone()));
+    for ((instance_type_id, tenant), group) in active_groups {
+        let in_use = capacities
+            .get(&instance_type_id)
+            .and_then(|capacity| capacity.in_use.get(&tenant))
+            .copied()
+            .unwrap_or_default();
+        for (reservation, used) in group.iter().zip(attribute_usage(&group, in_use)) {
+            usage.insert(reservation.id, used);
+        }
+    }
+
+    Ok(reservations
+        .into_iter()
+        .map(|reservation| {
+            let in_use = usage.get(&reservation.id).copied().unwrap_or_default();
+            let available = capacities
+                .get(&reservation.instance_type_id)
+                .map(|capacity| capacity.available)
+                .unwrap_or_default();
+            reservation.into_rpc(now, in_use, available)
+        })
+        .collect())
+}
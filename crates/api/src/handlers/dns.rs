@@ -15,9 +15,12 @@
  * limitations under the License.
  */
 use ::rpc::protos;
-use db::dns::resource_record;
+use carbide_uuid::domain::DomainId;
+use db::dns::{domain_key, resource_record, tenant_record};
 use dns_record::constants::*;
 use dns_record::{DnsResourceRecordReply, DnsResourceRecordType};
+use forge_secrets::credentials::{CredentialKey, Credentials};
+use model::dns::{DomainKeyInfo, DomainKeyKind};
 use tonic::{Request, Response, Status};
 
 use crate::CarbideError;
@@ -60,10 +63,13 @@ async fn lookup_soa_record(
 }
 
 /// Returns ALL record types (A, AAAA, CNAME, etc.) - PowerDNS filters to requested type
-async fn lookup_records_by_qname(
-    txn: impl DbReader<'_>,
+async fn lookup_records_by_qname<DB>(
+    txn: &mut DB,
     query_name: &str,
-) -> Result<Vec<DnsResourceRecordReply>, tonic::Status> {
+) -> Result<Vec<DnsResourceRecordReply>, tonic::Status>
+where
+    for<'db> &'db mut DB: DbReader<'db>,
+{
     tracing::debug!("Looking up records for {}", query_name);
 
     // dns_records view expects trailing dots (FQDN format)
@@ -73,7 +79,7 @@ async fn lookup_records_by_qname(
         query_name.to_string()
     };
 
-    let result = resource_record::find_record(txn, &qname_with_dot)
+    let mut result = resource_record::find_record(&mut *txn, &qname_with_dot)
         .await
         .map_err(CarbideError::from)?
         .into_iter()
@@ -83,6 +89,15 @@ async fn lookup_records_by_qname(
         })
         .collect::<Vec<_>>();
 
+    // Records defined by tenants are stored lowercase
+    result.extend(
+        tenant_record::find_by_qname(txn, &qname_with_dot.to_lowercase())
+            .await
+            .map_err(CarbideError::from)?
+            .into_iter()
+            .map(DnsResourceRecordReply::from),
+    );
+
     Ok(result)
 }
 
@@ -256,6 +271,291 @@ pub async fn get_all_domain_metadata(
         result: proto_metadata,
     }))
 }
+
+/// Bumps the serials of changed domains before they are served, see
+/// [`db::dns::domain::apply_pending_serial_bumps`]
+async fn apply_pending_serial_bumps(api: &Api, domain_name: Option<&str>) -> Result<(), Status> {
+    let mut txn = api.txn_begin().await?;
+    db::dns::domain::apply_pending_serial_bumps(&mut txn, domain_name).await?;
+    txn.commit().await?;
+    Ok(())
+}
+
+/// Returns all records of a domain for zone transfers (the PowerDNS `list` method)
+///
+/// The SOA record is returned first, as AXFR responses have to start with it.
+pub async fn get_all_records_for_domain(
+    api: &Api,
+    request: Request<protos::dns::GetAllRecordsForDomainRequest>,
+) -> Result<Response<protos::dns::GetAllRecordsForDomainResponse>, Status> {
+    log_request_data(&request);
+
+    let domain_name = db::dns::normalize_domain(&request.into_inner().name);
+    if domain_name.is_empty() {
+        return Err(Status::invalid_argument("name cannot be empty"));
+    }
+
+    apply_pending_serial_bumps(api, Some(&domain_name)).await?;
+    let mut soa = lookup_soa_record(&api.database_connection, &domain_name).await?;
+    // Records from the dns_records view are fully qualified, so the SOA should be as well
+    soa.qname = format!("{domain_name}.");
+    let mut records = vec![soa];
+    records.extend(
+        resource_record::get_all_records(&api.database_connection, &domain_name)
+            .await
+            .map_err(CarbideError::from)?
+            .into_iter()
+            .map(|db_record| {
+                let model_record: model::dns::ResourceRecord = db_record.into();
+                DnsResourceRecordReply::from(model_record)
+            }),
+    );
+    records.extend(
+        tenant_record::find_by_domain_name(&api.database_connection, &domain_name)
+            .await
+            .map_err(CarbideError::from)?
+            .into_iter()
+            .map(DnsResourceRecordReply::from),
+    );
+
+    tracing::debug!(
+        domain = %domain_name,
+        count = records.len(),
+        "Listing all records of domain"
+    );
+
+    Ok(Response::new(protos::dns::GetAllRecordsForDomainResponse {
+        result: records.into_iter().map(Into::into).collect(),
+    }))
+}
+
+pub async fn get_domain_info(
+    api: &Api,
+    request: Request<protos::dns::GetDomainInfoRequest>,
+) -> Result<Response<protos::dns::GetDomainInfoResponse>, Status> {
+    log_request_data(&request);
+
+    let domain_name = db::dns::normalize_domain(&request.into_inner().name);
+    apply_pending_serial_bumps(api, Some(&domain_name)).await?;
+    let domains = db::dns::domain::find_by_name(&api.database_connection, &domain_name).await?;
+
+    let result = domains
+        .into_iter()
+        .next()
+        .map(model::dns::DomainInfo::from)
+        .map(protos::dns::DomainInfo::from);
+
+    Ok(Response::new(protos::dns::GetDomainInfoResponse { result }))
+}
+
+pub async fn get_domain_keys(
+    api: &Api,
+    request: Request<protos::dns::GetDomainKeysRequest>,
+) -> Result<Response<protos::dns::GetDomainKeysResponse>, Status> {
+    log_request_data(&request);
+
+    let domain_name = db::dns::normalize_domain(&request.into_inner().name);
+    let keys = domain_key::find_by_domain_name(&api.database_connection, &domain_name).await?;
+
+    let mut result = Vec::with_capacity(keys.len());
+    for key in keys {
+        let credential_key = CredentialKey::DnsDomainKey {
+            domain_id: key.domain_id,
+            key_id: key.id,
+        };
+        match api
+            .credential_provider
+            .get_credentials(&credential_key)
+            .await
+        {
+            Ok(Some(Credentials::UsernamePassword { password, .. })) => {
+                result.push(key.with_content(password).into());
+            }
+            Ok(None) => tracing::warn!(
+                domain = %domain_name,
+                key_id = key.id,
+                "Private key of DNSSEC key not found in credential provider, skipping key"
+            ),
+            Err(error) => {
+                return Err(CarbideError::internal(format!(
+                    "Failed to read private key {} of domain {domain_name}: {error}",
+                    key.id
+                ))
+                .into());
+            }
+        }
+    }
+
+    tracing::debug!(domain = %domain_name, count = result.len(), "Found domain keys");
+
+    Ok(Response::new(protos::dns::GetDomainKeysResponse { result }))
+}
+
+/// Creates a new active DNSSEC key and stores its private key in the credential provider
+async fn create_domain_key(
+    api: &Api,
+    txn: &mut sqlx::PgConnection,
+    domain_id: DomainId,
+    kind: DomainKeyKind,
+) -> Result<DomainKeyInfo, Status> {
+    let key = domain_key::persist(txn, domain_id, kind.flags(), true, true).await?;
+    api.credential_provider
+        .set_credentials(
+            &CredentialKey::DnsDomainKey {
+                domain_id,
+                key_id: key.id,
+            },
+            &Credentials::UsernamePassword {
+                username: kind.to_string(),
+                password: model::dns::domain_key::generate_ed25519_private_key(),
+            },
+        )
+        .await
+        .map_err(|e| {
+            CarbideError::internal(format!(
+                "Failed to store private key {} of domain {domain_id}: {e}",
+                key.id
+            ))
+        })?;
+    tracing::info!(%domain_id, key_id = key.id, %kind, "Generated DNSSEC key");
+    Ok(key)
+}
+
+fn domain_key_request(
+    request: protos::dns::DomainKeyRequest,
+) -> Result<(DomainId, DomainKeyKind), CarbideError> {
+    let kind = DomainKeyKind::from(request.kind());
+    let domain_id = request
+        .domain_id
+        .ok_or(CarbideError::MissingArgument("domain_id"))?;
+    Ok((domain_id, kind))
+}
+
+async fn find_domain(txn: &mut sqlx::PgConnection, domain_id: DomainId) -> Result<(), Status> {
+    match db::dns::domain::find_by_uuid(txn, domain_id).await? {
+        Some(domain) if domain.deleted.is_none() => Ok(()),
+        _ => Err(CarbideError::NotFoundError {
+            kind: "domain",
+            id: domain_id.to_string(),
+        }
+        .into()),
+    }
+}
+
+/// Generates an additional active DNSSEC key for a domain
+pub async fn generate_domain_key(
+    api: &Api,
+    request: Request<protos::dns::DomainKeyRequest>,
+) -> Result<Response<protos::dns::DomainKeysResponse>, Status> {
+    log_request_data(&request);
+
+    let (domain_id, kind) = domain_key_request(request.into_inner())?;
+
+    let mut txn = api.txn_begin().await?;
+    find_domain(&mut txn, domain_id).await?;
+    create_domain_key(api, &mut txn, domain_id, kind).await?;
+    let keys = domain_key::find_by_domain_id(&mut txn, domain_id).await?;
+    txn.commit().await?;
+
+    Ok(Response::new(protos::dns::DomainKeysResponse {
+        keys: keys.into_iter().map(Into::into).collect(),
+    }))
+}
+
+/// Rolls the DNSSEC keys of one kind of a domain.
+///
+/// A new active key is generated, keys which were active until now are
+/// deactivated but stay published, so that resolvers can still validate
+/// cached signatures, and keys which were already inactive are removed.
+/// Rolling twice, with at least the maximum TTL of the zone in between,
+/// therefore completes a rollover.
+pub async fn roll_domain_key(
+    api: &Api,
+    request: Request<protos::dns::DomainKeyRequest>,
+) -> Result<Response<protos::dns::DomainKeysResponse>, Status> {
+    log_request_data(&request);
+
+    let (domain_id, kind) = domain_key_request(request.into_inner())?;
+
+    let mut txn = api.txn_begin().await?;
+    find_domain(&mut txn, domain_id).await?;
+
+    let (active, retired): (Vec<_>, Vec<_>) = domain_key::find_by_domain_id(&mut txn, domain_id)
+        .await?
+        .into_iter()
+        .filter(|key| key.kind() == kind)
+        .partition(|key| key.active);
+    let active: Vec<u32> = active.into_iter().map(|key| key.id).collect();
+    let retired: Vec<u32> = retired.into_iter().map(|key| key.id).collect();
+
+    create_domain_key(api, &mut txn, domain_id, kind).await?;
+    domain_key::deactivate(&mut txn, &active).await?;
+    domain_key::delete(&mut txn, &retired).await?;
+    let keys = domain_key::find_by_domain_id(&mut txn, domain_id).await?;
+    txn.commit().await?;
+
+    // The key rows are gone, so a failure only leaves an unused secret behind
+    for key_id in &retired {
+        if let Err(error) = api
+            .credential_provider
+            .delete_credentials(&CredentialKey::DnsDomainKey {
+                domain_id,
+                key_id: *key_id,
+            })
+            .await
+        {
+            tracing::warn!(%domain_id, key_id = *key_id, ?error, "Failed to delete retired DNSSEC key");
+        }
+    }
+
+    tracing::info!(
+        %domain_id,
+        %kind,
+        deactivated = ?active,
+        removed = ?retired,
+        "Rolled DNSSEC keys"
+    );
+
+    Ok(Response::new(protos::dns::DomainKeysResponse {
+        keys: keys.into_iter().map(Into::into).collect(),
+    }))
+}
+
+/// Returns the domains which need a NOTIFY to be sent to secondaries (the
+/// PowerDNS `getUpdatedMasters` method)
+pub async fn get_updated_masters(
+    api: &Api,
+    request: Request<protos::dns::GetUpdatedMastersRequest>,
+) -> Result<Response<protos::dns::GetUpdatedMastersResponse>, Status> {
+    log_request_data(&request);
+
+    apply_pending_serial_bumps(api, None).await?;
+    let domains = db::dns::domain::find_updated_masters(&api.database_connection).await?;
+
+    tracing::debug!(count = domains.len(), "Found updated master domains");
+
+    Ok(Response::new(protos::dns::GetUpdatedMastersResponse {
+        result: domains.into_iter().map(Into::into).collect(),
+    }))
+}
+
+/// Records the serial a NOTIFY was sent for (the PowerDNS `setNotified` method)
+pub async fn set_notified(
+    api: &Api,
+    request: Request<protos::dns::SetNotifiedRequest>,
+) -> Result<Response<protos::dns::SetNotifiedResponse>, Status> {
+    log_request_data(&request);
+
+    let request = request.into_inner();
+    let domain_id = request.id.ok_or(CarbideError::MissingArgument("id"))?;
+
+    let mut txn = api.txn_begin().await?;
+    db::dns::domain::set_notified(&mut txn, domain_id, request.serial).await?;
+    txn.commit().await?;
+
+    Ok(Response::new(protos::dns::SetNotifiedResponse {}))
+}
+
 pub async fn lookup_record(
     api: &Api,
     request: Request<protos::dns::DnsResourceRecordLookupRequest>,
@@ -295,7 +595,7 @@ pub async fn lookup_record(
         }
         _ => {
             // For all other types (A, AAAA, MX, CNAME, etc.):
-            lookup_records_by_qname(&api.database_connection, &qname).await?
+            lookup_records_by_qname(&mut api.db_reader(), &qname).await?
         }
     };
 
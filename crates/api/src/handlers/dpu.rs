@@ -447,6 +447,9 @@ pub(crate) async fn get_managed_host_network_config_inner(
                             Some(vpc_peering_policy) => Some(vpc_peering_policy)
                         },
                         &booturl_override,
+                        api.runtime_config
+                            .network_security_group
+                            .max_network_security_group_size as usize,
                 )
                 .await?;
 
@@ -564,6 +567,39 @@ pub(crate) async fn get_managed_host_network_config_inner(
         Vec::new()
     };
 
+    // Site-wide policy overrides are not owned by any tenant, so references
+    // in them resolve regardless of the tenant of the referenced object.
+    let policy_overrides = &api.runtime_config.network_security_group.policy_overrides;
+    let policy_override_prefixes =
+        ethernet_virtualization::load_rule_net_prefixes(&mut txn, policy_overrides, None).await?;
+
+    // The TenantConfig of the instance can override the FMDS session token settings of the tenant
+    let (fmds_token_required, fmds_token_hop_limit) = match snapshot.instance.as_ref() {
+        Some(instance) => {
+            let config = &instance.config.tenant;
+            let tenant = if config.metadata_token_required.is_none()
+                || config.metadata_token_hop_limit.is_none()
+            {
+                db::tenant::find(config.tenant_organization_id.as_str(), false, &mut txn).await?
+            } else {
+                None
+            };
+            (
+                config.metadata_token_required.unwrap_or_else(|| {
+                    tenant
+                        .as_ref()
+                        .is_some_and(|tenant| tenant.metadata_token_required)
+                }),
+                config.metadata_token_hop_limit.or_else(|| {
+                    tenant
+                        .as_ref()
+                        .and_then(|tenant| tenant.metadata_token_hop_limit)
+                }),
+            )
+        }
+        None => (false, None),
+    };
+
     // Next, get credentials for each extension service from vault. This should be done after the
     // transaction is committed.
     txn.commit().await?;
@@ -629,17 +665,21 @@ pub(crate) async fn get_managed_host_network_config_inner(
             HBN_SINGLE_VLAN_DEVICE.to_string()
         },
         site_global_vpc_vni: api.runtime_config.site_global_vpc_vni,
+        fmds_token_required,
+        fmds_token_hop_limit,
         managed_host_config: Some(network_config),
         managed_host_config_version: dpu_snapshot.network_config.version.version_string(),
         use_admin_network,
         admin_interface: Some(admin_interface_rpc),
         tenant_interfaces,
-        network_security_policy_overrides: api
-            .runtime_config
-            .network_security_group
-            .policy_overrides
+        network_security_policy_overrides: policy_overrides
             .iter()
-            .map(|r| ethernet_virtualization::resolve_security_group_rule(r.clone()))
+            .map(|r| {
+                ethernet_virtualization::resolve_security_group_rule(
+                    r.clone(),
+                    &policy_override_prefixes,
+                )
+            })
             .collect::<Result<Vec<rpc::ResolvedNetworkSecurityGroupRule>, CarbideError>>()?,
         stateful_acls_enabled: api
             .runtime_config
@@ -666,6 +706,7 @@ pub(crate) async fn get_managed_host_network_config_inner(
         is_primary_dpu,
         min_dpu_functioning_links: api.runtime_config.min_dpu_functioning_links,
         dpu_network_pinger_type: api.runtime_config.dpu_network_monitor_pinger_type.clone(),
+        dpu_network_monitor_config: Some((&api.runtime_config.dpu_network_monitor).into()),
         internet_l3_vni: Some(api.runtime_config.internet_l3_vni), // Deprecated.  Remove when all agents and controllers are on a version that doesn't expect this.
         common_internal_route_target: api.runtime_config.fnn.as_ref().and_then(|c| {
             c.common_internal_route_target
@@ -1193,6 +1234,10 @@ pub(crate) async fn trigger_dpu_reprovisioning(
 
     txn.commit().await?;
 
+    api.machine_state_handler_enqueuer
+        .wake_up(&snapshot.host_snapshot.id)
+        .await;
+
     Ok(Response::new(()))
 }
 
@@ -0,0 +1,91 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::db::dpu_reachability as db;
+use ::rpc::forge as rpc;
+use model::dpu_reachability::DpuReachabilityReport;
+use tonic::{Request, Response, Status};
+
+use crate::CarbideError;
+use crate::api::{Api, log_request_data};
+
+/// Maximum number of DPU reports returned per page of the reachability matrix
+const MAX_MATRIX_PAGE_SIZE: u32 = 100;
+
+/// Store the results of the latest check cycle of the network monitor of a DPU
+pub async fn record_report(
+    api: &Api,
+    request: Request<rpc::DpuReachabilityReport>,
+) -> Result<Response<()>, Status> {
+    log_request_data(&request);
+
+    let report =
+        DpuReachabilityReport::try_from(request.into_inner()).map_err(CarbideError::from)?;
+    if !report.dpu_id.machine_type().is_dpu() {
+        return Err(CarbideError::InvalidArgument(format!(
+            "{} is not a DPU machine ID",
+            report.dpu_id
+        ))
+        .into());
+    }
+
+    let mut txn = api.txn_begin().await?;
+    db::upsert(&mut txn, &report).await?;
+    txn.commit().await?;
+
+    Ok(Response::new(()))
+}
+
+/// One page of the latest reports of the DPUs, optionally limited to the probes on the overlay
+/// of a VPC
+pub async fn get_matrix(
+    api: &Api,
+    request: Request<rpc::DpuReachabilityMatrixRequest>,
+) -> Result<Response<rpc::DpuReachabilityMatrix>, Status> {
+    log_request_data(&request);
+
+    let request = request.into_inner();
+    let limit = match request.limit {
+        None | Some(0) => MAX_MATRIX_PAGE_SIZE,
+        Some(limit) => limit.min(MAX_MATRIX_PAGE_SIZE),
+    };
+
+    // One more report than requested tells whether there is another page
+    let mut reports = db::find_page(
+        &api.database_connection,
+        request.after_dpu_id.as_ref(),
+        limit + 1,
+    )
+    .await?;
+    let next_dpu_id = if reports.len() > limit as usize {
+        reports.truncate(limit as usize);
+        reports.last().map(|report| report.dpu_id)
+    } else {
+        None
+    };
+
+    if let Some(vpc_id) = request.vpc_id.as_deref() {
+        for report in reports.iter_mut() {
+            report.retain_vpc(vpc_id);
+        }
+    }
+
+    Ok(Response::new(rpc::DpuReachabilityMatrix {
+        reports: reports.into_iter().map(Into::into).collect(),
+        next_dpu_id,
+    }))
+}
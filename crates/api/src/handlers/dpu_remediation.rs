@@ -15,15 +15,18 @@
  * limitations under the License.
  */
 use ::rpc::forge as rpc;
+use chrono::Utc;
 use db::dpu_remediation::AppliedRemediationIdQueryType;
 use model::dpu_remediation::{
     ApproveRemediation, DisableRemediation, EnableRemediation, NewRemediation, RevokeRemediation,
 };
+use model::machine::LoadSnapshotOptions;
 use tonic::{Request, Response, Status};
 
 use crate::api::Api;
 use crate::auth;
 use crate::errors::CarbideError;
+use crate::maintenance_window;
 
 /// all of the requests that modify a remediation _require_ an external_user_name from a client cert.
 /// (even if that particular request doesn't actually persist the name, it's always at least logged)
@@ -288,9 +291,25 @@ pub(crate) async fn get_next_remediation_for_machine(
         .dpu_machine_id
         .ok_or(CarbideError::MissingArgument("machine id"))?;
 
-    let remediation_to_apply =
+    let mut remediation_to_apply =
         db::dpu_remediation::find_next_remediation_for_machine(&mut txn, machine_id).await?;
 
+    // Remediations are only applied while the maintenance windows of the host allow it
+    if remediation_to_apply.is_some() {
+        let snapshot =
+            db::managed_host::load_snapshot(&mut txn, &machine_id, LoadSnapshotOptions::default())
+                .await?;
+        if let Some(snapshot) = snapshot
+            && !maintenance_window::is_host_allowed(&mut txn, &snapshot, Utc::now()).await?
+        {
+            tracing::debug!(
+                %machine_id,
+                "Deferring DPU remediation until the maintenance window of the host opens"
+            );
+            remediation_to_apply = None;
+        }
+    }
+
     let remediation_id = remediation_to_apply.as_ref().map(|r| r.id);
     let remediation_script = remediation_to_apply.map(|r| r.script);
 
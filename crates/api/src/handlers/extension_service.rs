@@ -21,6 +21,7 @@ use config_version::ConfigVersion;
 use db::{WithTransaction, extension_service, instance};
 use forge_secrets::credentials::{CredentialKey, Credentials};
 use futures_util::FutureExt;
+use model::extension_service::container::ContainerSpec;
 use model::extension_service::{ExtensionServiceObservability, ExtensionServiceType};
 use model::tenant::TenantOrganizationId;
 use tonic::{Request, Response, Status};
@@ -871,6 +872,71 @@ fn validate_pod_spec_file(data: &str) -> Result<(), CarbideError> {
     Ok(())
 }
 
+/// Validates the container spec for Container service.
+/// The container spec must be a valid YAML/JSON object with a non-empty image. Mounts must use
+/// absolute paths and environment variable names must not contain `=`.
+fn validate_container_spec(data: &str) -> Result<(), CarbideError> {
+    if data.is_empty() {
+        return Err(CarbideError::InvalidArgument(
+            "Invalid empty data for Container service, need a valid container spec".to_string(),
+        ));
+    }
+
+    let spec = serde_yaml::from_str::<ContainerSpec>(data).map_err(|e| {
+        CarbideError::InvalidArgument(format!("Invalid container spec for Container service: {e}"))
+    })?;
+
+    spec.validate().map_err(CarbideError::InvalidArgument)
+}
+
+/// Validates the unit file for SystemdUnit service.
+/// The unit file must contain a `[Service]` section with an `ExecStart` setting.
+fn validate_systemd_unit(data: &str) -> Result<(), CarbideError> {
+    if data.trim().is_empty() {
+        return Err(CarbideError::InvalidArgument(
+            "Invalid empty data for SystemdUnit service, need a valid unit file".to_string(),
+        ));
+    }
+
+    let mut section = None;
+    let mut has_exec_start = false;
+    for line in data.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            section = Some(name);
+            continue;
+        }
+        let Some((key, _value)) = line.split_once('=') else {
+            // Continuation of the previous line
+            if section.is_none() {
+                return Err(CarbideError::InvalidArgument(format!(
+                    "Invalid unit file for SystemdUnit service, setting outside of a section: {line}"
+                )));
+            }
+            continue;
+        };
+        match section {
+            Some("Service") if key.trim() == "ExecStart" => has_exec_start = true,
+            Some(_) => {}
+            None => {
+                return Err(CarbideError::InvalidArgument(format!(
+                    "Invalid unit file for SystemdUnit service, setting outside of a section: {line}"
+                )));
+            }
+        }
+    }
+
+    if !has_exec_start {
+        return Err(CarbideError::InvalidArgument(
+            "Unit file missing required setting: ExecStart in section [Service]".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
 /// Validates extension service data fields based on service type
 fn validate_extension_service_data(
     service_type: &ExtensionServiceType,
@@ -889,6 +955,8 @@ fn validate_extension_service_data(
 
             Ok(())
         }
+        ExtensionServiceType::Container => validate_container_spec(data),
+        ExtensionServiceType::SystemdUnit => validate_systemd_unit(data),
     }
 }
 
@@ -897,6 +965,12 @@ fn validate_extension_service_credential(
     service_type: &ExtensionServiceType,
     credential: &rpc::DpuExtensionServiceCredential,
 ) -> Result<(), CarbideError> {
+    if *service_type == ExtensionServiceType::SystemdUnit {
+        return Err(CarbideError::InvalidArgument(
+            "Credentials are not supported for SystemdUnit service".to_string(),
+        ));
+    }
+
     match credential.r#type.as_ref() {
         Some(rpc::dpu_extension_service_credential::Type::UsernamePassword(up)) => {
             // @TODO(Felicity): Add more validation for username and password
@@ -919,16 +993,18 @@ fn validate_extension_service_credential(
     };
 
     match service_type {
-        ExtensionServiceType::KubernetesPod => {
+        ExtensionServiceType::KubernetesPod | ExtensionServiceType::Container => {
             // Validate registry URL, this will be fed into the credential provider as
             // image match pattern. For example, if the registry URL is "nvcr.io/nvforge",
-            // kubelet will match all images under "nvcr.io/nvforge/*".
+            // kubelet will match all images under "nvcr.io/nvforge/*". For Container services
+            // the DPU agent uses the credential for images with the registry URL as prefix.
             if credential.registry_url.is_empty() || credential.registry_url.len() > 255 {
                 return Err(CarbideError::InvalidArgument(
                     "Invalid credential registry URL".to_string(),
                 ));
             }
         }
+        ExtensionServiceType::SystemdUnit => {}
     }
 
     Ok(())
@@ -960,6 +1036,24 @@ fn detect_extension_service_spec_change(
                 })?;
             old_data_yaml != new_data_yaml
         }
+        ExtensionServiceType::Container => {
+            let old_data_yaml =
+                serde_yaml::from_str::<serde_yaml::Value>(old_data).map_err(|e| {
+                    CarbideError::internal(format!(
+                        "Found corrupted data for Container service: {}",
+                        e
+                    ))
+                })?;
+            let new_data_yaml =
+                serde_yaml::from_str::<serde_yaml::Value>(new_data).map_err(|e| {
+                    CarbideError::InvalidArgument(format!(
+                        "Invalid container spec for Container service: {}",
+                        e
+                    ))
+                })?;
+            old_data_yaml != new_data_yaml
+        }
+        ExtensionServiceType::SystemdUnit => old_data != new_data,
     };
 
     let cred_changed = match (old_cred.as_ref(), new_cred.as_ref()) {
@@ -990,7 +1084,7 @@ async fn create_extension_service_credential(
     credential: &rpc::DpuExtensionServiceCredential,
 ) -> Result<(), CarbideError> {
     match service_type {
-        ExtensionServiceType::KubernetesPod => {
+        ExtensionServiceType::KubernetesPod | ExtensionServiceType::Container => {
             use ::rpc::forge::dpu_extension_service_credential::Type as CredType;
 
             match credential.r#type.as_ref() {
@@ -1021,6 +1115,9 @@ async fn create_extension_service_credential(
                 )),
             }
         }
+        ExtensionServiceType::SystemdUnit => Err(CarbideError::InvalidArgument(
+            "Credentials are not supported for SystemdUnit service".to_string(),
+        )),
     }
 }
 
@@ -86,6 +86,10 @@ pub(crate) async fn trigger_host_reprovisioning(
 
     txn.commit().await?;
 
+    api.machine_state_handler_enqueuer
+        .wake_up(&machine_id)
+        .await;
+
     Ok(Response::new(()))
 }
 
@@ -37,6 +37,7 @@ use model::instance::config::network::{InstanceNetworkConfig, NetworkDetails};
 use model::instance::config::nvlink::InstanceNvLinkConfig;
 use model::instance::config::tenant_config::TenantConfig;
 use model::instance::snapshot::InstanceSnapshot;
+use model::instance_webhook::{InstanceLifecycleEvent, InstanceLifecycleEventType};
 use model::machine::machine_search_config::MachineSearchConfig;
 use model::machine::{
     InstanceState, LoadSnapshotOptions, ManagedHostState, ManagedHostStateSnapshot,
@@ -48,6 +49,9 @@ use tonic::{Request, Response, Status};
 
 use crate::api::{Api, log_machine_id, log_request_data, log_tenant_organization_id};
 use crate::handlers::utils::convert_and_log_machine_id;
+use crate::instance::placement::{
+    InstanceTypeAllocationRequest, allocate_instances_by_instance_type,
+};
 use crate::instance::{
     InstanceAllocationRequest, allocate_ib_port_guid, allocate_instance, allocate_network,
     validate_ib_partition_ownership,
@@ -177,6 +181,38 @@ pub(crate) async fn batch_allocate(
     }))
 }
 
+pub(crate) async fn allocate_by_instance_type(
+    api: &Api,
+    request: Request<rpc::InstanceTypeAllocationRequest>,
+) -> Result<Response<rpc::BatchInstanceAllocationResponse>, Status> {
+    log_request_data(&request);
+
+    let request = InstanceTypeAllocationRequest::try_from(request.into_inner())?;
+
+    for instance_request in &request.instance_requests {
+        if let Some(tenant) = instance_request
+            .config
+            .as_ref()
+            .and_then(|config| config.tenant.as_ref())
+        {
+            log_tenant_organization_id(tenant.tenant_organization_id.as_str());
+        }
+    }
+
+    // Row-locking on Machine records happens in allocate_instances_by_instance_type
+    let snapshots =
+        allocate_instances_by_instance_type(api, request, api.runtime_config.host_health).await?;
+
+    let instances = snapshots
+        .into_iter()
+        .map(snapshot_to_instance)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Response::new(rpc::BatchInstanceAllocationResponse {
+        instances,
+    }))
+}
+
 pub(crate) async fn find_ids(
     api: &Api,
     request: Request<rpc::InstanceSearchFilter>,
@@ -695,9 +731,21 @@ pub(crate) async fn release(
     // see an error here that is not returned as `NotFound` error. Ideally
     // we convert this case of the DatabaseError into NotFound too.
     db::instance::mark_as_deleted(delete_instance.instance_id, &mut txn).await?;
+    db::instance_webhook::enqueue_event(
+        &mut txn,
+        &InstanceLifecycleEvent::for_instance(
+            InstanceLifecycleEventType::ReleaseStarted,
+            &instance,
+        ),
+    )
+    .await?;
 
     txn.commit().await?;
 
+    api.machine_state_handler_enqueuer
+        .wake_up(&instance.machine_id)
+        .await;
+
     Ok(Response::new(rpc::InstanceReleaseResult {}))
 }
 
@@ -1474,6 +1522,11 @@ pub async fn force_delete_instance(
     // TODO: This might need some changes with the new state machine
     let mut txn = api.txn_begin().await?;
     db::instance::delete(instance_id, &mut txn).await?;
+    db::instance_webhook::enqueue_event(
+        &mut txn,
+        &InstanceLifecycleEvent::for_instance(InstanceLifecycleEventType::CleanedUp, &instance),
+    )
+    .await?;
 
     let mut network_segment_ids_with_vpc = vec![];
     if let Some(update_network_req) = &instance.update_network_config_request {
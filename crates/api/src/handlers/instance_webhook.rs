@@ -0,0 +1,180 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::db::instance_webhook as db;
+use ::rpc::forge as rpc;
+use forge_secrets::credentials::{CredentialKey, Credentials};
+use model::instance_webhook::{InstanceWebhookSubscription, validate_webhook_target};
+use model::tenant::TenantOrganizationId;
+use tonic::{Request, Response, Status};
+use uuid::Uuid;
+
+use crate::CarbideError;
+use crate::api::{Api, log_request_data};
+
+fn parse_tenant_filter(
+    filter: rpc::InstanceWebhookSearchFilter,
+) -> Result<Option<TenantOrganizationId>, CarbideError> {
+    filter
+        .tenant_organization_id
+        .map(|tenant| {
+            TenantOrganizationId::try_from(tenant.clone()).map_err(|_| {
+                CarbideError::InvalidArgument(format!("invalid tenant organization ID {tenant}"))
+            })
+        })
+        .transpose()
+}
+
+pub async fn create_subscription(
+    api: &Api,
+    request: Request<rpc::InstanceWebhookSubscription>,
+) -> Result<Response<rpc::InstanceWebhookSubscription>, Status> {
+    // Do not log_request_data as the request contains the signing secret
+
+    let subscription =
+        InstanceWebhookSubscription::try_from(request.into_inner()).map_err(CarbideError::from)?;
+    let Some(secret) = subscription.secret.clone() else {
+        return Err(CarbideError::MissingArgument("secret").into());
+    };
+    if !api.runtime_config.instance_webhooks.allow_insecure_targets {
+        validate_webhook_target(&subscription.url)
+            .await
+            .map_err(|e| CarbideError::InvalidArgument(format!("invalid webhook url: {e}")))?;
+    }
+
+    let mut txn = api.txn_begin().await?;
+    let mut created = db::create_subscription(&mut txn, &subscription).await?;
+    api.credential_provider
+        .set_credentials(
+            &CredentialKey::InstanceWebhookSecret {
+                subscription_id: created.id.to_string(),
+            },
+            &Credentials::UsernamePassword {
+                username: created.tenant_organization_id.to_string(),
+                password: secret,
+            },
+        )
+        .await
+        .map_err(|e| {
+            CarbideError::internal(format!(
+                "Failed to store secret of instance webhook subscription {}: {e}",
+                created.id
+            ))
+        })?;
+    txn.commit().await?;
+    created.secret = subscription.secret;
+    let subscription = created;
+
+    tracing::info!(
+        subscription_id = %subscription.id,
+        tenant_organization_id = %subscription.tenant_organization_id,
+        url = subscription.url,
+        "Created instance webhook subscription"
+    );
+
+    // The secret is returned once, so that callers which didn't provide one learn it
+    Ok(Response::new(subscription.into()))
+}
+
+pub async fn find_subscriptions(
+    api: &Api,
+    request: Request<rpc::InstanceWebhookSearchFilter>,
+) -> Result<Response<rpc::InstanceWebhookSubscriptionList>, Status> {
+    log_request_data(&request);
+
+    let tenant_organization_id = parse_tenant_filter(request.into_inner())?;
+
+    let mut txn = api.txn_begin().await?;
+    let subscriptions = db::find_subscriptions(&mut txn, tenant_organization_id.as_ref()).await?;
+    txn.commit().await?;
+
+    Ok(Response::new(rpc::InstanceWebhookSubscriptionList {
+        subscriptions: subscriptions.into_iter().map(Into::into).collect(),
+    }))
+}
+
+pub async fn delete_subscription(
+    api: &Api,
+    request: Request<rpc::DeleteInstanceWebhookSubscriptionRequest>,
+) -> Result<Response<()>, Status> {
+    log_request_data(&request);
+
+    let rpc::DeleteInstanceWebhookSubscriptionRequest { id } = request.into_inner();
+    let id = Uuid::parse_str(&id).map_err(|_| {
+        CarbideError::InvalidArgument(format!("invalid instance webhook subscription ID {id}"))
+    })?;
+
+    let mut txn = api.txn_begin().await?;
+    db::delete_subscription(&mut txn, id).await?;
+    txn.commit().await?;
+
+    // The subscription is gone, so a failure only leaves an unused secret behind
+    if let Err(error) = api
+        .credential_provider
+        .delete_credentials(&CredentialKey::InstanceWebhookSecret {
+            subscription_id: id.to_string(),
+        })
+        .await
+    {
+        tracing::warn!(subscription_id = %id, ?error, "Failed to delete instance webhook secret");
+    }
+
+    Ok(Response::new(()))
+}
+
+pub async fn find_dead_letters(
+    api: &Api,
+    request: Request<rpc::InstanceWebhookSearchFilter>,
+) -> Result<Response<rpc::InstanceWebhookDeliveryList>, Status> {
+    log_request_data(&request);
+
+    let tenant_organization_id = parse_tenant_filter(request.into_inner())?;
+
+    let mut txn = api.txn_begin().await?;
+    let deliveries = db::find_dead_letters(&mut txn, tenant_organization_id.as_ref()).await?;
+    txn.commit().await?;
+
+    Ok(Response::new(rpc::InstanceWebhookDeliveryList {
+        deliveries: deliveries.into_iter().map(Into::into).collect(),
+    }))
+}
+
+pub async fn retry_dead_letters(
+    api: &Api,
+    request: Request<rpc::RetryInstanceWebhookDeadLettersRequest>,
+) -> Result<Response<rpc::RetryInstanceWebhookDeadLettersResponse>, Status> {
+    log_request_data(&request);
+
+    let rpc::RetryInstanceWebhookDeadLettersRequest { delivery_ids } = request.into_inner();
+    if delivery_ids.is_empty() {
+        return Err(CarbideError::InvalidArgument(
+            "at least one delivery ID is required".to_string(),
+        )
+        .into());
+    }
+    let delivery_ids: Vec<i64> = delivery_ids.into_iter().map(|id| id as i64).collect();
+
+    let mut txn = api.txn_begin().await?;
+    let retried_count = db::retry_dead_letters(&mut txn, &delivery_ids).await?;
+    txn.commit().await?;
+
+    Ok(Response::new(
+        rpc::RetryInstanceWebhookDeadLettersResponse {
+            retried_count: retried_count as u32,
+        },
+    ))
+}
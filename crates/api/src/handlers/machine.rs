@@ -195,6 +195,48 @@ pub(crate) async fn find_machine_health_histories(
     Ok(Response::new(response))
 }
 
+pub(crate) async fn find_state_handler_outcome_history(
+    api: &Api,
+    request: Request<rpc::StateHandlerOutcomeHistoryRequest>,
+) -> Result<Response<rpc::StateHandlerOutcomeHistories>, Status> {
+    log_request_data(&request);
+    let request = request.into_inner();
+
+    let machine_ids = request.machine_ids;
+
+    let max_find_by_ids = api.runtime_config.max_find_by_ids as usize;
+    if machine_ids.len() > max_find_by_ids {
+        return Err(CarbideError::InvalidArgument(format!(
+            "no more than {max_find_by_ids} IDs can be accepted"
+        ))
+        .into());
+    } else if machine_ids.is_empty() {
+        return Err(
+            CarbideError::InvalidArgument("at least one ID must be provided".to_string()).into(),
+        );
+    }
+
+    let mut txn = api.txn_begin().await?;
+
+    let results =
+        db::machine_state_handler_outcome_history::find_by_machine_ids(&mut txn, &machine_ids)
+            .await?;
+
+    let mut response = rpc::StateHandlerOutcomeHistories::default();
+    for (machine_id, records) in results {
+        response.histories.insert(
+            machine_id.to_string(),
+            ::rpc::forge::StateHandlerOutcomeHistoryRecords {
+                records: records.into_iter().map(Into::into).collect(),
+            },
+        );
+    }
+
+    txn.commit().await?;
+
+    Ok(Response::new(response))
+}
+
 pub(crate) async fn machine_set_auto_update(
     api: &Api,
     request: Request<rpc::MachineSetAutoUpdateRequest>,
@@ -636,7 +678,7 @@ pub(crate) async fn admin_force_delete_machine(
     Ok(Response::new(response))
 }
 
-/// Retrieves all DPU information including id and loopback IP
+/// Retrieves all DPU information including id, loopback IP and the targets of VPC overlay probes
 pub(crate) async fn get_dpu_info_list(
     api: &Api,
     request: Request<rpc::GetDpuInfoListRequest>,
@@ -645,7 +687,17 @@ pub(crate) async fn get_dpu_info_list(
 
     let mut txn = api.txn_begin().await?;
 
-    let dpu_list = db::machine::find_dpu_ids_and_loopback_ips(&mut txn).await?;
+    let mut dpu_list = db::machine::find_dpu_ids_and_loopback_ips(&mut txn).await?;
+
+    if api.runtime_config.dpu_network_monitor.vpc_overlay_probes {
+        let mut targets: HashMap<String, Vec<rpc::DpuVpcProbeTarget>> = HashMap::new();
+        for (dpu_id, target) in db::machine::find_dpu_vpc_probe_targets(&mut txn).await? {
+            targets.entry(dpu_id).or_default().push(target);
+        }
+        for dpu in dpu_list.iter_mut() {
+            dpu.vpc_probe_targets = targets.remove(&dpu.id).unwrap_or_default();
+        }
+    }
 
     txn.commit().await?;
 
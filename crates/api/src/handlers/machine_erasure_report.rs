@@ -0,0 +1,85 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::db::machine_erasure_report as db;
+use ::rpc::forge as rpc;
+use model::machine_erasure_report::MachineErasureReport;
+use tonic::{Request, Response, Status};
+
+use crate::CarbideError;
+use crate::api::{Api, log_request_data};
+use crate::cfg::file::ErasureReportConfig;
+use crate::machine_identity::{Es256Signer, SignOptions, Signer};
+
+/// The certificate-of-erasure reports of a machine and/or a released instance, newest first
+pub async fn get_reports(
+    api: &Api,
+    request: Request<rpc::MachineErasureReportsRequest>,
+) -> Result<Response<rpc::MachineErasureReportList>, Status> {
+    log_request_data(&request);
+
+    let request = request.into_inner();
+    if request.machine_id.is_none() && request.instance_id.is_none() {
+        return Err(CarbideError::InvalidArgument(
+            "Either a machine ID or an instance ID is required".to_string(),
+        )
+        .into());
+    }
+
+    let reports = db::find(
+        &api.database_connection,
+        request.machine_id.as_ref(),
+        request.instance_id,
+    )
+    .await?;
+
+    Ok(Response::new(rpc::MachineErasureReportList {
+        reports: reports.into_iter().map(Into::into).collect(),
+    }))
+}
+
+/// Signs a completed report with the configured key.
+/// Returns `None` if no key is configured or signing fails, in which case the report is stored
+/// unsigned rather than failing the cleanup of the machine.
+pub(crate) fn sign_report(
+    config: &ErasureReportConfig,
+    report: &MachineErasureReport,
+) -> Option<String> {
+    let key_path = config.signing_key_path.as_ref()?;
+    let result = std::fs::read(key_path)
+        .map_err(|e| format!("failed to read {}: {e}", key_path.display()))
+        .and_then(|key| {
+            Es256Signer::new(&key, config.signing_key_id.clone()).map_err(|e| e.to_string())
+        })
+        .and_then(|signer| {
+            signer
+                .sign(&report.claims(), &SignOptions::default())
+                .map_err(|e| e.to_string())
+        });
+    match result {
+        Ok(signed_report) => Some(signed_report),
+        Err(error) => {
+            tracing::error!(
+                %error,
+                machine_id = %report.machine_id,
+                report_id = %report.id,
+                "Failed to sign erasure report"
+            );
+            None
+        }
+    }
+}
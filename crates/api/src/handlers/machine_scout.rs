@@ -16,18 +16,22 @@
  */
 use ::rpc::forge as rpc;
 use ::rpc::forge_agent_control_response::forge_agent_control_extra_info::KeyValuePair;
+use carbide_uuid::machine::MachineId;
 use model::machine::machine_search_config::MachineSearchConfig;
 use model::machine::{
     BomValidating, CleanupState, FailureCause, FailureDetails, FailureSource, InstanceState,
     MachineState, MachineValidatingState, ManagedHostState, MeasuringState, ValidationState,
     get_action_for_dpu_state,
 };
+use model::machine_erasure_report::{MachineErasureReport, StorageErasureRecord};
 use model::machine_validation::{MachineValidationState, MachineValidationStatus};
+use sqlx::PgConnection;
 use tonic::{Request, Response, Status};
 
 use crate::CarbideError;
 use crate::api::metrics::ApiMetricsEmitter;
 use crate::api::{Api, log_request_data};
+use crate::handlers::machine_erasure_report;
 use crate::handlers::utils::convert_and_log_machine_id;
 
 // Transitions the machine to Ready state.
@@ -42,6 +46,13 @@ pub(crate) async fn cleanup_machine_completed(
     tracing::info!(?cleanup_info, "cleanup_machine_completed");
 
     let machine_id = convert_and_log_machine_id(cleanup_info.machine_id.as_ref())?;
+    let erasure_records = cleanup_info
+        .erasure_records
+        .iter()
+        .cloned()
+        .map(StorageErasureRecord::try_from)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(CarbideError::from)?;
 
     // Load machine from DB
     let (machine, mut txn) = api
@@ -49,22 +60,28 @@ pub(crate) async fn cleanup_machine_completed(
         .await?;
 
     // Check if cleanup failed
-    if let Some(ref nvme_result) = cleanup_info.nvme
-        && rpc::machine_cleanup_info::CleanupResult::Error as i32 == nvme_result.result
-    {
-        // NVME Cleanup failed. Move machine to failed state.
-        tracing::warn!(
-            machine_id = %machine_id,
-            error = %nvme_result.message,
-            "NVMe cleanup failed"
-        );
+    let step_failed = |step: &Option<rpc::machine_cleanup_info::CleanupStepResult>| {
+        step.as_ref()
+            .filter(|step| rpc::machine_cleanup_info::CleanupResult::Error as i32 == step.result)
+            .map(|step| step.message.to_string())
+    };
+    let failure_cause = if let Some(err) = step_failed(&cleanup_info.nvme) {
+        tracing::warn!(machine_id = %machine_id, error = %err, "NVMe cleanup failed");
+        Some(FailureCause::NVMECleanFailed { err })
+    } else if let Some(err) = step_failed(&cleanup_info.sata_sas) {
+        tracing::warn!(machine_id = %machine_id, error = %err, "SATA/SAS cleanup failed");
+        Some(FailureCause::SataSasCleanFailed { err })
+    } else {
+        None
+    };
+    let cleanup_failed = failure_cause.is_some();
+    if let Some(cause) = failure_cause {
+        // Cleanup failed. Move machine to failed state.
         db::machine::update_failure_details(
             &machine,
             &mut txn,
             FailureDetails {
-                cause: FailureCause::NVMECleanFailed {
-                    err: nvme_result.message.to_string(),
-                },
+                cause,
                 failed_at: chrono::Utc::now(),
                 source: FailureSource::Scout,
             },
@@ -82,22 +99,58 @@ pub(crate) async fn cleanup_machine_completed(
         db::machine::update_cleanup_time(&machine, &mut txn).await?;
     }
 
+    record_erasure_report(api, &mut txn, &machine_id, erasure_records, cleanup_failed).await?;
+
     txn.commit().await?;
 
     // State handler should mark Machine as Adopted and reboot host for bios/bmc lockdown.
     // Wake it up
-    if machine_id.machine_type().is_host()
-        && let Err(err) = api
-            .machine_state_handler_enqueuer
-            .enqueue_object(&machine_id)
-            .await
-    {
-        tracing::warn!(%err, %machine_id, "Failed to wake up state handler for machine");
+    if machine_id.machine_type().is_host() {
+        api.machine_state_handler_enqueuer
+            .wake_up(&machine_id)
+            .await;
     }
 
     Ok(Response::new(rpc::MachineCleanupResult {}))
 }
 
+// Completes the erasure report opened when the instance of the machine was released.
+// Cleanups outside of an instance release only get a report if scout sent erasure records.
+// A failed cleanup is retried, so its records are stored without completing the report.
+async fn record_erasure_report(
+    api: &Api,
+    txn: &mut PgConnection,
+    machine_id: &MachineId,
+    records: Vec<StorageErasureRecord>,
+    cleanup_failed: bool,
+) -> Result<(), CarbideError> {
+    let pending = db::machine_erasure_report::find_pending(txn, machine_id).await?;
+    if pending.is_none() && records.is_empty() {
+        return Ok(());
+    }
+
+    let mut report = pending.unwrap_or_else(|| MachineErasureReport::without_release(*machine_id));
+    report.records = records;
+    if cleanup_failed {
+        db::machine_erasure_report::save(txn, &report).await?;
+        return Ok(());
+    }
+
+    report.completed_at = Some(chrono::Utc::now());
+    report.signed_report =
+        machine_erasure_report::sign_report(&api.runtime_config.erasure_reports, &report);
+    if !report.complete() {
+        tracing::warn!(
+            %machine_id,
+            report_id = %report.id,
+            "Erasure report is incomplete"
+        );
+    }
+
+    db::machine_erasure_report::save(txn, &report).await?;
+    Ok(())
+}
+
 // Invoked by forge-scout whenever a certain Machine can not be properly acted on
 pub(crate) fn report_forge_scout_error(
     _api: &Api,
@@ -251,6 +304,14 @@ pub(crate) async fn forge_agent_control(
                         ..
                     },
                 ..
+            }
+            | ManagedHostState::Failed {
+                details:
+                    FailureDetails {
+                        cause: FailureCause::SataSasCleanFailed { .. },
+                        ..
+                    },
+                ..
             } => {
                 let last_cleanup_time = host_machine.last_cleanup_time;
                 let state_version = host_machine.state.version;
@@ -395,13 +456,10 @@ pub(crate) async fn reboot_completed(
 
     // Wake up the state handler for the machine
     // Don't do it for DPUs - state handlers only run on hosts
-    if (machine_id.machine_type().is_host() || machine_id.machine_type().is_predicted_host())
-        && let Err(err) = api
-            .machine_state_handler_enqueuer
-            .enqueue_object(&machine_id)
-            .await
-    {
-        tracing::warn!(%err, %machine_id, "Failed to wake up state handler for machine");
+    if machine_id.machine_type().is_host() || machine_id.machine_type().is_predicted_host() {
+        api.machine_state_handler_enqueuer
+            .wake_up(&machine_id)
+            .await;
     }
 
     Ok(Response::new(rpc::MachineRebootCompletedResponse {}))
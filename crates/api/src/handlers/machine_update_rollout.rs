@@ -0,0 +1,109 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::db::machine_update_rollout as db;
+use ::rpc::forge as rpc;
+use chrono::Utc;
+use model::machine_update_rollout::{
+    MachineUpdateRollout, MachineUpdateRolloutAction, MachineUpdateRolloutState,
+};
+use tonic::{Request, Response, Status};
+
+use crate::CarbideError;
+use crate::api::{Api, log_request_data};
+
+pub async fn get(
+    api: &Api,
+    request: Request<rpc::MachineUpdateRolloutRequest>,
+) -> Result<Response<rpc::MachineUpdateRollout>, Status> {
+    log_request_data(&request);
+
+    let rpc::MachineUpdateRolloutRequest { id } = request.into_inner();
+
+    let mut txn = api.txn_begin().await?;
+
+    let rollout = match id {
+        Some(id) => db::find_by_id(&mut txn, id).await?,
+        None => db::find_latest(&mut txn, false).await?,
+    };
+
+    txn.commit().await?;
+
+    let rollout = rollout.ok_or_else(|| CarbideError::NotFoundError {
+        kind: "MachineUpdateRollout",
+        id: id
+            .map(|id| id.to_string())
+            .unwrap_or_else(|| "latest".to_string()),
+    })?;
+
+    Ok(Response::new(to_rpc(api, rollout)))
+}
+
+pub async fn action(
+    api: &Api,
+    request: Request<rpc::MachineUpdateRolloutActionRequest>,
+) -> Result<Response<rpc::MachineUpdateRollout>, Status> {
+    log_request_data(&request);
+
+    let request = request.into_inner();
+    let action = MachineUpdateRolloutAction::from(request.action());
+
+    let mut txn = api.txn_begin().await?;
+
+    let mut rollout =
+        db::find_latest(&mut txn, true)
+            .await?
+            .ok_or_else(|| CarbideError::NotFoundError {
+                kind: "MachineUpdateRollout",
+                id: "latest".to_string(),
+            })?;
+
+    let rollout = if rollout.state == MachineUpdateRolloutState::Aborted
+        && action == MachineUpdateRolloutAction::Resume
+    {
+        // Resuming after an abort starts over with the first wave
+        let mut new_rollout = MachineUpdateRollout::new(Utc::now());
+        new_rollout.state_reason = request.reason;
+        db::create(&mut txn, &new_rollout).await?
+    } else {
+        rollout
+            .apply(action, request.reason)
+            .map_err(CarbideError::FailedPrecondition)?;
+        db::save(&mut txn, &rollout).await?
+    };
+
+    txn.commit().await?;
+
+    tracing::info!(
+        rollout_id = ?rollout.id,
+        state = %rollout.state,
+        "Applied {action:?} to machine update rollout"
+    );
+
+    Ok(Response::new(to_rpc(api, rollout)))
+}
+
+/// Converts a rollout into its RPC representation, using the configured rollout policy
+fn to_rpc(api: &Api, rollout: MachineUpdateRollout) -> rpc::MachineUpdateRollout {
+    let policy = api.runtime_config.machine_updater.rollout.as_ref();
+    let wave_count = policy.map(|policy| policy.wave_count()).unwrap_or_default();
+    let soak_ends_at = rollout
+        .wave_completed_at
+        .zip(policy)
+        .map(|(wave_completed_at, policy)| wave_completed_at + policy.soak_period);
+    rollout.into_rpc(wave_count, soak_ends_at)
+}
@@ -0,0 +1,74 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::db::maintenance_window as db;
+use ::rpc::forge as rpc;
+use chrono::Utc;
+use model::maintenance_window::MaintenanceWindow;
+use tonic::{Request, Response, Status};
+
+use crate::CarbideError;
+use crate::api::{Api, log_request_data};
+
+pub async fn create(
+    api: &Api,
+    request: Request<rpc::MaintenanceWindow>,
+) -> Result<Response<rpc::MaintenanceWindow>, Status> {
+    log_request_data(&request);
+
+    let window = MaintenanceWindow::try_from(request.into_inner()).map_err(CarbideError::from)?;
+
+    let mut txn = api.txn_begin().await?;
+    let window = db::create(&mut txn, &window).await?;
+    txn.commit().await?;
+
+    Ok(Response::new(window.into_rpc(Utc::now())))
+}
+
+pub async fn find(
+    api: &Api,
+    request: Request<()>,
+) -> Result<Response<rpc::MaintenanceWindowList>, Status> {
+    log_request_data(&request);
+
+    let mut txn = api.txn_begin().await?;
+    let windows = db::find_all(&mut txn).await?;
+    txn.commit().await?;
+
+    let now = Utc::now();
+    Ok(Response::new(rpc::MaintenanceWindowList {
+        windows: windows
+            .into_iter()
+            .map(|window| window.into_rpc(now))
+            .collect(),
+    }))
+}
+
+pub async fn delete(
+    api: &Api,
+    request: Request<rpc::DeleteMaintenanceWindowRequest>,
+) -> Result<Response<()>, Status> {
+    log_request_data(&request);
+
+    let rpc::DeleteMaintenanceWindowRequest { name } = request.into_inner();
+
+    let mut txn = api.txn_begin().await?;
+    db::delete(&mut txn, &name).await?;
+    txn.commit().await?;
+
+    Ok(Response::new(()))
+}
@@ -299,5 +299,9 @@ pub(crate) async fn set_maintenance(
         }
     };
 
+    api.machine_state_handler_enqueuer
+        .wake_up(&machine_id)
+        .await;
+
     Ok(Response::new(()))
 }
@@ -20,6 +20,7 @@ pub mod attestation;
 pub mod bmc_endpoint_explorer;
 pub mod bmc_metadata;
 pub mod boot_override;
+pub mod capacity_reservation;
 pub mod credential;
 pub mod db;
 pub mod dns;
@@ -27,6 +28,7 @@ pub mod domain;
 pub mod dpa;
 pub mod dpf;
 pub mod dpu;
+pub mod dpu_reachability;
 pub mod dpu_remediation;
 pub mod expected_machine;
 pub mod expected_power_shelf;
@@ -40,15 +42,19 @@ pub mod ib_fabric;
 pub mod ib_partition;
 pub mod instance;
 pub mod instance_type;
+pub mod instance_webhook;
 pub mod logical_partition;
 pub mod machine;
 pub mod machine_discovery;
+pub mod machine_erasure_report;
 pub mod machine_hardware_info;
 pub mod machine_identity;
 pub mod machine_interface;
 pub mod machine_quarantine;
 pub mod machine_scout;
+pub mod machine_update_rollout;
 pub mod machine_validation;
+pub mod maintenance_window;
 pub mod managed_host;
 pub mod measured_boot;
 pub mod mlx_admin;
@@ -67,8 +73,10 @@ pub mod route_server;
 pub mod scout_stream;
 pub mod site_explorer;
 pub mod sku;
+pub mod ssh_console_session;
 pub mod switch;
 pub mod tenant;
+pub mod tenant_dns_record;
 pub mod tenant_keyset;
 pub mod tpm_ca;
 pub mod uefi;
@@ -25,13 +25,16 @@ use carbide_uuid::vpc::VpcId;
 use config_version::ConfigVersion;
 use db::network_security_group;
 use model::metadata::Metadata;
-use model::network_security_group::{NetworkSecurityGroupRule, NetworkSecurityGroupRuleNet};
+use model::network_security_group::analysis::{self, NetworkSecurityGroupFlow};
+use model::network_security_group::{
+    NetworkSecurityGroupRule, NetworkSecurityGroupRuleNetPrefixes,
+};
 use model::tenant::{InvalidTenantOrg, TenantOrganizationId};
 use tonic::{Request, Response, Status};
 use uuid::Uuid;
 
-use crate::CarbideError;
 use crate::api::{Api, log_request_data, log_tenant_organization_id};
+use crate::{CarbideError, ethernet_virtualization};
 
 pub(crate) async fn create(
     api: &Api,
@@ -82,8 +85,6 @@ pub(crate) async fn create(
         .network_security_group
         .max_network_security_group_size as usize;
 
-    validate_expanded_rule_set(&rules, max_nsg_size)?;
-
     // Log tenant organization ID
     log_tenant_organization_id(&req.tenant_organization_id);
 
@@ -100,6 +101,15 @@ pub(crate) async fn create(
     // Start a new transaction for a db write.
     let mut txn = api.txn_begin().await?;
 
+    let prefixes = ethernet_virtualization::load_rule_net_prefixes(
+        &mut txn,
+        &rules,
+        Some(&tenant_organization_id),
+    )
+    .await?;
+    validate_rule_references(&rules, &prefixes)?;
+    validate_expanded_rule_set(&rules, &prefixes, max_nsg_size)?;
+
     // Write a new NetworkSecurityGroup to the DB and get back
     // our new NetworkSecurityGroup.
     let network_security_group = network_security_group::create(
@@ -299,8 +309,6 @@ pub(crate) async fn update(
         .network_security_group
         .max_network_security_group_size as usize;
 
-    validate_expanded_rule_set(&rules, max_nsg_size)?;
-
     // Log tenant organization ID from request
     log_tenant_organization_id(&req.tenant_organization_id);
 
@@ -317,6 +325,15 @@ pub(crate) async fn update(
     // Start a new transaction for a db write.
     let mut txn = api.txn_begin().await?;
 
+    let prefixes = ethernet_virtualization::load_rule_net_prefixes(
+        &mut txn,
+        &rules,
+        Some(&tenant_organization_id),
+    )
+    .await?;
+    validate_rule_references(&rules, &prefixes)?;
+    validate_expanded_rule_set(&rules, &prefixes, max_nsg_size)?;
+
     // Look up the NetworkSecurityGroup.  We'll need to check the current
     // version. We could probably do everything with a single query
     // with a few subqueries, but we'd only be able to send back a
@@ -629,12 +646,126 @@ pub(crate) async fn get_attachments(
     Ok(Response::new(rpc_out))
 }
 
+pub(crate) async fn simulate(
+    api: &Api,
+    request: Request<rpc::SimulateNetworkSecurityGroupRequest>,
+) -> Result<Response<rpc::SimulateNetworkSecurityGroupResponse>, Status> {
+    log_request_data(&request);
+
+    let req = request.into_inner();
+
+    // Validate the flows before touching the DB
+    let flows = req
+        .flows
+        .into_iter()
+        .map(|f| NetworkSecurityGroupFlow::try_from(f.clone()).map(|flow| (f, flow)))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(CarbideError::from)?;
+
+    let mut txn = api.txn_begin().await?;
+
+    let (rules, prefixes) = match req.rule_source {
+        Some(rpc::simulate_network_security_group_request::RuleSource::NetworkSecurityGroupId(
+            id,
+        )) => {
+            let id = id.parse::<NetworkSecurityGroupId>().map_err(|e| {
+                CarbideError::from(RpcDataConversionError::InvalidNetworkSecurityGroupId(
+                    e.value(),
+                ))
+            })?;
+
+            let network_security_group =
+                network_security_group::find_by_ids(&mut txn, std::slice::from_ref(&id), None, false)
+                    .await?
+                    .into_iter()
+                    .next()
+                    .ok_or_else(|| CarbideError::NotFoundError {
+                        kind: "NetworkSecurityGroup",
+                        id: id.to_string(),
+                    })?;
+
+            // References are resolved the same way as for propagation to DPUs.
+            let prefixes = ethernet_virtualization::load_rule_net_prefixes(
+                &mut txn,
+                &network_security_group.rules,
+                Some(&network_security_group.tenant_organization_id),
+            )
+            .await?;
+
+            (network_security_group.rules, prefixes)
+        }
+        Some(
+            rpc::simulate_network_security_group_request::RuleSource::NetworkSecurityGroupAttributes(
+                attr,
+            ),
+        ) => {
+            // Proposed rules get the same validation as on create/update.
+            let rules = attr
+                .rules
+                .into_iter()
+                .map(|r| r.try_into())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(CarbideError::from)?;
+
+            let max_nsg_size = api
+                .runtime_config
+                .network_security_group
+                .max_network_security_group_size as usize;
+
+            let prefixes =
+                ethernet_virtualization::load_rule_net_prefixes(&mut txn, &rules, None).await?;
+            validate_rule_references(&rules, &prefixes)?;
+            validate_expanded_rule_set(&rules, &prefixes, max_nsg_size)?;
+
+            (rules, prefixes)
+        }
+        None => return Err(CarbideError::MissingArgument("rule_source").into()),
+    };
+
+    txn.commit().await?;
+
+    let results = flows
+        .into_iter()
+        .map(|(rpc_flow, flow)| {
+            let verdict = analysis::simulate_flow(&rules, &prefixes, &flow);
+            rpc::NetworkSecurityGroupFlowResult {
+                flow: Some(rpc_flow),
+                action: rpc::NetworkSecurityGroupRuleAction::from(verdict.action).into(),
+                matched_rule_id: verdict.rule_id,
+            }
+        })
+        .collect();
+
+    Ok(Response::new(rpc::SimulateNetworkSecurityGroupResponse {
+        results,
+        findings: analysis::analyze_rules(&rules, &prefixes)
+            .into_iter()
+            .map(|f| f.into())
+            .collect(),
+    }))
+}
+
+/// Rejects references to VPCs and VPC prefixes which don't exist or belong to another tenant.
+fn validate_rule_references(
+    rules: &[NetworkSecurityGroupRule],
+    prefixes: &NetworkSecurityGroupRuleNetPrefixes,
+) -> Result<(), CarbideError> {
+    let dangling = prefixes.dangling_references(rules);
+    if !dangling.is_empty() {
+        return Err(CarbideError::InvalidArgument(format!(
+            "rule set references objects which do not exist or belong to another tenant: {}",
+            dangling.join(", ")
+        )));
+    }
+
+    Ok(())
+}
+
 fn validate_expanded_rule_set(
     rules: &[NetworkSecurityGroupRule],
+    prefixes: &NetworkSecurityGroupRuleNetPrefixes,
     limit: usize,
 ) -> Result<(), CarbideError> {
-    let mut total_rules = 0u32;
-
     let mut ids = HashSet::<Option<String>>::new();
 
     if rules.len() > limit {
@@ -650,35 +781,13 @@ fn validate_expanded_rule_set(
                 rule.id.clone().unwrap_or_default()
             )));
         }
+    }
 
-        match (&rule.src_net, &rule.dst_net) {
-            (NetworkSecurityGroupRuleNet::Prefix(_), NetworkSecurityGroupRuleNet::Prefix(_)) => {
-                // Negative ranges are caught when we convert from rpc to internal struct.
-                // so we can keep this simple.
-                let rule_count = (rule.src_port_end.unwrap_or_default()
-                    - rule.src_port_start.unwrap_or_default()
-                    + 1)
-                .saturating_mul(
-                    rule.dst_port_end.unwrap_or_default() - rule.dst_port_start.unwrap_or_default()
-                        + 1,
-                );
-
-                total_rules = match total_rules.overflowing_add(rule_count) {
-                    (_, true) => {
-                        return Err(CarbideError::InvalidArgument(format!(
-                            "expanded rule set contains more than {limit} maximum number of rules"
-                        )));
-                    }
-                    (v, false) => v,
-                };
-
-                if total_rules as usize > limit {
-                    return Err(CarbideError::InvalidArgument(format!(
-                        "expanded rule set contains more than {limit} maximum number of rules"
-                    )));
-                }
-            }
-        }
+    // Object references expand to one rule per prefix of the referenced object.
+    if prefixes.expanded_size(rules) > limit as u64 {
+        return Err(CarbideError::InvalidArgument(format!(
+            "expanded rule set contains more than {limit} maximum number of rules"
+        )));
     }
 
     Ok(())
@@ -147,6 +147,24 @@ pub(crate) async fn create(
         false
     };
 
+    // Network security groups referencing the VPC grow by the prefixes of the segment.
+    if let Some(vpc_id) = new_network_segment.vpc_id.as_ref() {
+        let segment_prefixes: Vec<_> = new_network_segment
+            .prefixes
+            .iter()
+            .map(|np| np.prefix)
+            .collect();
+        crate::ethernet_virtualization::validate_vpc_growth(
+            &mut txn,
+            vpc_id,
+            &segment_prefixes,
+            api.runtime_config
+                .network_security_group
+                .max_network_security_group_size as u64,
+        )
+        .await?;
+    }
+
     let network_segment = save(api, &mut txn, new_network_segment, false, allocate_svi_ip).await?;
 
     let response = Ok(Response::new(network_segment.try_into()?));
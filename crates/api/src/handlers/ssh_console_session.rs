@@ -0,0 +1,101 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ::db::ssh_console_session as db;
+use ::rpc::forge as rpc;
+use model::ssh_console_session::SshConsoleSession;
+use tonic::{Request, Response, Status};
+
+use crate::CarbideError;
+use crate::api::{Api, log_request_data};
+
+/// How many recorded sessions are kept for each machine. Older sessions are deleted when a new
+/// one is recorded.
+const MAX_SESSIONS_PER_MACHINE: usize = 500;
+
+/// Stores the recording of a session ssh-console opened to a machine
+pub async fn record(
+    api: &Api,
+    request: Request<rpc::SshConsoleSessionRecording>,
+) -> Result<Response<()>, Status> {
+    // Do not log_request_data as the recording contains everything the user typed
+
+    let request = request.into_inner();
+    let session = request
+        .session
+        .ok_or(CarbideError::MissingArgument("session"))?;
+    let session = SshConsoleSession::try_from(session).map_err(CarbideError::from)?;
+
+    let mut txn = api.txn_begin().await?;
+    db::save(&mut txn, &session, &request.recording).await?;
+    let pruned = db::prune(&mut txn, &session.machine_id, MAX_SESSIONS_PER_MACHINE).await?;
+    txn.commit().await?;
+
+    tracing::info!(
+        session_id = %session.session_id,
+        machine_id = %session.machine_id,
+        pruned,
+        "Recorded ssh-console session"
+    );
+
+    Ok(Response::new(()))
+}
+
+/// The recorded sessions of a machine, oldest first
+pub async fn find(
+    api: &Api,
+    request: Request<rpc::SshConsoleSessionsRequest>,
+) -> Result<Response<rpc::SshConsoleSessionList>, Status> {
+    log_request_data(&request);
+
+    let machine_id = request
+        .into_inner()
+        .machine_id
+        .ok_or(CarbideError::MissingArgument("machine_id"))?;
+
+    let sessions = db::find(&api.database_connection, &machine_id).await?;
+
+    Ok(Response::new(rpc::SshConsoleSessionList {
+        sessions: sessions.into_iter().map(Into::into).collect(),
+    }))
+}
+
+/// A recorded session of a machine including its recording
+pub async fn get_recording(
+    api: &Api,
+    request: Request<rpc::SshConsoleSessionRecordingRequest>,
+) -> Result<Response<rpc::SshConsoleSessionRecording>, Status> {
+    log_request_data(&request);
+
+    let request = request.into_inner();
+    let machine_id = request
+        .machine_id
+        .ok_or(CarbideError::MissingArgument("machine_id"))?;
+
+    let (session, recording) =
+        db::find_recording(&api.database_connection, &machine_id, &request.session)
+            .await?
+            .ok_or_else(|| CarbideError::NotFoundError {
+                kind: "ssh_console_session",
+                id: format!("{machine_id}/{}", request.session),
+            })?;
+
+    Ok(Response::new(rpc::SshConsoleSessionRecording {
+        session: Some(session.into()),
+        recording,
+    }))
+}
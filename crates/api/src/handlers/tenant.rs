@@ -18,7 +18,7 @@ use ::rpc::errors::RpcDataConversionError;
 use ::rpc::forge as rpc;
 use model::ConfigValidationError;
 use model::metadata::Metadata;
-use model::tenant::RoutingProfileType;
+use model::tenant::{RoutingProfileType, is_valid_metadata_token_hop_limit};
 use tonic::{Request, Response, Status};
 
 use crate::CarbideError;
@@ -52,6 +52,17 @@ fn metadata_to_valid_tenant_metadata(metadata: Option<rpc::Metadata>) -> Result<
     })
 }
 
+fn validate_metadata_token_hop_limit(hop_limit: Option<u32>) -> Result<(), CarbideError> {
+    match hop_limit {
+        Some(hop_limit) if !is_valid_metadata_token_hop_limit(hop_limit) => {
+            Err(CarbideError::InvalidArgument(format!(
+                "metadata token hop limit {hop_limit} is not between 1 and 255"
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
 pub(crate) async fn create(
     api: &Api,
     request: Request<rpc::CreateTenantRequest>,
@@ -62,6 +73,8 @@ pub(crate) async fn create(
         organization_id,
         metadata,
         routing_profile_type,
+        metadata_token_required,
+        metadata_token_hop_limit,
     } = request.into_inner();
 
     log_tenant_organization_id(&organization_id);
@@ -69,6 +82,7 @@ pub(crate) async fn create(
     let metadata: Metadata = metadata_to_valid_tenant_metadata(metadata)?;
 
     metadata.validate(true).map_err(CarbideError::from)?;
+    validate_metadata_token_hop_limit(metadata_token_hop_limit)?;
 
     // We won't use it if FNN isn't enabled, but we can still map so a caller integrating
     // with us before FNN is enabled on a site will be told if they're sending invalid values.
@@ -96,6 +110,8 @@ pub(crate) async fn create(
         } else {
             routing_profile_type
         },
+        metadata_token_required.unwrap_or_default(),
+        metadata_token_hop_limit,
         &mut txn,
     )
     .await?
@@ -147,6 +163,8 @@ pub(crate) async fn update(
         if_version_match,
         metadata,
         routing_profile_type,
+        metadata_token_required,
+        metadata_token_hop_limit,
     } = request.into_inner();
 
     log_tenant_organization_id(&organization_id);
@@ -154,6 +172,10 @@ pub(crate) async fn update(
     let metadata: Metadata = metadata_to_valid_tenant_metadata(metadata)?;
 
     metadata.validate(true).map_err(CarbideError::from)?;
+    // 0 removes the hop limit of the tenant
+    let metadata_token_hop_limit =
+        metadata_token_hop_limit.map(|hop_limit| (hop_limit > 0).then_some(hop_limit));
+    validate_metadata_token_hop_limit(metadata_token_hop_limit.flatten())?;
 
     let routing_profile_type = routing_profile_type
         .map(rpc::RoutingProfileType::try_from)
@@ -214,6 +236,8 @@ pub(crate) async fn update(
         metadata,
         expected_version,
         routing_profile_type,
+        metadata_token_required.unwrap_or(current_tenant.metadata_token_required),
+        metadata_token_hop_limit.unwrap_or(current_tenant.metadata_token_hop_limit),
         &mut txn,
     )
     .await?
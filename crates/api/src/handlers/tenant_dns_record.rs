@@ -0,0 +1,137 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use ::db::dns::tenant_record as db;
+use ::db::{ObjectColumnFilter, vpc};
+use ::rpc::protos::dns as rpc;
+use model::dns::NewTenantDnsRecord;
+use tonic::{Request, Response, Status};
+
+use crate::CarbideError;
+use crate::api::{Api, log_request_data};
+
+pub async fn create(
+    api: &Api,
+    request: Request<rpc::TenantDnsRecordCreationRequest>,
+) -> Result<Response<rpc::TenantDnsRecord>, Status> {
+    log_request_data(&request);
+
+    let new_record =
+        NewTenantDnsRecord::try_from(request.into_inner()).map_err(CarbideError::from)?;
+
+    let mut txn = api.txn_begin().await?;
+
+    let vpcs = vpc::find_by(
+        &mut txn,
+        ObjectColumnFilter::One(vpc::IdColumn, &new_record.vpc_id),
+    )
+    .await?;
+    if vpcs.is_empty() {
+        return Err(CarbideError::NotFoundError {
+            kind: "VPC",
+            id: new_record.vpc_id.to_string(),
+        }
+        .into());
+    }
+
+    let record = db::create(&mut txn, new_record).await?;
+
+    txn.commit().await?;
+
+    Ok(Response::new(record.into()))
+}
+
+pub async fn find_ids(
+    api: &Api,
+    request: Request<rpc::TenantDnsRecordSearchFilter>,
+) -> Result<Response<rpc::TenantDnsRecordIdList>, Status> {
+    log_request_data(&request);
+
+    let rpc::TenantDnsRecordSearchFilter { vpc_id } = request.into_inner();
+
+    let mut txn = api.txn_begin().await?;
+
+    let record_ids = db::find_ids(&mut txn, vpc_id).await?;
+
+    txn.commit().await?;
+
+    Ok(Response::new(rpc::TenantDnsRecordIdList { record_ids }))
+}
+
+pub async fn find_by_ids(
+    api: &Api,
+    request: Request<rpc::TenantDnsRecordsByIdsRequest>,
+) -> Result<Response<rpc::TenantDnsRecordList>, Status> {
+    log_request_data(&request);
+
+    let rpc::TenantDnsRecordsByIdsRequest { record_ids } = request.into_inner();
+
+    let max_find_by_ids = api.runtime_config.max_find_by_ids as usize;
+    if record_ids.len() > max_find_by_ids {
+        return Err(CarbideError::InvalidArgument(format!(
+            "no more than {max_find_by_ids} IDs can be submitted to find_tenant_dns_records_by_ids"
+        ))
+        .into());
+    }
+
+    let mut txn = api.txn_begin().await?;
+
+    let records = db::find_by_ids(&mut txn, &record_ids).await?;
+
+    txn.commit().await?;
+
+    Ok(Response::new(rpc::TenantDnsRecordList {
+        records: records.into_iter().map(Into::into).collect(),
+    }))
+}
+
+pub async fn update(
+    api: &Api,
+    request: Request<rpc::TenantDnsRecordUpdateRequest>,
+) -> Result<Response<rpc::TenantDnsRecord>, Status> {
+    log_request_data(&request);
+
+    let rpc::TenantDnsRecordUpdateRequest { id, ttl, content } = request.into_inner();
+
+    let id = id.ok_or_else(|| CarbideError::MissingArgument("id cannot be null"))?;
+
+    let mut txn = api.txn_begin().await?;
+
+    let record = db::update(&mut txn, id, ttl, &content).await?;
+
+    txn.commit().await?;
+
+    Ok(Response::new(record.into()))
+}
+
+pub async fn delete(
+    api: &Api,
+    request: Request<rpc::TenantDnsRecordDeletionRequest>,
+) -> Result<Response<rpc::TenantDnsRecordDeletionResult>, Status> {
+    log_request_data(&request);
+
+    let rpc::TenantDnsRecordDeletionRequest { id } = request.into_inner();
+
+    let id = id.ok_or_else(|| CarbideError::MissingArgument("id cannot be null"))?;
+
+    let mut txn = api.txn_begin().await?;
+
+    let _ = db::delete(&mut txn, id).await?;
+
+    txn.commit().await?;
+
+    Ok(Response::new(rpc::TenantDnsRecordDeletionResult {}))
+}
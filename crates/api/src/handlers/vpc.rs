@@ -296,6 +296,10 @@ pub(crate) async fn delete(
         .id
         .ok_or(CarbideError::MissingArgument("id"))?;
 
+    let referencing_nsg_ids =
+        network_security_group::find_ids_referencing_vpc(&mut txn, &vpc_id).await?;
+    crate::ethernet_virtualization::validate_unreferenced("VPC", &vpc_id, &referencing_nsg_ids)?;
+
     let vpc = match db::vpc::try_delete(&mut txn, vpc_id).await? {
         Some(vpc) => vpc,
         None => {
@@ -353,6 +357,9 @@ pub(crate) async fn delete(
     // Delete associated VPC peerings
     db::vpc_peering::delete_by_vpc_id(&mut txn, vpc_id).await?;
 
+    // Delete DNS records the tenant defined inside the VPC
+    db::dns::tenant_record::delete_by_vpc_id(&mut txn, vpc_id).await?;
+
     txn.commit().await?;
 
     Ok(Response::new(rpc::VpcDeletionResult {}))
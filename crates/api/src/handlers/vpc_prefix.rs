@@ -15,7 +15,7 @@
  * limitations under the License.
  */
 
-use ::db::{ObjectColumnFilter, vpc_prefix as db};
+use ::db::{ObjectColumnFilter, network_security_group, vpc_prefix as db};
 use ::rpc::forge as rpc;
 use ::rpc::forge::PrefixMatchType;
 use ipnetwork::IpNetwork;
@@ -23,8 +23,9 @@ use model::network_prefix::NetworkPrefix;
 use model::vpc_prefix;
 use tonic::{Request, Response, Status};
 
-use crate::CarbideError;
 use crate::api::{Api, log_request_data};
+use crate::{CarbideError, ethernet_virtualization};
+
 pub async fn create(
     api: &Api,
     request: Request<rpc::VpcPrefixCreationRequest>,
@@ -145,6 +146,18 @@ pub async fn create(
         .validate(true)
         .map_err(CarbideError::from)?;
 
+    // Network security groups referencing the VPC grow by the new prefix.
+    // Refuse the prefix if any of them would exceed the rule limits.
+    ethernet_virtualization::validate_vpc_growth(
+        &mut txn,
+        &new_prefix.vpc_id,
+        &[new_prefix.config.prefix],
+        api.runtime_config
+            .network_security_group
+            .max_network_security_group_size as u64,
+    )
+    .await?;
+
     let vpc_prefix = db::persist(new_prefix, &mut txn).await?;
 
     // Associate all of the network segment prefixes with the new VPC prefix.
@@ -286,6 +299,15 @@ pub async fn delete(
     // whatever else might be pointing at them. For now we're just relying on
     // the DB constraints and returning whatever error that results in.
 
+    let referencing_nsg_ids =
+        network_security_group::find_ids_referencing_vpc_prefix(&mut txn, &delete_prefix.id)
+            .await?;
+    ethernet_virtualization::validate_unreferenced(
+        "VPC prefix",
+        &delete_prefix.id,
+        &referencing_nsg_ids,
+    )?;
+
     db::delete(&delete_prefix, &mut txn).await?;
 
     txn.commit().await?;
@@ -15,6 +15,10 @@
  * limitations under the License.
  */
 
+pub mod placement;
+pub mod reservation;
+pub mod reservation_metrics;
+
 use std::collections::{HashMap, HashSet};
 
 use ::rpc::errors::RpcDataConversionError;
@@ -36,6 +40,7 @@ use model::instance::config::infiniband::InstanceInfinibandConfig;
 use model::instance::config::network::{
     InstanceNetworkConfig, InterfaceFunctionId, NetworkDetails,
 };
+use model::instance_webhook::{InstanceLifecycleEvent, InstanceLifecycleEventType};
 use model::machine::machine_search_config::MachineSearchConfig;
 use model::machine::{
     HostHealthConfig, LoadSnapshotOptions, Machine, ManagedHostStateSnapshot, NotAllocatableReason,
@@ -344,6 +349,28 @@ pub async fn allocate_instance(
 
 /// Allocates multiple instances in a single transaction.
 /// Rolls back entirely if any allocation fails.
+pub async fn batch_allocate_instances(
+    api: &Api,
+    requests: Vec<InstanceAllocationRequest>,
+    host_health_config: HostHealthConfig,
+) -> Result<Vec<ManagedHostStateSnapshot>, CarbideError> {
+    let mut txn = api.txn_begin().await?;
+
+    let snapshots =
+        batch_allocate_instances_with_txn(api, &mut *txn, requests, host_health_config).await?;
+
+    txn.commit().await?;
+
+    tracing::info!(
+        instance_count = snapshots.len(),
+        "Successfully completed batch instance allocation"
+    );
+
+    Ok(snapshots)
+}
+
+/// Allocates multiple instances as part of an existing transaction.
+/// Nothing is persisted unless the caller commits the transaction.
 ///
 /// ## Flow:
 /// 1. Validate machine types and metadata (in-memory)
@@ -351,9 +378,10 @@ pub async fn allocate_instance(
 /// 3. Validate shared resources: NSG, extension services, OS images, IB partitions, DPA
 /// 4. Network allocation + config validation (sequential)
 /// 5. Batch persist instances, process configs (IPs, IB GUIDs), batch update
-/// 6. Load final instances, assemble snapshots, commit
-pub async fn batch_allocate_instances(
+/// 6. Load final instances, assemble snapshots
+pub async fn batch_allocate_instances_with_txn(
     api: &Api,
+    txn: &mut PgConnection,
     requests: Vec<InstanceAllocationRequest>,
     host_health_config: HostHealthConfig,
 ) -> Result<Vec<ManagedHostStateSnapshot>, CarbideError> {
@@ -384,14 +412,11 @@ pub async fn batch_allocate_instances(
         request.metadata.validate(true)?;
     }
 
-    // Start a single transaction for all allocations
-    let mut txn = api.txn_begin().await?;
-
     // ==== Phase 2: Batch query machines (FOR UPDATE) ====
     let machine_ids: Vec<_> = requests.iter().map(|r| r.machine_id).collect();
 
     let machines = db::machine::find(
-        &mut txn,
+        &mut *txn,
         ObjectFilter::List(&machine_ids),
         MachineSearchConfig {
             for_update: true,
@@ -416,7 +441,7 @@ pub async fn batch_allocate_instances(
 
     // ==== Phase 3: Batch load managed host snapshots ====
     let mut snapshot_map = db::managed_host::load_by_machine_ids(
-        &mut txn,
+        &mut *txn,
         &machine_ids,
         LoadSnapshotOptions::default().with_host_health(host_health_config),
     )
@@ -452,6 +477,9 @@ pub async fn batch_allocate_instances(
         }
     }
 
+    // Machines reserved for other tenants can't be used
+    reservation::check_reservations(&mut *txn, &requests, &machine_map).await?;
+
     // ==== Phase 4: Validate shared resources ====
 
     // Collect all unique NSG IDs with their tenant org IDs for validation
@@ -468,7 +496,7 @@ pub async fn batch_allocate_instances(
     // Validate each unique NSG
     for (nsg_id, tenant_org_id) in &nsg_validations {
         if network_security_group::find_by_ids(
-            &mut txn,
+            &mut *txn,
             std::slice::from_ref(nsg_id),
             Some(tenant_org_id),
             true,
@@ -519,7 +547,7 @@ pub async fn batch_allocate_instances(
 
         // Batch query all extension services
         let services =
-            extension_service::find_versions_by_service_ids(&mut txn, &unique_service_ids, true)
+            extension_service::find_versions_by_service_ids(&mut *txn, &unique_service_ids, true)
                 .await?;
 
         // Validate each service config
@@ -562,7 +590,7 @@ pub async fn batch_allocate_instances(
                 "Image ID is required for image based storage".to_string(),
             ));
         }
-        if let Err(e) = db::os_image::get(&mut txn, *os_image_id).await {
+        if let Err(e) = db::os_image::get(&mut *txn, *os_image_id).await {
             return if e.is_not_found() {
                 Err(CarbideError::FailedPrecondition(format!(
                     "Image OS `{}` does not exist",
@@ -589,12 +617,12 @@ pub async fn batch_allocate_instances(
         })
         .collect();
 
-    batch_validate_ib_partition_ownership(&mut txn, &ib_partition_validations).await?;
+    batch_validate_ib_partition_ownership(&mut *txn, &ib_partition_validations).await?;
 
     // Batch query inband segments for all machines
     let inband_segments_map =
         db::instance_network_config::batch_get_inband_segments_by_machine_ids(
-            &mut txn,
+            &mut *txn,
             &machine_ids,
         )
         .await?;
@@ -613,7 +641,7 @@ pub async fn batch_allocate_instances(
             })?;
 
         // Allocate network
-        allocate_network(&mut request.config.network, &mut txn).await?;
+        allocate_network(&mut request.config.network, &mut *txn).await?;
 
         // Validate config (after network allocation sets network_segment_id)
         request.config.validate(
@@ -651,7 +679,7 @@ pub async fn batch_allocate_instances(
         })
         .collect();
 
-    let _persisted_instances = db::instance::batch_persist(new_instances, &mut txn).await?;
+    let _persisted_instances = db::instance::batch_persist(new_instances, &mut *txn).await?;
 
     // ==== Phase 7: Process configs (IPs, inband interfaces, IB GUIDs) ====
     // These need to be done per-instance but we collect results for batch update
@@ -688,7 +716,7 @@ pub async fn batch_allocate_instances(
         // Allocate IPs
         let updated_network_config = db::instance_network_config::with_allocated_ips(
             updated_network_config,
-            &mut txn,
+            &mut *txn,
             instance_id,
             &mh_snapshot.host_snapshot,
         )
@@ -724,26 +752,26 @@ pub async fn batch_allocate_instances(
         .iter()
         .map(|(id, ver, cfg)| (*id, *ver, cfg))
         .collect();
-    db::instance::batch_update_network_config(&mut txn, &network_refs, false).await?;
+    db::instance::batch_update_network_config(&mut *txn, &network_refs, false).await?;
 
     let ib_refs: Vec<_> = ib_config_updates
         .iter()
         .map(|(id, ver, cfg)| (*id, *ver, cfg))
         .collect();
-    db::instance::batch_update_ib_config(&mut txn, &ib_refs, false).await?;
+    db::instance::batch_update_ib_config(&mut *txn, &ib_refs, false).await?;
 
     let nvlink_refs: Vec<_> = nvlink_config_updates
         .iter()
         .map(|(id, ver, cfg)| (*id, *ver, cfg))
         .collect();
-    db::instance::batch_update_nvlink_config(&mut txn, &nvlink_refs, false).await?;
+    db::instance::batch_update_nvlink_config(&mut *txn, &nvlink_refs, false).await?;
 
     // ==== Phase 9: Load final instances ====
     let machine_id_refs: Vec<&MachineId> = processed_requests
         .iter()
         .map(|(r, _)| &r.machine_id)
         .collect();
-    let final_instances = db::instance::find_by_machine_ids(&mut txn, &machine_id_refs).await?;
+    let final_instances = db::instance::find_by_machine_ids(&mut *txn, &machine_id_refs).await?;
     let mut final_instance_map: HashMap<_, _> = final_instances
         .into_iter()
         .map(|i| (i.machine_id, i))
@@ -753,22 +781,20 @@ pub async fn batch_allocate_instances(
     let mut snapshots = Vec::with_capacity(request_count);
     for (request, mut mh_snapshot) in processed_requests {
         let machine_id = request.machine_id;
-        mh_snapshot.instance = Some(final_instance_map.remove(&machine_id).ok_or_else(|| {
+        let instance = final_instance_map.remove(&machine_id).ok_or_else(|| {
             CarbideError::internal(format!(
                 "Newly created instance for {machine_id} was not found"
             ))
-        })?);
+        })?;
+        db::instance_webhook::enqueue_event(
+            &mut *txn,
+            &InstanceLifecycleEvent::for_instance(InstanceLifecycleEventType::Allocated, &instance),
+        )
+        .await?;
+        mh_snapshot.instance = Some(instance);
         snapshots.push(mh_snapshot);
     }
 
-    // ==== Phase 11: Commit ====
-    txn.commit().await?;
-
-    tracing::info!(
-        instance_count = snapshots.len(),
-        "Successfully completed batch instance allocation"
-    );
-
     Ok(snapshots)
 }
 
@@ -0,0 +1,499 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Picks the Machines for instances which are requested by InstanceType rather than by Machine.
+//!
+//! Candidates are all Machines of the InstanceType which can currently be used as instances.
+//! They are grouped by rack, NVLink domain or InfiniBand fabric depending on the requested
+//! [`PlacementPolicy`], and the batch is then allocated through the regular batch allocation path
+//! within the same transaction. All Machines of the InstanceType are row-locked while picking, so
+//! that concurrent requests for the same InstanceType can't pick the same Machines.
+
+use std::collections::{BTreeMap, HashMap};
+use std::net::IpAddr;
+
+use ::rpc::errors::RpcDataConversionError;
+use ::rpc::forge as rpc;
+use carbide_uuid::instance_type::InstanceTypeId;
+use carbide_uuid::machine::MachineId;
+use itertools::Itertools;
+use model::machine::{HostHealthConfig, LoadSnapshotOptions, ManagedHostStateSnapshot};
+
+use crate::api::Api;
+use crate::instance::{InstanceAllocationRequest, batch_allocate_instances_with_txn};
+use crate::{CarbideError, CarbideResult};
+
+/// How the Machines of a batch are placed relative to each other
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlacementPolicy {
+    Any,
+    SameRack,
+    SameNvLinkDomain,
+    SameIbFabric,
+    SpreadRacks,
+}
+
+impl From<rpc::InstancePlacementPolicy> for PlacementPolicy {
+    fn from(policy: rpc::InstancePlacementPolicy) -> Self {
+        match policy {
+            rpc::InstancePlacementPolicy::PlacementAny => PlacementPolicy::Any,
+            rpc::InstancePlacementPolicy::PlacementSameRack => PlacementPolicy::SameRack,
+            rpc::InstancePlacementPolicy::PlacementSameNvlinkDomain => {
+                PlacementPolicy::SameNvLinkDomain
+            }
+            rpc::InstancePlacementPolicy::PlacementSameIbFabric => PlacementPolicy::SameIbFabric,
+            rpc::InstancePlacementPolicy::PlacementSpreadRacks => PlacementPolicy::SpreadRacks,
+        }
+    }
+}
+
+/// User parameters for creating instances on Machines of an InstanceType
+#[derive(Debug)]
+pub struct InstanceTypeAllocationRequest {
+    pub instance_type_id: InstanceTypeId,
+    pub placement_policy: PlacementPolicy,
+    /// One request per instance. The Machine is filled in once it has been picked.
+    pub instance_requests: Vec<rpc::InstanceAllocationRequest>,
+}
+
+impl TryFrom<rpc::InstanceTypeAllocationRequest> for InstanceTypeAllocationRequest {
+    type Error = CarbideError;
+
+    fn try_from(request: rpc::InstanceTypeAllocationRequest) -> Result<Self, Self::Error> {
+        let instance_type_id =
+            request
+                .instance_type_id
+                .parse::<InstanceTypeId>()
+                .map_err(|_| {
+                    RpcDataConversionError::InvalidInstanceTypeId(request.instance_type_id.clone())
+                })?;
+
+        let placement_policy = rpc::InstancePlacementPolicy::try_from(request.placement_policy)
+            .map_err(|_| {
+                CarbideError::InvalidArgument(format!(
+                    "Unknown placement policy {}",
+                    request.placement_policy
+                ))
+            })?
+            .into();
+
+        if request.instance_requests.is_empty() {
+            return Err(CarbideError::InvalidArgument(
+                "Request must contain at least one instance".to_string(),
+            ));
+        }
+
+        for instance_request in &request.instance_requests {
+            if let Some(machine_id) = instance_request.machine_id {
+                return Err(CarbideError::InvalidArgument(format!(
+                    "Machine {machine_id} was specified, but Machines are picked by placement"
+                )));
+            }
+            if let Some(id) = &instance_request.instance_type_id
+                && *id != request.instance_type_id
+            {
+                return Err(CarbideError::InvalidArgument(format!(
+                    "Instance requests InstanceType {id}, but the batch is for InstanceType {}",
+                    request.instance_type_id
+                )));
+            }
+        }
+
+        Ok(InstanceTypeAllocationRequest {
+            instance_type_id,
+            placement_policy,
+            instance_requests: request.instance_requests,
+        })
+    }
+}
+
+/// A Machine which can host one of the requested instances, with where it is located
+#[derive(Debug, Clone)]
+pub struct PlacementCandidate {
+    pub machine_id: MachineId,
+    pub rack: Option<String>,
+    pub nvlink_domain: Option<String>,
+    /// A Machine can be attached to multiple fabrics
+    pub ib_fabrics: Vec<String>,
+    /// Compute tray index and physical slot number as reported by the BMC.
+    /// Used to pack the instances of a batch into neighbouring Machines.
+    pub position: (Option<i32>, Option<i32>),
+}
+
+impl PlacementCandidate {
+    fn sort_key(&self) -> (Option<&str>, (Option<i32>, Option<i32>), String) {
+        (
+            self.rack.as_deref(),
+            self.position,
+            self.machine_id.to_string(),
+        )
+    }
+}
+
+/// Picks `count` Machines out of `candidates` according to `policy`.
+///
+/// When all Machines have to share a rack, NVLink domain or fabric, the smallest group which can
+/// fit the whole batch is used, so that larger groups stay available for larger batches.
+pub fn select_machines(
+    mut candidates: Vec<PlacementCandidate>,
+    count: usize,
+    policy: PlacementPolicy,
+) -> CarbideResult<Vec<MachineId>> {
+    if candidates.len() < count {
+        return Err(CarbideError::ResourceExhausted(format!(
+            "{count} Machines requested, but only {} are available",
+            candidates.len()
+        )));
+    }
+
+    candidates.sort_by(|a, b| a.sort_key().cmp(&b.sort_key()));
+
+    let group_keys: fn(&PlacementCandidate) -> Vec<String> = match policy {
+        PlacementPolicy::Any => {
+            return Ok(candidates
+                .into_iter()
+                .take(count)
+                .map(|c| c.machine_id)
+                .collect());
+        }
+        PlacementPolicy::SpreadRacks => return Ok(spread_across_racks(candidates, count)),
+        PlacementPolicy::SameRack => |c| c.rack.iter().cloned().collect(),
+        PlacementPolicy::SameNvLinkDomain => |c| c.nvlink_domain.iter().cloned().collect(),
+        PlacementPolicy::SameIbFabric => |c| c.ib_fabrics.clone(),
+    };
+
+    let mut groups: BTreeMap<String, Vec<MachineId>> = BTreeMap::new();
+    for candidate in &candidates {
+        for key in group_keys(candidate) {
+            groups.entry(key).or_default().push(candidate.machine_id);
+        }
+    }
+
+    groups
+        .into_values()
+        .filter(|machines| machines.len() >= count)
+        .min_by_key(|machines| machines.len())
+        .map(|machines| machines.into_iter().take(count).collect())
+        .ok_or_else(|| {
+            CarbideError::ResourceExhausted(format!(
+                "No {} has {count} available Machines",
+                match policy {
+                    PlacementPolicy::SameRack => "rack",
+                    PlacementPolicy::SameNvLinkDomain => "NVLink domain",
+                    _ => "InfiniBand fabric",
+                }
+            ))
+        })
+}
+
+/// Round-robin across racks, starting with the racks with the most candidates. A rack only gets
+/// a second Machine once every rack has one. Machines without a known rack count as one rack.
+fn spread_across_racks(candidates: Vec<PlacementCandidate>, count: usize) -> Vec<MachineId> {
+    let mut racks: BTreeMap<Option<String>, Vec<MachineId>> = BTreeMap::new();
+    for candidate in candidates {
+        racks
+            .entry(candidate.rack)
+            .or_default()
+            .push(candidate.machine_id);
+    }
+
+    let mut racks: Vec<_> = racks.into_values().map(|m| m.into_iter()).collect();
+    // Stable, so that racks with the same number of candidates stay in rack order
+    racks.sort_by_key(|machines| std::cmp::Reverse(machines.len()));
+
+    let mut selected = Vec::with_capacity(count);
+    while selected.len() < count {
+        for machines in racks.iter_mut() {
+            if selected.len() == count {
+                break;
+            }
+            if let Some(machine_id) = machines.next() {
+                selected.push(machine_id);
+            }
+        }
+    }
+    selected
+}
+
+/// Finds rack, NVLink domain, fabric and position of the given Machines
+async fn load_candidates(
+    txn: &mut sqlx::PgConnection,
+    snapshots: &[&ManagedHostStateSnapshot],
+) -> CarbideResult<Vec<PlacementCandidate>> {
+    let racks = db::expected_machine::find_rack_ids_of_hosts(
+        &mut *txn,
+        snapshots.iter().map(|s| &s.host_snapshot),
+    )
+    .await?;
+
+    // Same lookup as for GetMachinePositionInfo: BMC IP -> explored endpoint
+    let machine_ids = snapshots
+        .iter()
+        .map(|s| s.host_snapshot.id)
+        .collect::<Vec<_>>();
+    let bmc_ips =
+        db::machine_topology::find_machine_bmc_pairs_by_machine_id(&mut *txn, machine_ids)
+            .await?
+            .into_iter()
+            .filter_map(|(machine_id, ip)| Some((machine_id, ip?.parse::<IpAddr>().ok()?)))
+            .collect::<HashMap<_, _>>();
+    let endpoints =
+        db::explored_endpoints::find_by_ips(&mut *txn, bmc_ips.values().copied().collect())
+            .await?
+            .into_iter()
+            .map(|endpoint| (endpoint.address, endpoint))
+            .collect::<HashMap<_, _>>();
+
+    Ok(snapshots
+        .iter()
+        .map(|snapshot| {
+            let machine = &snapshot.host_snapshot;
+            let endpoint = bmc_ips.get(&machine.id).and_then(|ip| endpoints.get(ip));
+            PlacementCandidate {
+                machine_id: machine.id,
+                rack: racks.get(&machine.id).map(|rack_id| rack_id.to_string()),
+                nvlink_domain: machine
+                    .nvlink_info
+                    .as_ref()
+                    .map(|info| info.domain_uuid.to_string()),
+                ib_fabrics: machine
+                    .infiniband_status_observation
+                    .iter()
+                    .flat_map(|status| status.ib_interfaces.iter())
+                    .filter(|iface| !iface.fabric_id.is_empty())
+                    .map(|iface| iface.fabric_id.clone())
+                    .sorted()
+                    .dedup()
+                    .collect(),
+                position: endpoint.map_or((None, None), |ep| {
+                    (ep.report.compute_tray_index, ep.report.physical_slot_number)
+                }),
+            }
+        })
+        .collect())
+}
+
+/// Allocates instances on Machines of an InstanceType which are picked according to the
+/// placement policy of the request.
+pub async fn allocate_instances_by_instance_type(
+    api: &Api,
+    request: InstanceTypeAllocationRequest,
+    host_health_config: HostHealthConfig,
+) -> CarbideResult<Vec<ManagedHostStateSnapshot>> {
+    let count = request.instance_requests.len();
+    // A Machine is only a candidate if every request of the batch may use it
+    let allow_unhealthy_machine = request
+        .instance_requests
+        .iter()
+        .all(|r| r.allow_unhealthy_machine);
+
+    let mut txn = api.txn_begin().await?;
+
+    // Lock all Machines of the InstanceType until the allocation is committed. Concurrent
+    // requests for the same InstanceType wait here and then see the Machines picked by this one
+    // as no longer usable.
+    let machine_ids =
+        db::machine::find_ids_by_instance_type_id(&mut txn, &request.instance_type_id, true)
+            .await?
+            .into_iter()
+            .map(|(machine_id, _)| machine_id)
+            .filter(|machine_id| machine_id.machine_type().is_host())
+            .collect::<Vec<_>>();
+
+    let snapshots = db::managed_host::load_by_machine_ids(
+        &mut txn,
+        &machine_ids,
+        LoadSnapshotOptions::default().with_host_health(host_health_config),
+    )
+    .await?;
+    let usable = snapshots
+        .values()
+        .filter(|s| s.is_usable_as_instance(allow_unhealthy_machine).is_ok())
+        .collect::<Vec<_>>();
+
+    let candidates = load_candidates(&mut txn, &usable).await?;
+    let selected =
+        select_machines(candidates, count, request.placement_policy).inspect_err(|e| {
+            tracing::info!(
+                instance_type_id = %request.instance_type_id,
+                count,
+                error = %e,
+                "No placement found for instances"
+            );
+        })?;
+
+    let requests = request
+        .instance_requests
+        .into_iter()
+        .zip(selected)
+        .map(|(mut instance_request, machine_id)| {
+            instance_request.machine_id = Some(machine_id);
+            instance_request.instance_type_id = Some(request.instance_type_id.to_string());
+            InstanceAllocationRequest::try_from(instance_request)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let snapshots =
+        batch_allocate_instances_with_txn(api, &mut txn, requests, host_health_config).await?;
+
+    txn.commit().await?;
+
+    tracing::info!(
+        instance_type_id = %request.instance_type_id,
+        placement_policy = ?request.placement_policy,
+        instance_count = snapshots.len(),
+        "Allocated instances by InstanceType"
+    );
+
+    Ok(snapshots)
+}
+
+#[cfg(test)]
+mod tests {
+    use carbide_uuid::machine::{MachineIdSource, MachineType};
+
+    use super::*;
+
+    fn candidate(id: u8, rack: Option<&str>, nvlink_domain: Option<&str>) -> PlacementCandidate {
+        PlacementCandidate {
+            machine_id: MachineId::new(
+                MachineIdSource::ProductBoardChassisSerial,
+                [id; 32],
+                MachineType::Host,
+            ),
+            rack: rack.map(str::to_string),
+            nvlink_domain: nvlink_domain.map(str::to_string),
+            ib_fabrics: vec![],
+            position: (Some(i32::from(id)), None),
+        }
+    }
+
+    fn racks_of(candidates: &[PlacementCandidate], selected: &[MachineId]) -> Vec<String> {
+        selected
+            .iter()
+            .map(|id| {
+                candidates
+                    .iter()
+                    .find(|c| c.machine_id == *id)
+                    .and_then(|c| c.rack.clone())
+                    .unwrap_or_default()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_same_rack_prefers_smallest_fitting_rack() {
+        let candidates = vec![
+            candidate(1, Some("rack-a"), None),
+            candidate(2, Some("rack-a"), None),
+            candidate(3, Some("rack-a"), None),
+            candidate(4, Some("rack-b"), None),
+            candidate(5, Some("rack-b"), None),
+            candidate(6, Some("rack-c"), None),
+        ];
+
+        let selected = select_machines(candidates.clone(), 2, PlacementPolicy::SameRack).unwrap();
+        assert_eq!(racks_of(&candidates, &selected), vec!["rack-b", "rack-b"]);
+
+        let selected = select_machines(candidates.clone(), 3, PlacementPolicy::SameRack).unwrap();
+        assert_eq!(racks_of(&candidates, &selected), vec!["rack-a"; 3]);
+
+        let err = select_machines(candidates, 4, PlacementPolicy::SameRack).unwrap_err();
+        assert!(matches!(err, CarbideError::ResourceExhausted(_)));
+    }
+
+    #[test]
+    fn test_same_nvlink_domain_ignores_machines_without_domain() {
+        let candidates = vec![
+            candidate(1, Some("rack-a"), None),
+            candidate(2, Some("rack-a"), None),
+            candidate(3, Some("rack-b"), Some("domain-1")),
+            candidate(4, Some("rack-b"), Some("domain-1")),
+        ];
+
+        let selected =
+            select_machines(candidates.clone(), 2, PlacementPolicy::SameNvLinkDomain).unwrap();
+        assert_eq!(
+            selected,
+            vec![candidates[2].machine_id, candidates[3].machine_id]
+        );
+
+        assert!(select_machines(candidates, 3, PlacementPolicy::SameNvLinkDomain).is_err());
+    }
+
+    #[test]
+    fn test_same_ib_fabric_considers_all_fabrics_of_a_machine() {
+        let mut candidates = vec![
+            candidate(1, None, None),
+            candidate(2, None, None),
+            candidate(3, None, None),
+        ];
+        candidates[0].ib_fabrics = vec!["fabric-1".to_string()];
+        candidates[1].ib_fabrics = vec!["fabric-1".to_string(), "fabric-2".to_string()];
+        candidates[2].ib_fabrics = vec!["fabric-2".to_string()];
+
+        let selected =
+            select_machines(candidates.clone(), 2, PlacementPolicy::SameIbFabric).unwrap();
+        assert_eq!(
+            selected,
+            vec![candidates[0].machine_id, candidates[1].machine_id]
+        );
+        assert!(select_machines(candidates, 3, PlacementPolicy::SameIbFabric).is_err());
+    }
+
+    #[test]
+    fn test_spread_racks() {
+        let candidates = vec![
+            candidate(1, Some("rack-a"), None),
+            candidate(2, Some("rack-a"), None),
+            candidate(3, Some("rack-a"), None),
+            candidate(4, Some("rack-b"), None),
+            candidate(5, Some("rack-c"), None),
+        ];
+
+        let selected =
+            select_machines(candidates.clone(), 3, PlacementPolicy::SpreadRacks).unwrap();
+        assert_eq!(
+            racks_of(&candidates, &selected),
+            vec!["rack-a", "rack-b", "rack-c"]
+        );
+
+        // Racks get a second Machine once all racks are used
+        let selected =
+            select_machines(candidates.clone(), 5, PlacementPolicy::SpreadRacks).unwrap();
+        assert_eq!(
+            racks_of(&candidates, &selected),
+            vec!["rack-a", "rack-b", "rack-c", "rack-a", "rack-a"]
+        );
+    }
+
+    #[test]
+    fn test_any_packs_by_rack_and_position() {
+        let candidates = vec![
+            candidate(3, Some("rack-b"), None),
+            candidate(2, Some("rack-a"), None),
+            candidate(1, Some("rack-a"), None),
+        ];
+
+        let selected = select_machines(candidates.clone(), 2, PlacementPolicy::Any).unwrap();
+        assert_eq!(
+            selected,
+            vec![candidates[2].machine_id, candidates[1].machine_id]
+        );
+        assert!(select_machines(candidates, 4, PlacementPolicy::Any).is_err());
+    }
+}
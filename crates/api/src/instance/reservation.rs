@@ -0,0 +1,287 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Enforcement of capacity reservations.
+//!
+//! A reservation sets aside a number of Machines of an InstanceType for a tenant without pinning
+//! specific Machines. The part of a reservation which the tenant doesn't use yet is held back
+//! from other tenants: they can only allocate Machines of the InstanceType as long as enough
+//! Machines remain available to cover all outstanding reservations.
+
+use std::collections::HashMap;
+
+use carbide_uuid::instance_type::InstanceTypeId;
+use carbide_uuid::machine::MachineId;
+use model::capacity_reservation::CapacityReservation;
+use model::machine::Machine;
+use model::tenant::TenantOrganizationId;
+use sqlx::PgConnection;
+
+use crate::instance::InstanceAllocationRequest;
+use crate::{CarbideError, CarbideResult};
+
+/// The capacity of an InstanceType, split into reserved and unreserved Machines
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InstanceTypeCapacity {
+    /// Ready Machines of the InstanceType without an instance
+    pub available: u32,
+    /// The sum of the active reservation counts of each tenant
+    pub reserved: HashMap<TenantOrganizationId, u32>,
+    /// The number of instances of each tenant on Machines of the InstanceType
+    pub in_use: HashMap<TenantOrganizationId, u32>,
+}
+
+impl InstanceTypeCapacity {
+    /// Loads the capacity of each of the given InstanceTypes.
+    ///
+    /// With `for_update`, the active reservations of the InstanceTypes are locked, which
+    /// serializes allocations that have to respect them.
+    pub async fn load(
+        txn: &mut PgConnection,
+        instance_type_ids: &[InstanceTypeId],
+        for_update: bool,
+    ) -> CarbideResult<HashMap<InstanceTypeId, Self>> {
+        let reservations = db::capacity_reservation::find_active_by_instance_type_ids(
+            &mut *txn,
+            instance_type_ids,
+            for_update,
+        )
+        .await?;
+        let mut available =
+            db::capacity_reservation::count_available_machines(&mut *txn, instance_type_ids)
+                .await?;
+        let in_use =
+            db::capacity_reservation::count_instances_by_tenant(&mut *txn, instance_type_ids)
+                .await?;
+
+        let mut capacities: HashMap<InstanceTypeId, Self> = instance_type_ids
+            .iter()
+            .map(|id| {
+                let capacity = Self {
+                    available: available.remove(id).unwrap_or_default(),
+                    ..Default::default()
+                };
+                (id.clone(), capacity)
+            })
+            .collect();
+        for reservation in reservations {
+            if let Some(capacity) = capacities.get_mut(&reservation.instance_type_id) {
+                capacity.add_reservation(&reservation);
+            }
+        }
+        for ((instance_type_id, tenant), count) in in_use {
+            if let Some(capacity) = capacities.get_mut(&instance_type_id) {
+                capacity.in_use.insert(tenant, count);
+            }
+        }
+
+        Ok(capacities)
+    }
+
+    pub fn add_reservation(&mut self, reservation: &CapacityReservation) {
+        *self
+            .reserved
+            .entry(reservation.tenant_organization_id.clone())
+            .or_default() += reservation.count;
+    }
+
+    /// The part of the tenant's reservations which is not yet used by instances
+    pub fn outstanding(&self, tenant: &TenantOrganizationId) -> u32 {
+        let reserved = self.reserved.get(tenant).copied().unwrap_or_default();
+        let in_use = self.in_use.get(tenant).copied().unwrap_or_default();
+        reserved.saturating_sub(in_use)
+    }
+
+    /// The available Machines which are not held back for any tenant
+    pub fn unreserved(&self) -> u32 {
+        let outstanding: u32 = self
+            .reserved
+            .keys()
+            .map(|tenant| self.outstanding(tenant))
+            .sum();
+        self.available.saturating_sub(outstanding)
+    }
+
+    /// Checks whether the given number of Machines can be allocated for each tenant.
+    ///
+    /// Machines a tenant allocates are first taken from its own outstanding reservations. Only
+    /// the excess has to be covered by the unreserved Machines.
+    pub fn check_allocation(
+        &self,
+        instance_type_id: &InstanceTypeId,
+        requested: &HashMap<TenantOrganizationId, u32>,
+    ) -> CarbideResult<()> {
+        let excess: u32 = requested
+            .iter()
+            .map(|(tenant, &count)| count.saturating_sub(self.outstanding(tenant)))
+            .sum();
+        let unreserved = self.unreserved();
+        if excess > unreserved {
+            return Err(CarbideError::ResourceExhausted(format!(
+                "InstanceType {instance_type_id}: {excess} unreserved Machines are required, but only {unreserved} are available"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Checks whether the tenant can reserve `count` additional Machines
+    pub fn check_reservation(
+        &self,
+        instance_type_id: &InstanceTypeId,
+        tenant: &TenantOrganizationId,
+        count: u32,
+    ) -> CarbideResult<()> {
+        let reserved = self.reserved.get(tenant).copied().unwrap_or_default();
+        let in_use = self.in_use.get(tenant).copied().unwrap_or_default();
+        let required = (reserved + count)
+            .saturating_sub(in_use)
+            .saturating_sub(self.outstanding(tenant));
+        let unreserved = self.unreserved();
+        if required > unreserved {
+            return Err(CarbideError::ResourceExhausted(format!(
+                "InstanceType {instance_type_id}: can not reserve {count} Machines for Tenant {tenant}, only {unreserved} unreserved Machines are available"
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Verifies that a batch of allocations doesn't consume Machines reserved for other tenants.
+///
+/// Must be called after the Machines of the batch have been locked. Machines without an
+/// InstanceType are not subject to reservations.
+pub async fn check_reservations(
+    txn: &mut PgConnection,
+    requests: &[InstanceAllocationRequest],
+    machine_map: &HashMap<MachineId, Machine>,
+) -> CarbideResult<()> {
+    let mut requested: HashMap<InstanceTypeId, HashMap<TenantOrganizationId, u32>> = HashMap::new();
+    for request in requests {
+        let Some(instance_type_id) = machine_map
+            .get(&request.machine_id)
+            .and_then(|machine| machine.instance_type_id.clone())
+        else {
+            continue;
+        };
+        *requested
+            .entry(instance_type_id)
+            .or_default()
+            .entry(request.config.tenant.tenant_organization_id.clone())
+            .or_default() += 1;
+    }
+    if requested.is_empty() {
+        return Ok(());
+    }
+
+    let instance_type_ids: Vec<_> = requested.keys().cloned().collect();
+    let capacities = InstanceTypeCapacity::load(txn, &instance_type_ids, true).await?;
+    for (instance_type_id, requested) in &requested {
+        if let Some(capacity) = capacities.get(instance_type_id) {
+            capacity.check_allocation(instance_type_id, requested)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tenant(name: &str) -> TenantOrganizationId {
+        name.parse().unwrap()
+    }
+
+    fn capacity(
+        available: u32,
+        reserved: &[(&str, u32)],
+        in_use: &[(&str, u32)],
+    ) -> InstanceTypeCapacity {
+        InstanceTypeCapacity {
+            available,
+            reserved: reserved.iter().map(|(t, c)| (tenant(t), *c)).collect(),
+            in_use: in_use.iter().map(|(t, c)| (tenant(t), *c)).collect(),
+        }
+    }
+
+    fn requested(counts: &[(&str, u32)]) -> HashMap<TenantOrganizationId, u32> {
+        counts.iter().map(|(t, c)| (tenant(t), *c)).collect()
+    }
+
+    #[test]
+    fn test_outstanding_and_unreserved() {
+        let capacity = capacity(6, &[("a", 3), ("b", 2)], &[("a", 1), ("b", 4), ("c", 2)]);
+        assert_eq!(capacity.outstanding(&tenant("a")), 2);
+        // Usage beyond the reservation doesn't make the outstanding part negative
+        assert_eq!(capacity.outstanding(&tenant("b")), 0);
+        assert_eq!(capacity.outstanding(&tenant("c")), 0);
+        assert_eq!(capacity.unreserved(), 4);
+
+        // More outstanding reservations than available Machines
+        assert_eq!(capacity(1, &[("a", 3)], &[]).unreserved(), 0);
+    }
+
+    #[test]
+    fn test_check_allocation() {
+        let id: InstanceTypeId = "it-1".parse().unwrap();
+        let capacity = capacity(4, &[("a", 3)], &[]);
+
+        // The reserving tenant can use its own reservation and the unreserved Machine
+        assert!(
+            capacity
+                .check_allocation(&id, &requested(&[("a", 4)]))
+                .is_ok()
+        );
+        // Other tenants can only use the unreserved Machine
+        assert!(
+            capacity
+                .check_allocation(&id, &requested(&[("b", 1)]))
+                .is_ok()
+        );
+        assert!(matches!(
+            capacity.check_allocation(&id, &requested(&[("b", 2)])),
+            Err(CarbideError::ResourceExhausted(_))
+        ));
+        // Both together
+        assert!(
+            capacity
+                .check_allocation(&id, &requested(&[("a", 3), ("b", 1)]))
+                .is_ok()
+        );
+        assert!(
+            capacity
+                .check_allocation(&id, &requested(&[("a", 4), ("b", 1)]))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_check_reservation() {
+        let id: InstanceTypeId = "it-1".parse().unwrap();
+        let capacity = capacity(4, &[("a", 3)], &[("a", 2)]);
+        assert_eq!(capacity.unreserved(), 3);
+
+        assert!(capacity.check_reservation(&id, &tenant("b"), 3).is_ok());
+        assert!(matches!(
+            capacity.check_reservation(&id, &tenant("b"), 4),
+            Err(CarbideError::ResourceExhausted(_))
+        ));
+        // Additional reservations of a tenant are first covered by its unused instances
+        assert!(capacity.check_reservation(&id, &tenant("a"), 3).is_ok());
+        assert!(capacity.check_reservation(&id, &tenant("a"), 4).is_err());
+    }
+}
@@ -0,0 +1,289 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+//! Metrics about the utilization of capacity reservations.
+//!
+//! The `CapacityReservationMetricsCollector` periodically loads the capacity of all InstanceTypes
+//! with active reservations and exports how much of each tenant's reservations is in use, as well
+//! as how many Machines of the InstanceTypes are still available to everybody.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use carbide_uuid::instance_type::InstanceTypeId;
+use itertools::Itertools;
+use model::tenant::TenantOrganizationId;
+use opentelemetry::KeyValue;
+use opentelemetry::metrics::Meter;
+use sqlx::PgPool;
+use tokio::sync::oneshot;
+
+use crate::CarbideResult;
+use crate::cfg::file::CapacityReservationMetricsConfig;
+use crate::instance::reservation::InstanceTypeCapacity;
+use crate::logging::metrics_utils::SharedMetricsHolder;
+
+/// The capacity of the InstanceTypes with active reservations, as seen in a single run
+#[derive(Clone, Debug, Default)]
+pub struct CapacityReservationMetrics {
+    pub capacities: HashMap<InstanceTypeId, InstanceTypeCapacity>,
+}
+
+impl CapacityReservationMetrics {
+    /// The reserved, used and outstanding Machines of each tenant with reservations
+    fn tenant_usage(
+        &self,
+    ) -> impl Iterator<Item = (&InstanceTypeId, &TenantOrganizationId, u32, u32, u32)> {
+        self.capacities
+            .iter()
+            .flat_map(|(instance_type_id, capacity)| {
+                capacity.reserved.iter().map(move |(tenant, &reserved)| {
+                    let outstanding = capacity.outstanding(tenant);
+                    (
+                        instance_type_id,
+                        tenant,
+                        reserved,
+                        reserved - outstanding,
+                        outstanding,
+                    )
+                })
+            })
+    }
+}
+
+/// `CapacityReservationMetricsCollector` periodically exports the utilization of capacity
+/// reservations
+pub struct CapacityReservationMetricsCollector {
+    db_pool: PgPool,
+    config: CapacityReservationMetricsConfig,
+    last_iteration_metrics: SharedMetricsHolder<CapacityReservationMetrics>,
+}
+
+impl CapacityReservationMetricsCollector {
+    pub fn new(db_pool: PgPool, config: CapacityReservationMetricsConfig, meter: Meter) -> Self {
+        // Hold metrics a bit longer than the run interval, so there is continuity in emitting them
+        let hold_period = config.run_interval.saturating_add(Duration::from_secs(60));
+        let last_iteration_metrics = SharedMetricsHolder::with_hold_period(hold_period);
+        hydrate_meter(meter, last_iteration_metrics.clone());
+
+        CapacityReservationMetricsCollector {
+            db_pool,
+            config,
+            last_iteration_metrics,
+        }
+    }
+
+    /// Start the CapacityReservationMetricsCollector and return a [sending channel](tokio::sync::oneshot::Sender)
+    /// that will stop the CapacityReservationMetricsCollector when dropped.
+    pub fn start(self) -> eyre::Result<oneshot::Sender<i32>> {
+        let (stop_sender, stop_receiver) = oneshot::channel();
+
+        if self.config.enabled {
+            tokio::task::Builder::new()
+                .name("capacity_reservation_metrics_collector")
+                .spawn(async move { self.run(stop_receiver).await })?;
+        }
+
+        Ok(stop_sender)
+    }
+
+    async fn run(&self, mut stop_receiver: oneshot::Receiver<i32>) {
+        loop {
+            if let Err(e) = self.run_single_iteration().await {
+                tracing::warn!("CapacityReservationMetricsCollector error: {}", e);
+            }
+
+            tokio::select! {
+                _ = tokio::time::sleep(self.config.run_interval) => {},
+                _ = &mut stop_receiver => {
+                    tracing::info!("CapacityReservationMetricsCollector stop was requested");
+                    return;
+                }
+            }
+        }
+    }
+
+    pub async fn run_single_iteration(&self) -> CarbideResult<()> {
+        let mut txn = db::Transaction::begin(&self.db_pool).await?;
+
+        let instance_type_ids: Vec<_> = db::capacity_reservation::find(&mut txn, None, None, false)
+            .await?
+            .into_iter()
+            .map(|r| r.instance_type_id)
+            .unique()
+            .collect();
+        let capacities = InstanceTypeCapacity::load(&mut txn, &instance_type_ids, false).await?;
+
+        txn.commit().await?;
+
+        self.last_iteration_metrics
+            .update(CapacityReservationMetrics { capacities });
+
+        Ok(())
+    }
+}
+
+fn hydrate_meter(meter: Meter, shared_metrics: SharedMetricsHolder<CapacityReservationMetrics>) {
+    {
+        let metrics = shared_metrics.clone();
+        meter
+            .u64_observable_gauge("carbide_capacity_reservation_reserved_machines_count")
+            .with_description("The amount of Machines of an InstanceType reserved for a tenant")
+            .with_callback(move |o| {
+                metrics.if_available(|metrics, attrs| {
+                    for (instance_type_id, tenant, reserved, _, _) in metrics.tenant_usage() {
+                        o.observe(
+                            reserved as u64,
+                            &tenant_attrs(attrs, instance_type_id, tenant),
+                        );
+                    }
+                })
+            })
+            .build();
+    }
+
+    {
+        let metrics = shared_metrics.clone();
+        meter
+            .u64_observable_gauge("carbide_capacity_reservation_used_machines_count")
+            .with_description(
+                "The amount of reserved Machines of an InstanceType which the tenant uses for instances",
+            )
+            .with_callback(move |o| {
+                metrics.if_available(|metrics, attrs| {
+                    for (instance_type_id, tenant, _, used, _) in metrics.tenant_usage() {
+                        o.observe(used as u64, &tenant_attrs(attrs, instance_type_id, tenant));
+                    }
+                })
+            })
+            .build();
+    }
+
+    {
+        let metrics = shared_metrics.clone();
+        meter
+            .u64_observable_gauge("carbide_capacity_reservation_outstanding_machines_count")
+            .with_description(
+                "The amount of reserved Machines of an InstanceType which are held back for the tenant",
+            )
+            .with_callback(move |o| {
+                metrics.if_available(|metrics, attrs| {
+                    for (instance_type_id, tenant, _, _, outstanding) in metrics.tenant_usage() {
+                        o.observe(
+                            outstanding as u64,
+                            &tenant_attrs(attrs, instance_type_id, tenant),
+                        );
+                    }
+                })
+            })
+            .build();
+    }
+
+    {
+        let metrics = shared_metrics.clone();
+        meter
+            .u64_observable_gauge("carbide_capacity_reservation_available_machines_count")
+            .with_description(
+                "The amount of ready Machines without an instance of an InstanceType with reservations",
+            )
+            .with_callback(move |o| {
+                metrics.if_available(|metrics, attrs| {
+                    for (instance_type_id, capacity) in metrics.capacities.iter() {
+                        o.observe(
+                            capacity.available as u64,
+                            &instance_type_attrs(attrs, instance_type_id),
+                        );
+                    }
+                })
+            })
+            .build();
+    }
+
+    {
+        let metrics = shared_metrics;
+        meter
+            .u64_observable_gauge("carbide_capacity_reservation_unreserved_machines_count")
+            .with_description(
+                "The amount of available Machines of an InstanceType which are not held back for any tenant",
+            )
+            .with_callback(move |o| {
+                metrics.if_available(|metrics, attrs| {
+                    for (instance_type_id, capacity) in metrics.capacities.iter() {
+                        o.observe(
+                            capacity.unreserved() as u64,
+                            &instance_type_attrs(attrs, instance_type_id),
+                        );
+                    }
+                })
+            })
+            .build();
+    }
+}
+
+fn instance_type_attrs(attrs: &[KeyValue], instance_type_id: &InstanceTypeId) -> Vec<KeyValue> {
+    [
+        attrs,
+        &[KeyValue::new(
+            "instance_type_id",
+            instance_type_id.to_string(),
+        )],
+    ]
+    .concat()
+}
+
+fn tenant_attrs(
+    attrs: &[KeyValue],
+    instance_type_id: &InstanceTypeId,
+    tenant: &TenantOrganizationId,
+) -> Vec<KeyValue> {
+    [
+        instance_type_attrs(attrs, instance_type_id).as_slice(),
+        &[KeyValue::new("tenant_organization_id", tenant.to_string())],
+    ]
+    .concat()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tenant_usage() {
+        let instance_type_id: InstanceTypeId = "test-type".parse().unwrap();
+        let tenant1: TenantOrganizationId = "Tenant1".parse().unwrap();
+        let tenant2: TenantOrganizationId = "Tenant2".parse().unwrap();
+        let capacity = InstanceTypeCapacity {
+            available: 5,
+            reserved: HashMap::from([(tenant1.clone(), 3), (tenant2.clone(), 2)]),
+            in_use: HashMap::from([(tenant1.clone(), 4)]),
+        };
+        let metrics = CapacityReservationMetrics {
+            capacities: HashMap::from([(instance_type_id.clone(), capacity)]),
+        };
+
+        let usage: HashMap<_, _> = metrics
+            .tenant_usage()
+            .map(|(_, tenant, reserved, used, outstanding)| {
+                (tenant.clone(), (reserved, used, outstanding))
+            })
+            .collect();
+        // Instances beyond the reservation don't count as used reservation
+        assert_eq!(usage[&tenant1], (3, 3, 0));
+        assert_eq!(usage[&tenant2], (2, 0, 2));
+        assert_eq!(metrics.capacities[&instance_type_id].unreserved(), 3);
+    }
+}
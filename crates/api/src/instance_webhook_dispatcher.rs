@@ -0,0 +1,320 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Delivery of instance lifecycle webhooks
+//!
+//! Events are queued in the database in the transaction which persists the change they are
+//! about. The `InstanceWebhookDispatcher` periodically posts the queued events to the URLs of
+//! the subscriptions, signed with the secret of the subscription. Deliveries which fail are
+//! retried with exponential backoff, and are moved to the dead letters once they run out of
+//! attempts. Events are delivered at least once; the delivery ID stays the same across retries.
+//! Dead letters are removed once they are older than the retention period.
+//!
+//! Redirects are not followed, and hosts which resolve to loopback, private or link-local
+//! addresses are refused when the request is made, so that a changed DNS record can't point
+//! deliveries at services inside the site.
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use chrono::Utc;
+use db::Transaction;
+use db::work_lock_manager::WorkLockManagerHandle;
+use forge_secrets::credentials::{CredentialKey, CredentialProvider, Credentials};
+use futures::StreamExt;
+use model::instance_webhook::{
+    DELIVERY_HEADER, EVENT_HEADER, InstanceWebhookDelivery, SIGNATURE_HEADER, TIMESTAMP_HEADER,
+    check_webhook_address, check_webhook_url, retry_backoff, sign_payload,
+};
+use sqlx::PgPool;
+use tokio::sync::oneshot;
+use uuid::Uuid;
+
+use crate::CarbideResult;
+use crate::cfg::file::{CarbideConfig, InstanceWebhookConfig};
+
+/// The outcome of a single run of the dispatcher
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DispatchSummary {
+    pub delivered: usize,
+    pub retried: usize,
+    pub dead_lettered: usize,
+    /// Dead letters which were removed because they exceeded the retention period
+    pub pruned: usize,
+}
+
+/// Resolves the hosts of subscriptions, and refuses the ones which resolve to an address
+/// events must not be posted to
+struct WebhookTargetResolver;
+
+impl reqwest::dns::Resolve for WebhookTargetResolver {
+    fn resolve(&self, name: reqwest::dns::Name) -> reqwest::dns::Resolving {
+        Box::pin(async move {
+            let host = name.as_str();
+            let addresses: Vec<SocketAddr> = tokio::net::lookup_host((host, 0)).await?.collect();
+            for address in &addresses {
+                check_webhook_address(address.ip())
+                    .map_err(|e| format!("{host} resolves to a forbidden address: {e}"))?;
+            }
+            let addresses: reqwest::dns::Addrs = Box::new(addresses.into_iter());
+            Ok(addresses)
+        })
+    }
+}
+
+/// `InstanceWebhookDispatcher` periodically delivers the queued instance lifecycle events to
+/// the webhooks of tenants
+///
+/// Config from [InstanceWebhookConfig]:
+/// * `enabled` and `run_interval` control the dispatcher itself
+/// * `max_attempts`, `initial_backoff` and `max_backoff` control retries
+/// * `request_timeout` and `max_deliveries_per_run` limit the time spent on each run
+/// * `max_concurrent_subscriptions` and `max_concurrent_deliveries_per_subscription` limit
+///   the number of requests in flight
+/// * `dead_letter_retention` controls when dead letters are removed
+/// * `allow_insecure_targets` disables the checks of the addresses events are posted to
+pub struct InstanceWebhookDispatcher {
+    db_pool: PgPool,
+    config: InstanceWebhookConfig,
+    http_client: reqwest::Client,
+    credential_provider: Arc<dyn CredentialProvider>,
+    work_lock_manager_handle: WorkLockManagerHandle,
+}
+
+impl InstanceWebhookDispatcher {
+    const ITERATION_WORK_KEY: &'static str = "InstanceWebhookDispatcher::run_single_iteration";
+
+    pub fn new(
+        db_pool: PgPool,
+        config: Arc<CarbideConfig>,
+        credential_provider: Arc<dyn CredentialProvider>,
+        work_lock_manager_handle: WorkLockManagerHandle,
+    ) -> eyre::Result<Self> {
+        let config = config.instance_webhooks.clone();
+        let mut http_client = reqwest::Client::builder()
+            .timeout(config.request_timeout)
+            .redirect(reqwest::redirect::Policy::none());
+        if !config.allow_insecure_targets {
+            http_client = http_client.dns_resolver(Arc::new(WebhookTargetResolver));
+        }
+        Ok(InstanceWebhookDispatcher {
+            db_pool,
+            config,
+            http_client: http_client.build()?,
+            credential_provider,
+            work_lock_manager_handle,
+        })
+    }
+
+    /// Start the InstanceWebhookDispatcher and return a [sending channel](tokio::sync::oneshot::Sender) that will stop the InstanceWebhookDispatcher when dropped.
+    pub fn start(self) -> eyre::Result<oneshot::Sender<i32>> {
+        let (stop_sender, stop_receiver) = oneshot::channel();
+
+        if self.config.enabled {
+            tokio::task::Builder::new()
+                .name("instance_webhook_dispatcher")
+                .spawn(async move { self.run(stop_receiver).await })?;
+        } else {
+            tracing::info!("Instance webhook dispatcher is disabled");
+        }
+
+        Ok(stop_sender)
+    }
+
+    async fn run(&self, mut stop_receiver: oneshot::Receiver<i32>) {
+        loop {
+            if let Err(e) = self.run_single_iteration().await {
+                tracing::warn!("InstanceWebhookDispatcher error: {}", e);
+            }
+
+            tokio::select! {
+                _ = tokio::time::sleep(self.config.run_interval) => {},
+                _ = &mut stop_receiver => {
+                    tracing::info!("InstanceWebhookDispatcher stop was requested");
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Delivers the events which are due and records the outcome of each attempt
+    pub async fn run_single_iteration(&self) -> CarbideResult<DispatchSummary> {
+        let _lock = match self
+            .work_lock_manager_handle
+            .try_acquire_lock(Self::ITERATION_WORK_KEY.into())
+            .await
+        {
+            Ok(lock) => lock,
+            Err(e) => {
+                tracing::warn!(
+                    "InstanceWebhookDispatcher failed to acquire work lock: Another instance of carbide running? {e}"
+                );
+                return Ok(DispatchSummary::default());
+            }
+        };
+
+        // The work lock makes this the only dispatcher, so the transaction isn't held while
+        // events are posted to subscribers
+        let mut txn = Transaction::begin(&self.db_pool).await?;
+        let deliveries = db::instance_webhook::find_due_deliveries(
+            &mut txn,
+            Utc::now(),
+            self.config.max_deliveries_per_run,
+        )
+        .await?;
+        txn.commit().await?;
+
+        let mut by_subscription: HashMap<Uuid, Vec<InstanceWebhookDelivery>> = HashMap::new();
+        for delivery in deliveries {
+            by_subscription
+                .entry(delivery.subscription_id)
+                .or_default()
+                .push(delivery);
+        }
+
+        let results: Vec<_> = futures::stream::iter(by_subscription)
+            .map(|(subscription_id, deliveries)| self.deliver_all(subscription_id, deliveries))
+            .buffer_unordered(self.config.max_concurrent_subscriptions.max(1))
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .flatten()
+            .collect();
+
+        let mut summary = DispatchSummary::default();
+        let mut txn = Transaction::begin(&self.db_pool).await?;
+        for (delivery, result) in results {
+            match result {
+                Ok(()) => {
+                    db::instance_webhook::mark_delivered(&mut txn, delivery.id).await?;
+                    summary.delivered += 1;
+                }
+                Err(error) => {
+                    let attempts = delivery.attempts + 1;
+                    let next_attempt_at = if attempts < self.config.max_attempts {
+                        summary.retried += 1;
+                        let backoff = retry_backoff(
+                            attempts,
+                            self.config.initial_backoff,
+                            self.config.max_backoff,
+                        );
+                        Some(Utc::now() + backoff)
+                    } else {
+                        tracing::warn!(
+                            delivery_id = delivery.id,
+                            subscription_id = %delivery.subscription_id,
+                            instance_id = %delivery.event.instance_id,
+                            event_type = %delivery.event.event_type,
+                            %error,
+                            "Instance webhook delivery ran out of attempts"
+                        );
+                        summary.dead_lettered += 1;
+                        None
+                    };
+                    db::instance_webhook::mark_failed(
+                        &mut txn,
+                        delivery.id,
+                        &error,
+                        next_attempt_at,
+                    )
+                    .await?;
+                }
+            }
+        }
+        summary.pruned = db::instance_webhook::prune_dead_letters(
+            &mut txn,
+            Utc::now() - self.config.dead_letter_retention,
+        )
+        .await? as usize;
+        txn.commit().await?;
+
+        Ok(summary)
+    }
+
+    /// Delivers the due events of a subscription, oldest first. At most
+    /// `max_concurrent_deliveries_per_subscription` requests are in flight at the same time.
+    async fn deliver_all(
+        &self,
+        subscription_id: Uuid,
+        deliveries: Vec<InstanceWebhookDelivery>,
+    ) -> Vec<(InstanceWebhookDelivery, Result<(), String>)> {
+        let secret = &self.load_secret(subscription_id).await;
+        futures::stream::iter(deliveries)
+            .map(|delivery| async move {
+                let result = match secret {
+                    Ok(secret) => self.deliver(&delivery, secret).await,
+                    Err(error) => Err(error.clone()),
+                };
+                (delivery, result)
+            })
+            .buffer_unordered(
+                self.config
+                    .max_concurrent_deliveries_per_subscription
+                    .max(1),
+            )
+            .collect()
+            .await
+    }
+
+    /// Reads the signing secret of a subscription from the credential provider
+    async fn load_secret(&self, subscription_id: Uuid) -> Result<String, String> {
+        match self
+            .credential_provider
+            .get_credentials(&CredentialKey::InstanceWebhookSecret {
+                subscription_id: subscription_id.to_string(),
+            })
+            .await
+        {
+            Ok(Some(Credentials::UsernamePassword { password, .. })) => Ok(password),
+            Ok(None) => Err("signing secret not found in credential provider".to_string()),
+            Err(e) => Err(format!("failed to read signing secret: {e}")),
+        }
+    }
+
+    /// Posts an event to the URL of its subscription. Any 2xx response counts as delivered.
+    async fn deliver(
+        &self,
+        delivery: &InstanceWebhookDelivery,
+        secret: &str,
+    ) -> Result<(), String> {
+        // Hosts are checked by the resolver, which isn't used for IP addresses
+        if !self.config.allow_insecure_targets {
+            check_webhook_url(&delivery.url)?;
+        }
+        let payload = serde_json::to_vec(&delivery.event).map_err(|e| e.to_string())?;
+        let timestamp = Utc::now().timestamp();
+
+        let response = self
+            .http_client
+            .post(&delivery.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(SIGNATURE_HEADER, sign_payload(secret, timestamp, &payload))
+            .header(TIMESTAMP_HEADER, timestamp.to_string())
+            .header(EVENT_HEADER, delivery.event.event_type.to_string())
+            .header(DELIVERY_HEADER, delivery.id.to_string())
+            .body(payload)
+            .send()
+            .await
+            .map_err(|e| format!("request failed: {e}"))?;
+
+        if !response.status().is_success() {
+            return Err(format!("subscriber responded with {}", response.status()));
+        }
+        Ok(())
+    }
+}
@@ -322,6 +322,14 @@ exit ||
                     },
                 ..
             }
+            | ManagedHostState::Failed {
+                details:
+                    FailureDetails {
+                        cause: FailureCause::SataSasCleanFailed { .. },
+                        ..
+                    },
+                ..
+            }
             | ManagedHostState::Failed {
                 details:
                     FailureDetails {
@@ -36,6 +36,7 @@
 mod api;
 mod attestation;
 mod auth;
+mod bmc_credential_rotation;
 mod cfg;
 mod credentials;
 mod db_init;
@@ -49,6 +50,7 @@ mod handlers;
 mod ib;
 mod ib_fabric_monitor;
 mod instance;
+mod instance_webhook_dispatcher;
 mod ipmitool;
 mod ipxe;
 mod listener;
@@ -56,8 +58,10 @@ mod logging;
 mod machine_identity;
 mod machine_update_manager;
 mod machine_validation;
+mod maintenance_window;
 mod measured_boot;
 mod mqtt_state_change_hook;
+mod network_security_group_monitor;
 mod network_segment;
 mod nvl_partition_monitor;
 mod nvlink;
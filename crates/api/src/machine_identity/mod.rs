@@ -72,7 +72,8 @@ impl Signer for Es256Signer {
             .map(|(k, v)| (k.clone(), v.clone()))
             .collect::<BTreeMap<_, _>>();
 
-        let header = Header::new(jsonwebtoken::Algorithm::ES256);
+        let mut header = Header::new(jsonwebtoken::Algorithm::ES256);
+        header.kid = Some(self.key_id.clone());
         let token = encode(&header, &claims, &self.encoding_key)?;
         Ok(token)
     }
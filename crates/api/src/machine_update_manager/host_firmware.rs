@@ -56,7 +56,7 @@ impl MachineUpdateModule for HostFirmwareUpdate {
         txn: &mut PgConnection,
         available_updates: i32,
         updating_host_machines: &HashSet<MachineId>,
-        _snapshots: &HashMap<MachineId, ManagedHostStateSnapshot>,
+        snapshots: &HashMap<MachineId, ManagedHostStateSnapshot>,
     ) -> CarbideResult<HashSet<MachineId>> {
         if let Ok(mut firmware_dir_last_read) = self.firmware_dir_last_read.try_lock() {
             let firmware_dir_mod_time = self.firmware_config.config_update_time();
@@ -74,7 +74,9 @@ impl MachineUpdateModule for HostFirmwareUpdate {
             }
         }
 
-        let machine_updates = self.check_for_updates(txn, available_updates).await?;
+        let machine_updates = self
+            .check_for_updates(txn, available_updates, snapshots)
+            .await?;
         let mut updates_started = HashSet::default();
         self.metrics
             .pending_firmware_updates
@@ -172,6 +174,7 @@ impl HostFirmwareUpdate {
         &self,
         txn: &mut PgConnection,
         mut available_updates: i32,
+        snapshots: &HashMap<MachineId, ManagedHostStateSnapshot>,
     ) -> CarbideResult<Vec<MachineId>> {
         let mut machines = vec![];
         if available_updates == 0 {
@@ -198,6 +201,10 @@ impl HostFirmwareUpdate {
                 // This machine is specifically disabled
                 break;
             }
+            if !snapshots.contains_key(&update_needed.id) {
+                // Not eligible for an update right now, e.g. due to the rollout policy
+                continue;
+            }
             available_updates -= 1;
             machines.push(update_needed.id);
         }
@@ -23,6 +23,7 @@ pub struct MachineUpdateManagerMetrics {
     pub machines_in_maintenance: Arc<AtomicU64>,
     pub machine_updates_started: Arc<AtomicU64>,
     pub concurrent_machine_updates_available: Arc<AtomicU64>,
+    pub machine_update_rollout_halted: Arc<AtomicU64>,
 }
 
 impl MachineUpdateManagerMetrics {
@@ -31,6 +32,7 @@ impl MachineUpdateManagerMetrics {
             machines_in_maintenance: Arc::new(AtomicU64::new(0)),
             machine_updates_started: Arc::new(AtomicU64::new(0)),
             concurrent_machine_updates_available: Arc::new(AtomicU64::new(0)),
+            machine_update_rollout_halted: Arc::new(AtomicU64::new(0)),
         }
     }
 
@@ -39,6 +41,7 @@ impl MachineUpdateManagerMetrics {
         let machine_updates_started = self.machine_updates_started.clone();
         let concurrent_machine_updates_available =
             self.concurrent_machine_updates_available.clone();
+        let machine_update_rollout_halted = self.machine_update_rollout_halted.clone();
         meter
             .u64_observable_gauge("carbide_machines_in_maintenance_count")
             .with_description("The total number of machines in the system that are in maintenance.")
@@ -67,5 +70,14 @@ impl MachineUpdateManagerMetrics {
                 )
             })
             .build();
+        meter
+            .u64_observable_gauge("carbide_machine_update_rollout_halted")
+            .with_description(
+                "Whether the machine update rollout is halted because too many updated machines are unhealthy.",
+            )
+            .with_callback(move |observer| {
+                observer.observe(machine_update_rollout_halted.load(Ordering::Relaxed), &[])
+            })
+            .build();
     }
 }
@@ -19,6 +19,7 @@ pub mod dpu_nic_firmware_metrics;
 pub mod host_firmware;
 pub mod machine_update_module;
 pub mod metrics;
+pub mod rollout;
 
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
@@ -34,13 +35,16 @@ use model::dpu_machine_update::DpuMachineUpdate;
 use model::machine::machine_search_config::MachineSearchConfig;
 use model::machine::{HostHealthConfig, LoadSnapshotOptions, ManagedHostStateSnapshot};
 use model::machine_update_module::HOST_UPDATE_HEALTH_REPORT_SOURCE;
+use model::machine_update_rollout::MachineUpdateRolloutState;
 use sqlx::{PgConnection, PgPool};
 use tokio::sync::oneshot;
 
 use self::dpu_nic_firmware::DpuNicFirmwareUpdate;
 use self::metrics::MachineUpdateManagerMetrics;
+use self::rollout::ActiveRollout;
 use crate::CarbideResult;
-use crate::cfg::file::{CarbideConfig, MaxConcurrentUpdates};
+use crate::cfg::file::{CarbideConfig, MachineUpdateRolloutPolicy, MaxConcurrentUpdates};
+use crate::maintenance_window;
 
 /// The MachineUpdateManager periodically runs [modules](machine_update_module::MachineUpdateModule) to initiate upgrades of machine components.
 /// On each iteration the MachineUpdateManager will:
@@ -48,9 +52,14 @@ use crate::cfg::file::{CarbideConfig, MaxConcurrentUpdates};
 /// 2. if there are less than the max allowed updates each module will be told to start updates until
 ///    the number of updates reaches the maximum allowed.
 ///
+/// If a rollout policy is configured, updates are additionally limited by the waves of the
+/// current [rollout](rollout::ActiveRollout), and by the per-rack and per-SKU limits.
+/// Updates are only started on hosts whose maintenance windows are open.
+///
 /// Config from [CarbideConfig]:
 /// * `max_concurrent_machine_updates` the maximum number of updates allowed across all modules
 /// * `machine_update_run_interval` how often the manager calls the modules to start updates
+/// * `machine_updater.rollout` how updates are rolled out in waves
 pub struct MachineUpdateManager {
     database_connection: PgPool,
     max_concurrent_machine_updates: MaxConcurrentUpdates,
@@ -58,6 +67,7 @@ pub struct MachineUpdateManager {
     update_modules: Vec<Box<dyn MachineUpdateModule>>,
     metrics: Option<MachineUpdateManagerMetrics>,
     host_health: HostHealthConfig,
+    rollout_policy: Option<MachineUpdateRolloutPolicy>,
     work_lock_manager_handle: WorkLockManagerHandle,
 }
 
@@ -80,6 +90,7 @@ impl MachineUpdateManager {
             update_modules: modules,
             metrics: None,
             host_health: config.host_health,
+            rollout_policy: config.machine_updater.rollout.clone(),
             work_lock_manager_handle,
         }
     }
@@ -113,6 +124,7 @@ impl MachineUpdateManager {
             update_modules,
             metrics: Some(machine_update_metrics),
             host_health: config.host_health,
+            rollout_policy: config.machine_updater.rollout.clone(),
             work_lock_manager_handle,
         }
     }
@@ -221,32 +233,105 @@ impl MachineUpdateManager {
             .max_concurrent_machine_updates
             .max_concurrent_updates(all_count, unhealthy_count)
             .unwrap_or(MachineUpdateManager::DEFAULT_MAX_CONCURRENT_MACHINE_UPDATES); // XXX
-        for update_module in self.update_modules.iter() {
-            if (current_updating_machines.len() as i32) >= max_concurrent_updates {
-                break;
+
+        let now = chrono::Utc::now();
+        // Updates are only started on hosts whose maintenance windows allow it
+        let schedule = maintenance_window::load_schedule(&mut txn, now).await?;
+        let allowed_snapshots =
+            maintenance_window::allowed_hosts(&mut txn, &schedule, &snapshots).await?;
+        if allowed_snapshots.len() < snapshots.len() {
+            tracing::debug!(
+                "Maintenance windows allow updates on {} of {} hosts",
+                allowed_snapshots.len(),
+                snapshots.len()
+            );
+        }
+
+        let mut rollout = match self.rollout_policy.as_ref() {
+            Some(policy) => {
+                let mut rollout = ActiveRollout::load(&mut txn, policy, &snapshots, now).await?;
+                rollout.check_health(&snapshots, &current_updating_machines);
+                Some(rollout)
+            }
+            None => None,
+        };
+
+        // The number of modules which had room for updates, but didn't start any
+        let mut idle_modules = 0;
+        'modules: for update_module in self.update_modules.iter() {
+            loop {
+                if (current_updating_machines.len() as i32) >= max_concurrent_updates {
+                    break 'modules;
+                }
+                tracing::debug!("in progress: {:?}", current_updating_machines);
+                let mut available_updates =
+                    max_concurrent_updates - current_updating_machines.len() as i32;
+
+                let batch = rollout.as_ref().map(|rollout| {
+                    let mut batch = rollout.next_batch(&snapshots, &current_updating_machines);
+                    batch
+                        .snapshots
+                        .retain(|machine_id, _| allowed_snapshots.contains_key(machine_id));
+                    batch
+                });
+                if let Some(batch) = batch.as_ref() {
+                    available_updates = available_updates.min(batch.available_updates);
+                    if available_updates <= 0 {
+                        break 'modules;
+                    }
+                }
+
+                let updates_started = update_module
+                    .start_updates(
+                        &mut txn,
+                        available_updates,
+                        &current_updating_machines,
+                        batch
+                            .as_ref()
+                            .map_or(&allowed_snapshots, |batch| &batch.snapshots),
+                    )
+                    .await?;
+                tracing::debug!("started: {:?}", updates_started);
+
+                if let Some(rollout) = rollout.as_mut() {
+                    rollout.record_started(&updates_started);
+                }
+                updates_started_count += updates_started.len();
+
+                current_updating_machines = current_updating_machines
+                    .union(&updates_started)
+                    .copied()
+                    .collect();
+
+                if updates_started.is_empty() {
+                    idle_modules += 1;
+                    break;
+                }
+                // Limits on racks and SKUs can leave room for more updates once the hosts of
+                // this batch are known
+                if !batch.is_some_and(|batch| batch.limited_by_caps) {
+                    break;
+                }
             }
-            tracing::debug!("in progress: {:?}", current_updating_machines);
-            let available_updates = max_concurrent_updates - current_updating_machines.len() as i32;
-
-            let updates_started = update_module
-                .start_updates(
-                    &mut txn,
-                    available_updates,
-                    &current_updating_machines,
-                    &snapshots,
-                )
-                .await?;
-            tracing::debug!("started: {:?}", updates_started);
-
-            updates_started_count += updates_started.len();
-
-            current_updating_machines = current_updating_machines
-                .union(&updates_started)
-                .copied()
-                .collect();
         }
         let current_updating_count = current_updating_machines.len();
 
+        let mut rollout_halted = false;
+        if let Some(mut rollout) = rollout {
+            // Hosts outside of their maintenance windows might still need updates
+            let no_more_updates = current_updating_machines.is_empty()
+                && idle_modules == self.update_modules.len()
+                && allowed_snapshots.len() == snapshots.len();
+            rollout.advance(
+                snapshots.len(),
+                &current_updating_machines,
+                no_more_updates,
+                now,
+            );
+            rollout_halted = rollout.state() == MachineUpdateRolloutState::Halted;
+            rollout.save(&mut txn).await?;
+        }
+
         //refresh snapshots for metrics
         let snapshots = self.get_all_snapshots(&mut txn).await?;
 
@@ -266,6 +351,9 @@ impl MachineUpdateManager {
             metrics
                 .concurrent_machine_updates_available
                 .store(max_concurrent_updates as u64, Ordering::Relaxed);
+            metrics
+                .machine_update_rollout_halted
+                .store(u64::from(rollout_halted), Ordering::Relaxed);
         }
 
         Ok(())
@@ -0,0 +1,334 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Staged rollouts of machine updates, as configured by [MachineUpdateRolloutPolicy]
+
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+use carbide_uuid::machine::MachineId;
+use carbide_uuid::rack::RackId;
+use chrono::{DateTime, Utc};
+use model::machine::ManagedHostStateSnapshot;
+use model::machine_update_module::HOST_UPDATE_HEALTH_PROBE_ID;
+use model::machine_update_rollout::{MachineUpdateRollout, MachineUpdateRolloutState};
+use sqlx::PgConnection;
+
+use crate::CarbideResult;
+use crate::cfg::file::MachineUpdateRolloutPolicy;
+
+/// The rollout which controls the updates started in one iteration of the
+/// [MachineUpdateManager](super::MachineUpdateManager)
+pub struct ActiveRollout {
+    policy: MachineUpdateRolloutPolicy,
+    pub rollout: MachineUpdateRollout,
+    /// The rollout as loaded from the database, to only store it when it changed
+    stored: Option<MachineUpdateRollout>,
+    racks: HashMap<MachineId, RackId>,
+}
+
+/// The hosts whose update may be started next
+pub struct RolloutBatch {
+    pub snapshots: HashMap<MachineId, ManagedHostStateSnapshot>,
+    pub available_updates: i32,
+    /// Whether the per-rack or per-SKU limits allow fewer updates than the current wave.
+    /// Another batch can be started once the updates of this batch are known.
+    pub limited_by_caps: bool,
+}
+
+impl ActiveRollout {
+    /// Loads the latest rollout, or starts a new one if the latest one completed.
+    /// The rollout stays locked until the end of the transaction.
+    pub async fn load(
+        txn: &mut PgConnection,
+        policy: &MachineUpdateRolloutPolicy,
+        snapshots: &HashMap<MachineId, ManagedHostStateSnapshot>,
+        now: DateTime<Utc>,
+    ) -> CarbideResult<Self> {
+        let stored = db::machine_update_rollout::find_latest(txn, true).await?;
+        let rollout = match stored.as_ref() {
+            Some(rollout) if rollout.is_active() => rollout.clone(),
+            _ => MachineUpdateRollout::new(now),
+        };
+
+        let racks = if policy.max_concurrent_updates_per_rack.is_some() {
+            db::expected_machine::find_rack_ids_of_hosts(
+                &mut *txn,
+                snapshots.values().map(|s| &s.host_snapshot),
+            )
+            .await?
+        } else {
+            HashMap::new()
+        };
+
+        Ok(Self {
+            policy: policy.clone(),
+            rollout,
+            stored,
+            racks,
+        })
+    }
+
+    pub fn state(&self) -> MachineUpdateRolloutState {
+        self.rollout.state
+    }
+
+    /// Halts the rollout if too many of the hosts it updated are unhealthy
+    pub fn check_health(
+        &mut self,
+        snapshots: &HashMap<MachineId, ManagedHostStateSnapshot>,
+        updating_host_machines: &HashSet<MachineId>,
+    ) {
+        let Some(halt_unhealthy_percent) = self.policy.halt_unhealthy_percent else {
+            return;
+        };
+        if self.rollout.state != MachineUpdateRolloutState::Running {
+            return;
+        }
+
+        let acknowledged: HashSet<&MachineId> = self.rollout.acknowledged_machines.iter().collect();
+        let updated: Vec<&ManagedHostStateSnapshot> = self
+            .rollout
+            .machines
+            .iter()
+            .filter(|m| {
+                !updating_host_machines.contains(&m.machine_id)
+                    && !acknowledged.contains(&m.machine_id)
+            })
+            .filter_map(|m| snapshots.get(&m.machine_id))
+            .collect();
+        let unhealthy: Vec<MachineId> = updated
+            .iter()
+            .filter(|snapshot| {
+                snapshot
+                    .aggregate_health
+                    .alerts
+                    .iter()
+                    .any(|alert| alert.id != *HOST_UPDATE_HEALTH_PROBE_ID)
+            })
+            .map(|snapshot| snapshot.host_snapshot.id)
+            .collect();
+
+        if exceeds_unhealthy_limit(unhealthy.len(), updated.len(), halt_unhealthy_percent) {
+            let reason = format!(
+                "{} of {} updated hosts are unhealthy, which exceeds the limit of {}%",
+                unhealthy.len(),
+                updated.len(),
+                halt_unhealthy_percent
+            );
+            tracing::warn!(?unhealthy, "Halting machine update rollout: {reason}");
+            self.rollout.halt(unhealthy, reason);
+        }
+    }
+
+    /// Selects the hosts whose update can be started without exceeding the current wave and the
+    /// per-rack and per-SKU limits
+    pub fn next_batch(
+        &self,
+        snapshots: &HashMap<MachineId, ManagedHostStateSnapshot>,
+        updating_host_machines: &HashSet<MachineId>,
+    ) -> RolloutBatch {
+        if self.rollout.state != MachineUpdateRolloutState::Running {
+            return RolloutBatch {
+                snapshots: HashMap::new(),
+                available_updates: 0,
+                limited_by_caps: false,
+            };
+        }
+
+        let wave_target = self.policy.wave_target(self.rollout.wave, snapshots.len());
+        let wave_remaining = wave_target.saturating_sub(self.rollout.machines.len()) as i32;
+
+        let mut rack_limit = GroupLimit::new(self.policy.max_concurrent_updates_per_rack);
+        let mut sku_limit = GroupLimit::new(self.policy.max_concurrent_updates_per_sku);
+        for machine_id in updating_host_machines {
+            if let Some(snapshot) = snapshots.get(machine_id) {
+                rack_limit.add_in_progress(self.racks.get(machine_id));
+                sku_limit.add_in_progress(snapshot.host_snapshot.hw_sku.as_ref());
+            }
+        }
+
+        let mut cap_remaining: Option<u32> = None;
+        let eligible: HashMap<MachineId, ManagedHostStateSnapshot> = snapshots
+            .iter()
+            .filter(|(machine_id, _)| !updating_host_machines.contains(machine_id))
+            .filter_map(|(machine_id, snapshot)| {
+                let remaining = [
+                    rack_limit.remaining(self.racks.get(machine_id)),
+                    sku_limit.remaining(snapshot.host_snapshot.hw_sku.as_ref()),
+                ]
+                .into_iter()
+                .flatten()
+                .min();
+                if remaining == Some(0) {
+                    return None;
+                }
+                if let Some(remaining) = remaining {
+                    cap_remaining = Some(cap_remaining.map_or(remaining, |r| r.min(remaining)));
+                }
+                Some((*machine_id, snapshot.clone()))
+            })
+            .collect();
+
+        let limited_by_caps = cap_remaining.is_some_and(|cap| (cap as i32) < wave_remaining);
+        let available_updates = match cap_remaining {
+            Some(cap) => wave_remaining.min(cap as i32),
+            None => wave_remaining,
+        };
+
+        RolloutBatch {
+            snapshots: eligible,
+            available_updates,
+            limited_by_caps,
+        }
+    }
+
+    /// Records updates which were started as part of the current wave
+    pub fn record_started(&mut self, updates_started: &HashSet<MachineId>) {
+        self.rollout.add_machines(updates_started.iter().copied());
+    }
+
+    /// Moves on to the next wave once all updates of the current wave are done and its soak
+    /// period passed. `no_more_updates` indicates that the update modules had nothing left to
+    /// update, which completes the rollout.
+    pub fn advance(
+        &mut self,
+        host_count: usize,
+        updating_host_machines: &HashSet<MachineId>,
+        no_more_updates: bool,
+        now: DateTime<Utc>,
+    ) {
+        if self.rollout.state != MachineUpdateRolloutState::Running {
+            return;
+        }
+        if self
+            .rollout
+            .machines
+            .iter()
+            .any(|m| updating_host_machines.contains(&m.machine_id))
+        {
+            self.rollout.wave_completed_at = None;
+            return;
+        }
+
+        if self.rollout.machines.len() < self.policy.wave_target(self.rollout.wave, host_count) {
+            if no_more_updates {
+                self.complete("No more hosts to update");
+            }
+            return;
+        }
+
+        let wave_completed_at = *self.rollout.wave_completed_at.get_or_insert(now);
+        if now < wave_completed_at + self.policy.soak_period {
+            return;
+        }
+        if self.policy.is_last_wave(self.rollout.wave) {
+            self.complete("All waves are done");
+        } else {
+            self.rollout.wave += 1;
+            self.rollout.wave_completed_at = None;
+            tracing::info!(
+                wave = self.rollout.wave,
+                "Machine update rollout moved on to the next wave"
+            );
+        }
+    }
+
+    fn complete(&mut self, reason: &str) {
+        tracing::info!("Machine update rollout completed: {reason}");
+        self.rollout.state = MachineUpdateRolloutState::Completed;
+        self.rollout.state_reason = Some(reason.to_string());
+    }
+
+    /// Stores the rollout if it changed. A new rollout is only stored once it started updates.
+    pub async fn save(self, txn: &mut PgConnection) -> CarbideResult<()> {
+        if self.rollout.id.is_none() && self.rollout.machines.is_empty() {
+            return Ok(());
+        }
+        if self.stored.as_ref() == Some(&self.rollout) {
+            return Ok(());
+        }
+        db::machine_update_rollout::save(txn, &self.rollout).await?;
+        Ok(())
+    }
+}
+
+fn exceeds_unhealthy_limit(unhealthy: usize, updated: usize, limit_percent: u32) -> bool {
+    unhealthy > 0 && unhealthy * 100 > updated * limit_percent as usize
+}
+
+/// Tracks the updates in progress per group of hosts, like a rack, for a concurrency limit
+struct GroupLimit<'a, T> {
+    limit: Option<u32>,
+    in_progress: HashMap<&'a T, u32>,
+}
+
+impl<'a, T: Eq + Hash> GroupLimit<'a, T> {
+    fn new(limit: Option<u32>) -> Self {
+        Self {
+            limit,
+            in_progress: HashMap::new(),
+        }
+    }
+
+    fn add_in_progress(&mut self, group: Option<&'a T>) {
+        if let Some(group) = group {
+            *self.in_progress.entry(group).or_default() += 1;
+        }
+    }
+
+    /// How many more updates can be started in the group. Hosts without a group are not limited.
+    fn remaining(&self, group: Option<&T>) -> Option<u32> {
+        let limit = self.limit?;
+        let in_progress = self.in_progress.get(group?).copied().unwrap_or_default();
+        Some(limit.saturating_sub(in_progress))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_group_limit() {
+        let rack_a = "rack-a".to_string();
+        let rack_b = "rack-b".to_string();
+
+        let mut limit = GroupLimit::new(Some(2));
+        limit.add_in_progress(Some(&rack_a));
+        limit.add_in_progress(Some(&rack_a));
+        limit.add_in_progress(Some(&rack_b));
+        limit.add_in_progress(None);
+        assert_eq!(limit.remaining(Some(&rack_a)), Some(0));
+        assert_eq!(limit.remaining(Some(&rack_b)), Some(1));
+        assert_eq!(limit.remaining(Some(&"rack-c".to_string())), Some(2));
+        assert_eq!(limit.remaining(None), None);
+
+        let unlimited = GroupLimit::<String>::new(None);
+        assert_eq!(unlimited.remaining(Some(&rack_a)), None);
+    }
+
+    #[test]
+    fn test_exceeds_unhealthy_limit() {
+        assert!(!exceeds_unhealthy_limit(0, 0, 0));
+        assert!(!exceeds_unhealthy_limit(0, 10, 0));
+        assert!(exceeds_unhealthy_limit(1, 10, 0));
+        assert!(!exceeds_unhealthy_limit(1, 10, 10));
+        assert!(exceeds_unhealthy_limit(2, 10, 10));
+        assert!(!exceeds_unhealthy_limit(10, 10, 100));
+    }
+}
@@ -20,7 +20,9 @@ use std::str::FromStr;
 
 use carbide::{Command, Options};
 use clap::CommandFactory;
+use forge_secrets::config::CredentialBackendConfig;
 use forge_secrets::forge_vault::VaultConfig;
+use forge_secrets::migration::copy_credentials;
 use sqlx::PgPool;
 use sqlx::postgres::{PgConnectOptions, PgSslMode};
 
@@ -54,6 +56,39 @@ async fn main() -> eyre::Result<()> {
             let pool = PgPool::connect_with(pg_connection_options).await?;
             db::migrations::migrate(&pool).await?;
         }
+        Command::MigrateCredentials(m) => {
+            let source: CredentialBackendConfig =
+                toml::from_str(&tokio::fs::read_to_string(&m.source).await?)?;
+            let target: CredentialBackendConfig =
+                toml::from_str(&tokio::fs::read_to_string(&m.target).await?)?;
+            if source == target {
+                eyre::bail!("source and target describe the same credential backend");
+            }
+
+            let meter = opentelemetry::global::meter("carbide-api");
+            let vault_config = VaultConfig::default();
+            let source = source.create_store(&vault_config, meter.clone()).await?;
+            let target = target.create_store(&vault_config, meter).await?;
+
+            let report =
+                copy_credentials(source.as_ref(), target.as_ref(), m.overwrite, m.dry_run).await?;
+            let verb = if m.dry_run { "Would copy" } else { "Copied" };
+            for key in &report.copied {
+                println!("{verb} {key}");
+            }
+            for key in &report.skipped_existing {
+                println!("Skipped {key}: already exists in target");
+            }
+            for key in &report.skipped_unreadable {
+                println!("Skipped {key}: does not hold credentials");
+            }
+            println!(
+                "{verb} {} credentials, skipped {} existing and {} unreadable entries",
+                report.copied.len(),
+                report.skipped_existing.len(),
+                report.skipped_unreadable.len()
+            );
+        }
         Command::Run(config) => {
             // THIS SECTION HAS BEEN INTENTIONALLY KEPT SMALL.
             // Nothing should go before the call to carbide::run that isn't already here.
@@ -0,0 +1,105 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Evaluates the [maintenance windows](model::maintenance_window::MaintenanceWindow) of the site
+//! before disruptive work, like machine updates or DPU remediations, is started on hosts
+
+use std::collections::HashMap;
+
+use carbide_uuid::machine::MachineId;
+use chrono::{DateTime, Utc};
+use model::machine::ManagedHostStateSnapshot;
+use model::maintenance_window::{HostMaintenanceScope, MaintenanceSchedule};
+use sqlx::PgConnection;
+
+use crate::CarbideResult;
+
+/// Loads the maintenance windows of the site, evaluated at `now`
+pub async fn load_schedule(
+    txn: &mut PgConnection,
+    now: DateTime<Utc>,
+) -> CarbideResult<MaintenanceSchedule> {
+    let windows = db::maintenance_window::find_all(txn).await?;
+    Ok(MaintenanceSchedule::new(windows, now))
+}
+
+/// Returns the hosts on which disruptive work may start now
+pub async fn allowed_hosts(
+    txn: &mut PgConnection,
+    schedule: &MaintenanceSchedule,
+    snapshots: &HashMap<MachineId, ManagedHostStateSnapshot>,
+) -> CarbideResult<HashMap<MachineId, ManagedHostStateSnapshot>> {
+    if schedule.is_unrestricted() {
+        return Ok(snapshots.clone());
+    }
+
+    let mut racks = if schedule.has_rack_windows() {
+        db::expected_machine::find_rack_ids_of_hosts(
+            &mut *txn,
+            snapshots.values().map(|s| &s.host_snapshot),
+        )
+        .await?
+    } else {
+        HashMap::new()
+    };
+    let mut tenants = if schedule.has_tenant_windows() {
+        load_tenants(txn, snapshots).await?
+    } else {
+        HashMap::new()
+    };
+
+    Ok(snapshots
+        .iter()
+        .filter(|(machine_id, _)| {
+            schedule.allows(&HostMaintenanceScope {
+                rack_id: racks.remove(*machine_id).map(|rack_id| rack_id.to_string()),
+                tenant_organization_id: tenants.remove(*machine_id),
+            })
+        })
+        .map(|(machine_id, snapshot)| (*machine_id, snapshot.clone()))
+        .collect())
+}
+
+/// Returns whether disruptive work may start on a host now
+pub async fn is_host_allowed(
+    txn: &mut PgConnection,
+    snapshot: &ManagedHostStateSnapshot,
+    now: DateTime<Utc>,
+) -> CarbideResult<bool> {
+    let schedule = load_schedule(txn, now).await?;
+    let snapshots = HashMap::from([(snapshot.host_snapshot.id, snapshot.clone())]);
+    Ok(!allowed_hosts(txn, &schedule, &snapshots).await?.is_empty())
+}
+
+/// Looks up the tenant of the instance on each host
+async fn load_tenants(
+    txn: &mut PgConnection,
+    snapshots: &HashMap<MachineId, ManagedHostStateSnapshot>,
+) -> CarbideResult<HashMap<MachineId, String>> {
+    let machine_ids = snapshots.keys().collect::<Vec<_>>();
+    let instances = db::instance::find_by_machine_ids(txn, &machine_ids).await?;
+
+    Ok(instances
+        .into_iter()
+        .map(|instance| {
+            (
+                instance.machine_id,
+                instance.config.tenant.tenant_organization_id.to_string(),
+            )
+        })
+        .collect())
+}
@@ -0,0 +1,210 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Tracking of the expanded size of the network security groups applied to DPUs
+//!
+//! Rules of network security groups can reference VPCs and VPC prefixes, which are expanded to
+//! the prefixes of the referenced objects when the group is propagated to a DPU. A group which
+//! fits the DPU ACL limits when it is written can outgrow them later on, when the referenced
+//! VPC grows. The `NetworkSecurityGroupLimitMonitor` periodically records the expanded size of
+//! the groups on each DPU. DPUs with groups that exceed the limits get a health alert and the
+//! group reports a propagation error. Such a group is left out of the network configuration of
+//! the affected interfaces until it fits the limits again.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use db::Transaction;
+use db::work_lock_manager::WorkLockManagerHandle;
+use health_report::OverrideMode;
+use model::network_security_group::{
+    NETWORK_SECURITY_GROUP_LIMITS_HEALTH_REPORT_SOURCE, NetworkSecurityGroupDpuUsage,
+    create_limits_exceeded_health_report,
+};
+use sqlx::PgPool;
+use tokio::sync::oneshot;
+
+use crate::cfg::file::{CarbideConfig, NetworkSecurityGroupConfig};
+use crate::{CarbideResult, ethernet_virtualization};
+
+/// `NetworkSecurityGroupLimitMonitor` periodically checks the expanded size of the network
+/// security groups applied to DPUs
+///
+/// Config from [NetworkSecurityGroupConfig]:
+/// * `max_network_security_group_size` the maximum expanded size of a group
+/// * `limit_monitor_enabled` and `limit_monitor_run_interval` control the monitor itself
+pub struct NetworkSecurityGroupLimitMonitor {
+    db_pool: PgPool,
+    config: NetworkSecurityGroupConfig,
+    work_lock_manager_handle: WorkLockManagerHandle,
+}
+
+impl NetworkSecurityGroupLimitMonitor {
+    const ITERATION_WORK_KEY: &'static str =
+        "NetworkSecurityGroupLimitMonitor::run_single_iteration";
+
+    pub fn new(
+        db_pool: PgPool,
+        config: Arc<CarbideConfig>,
+        work_lock_manager_handle: WorkLockManagerHandle,
+    ) -> Self {
+        NetworkSecurityGroupLimitMonitor {
+            db_pool,
+            config: config.network_security_group.clone(),
+            work_lock_manager_handle,
+        }
+    }
+
+    /// Start the NetworkSecurityGroupLimitMonitor and return a [sending channel](tokio::sync::oneshot::Sender) that will stop the NetworkSecurityGroupLimitMonitor when dropped.
+    pub fn start(self) -> eyre::Result<oneshot::Sender<i32>> {
+        let (stop_sender, stop_receiver) = oneshot::channel();
+
+        if self.config.limit_monitor_enabled {
+            tokio::task::Builder::new()
+                .name("network_security_group_limit_monitor")
+                .spawn(async move { self.run(stop_receiver).await })?;
+        } else {
+            tracing::info!("Network security group limit monitor is disabled");
+        }
+
+        Ok(stop_sender)
+    }
+
+    async fn run(&self, mut stop_receiver: oneshot::Receiver<i32>) {
+        loop {
+            if let Err(e) = self.run_single_iteration().await {
+                tracing::warn!("NetworkSecurityGroupLimitMonitor error: {}", e);
+            }
+
+            tokio::select! {
+                _ = tokio::time::sleep(self.config.limit_monitor_run_interval) => {},
+                _ = &mut stop_receiver => {
+                    tracing::info!("NetworkSecurityGroupLimitMonitor stop was requested");
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Records the expanded size of the groups applied to DPUs, raises or clears the
+    /// health alerts of the DPUs and returns the number of DPUs with groups exceeding the limits
+    pub async fn run_single_iteration(&self) -> CarbideResult<usize> {
+        let _lock = match self
+            .work_lock_manager_handle
+            .try_acquire_lock(Self::ITERATION_WORK_KEY.into())
+            .await
+        {
+            Ok(lock) => lock,
+            Err(e) => {
+                tracing::warn!(
+                    "NetworkSecurityGroupLimitMonitor failed to acquire work lock: Another instance of carbide running? {e}"
+                );
+                return Ok(0);
+            }
+        };
+
+        let limit = self.config.max_network_security_group_size as u64;
+        let mut txn = Transaction::begin(&self.db_pool).await?;
+
+        let previous_usage = db::network_security_group::find_dpu_usage(&mut txn).await?;
+        let assignments = db::network_security_group::find_dpu_assignments(&mut txn).await?;
+
+        let nsg_ids: Vec<_> = assignments
+            .iter()
+            .map(|(_, nsg_id)| *nsg_id)
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+
+        // The expanded size only depends on the group, so every group is expanded once.
+        let mut expanded_sizes = HashMap::new();
+        for nsg in db::network_security_group::find_by_ids(&mut txn, &nsg_ids, None, false).await? {
+            let prefixes = ethernet_virtualization::load_rule_net_prefixes(
+                &mut txn,
+                &nsg.rules,
+                Some(&nsg.tenant_organization_id),
+            )
+            .await?;
+            expanded_sizes.insert(
+                nsg.id,
+                (
+                    prefixes.expanded_size(&nsg.rules),
+                    ethernet_virtualization::network_security_group_limit_error(
+                        &nsg.rules, &prefixes, limit,
+                    ),
+                ),
+            );
+        }
+
+        let usage: Vec<_> = assignments
+            .into_iter()
+            .filter_map(|(dpu_machine_id, network_security_group_id)| {
+                let (expanded_rule_count, error) =
+                    expanded_sizes.get(&network_security_group_id)?;
+                Some(NetworkSecurityGroupDpuUsage {
+                    dpu_machine_id,
+                    network_security_group_id,
+                    expanded_rule_count: *expanded_rule_count,
+                    error: error.clone(),
+                })
+            })
+            .collect();
+
+        db::network_security_group::replace_dpu_usage(&mut txn, &usage).await?;
+
+        let mut failed_usage_by_dpu = HashMap::<_, Vec<_>>::new();
+        for u in usage.iter().filter(|u| u.error.is_some()) {
+            failed_usage_by_dpu
+                .entry(u.dpu_machine_id)
+                .or_default()
+                .push(u);
+        }
+
+        for (dpu_machine_id, failed_usage) in failed_usage_by_dpu.iter() {
+            tracing::warn!(%dpu_machine_id, ?failed_usage, "network security groups exceed the DPU limits");
+            db::machine::insert_health_report_override(
+                &mut txn,
+                dpu_machine_id,
+                OverrideMode::Merge,
+                &create_limits_exceeded_health_report(failed_usage),
+                false,
+            )
+            .await?;
+        }
+
+        // Clear the alerts of DPUs which are fine again
+        let recovered_dpus: HashSet<_> = previous_usage
+            .iter()
+            .filter(|u| u.error.is_some() && !failed_usage_by_dpu.contains_key(&u.dpu_machine_id))
+            .map(|u| u.dpu_machine_id)
+            .collect();
+        for dpu_machine_id in recovered_dpus.iter() {
+            tracing::info!(%dpu_machine_id, "network security groups fit the DPU limits again");
+            db::machine::remove_health_report_override(
+                &mut txn,
+                dpu_machine_id,
+                OverrideMode::Merge,
+                NETWORK_SECURITY_GROUP_LIMITS_HEALTH_REPORT_SOURCE,
+            )
+            .await?;
+        }
+
+        txn.commit().await?;
+
+        Ok(failed_usage_by_dpu.len())
+    }
+}
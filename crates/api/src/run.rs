@@ -18,7 +18,6 @@
 use std::sync::Arc;
 
 use eyre::WrapErr;
-use forge_secrets::forge_vault;
 use forge_secrets::forge_vault::VaultConfig;
 use tokio::sync::oneshot;
 use tokio::sync::oneshot::{Receiver, Sender};
@@ -126,7 +125,10 @@ pub async fn run(
         "Start carbide-api",
     );
 
-    let vault_client = forge_vault::create_vault_client(&vault_config, metrics.meter.clone())?;
+    let secret_providers = carbide_config
+        .secrets
+        .create_providers(&vault_config, metrics.meter.clone())
+        .await?;
     let redfish_pool = {
         let rf_pool = libredfish::RedfishClientPool::builder()
             .build()
@@ -175,7 +177,7 @@ pub async fn run(
             (None, None, _) => {} // leave bmc_proxy untouched
         }
         let redfish_pool = RedfishClientPoolImpl::new(
-            vault_client.clone(),
+            secret_providers.credentials.clone(),
             rf_pool,
             carbide_config.site_explorer.bmc_proxy.clone(),
         );
@@ -202,7 +204,8 @@ pub async fn run(
         metrics.meter,
         dynamic_settings,
         redfish_pool,
-        vault_client,
+        secret_providers.credentials,
+        secret_providers.certificates,
         api_stop_rx,
         ready_channel,
     )
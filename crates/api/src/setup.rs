@@ -26,7 +26,7 @@ use db::{Transaction, work_lock_manager};
 use eyre::WrapErr;
 use figment::Figment;
 use figment::providers::{Env, Format, Toml};
-use forge_secrets::ForgeVaultClient;
+use forge_secrets::certificates::CertificateProvider;
 use forge_secrets::credentials::CredentialProvider;
 use futures_util::TryFutureExt;
 use librms::RackManagerClientPool;
@@ -45,6 +45,7 @@ use tracing_log::AsLog as _;
 
 use crate::api::Api;
 use crate::api::metrics::ApiMetricsEmitter;
+use crate::bmc_credential_rotation::BmcCredentialRotator;
 use crate::cfg::file::{CarbideConfig, ListenMode};
 use crate::dpa::handler::{DpaInfo, start_dpa_handler};
 use crate::dynamic_settings::DynamicSettings;
@@ -53,6 +54,8 @@ use crate::firmware_downloader::FirmwareDownloader;
 use crate::handlers::machine_validation::apply_config_on_startup;
 use crate::ib::{self, IBFabricManager};
 use crate::ib_fabric_monitor::IbFabricMonitor;
+use crate::instance::reservation_metrics::CapacityReservationMetricsCollector;
+use crate::instance_webhook_dispatcher::InstanceWebhookDispatcher;
 use crate::ipmitool::{IPMITool, IPMIToolImpl, IPMIToolTestImpl};
 use crate::listener::ApiListenMode;
 use crate::logging::log_limiter::LogLimiter;
@@ -63,6 +66,7 @@ use crate::logging::sqlx_query_tracing::SQLX_STATEMENTS_LOG_LEVEL;
 use crate::machine_update_manager::MachineUpdateManager;
 use crate::measured_boot::metrics_collector::MeasuredBootMetricsCollector;
 use crate::mqtt_state_change_hook::hook::MqttStateChangeHook;
+use crate::network_security_group_monitor::NetworkSecurityGroupLimitMonitor;
 use crate::nvl_partition_monitor::NvlPartitionMonitor;
 use crate::nvlink::{NmxmClientPool, NmxmClientPoolImpl};
 use crate::preingestion_manager::PreingestionManager;
@@ -223,11 +227,12 @@ pub async fn start_api(
     meter: Meter,
     dynamic_settings: DynamicSettings,
     shared_redfish_pool: Arc<dyn RedfishClientPool>,
-    vault_client: Arc<ForgeVaultClient>,
+    credential_provider: Arc<dyn CredentialProvider>,
+    certificate_provider: Arc<dyn CertificateProvider>,
     stop_channel: Receiver<()>,
     ready_channel: Sender<()>,
 ) -> eyre::Result<()> {
-    let ipmi_tool = create_ipmi_tool(vault_client.clone(), &carbide_config);
+    let ipmi_tool = create_ipmi_tool(credential_provider.clone(), &carbide_config);
 
     let db_pool = create_and_connect_postgres_pool(&carbide_config).await?;
 
@@ -305,7 +310,7 @@ pub async fn start_api(
         db::resource_pool::create_common_pools(db_pool.clone(), ib_fabric_ids).await?;
 
     let ib_fabric_manager_impl = ib::create_ib_fabric_manager(
-        vault_client.clone(),
+        credential_provider.clone(),
         ib::IBFabricManagerConfig {
             endpoints: if ib_config.enabled {
                 carbide_config
@@ -361,7 +366,7 @@ pub async fn start_api(
     let bmc_explorer = Arc::new(BmcEndpointExplorer::new(
         shared_redfish_pool.clone(),
         ipmi_tool.clone(),
-        vault_client.clone(),
+        credential_provider.clone(),
         carbide_config
             .site_explorer
             .rotate_switch_nvos_credentials
@@ -372,14 +377,14 @@ pub async fn start_api(
 
     let nmxm_client_pool =
         libnmxm::NmxmClientPool::builder(nvlink_config.allow_insecure).build()?;
-    let nmxm_pool = NmxmClientPoolImpl::new(vault_client.clone(), nmxm_client_pool);
+    let nmxm_pool = NmxmClientPoolImpl::new(credential_provider.clone(), nmxm_client_pool);
 
     let shared_nmxm_pool: Arc<dyn NmxmClientPool> = Arc::new(nmxm_pool);
 
     let api_service = Arc::new(Api {
-        certificate_provider: vault_client.clone(),
+        certificate_provider,
         common_pools,
-        credential_provider: vault_client,
+        credential_provider,
         database_connection: db_pool.clone(),
         dpu_health_log_limiter: LogLimiter::default(),
         dynamic_settings,
@@ -564,7 +569,11 @@ pub async fn initialize_and_start_controllers(
         tracing::warn!("Failed to update ASN for DPUs: {e}");
     }
 
-    let downloader = FirmwareDownloader::new();
+    let downloader = FirmwareDownloader::with_config(&carbide_config.firmware_global.cache, &meter);
+    let _firmware_cache_gc_stop_handle = downloader.start_garbage_collection(
+        db_pool.clone(),
+        Arc::new(carbide_config.get_firmware_config()),
+    )?;
     let upload_limiter = Arc::new(Semaphore::new(carbide_config.firmware_global.max_uploads));
 
     let mut dpa_info: Option<Arc<DpaInfo>> = None;
@@ -871,6 +880,31 @@ pub async fn initialize_and_start_controllers(
     );
     let _site_explorer_stop_handle = site_explorer.start()?;
 
+    let bmc_credential_rotator = BmcCredentialRotator::new(
+        db_pool.clone(),
+        carbide_config.clone(),
+        meter.clone(),
+        bmc_explorer.clone(),
+        work_lock_manager_handle.clone(),
+    );
+    let _bmc_credential_rotator_stop_handle = bmc_credential_rotator.start()?;
+
+    let network_security_group_limit_monitor = NetworkSecurityGroupLimitMonitor::new(
+        db_pool.clone(),
+        carbide_config.clone(),
+        work_lock_manager_handle.clone(),
+    );
+    let _network_security_group_limit_monitor_stop_handle =
+        network_security_group_limit_monitor.start()?;
+
+    let instance_webhook_dispatcher = InstanceWebhookDispatcher::new(
+        db_pool.clone(),
+        carbide_config.clone(),
+        api_service.credential_provider.clone(),
+        work_lock_manager_handle.clone(),
+    )?;
+    let _instance_webhook_dispatcher_stop_handle = instance_webhook_dispatcher.start()?;
+
     let machine_update_manager = MachineUpdateManager::new(
         db_pool.clone(),
         carbide_config.clone(),
@@ -898,6 +932,14 @@ pub async fn initialize_and_start_controllers(
     );
     let _measured_boot_collector_handle = measured_boot_collector.start()?;
 
+    let capacity_reservation_metrics_collector = CapacityReservationMetricsCollector::new(
+        db_pool.clone(),
+        carbide_config.capacity_reservation_metrics.clone(),
+        meter.clone(),
+    );
+    let _capacity_reservation_metrics_collector_handle =
+        capacity_reservation_metrics_collector.start()?;
+
     // we need to create ek_cert_status entries for all existing machines
     attestation::backfill_ek_cert_status_for_existing_machines(db_pool).await?;
 
@@ -153,6 +153,130 @@ impl BmcEndpointExplorer {
         })
     }
 
+    // Rotate the root password of a BMC whose credentials are already stored in vault:
+    // (1) use Redfish to change the password from the stored one to a newly generated one
+    // (2) log in using the new password
+    // (3) update the BMC specific root password path in vault
+    // If any of these steps fail, the stored password is restored on the BMC, so that the
+    // BMC and vault don't diverge.
+    pub async fn rotate_bmc_root_password(
+        &self,
+        bmc_ip_address: SocketAddr,
+        bmc_mac_address: MacAddress,
+    ) -> Result<(), EndpointExplorationError> {
+        let current_bmc_credentials = self.get_bmc_root_credentials(bmc_mac_address).await?;
+        let vendor = self.probe_redfish_endpoint(bmc_ip_address).await?;
+
+        tracing::info!(%bmc_ip_address, %bmc_mac_address, %vendor, "rotating the BMC root password");
+        let new_bmc_credentials = match current_bmc_credentials.clone() {
+            Credentials::UsernamePassword { username, .. } => Credentials::UsernamePassword {
+                username,
+                password: Credentials::generate_password(),
+            },
+        };
+
+        let result = self
+            .apply_rotated_bmc_root_credentials(
+                bmc_ip_address,
+                bmc_mac_address,
+                vendor,
+                &current_bmc_credentials,
+                &new_bmc_credentials,
+            )
+            .await;
+        if let Err(e) = &result {
+            tracing::warn!(%bmc_ip_address, %bmc_mac_address, %vendor, error = %e, "rotating the BMC root password failed, restoring the previous password");
+            self.restore_bmc_root_password(
+                bmc_ip_address,
+                bmc_mac_address,
+                vendor,
+                current_bmc_credentials,
+                new_bmc_credentials,
+            )
+            .await;
+        }
+
+        result
+    }
+
+    async fn apply_rotated_bmc_root_credentials(
+        &self,
+        bmc_ip_address: SocketAddr,
+        bmc_mac_address: MacAddress,
+        vendor: RedfishVendor,
+        current_bmc_credentials: &Credentials,
+        new_bmc_credentials: &Credentials,
+    ) -> Result<(), EndpointExplorationError> {
+        let Credentials::UsernamePassword {
+            password: new_password,
+            ..
+        } = new_bmc_credentials;
+
+        self.redfish_client
+            .set_bmc_root_password(
+                bmc_ip_address,
+                vendor,
+                current_bmc_credentials.clone(),
+                new_password.clone(),
+            )
+            .await?;
+        self.redfish_client
+            .verify_credentials(bmc_ip_address, new_bmc_credentials.clone())
+            .await?;
+        self.set_bmc_root_credentials(bmc_mac_address, new_bmc_credentials)
+            .await
+    }
+
+    // Undo a failed rotation. Depending on where the rotation failed, the BMC either still
+    // uses the previous password or already uses the new one. In the latter case the previous
+    // password is set again. If that isn't possible either, the new password is stored in vault
+    // instead, since losing it would lock us out of the BMC.
+    async fn restore_bmc_root_password(
+        &self,
+        bmc_ip_address: SocketAddr,
+        bmc_mac_address: MacAddress,
+        vendor: RedfishVendor,
+        previous_bmc_credentials: Credentials,
+        new_bmc_credentials: Credentials,
+    ) {
+        if self
+            .redfish_client
+            .verify_credentials(bmc_ip_address, previous_bmc_credentials.clone())
+            .await
+            .is_ok()
+        {
+            return;
+        }
+
+        let Credentials::UsernamePassword {
+            password: previous_password,
+            ..
+        } = previous_bmc_credentials;
+        match self
+            .redfish_client
+            .set_bmc_root_password(
+                bmc_ip_address,
+                vendor,
+                new_bmc_credentials.clone(),
+                previous_password,
+            )
+            .await
+        {
+            Ok(()) => {
+                tracing::info!(%bmc_ip_address, %bmc_mac_address, "restored the previous BMC root password");
+            }
+            Err(e) => {
+                tracing::error!(%bmc_ip_address, %bmc_mac_address, error = %e, "failed to restore the previous BMC root password, keeping the new one");
+                if let Err(e) = self
+                    .set_bmc_root_credentials(bmc_mac_address, &new_bmc_credentials)
+                    .await
+                {
+                    tracing::error!(%bmc_ip_address, %bmc_mac_address, error = %e, "failed to store the new BMC root password");
+                }
+            }
+        }
+    }
+
     pub async fn generate_exploration_report(
         &self,
         bmc_ip_address: SocketAddr,
@@ -1131,4 +1255,13 @@ impl EndpointExplorer for BmcEndpointExplorer {
             }
         }
     }
+
+    async fn rotate_bmc_root_credentials(
+        &self,
+        bmc_ip_address: SocketAddr,
+        interface: &MachineInterfaceSnapshot,
+    ) -> Result<(), EndpointExplorationError> {
+        self.rotate_bmc_root_password(bmc_ip_address, interface.mac_address)
+            .await
+    }
 }
@@ -166,4 +166,12 @@ pub trait EndpointExplorer: Send + Sync + 'static {
         interface: &MachineInterfaceSnapshot,
         username: &str,
     ) -> Result<(), EndpointExplorationError>;
+
+    /// Rotate the root password of an ingested BMC and store the new credentials.
+    /// The previous password is restored if the new one can not be verified or stored.
+    async fn rotate_bmc_root_credentials(
+        &self,
+        address: SocketAddr,
+        interface: &MachineInterfaceSnapshot,
+    ) -> Result<(), EndpointExplorationError>;
 }
@@ -526,6 +526,21 @@ impl RedfishClient {
         Ok(())
     }
 
+    /// Logs into the BMC with the given credentials and issues an authenticated request
+    pub async fn verify_credentials(
+        &self,
+        bmc_ip_address: SocketAddr,
+        credentials: Credentials,
+    ) -> Result<(), EndpointExplorationError> {
+        let client = self
+            .create_authenticated_redfish_client(bmc_ip_address, credentials)
+            .await
+            .map_err(map_redfish_client_creation_error)?;
+
+        client.get_managers().await.map_err(map_redfish_error)?;
+        Ok(())
+    }
+
     pub async fn delete_bmc_user(
         &self,
         bmc_ip_address: SocketAddr,
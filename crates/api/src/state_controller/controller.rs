@@ -63,6 +63,48 @@ impl<'r> FromRow<'r, PgRow> for ControllerIteration {
     }
 }
 
+/// Determines in which order queued objects are picked up by state processors
+///
+/// Objects with a higher priority are always dequeued before objects with a
+/// lower priority. Within the same priority, the oldest objects are dequeued first.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum QueuePriority {
+    /// Used for objects which are enqueued by the periodic enqueuer
+    #[default]
+    Normal,
+    /// Used for objects where state handling was explicitly requested,
+    /// e.g. because an API call modified the object
+    High,
+}
+
+impl QueuePriority {
+    /// Returns the value that is used to store the priority in the database
+    pub fn db_value(self) -> i16 {
+        match self {
+            QueuePriority::Normal => 0,
+            QueuePriority::High => 1,
+        }
+    }
+
+    /// Converts a value stored in the database into a priority.
+    /// Unknown values are treated as the closest known priority.
+    pub fn from_db_value(value: i16) -> Self {
+        if value >= QueuePriority::High.db_value() {
+            QueuePriority::High
+        } else {
+            QueuePriority::Normal
+        }
+    }
+
+    /// Returns the name that is used for the priority in metrics and logs
+    pub fn as_str(self) -> &'static str {
+        match self {
+            QueuePriority::Normal => "normal",
+            QueuePriority::High => "high",
+        }
+    }
+}
+
 /// Metadata for a single state controller iteration
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct QueuedObject {
@@ -71,15 +113,38 @@ pub struct QueuedObject {
     /// Identifies the processor which is executing the state handler
     /// The value of this field will be NULL in case the object is not yet processed
     pub processed_by: Option<String>,
+    /// The priority with which the object had been enqueued
+    pub priority: QueuePriority,
 }
 
 impl<'r> FromRow<'r, PgRow> for QueuedObject {
     fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
         let object_id = row.try_get("object_id")?;
         let processed_by: Option<String> = row.try_get("processed_by")?;
+        let priority: i16 = row.try_get("priority")?;
         Ok(QueuedObject {
             object_id,
             processed_by,
+            priority: QueuePriority::from_db_value(priority),
+        })
+    }
+}
+
+/// An object which has been acquired by a state processor for state handling
+#[derive(Debug, Clone)]
+pub struct AcquiredObject {
+    pub object: QueuedObject,
+    /// The time the object spent in the queue before it was acquired
+    pub queue_latency: std::time::Duration,
+}
+
+impl<'r> FromRow<'r, PgRow> for AcquiredObject {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let object = QueuedObject::from_row(row)?;
+        let queue_latency_us: i64 = row.try_get("queue_latency_us")?;
+        Ok(AcquiredObject {
+            object,
+            queue_latency: std::time::Duration::from_micros(queue_latency_us.max(0) as u64),
         })
     }
 }
@@ -15,7 +15,7 @@
  * limitations under the License.
  */
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use db::work_lock_manager::WorkLockManagerHandle;
@@ -182,6 +182,7 @@ impl<IO: StateControllerIO> Builder<IO> {
             metric_emitter: period_enqueuer_metric_emitter,
             iteration_config: self.iteration_config,
             io: self.io.clone().unwrap_or_default(),
+            last_outcome_history_prune: None,
         };
 
         let (task_sender, task_receiver) = tokio::sync::mpsc::unbounded_channel();
@@ -208,9 +209,10 @@ impl<IO: StateControllerIO> Builder<IO> {
             metric_holder,
             state_change_emitter: self.state_change_emitter,
             published_metrics_iteration_id: None,
-            in_flight: HashSet::new(),
+            latest_iteration_id: None,
+            in_flight: HashMap::new(),
             completed_objects: HashSet::new(),
-            requeue_objects: HashSet::new(),
+            requeue_objects: HashMap::new(),
             task_sender,
             task_receiver,
             data_since_iteration_start: Default::default(),
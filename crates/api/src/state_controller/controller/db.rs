@@ -25,7 +25,8 @@ use sqlx::{PgConnection, PgPool};
 
 use crate::api::TransactionVending;
 use crate::state_controller::controller::{
-    ControllerIteration, ControllerIterationId, LockedControllerIteration, QueuedObject,
+    AcquiredObject, ControllerIteration, ControllerIterationId, LockedControllerIteration,
+    QueuePriority, QueuedObject,
 };
 
 /// Inserts a new entry into the iteration table
@@ -115,6 +116,21 @@ pub async fn queue_objects(
     txn: &mut PgConnection,
     table_id: &str,
     queued_objects: &[String],
+) -> Result<usize, DatabaseError> {
+    queue_objects_with_priority(txn, table_id, queued_objects, QueuePriority::Normal).await
+}
+
+/// Enqueues object IDs for processing with a certain priority
+///
+/// Objects that are already enqueued keep their current entry. If the entry has a
+/// lower priority and is not yet processed, its priority will be raised to `priority`.
+/// The returned count includes objects that had been freshly enqueued as well as
+/// objects where the priority had been raised.
+pub async fn queue_objects_with_priority(
+    txn: &mut PgConnection,
+    table_id: &str,
+    queued_objects: &[String],
+    priority: QueuePriority,
 ) -> Result<usize, DatabaseError> {
     // Object IDs need to be sorted in order to avoid a deadlock on concurrent calls to this
     // method.
@@ -132,6 +148,8 @@ pub async fn queue_objects(
     // of sort order across all callers.
     let mut sorted = queued_objects.to_vec();
     sorted.sort();
+    // Postgres rejects upserts that touch the same row twice within a statement
+    sorted.dedup();
     // Make sure we are not running into the BIND_LIMIT
     // The theoretical limit would be BIND_LIMIT
     // However shorter transactions are ok here - we still queue 1k objects
@@ -143,13 +161,27 @@ pub async fn queue_objects(
     for queued_objects in sorted.chunks(OBJECTS_PER_QUERY) {
         let mut builder = sqlx::QueryBuilder::new("INSERT INTO ");
         builder.push(table_id);
-        builder.push("(object_id)");
+        builder.push("(object_id, priority)");
 
         builder.push_values(queued_objects, |mut b, object_id| {
             b.push_bind(object_id);
+            b.push_bind(priority.db_value());
         });
 
-        builder.push("ON CONFLICT (object_id) DO NOTHING");
+        match priority {
+            QueuePriority::Normal => {
+                builder.push("ON CONFLICT (object_id) DO NOTHING");
+            }
+            QueuePriority::High => {
+                builder.push(
+                    "ON CONFLICT (object_id) DO UPDATE SET priority=EXCLUDED.priority WHERE ",
+                );
+                builder.push(table_id);
+                builder.push(".priority < EXCLUDED.priority AND ");
+                builder.push(table_id);
+                builder.push(".processed_by IS NULL");
+            }
+        }
         let query = builder.build();
 
         let result = query
@@ -182,22 +214,28 @@ pub async fn fetch_queued_objects(
 /// current processor.
 /// The objects will be marked as `processed_by` with the given ID - which will avoid
 /// other processors to pick up the objects.
+///
+/// Objects with a higher priority are acquired first. The returned queue latency
+/// describes how long the object was waiting since it was enqueued - or since
+/// it was acquired by a different processor that didn't finish handling it.
 pub async fn acquire_queued_objects(
     txn: &mut PgConnection,
     table_id: &str,
     count: u32, // u32 to avoid u64 numbers getting passed that are not valid in postgres
     processor_id: &str,
     max_outdated: std::time::Duration,
-) -> Result<Vec<QueuedObject>, DatabaseError> {
-    // Grab the oldest ones first
+) -> Result<Vec<AcquiredObject>, DatabaseError> {
+    // Grab the highest priority and oldest ones first
     let query = format!(
         "WITH dequeued_ids AS (
-            SELECT object_id FROM {table_id} WHERE (processed_by IS NULL OR processing_started_at + $1::interval < now())
-            ORDER BY processing_started_at ASC
+            SELECT object_id, processing_started_at AS queued_at FROM {table_id} WHERE (processed_by IS NULL OR processing_started_at + $1::interval < now())
+            ORDER BY priority DESC, processing_started_at ASC
             FOR UPDATE SKIP LOCKED
             LIMIT {count}
         )
-        UPDATE {table_id} SET processed_by=$2, processing_started_at=now() WHERE object_id in (SELECT object_id FROM dequeued_ids) RETURNING *"
+        UPDATE {table_id} SET processed_by=$2, processing_started_at=now()
+        FROM dequeued_ids WHERE {table_id}.object_id = dequeued_ids.object_id
+        RETURNING {table_id}.*, (EXTRACT(EPOCH FROM (now() - dequeued_ids.queued_at)) * 1000000)::BIGINT AS queue_latency_us"
     );
 
     let result = sqlx::query_as(&query)
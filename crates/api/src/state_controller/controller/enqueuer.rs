@@ -17,7 +17,7 @@
 
 use ::db::DatabaseError;
 
-use super::db;
+use super::{QueuePriority, db};
 use crate::state_controller::io::StateControllerIO;
 
 /// Allows to request state handling for objects of a certain type
@@ -36,16 +36,36 @@ impl<IO: StateControllerIO> Enqueuer<IO> {
     }
 
     /// Requests state handling for the given object
+    ///
+    /// The object is enqueued with [`QueuePriority::High`], and will therefore be
+    /// handled ahead of objects that have been enqueued by the periodic enqueuer.
     pub async fn enqueue_object(&self, object_id: &IO::ObjectId) -> Result<bool, DatabaseError> {
         let mut conn = self.pool.acquire().await.map_err(DatabaseError::acquire)?;
 
-        let num_enqueued = db::queue_objects(
+        let num_enqueued = db::queue_objects_with_priority(
             &mut conn,
             IO::DB_QUEUED_OBJECTS_TABLE_NAME,
             &[object_id.to_string()],
+            QueuePriority::High,
         )
         .await?;
 
         Ok(num_enqueued == 1)
     }
+
+    /// Wakes up the state handler for the given object, so that it doesn't need
+    /// to wait for the next periodic iteration
+    ///
+    /// Failures are only logged, since the periodic enqueuer will pick the
+    /// object up anyway.
+    pub async fn wake_up(&self, object_id: &IO::ObjectId) {
+        if let Err(err) = self.enqueue_object(object_id).await {
+            tracing::warn!(
+                %err,
+                %object_id,
+                controller = IO::LOG_SPAN_CONTROLLER_NAME,
+                "Failed to wake up state handler"
+            );
+        }
+    }
 }
@@ -40,8 +40,13 @@ pub(super) struct PeriodicEnqueuer<IO: StateControllerIO> {
     pub(super) metric_emitter: Option<EnqueuerMetricsEmitter>,
     pub(super) stop_token: CancellationToken,
     pub(super) iteration_config: IterationConfig,
+    /// When old state handler outcomes had last been removed
+    pub(super) last_outcome_history_prune: Option<Instant>,
 }
 
+/// How often old state handler outcomes are removed
+const OUTCOME_HISTORY_PRUNE_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
 pub(super) struct SingleIterationResult {
     /// Whether the iteration was skipped due to not being able to obtain the lock.
     /// This will be `true` if the lock could not be obtained.
@@ -191,10 +196,43 @@ impl<IO: StateControllerIO> PeriodicEnqueuer<IO> {
         iteration_metrics.iteration_id = Some(locked_controller_iteration.iteration_data.id);
 
         self.enqueue_objects(iteration_metrics).await?;
+        self.prune_outcome_history_if_due().await;
 
         Ok(locked_controller_iteration.iteration_data)
     }
 
+    /// Removes old state handler outcomes once per [OUTCOME_HISTORY_PRUNE_INTERVAL].
+    /// Failures are only logged, since the history is informational.
+    async fn prune_outcome_history_if_due(&mut self) {
+        if self
+            .last_outcome_history_prune
+            .is_some_and(|last| last.elapsed() < OUTCOME_HISTORY_PRUNE_INTERVAL)
+        {
+            return;
+        }
+        self.last_outcome_history_prune = Some(Instant::now());
+
+        let result = async {
+            let mut txn = self.pool.begin().await?;
+            let deleted = self.io.prune_outcome_history(&mut txn).await?;
+            txn.commit().await?;
+            Ok::<_, IterationError>(deleted)
+        }
+        .await;
+        match result {
+            Ok(deleted) => tracing::debug!(
+                controller = IO::LOG_SPAN_CONTROLLER_NAME,
+                deleted,
+                "Pruned state handler outcome history"
+            ),
+            Err(e) => tracing::warn!(
+                controller = IO::LOG_SPAN_CONTROLLER_NAME,
+                err = ?e,
+                "Failed to prune state handler outcome history"
+            ),
+        }
+    }
+
     /// Identifies all active objects that the PeriodicEnqueuer manages
     /// and enqueues them for state handler execution
     async fn enqueue_objects(
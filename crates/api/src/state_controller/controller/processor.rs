@@ -29,7 +29,7 @@ use tracing::Instrument;
 use super::db;
 use crate::logging::sqlx_query_tracing::{self, SqlxQueryDataAggregation};
 use crate::state_controller::config::IterationConfig;
-use crate::state_controller::controller::ControllerIterationId;
+use crate::state_controller::controller::{ControllerIterationId, QueuePriority};
 use crate::state_controller::db_write_batch::DbWriteBatch;
 use crate::state_controller::io::StateControllerIO;
 use crate::state_controller::metrics::{
@@ -62,16 +62,21 @@ pub(super) struct StateProcessor<IO: StateControllerIO> {
     pub(super) object_metrics: HashMap<IO::ObjectId, CollectedMetrics<IO>>,
     /// The iteration ID for which metrics have been passed towards `metric_holder`
     pub(super) published_metrics_iteration_id: Option<ControllerIterationId>,
+    /// The latest iteration ID observed by the processor. Recorded alongside
+    /// state handler outcomes for the objects dispatched afterwards.
+    pub(super) latest_iteration_id: Option<ControllerIterationId>,
     pub(super) stop_token: CancellationToken,
     pub(super) iteration_config: IterationConfig,
-    /// IDs of objects where the task handler is currently executed
-    pub(super) in_flight: HashSet<IO::ObjectId>,
+    /// IDs of objects where the task handler is currently executed,
+    /// together with the priority they had been dequeued with
+    pub(super) in_flight: HashMap<IO::ObjectId, QueuePriority>,
     /// Objects where the state handling task was finished but where the entry
     /// in the database has not yet been deleted.
     pub(super) completed_objects: HashSet<IO::ObjectId>,
     /// Objects for which another object handling task should be queued since
-    /// the state handler returned `Transition`
-    pub(super) requeue_objects: HashSet<IO::ObjectId>,
+    /// the state handler returned `Transition`. The objects are requeued with
+    /// the priority they had been dequeued with.
+    pub(super) requeue_objects: HashMap<IO::ObjectId, QueuePriority>,
     pub(super) task_sender: tokio::sync::mpsc::UnboundedSender<ObjectHandlingTaskResult<IO>>,
     pub(super) task_receiver: tokio::sync::mpsc::UnboundedReceiver<ObjectHandlingTaskResult<IO>>,
     pub(super) data_since_iteration_start: DataSinceStartOfIteration,
@@ -140,7 +145,6 @@ pub(super) struct StatsSinceLastLog {
 #[derive(Debug, Default, Clone)]
 struct QueueStats {
     /// The ID of the latest iteration that had been started
-    #[allow(dead_code)]
     latest_iteration: Option<ControllerIterationId>,
     /// The ID of the last iteration (before the most recent one)
     previous_iteration: Option<ControllerIterationId>,
@@ -245,6 +249,7 @@ impl<IO: StateControllerIO> StateProcessor<IO> {
         self.requeue_transitioned_objects().await?;
 
         let queue_stats = self.gather_queue_stats().await?;
+        self.latest_iteration_id = queue_stats.latest_iteration;
         self.emit_metric_if_iteration_changed(&queue_stats);
 
         self.emit_periodic_log_if_necessary();
@@ -445,28 +450,39 @@ impl<IO: StateControllerIO> StateProcessor<IO> {
             Vec::new()
         };
 
-        let objects: Vec<IO::ObjectId> = objects
+        if let Some(emitter) = &self.metric_emitter {
+            for acquired in &objects {
+                emitter.queue_latency.record(
+                    1000.0 * acquired.queue_latency.as_secs_f64(),
+                    &[KeyValue::new("priority", acquired.object.priority.as_str())],
+                );
+            }
+        }
+
+        let objects: Vec<(IO::ObjectId, QueuePriority)> = objects
             .into_iter()
-            .filter_map(|object| match IO::ObjectId::from_str(&object.object_id) {
-                Ok(id) => Some(id),
-                Err(_) => {
-                    tracing::error!(
-                        controller = IO::LOG_SPAN_CONTROLLER_NAME,
-                        "Can not convert queued object ID \"{}\" to IO::ObjectID format",
-                        object.object_id
-                    );
-                    None
-                }
-            })
+            .filter_map(
+                |acquired| match IO::ObjectId::from_str(&acquired.object.object_id) {
+                    Ok(id) => Some((id, acquired.object.priority)),
+                    Err(_) => {
+                        tracing::error!(
+                            controller = IO::LOG_SPAN_CONTROLLER_NAME,
+                            "Can not convert queued object ID \"{}\" to IO::ObjectID format",
+                            acquired.object.object_id
+                        );
+                        None
+                    }
+                },
+            )
             .collect();
 
         let num_dispatched_tasks = objects.len();
         self.stats_since_last_log.num_dispatched_tasks += num_dispatched_tasks;
 
         // Send off the new objects for processing
-        for object_id in objects {
+        for (object_id, priority) in objects {
             self.dispatch_object_handling_task(object_id.clone());
-            self.in_flight.insert(object_id);
+            self.in_flight.insert(object_id, priority);
         }
 
         if let Some(emitter) = &self.metric_emitter
@@ -491,6 +507,7 @@ impl<IO: StateControllerIO> StateProcessor<IO> {
         let metrics_emitter = self.metric_holder.emitter.clone();
         let state_change_emitter = self.state_change_emitter.clone();
         let result_sender = self.task_sender.clone();
+        let iteration_id = self.latest_iteration_id;
 
         let _join_handle = tokio::task::Builder::new()
             .name(&format!("state_processor {object_id}"))
@@ -505,6 +522,7 @@ impl<IO: StateControllerIO> StateProcessor<IO> {
                         max_object_handling_time,
                         metrics_emitter,
                         state_change_emitter,
+                        iteration_id,
                     )
                     .await;
 
@@ -559,14 +577,24 @@ impl<IO: StateControllerIO> StateProcessor<IO> {
             return Ok(());
         }
 
-        let queue_objects: Vec<String> = self
-            .requeue_objects
-            .iter()
-            .map(|id| id.to_string())
-            .collect();
+        let mut queue_objects: HashMap<QueuePriority, Vec<String>> = HashMap::new();
+        for (object_id, priority) in self.requeue_objects.iter() {
+            queue_objects
+                .entry(*priority)
+                .or_default()
+                .push(object_id.to_string());
+        }
         let mut txn = self.pool.begin().await?;
-        let num_requeued =
-            db::queue_objects(&mut txn, IO::DB_QUEUED_OBJECTS_TABLE_NAME, &queue_objects).await?;
+        let mut num_requeued = 0;
+        for (priority, object_ids) in queue_objects {
+            num_requeued += db::queue_objects_with_priority(
+                &mut txn,
+                IO::DB_QUEUED_OBJECTS_TABLE_NAME,
+                &object_ids,
+                priority,
+            )
+            .await?;
+        }
         txn.commit().await?;
 
         self.stats_since_last_log.num_requeued_objects += num_requeued;
@@ -586,10 +614,15 @@ impl<IO: StateControllerIO> StateProcessor<IO> {
         // and remove them later in order to not forget about these in case there
         // is a transient database error
         self.completed_objects.insert(task_result.object_id.clone());
+        let priority = self
+            .in_flight
+            .remove(&task_result.object_id)
+            .unwrap_or_default();
         // If the state handler returned `Transition`, then run the handler again
         // as soon as possible.
         if allow_requeue && task_result.metrics.common.next_state.is_some() {
-            self.requeue_objects.insert(task_result.object_id.clone());
+            self.requeue_objects
+                .insert(task_result.object_id.clone(), priority);
         }
 
         self.stats_since_last_log.num_completed_tasks += 1;
@@ -597,7 +630,6 @@ impl<IO: StateControllerIO> StateProcessor<IO> {
             self.stats_since_last_log.num_errored_tasks += 1;
         }
 
-        self.in_flight.remove(&task_result.object_id);
         self.object_metrics.insert(
             task_result.object_id.clone(),
             CollectedMetrics {
@@ -620,6 +652,38 @@ pub(super) enum IterationError {
     StateHandlerError(#[from] StateHandlerError),
 }
 
+/// Appends the outcome to the outcome history of the object. The history is
+/// informational, so a failure is only logged. It is written in a savepoint,
+/// so that a failure does not abort the transaction which persists the outcome.
+async fn persist_outcome_history<IO: StateControllerIO>(
+    io: &IO,
+    txn: &mut sqlx::PgConnection,
+    object_id: &IO::ObjectId,
+    state: &IO::ControllerState,
+    outcome: &PersistentStateHandlerOutcome,
+    iteration_id: Option<i64>,
+    handler_latency: Duration,
+) {
+    let result = async {
+        let mut savepoint = ::db::Transaction::begin_inner(txn).await?;
+        io.persist_outcome_history(
+            savepoint.as_pgconn(),
+            object_id,
+            state,
+            outcome,
+            iteration_id,
+            handler_latency,
+        )
+        .await?;
+        savepoint.commit().await
+    }
+    .await;
+
+    if let Err(e) = result {
+        tracing::warn!(%object_id, error = %e, "Failed to record state handler outcome history");
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 async fn process_object<IO: StateControllerIO>(
     object_id: IO::ObjectId,
@@ -637,6 +701,7 @@ async fn process_object<IO: StateControllerIO>(
     max_object_handling_time: std::time::Duration,
     metrics_emitter: Option<Arc<StateProcessorMetricEmitter<IO>>>,
     state_change_emitter: Arc<StateChangeEmitter<IO::ObjectId, IO::ControllerState>>,
+    iteration_id: Option<ControllerIterationId>,
 ) -> ObjectHandlerMetrics<IO> {
     let mut metrics = ObjectHandlerMetrics::<IO>::default();
 
@@ -743,6 +808,16 @@ async fn process_object<IO: StateControllerIO>(
             if !matches!(handler_outcome, Ok(StateHandlerOutcome::Deleted { .. })) {
                 let db_outcome =
                     PersistentStateHandlerOutcome::from_result(handler_outcome.as_ref());
+                persist_outcome_history(
+                    &*io,
+                    &mut txn,
+                    &object_id,
+                    &controller_state.value,
+                    &db_outcome,
+                    iteration_id.map(|id| id.0),
+                    start.elapsed(),
+                )
+                .await;
                 io.persist_outcome(&mut txn, &object_id, db_outcome).await?;
             }
 
@@ -754,6 +829,16 @@ async fn process_object<IO: StateControllerIO>(
             let _ = txn.rollback().await;
             let mut txn = pool.begin().await?;
             let db_outcome = PersistentStateHandlerOutcome::from_result(handler_outcome.as_ref());
+            persist_outcome_history(
+                &*io,
+                &mut txn,
+                &object_id,
+                &controller_state.value,
+                &db_outcome,
+                iteration_id.map(|id| id.0),
+                start.elapsed(),
+            )
+            .await;
             io.persist_outcome(&mut txn, &object_id, db_outcome).await?;
             txn.commit()
                 .await
@@ -812,6 +897,7 @@ pub(super) struct ProcessorMetricsEmitter {
     dispatched_tasks_counter: Counter<u64>,
     completed_tasks_counter: Counter<u64>,
     requeued_tasks_counter: Counter<u64>,
+    queue_latency: Histogram<f64>,
     db: sqlx_query_tracing::DatabaseMetricEmitters,
 }
 
@@ -848,12 +934,21 @@ impl ProcessorMetricsEmitter {
             ))
             .build();
 
+        let queue_latency = meter
+            .f64_histogram(format!("{object_type}_object_tasks_queue_latency"))
+            .with_description(format!(
+                "The time object handling tasks for objects of type {object_type} spent in the queue before being dispatched"
+            ))
+            .with_unit("ms")
+            .build();
+
         Self {
             controller_iteration_latency,
             db,
             dispatched_tasks_counter,
             completed_tasks_counter,
             requeued_tasks_counter,
+            queue_latency,
         }
     }
 
@@ -104,6 +104,32 @@ pub trait StateControllerIO: Send + Sync + std::fmt::Debug + 'static + Default {
         outcome: PersistentStateHandlerOutcome,
     ) -> Result<(), DatabaseError>;
 
+    /// Appends the result of a state handler invocation to the outcome history
+    /// of the object.
+    ///
+    /// The default implementation does not record any history.
+    async fn persist_outcome_history(
+        &self,
+        _txn: &mut PgConnection,
+        _object_id: &Self::ObjectId,
+        _state: &Self::ControllerState,
+        _outcome: &PersistentStateHandlerOutcome,
+        _iteration_id: Option<i64>,
+        _handler_latency: std::time::Duration,
+    ) -> Result<(), DatabaseError> {
+        Ok(())
+    }
+
+    /// Removes old entries from the outcome history of all objects.
+    /// This is called periodically by the state controller rather than
+    /// on every recorded outcome.
+    ///
+    /// Returns the number of removed entries. The default implementation does
+    /// not record any history and therefore has nothing to remove.
+    async fn prune_outcome_history(&self, _txn: &mut PgConnection) -> Result<u64, DatabaseError> {
+        Ok(0)
+    }
+
     /// Returns the names that should be used in metrics for a given object state
     /// The first returned value is the value that will be used for the main `state`
     /// attribute on each metric. The 2nd value - if not empty - will be used for
@@ -60,6 +60,7 @@ use model::instance::status::extension_service::{
     self, ExtensionServiceDeploymentStatus, ExtensionServicesReadiness,
     InstanceExtensionServicesStatus,
 };
+use model::instance_webhook::{InstanceLifecycleEvent, InstanceLifecycleEventType};
 use model::machine::LockdownMode::{self, Enable};
 use model::machine::infiniband::{IbConfigNotSyncedReason, ib_config_synced};
 use model::machine::nvlink::nvlink_config_synced;
@@ -76,6 +77,7 @@ use model::machine::{
     StateMachineArea, UefiSetupInfo, UefiSetupState, ValidationState,
     dpf_based_dpu_provisioning_possible, get_display_ids,
 };
+use model::machine_erasure_report::MachineErasureReport;
 use model::power_manager::PowerHandlingOutcome;
 use model::resource_pool::common::CommonPools;
 use model::site_explorer::ExploredEndpoint;
@@ -92,6 +94,7 @@ use crate::cfg::file::{
     PowerManagerOptions, TimePeriod,
 };
 use crate::firmware_downloader::FirmwareDownloader;
+use crate::maintenance_window;
 use crate::redfish::{
     self, host_power_control, host_power_control_with_location, set_host_uefi_password,
 };
@@ -1039,7 +1042,9 @@ impl MachineStateHandler {
                 }
 
                 // Check if DPU reprovisioning is requested
-                if dpu_reprovisioning_needed(&mh_snapshot.dpu_snapshots) {
+                if dpu_reprovisioning_needed(&mh_snapshot.dpu_snapshots)
+                    && dpu_reprovisioning_allowed(mh_snapshot, ctx).await?
+                {
                     let mut dpus_for_reprov = vec![];
                     for dpu_snapshot in &mh_snapshot.dpu_snapshots {
                         if dpu_snapshot.reprovision_requested.is_some() {
@@ -1518,7 +1523,10 @@ impl MachineStateHandler {
                             Ok(StateHandlerOutcome::do_nothing())
                         }
                     }
-                    FailureCause::NVMECleanFailed { .. } if machine_id.machine_type().is_host() => {
+                    FailureCause::NVMECleanFailed { .. }
+                    | FailureCause::SataSasCleanFailed { .. }
+                        if machine_id.machine_type().is_host() =>
+                    {
                         if cleanedup_after_state_transition(
                             mh_snapshot.host_snapshot.state.version,
                             mh_snapshot.host_snapshot.last_cleanup_time,
@@ -1861,6 +1869,25 @@ fn dpu_reprovisioning_needed(dpu_snapshots: &[Machine]) -> bool {
         .any(|x| x.reprovision_requested.is_some())
 }
 
+/// Reprovisioning disrupts the host, so requested reprovisioning only starts while the
+/// maintenance windows of the host allow it
+async fn dpu_reprovisioning_allowed(
+    mh_snapshot: &ManagedHostStateSnapshot,
+    ctx: &mut StateHandlerContext<'_, MachineStateHandlerContextObjects>,
+) -> Result<bool, StateHandlerError> {
+    let mut txn = ctx.services.db_pool.begin().await?;
+    let allowed = maintenance_window::is_host_allowed(&mut txn, mh_snapshot, Utc::now())
+        .await
+        .map_err(|err| StateHandlerError::GenericError(err.into()))?;
+    if !allowed {
+        tracing::debug!(
+            machine_id = %mh_snapshot.host_snapshot.id,
+            "Deferring DPU reprovisioning until the maintenance window of the host opens"
+        );
+    }
+    Ok(allowed)
+}
+
 async fn handle_restart_verification(
     mh_snapshot: &ManagedHostStateSnapshot,
     ctx: &mut StateHandlerContext<'_, MachineStateHandlerContextObjects>,
@@ -5306,7 +5333,13 @@ impl StateHandler for InstanceStateHandler {
                         }
                         InstanceNetworkSyncStatus::InstanceNetworkSynced => {}
                         InstanceNetworkSyncStatus::ZeroDpuNoObservationNeeded => {
-                            return Ok(StateHandlerOutcome::transition(next_state));
+                            return transition_with_instance_event(
+                                ctx,
+                                instance,
+                                InstanceLifecycleEventType::NetworkConfigSynced,
+                                next_state,
+                            )
+                            .await;
                         }
                         InstanceNetworkSyncStatus::InstanceNetworkNotSynced(outdated_dpus) => {
                             return Ok(StateHandlerOutcome::wait(format!(
@@ -5341,7 +5374,13 @@ impl StateHandler for InstanceStateHandler {
                             not_synced_reason.0
                         )));
                     }
-                    Ok(StateHandlerOutcome::transition(next_state))
+                    transition_with_instance_event(
+                        ctx,
+                        instance,
+                        InstanceLifecycleEventType::NetworkConfigSynced,
+                        next_state,
+                    )
+                    .await
                 }
                 InstanceState::WaitingForStorageConfig => {
                     // This state used to do something but doesn't any more, we can delete
@@ -5417,7 +5456,13 @@ impl StateHandler for InstanceStateHandler {
                     let next_state = ManagedHostState::Assigned {
                         instance_state: InstanceState::Ready,
                     };
-                    Ok(StateHandlerOutcome::transition(next_state))
+                    transition_with_instance_event(
+                        ctx,
+                        instance,
+                        InstanceLifecycleEventType::Ready,
+                        next_state,
+                    )
+                    .await
                 }
                 InstanceState::Ready => {
                     // Machine is up after reboot. Hurray. Instance is up.
@@ -5907,6 +5952,27 @@ impl StateHandler for InstanceStateHandler {
                     release_vpc_dpu_loopback(mh_snapshot, self.common_pools.as_deref(), &mut txn)
                         .await?;
 
+                    db::instance_webhook::enqueue_event(
+                        &mut txn,
+                        &InstanceLifecycleEvent::for_instance(
+                            InstanceLifecycleEventType::CleanedUp,
+                            instance,
+                        ),
+                    )
+                    .await?;
+
+                    // The storage of the host is wiped during the upcoming cleanup, which
+                    // completes this report with the erasure records of scout.
+                    db::machine_erasure_report::open(
+                        &mut txn,
+                        &MachineErasureReport::for_release(
+                            instance.machine_id,
+                            instance.id,
+                            instance.config.tenant.tenant_organization_id.clone(),
+                        ),
+                    )
+                    .await?;
+
                     let next_state = if self.attestation_enabled {
                         ManagedHostState::PostAssignedMeasuring {
                             measuring_state: MeasuringState::WaitingForMeasurements,
@@ -6238,6 +6304,14 @@ async fn handle_instance_network_config_update_request(
                 release_vpc_dpu_loopback(mh_snapshot, common_pools.as_deref(), &mut txn).await?;
             }
             db::instance::delete_update_network_config_request(&instance.id, &mut txn).await?;
+            db::instance_webhook::enqueue_event(
+                &mut txn,
+                &InstanceLifecycleEvent::for_instance(
+                    InstanceLifecycleEventType::NetworkConfigSynced,
+                    instance,
+                ),
+            )
+            .await?;
             let next_state = ManagedHostState::Assigned {
                 instance_state: InstanceState::Ready,
             };
@@ -6246,6 +6320,23 @@ async fn handle_instance_network_config_update_request(
     }
 }
 
+/// Transitions into `next_state`, queueing the webhook deliveries of an instance lifecycle
+/// event in the transaction which persists the transition
+async fn transition_with_instance_event(
+    ctx: &mut StateHandlerContext<'_, MachineStateHandlerContextObjects>,
+    instance: &InstanceSnapshot,
+    event_type: InstanceLifecycleEventType,
+    next_state: ManagedHostState,
+) -> Result<StateHandlerOutcome<ManagedHostState>, StateHandlerError> {
+    let mut txn = ctx.services.db_pool.begin().await?;
+    db::instance_webhook::enqueue_event(
+        &mut txn,
+        &InstanceLifecycleEvent::for_instance(event_type, instance),
+    )
+    .await?;
+    Ok(StateHandlerOutcome::transition(next_state).with_txn(txn))
+}
+
 /// Checks if an instance's network is synced and its DPU is healthy.
 ///
 /// This function compares the expected network configuration version with the actual version.
@@ -36,6 +36,9 @@ use crate::state_controller::machine::metrics::MachineMetricsEmitter;
 // This should be updated on each new model introdunction
 pub const CURRENT_STATE_MODEL_VERSION: i16 = 2;
 
+/// How many state handler outcomes are retained per Machine
+pub const OUTCOME_HISTORY_LIMIT: u32 = 250;
+
 /// State Controller IO implementation for Machines
 #[derive(Default, Debug)]
 pub struct MachineStateControllerIO {
@@ -137,6 +140,30 @@ impl StateControllerIO for MachineStateControllerIO {
         db::machine::update_controller_state_outcome(txn, object_id, outcome).await
     }
 
+    async fn persist_outcome_history(
+        &self,
+        txn: &mut PgConnection,
+        object_id: &Self::ObjectId,
+        state: &Self::ControllerState,
+        outcome: &PersistentStateHandlerOutcome,
+        iteration_id: Option<i64>,
+        handler_latency: std::time::Duration,
+    ) -> Result<(), DatabaseError> {
+        db::machine_state_handler_outcome_history::persist(
+            txn,
+            object_id,
+            state,
+            outcome,
+            iteration_id,
+            handler_latency,
+        )
+        .await
+    }
+
+    async fn prune_outcome_history(&self, txn: &mut PgConnection) -> Result<u64, DatabaseError> {
+        db::machine_state_handler_outcome_history::prune(txn, OUTCOME_HISTORY_LIMIT).await
+    }
+
     fn metric_state_names(state: &ManagedHostState) -> (&'static str, &'static str) {
         use model::machine::{CleanupState, InstanceState, MachineState};
 
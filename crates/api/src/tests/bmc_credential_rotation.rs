@@ -0,0 +1,162 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Tests for the automatic rotation of BMC root credentials
+
+use std::sync::Arc;
+
+use common::api_fixtures::{TestEnv, create_managed_host, create_test_env};
+use model::bmc_credential_rotation::BMC_CREDENTIAL_ROTATION_HEALTH_REPORT_SOURCE;
+use model::site_explorer::EndpointExplorationError;
+
+use crate::bmc_credential_rotation::BmcCredentialRotator;
+use crate::cfg::file::BmcCredentialRotationConfig;
+use crate::tests::common;
+
+fn create_rotator(env: &TestEnv) -> BmcCredentialRotator {
+    let mut config = (*env.config).clone();
+    config.bmc_credential_rotation = BmcCredentialRotationConfig {
+        enabled: true,
+        rotations_per_run: 10,
+        ..Default::default()
+    };
+
+    BmcCredentialRotator::new(
+        env.pool.clone(),
+        Arc::new(config),
+        env.test_meter.meter(),
+        Arc::new(env.endpoint_explorer.clone()),
+        env.api.work_lock_manager_handle.clone(),
+    )
+}
+
+#[crate::sqlx_test]
+async fn test_rotate_bmc_credentials(pool: sqlx::PgPool) {
+    let env = create_test_env(pool).await;
+    let mh = create_managed_host(&env).await;
+    let rotator = create_rotator(&env);
+
+    let mut txn = env.pool.begin().await.unwrap();
+    let host = mh.host().db_machine(&mut txn).await;
+    let host_bmc_ip = host.bmc_info.ip_addr().unwrap();
+    let host_bmc_mac = host.bmc_info.mac.unwrap();
+    txn.commit().await.unwrap();
+
+    // None of the BMC credentials were rotated before
+    let num_rotations = rotator.run_single_iteration().await.unwrap();
+    let rotated_endpoints = env
+        .endpoint_explorer
+        .rotated_endpoints
+        .lock()
+        .unwrap()
+        .clone();
+    assert_eq!(num_rotations, rotated_endpoints.len());
+    assert!(rotated_endpoints.contains(&host_bmc_ip));
+
+    let mut txn = env.pool.begin().await.unwrap();
+    let rotation = db::bmc_credential_rotation::find_by_mac_address(&mut txn, host_bmc_mac)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(rotation.rotated_at.is_some());
+    assert!(!rotation.is_failed());
+    txn.commit().await.unwrap();
+
+    // Nothing is due anymore
+    assert_eq!(rotator.run_single_iteration().await.unwrap(), 0);
+    let never_rotated = env
+        .test_meter
+        .parsed_metrics("carbide_bmc_credentials_never_rotated_count");
+    assert!(!never_rotated.is_empty());
+    assert!(never_rotated.iter().all(|(_, count)| count == "0"));
+}
+
+#[crate::sqlx_test]
+async fn test_failed_rotation_raises_health_alert(pool: sqlx::PgPool) {
+    let env = create_test_env(pool).await;
+    let mh = create_managed_host(&env).await;
+    let rotator = create_rotator(&env);
+
+    let mut txn = env.pool.begin().await.unwrap();
+    let host = mh.host().db_machine(&mut txn).await;
+    let host_bmc_ip = host.bmc_info.ip_addr().unwrap();
+    let host_bmc_mac = host.bmc_info.mac.unwrap();
+    txn.commit().await.unwrap();
+
+    env.endpoint_explorer
+        .rotation_errors
+        .lock()
+        .unwrap()
+        .insert(
+            host_bmc_ip,
+            EndpointExplorationError::Unauthorized {
+                details: "login with the new password failed".to_string(),
+                response_body: None,
+                response_code: None,
+            },
+        );
+    rotator.run_single_iteration().await.unwrap();
+
+    let mut txn = env.pool.begin().await.unwrap();
+    let rotation = db::bmc_credential_rotation::find_by_mac_address(&mut txn, host_bmc_mac)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(rotation.is_failed());
+    assert!(rotation.rotated_at.is_none());
+    assert!(
+        rotation
+            .last_error
+            .unwrap()
+            .contains("login with the new password failed")
+    );
+    let host = mh.host().db_machine(&mut txn).await;
+    assert!(
+        host.health_report_overrides
+            .merges
+            .contains_key(BMC_CREDENTIAL_ROTATION_HEALTH_REPORT_SOURCE)
+    );
+    txn.commit().await.unwrap();
+
+    // The failed rotation is not retried before the retry interval passed
+    env.endpoint_explorer
+        .rotation_errors
+        .lock()
+        .unwrap()
+        .clear();
+    assert_eq!(rotator.run_single_iteration().await.unwrap(), 0);
+
+    // Once the rotation succeeds, the alert is cleared
+    sqlx::query(
+        "UPDATE bmc_credential_rotations SET last_attempt_at = NOW() - INTERVAL '1 day' WHERE bmc_mac_address = $1",
+    )
+    .bind(host_bmc_mac)
+    .execute(&env.pool)
+    .await
+    .unwrap();
+    assert_eq!(rotator.run_single_iteration().await.unwrap(), 1);
+
+    let mut txn = env.pool.begin().await.unwrap();
+    let host = mh.host().db_machine(&mut txn).await;
+    assert!(
+        !host
+            .health_report_overrides
+            .merges
+            .contains_key(BMC_CREDENTIAL_ROTATION_HEALTH_REPORT_SOURCE)
+    );
+    txn.commit().await.unwrap();
+}
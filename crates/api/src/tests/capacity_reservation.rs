@@ -0,0 +1,309 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Tests for capacity reservations of InstanceTypes
+
+use ::rpc::forge::forge_server::Forge;
+use carbide_uuid::network::NetworkSegmentId;
+use common::api_fixtures::instance::{
+    default_os_config, default_tenant_config, single_interface_network_config,
+};
+use common::api_fixtures::{TestEnv, create_managed_host, create_test_env};
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+use tonic::Code;
+
+use crate::cfg::file::CapacityReservationMetricsConfig;
+use crate::instance::reservation_metrics::CapacityReservationMetricsCollector;
+use crate::tests::common;
+
+const INSTANCE_TYPE_ID: &str = "reservation-test-type";
+const OTHER_TENANT: &str = "Tenant2";
+
+async fn create_instance_type_with_hosts(env: &TestEnv, host_count: usize) {
+    env.api
+        .create_instance_type(tonic::Request::new(rpc::forge::CreateInstanceTypeRequest {
+            id: Some(INSTANCE_TYPE_ID.to_string()),
+            metadata: Some(rpc::forge::Metadata {
+                name: "reservation test type".to_string(),
+                description: "".to_string(),
+                labels: vec![],
+            }),
+            instance_type_attributes: None,
+        }))
+        .await
+        .unwrap();
+
+    let mut machine_ids = Vec::with_capacity(host_count);
+    for _ in 0..host_count {
+        machine_ids.push(create_managed_host(env).await.host().id.to_string());
+    }
+
+    env.api
+        .associate_machines_with_instance_type(tonic::Request::new(
+            rpc::forge::AssociateMachinesWithInstanceTypeRequest {
+                instance_type_id: INSTANCE_TYPE_ID.to_string(),
+                machine_ids,
+            },
+        ))
+        .await
+        .unwrap();
+}
+
+async fn reserve(
+    env: &TestEnv,
+    tenant_organization_id: &str,
+    count: u32,
+) -> Result<rpc::forge::CapacityReservation, tonic::Status> {
+    env.api
+        .create_capacity_reservation(tonic::Request::new(
+            rpc::forge::CapacityReservationCreationRequest {
+                id: None,
+                tenant_organization_id: tenant_organization_id.to_string(),
+                instance_type_id: INSTANCE_TYPE_ID.to_string(),
+                count,
+                expires_at: None,
+            },
+        ))
+        .await
+        .map(|response| response.into_inner())
+}
+
+/// Allocates `count` instances of the test InstanceType for the tenant
+async fn allocate(
+    env: &TestEnv,
+    segment_id: NetworkSegmentId,
+    tenant_organization_id: &str,
+    count: usize,
+) -> Result<(), tonic::Status> {
+    let instance_request = rpc::forge::InstanceAllocationRequest {
+        machine_id: None,
+        config: Some(rpc::forge::InstanceConfig {
+            tenant: Some(rpc::TenantConfig {
+                tenant_organization_id: tenant_organization_id.to_string(),
+                ..default_tenant_config()
+            }),
+            os: Some(default_os_config()),
+            network: Some(single_interface_network_config(segment_id)),
+            infiniband: None,
+            network_security_group_id: None,
+            dpu_extension_services: None,
+            nvlink: None,
+        }),
+        instance_id: None,
+        instance_type_id: None,
+        metadata: Some(rpc::forge::Metadata {
+            name: format!("test-instance-{}", uuid::Uuid::new_v4()),
+            description: "Test instance for capacity reservations".to_string(),
+            labels: vec![],
+        }),
+        allow_unhealthy_machine: false,
+    };
+
+    env.api
+        .allocate_instances_by_instance_type(tonic::Request::new(
+            rpc::forge::InstanceTypeAllocationRequest {
+                instance_type_id: INSTANCE_TYPE_ID.to_string(),
+                placement_policy: rpc::forge::InstancePlacementPolicy::PlacementAny.into(),
+                instance_requests: vec![instance_request; count],
+            },
+        ))
+        .await
+        .map(|_| ())
+}
+
+async fn find(
+    env: &TestEnv,
+    tenant_organization_id: Option<&str>,
+    include_inactive: bool,
+) -> Vec<rpc::forge::CapacityReservation> {
+    env.api
+        .find_capacity_reservations(tonic::Request::new(
+            rpc::forge::CapacityReservationSearchFilter {
+                tenant_organization_id: tenant_organization_id.map(str::to_string),
+                instance_type_id: Some(INSTANCE_TYPE_ID.to_string()),
+                include_inactive,
+            },
+        ))
+        .await
+        .unwrap()
+        .into_inner()
+        .reservations
+}
+
+/// Reserved Machines can only be allocated by the reserving tenant
+#[crate::sqlx_test]
+async fn test_reservation_holds_machines(_: PgPoolOptions, options: PgConnectOptions) {
+    let pool = PgPoolOptions::new().connect_with(options).await.unwrap();
+    let env = create_test_env(pool).await;
+    let segment_id = env.create_vpc_and_tenant_segment().await;
+    create_instance_type_with_hosts(&env, 3).await;
+    let tenant = default_tenant_config().tenant_organization_id;
+    assert_eq!(tenant, "Tenant1");
+
+    let reservation = reserve(&env, OTHER_TENANT, 2).await.unwrap();
+    assert_eq!(reservation.count, 2);
+    let status = reservation.status.unwrap();
+    assert!(status.active);
+    assert_eq!(status.in_use, 0);
+    assert_eq!(status.instance_type_available, 3);
+
+    // Only a single Machine is unreserved
+    let err = allocate(&env, segment_id, &tenant, 2).await.unwrap_err();
+    assert_eq!(err.code(), Code::ResourceExhausted);
+    allocate(&env, segment_id, &tenant, 1).await.unwrap();
+
+    // Nothing is left to reserve
+    let err = reserve(&env, &tenant, 1).await.unwrap_err();
+    assert_eq!(err.code(), Code::ResourceExhausted);
+
+    // The reserving tenant can still use its reservation
+    allocate(&env, segment_id, OTHER_TENANT, 1).await.unwrap();
+    let reservations = find(&env, Some(OTHER_TENANT), false).await;
+    assert_eq!(reservations.len(), 1);
+    let status = reservations[0].status.clone().unwrap();
+    assert_eq!(status.in_use, 1);
+    assert_eq!(status.instance_type_available, 1);
+
+    assert!(find(&env, Some(tenant.as_str()), false).await.is_empty());
+}
+
+/// Releasing a reservation makes its Machines available to everybody again
+#[crate::sqlx_test]
+async fn test_release_reservation(_: PgPoolOptions, options: PgConnectOptions) {
+    let pool = PgPoolOptions::new().connect_with(options).await.unwrap();
+    let env = create_test_env(pool).await;
+    let segment_id = env.create_vpc_and_tenant_segment().await;
+    create_instance_type_with_hosts(&env, 2).await;
+    let tenant = default_tenant_config().tenant_organization_id;
+
+    let reservation = reserve(&env, OTHER_TENANT, 2).await.unwrap();
+    let err = allocate(&env, segment_id, &tenant, 1).await.unwrap_err();
+    assert_eq!(err.code(), Code::ResourceExhausted);
+
+    env.api
+        .release_capacity_reservation(tonic::Request::new(
+            rpc::forge::CapacityReservationReleaseRequest { id: reservation.id },
+        ))
+        .await
+        .unwrap();
+
+    // Releasing twice fails
+    let err = env
+        .api
+        .release_capacity_reservation(tonic::Request::new(
+            rpc::forge::CapacityReservationReleaseRequest { id: reservation.id },
+        ))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), Code::NotFound);
+
+    assert!(find(&env, None, false).await.is_empty());
+    let reservations = find(&env, None, true).await;
+    assert_eq!(reservations.len(), 1);
+    assert!(reservations[0].released.is_some());
+    assert!(!reservations[0].status.clone().unwrap().active);
+
+    allocate(&env, segment_id, &tenant, 2).await.unwrap();
+}
+
+/// Reservations can't exceed the available Machines and need an existing InstanceType
+#[crate::sqlx_test]
+async fn test_create_reservation_validation(_: PgPoolOptions, options: PgConnectOptions) {
+    let pool = PgPoolOptions::new().connect_with(options).await.unwrap();
+    let env = create_test_env(pool).await;
+    create_instance_type_with_hosts(&env, 2).await;
+
+    let err = reserve(&env, OTHER_TENANT, 3).await.unwrap_err();
+    assert_eq!(err.code(), Code::ResourceExhausted);
+
+    let err = reserve(&env, OTHER_TENANT, 0).await.unwrap_err();
+    assert_eq!(err.code(), Code::InvalidArgument);
+
+    let err = env
+        .api
+        .create_capacity_reservation(tonic::Request::new(
+            rpc::forge::CapacityReservationCreationRequest {
+                id: None,
+                tenant_organization_id: OTHER_TENANT.to_string(),
+                instance_type_id: "unknown-type".to_string(),
+                count: 1,
+                expires_at: None,
+            },
+        ))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), Code::NotFound);
+
+    let err = env
+        .api
+        .create_capacity_reservation(tonic::Request::new(
+            rpc::forge::CapacityReservationCreationRequest {
+                id: None,
+                tenant_organization_id: OTHER_TENANT.to_string(),
+                instance_type_id: INSTANCE_TYPE_ID.to_string(),
+                count: 1,
+                expires_at: Some((chrono::Utc::now() - chrono::Duration::hours(1)).into()),
+            },
+        ))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), Code::InvalidArgument);
+
+    reserve(&env, OTHER_TENANT, 1).await.unwrap();
+    reserve(&env, OTHER_TENANT, 1).await.unwrap();
+    let err = reserve(&env, "Tenant1", 1).await.unwrap_err();
+    assert_eq!(err.code(), Code::ResourceExhausted);
+}
+
+/// The utilization of reservations is exported as metrics
+#[crate::sqlx_test]
+async fn test_reservation_metrics(_: PgPoolOptions, options: PgConnectOptions) {
+    let pool = PgPoolOptions::new().connect_with(options).await.unwrap();
+    let env = create_test_env(pool).await;
+    let segment_id = env.create_vpc_and_tenant_segment().await;
+    create_instance_type_with_hosts(&env, 3).await;
+
+    reserve(&env, OTHER_TENANT, 2).await.unwrap();
+    allocate(&env, segment_id, OTHER_TENANT, 1).await.unwrap();
+
+    let collector = CapacityReservationMetricsCollector::new(
+        env.pool.clone(),
+        CapacityReservationMetricsConfig::default(),
+        env.test_meter.meter(),
+    );
+    collector.run_single_iteration().await.unwrap();
+
+    let tenant_attrs = format!(
+        "{{instance_type_id=\"{INSTANCE_TYPE_ID}\",tenant_organization_id=\"{OTHER_TENANT}\"}}"
+    );
+    let instance_type_attrs = format!("{{instance_type_id=\"{INSTANCE_TYPE_ID}\"}}");
+    for (metric, attrs, value) in [
+        ("reserved", &tenant_attrs, "2"),
+        ("used", &tenant_attrs, "1"),
+        ("outstanding", &tenant_attrs, "1"),
+        ("available", &instance_type_attrs, "2"),
+        ("unreserved", &instance_type_attrs, "1"),
+    ] {
+        assert_eq!(
+            env.test_meter.parsed_metrics(&format!(
+                "carbide_capacity_reservation_{metric}_machines_count"
+            )),
+            vec![(attrs.clone(), value.to_string())],
+            "{metric}"
+        );
+    }
+}
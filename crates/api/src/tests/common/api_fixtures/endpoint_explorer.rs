@@ -36,6 +36,11 @@ use crate::site_explorer::{EndpointExplorer, SiteExplorationMetrics};
 pub struct MockEndpointExplorer {
     pub reports:
         Arc<Mutex<HashMap<IpAddr, Result<EndpointExplorationReport, EndpointExplorationError>>>>,
+    /// Errors returned when rotating the BMC credentials of an endpoint.
+    /// Rotations of all other endpoints succeed.
+    pub rotation_errors: Arc<Mutex<HashMap<IpAddr, EndpointExplorationError>>>,
+    /// The endpoints whose BMC credentials were rotated, in order
+    pub rotated_endpoints: Arc<Mutex<Vec<IpAddr>>>,
 }
 
 impl MockEndpointExplorer {
@@ -222,6 +227,18 @@ impl EndpointExplorer for MockEndpointExplorer {
         Ok(())
     }
 
+    async fn rotate_bmc_root_credentials(
+        &self,
+        address: SocketAddr,
+        _interface: &MachineInterfaceSnapshot,
+    ) -> Result<(), EndpointExplorationError> {
+        if let Some(error) = self.rotation_errors.lock().unwrap().get(&address.ip()) {
+            return Err(error.clone());
+        }
+        self.rotated_endpoints.lock().unwrap().push(address.ip());
+        Ok(())
+    }
+
     async fn enable_infinite_boot(
         &self,
         _address: SocketAddr,
@@ -284,6 +284,8 @@ pub fn default_tenant_config() -> rpc::TenantConfig {
         tenant_organization_id: "Tenant1".to_string(),
         tenant_keyset_ids: vec![],
         hostname: None,
+        metadata_token_required: None,
+        metadata_token_hop_limit: None,
     }
 }
 
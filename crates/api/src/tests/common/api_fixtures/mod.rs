@@ -82,8 +82,9 @@ use crate::api::metrics::ApiMetricsEmitter;
 use crate::cfg::file::{
     BomValidationConfig, CarbideConfig, DpaConfig, DpaInterfaceStateControllerConfig,
     DpuConfig as InitialDpuConfig, FirmwareGlobal, FnnConfig, IBFabricConfig, IbFabricDefinition,
-    IbPartitionStateControllerConfig, ListenMode, MachineStateControllerConfig, MachineUpdater,
-    MachineValidationConfig, MeasuredBootMetricsCollectorConfig, NetworkSecurityGroupConfig,
+    IbPartitionStateControllerConfig, InstanceWebhookConfig, ListenMode,
+    MachineStateControllerConfig, MachineUpdater, MachineValidationConfig,
+    MeasuredBootMetricsCollectorConfig, NetworkSecurityGroupConfig,
     NetworkSegmentStateControllerConfig, NvLinkConfig, PowerManagerOptions,
     PowerShelfStateControllerConfig, RackStateControllerConfig, SiteExplorerConfig, SpdmConfig,
     SpdmStateControllerConfig, StateControllerConfig, SwitchStateControllerConfig, VmaasConfig,
@@ -1099,9 +1100,16 @@ pub fn get_config() -> CarbideConfig {
             instance_autoreboot_period: None,
             max_concurrent_machine_updates_absolute: Some(10),
             max_concurrent_machine_updates_percent: None,
+            rollout: None,
         },
         max_find_by_ids: default_max_find_by_ids(),
         network_security_group: NetworkSecurityGroupConfig::default(),
+        instance_webhooks: InstanceWebhookConfig {
+            // The webhook receivers of the tests listen on localhost
+            allow_insecure_targets: true,
+            ..Default::default()
+        },
+        erasure_reports: ErasureReportConfig::default(),
         min_dpu_functioning_links: None,
         dpu_network_monitor_pinger_type: None,
         host_health: HostHealthConfig::default(),
@@ -1110,6 +1118,7 @@ pub fn get_config() -> CarbideConfig {
             enabled: true,
             run_interval: std::time::Duration::from_secs(10),
         },
+        capacity_reservation_metrics: Default::default(),
         machine_validation_config: MachineValidationConfig {
             enabled: true,
             ..MachineValidationConfig::default()
@@ -1162,6 +1171,8 @@ pub fn get_config() -> CarbideConfig {
         x86_pxe_boot_url_override: None,
         arm_pxe_boot_url_override: None,
         supernic_firmware_profiles: HashMap::default(),
+        secrets: Default::default(),
+        bmc_credential_rotation: Default::default(),
     }
 }
 
@@ -1554,6 +1565,7 @@ pub async fn create_test_env_with_overrides(
 
     let fake_endpoint_explorer = MockEndpointExplorer {
         reports: Arc::new(std::sync::Mutex::new(Default::default())),
+        ..Default::default()
     };
 
     // The API server is launched with a disabled site-explorer config so that it doesn't launch one
@@ -1720,6 +1732,8 @@ pub async fn populate_network_security_groups(api: Arc<Api>) {
                 description: "".to_string(),
                 labels: vec![],
             }),
+            metadata_token_required: None,
+            metadata_token_hop_limit: None,
         }))
         .await
         .unwrap();
@@ -1734,6 +1748,8 @@ pub async fn populate_network_security_groups(api: Arc<Api>) {
                 description: "".to_string(),
                 labels: vec![],
             }),
+            metadata_token_required: None,
+            metadata_token_hop_limit: None,
         }))
         .await
         .unwrap();
@@ -437,3 +437,237 @@ pub async fn get_dns_record_count(pool: &sqlx::Pool<Postgres>) -> i64 {
     let rows = sqlx::query::<_>(query).fetch_one(&mut *txn).await.unwrap();
     rows.try_get("row_cnt").unwrap()
 }
+
+// test_dns_zone_transfer verifies that all records of a zone can be listed for
+// AXFR, that the zone serial follows record changes, and that DNSSEC keys are
+// returned for the zone.
+#[crate::sqlx_test]
+async fn test_dns_zone_transfer(pool: sqlx::PgPool) {
+    let env = create_test_env(pool).await;
+    env.create_vpc_and_tenant_segment().await;
+    let api = &env.api;
+
+    let domain_info = |name: &str| {
+        api.get_domain_info(tonic::Request::new(
+            rpc::protos::dns::GetDomainInfoRequest {
+                name: name.to_string(),
+            },
+        ))
+    };
+
+    let initial = domain_info(concatcp!(DOMAIN_NAME, "."))
+        .await
+        .unwrap()
+        .into_inner()
+        .result
+        .expect("domain should exist");
+    assert_eq!(initial.zone, concatcp!(DOMAIN_NAME, "."));
+
+    let unknown = domain_info("unknown.example.com.")
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(unknown.result.is_none());
+
+    let interface = api
+        .discover_dhcp(DhcpDiscovery::builder("FF:FF:FF:FF:FF:FF", "192.0.2.1").tonic_request())
+        .await
+        .unwrap()
+        .into_inner();
+
+    // Adding an interface with an address changes the zone, so the serial must move forward
+    let updated = domain_info(DOMAIN_NAME)
+        .await
+        .unwrap()
+        .into_inner()
+        .result
+        .unwrap();
+    assert!(
+        updated.serial > initial.serial,
+        "serial should be bumped after a record change: {} -> {}",
+        initial.serial,
+        updated.serial
+    );
+
+    let records = api
+        .get_all_records_for_domain(tonic::Request::new(
+            rpc::protos::dns::GetAllRecordsForDomainRequest {
+                name: concatcp!(DOMAIN_NAME, ".").to_string(),
+            },
+        ))
+        .await
+        .unwrap()
+        .into_inner()
+        .result;
+    assert_eq!(records[0].qtype, "SOA");
+    assert_eq!(records[0].qname, concatcp!(DOMAIN_NAME, "."));
+    assert!(
+        records[0]
+            .content
+            .contains(&format!(" {} ", updated.serial))
+    );
+    let fqdn = format!("{}.", interface.fqdn);
+    let record = records
+        .iter()
+        .find(|r| r.qname == fqdn)
+        .expect("interface record should be part of the zone");
+    assert_eq!(record.content, interface.address.split('/').next().unwrap());
+
+    let keys = api
+        .get_domain_keys(tonic::Request::new(
+            rpc::protos::dns::GetDomainKeysRequest {
+                name: DOMAIN_NAME.to_string(),
+            },
+        ))
+        .await
+        .unwrap()
+        .into_inner()
+        .result;
+    assert!(keys.is_empty());
+
+    let domain_id = db::dns::domain::find_by_name(&env.pool, DOMAIN_NAME)
+        .await
+        .unwrap()[0]
+        .id;
+    let roll_key = |kind: rpc::protos::dns::DomainKeyKind| {
+        api.roll_domain_key(tonic::Request::new(rpc::protos::dns::DomainKeyRequest {
+            domain_id: Some(domain_id),
+            kind: kind as i32,
+        }))
+    };
+
+    let generated = api
+        .generate_domain_key(tonic::Request::new(rpc::protos::dns::DomainKeyRequest {
+            domain_id: Some(domain_id),
+            kind: rpc::protos::dns::DomainKeyKind::KeySigning as i32,
+        }))
+        .await
+        .unwrap()
+        .into_inner()
+        .keys;
+    assert_eq!(generated.len(), 1);
+    let ksk_id = generated[0].id;
+    assert_eq!(generated[0].flags, 257);
+
+    let get_keys = || {
+        api.get_domain_keys(tonic::Request::new(
+            rpc::protos::dns::GetDomainKeysRequest {
+                name: concatcp!(DOMAIN_NAME, ".").to_string(),
+            },
+        ))
+    };
+    let keys = get_keys().await.unwrap().into_inner().result;
+    assert_eq!(keys.len(), 1);
+    assert_eq!(keys[0].id, ksk_id);
+    assert!(keys[0].active && keys[0].published);
+    assert!(
+        keys[0]
+            .content
+            .starts_with("Private-key-format: v1.2\nAlgorithm: 15 (ED25519)\n")
+    );
+
+    // The private key is kept in the credential provider, not in the database
+    let row = sqlx::query("SELECT * FROM dns_domain_keys WHERE id = $1")
+        .bind(ksk_id as i32)
+        .fetch_one(&env.pool)
+        .await
+        .unwrap();
+    assert!(row.try_get::<String, _>("content").is_err());
+
+    // Rolling keeps the previous key published but inactive, the next roll removes it
+    let zsk_1 = roll_key(rpc::protos::dns::DomainKeyKind::ZoneSigning)
+        .await
+        .unwrap()
+        .into_inner()
+        .keys;
+    assert_eq!(zsk_1.len(), 2);
+    let zsk_1_id = zsk_1.iter().find(|key| key.flags == 256).unwrap().id;
+
+    let zsk_2 = roll_key(rpc::protos::dns::DomainKeyKind::ZoneSigning)
+        .await
+        .unwrap()
+        .into_inner()
+        .keys;
+    let previous = zsk_2.iter().find(|key| key.id == zsk_1_id).unwrap();
+    assert!(!previous.active && previous.published);
+    assert_eq!(zsk_2.iter().filter(|key| key.active).count(), 2);
+
+    let zsk_3 = roll_key(rpc::protos::dns::DomainKeyKind::ZoneSigning)
+        .await
+        .unwrap()
+        .into_inner()
+        .keys;
+    assert!(zsk_3.iter().all(|key| key.id != zsk_1_id));
+    assert_eq!(zsk_3.len(), 3);
+    assert!(zsk_3.iter().any(|key| key.id == ksk_id && key.active));
+
+    let keys = get_keys().await.unwrap().into_inner().result;
+    assert_eq!(keys.len(), 3);
+}
+
+// test_dns_updated_masters verifies that domains whose serial changed are
+// returned by getUpdatedMasters until PowerDNS reports the NOTIFY as sent.
+#[crate::sqlx_test]
+async fn test_dns_updated_masters(pool: sqlx::PgPool) {
+    let env = create_test_env(pool).await;
+    env.create_vpc_and_tenant_segment().await;
+    let api = &env.api;
+
+    let updated_masters = || {
+        api.get_updated_masters(tonic::Request::new(
+            rpc::protos::dns::GetUpdatedMastersRequest {},
+        ))
+    };
+    let set_notified = |domain: &rpc::protos::dns::DomainInfo| {
+        api.set_notified(tonic::Request::new(rpc::protos::dns::SetNotifiedRequest {
+            id: domain.id,
+            serial: domain.serial as u32,
+        }))
+    };
+
+    // Domains which were never notified are returned
+    let masters = updated_masters().await.unwrap().into_inner().result;
+    let domain = masters
+        .iter()
+        .find(|domain| domain.zone == concatcp!(DOMAIN_NAME, "."))
+        .expect("domain should need a notify")
+        .clone();
+    assert_eq!(domain.kind, "master");
+    assert_eq!(domain.notified_serial, None);
+    for domain in &masters {
+        set_notified(domain).await.unwrap();
+    }
+    assert!(
+        updated_masters()
+            .await
+            .unwrap()
+            .into_inner()
+            .result
+            .is_empty()
+    );
+
+    // A record change marks the domain as changed. The serial is bumped once
+    // getUpdatedMasters is polled.
+    api.discover_dhcp(DhcpDiscovery::builder("FF:FF:FF:FF:FF:FF", "192.0.2.1").tonic_request())
+        .await
+        .unwrap();
+    api.discover_dhcp(DhcpDiscovery::builder("F1:FF:FF:FF:FF:FF", "192.0.2.1").tonic_request())
+        .await
+        .unwrap();
+
+    let masters = updated_masters().await.unwrap().into_inner().result;
+    assert_eq!(masters.len(), 1);
+    assert_eq!(masters[0].id, domain.id);
+    assert_eq!(masters[0].notified_serial, Some(domain.serial));
+    assert!(masters[0].serial > domain.serial);
+
+    set_notified(&masters[0]).await.unwrap();
+    assert!(
+        updated_masters()
+            .await
+            .unwrap()
+            .into_inner()
+            .result
+            .is_empty()
+    );
+}
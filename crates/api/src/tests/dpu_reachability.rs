@@ -0,0 +1,297 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use carbide_uuid::machine::MachineId;
+use chrono::{Duration, Utc};
+use common::api_fixtures::{
+    TestEnvOverrides, create_managed_host, create_test_env, create_test_env_with_overrides,
+    get_config,
+};
+use model::vpc::VpcDpuLoopback;
+use rpc::forge::forge_server::Forge;
+use rpc::forge::{
+    DpuNetworkProbeProtocol, DpuReachabilityMatrixRequest, DpuReachabilityReport,
+    DpuReachabilityResult, GetDpuInfoListRequest,
+};
+
+use crate::tests::common;
+
+const VPC_ID: &str = "7f1c64a5-2b2b-4d5e-9b1b-5b2a3c6b7d8e";
+
+fn result(
+    peer: &MachineId,
+    protocol: DpuNetworkProbeProtocol,
+    vpc_id: Option<&str>,
+    received: u32,
+) -> DpuReachabilityResult {
+    DpuReachabilityResult {
+        peer_dpu_id: peer.to_string(),
+        protocol: protocol as i32,
+        port: (protocol != DpuNetworkProbeProtocol::Icmp).then_some(4789),
+        vpc_id: vpc_id.map(str::to_string),
+        target_ip: "172.20.0.200".to_string(),
+        sent: 5,
+        received,
+        latency_p50_ms: (received > 0).then_some(0.21),
+        latency_p90_ms: (received > 0).then_some(0.35),
+        latency_p99_ms: (received > 0).then_some(0.4),
+        jitter_ms: (received > 0).then_some(0.02),
+    }
+}
+
+fn report(
+    dpu: &MachineId,
+    observed_at: chrono::DateTime<Utc>,
+    results: Vec<DpuReachabilityResult>,
+) -> DpuReachabilityReport {
+    DpuReachabilityReport {
+        dpu_machine_id: Some(*dpu),
+        observed_at: Some(observed_at.into()),
+        results,
+    }
+}
+
+#[crate::sqlx_test]
+async fn test_record_and_get_dpu_reachability_matrix(pool: sqlx::PgPool) {
+    let env = create_test_env(pool).await;
+    let mh1 = create_managed_host(&env).await;
+    let mh2 = create_managed_host(&env).await;
+    let dpu1 = mh1.dpu().id;
+    let dpu2 = mh2.dpu().id;
+
+    let now = Utc::now();
+    env.api
+        .record_dpu_reachability_report(tonic::Request::new(report(
+            &dpu1,
+            now,
+            vec![
+                result(&dpu2, DpuNetworkProbeProtocol::Icmp, None, 5),
+                result(&dpu2, DpuNetworkProbeProtocol::Udp, Some(VPC_ID), 3),
+            ],
+        )))
+        .await
+        .unwrap();
+    env.api
+        .record_dpu_reachability_report(tonic::Request::new(report(
+            &dpu2,
+            now,
+            vec![result(&dpu1, DpuNetworkProbeProtocol::Icmp, None, 0)],
+        )))
+        .await
+        .unwrap();
+
+    // A report which is older than the stored one is ignored
+    env.api
+        .record_dpu_reachability_report(tonic::Request::new(report(
+            &dpu2,
+            now - Duration::minutes(5),
+            vec![result(&dpu1, DpuNetworkProbeProtocol::Icmp, None, 5)],
+        )))
+        .await
+        .unwrap();
+
+    let matrix = env
+        .api
+        .get_dpu_reachability_matrix(tonic::Request::new(DpuReachabilityMatrixRequest {
+            vpc_id: None,
+            after_dpu_id: None,
+            limit: None,
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(matrix.reports.len(), 2);
+    assert_eq!(matrix.next_dpu_id, None);
+    let dpu1_report = matrix
+        .reports
+        .iter()
+        .find(|r| r.dpu_machine_id == Some(dpu1))
+        .unwrap();
+    assert_eq!(dpu1_report.results.len(), 2);
+    let dpu2_report = matrix
+        .reports
+        .iter()
+        .find(|r| r.dpu_machine_id == Some(dpu2))
+        .unwrap();
+    assert_eq!(dpu2_report.results.len(), 1);
+    assert_eq!(dpu2_report.results[0].received, 0);
+
+    // Only the overlay probes of the VPC
+    let matrix = env
+        .api
+        .get_dpu_reachability_matrix(tonic::Request::new(DpuReachabilityMatrixRequest {
+            vpc_id: Some(VPC_ID.to_string()),
+            after_dpu_id: None,
+            limit: None,
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    let results: Vec<_> = matrix
+        .reports
+        .iter()
+        .flat_map(|r| r.results.iter())
+        .collect();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].protocol, DpuNetworkProbeProtocol::Udp as i32);
+    assert_eq!(results[0].received, 3);
+
+    // The matrix is paginated by DPU
+    let first_page = env
+        .api
+        .get_dpu_reachability_matrix(tonic::Request::new(DpuReachabilityMatrixRequest {
+            vpc_id: None,
+            after_dpu_id: None,
+            limit: Some(1),
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(first_page.reports.len(), 1);
+    assert_eq!(first_page.next_dpu_id, first_page.reports[0].dpu_machine_id);
+    let second_page = env
+        .api
+        .get_dpu_reachability_matrix(tonic::Request::new(DpuReachabilityMatrixRequest {
+            vpc_id: None,
+            after_dpu_id: first_page.next_dpu_id,
+            limit: Some(1),
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(second_page.reports.len(), 1);
+    assert_eq!(second_page.next_dpu_id, None);
+    assert_ne!(
+        first_page.reports[0].dpu_machine_id,
+        second_page.reports[0].dpu_machine_id
+    );
+}
+
+#[crate::sqlx_test]
+async fn test_record_dpu_reachability_report_invalid(pool: sqlx::PgPool) {
+    let env = create_test_env(pool).await;
+    let mh = create_managed_host(&env).await;
+    let dpu = mh.dpu().id;
+    let host = mh.host().id;
+
+    // Only DPUs report reachability
+    let err = env
+        .api
+        .record_dpu_reachability_report(tonic::Request::new(report(
+            &host,
+            Utc::now(),
+            vec![result(&dpu, DpuNetworkProbeProtocol::Icmp, None, 5)],
+        )))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::InvalidArgument);
+
+    // TCP and UDP probes need a port
+    let mut tcp_result = result(&host, DpuNetworkProbeProtocol::Tcp, None, 5);
+    tcp_result.port = None;
+    let err = env
+        .api
+        .record_dpu_reachability_report(tonic::Request::new(report(
+            &dpu,
+            Utc::now(),
+            vec![tcp_result],
+        )))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::InvalidArgument);
+}
+
+#[crate::sqlx_test]
+async fn test_get_dpu_info_list_vpc_probe_targets(pool: sqlx::PgPool) {
+    let env = {
+        let mut config = get_config();
+        config.dpu_network_monitor.vpc_overlay_probes = true;
+        create_test_env_with_overrides(pool, TestEnvOverrides::with_config(config)).await
+    };
+    let segment_id = env.create_vpc_and_tenant_segment().await;
+    let vpc = db::vpc::find_by_name(&env.pool, "test vpc 1")
+        .await
+        .unwrap()
+        .remove(0);
+    let mh1 = create_managed_host(&env).await;
+    let mh2 = create_managed_host(&env).await;
+    let idle = create_managed_host(&env).await;
+    // Instance addresses are not probed, only the addresses of the DPUs in the VRF of the VPC
+    mh1.instance_builer(&env)
+        .single_interface_network_config(segment_id)
+        .build()
+        .await;
+    let mut txn = env.db_txn().await;
+    for (mh, loopback_ip) in [(&mh1, "10.210.0.1"), (&mh2, "10.210.0.2")] {
+        db::vpc_dpu_loopback::persist(
+            VpcDpuLoopback::new(mh.dpu().id, vpc.id, loopback_ip.parse().unwrap()),
+            &mut txn,
+        )
+        .await
+        .unwrap();
+    }
+    txn.commit().await.unwrap();
+
+    let dpu_list = env
+        .api
+        .get_dpu_info_list(tonic::Request::new(GetDpuInfoListRequest {}))
+        .await
+        .unwrap()
+        .into_inner()
+        .dpu_list;
+
+    let targets = |dpu: &MachineId| {
+        dpu_list
+            .iter()
+            .find(|d| d.id == dpu.to_string())
+            .unwrap()
+            .vpc_probe_targets
+            .clone()
+    };
+    let targets1 = targets(&mh1.dpu().id);
+    let targets2 = targets(&mh2.dpu().id);
+    assert_eq!(targets1.len(), 1);
+    assert_eq!(targets2.len(), 1);
+    // Both DPUs have a loopback address in the same VPC
+    assert_eq!(targets1[0].vpc_id, vpc.id.to_string());
+    assert_eq!(targets2[0].vpc_id, vpc.id.to_string());
+    assert_eq!(targets1[0].vni, targets2[0].vni);
+    assert_eq!(targets1[0].address, "10.210.0.1");
+    assert_eq!(targets2[0].address, "10.210.0.2");
+    // DPUs without an address in any VPC have nothing to probe on the overlay
+    assert!(targets(&idle.dpu().id).is_empty());
+}
+
+#[crate::sqlx_test]
+async fn test_get_dpu_info_list_without_vpc_overlay_probes(pool: sqlx::PgPool) {
+    let env = create_test_env(pool).await;
+    let segment_id = env.create_vpc_and_tenant_segment().await;
+    let mh = create_managed_host(&env).await;
+    mh.instance_builer(&env)
+        .single_interface_network_config(segment_id)
+        .build()
+        .await;
+
+    let dpu_list = env
+        .api
+        .get_dpu_info_list(tonic::Request::new(GetDpuInfoListRequest {}))
+        .await
+        .unwrap()
+        .into_inner()
+        .dpu_list;
+    assert!(dpu_list.iter().all(|dpu| dpu.vpc_probe_targets.is_empty()));
+}
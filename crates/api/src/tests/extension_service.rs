@@ -33,6 +33,8 @@ use crate::tests::common::api_fixtures::{TestEnv, create_managed_host, create_te
 const TEST_SERVICE_DATA: &str = "apiVersion: v1\nkind: Pod\nmetadata:\n  name: test\nspec:\n  containers:\n    - name: app\n      image: nginx:1.27";
 const TEST_SERVICE_DATA_VERSION_2: &str = "apiVersion: v1\nkind: Pod\nmetadata:\n  name: version-2\nspec:\n  containers:\n    - name: app\n      image: nginx:1.27";
 const TEST_SERVICE_DATA_VERSION_3: &str = "apiVersion: v1\nkind: Pod\nmetadata:\n  name: version-3\nspec:\n  containers:\n    - name: app\n      image: nginx:1.27";
+const TEST_CONTAINER_SERVICE_DATA: &str = "image: registry.test.com/app:1.0\nargs: [\"--port\", \"8080\"]\nenv:\n  LOG_LEVEL: debug\nmounts:\n  - source: /var/log/app\n    destination: /log\n";
+const TEST_SYSTEMD_UNIT_SERVICE_DATA: &str = "[Unit]\nDescription=Test service\n\n[Service]\nExecStart=/usr/local/bin/app \\\n    --port 8080\nRestart=on-failure\n";
 
 fn create_credential() -> rpc::DpuExtensionServiceCredential {
     rpc::DpuExtensionServiceCredential {
@@ -73,6 +75,8 @@ async fn create_test_tenants(env: &TestEnv) -> Result<(), eyre::Report> {
                 description: "".to_string(),
                 labels: vec![],
             }),
+            metadata_token_required: None,
+            metadata_token_hop_limit: None,
         }))
         .await
         .unwrap();
@@ -87,6 +91,8 @@ async fn create_test_tenants(env: &TestEnv) -> Result<(), eyre::Report> {
                 description: "".to_string(),
                 labels: vec![],
             }),
+            metadata_token_required: None,
+            metadata_token_hop_limit: None,
         }))
         .await
         .unwrap();
@@ -346,6 +352,156 @@ async fn test_extension_service_create_with_credential(
     Ok(())
 }
 
+fn create_request(
+    name: &str,
+    service_type: rpc::DpuExtensionServiceType,
+    data: &str,
+    credential: Option<rpc::DpuExtensionServiceCredential>,
+) -> rpc::CreateDpuExtensionServiceRequest {
+    rpc::CreateDpuExtensionServiceRequest {
+        service_id: None,
+        service_name: name.to_string(),
+        description: None,
+        tenant_organization_id: "best_org".to_string(),
+        service_type: service_type.into(),
+        data: data.to_string(),
+        credential,
+        observability: None,
+    }
+}
+
+#[crate::sqlx_test]
+async fn test_extension_service_container_creation(
+    db_pool: sqlx::PgPool,
+) -> Result<(), eyre::Report> {
+    let env = create_test_env(db_pool).await;
+
+    create_test_tenants(&env).await?;
+
+    let mut request = create_request(
+        "container-service",
+        rpc::DpuExtensionServiceType::Container,
+        TEST_CONTAINER_SERVICE_DATA,
+        Some(create_credential()),
+    );
+    request.observability = Some(create_observability());
+    let extension_service = env
+        .api
+        .create_dpu_extension_service(Request::new(request))
+        .await?
+        .into_inner();
+    assert_eq!(
+        extension_service.service_type,
+        rpc::DpuExtensionServiceType::Container as i32
+    );
+    assert!(
+        extension_service
+            .latest_version_info
+            .as_ref()
+            .unwrap()
+            .has_credential
+    );
+
+    for (data, expected_message) in [
+        ("", "empty data"),
+        ("args: [\"--debug\"]", "missing field `image`"),
+        ("image: \"  \"", "missing required field: image"),
+        (
+            "image: nginx:1.27\nrestart: always",
+            "unknown field `restart`",
+        ),
+        (
+            "image: nginx:1.27\nrestart_policy: on_failure",
+            "unknown variant `on_failure`",
+        ),
+        (
+            "image: nginx:1.27\nmounts:\n  - source: data\n    destination: /data",
+            "absolute paths",
+        ),
+        (
+            "image: nginx:1.27\nenv:\n  \"A=B\": c",
+            "environment variable name",
+        ),
+    ] {
+        let err = env
+            .api
+            .create_dpu_extension_service(Request::new(create_request(
+                "invalid-container-service",
+                rpc::DpuExtensionServiceType::Container,
+                data,
+                None,
+            )))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::InvalidArgument, "data: {data}");
+        assert!(
+            err.message().contains(expected_message),
+            "data: {data}, message: {}",
+            err.message()
+        );
+    }
+
+    Ok(())
+}
+
+#[crate::sqlx_test]
+async fn test_extension_service_systemd_unit_creation(
+    db_pool: sqlx::PgPool,
+) -> Result<(), eyre::Report> {
+    let env = create_test_env(db_pool).await;
+
+    create_test_tenants(&env).await?;
+
+    let extension_service = env
+        .api
+        .create_dpu_extension_service(Request::new(create_request(
+            "unit-service",
+            rpc::DpuExtensionServiceType::SystemdUnit,
+            TEST_SYSTEMD_UNIT_SERVICE_DATA,
+            None,
+        )))
+        .await?
+        .into_inner();
+    assert_eq!(
+        extension_service.service_type,
+        rpc::DpuExtensionServiceType::SystemdUnit as i32
+    );
+
+    // Units can't use registry credentials
+    let err = env
+        .api
+        .create_dpu_extension_service(Request::new(create_request(
+            "unit-service-with-credential",
+            rpc::DpuExtensionServiceType::SystemdUnit,
+            TEST_SYSTEMD_UNIT_SERVICE_DATA,
+            Some(create_credential()),
+        )))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::InvalidArgument);
+
+    for data in [
+        "",
+        "[Unit]\nDescription=No command\n",
+        "[Service]\nExecStartPre=/bin/true\n",
+        "ExecStart=/usr/bin/agent\n[Service]\n",
+    ] {
+        let err = env
+            .api
+            .create_dpu_extension_service(Request::new(create_request(
+                "invalid-unit-service",
+                rpc::DpuExtensionServiceType::SystemdUnit,
+                data,
+                None,
+            )))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::InvalidArgument, "data: {data}");
+    }
+
+    Ok(())
+}
+
 #[crate::sqlx_test]
 async fn test_extension_service_create_failure(db_pool: sqlx::PgPool) -> Result<(), eyre::Report> {
     let env = create_test_env(db_pool).await;
@@ -2085,6 +2085,8 @@ async fn test_create_instance_duplicate_keyset_ids(_: PgPoolOptions, options: Pg
                 "bad_id".to_string(),
             ],
             hostname: Some("test-instance".to_string()),
+            metadata_token_required: None,
+            metadata_token_hop_limit: None,
         }),
         network: Some(single_interface_network_config(segment_id)),
         infiniband: None,
@@ -2141,6 +2143,8 @@ async fn test_create_instance_keyset_ids_max(_: PgPoolOptions, options: PgConnec
                 "k".to_string(),
             ],
             hostname: Some("test-hostname".to_string()),
+            metadata_token_required: None,
+            metadata_token_hop_limit: None,
         }),
         network: Some(single_interface_network_config(segment_id)),
         infiniband: None,
@@ -2284,6 +2288,8 @@ async fn test_allocate_network_vpc_prefix_id(_: PgPoolOptions, options: PgConnec
             tenant_organization_id: "abc".to_string(),
             hostname: Some("xyz".to_string()),
             tenant_keyset_ids: vec![],
+            metadata_token_required: None,
+            metadata_token_hop_limit: None,
         }),
         os: Some(default_os_config()),
         network: Some(x),
@@ -4278,6 +4284,8 @@ async fn test_allocate_network_multi_dpu_vpc_prefix_id(
             tenant_organization_id: "abc".to_string(),
             hostname: Some("xyz".to_string()),
             tenant_keyset_ids: vec![],
+            metadata_token_required: None,
+            metadata_token_hop_limit: None,
         }),
         os: Some(default_os_config()),
         network: Some(network_config),
@@ -5172,6 +5180,8 @@ async fn test_allocate_instance_with_extension_services(
                 description: "".to_string(),
                 labels: vec![],
             }),
+            metadata_token_required: None,
+            metadata_token_hop_limit: None,
         }))
         .await
         .unwrap();
@@ -5261,6 +5271,8 @@ async fn create_dpu_extension_services(
                 description: "".to_string(),
                 labels: vec![],
             }),
+            metadata_token_required: None,
+            metadata_token_hop_limit: None,
         }))
         .await
         .unwrap();
@@ -194,6 +194,8 @@ async fn test_zero_dpu_instance_allocation_explicit_network_config(
                     tenant_organization_id: "2829bbe3-c169-4cd9-8b2a-19a8b1618a93".to_string(), // from sql fixture
                     hostname: None,
                     tenant_keyset_ids: vec![],
+                    metadata_token_required: None,
+                    metadata_token_hop_limit: None,
                 }),
                 network_security_group_id: None,
                 os: Some(forge::OperatingSystem {
@@ -288,6 +290,8 @@ async fn test_zero_dpu_instance_allocation_no_network_config(
                     tenant_organization_id: "2829bbe3-c169-4cd9-8b2a-19a8b1618a93".to_string(), // from sql fixture
                     hostname: None,
                     tenant_keyset_ids: vec![],
+                    metadata_token_required: None,
+                    metadata_token_hop_limit: None,
                 }),
                 os: Some(forge::OperatingSystem {
                     phone_home_enabled: false,
@@ -382,6 +386,8 @@ async fn test_zero_dpu_instance_allocation_multi_segment_no_network_config(
                     tenant_organization_id: "2829bbe3-c169-4cd9-8b2a-19a8b1618a93".to_string(), // from sql fixture
                     hostname: None,
                     tenant_keyset_ids: vec![],
+                    metadata_token_required: None,
+                    metadata_token_hop_limit: None,
                 }),
                 os: Some(forge::OperatingSystem {
                     phone_home_enabled: false,
@@ -507,6 +513,8 @@ async fn test_reject_single_dpu_instance_allocation_no_network_config(
                     tenant_organization_id: "2829bbe3-c169-4cd9-8b2a-19a8b1618a93".to_string(), // from sql fixture
                     hostname: None,
                     tenant_keyset_ids: vec![],
+                    metadata_token_required: None,
+                    metadata_token_hop_limit: None,
                 }),
                 os: Some(forge::OperatingSystem {
                     phone_home_enabled: false,
@@ -565,6 +573,8 @@ async fn test_reject_single_dpu_instance_allocation_host_inband_network_config(
                     tenant_organization_id: "2829bbe3-c169-4cd9-8b2a-19a8b1618a93".to_string(), // from sql fixture
                     hostname: None,
                     tenant_keyset_ids: vec![],
+                    metadata_token_required: None,
+                    metadata_token_hop_limit: None,
                 }),
                 os: Some(forge::OperatingSystem {
                     phone_home_enabled: false,
@@ -711,6 +721,8 @@ async fn test_reject_zero_dpu_instance_allocation_multiple_vpcs(
                     tenant_organization_id: "2829bbe3-c169-4cd9-8b2a-19a8b1618a93".to_string(), // from sql fixture
                     hostname: None,
                     tenant_keyset_ids: vec![],
+                    metadata_token_required: None,
+                    metadata_token_hop_limit: None,
                 }),
                 os: Some(forge::OperatingSystem {
                     phone_home_enabled: false,
@@ -769,6 +781,8 @@ async fn test_single_dpu_instance_allocation(
                     tenant_organization_id: "2829bbe3-c169-4cd9-8b2a-19a8b1618a93".to_string(), // from sql fixture
                     hostname: None,
                     tenant_keyset_ids: vec![],
+                    metadata_token_required: None,
+                    metadata_token_hop_limit: None,
                 }),
                 os: Some(forge::OperatingSystem {
                     phone_home_enabled: false,
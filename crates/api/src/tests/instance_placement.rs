@@ -0,0 +1,211 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Tests for allocating instances by InstanceType with placement policies
+
+use ::rpc::forge::forge_server::Forge;
+use carbide_uuid::network::NetworkSegmentId;
+use common::api_fixtures::instance::{
+    default_os_config, default_tenant_config, single_interface_network_config,
+};
+use common::api_fixtures::{TestEnv, create_managed_host, create_test_env};
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+use tonic::Code;
+
+use crate::tests::common;
+
+/// Creates an InstanceType without capability filters and associates `host_count` new hosts
+async fn create_instance_type_with_hosts(env: &TestEnv, host_count: usize) -> String {
+    let instance_type_id = "placement-test-type".to_string();
+    env.api
+        .create_instance_type(tonic::Request::new(rpc::forge::CreateInstanceTypeRequest {
+            id: Some(instance_type_id.clone()),
+            metadata: Some(rpc::forge::Metadata {
+                name: "placement test type".to_string(),
+                description: "".to_string(),
+                labels: vec![],
+            }),
+            instance_type_attributes: None,
+        }))
+        .await
+        .unwrap();
+
+    let mut machine_ids = Vec::with_capacity(host_count);
+    for _ in 0..host_count {
+        machine_ids.push(create_managed_host(env).await.host().id.to_string());
+    }
+
+    env.api
+        .associate_machines_with_instance_type(tonic::Request::new(
+            rpc::forge::AssociateMachinesWithInstanceTypeRequest {
+                instance_type_id: instance_type_id.clone(),
+                machine_ids,
+            },
+        ))
+        .await
+        .unwrap();
+
+    instance_type_id
+}
+
+fn build_instance_request(segment_id: NetworkSegmentId) -> rpc::forge::InstanceAllocationRequest {
+    rpc::forge::InstanceAllocationRequest {
+        machine_id: None,
+        config: Some(rpc::forge::InstanceConfig {
+            tenant: Some(default_tenant_config()),
+            os: Some(default_os_config()),
+            network: Some(single_interface_network_config(segment_id)),
+            infiniband: None,
+            network_security_group_id: None,
+            dpu_extension_services: None,
+            nvlink: None,
+        }),
+        instance_id: None,
+        instance_type_id: None,
+        metadata: Some(rpc::forge::Metadata {
+            name: format!("test-instance-{}", uuid::Uuid::new_v4()),
+            description: "Test instance for placement".to_string(),
+            labels: vec![],
+        }),
+        allow_unhealthy_machine: false,
+    }
+}
+
+fn build_request(
+    instance_type_id: &str,
+    placement_policy: rpc::forge::InstancePlacementPolicy,
+    segment_id: NetworkSegmentId,
+    count: usize,
+) -> rpc::forge::InstanceTypeAllocationRequest {
+    rpc::forge::InstanceTypeAllocationRequest {
+        instance_type_id: instance_type_id.to_string(),
+        placement_policy: placement_policy.into(),
+        instance_requests: (0..count)
+            .map(|_| build_instance_request(segment_id))
+            .collect(),
+    }
+}
+
+/// Allocate instances by InstanceType until the InstanceType has no Machines left.
+#[crate::sqlx_test]
+async fn test_allocate_instances_by_instance_type(_: PgPoolOptions, options: PgConnectOptions) {
+    let pool = PgPoolOptions::new().connect_with(options).await.unwrap();
+    let env = create_test_env(pool).await;
+    let segment_id = env.create_vpc_and_tenant_segment().await;
+    let instance_type_id = create_instance_type_with_hosts(&env, 3).await;
+
+    let instances = env
+        .api
+        .allocate_instances_by_instance_type(tonic::Request::new(build_request(
+            &instance_type_id,
+            rpc::forge::InstancePlacementPolicy::PlacementAny,
+            segment_id,
+            2,
+        )))
+        .await
+        .unwrap()
+        .into_inner()
+        .instances;
+
+    assert_eq!(instances.len(), 2);
+    assert_ne!(instances[0].machine_id, instances[1].machine_id);
+    for instance in &instances {
+        assert_eq!(
+            instance.instance_type_id.as_deref(),
+            Some(instance_type_id.as_str())
+        );
+    }
+
+    // Only one Machine is left, so the whole batch must fail
+    let err = env
+        .api
+        .allocate_instances_by_instance_type(tonic::Request::new(build_request(
+            &instance_type_id,
+            rpc::forge::InstancePlacementPolicy::PlacementAny,
+            segment_id,
+            2,
+        )))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), Code::ResourceExhausted);
+
+    let instances = env
+        .api
+        .allocate_instances_by_instance_type(tonic::Request::new(build_request(
+            &instance_type_id,
+            rpc::forge::InstancePlacementPolicy::PlacementSpreadRacks,
+            segment_id,
+            1,
+        )))
+        .await
+        .unwrap()
+        .into_inner()
+        .instances;
+    assert_eq!(instances.len(), 1);
+}
+
+/// Hosts of the test environment have no rack, so no placement within a rack is possible.
+#[crate::sqlx_test]
+async fn test_allocate_instances_by_instance_type_same_rack_without_racks(
+    _: PgPoolOptions,
+    options: PgConnectOptions,
+) {
+    let pool = PgPoolOptions::new().connect_with(options).await.unwrap();
+    let env = create_test_env(pool).await;
+    let segment_id = env.create_vpc_and_tenant_segment().await;
+    let instance_type_id = create_instance_type_with_hosts(&env, 2).await;
+
+    let err = env
+        .api
+        .allocate_instances_by_instance_type(tonic::Request::new(build_request(
+            &instance_type_id,
+            rpc::forge::InstancePlacementPolicy::PlacementSameRack,
+            segment_id,
+            2,
+        )))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), Code::ResourceExhausted);
+}
+
+/// Machines are picked by placement, so requests must not name one.
+#[crate::sqlx_test]
+async fn test_allocate_instances_by_instance_type_rejects_machine_id(
+    _: PgPoolOptions,
+    options: PgConnectOptions,
+) {
+    let pool = PgPoolOptions::new().connect_with(options).await.unwrap();
+    let env = create_test_env(pool).await;
+    let segment_id = env.create_vpc_and_tenant_segment().await;
+    let instance_type_id = create_instance_type_with_hosts(&env, 1).await;
+    let mh = create_managed_host(&env).await;
+
+    let mut request = build_request(
+        &instance_type_id,
+        rpc::forge::InstancePlacementPolicy::PlacementAny,
+        segment_id,
+        1,
+    );
+    request.instance_requests[0].machine_id = Some(mh.host().id);
+
+    let err = env
+        .api
+        .allocate_instances_by_instance_type(tonic::Request::new(request))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), Code::InvalidArgument);
+}
@@ -0,0 +1,523 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Tests for instance lifecycle webhooks
+
+use std::sync::atomic::{AtomicU16, Ordering};
+use std::sync::{Arc, Mutex};
+
+use axum::Router;
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode};
+use axum::routing::post;
+use bytes::Bytes;
+use carbide_uuid::instance::InstanceId;
+use common::api_fixtures::{
+    TestEnvOverrides, create_managed_host, create_test_env, create_test_env_with_overrides,
+    get_config,
+};
+use forge_secrets::credentials::{CredentialKey, CredentialProvider, Credentials};
+use model::instance_webhook::{
+    DELIVERY_HEADER, EVENT_HEADER, InstanceLifecycleEvent, InstanceLifecycleEventType,
+    SIGNATURE_HEADER, TIMESTAMP_HEADER, sign_payload,
+};
+use rpc::forge::forge_server::Forge;
+use rpc::forge::{
+    DeleteInstanceWebhookSubscriptionRequest, InstanceWebhookSearchFilter,
+    InstanceWebhookSubscription, RetryInstanceWebhookDeadLettersRequest,
+};
+use tonic::Code;
+
+use crate::instance_webhook_dispatcher::{DispatchSummary, InstanceWebhookDispatcher};
+use crate::tests::common;
+use crate::tests::common::api_fixtures::TestEnv;
+
+const SECRET: &str = "webhook-test-secret";
+
+#[derive(Clone)]
+struct Receiver {
+    status: Arc<AtomicU16>,
+    requests: Arc<Mutex<Vec<(HeaderMap, Bytes)>>>,
+}
+
+impl Receiver {
+    /// Starts a webhook receiver on a random local port, and returns it with its URL
+    async fn start() -> (Self, String) {
+        let receiver = Receiver {
+            status: Arc::new(AtomicU16::new(StatusCode::OK.as_u16())),
+            requests: Arc::default(),
+        };
+        let router = Router::new()
+            .route("/hook", post(Self::handle))
+            .with_state(receiver.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await });
+        (receiver, url)
+    }
+
+    async fn handle(
+        State(receiver): State<Receiver>,
+        headers: HeaderMap,
+        body: Bytes,
+    ) -> StatusCode {
+        receiver.requests.lock().unwrap().push((headers, body));
+        StatusCode::from_u16(receiver.status.load(Ordering::SeqCst)).unwrap()
+    }
+
+    fn respond_with(&self, status: StatusCode) {
+        self.status.store(status.as_u16(), Ordering::SeqCst);
+    }
+
+    /// Returns the events received so far, after checking their signatures
+    fn take_events(&self) -> Vec<InstanceLifecycleEvent> {
+        std::mem::take(&mut *self.requests.lock().unwrap())
+            .into_iter()
+            .map(|(headers, body)| {
+                let header = |name: &str| headers.get(name).unwrap().to_str().unwrap().to_string();
+                let timestamp: i64 = header(TIMESTAMP_HEADER).parse().unwrap();
+                assert_eq!(
+                    header(SIGNATURE_HEADER),
+                    sign_payload(SECRET, timestamp, &body)
+                );
+                assert!(header(DELIVERY_HEADER).parse::<i64>().is_ok());
+                let event: InstanceLifecycleEvent = serde_json::from_slice(&body).unwrap();
+                assert_eq!(header(EVENT_HEADER), event.event_type.to_string());
+                event
+            })
+            .collect()
+    }
+}
+
+fn subscription(tenant: &str, url: &str) -> InstanceWebhookSubscription {
+    InstanceWebhookSubscription {
+        tenant_organization_id: tenant.to_string(),
+        url: url.to_string(),
+        secret: Some(SECRET.to_string()),
+        ..Default::default()
+    }
+}
+
+fn dispatcher(env: &TestEnv, max_attempts: u32) -> InstanceWebhookDispatcher {
+    let mut config = (*env.config).clone();
+    config.instance_webhooks.max_attempts = max_attempts;
+    config.instance_webhooks.initial_backoff = std::time::Duration::ZERO;
+    InstanceWebhookDispatcher::new(
+        env.pool.clone(),
+        Arc::new(config),
+        env.api.credential_provider.clone(),
+        env.api.work_lock_manager_handle.clone(),
+    )
+    .unwrap()
+}
+
+#[crate::sqlx_test]
+async fn test_instance_webhook_subscription_crud(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool).await;
+
+    // A secret is generated and returned once if none is given
+    let created = env
+        .api
+        .create_instance_webhook_subscription(tonic::Request::new(InstanceWebhookSubscription {
+            secret: None,
+            event_types: vec![rpc::forge::InstanceLifecycleEventType::InstanceEventReady as i32],
+            ..subscription("Tenant1", "https://hooks.example.com/carbide")
+        }))
+        .await?
+        .into_inner();
+    assert_eq!(created.secret.as_ref().map(|secret| secret.len()), Some(64));
+    // The secret is kept in the credential provider
+    let secret_key = CredentialKey::InstanceWebhookSecret {
+        subscription_id: created.id.clone(),
+    };
+    match env
+        .test_credential_provider
+        .get_credentials(&secret_key)
+        .await?
+    {
+        Some(Credentials::UsernamePassword { password, .. }) => {
+            assert_eq!(Some(password), created.secret)
+        }
+        None => panic!("secret of subscription was not stored"),
+    }
+    env.api
+        .create_instance_webhook_subscription(tonic::Request::new(subscription(
+            "Tenant2",
+            "https://hooks.example.com/other",
+        )))
+        .await?;
+
+    let all = env
+        .api
+        .find_instance_webhook_subscriptions(tonic::Request::new(Default::default()))
+        .await?
+        .into_inner()
+        .subscriptions;
+    assert_eq!(all.len(), 2);
+    // Secrets are never returned after creation
+    assert!(all.iter().all(|subscription| subscription.secret.is_none()));
+
+    let tenant1 = env
+        .api
+        .find_instance_webhook_subscriptions(tonic::Request::new(InstanceWebhookSearchFilter {
+            tenant_organization_id: Some("Tenant1".to_string()),
+        }))
+        .await?
+        .into_inner()
+        .subscriptions;
+    assert_eq!(tenant1.len(), 1);
+    assert_eq!(tenant1[0].id, created.id);
+    assert_eq!(tenant1[0].event_types, created.event_types);
+
+    // Invalid subscriptions are rejected
+    for invalid in [
+        subscription("Tenant1", "ftp://hooks.example.com"),
+        subscription("not a tenant", "https://hooks.example.com"),
+        InstanceWebhookSubscription {
+            secret: Some("short".to_string()),
+            ..subscription("Tenant1", "https://hooks.example.com")
+        },
+    ] {
+        let err = env
+            .api
+            .create_instance_webhook_subscription(tonic::Request::new(invalid))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), Code::InvalidArgument);
+    }
+
+    env.api
+        .delete_instance_webhook_subscription(tonic::Request::new(
+            DeleteInstanceWebhookSubscriptionRequest {
+                id: created.id.clone(),
+            },
+        ))
+        .await?;
+    let err = env
+        .api
+        .delete_instance_webhook_subscription(tonic::Request::new(
+            DeleteInstanceWebhookSubscriptionRequest { id: created.id },
+        ))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), Code::NotFound);
+    assert!(
+        env.test_credential_provider
+            .get_credentials(&secret_key)
+            .await?
+            .is_none()
+    );
+
+    Ok(())
+}
+
+#[crate::sqlx_test]
+async fn test_instance_webhook_insecure_targets(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = get_config();
+    config.instance_webhooks.allow_insecure_targets = false;
+    let env = create_test_env_with_overrides(pool, TestEnvOverrides::with_config(config)).await;
+
+    // Only https URLs of public addresses are accepted
+    for url in [
+        "http://198.51.100.10/hook",
+        "https://localhost/hook",
+        "https://127.0.0.1/hook",
+        "https://10.217.5.1/hook",
+        "https://169.254.169.254/latest/meta-data",
+        "https://[::1]/hook",
+    ] {
+        let err = env
+            .api
+            .create_instance_webhook_subscription(tonic::Request::new(subscription("Tenant1", url)))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), Code::InvalidArgument, "{url}");
+    }
+
+    // Deliveries to forbidden addresses fail without a request being made
+    let (receiver, url) = Receiver::start().await;
+    let mh = create_managed_host(&env).await;
+    let mut txn = env.pool.begin().await?;
+    let created = db::instance_webhook::create_subscription(
+        &mut txn,
+        &model::instance_webhook::InstanceWebhookSubscription::try_from(subscription(
+            "Tenant1", &url,
+        ))?,
+    )
+    .await?;
+    env.test_credential_provider
+        .set_credentials(
+            &CredentialKey::InstanceWebhookSecret {
+                subscription_id: created.id.to_string(),
+            },
+            &Credentials::UsernamePassword {
+                username: "Tenant1".to_string(),
+                password: SECRET.to_string(),
+            },
+        )
+        .await?;
+    db::instance_webhook::enqueue_event(
+        &mut txn,
+        &InstanceLifecycleEvent {
+            event_type: InstanceLifecycleEventType::Allocated,
+            instance_id: InstanceId::new(),
+            machine_id: mh.host().id,
+            tenant_organization_id: "Tenant1".parse()?,
+            occurred_at: chrono::Utc::now(),
+        },
+    )
+    .await?;
+    txn.commit().await?;
+
+    assert_eq!(dispatcher(&env, 3).run_single_iteration().await?.retried, 1);
+    assert!(receiver.take_events().is_empty());
+
+    Ok(())
+}
+
+#[crate::sqlx_test]
+async fn test_instance_webhook_lifecycle_events(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool).await;
+    let (receiver, url) = Receiver::start().await;
+    let dispatcher = dispatcher(&env, 3);
+
+    env.api
+        .create_instance_webhook_subscription(tonic::Request::new(subscription("Tenant1", &url)))
+        .await?;
+    // Events are only delivered to the subscriptions of the tenant of the instance
+    env.api
+        .create_instance_webhook_subscription(tonic::Request::new(subscription(
+            "Tenant2",
+            "http://127.0.0.1:1/unreachable",
+        )))
+        .await?;
+
+    let segment_id = env.create_vpc_and_tenant_segment().await;
+    let mh = create_managed_host(&env).await;
+    let tinstance = mh
+        .instance_builer(&env)
+        .single_interface_network_config(segment_id)
+        .build()
+        .await;
+
+    assert_eq!(
+        dispatcher.run_single_iteration().await?,
+        DispatchSummary {
+            delivered: 3,
+            retried: 0,
+            dead_lettered: 0,
+            pruned: 0,
+        }
+    );
+    let events = receiver.take_events();
+    assert_eq!(
+        events
+            .iter()
+            .map(|event| event.event_type)
+            .collect::<Vec<_>>(),
+        vec![
+            InstanceLifecycleEventType::Allocated,
+            InstanceLifecycleEventType::NetworkConfigSynced,
+            InstanceLifecycleEventType::Ready,
+        ]
+    );
+    for event in events {
+        assert_eq!(event.instance_id, tinstance.id);
+        assert_eq!(event.machine_id, mh.host().id);
+        assert_eq!(event.tenant_organization_id.as_str(), "Tenant1");
+    }
+
+    // Delivered events are not sent again
+    assert_eq!(
+        dispatcher.run_single_iteration().await?,
+        DispatchSummary::default()
+    );
+
+    tinstance.delete().await;
+    dispatcher.run_single_iteration().await?;
+    assert_eq!(
+        receiver
+            .take_events()
+            .iter()
+            .map(|event| event.event_type)
+            .collect::<Vec<_>>(),
+        vec![
+            InstanceLifecycleEventType::ReleaseStarted,
+            InstanceLifecycleEventType::CleanedUp,
+        ]
+    );
+
+    Ok(())
+}
+
+#[crate::sqlx_test]
+async fn test_instance_webhook_dead_letters(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool).await;
+    let (receiver, url) = Receiver::start().await;
+    receiver.respond_with(StatusCode::INTERNAL_SERVER_ERROR);
+    let dispatcher = dispatcher(&env, 2);
+    let mh = create_managed_host(&env).await;
+
+    env.api
+        .create_instance_webhook_subscription(tonic::Request::new(subscription("Tenant1", &url)))
+        .await?;
+
+    let mut txn = env.pool.begin().await?;
+    let event = InstanceLifecycleEvent {
+        event_type: InstanceLifecycleEventType::Allocated,
+        instance_id: InstanceId::new(),
+        machine_id: mh.host().id,
+        tenant_organization_id: "Tenant1".parse()?,
+        occurred_at: chrono::Utc::now(),
+    };
+    assert_eq!(
+        db::instance_webhook::enqueue_event(&mut txn, &event).await?,
+        1
+    );
+    txn.commit().await?;
+
+    // The first failure is retried, the second one runs out of attempts
+    assert_eq!(dispatcher.run_single_iteration().await?.retried, 1);
+    assert_eq!(dispatcher.run_single_iteration().await?.dead_lettered, 1);
+    assert_eq!(receiver.take_events(), vec![event.clone(), event.clone()]);
+    assert_eq!(
+        dispatcher.run_single_iteration().await?,
+        DispatchSummary::default()
+    );
+
+    let dead_letters = env
+        .api
+        .find_instance_webhook_dead_letters(tonic::Request::new(InstanceWebhookSearchFilter {
+            tenant_organization_id: Some("Tenant1".to_string()),
+        }))
+        .await?
+        .into_inner()
+        .deliveries;
+    assert_eq!(dead_letters.len(), 1);
+    assert_eq!(dead_letters[0].attempts, 2);
+    assert_eq!(dead_letters[0].instance_id, Some(event.instance_id));
+    assert!(
+        dead_letters[0]
+            .last_error
+            .as_ref()
+            .is_some_and(|error| error.contains("500"))
+    );
+    assert!(dead_letters[0].dead_lettered.is_some());
+    assert!(
+        env.api
+            .find_instance_webhook_dead_letters(tonic::Request::new(InstanceWebhookSearchFilter {
+                tenant_organization_id: Some("Tenant2".to_string()),
+            }))
+            .await?
+            .into_inner()
+            .deliveries
+            .is_empty()
+    );
+
+    // Retried dead letters get a fresh set of attempts
+    receiver.respond_with(StatusCode::OK);
+    let retried = env
+        .api
+        .retry_instance_webhook_dead_letters(tonic::Request::new(
+            RetryInstanceWebhookDeadLettersRequest {
+                delivery_ids: vec![dead_letters[0].id],
+            },
+        ))
+        .await?
+        .into_inner();
+    assert_eq!(retried.retried_count, 1);
+    assert_eq!(dispatcher.run_single_iteration().await?.delivered, 1);
+    assert_eq!(receiver.take_events(), vec![event]);
+    assert!(
+        env.api
+            .find_instance_webhook_dead_letters(tonic::Request::new(Default::default()))
+            .await?
+            .into_inner()
+            .deliveries
+            .is_empty()
+    );
+
+    Ok(())
+}
+
+#[crate::sqlx_test]
+async fn test_instance_webhook_dead_letter_pruning(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool).await;
+    let (receiver, url) = Receiver::start().await;
+    receiver.respond_with(StatusCode::INTERNAL_SERVER_ERROR);
+    let mh = create_managed_host(&env).await;
+
+    env.api
+        .create_instance_webhook_subscription(tonic::Request::new(subscription("Tenant1", &url)))
+        .await?;
+
+    let mut txn = env.pool.begin().await?;
+    db::instance_webhook::enqueue_event(
+        &mut txn,
+        &InstanceLifecycleEvent {
+            event_type: InstanceLifecycleEventType::Ready,
+            instance_id: InstanceId::new(),
+            machine_id: mh.host().id,
+            tenant_organization_id: "Tenant1".parse()?,
+            occurred_at: chrono::Utc::now(),
+        },
+    )
+    .await?;
+    txn.commit().await?;
+
+    // Dead letters are kept for the retention period
+    assert_eq!(
+        dispatcher(&env, 1).run_single_iteration().await?,
+        DispatchSummary {
+            delivered: 0,
+            retried: 0,
+            dead_lettered: 1,
+            pruned: 0,
+        }
+    );
+
+    let mut config = (*env.config).clone();
+    config.instance_webhooks.dead_letter_retention = std::time::Duration::ZERO;
+    let dispatcher = InstanceWebhookDispatcher::new(
+        env.pool.clone(),
+        Arc::new(config),
+        env.api.credential_provider.clone(),
+        env.api.work_lock_manager_handle.clone(),
+    )
+    .unwrap();
+    assert_eq!(dispatcher.run_single_iteration().await?.pruned, 1);
+    assert!(
+        env.api
+            .find_instance_webhook_dead_letters(tonic::Request::new(Default::default()))
+            .await?
+            .into_inner()
+            .deliveries
+            .is_empty()
+    );
+
+    Ok(())
+}
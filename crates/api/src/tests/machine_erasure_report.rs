@@ -0,0 +1,345 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Tests for the certificate-of-erasure reports of host cleanups
+
+use carbide_uuid::machine::MachineId;
+use common::api_fixtures::{
+    TestEnvOverrides, create_managed_host, create_test_env, create_test_env_with_overrides,
+    get_config,
+};
+use jsonwebtoken::{Algorithm, DecodingKey, Validation};
+use rpc::forge::forge_server::Forge;
+use rpc::forge::machine_cleanup_info::{CleanupResult, CleanupStepResult};
+use rpc::forge::{
+    MachineCleanupInfo, MachineErasureReportsRequest, StorageErasureMethod, StorageErasureRecord,
+    StorageErasureVerificationSample,
+};
+use tonic::Code;
+
+use crate::tests::common;
+use crate::tests::common::api_fixtures::TestEnv;
+
+fn erasure_record(device: &str, success: bool) -> StorageErasureRecord {
+    StorageErasureRecord {
+        device: device.to_string(),
+        serial_number: format!("SN-{device}"),
+        model: "SAMSUNG MZQL23T8HCLS".to_string(),
+        firmware_version: "GDC5502Q".to_string(),
+        capacity_bytes: 3_840_755_982_336,
+        method: StorageErasureMethod::NvmeCryptoErase as i32,
+        success,
+        error: (!success).then(|| "format failed".to_string()),
+        verification_samples: vec![StorageErasureVerificationSample {
+            offset_bytes: 0,
+            length_bytes: 4096,
+            zeroed: false,
+            changed: success,
+        }],
+        started_at: Some(chrono::Utc::now().into()),
+        completed_at: Some(chrono::Utc::now().into()),
+    }
+}
+
+async fn complete_cleanup(
+    env: &TestEnv,
+    machine_id: MachineId,
+    nvme_result: CleanupResult,
+    sata_sas_result: CleanupResult,
+    erasure_records: Vec<StorageErasureRecord>,
+) {
+    env.api
+        .cleanup_machine_completed(tonic::Request::new(MachineCleanupInfo {
+            machine_id: Some(machine_id),
+            nvme: Some(CleanupStepResult {
+                result: nvme_result as i32,
+                message: String::new(),
+            }),
+            ram: None,
+            mem_overwrite: None,
+            ib: None,
+            sata_sas: Some(CleanupStepResult {
+                result: sata_sas_result as i32,
+                message: "drive is frozen".to_string(),
+            }),
+            result: if nvme_result == CleanupResult::Error {
+                nvme_result
+            } else {
+                sata_sas_result
+            } as i32,
+            erasure_records,
+        }))
+        .await
+        .unwrap();
+}
+
+async fn reports_of_machine(
+    env: &TestEnv,
+    machine_id: MachineId,
+) -> Vec<rpc::forge::MachineErasureReport> {
+    env.api
+        .get_machine_erasure_reports(tonic::Request::new(MachineErasureReportsRequest {
+            machine_id: Some(machine_id),
+            instance_id: None,
+        }))
+        .await
+        .unwrap()
+        .into_inner()
+        .reports
+}
+
+#[crate::sqlx_test]
+async fn test_erasure_report_of_instance_release(pool: sqlx::PgPool) {
+    let env = create_test_env(pool).await;
+    let segment_id = env.create_vpc_and_tenant_segment().await;
+    let mh = create_managed_host(&env).await;
+    let tinstance = mh
+        .instance_builer(&env)
+        .single_interface_network_config(segment_id)
+        .build()
+        .await;
+    let instance_id = tinstance.id;
+    tinstance.delete().await;
+
+    // Releasing the instance opens the report
+    let request = MachineErasureReportsRequest {
+        machine_id: None,
+        instance_id: Some(instance_id),
+    };
+    let reports = env
+        .api
+        .get_machine_erasure_reports(tonic::Request::new(request.clone()))
+        .await
+        .unwrap()
+        .into_inner()
+        .reports;
+    assert_eq!(reports.len(), 1);
+    assert_eq!(reports[0].machine_id, Some(mh.host().id));
+    assert_eq!(
+        reports[0].tenant_organization_id.as_deref(),
+        Some("Tenant1")
+    );
+    assert!(reports[0].released_at.is_some());
+    assert!(reports[0].completed_at.is_none());
+    assert!(!reports[0].complete);
+
+    // A failed SATA/SAS erasure fails the cleanup like a failed NVMe erasure
+    complete_cleanup(
+        &env,
+        mh.host().id,
+        CleanupResult::Ok,
+        CleanupResult::Error,
+        vec![
+            erasure_record("/dev/nvme0", true),
+            erasure_record("/dev/sda", false),
+        ],
+    )
+    .await;
+
+    let reports = env
+        .api
+        .get_machine_erasure_reports(tonic::Request::new(request.clone()))
+        .await
+        .unwrap()
+        .into_inner()
+        .reports;
+    assert_eq!(reports.len(), 1);
+    let report = &reports[0];
+    assert_eq!(report.instance_id, Some(instance_id));
+    assert!(report.completed_at.is_none());
+    assert_eq!(report.records.len(), 2);
+    assert_eq!(report.records[1].error.as_deref(), Some("format failed"));
+    assert!(!report.complete);
+
+    let mut txn = env.pool.begin().await.unwrap();
+    let host = mh.host().db_machine(&mut txn).await;
+    assert_eq!(
+        host.failure_details.cause,
+        model::machine::FailureCause::SataSasCleanFailed {
+            err: "drive is frozen".to_string()
+        }
+    );
+
+    // The retried cleanup completes the report
+    complete_cleanup(
+        &env,
+        mh.host().id,
+        CleanupResult::Ok,
+        CleanupResult::Ok,
+        vec![
+            erasure_record("/dev/nvme0", true),
+            erasure_record("/dev/sda", true),
+        ],
+    )
+    .await;
+
+    let reports = env
+        .api
+        .get_machine_erasure_reports(tonic::Request::new(request))
+        .await
+        .unwrap()
+        .into_inner()
+        .reports;
+    assert_eq!(reports.len(), 1);
+    let report = &reports[0];
+    assert!(report.completed_at.is_some());
+    assert_eq!(report.records.len(), 2);
+    assert!(report.complete);
+    // No signing key is configured
+    assert!(report.signed_report.is_none());
+}
+
+#[crate::sqlx_test]
+async fn test_erasure_report_without_release(pool: sqlx::PgPool) {
+    let env = create_test_env(pool).await;
+    let mh = create_managed_host(&env).await;
+
+    // Cleanups without records and without a released instance don't create reports
+    complete_cleanup(
+        &env,
+        mh.host().id,
+        CleanupResult::Ok,
+        CleanupResult::Ok,
+        vec![],
+    )
+    .await;
+    assert!(reports_of_machine(&env, mh.host().id).await.is_empty());
+
+    complete_cleanup(
+        &env,
+        mh.host().id,
+        CleanupResult::Ok,
+        CleanupResult::Ok,
+        vec![erasure_record("/dev/nvme0", true)],
+    )
+    .await;
+    complete_cleanup(
+        &env,
+        mh.host().id,
+        CleanupResult::Ok,
+        CleanupResult::Ok,
+        vec![erasure_record("/dev/nvme0", true)],
+    )
+    .await;
+    let reports = reports_of_machine(&env, mh.host().id).await;
+    assert_eq!(reports.len(), 2);
+    for report in reports {
+        assert!(report.instance_id.is_none());
+        assert!(report.tenant_organization_id.is_none());
+        assert!(report.complete);
+        assert_eq!(
+            report.records[0].method,
+            StorageErasureMethod::NvmeCryptoErase as i32
+        );
+    }
+}
+
+#[crate::sqlx_test]
+async fn test_failed_cleanup_keeps_erasure_report_pending(pool: sqlx::PgPool) {
+    let env = create_test_env(pool).await;
+    let mh = create_managed_host(&env).await;
+
+    complete_cleanup(
+        &env,
+        mh.host().id,
+        CleanupResult::Error,
+        CleanupResult::Ok,
+        vec![erasure_record("/dev/nvme0", false)],
+    )
+    .await;
+    let reports = reports_of_machine(&env, mh.host().id).await;
+    assert_eq!(reports.len(), 1);
+    assert!(reports[0].completed_at.is_none());
+    assert!(!reports[0].records[0].success);
+
+    // The retried cleanup completes the same report
+    complete_cleanup(
+        &env,
+        mh.host().id,
+        CleanupResult::Ok,
+        CleanupResult::Ok,
+        vec![erasure_record("/dev/nvme0", true)],
+    )
+    .await;
+    let completed = reports_of_machine(&env, mh.host().id).await;
+    assert_eq!(completed.len(), 1);
+    assert_eq!(completed[0].id, reports[0].id);
+    assert!(completed[0].completed_at.is_some());
+    assert!(completed[0].complete);
+}
+
+#[crate::sqlx_test]
+async fn test_signed_erasure_report(pool: sqlx::PgPool) {
+    let key_pair = rcgen::KeyPair::generate().unwrap();
+    let key_path =
+        std::env::temp_dir().join(format!("erasure-report-{}.pem", uuid::Uuid::new_v4()));
+    std::fs::write(&key_path, key_pair.serialize_pem()).unwrap();
+
+    let env = {
+        let mut config = get_config();
+        config.erasure_reports.signing_key_path = Some(key_path.clone());
+        config.erasure_reports.signing_key_id = "erasure-test".to_string();
+        create_test_env_with_overrides(pool, TestEnvOverrides::with_config(config)).await
+    };
+    let mh = create_managed_host(&env).await;
+    complete_cleanup(
+        &env,
+        mh.host().id,
+        CleanupResult::Ok,
+        CleanupResult::Ok,
+        vec![erasure_record("/dev/nvme0", true)],
+    )
+    .await;
+    std::fs::remove_file(&key_path).unwrap();
+
+    let reports = reports_of_machine(&env, mh.host().id).await;
+    assert_eq!(reports.len(), 1);
+    let signed_report = reports[0].signed_report.as_deref().unwrap();
+
+    let header = jsonwebtoken::decode_header(signed_report).unwrap();
+    assert_eq!(header.kid.as_deref(), Some("erasure-test"));
+
+    let mut validation = Validation::new(Algorithm::ES256);
+    validation.required_spec_claims.clear();
+    validation.validate_exp = false;
+    validation.set_issuer(&["carbide-api"]);
+    let claims = jsonwebtoken::decode::<serde_json::Value>(
+        signed_report,
+        &DecodingKey::from_ec_pem(key_pair.public_key_pem().as_bytes()).unwrap(),
+        &validation,
+    )
+    .unwrap()
+    .claims;
+    assert_eq!(claims["jti"], reports[0].id);
+    assert_eq!(claims["sub"], mh.host().id.to_string());
+    assert_eq!(claims["complete"], true);
+    assert_eq!(claims["records"][0]["device"], "/dev/nvme0");
+}
+
+#[crate::sqlx_test]
+async fn test_get_erasure_reports_requires_filter(pool: sqlx::PgPool) {
+    let env = create_test_env(pool).await;
+    let err = env
+        .api
+        .get_machine_erasure_reports(tonic::Request::new(MachineErasureReportsRequest {
+            machine_id: None,
+            instance_id: None,
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), Code::InvalidArgument);
+}
@@ -17,6 +17,7 @@
 use common::api_fixtures::{create_managed_host, create_test_env};
 use config_version::ConfigVersion;
 use db::{self};
+use model::controller_outcome::PersistentStateHandlerOutcome;
 use model::machine::{MachineStateHistory, ManagedHostState};
 use rpc::forge::forge_server::Forge;
 
@@ -273,6 +274,121 @@ async fn test_old_machine_state_history(
     Ok(())
 }
 
+#[crate::sqlx_test]
+async fn test_state_handler_outcome_history(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool).await;
+    let (host_machine_id, dpu_machine_id) = create_managed_host(&env).await.into();
+
+    let mut rpc_histories = env
+        .api
+        .find_state_handler_outcome_history(tonic::Request::new(
+            rpc::forge::StateHandlerOutcomeHistoryRequest {
+                machine_ids: vec![host_machine_id, dpu_machine_id],
+            },
+        ))
+        .await?
+        .into_inner();
+    // The state controller only acts on hosts
+    assert_eq!(rpc_histories.histories.len(), 1);
+    let rpc_history = rpc_histories
+        .histories
+        .remove(&host_machine_id.to_string())
+        .unwrap();
+    assert!(!rpc_history.records.is_empty());
+
+    for record in &rpc_history.records {
+        serde_json::from_str::<serde_json::Value>(&record.state)?;
+        assert!(record.handler_latency.is_some());
+        assert!(record.time.is_some());
+    }
+    let transition = rpc_history
+        .records
+        .iter()
+        .filter_map(|record| record.outcome.as_ref())
+        .find(|outcome| outcome.outcome() == rpc::forge::ControllerStateOutcome::Transition)
+        .expect("The host should have transitioned at least once");
+    assert!(
+        transition
+            .source_ref
+            .as_ref()
+            .is_some_and(|source_ref| source_ref.file.ends_with(".rs"))
+    );
+
+    // Consecutive identical outcomes are collapsed into one entry
+    let mut txn = env.pool.begin().await?;
+    for _ in 0..300 {
+        db::machine_state_handler_outcome_history::persist(
+            &mut txn,
+            &host_machine_id,
+            &ManagedHostState::Ready,
+            &PersistentStateHandlerOutcome::DoNothing { source_ref: None },
+            None,
+            std::time::Duration::from_millis(1),
+        )
+        .await?;
+    }
+    let mut histories = db::machine_state_handler_outcome_history::find_by_machine_ids(
+        &mut txn,
+        &[host_machine_id],
+    )
+    .await?;
+    let history = histories.remove(&host_machine_id).unwrap();
+    let last = history.last().unwrap();
+    assert_eq!(last.repeat_count, 300);
+    assert!(last.last_time >= last.time);
+    assert!(matches!(
+        last.outcome,
+        PersistentStateHandlerOutcome::DoNothing { .. }
+    ));
+
+    // Check if older outcomes get deleted
+    for i in 0..300 {
+        db::machine_state_handler_outcome_history::persist(
+            &mut txn,
+            &host_machine_id,
+            &ManagedHostState::Ready,
+            &PersistentStateHandlerOutcome::Wait {
+                reason: format!("Waiting {i}"),
+                source_ref: None,
+            },
+            None,
+            std::time::Duration::from_millis(1),
+        )
+        .await?;
+    }
+    db::machine_state_handler_outcome_history::prune(&mut txn, 250).await?;
+    let mut histories = db::machine_state_handler_outcome_history::find_by_machine_ids(
+        &mut txn,
+        &[host_machine_id],
+    )
+    .await?;
+    txn.commit().await?;
+    let history = histories.remove(&host_machine_id).unwrap();
+    assert_eq!(history.len(), 250);
+    assert!(history.iter().all(|record| record.repeat_count == 1
+        && matches!(record.outcome, PersistentStateHandlerOutcome::Wait { .. })));
+    assert!(matches!(
+        &history.last().unwrap().outcome,
+        PersistentStateHandlerOutcome::Wait { reason, .. } if reason == "Waiting 299"
+    ));
+
+    // At least one ID needs to be passed
+    let err = env
+        .api
+        .find_state_handler_outcome_history(tonic::Request::new(
+            rpc::forge::StateHandlerOutcomeHistoryRequest {
+                machine_ids: vec![],
+            },
+        ))
+        .await
+        .expect_err("Request without IDs should fail");
+    assert_eq!(err.code(), tonic::Code::InvalidArgument);
+
+    Ok(())
+}
+
 fn json_history(history: &[MachineStateHistory]) -> serde_json::Result<Vec<serde_json::Value>> {
     // // Check that version numbers are always incrementing by 1
     if !history.is_empty() {
@@ -207,6 +207,8 @@ async fn test_managed_host_network_config_with_extension_services(pool: sqlx::Pg
                 description: "".to_string(),
                 labels: vec![],
             }),
+            metadata_token_required: None,
+            metadata_token_hop_limit: None,
         }))
         .await
         .unwrap();
@@ -306,6 +308,97 @@ async fn test_managed_host_network_config_with_extension_services(pool: sqlx::Pg
     assert_eq!(response.dpu_extension_services[1].removed, None);
 }
 
+#[crate::sqlx_test]
+async fn test_managed_host_network_config_fmds_token_required(pool: sqlx::PgPool) {
+    let env = api_fixtures::create_test_env(pool).await;
+    let segment_id = env.create_vpc_and_tenant_segment().await;
+    let mh = create_managed_host(&env).await;
+
+    let tenant_org = "token_org";
+    env.api
+        .create_tenant(tonic::Request::new(rpc::forge::CreateTenantRequest {
+            organization_id: tenant_org.to_string(),
+            routing_profile_type: None,
+            metadata: Some(rpc::forge::Metadata {
+                name: tenant_org.to_string(),
+                description: "".to_string(),
+                labels: vec![],
+            }),
+            metadata_token_required: Some(true),
+            metadata_token_hop_limit: Some(1),
+        }))
+        .await
+        .unwrap();
+
+    mh.instance_builer(&env)
+        .single_interface_network_config(segment_id)
+        .tenant_org(tenant_org)
+        .build()
+        .await;
+
+    // The instance inherits the settings of its tenant
+    let response = env
+        .api
+        .get_managed_host_network_config(tonic::Request::new(ManagedHostNetworkConfigRequest {
+            dpu_machine_id: Some(mh.dpu().id),
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(response.fmds_token_required);
+    assert_eq!(response.fmds_token_hop_limit, Some(1));
+
+    let update = |metadata_token_hop_limit| rpc::forge::UpdateTenantRequest {
+        organization_id: tenant_org.to_string(),
+        metadata: Some(rpc::forge::Metadata {
+            name: tenant_org.to_string(),
+            description: "".to_string(),
+            labels: vec![],
+        }),
+        if_version_match: None,
+        routing_profile_type: None,
+        metadata_token_required: None,
+        metadata_token_hop_limit,
+    };
+    let err = env
+        .api
+        .update_tenant(tonic::Request::new(update(Some(256))))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::InvalidArgument);
+    // 0 removes the hop limit
+    env.api
+        .update_tenant(tonic::Request::new(update(Some(0))))
+        .await
+        .unwrap();
+
+    env.api
+        .update_tenant(tonic::Request::new(rpc::forge::UpdateTenantRequest {
+            organization_id: tenant_org.to_string(),
+            metadata: Some(rpc::forge::Metadata {
+                name: tenant_org.to_string(),
+                description: "".to_string(),
+                labels: vec![],
+            }),
+            if_version_match: None,
+            routing_profile_type: None,
+            metadata_token_required: Some(false),
+            metadata_token_hop_limit: None,
+        }))
+        .await
+        .unwrap();
+    let response = env
+        .api
+        .get_managed_host_network_config(tonic::Request::new(ManagedHostNetworkConfigRequest {
+            dpu_machine_id: Some(mh.dpu().id),
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(!response.fmds_token_required);
+    assert_eq!(response.fmds_token_hop_limit, None);
+}
+
 #[crate::sqlx_test]
 async fn test_dpu_health_is_required(pool: sqlx::PgPool) {
     let env = api_fixtures::create_test_env(pool).await;
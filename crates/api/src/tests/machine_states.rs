@@ -219,7 +219,9 @@ async fn test_nvme_clean_failed_state_host(pool: sqlx::PgPool) {
         ram: None,
         mem_overwrite: None,
         ib: None,
+        sata_sas: None,
         result: 0,
+        erasure_records: vec![],
     });
 
     env.api
@@ -263,7 +265,9 @@ async fn test_nvme_clean_failed_state_host(pool: sqlx::PgPool) {
         ram: None,
         mem_overwrite: None,
         ib: None,
+        sata_sas: None,
         result: 0,
+        erasure_records: vec![],
     });
     env.api
         .cleanup_machine_completed(clean_failed_req)
@@ -295,7 +299,9 @@ async fn test_nvme_clean_failed_state_host(pool: sqlx::PgPool) {
         ram: None,
         mem_overwrite: None,
         ib: None,
+        sata_sas: None,
         result: 0,
+        erasure_records: vec![],
     });
     env.api
         .cleanup_machine_completed(clean_succeeded_req)
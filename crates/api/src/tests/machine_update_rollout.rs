@@ -0,0 +1,349 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Tests for staged rollouts of machine updates
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use carbide_uuid::machine::MachineId;
+use common::api_fixtures::{
+    TestEnv, TestEnvOverrides, create_managed_host, create_test_env_with_overrides, get_config,
+};
+use model::machine::ManagedHostStateSnapshot;
+use model::machine_update_rollout::{MachineUpdateRollout, MachineUpdateRolloutState};
+use rpc::forge::forge_server::Forge;
+use sqlx::PgConnection;
+
+use crate::CarbideResult;
+use crate::cfg::file::MachineUpdateRolloutPolicy;
+use crate::machine_update_manager::MachineUpdateManager;
+use crate::machine_update_manager::machine_update_module::MachineUpdateModule;
+use crate::tests::common;
+
+/// Updates every host it is offered once. Updates stay in progress until they are finished by
+/// the test.
+#[derive(Clone, Default)]
+pub(crate) struct RolloutTestModule {
+    in_progress: Arc<Mutex<HashSet<MachineId>>>,
+    started: Arc<Mutex<Vec<MachineId>>>,
+}
+
+#[async_trait]
+impl MachineUpdateModule for RolloutTestModule {
+    async fn get_updates_in_progress(
+        &self,
+        _txn: &mut PgConnection,
+    ) -> CarbideResult<HashSet<MachineId>> {
+        Ok(self.in_progress.lock().unwrap().clone())
+    }
+
+    async fn start_updates(
+        &self,
+        _txn: &mut PgConnection,
+        available_updates: i32,
+        updating_machines: &HashSet<MachineId>,
+        snapshots: &HashMap<MachineId, ManagedHostStateSnapshot>,
+    ) -> CarbideResult<HashSet<MachineId>> {
+        let mut started = self.started.lock().unwrap();
+        let mut candidates: Vec<MachineId> = snapshots
+            .keys()
+            .filter(|id| !updating_machines.contains(id) && !started.contains(id))
+            .copied()
+            .collect();
+        candidates.sort();
+        candidates.truncate(available_updates.max(0) as usize);
+
+        started.extend(candidates.iter().copied());
+        self.in_progress
+            .lock()
+            .unwrap()
+            .extend(candidates.iter().copied());
+        Ok(candidates.into_iter().collect())
+    }
+
+    async fn clear_completed_updates(&self, _txn: &mut PgConnection) -> CarbideResult<()> {
+        Ok(())
+    }
+
+    async fn update_metrics(
+        &self,
+        _txn: &mut PgConnection,
+        _snapshots: &HashMap<MachineId, ManagedHostStateSnapshot>,
+    ) {
+    }
+}
+
+impl RolloutTestModule {
+    pub(crate) fn started(&self) -> Vec<MachineId> {
+        self.started.lock().unwrap().clone()
+    }
+
+    pub(crate) fn finish_updates(&self) {
+        self.in_progress.lock().unwrap().clear();
+    }
+}
+
+impl fmt::Display for RolloutTestModule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "RolloutTestModule")
+    }
+}
+
+async fn create_env(pool: sqlx::PgPool, host_count: usize) -> TestEnv {
+    let mut config = get_config();
+    config.machine_updater.rollout = Some(MachineUpdateRolloutPolicy {
+        canary_count: 1,
+        wave_percentages: vec![50],
+        soak_period: chrono::Duration::hours(1),
+        max_concurrent_updates_per_rack: None,
+        max_concurrent_updates_per_sku: None,
+        halt_unhealthy_percent: Some(25),
+    });
+    let env = create_test_env_with_overrides(pool, TestEnvOverrides::with_config(config)).await;
+    for _ in 0..host_count {
+        create_managed_host(&env).await;
+    }
+    env
+}
+
+fn create_manager(env: &TestEnv, module: &RolloutTestModule) -> MachineUpdateManager {
+    MachineUpdateManager::new_with_modules(
+        env.pool.clone(),
+        env.config.clone(),
+        vec![Box::new(module.clone())],
+        env.api.work_lock_manager_handle.clone(),
+    )
+}
+
+async fn latest_rollout(env: &TestEnv) -> MachineUpdateRollout {
+    let mut txn = env.pool.begin().await.unwrap();
+    let rollout = db::machine_update_rollout::find_latest(&mut txn, false)
+        .await
+        .unwrap()
+        .expect("a rollout should have been stored");
+    txn.commit().await.unwrap();
+    rollout
+}
+
+/// Pretends that the soak period of the current wave has passed
+async fn end_soak_period(env: &TestEnv) {
+    let mut rollout = latest_rollout(env).await;
+    rollout.wave_completed_at = rollout
+        .wave_completed_at
+        .map(|completed_at| completed_at - chrono::Duration::hours(2));
+    let mut txn = env.pool.begin().await.unwrap();
+    db::machine_update_rollout::save(&mut txn, &rollout)
+        .await
+        .unwrap();
+    txn.commit().await.unwrap();
+}
+
+async fn apply_action(
+    env: &TestEnv,
+    action: rpc::forge::MachineUpdateRolloutAction,
+) -> Result<rpc::forge::MachineUpdateRollout, tonic::Status> {
+    env.api
+        .machine_update_rollout_action(tonic::Request::new(
+            rpc::forge::MachineUpdateRolloutActionRequest {
+                action: action as i32,
+                reason: Some("test".to_string()),
+            },
+        ))
+        .await
+        .map(|response| response.into_inner())
+}
+
+#[crate::sqlx_test]
+async fn test_rollout_waves(pool: sqlx::PgPool) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_env(pool, 4).await;
+    let module = RolloutTestModule::default();
+    let manager = create_manager(&env, &module);
+
+    // No rollout is stored before it started any updates
+    let err = env
+        .api
+        .get_machine_update_rollout(tonic::Request::new(
+            rpc::forge::MachineUpdateRolloutRequest { id: None },
+        ))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::NotFound);
+
+    // The canary wave updates a single host, even after its update finished
+    manager.run_single_iteration().await?;
+    assert_eq!(module.started().len(), 1);
+    manager.run_single_iteration().await?;
+    module.finish_updates();
+    manager.run_single_iteration().await?;
+    assert_eq!(module.started().len(), 1);
+
+    let rollout = latest_rollout(&env).await;
+    assert_eq!(rollout.state, MachineUpdateRolloutState::Running);
+    assert_eq!(rollout.wave, 0);
+    assert!(rollout.wave_completed_at.is_some());
+
+    // The next wave starts after the soak period and covers 50% of the hosts
+    end_soak_period(&env).await;
+    manager.run_single_iteration().await?;
+    assert_eq!(latest_rollout(&env).await.wave, 1);
+    manager.run_single_iteration().await?;
+    assert_eq!(module.started().len(), 2);
+
+    let rollout = env
+        .api
+        .get_machine_update_rollout(tonic::Request::new(
+            rpc::forge::MachineUpdateRolloutRequest { id: None },
+        ))
+        .await?
+        .into_inner();
+    assert_eq!(rollout.wave, 1);
+    assert_eq!(rollout.wave_count, 3);
+    assert_eq!(
+        rollout
+            .hosts
+            .iter()
+            .map(|host| (host.machine_id.unwrap(), host.wave))
+            .collect::<Vec<_>>(),
+        vec![(module.started()[0], 0), (module.started()[1], 1)]
+    );
+
+    Ok(())
+}
+
+#[crate::sqlx_test]
+async fn test_rollout_actions(pool: sqlx::PgPool) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_env(pool, 4).await;
+    let module = RolloutTestModule::default();
+    let manager = create_manager(&env, &module);
+
+    manager.run_single_iteration().await?;
+    assert_eq!(module.started().len(), 1);
+    module.finish_updates();
+    manager.run_single_iteration().await?;
+    end_soak_period(&env).await;
+
+    // A paused rollout doesn't start updates or move on to the next wave
+    let rollout = apply_action(&env, rpc::forge::MachineUpdateRolloutAction::RolloutPause).await?;
+    assert_eq!(
+        rollout.state,
+        rpc::forge::MachineUpdateRolloutState::RolloutPaused as i32
+    );
+    assert_eq!(rollout.state_reason.as_deref(), Some("test"));
+    manager.run_single_iteration().await?;
+    manager.run_single_iteration().await?;
+    assert_eq!(module.started().len(), 1);
+    assert_eq!(latest_rollout(&env).await.wave, 0);
+
+    // Pausing twice is rejected
+    let err = apply_action(&env, rpc::forge::MachineUpdateRolloutAction::RolloutPause)
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::FailedPrecondition);
+
+    apply_action(&env, rpc::forge::MachineUpdateRolloutAction::RolloutResume).await?;
+    manager.run_single_iteration().await?;
+    manager.run_single_iteration().await?;
+    assert_eq!(module.started().len(), 2);
+
+    // An aborted rollout doesn't start updates until it is resumed, which starts over
+    let aborted = apply_action(&env, rpc::forge::MachineUpdateRolloutAction::RolloutAbort).await?;
+    module.finish_updates();
+    manager.run_single_iteration().await?;
+    assert_eq!(module.started().len(), 2);
+
+    let restarted =
+        apply_action(&env, rpc::forge::MachineUpdateRolloutAction::RolloutResume).await?;
+    assert_ne!(restarted.id, aborted.id);
+    assert_eq!(restarted.wave, 0);
+    assert!(restarted.hosts.is_empty());
+    manager.run_single_iteration().await?;
+    assert_eq!(module.started().len(), 3);
+
+    Ok(())
+}
+
+#[crate::sqlx_test]
+async fn test_rollout_halts_on_unhealthy_hosts(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_env(pool, 4).await;
+    let module = RolloutTestModule::default();
+    let manager = create_manager(&env, &module);
+
+    manager.run_single_iteration().await?;
+    let canary = module.started()[0];
+
+    // The canary host is unhealthy after its update
+    let unhealthy_report = health_report::HealthReport {
+        source: "rollout-test".to_string(),
+        observed_at: Some(chrono::Utc::now()),
+        successes: vec![],
+        alerts: vec![health_report::HealthProbeAlert {
+            id: "RolloutTest".parse().unwrap(),
+            target: None,
+            in_alert_since: Some(chrono::Utc::now()),
+            message: "Broken after update".to_string(),
+            tenant_message: None,
+            classifications: vec![],
+        }],
+    };
+    let mut txn = env.pool.begin().await?;
+    db::machine::insert_health_report_override(
+        &mut txn,
+        &canary,
+        health_report::OverrideMode::Merge,
+        &unhealthy_report,
+        false,
+    )
+    .await?;
+    txn.commit().await?;
+
+    // Hosts are only checked once their update is done
+    manager.run_single_iteration().await?;
+    assert_eq!(
+        latest_rollout(&env).await.state,
+        MachineUpdateRolloutState::Running
+    );
+
+    module.finish_updates();
+    manager.run_single_iteration().await?;
+    let rollout = latest_rollout(&env).await;
+    assert_eq!(rollout.state, MachineUpdateRolloutState::Halted);
+    assert_eq!(rollout.unhealthy_machines, vec![canary]);
+
+    // A halted rollout doesn't move on to the next wave
+    end_soak_period(&env).await;
+    manager.run_single_iteration().await?;
+    manager.run_single_iteration().await?;
+    assert_eq!(module.started().len(), 1);
+
+    // Resuming accepts the unhealthy host, so that the rollout continues
+    apply_action(&env, rpc::forge::MachineUpdateRolloutAction::RolloutResume).await?;
+    manager.run_single_iteration().await?;
+    end_soak_period(&env).await;
+    manager.run_single_iteration().await?;
+    manager.run_single_iteration().await?;
+    let rollout = latest_rollout(&env).await;
+    assert_eq!(rollout.state, MachineUpdateRolloutState::Running);
+    assert_eq!(rollout.acknowledged_machines, vec![canary]);
+    assert_eq!(module.started().len(), 2);
+
+    Ok(())
+}
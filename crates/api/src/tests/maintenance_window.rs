@@ -0,0 +1,219 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Tests for maintenance windows
+
+use common::api_fixtures::{create_managed_host, create_test_env};
+use model::machine::ManagedHostState;
+use rpc::forge::dpu_reprovisioning_request::Mode;
+use rpc::forge::forge_server::Forge;
+use rpc::forge::{
+    DeleteMaintenanceWindowRequest, DpuReprovisioningRequest, MaintenanceWindow,
+    MaintenanceWindowKind, MaintenanceWindowScope, UpdateInitiator,
+};
+
+use crate::machine_update_manager::MachineUpdateManager;
+use crate::tests::common;
+use crate::tests::machine_update_rollout::RolloutTestModule;
+
+fn blackout(name: &str) -> MaintenanceWindow {
+    let now = chrono::Utc::now();
+    MaintenanceWindow {
+        name: name.to_string(),
+        kind: MaintenanceWindowKind::MaintenanceWindowBlackout as i32,
+        scope: MaintenanceWindowScope::MaintenanceScopeSite as i32,
+        starts_at: Some((now - chrono::Duration::hours(1)).into()),
+        ends_at: Some((now + chrono::Duration::hours(1)).into()),
+        description: "change freeze".to_string(),
+        ..Default::default()
+    }
+}
+
+#[crate::sqlx_test]
+async fn test_maintenance_window_crud(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool).await;
+
+    let created = env
+        .api
+        .create_maintenance_window(tonic::Request::new(blackout("freeze")))
+        .await?
+        .into_inner();
+    assert!(created.active);
+    assert_eq!(created.timezone.as_deref(), Some("UTC"));
+
+    // Names are unique
+    assert!(
+        env.api
+            .create_maintenance_window(tonic::Request::new(blackout("freeze")))
+            .await
+            .is_err()
+    );
+
+    let weekend = MaintenanceWindow {
+        name: "weekend".to_string(),
+        kind: MaintenanceWindowKind::MaintenanceWindowAllow as i32,
+        scope: MaintenanceWindowScope::MaintenanceScopeRack as i32,
+        scope_id: Some("rack-a".to_string()),
+        schedule: Some("0 2 * * 6".to_string()),
+        duration_minutes: 240,
+        timezone: Some("America/Los_Angeles".to_string()),
+        ..Default::default()
+    };
+    env.api
+        .create_maintenance_window(tonic::Request::new(weekend.clone()))
+        .await?;
+
+    // Invalid schedules are rejected
+    let err = env
+        .api
+        .create_maintenance_window(tonic::Request::new(MaintenanceWindow {
+            name: "invalid".to_string(),
+            schedule: Some("0 25 * * *".to_string()),
+            ..weekend
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::InvalidArgument);
+
+    let windows = env
+        .api
+        .find_maintenance_windows(tonic::Request::new(()))
+        .await?
+        .into_inner()
+        .windows;
+    assert_eq!(
+        windows.iter().map(|w| w.name.as_str()).collect::<Vec<_>>(),
+        vec!["freeze", "weekend"]
+    );
+    assert_eq!(windows[1].schedule.as_deref(), Some("0 2 * * 6"));
+    assert_eq!(windows[1].duration_minutes, 240);
+
+    env.api
+        .delete_maintenance_window(tonic::Request::new(DeleteMaintenanceWindowRequest {
+            name: "freeze".to_string(),
+        }))
+        .await?;
+    let err = env
+        .api
+        .delete_maintenance_window(tonic::Request::new(DeleteMaintenanceWindowRequest {
+            name: "freeze".to_string(),
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::NotFound);
+
+    Ok(())
+}
+
+#[crate::sqlx_test]
+async fn test_blackout_defers_machine_updates(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool).await;
+    create_managed_host(&env).await;
+    create_managed_host(&env).await;
+
+    let module = RolloutTestModule::default();
+    let manager = MachineUpdateManager::new_with_modules(
+        env.pool.clone(),
+        env.config.clone(),
+        vec![Box::new(module.clone())],
+        env.api.work_lock_manager_handle.clone(),
+    );
+
+    // Windows of other racks don't restrict the hosts
+    env.api
+        .create_maintenance_window(tonic::Request::new(MaintenanceWindow {
+            scope: MaintenanceWindowScope::MaintenanceScopeRack as i32,
+            scope_id: Some("other-rack".to_string()),
+            ..blackout("other-rack")
+        }))
+        .await?;
+    env.api
+        .create_maintenance_window(tonic::Request::new(blackout("freeze")))
+        .await?;
+
+    manager.run_single_iteration().await?;
+    assert!(module.started().is_empty());
+
+    env.api
+        .delete_maintenance_window(tonic::Request::new(DeleteMaintenanceWindowRequest {
+            name: "freeze".to_string(),
+        }))
+        .await?;
+    manager.run_single_iteration().await?;
+    assert_eq!(module.started().len(), 2);
+
+    Ok(())
+}
+
+#[crate::sqlx_test]
+async fn test_blackout_defers_dpu_reprovisioning(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool).await;
+    let mh = create_managed_host(&env).await;
+    mh.mark_machine_for_updates().await;
+
+    env.api
+        .create_maintenance_window(tonic::Request::new(blackout("freeze")))
+        .await?;
+
+    let request = |mode: Mode| {
+        tonic::Request::new(DpuReprovisioningRequest {
+            dpu_id: None,
+            machine_id: mh.dpu().id.into(),
+            mode: mode as i32,
+            initiator: UpdateInitiator::AdminCli as i32,
+            update_firmware: true,
+        })
+    };
+    // Requests are stored, but don't start before the maintenance windows allow it
+    env.api
+        .trigger_dpu_reprovisioning(request(Mode::Set))
+        .await?;
+    let mut txn = env.pool.begin().await?;
+    env.run_machine_state_controller_iteration().await;
+    let dpu = mh.dpu().db_machine(&mut txn).await;
+    assert!(
+        dpu.reprovision_requested
+            .as_ref()
+            .is_some_and(|request| request.started_at.is_none())
+    );
+    assert_eq!(dpu.current_state(), &ManagedHostState::Ready);
+
+    env.api
+        .delete_maintenance_window(tonic::Request::new(DeleteMaintenanceWindowRequest {
+            name: "freeze".to_string(),
+        }))
+        .await?;
+    env.run_machine_state_controller_iteration().await;
+    let dpu = mh.dpu().db_machine(&mut txn).await;
+    assert!(
+        dpu.reprovision_requested
+            .as_ref()
+            .is_some_and(|request| request.started_at.is_some())
+    );
+    assert!(matches!(
+        dpu.current_state(),
+        ManagedHostState::DPUReprovision { .. }
+    ));
+
+    Ok(())
+}
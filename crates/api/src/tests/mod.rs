@@ -14,6 +14,8 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+mod bmc_credential_rotation;
+mod capacity_reservation;
 pub(crate) mod common;
 mod connected_device;
 mod create_domain;
@@ -23,6 +25,7 @@ mod dpa_interfaces;
 mod dpf;
 mod dpu_agent_upgrade;
 mod dpu_info_list;
+mod dpu_reachability;
 mod dpu_machine_inventory;
 mod dpu_machine_update;
 mod dpu_nic_firmware;
@@ -49,7 +52,9 @@ mod instance_config_update;
 mod instance_find;
 mod instance_ipxe_behaviors;
 mod instance_os;
+mod instance_placement;
 mod instance_type;
+mod instance_webhook;
 mod ipxe;
 mod level_filter;
 mod lldp;
@@ -60,6 +65,7 @@ mod machine_boot_override;
 mod machine_creator;
 mod machine_dhcp;
 mod machine_discovery;
+mod machine_erasure_report;
 mod machine_find;
 mod machine_health;
 mod machine_history;
@@ -71,8 +77,10 @@ mod machine_power;
 mod machine_states;
 mod machine_topology;
 pub mod machine_update_manager;
+pub mod machine_update_rollout;
 mod machine_validation;
 mod maintenance;
+mod maintenance_window;
 #[cfg(feature = "linux-build")]
 mod measured_boot;
 mod mqtt_state_change_hook;
@@ -94,11 +102,13 @@ mod route_servers;
 mod service_health_metrics;
 mod site_explorer;
 mod sku;
+mod ssh_console_session;
 mod spdm;
 mod state_controller;
 mod storage;
 mod switch;
 mod switch_state_controller;
+mod tenant_dns_record;
 mod tenant_keyset_find;
 mod tenants;
 mod test_meter;
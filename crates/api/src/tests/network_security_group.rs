@@ -15,6 +15,7 @@
  * limitations under the License.
  */
 
+use std::sync::Arc;
 use std::time::SystemTime;
 
 use carbide_uuid::instance::InstanceId;
@@ -23,6 +24,7 @@ use carbide_uuid::vpc::VpcId;
 use config_version::ConfigVersion;
 use model::instance::config::network::DeviceLocator;
 use model::metadata::Metadata;
+use model::network_security_group::NETWORK_SECURITY_GROUP_LIMITS_HEALTH_REPORT_SOURCE;
 use rpc::forge::forge_server::Forge;
 use rpc::health::HealthReport;
 use tonic::Code;
@@ -30,6 +32,7 @@ use uuid::uuid;
 
 use super::common::api_fixtures::TestEnv;
 use crate::cfg::file::default_max_network_security_group_size;
+use crate::network_security_group_monitor::NetworkSecurityGroupLimitMonitor;
 use crate::tests::common::api_fixtures::dpu::DpuConfig;
 use crate::tests::common::api_fixtures::instance::{
     default_os_config, default_tenant_config, interface_network_config_with_devices,
@@ -37,7 +40,7 @@ use crate::tests::common::api_fixtures::instance::{
 };
 use crate::tests::common::api_fixtures::managed_host::ManagedHostConfig;
 use crate::tests::common::api_fixtures::{
-    create_test_env, populate_network_security_groups, site_explorer,
+    create_managed_host, create_test_env, populate_network_security_groups, site_explorer,
 };
 use crate::tests::common::rpc_builder::VpcCreationRequest;
 
@@ -111,6 +114,8 @@ async fn test_network_security_group_create(
                 description: "".to_string(),
                 labels: vec![],
             }),
+            metadata_token_required: None,
+            metadata_token_hop_limit: None,
         }))
         .await
         .unwrap();
@@ -126,6 +131,8 @@ async fn test_network_security_group_create(
                 description: "".to_string(),
                 labels: vec![],
             }),
+            metadata_token_required: None,
+            metadata_token_hop_limit: None,
         }))
         .await
         .unwrap();
@@ -1882,3 +1889,507 @@ async fn test_network_security_group_get_attachments(
 
     Ok(())
 }
+
+#[crate::sqlx_test]
+async fn test_network_security_group_simulate(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool).await;
+
+    populate_network_security_groups(env.api.clone()).await;
+
+    // Our known fixture network security group, which denies all ingress traffic
+    let good_network_security_group_id = "fd3ab096-d811-11ef-8fe9-7be4b2483448";
+
+    let ssh_flow = |src_ip: &str| rpc::forge::NetworkSecurityGroupFlow {
+        direction: rpc::forge::NetworkSecurityGroupRuleDirection::NsgRuleDirectionIngress.into(),
+        protocol: rpc::forge::NetworkSecurityGroupRuleProtocol::NsgRuleProtoTcp.into(),
+        src_ip: src_ip.to_string(),
+        dst_ip: "192.168.0.1".to_string(),
+        src_port: Some(40000),
+        dst_port: Some(22),
+    };
+
+    // Simulate the rules of an existing group.
+    let response = env
+        .api
+        .simulate_network_security_group(tonic::Request::new(
+            rpc::forge::SimulateNetworkSecurityGroupRequest {
+                rule_source: Some(
+                    rpc::forge::simulate_network_security_group_request::RuleSource::NetworkSecurityGroupId(
+                        good_network_security_group_id.to_string(),
+                    ),
+                ),
+                flows: vec![ssh_flow("10.1.2.3")],
+            },
+        ))
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(response.results.len(), 1);
+    assert_eq!(
+        response.results[0].action,
+        i32::from(rpc::forge::NetworkSecurityGroupRuleAction::NsgRuleActionDeny)
+    );
+    assert_eq!(
+        response.results[0].matched_rule_id.as_deref(),
+        Some(good_network_security_group_id)
+    );
+    assert_eq!(response.results[0].flow, Some(ssh_flow("10.1.2.3")));
+
+    // A deny rule without any permit rule after it doesn't change anything
+    // compared to the implicit deny.
+    assert_eq!(response.findings.len(), 1);
+    assert_eq!(
+        response.findings[0].r#type,
+        i32::from(rpc::forge::NetworkSecurityGroupRuleFindingType::NsgFindingRedundant)
+    );
+    assert_eq!(response.findings[0].rule_id, good_network_security_group_id);
+
+    // Simulate a proposed rule set.
+    let ssh_rule = |id: &str,
+                    prefix: &str,
+                    action: rpc::forge::NetworkSecurityGroupRuleAction,
+                    priority: u32| {
+        rpc::forge::NetworkSecurityGroupRuleAttributes {
+            id: Some(id.to_string()),
+            direction: rpc::forge::NetworkSecurityGroupRuleDirection::NsgRuleDirectionIngress
+                .into(),
+            ipv6: false,
+            src_port_start: None,
+            src_port_end: None,
+            dst_port_start: Some(22),
+            dst_port_end: Some(22),
+            protocol: rpc::forge::NetworkSecurityGroupRuleProtocol::NsgRuleProtoTcp.into(),
+            action: action.into(),
+            priority,
+            source_net: Some(
+                rpc::forge::network_security_group_rule_attributes::SourceNet::SrcPrefix(
+                    prefix.to_string(),
+                ),
+            ),
+            destination_net: Some(
+                rpc::forge::network_security_group_rule_attributes::DestinationNet::DstPrefix(
+                    "0.0.0.0/0".to_string(),
+                ),
+            ),
+        }
+    };
+
+    let proposed = rpc::forge::NetworkSecurityGroupAttributes {
+        stateful_egress: false,
+        rules: vec![
+            ssh_rule(
+                "allow_ssh",
+                "10.0.0.0/8",
+                rpc::forge::NetworkSecurityGroupRuleAction::NsgRuleActionPermit,
+                100,
+            ),
+            ssh_rule(
+                "deny_ssh",
+                "10.1.0.0/16",
+                rpc::forge::NetworkSecurityGroupRuleAction::NsgRuleActionDeny,
+                200,
+            ),
+        ],
+    };
+
+    let response = env
+        .api
+        .simulate_network_security_group(tonic::Request::new(
+            rpc::forge::SimulateNetworkSecurityGroupRequest {
+                rule_source: Some(
+                    rpc::forge::simulate_network_security_group_request::RuleSource::NetworkSecurityGroupAttributes(
+                        proposed.clone(),
+                    ),
+                ),
+                flows: vec![ssh_flow("10.1.2.3"), ssh_flow("172.16.0.1")],
+            },
+        ))
+        .await
+        .unwrap()
+        .into_inner();
+
+    let verdicts = response
+        .results
+        .iter()
+        .map(|r| (r.action, r.matched_rule_id.as_deref()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        verdicts,
+        vec![
+            (
+                i32::from(rpc::forge::NetworkSecurityGroupRuleAction::NsgRuleActionPermit),
+                Some("allow_ssh")
+            ),
+            // No rule matches, so the implicit deny applies.
+            (
+                i32::from(rpc::forge::NetworkSecurityGroupRuleAction::NsgRuleActionDeny),
+                None
+            ),
+        ]
+    );
+
+    // The deny rule can never match because the permit rule covers it.
+    assert_eq!(response.findings.len(), 1);
+    assert_eq!(
+        response.findings[0].r#type,
+        i32::from(rpc::forge::NetworkSecurityGroupRuleFindingType::NsgFindingShadowed)
+    );
+    assert_eq!(response.findings[0].rule_id, "deny_ssh");
+    assert_eq!(
+        response.findings[0].related_rule_id.as_deref(),
+        Some("allow_ssh")
+    );
+
+    // Flows must be fully specified.
+    let mut any_flow = ssh_flow("10.1.2.3");
+    any_flow.protocol = rpc::forge::NetworkSecurityGroupRuleProtocol::NsgRuleProtoAny.into();
+    let err = env
+        .api
+        .simulate_network_security_group(tonic::Request::new(
+            rpc::forge::SimulateNetworkSecurityGroupRequest {
+                rule_source: Some(
+                    rpc::forge::simulate_network_security_group_request::RuleSource::NetworkSecurityGroupAttributes(
+                        proposed,
+                    ),
+                ),
+                flows: vec![any_flow],
+            },
+        ))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), Code::InvalidArgument);
+
+    // Unknown groups are reported as such.
+    let err = env
+        .api
+        .simulate_network_security_group(tonic::Request::new(
+            rpc::forge::SimulateNetworkSecurityGroupRequest {
+                rule_source: Some(
+                    rpc::forge::simulate_network_security_group_request::RuleSource::NetworkSecurityGroupId(
+                        "0ed1eb04-5a1b-4d3a-9f51-0b8c8f1f4a6e".to_string(),
+                    ),
+                ),
+                flows: vec![ssh_flow("10.1.2.3")],
+            },
+        ))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), Code::NotFound);
+
+    Ok(())
+}
+
+#[crate::sqlx_test]
+async fn test_network_security_group_vpc_references(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool).await;
+
+    populate_network_security_groups(env.api.clone()).await;
+
+    // Provided by fixtures
+    let default_tenant_org = "Tenant1";
+    let tenant_org2 = "Tenant2";
+
+    let vpc_id = VpcId::new();
+    let segment_ids = env
+        .create_vpc_and_tenant_segments_with_vpc_details(
+            VpcCreationRequest::builder("nsg reference vpc", default_tenant_org)
+                .id(vpc_id)
+                .rpc(),
+            1,
+        )
+        .await;
+
+    let max_nsg_size = default_max_network_security_group_size();
+
+    // Half of the limit for each prefix of the referenced VPC
+    let vpc_rule = |vpc_id: String| rpc::forge::NetworkSecurityGroupAttributes {
+        stateful_egress: false,
+        rules: vec![rpc::forge::NetworkSecurityGroupRuleAttributes {
+            id: Some("from_vpc".to_string()),
+            direction: rpc::forge::NetworkSecurityGroupRuleDirection::NsgRuleDirectionIngress
+                .into(),
+            ipv6: false,
+            src_port_start: None,
+            src_port_end: None,
+            dst_port_start: Some(1),
+            dst_port_end: Some(max_nsg_size / 2),
+            protocol: rpc::forge::NetworkSecurityGroupRuleProtocol::NsgRuleProtoTcp.into(),
+            action: rpc::forge::NetworkSecurityGroupRuleAction::NsgRuleActionPermit.into(),
+            priority: 100,
+            source_net: Some(
+                rpc::forge::network_security_group_rule_attributes::SourceNet::SrcVpcId(vpc_id),
+            ),
+            destination_net: Some(
+                rpc::forge::network_security_group_rule_attributes::DestinationNet::DstPrefix(
+                    "0.0.0.0/0".to_string(),
+                ),
+            ),
+        }],
+    };
+
+    let create_request = |tenant_org: &str, attributes| {
+        tonic::Request::new(rpc::forge::CreateNetworkSecurityGroupRequest {
+            id: None,
+            tenant_organization_id: tenant_org.to_string(),
+            metadata: Some(rpc::forge::Metadata {
+                name: format!("vpc references of {tenant_org}"),
+                description: "".to_string(),
+                labels: vec![],
+            }),
+            network_security_group_attributes: Some(attributes),
+        })
+    };
+
+    // References to unknown VPCs or VPCs of other tenants are rejected.
+    let err = env
+        .api
+        .create_network_security_group(create_request(
+            default_tenant_org,
+            vpc_rule(VpcId::new().to_string()),
+        ))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), Code::InvalidArgument);
+    assert!(err.message().contains("another tenant"));
+
+    let err = env
+        .api
+        .create_network_security_group(create_request(tenant_org2, vpc_rule(vpc_id.to_string())))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), Code::InvalidArgument);
+    assert!(err.message().contains("another tenant"));
+
+    let nsg = env
+        .api
+        .create_network_security_group(create_request(
+            default_tenant_org,
+            vpc_rule(vpc_id.to_string()),
+        ))
+        .await
+        .unwrap()
+        .into_inner()
+        .network_security_group
+        .unwrap();
+
+    // The reference is kept as is.
+    assert_eq!(
+        nsg.attributes.unwrap().rules[0].source_net,
+        Some(
+            rpc::forge::network_security_group_rule_attributes::SourceNet::SrcVpcId(
+                vpc_id.to_string()
+            )
+        )
+    );
+
+    let vpc_prefix_request = |prefix: &str| {
+        tonic::Request::new(rpc::forge::VpcPrefixCreationRequest {
+            id: None,
+            prefix: String::new(),
+            name: String::new(),
+            vpc_id: Some(vpc_id),
+            config: Some(rpc::forge::VpcPrefixConfig {
+                prefix: prefix.into(),
+            }),
+            metadata: Some(rpc::forge::Metadata {
+                name: format!("VPC prefix {prefix}"),
+                description: String::new(),
+                labels: vec![],
+            }),
+        })
+    };
+
+    // The segment prefix and a VPC prefix fill up the group.
+    env.api
+        .create_vpc_prefix(vpc_prefix_request("192.1.4.0/24"))
+        .await
+        .unwrap();
+
+    // Another VPC prefix would overflow it.
+    let err = env
+        .api
+        .create_vpc_prefix(vpc_prefix_request("192.1.5.0/24"))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), Code::InvalidArgument);
+    assert!(err.message().contains(&nsg.id));
+
+    // Flows from the VPC prefix match the expanded rule.
+    let response = env
+        .api
+        .simulate_network_security_group(tonic::Request::new(
+            rpc::forge::SimulateNetworkSecurityGroupRequest {
+                rule_source: Some(
+                    rpc::forge::simulate_network_security_group_request::RuleSource::NetworkSecurityGroupId(
+                        nsg.id.clone(),
+                    ),
+                ),
+                flows: vec![rpc::forge::NetworkSecurityGroupFlow {
+                    direction: rpc::forge::NetworkSecurityGroupRuleDirection::NsgRuleDirectionIngress.into(),
+                    protocol: rpc::forge::NetworkSecurityGroupRuleProtocol::NsgRuleProtoTcp.into(),
+                    src_ip: "192.1.4.10".to_string(),
+                    dst_ip: "192.168.0.1".to_string(),
+                    src_port: Some(40000),
+                    dst_port: Some(22),
+                }],
+            },
+        ))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(
+        response.results[0].matched_rule_id.as_deref(),
+        Some("from_vpc")
+    );
+
+    // Attach the group to an instance.
+    let mh = create_managed_host(&env).await;
+    let instance_id = env
+        .api
+        .allocate_instance(tonic::Request::new(rpc::forge::InstanceAllocationRequest {
+            machine_id: Some(mh.id),
+            config: Some(rpc::InstanceConfig {
+                tenant: Some(default_tenant_config()),
+                os: Some(default_os_config()),
+                network: Some(single_interface_network_config(segment_ids[0])),
+                infiniband: None,
+                nvlink: None,
+                network_security_group_id: Some(nsg.id.clone()),
+                dpu_extension_services: None,
+            }),
+            instance_id: None,
+            instance_type_id: None,
+            metadata: None,
+            allow_unhealthy_machine: false,
+        }))
+        .await
+        .unwrap()
+        .into_inner()
+        .id
+        .unwrap();
+
+    let limit_monitor = |max_network_security_group_size| {
+        let mut config = (*env.config).clone();
+        config
+            .network_security_group
+            .max_network_security_group_size = max_network_security_group_size;
+        NetworkSecurityGroupLimitMonitor::new(
+            env.pool.clone(),
+            Arc::new(config),
+            env.api.work_lock_manager_handle.clone(),
+        )
+    };
+
+    // The group fits the default limit.
+    assert_eq!(
+        limit_monitor(max_nsg_size)
+            .run_single_iteration()
+            .await
+            .unwrap(),
+        0
+    );
+
+    // With a lower limit, the DPU gets an alert and the propagation fails.
+    assert_eq!(
+        limit_monitor(max_nsg_size / 2)
+            .run_single_iteration()
+            .await
+            .unwrap(),
+        1
+    );
+
+    let mut txn = env.pool.begin().await?;
+    let dpu = mh.dpu().db_machine(&mut txn).await;
+    assert!(
+        dpu.health_report_overrides
+            .merges
+            .contains_key(NETWORK_SECURITY_GROUP_LIMITS_HEALTH_REPORT_SOURCE)
+    );
+    txn.commit().await?;
+
+    let prop_status = env
+        .api
+        .get_network_security_group_propagation_status(tonic::Request::new(
+            rpc::forge::GetNetworkSecurityGroupPropagationStatusRequest {
+                network_security_group_ids: None,
+                vpc_ids: vec![],
+                instance_ids: vec![instance_id.to_string()],
+            },
+        ))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(
+        prop_status.instances[0].status,
+        i32::from(rpc::forge::NetworkSecurityGroupPropagationStatus::NsgPropStatusError)
+    );
+    assert!(
+        prop_status.instances[0]
+            .details
+            .as_deref()
+            .unwrap()
+            .contains("exceeds the maximum")
+    );
+
+    // The alert is cleared once the group fits again.
+    assert_eq!(
+        limit_monitor(max_nsg_size)
+            .run_single_iteration()
+            .await
+            .unwrap(),
+        0
+    );
+
+    let mut txn = env.pool.begin().await?;
+    let dpu = mh.dpu().db_machine(&mut txn).await;
+    assert!(
+        !dpu.health_report_overrides
+            .merges
+            .contains_key(NETWORK_SECURITY_GROUP_LIMITS_HEALTH_REPORT_SOURCE)
+    );
+    txn.commit().await?;
+
+    // A segment outside of VPC prefixes would overflow the group as well.
+    let err = env
+        .api
+        .create_network_segment(tonic::Request::new(
+            rpc::forge::NetworkSegmentCreationRequest {
+                id: None,
+                mtu: Some(1500),
+                name: "nsg reference overflow".to_string(),
+                prefixes: vec![rpc::forge::NetworkPrefix {
+                    id: None,
+                    prefix: "192.2.4.0/24".to_string(),
+                    gateway: Some("192.2.4.1".to_string()),
+                    reserve_first: 3,
+                    free_ip_count: 0,
+                    svi_ip: None,
+                }],
+                subdomain_id: None,
+                vpc_id: Some(vpc_id),
+                segment_type: rpc::forge::NetworkSegmentType::Tenant as _,
+            },
+        ))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), Code::InvalidArgument);
+    assert!(err.message().contains(&nsg.id));
+
+    // The referenced VPC can't be deleted while the group refers to it.
+    let err = env
+        .api
+        .delete_vpc(tonic::Request::new(rpc::forge::VpcDeletionRequest {
+            id: Some(vpc_id),
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), Code::FailedPrecondition);
+    assert!(err.message().contains(&nsg.id));
+
+    Ok(())
+}
@@ -0,0 +1,138 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Tests for the recordings of ssh-console sessions
+
+use carbide_uuid::machine::MachineId;
+use common::api_fixtures::{create_managed_host, create_test_env};
+use rpc::forge::forge_server::Forge;
+use rpc::forge::{
+    SshConsoleSession, SshConsoleSessionRecording, SshConsoleSessionRecordingRequest,
+    SshConsoleSessionsRequest,
+};
+use tonic::Code;
+use uuid::Uuid;
+
+use crate::tests::common;
+use crate::tests::common::api_fixtures::TestEnv;
+
+fn session(machine_id: MachineId, started: &str) -> SshConsoleSession {
+    let session_id = Uuid::new_v4();
+    let started = chrono::DateTime::parse_from_rfc3339(started)
+        .unwrap()
+        .with_timezone(&chrono::Utc);
+    SshConsoleSession {
+        session_id: session_id.to_string(),
+        machine_id: Some(machine_id),
+        name: format!("{}_{session_id}", started.format("%Y%m%dT%H%M%SZ")),
+        started: Some(started.into()),
+        user: Some("alice".to_string()),
+        certificate_key_id: None,
+        peer_addr: "10.0.0.1:51234".to_string(),
+        duration_secs: 1.5,
+        input_events: 1,
+        output_events: 1,
+    }
+}
+
+async fn record(env: &TestEnv, session: &SshConsoleSession, recording: &str) {
+    env.api
+        .record_ssh_console_session(tonic::Request::new(SshConsoleSessionRecording {
+            session: Some(session.clone()),
+            recording: recording.to_string(),
+        }))
+        .await
+        .unwrap();
+}
+
+async fn sessions_of_machine(env: &TestEnv, machine_id: MachineId) -> Vec<SshConsoleSession> {
+    env.api
+        .find_ssh_console_sessions(tonic::Request::new(SshConsoleSessionsRequest {
+            machine_id: Some(machine_id),
+        }))
+        .await
+        .unwrap()
+        .into_inner()
+        .sessions
+}
+
+#[crate::sqlx_test]
+async fn test_record_and_replay_ssh_console_sessions(pool: sqlx::PgPool) {
+    let env = create_test_env(pool).await;
+    let mh = create_managed_host(&env).await;
+    let machine_id = mh.host().id;
+
+    let second = session(machine_id, "2026-03-02T00:00:00Z");
+    let first = session(machine_id, "2026-03-01T00:00:00Z");
+    record(&env, &second, "{\"version\":2}\n[0.5,\"o\",\"login: \"]\n").await;
+    record(&env, &first, "{\"version\":2}\n").await;
+
+    // Sessions are listed oldest first
+    assert_eq!(
+        sessions_of_machine(&env, machine_id).await,
+        vec![first.clone(), second.clone()]
+    );
+
+    // Recording a session again replaces its recording
+    let mut updated = second.clone();
+    updated.duration_secs = 3.0;
+    updated.input_events = 2;
+    let recording = "{\"version\":2}\n[0.5,\"o\",\"login: \"]\n[3.0,\"i\",\"root\\r\"]\n";
+    record(&env, &updated, recording).await;
+
+    // Recordings can be found by name or by session ID
+    for session in [&updated.name, &updated.session_id] {
+        let response = env
+            .api
+            .get_ssh_console_session_recording(tonic::Request::new(
+                SshConsoleSessionRecordingRequest {
+                    machine_id: Some(machine_id),
+                    session: session.clone(),
+                },
+            ))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(response.session, Some(updated.clone()));
+        assert_eq!(response.recording, recording);
+    }
+
+    // Sessions of other machines are not found
+    let err = env
+        .api
+        .get_ssh_console_session_recording(tonic::Request::new(SshConsoleSessionRecordingRequest {
+            machine_id: Some(mh.dpu().id),
+            session: updated.session_id.clone(),
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), Code::NotFound);
+}
+
+#[crate::sqlx_test]
+async fn test_record_ssh_console_session_requires_session(pool: sqlx::PgPool) {
+    let env = create_test_env(pool).await;
+    let err = env
+        .api
+        .record_ssh_console_session(tonic::Request::new(SshConsoleSessionRecording {
+            session: None,
+            recording: String::new(),
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), Code::InvalidArgument);
+}
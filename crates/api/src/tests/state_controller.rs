@@ -30,7 +30,9 @@ use sqlx::postgres::PgRow;
 use sqlx::{FromRow, PgConnection, Row};
 
 use crate::state_controller::config::IterationConfig;
-use crate::state_controller::controller::{self, Enqueuer, QueuedObject, StateController};
+use crate::state_controller::controller::{
+    self, Enqueuer, QueuePriority, QueuedObject, StateController,
+};
 use crate::state_controller::io::StateControllerIO;
 use crate::state_controller::metrics::NoopMetricsEmitter;
 use crate::state_controller::state_change_emitter::{
@@ -194,14 +196,17 @@ async fn test_queue_objects(pool: sqlx::PgPool) -> sqlx::Result<()> {
             QueuedObject {
                 object_id: "0".to_string(),
                 processed_by: None,
+                priority: QueuePriority::Normal,
             },
             QueuedObject {
                 object_id: "1".to_string(),
                 processed_by: None,
+                priority: QueuePriority::Normal,
             },
             QueuedObject {
                 object_id: "2".to_string(),
                 processed_by: None,
+                priority: QueuePriority::Normal,
             },
         ]
     );
@@ -240,18 +245,22 @@ async fn test_queue_objects(pool: sqlx::PgPool) -> sqlx::Result<()> {
             QueuedObject {
                 object_id: "0".to_string(),
                 processed_by: None,
+                priority: QueuePriority::Normal,
             },
             QueuedObject {
                 object_id: "1".to_string(),
                 processed_by: None,
+                priority: QueuePriority::Normal,
             },
             QueuedObject {
                 object_id: "2".to_string(),
                 processed_by: None,
+                priority: QueuePriority::Normal,
             },
             QueuedObject {
                 object_id: "3".to_string(),
                 processed_by: None,
+                priority: QueuePriority::Normal,
             },
         ]
     );
@@ -262,7 +271,7 @@ async fn test_queue_objects(pool: sqlx::PgPool) -> sqlx::Result<()> {
     let processor_id2 = "000000000002".to_string();
     let mut txn: sqlx::Transaction<'_, sqlx::Postgres> = pool.begin().await.unwrap();
     let mut txn2: sqlx::Transaction<'_, sqlx::Postgres> = pool.begin().await.unwrap();
-    let mut queued = controller::db::acquire_queued_objects(
+    let mut queued: Vec<QueuedObject> = controller::db::acquire_queued_objects(
         &mut txn,
         TestStateControllerIO::DB_QUEUED_OBJECTS_TABLE_NAME,
         2,
@@ -270,7 +279,10 @@ async fn test_queue_objects(pool: sqlx::PgPool) -> sqlx::Result<()> {
         std::time::Duration::from_secs(60),
     )
     .await
-    .unwrap();
+    .unwrap()
+    .into_iter()
+    .map(|acquired| acquired.object)
+    .collect();
     queued.sort_by(|a, b| a.object_id.cmp(&b.object_id));
     assert_eq!(
         queued,
@@ -278,14 +290,16 @@ async fn test_queue_objects(pool: sqlx::PgPool) -> sqlx::Result<()> {
             QueuedObject {
                 object_id: "0".to_string(),
                 processed_by: Some(processor_id1.clone()),
+                priority: QueuePriority::Normal,
             },
             QueuedObject {
                 object_id: "1".to_string(),
                 processed_by: Some(processor_id1.clone()),
+                priority: QueuePriority::Normal,
             },
         ]
     );
-    let mut queued2 = controller::db::acquire_queued_objects(
+    let mut queued2: Vec<QueuedObject> = controller::db::acquire_queued_objects(
         &mut txn2,
         TestStateControllerIO::DB_QUEUED_OBJECTS_TABLE_NAME,
         1,
@@ -293,13 +307,17 @@ async fn test_queue_objects(pool: sqlx::PgPool) -> sqlx::Result<()> {
         std::time::Duration::from_secs(60),
     )
     .await
-    .unwrap();
+    .unwrap()
+    .into_iter()
+    .map(|acquired| acquired.object)
+    .collect();
     queued2.sort_by(|a, b| a.object_id.cmp(&b.object_id));
     assert_eq!(
         queued2,
         vec![QueuedObject {
             object_id: "2".to_string(),
             processed_by: Some(processor_id2.clone()),
+            priority: QueuePriority::Normal,
         },]
     );
 
@@ -342,14 +360,17 @@ async fn test_queue_objects(pool: sqlx::PgPool) -> sqlx::Result<()> {
             QueuedObject {
                 object_id: "0".to_string(),
                 processed_by: Some(processor_id1.clone()),
+                priority: QueuePriority::Normal,
             },
             QueuedObject {
                 object_id: "2".to_string(),
                 processed_by: Some(processor_id2.clone()),
+                priority: QueuePriority::Normal,
             },
             QueuedObject {
                 object_id: "3".to_string(),
                 processed_by: None,
+                priority: QueuePriority::Normal,
             },
         ]
     );
@@ -359,7 +380,7 @@ async fn test_queue_objects(pool: sqlx::PgPool) -> sqlx::Result<()> {
 
     // Test acquire with max_outdated
     let mut txn: sqlx::Transaction<'_, sqlx::Postgres> = pool.begin().await.unwrap();
-    let queued = controller::db::acquire_queued_objects(
+    let queued: Vec<QueuedObject> = controller::db::acquire_queued_objects(
         &mut txn,
         TestStateControllerIO::DB_QUEUED_OBJECTS_TABLE_NAME,
         2,
@@ -367,7 +388,10 @@ async fn test_queue_objects(pool: sqlx::PgPool) -> sqlx::Result<()> {
         std::time::Duration::from_millis(500),
     )
     .await
-    .unwrap();
+    .unwrap()
+    .into_iter()
+    .map(|acquired| acquired.object)
+    .collect();
     // We might see 2-3 tasks not being acquired by the processor.
     // 2 if it re-acquires the tasks it already has, or 3 if it acquires other tasks
     let acquired = queued
@@ -389,6 +413,107 @@ async fn test_queue_objects(pool: sqlx::PgPool) -> sqlx::Result<()> {
     Ok(())
 }
 
+#[crate::sqlx_test]
+async fn test_queue_objects_with_priority(pool: sqlx::PgPool) -> sqlx::Result<()> {
+    create_test_state_controller_tables(&pool).await;
+
+    let mut txn = pool.begin().await.unwrap();
+    for idx in 0..4 {
+        create_test_object(idx.to_string(), &mut txn).await;
+    }
+    txn.commit().await.unwrap();
+
+    let mut txn = pool.begin().await.unwrap();
+    let num_enqueued = controller::db::queue_objects(
+        &mut txn,
+        TestStateControllerIO::DB_QUEUED_OBJECTS_TABLE_NAME,
+        &["0".to_string(), "1".to_string(), "2".to_string()],
+    )
+    .await
+    .unwrap();
+    assert_eq!(num_enqueued, 3);
+
+    // Raising the priority of an already queued object counts as enqueued
+    let num_enqueued = controller::db::queue_objects_with_priority(
+        &mut txn,
+        TestStateControllerIO::DB_QUEUED_OBJECTS_TABLE_NAME,
+        &["2".to_string(), "3".to_string(), "3".to_string()],
+        QueuePriority::High,
+    )
+    .await
+    .unwrap();
+    assert_eq!(num_enqueued, 2);
+
+    // Enqueuing with a lower priority does not change the queued priority
+    let num_enqueued = controller::db::queue_objects(
+        &mut txn,
+        TestStateControllerIO::DB_QUEUED_OBJECTS_TABLE_NAME,
+        &["2".to_string(), "3".to_string()],
+    )
+    .await
+    .unwrap();
+    assert_eq!(num_enqueued, 0);
+    txn.commit().await.unwrap();
+
+    // High priority objects are acquired first, even though they had been queued later
+    let processor_id = "000000000001".to_string();
+    let mut txn = pool.begin().await.unwrap();
+    let acquired = controller::db::acquire_queued_objects(
+        &mut txn,
+        TestStateControllerIO::DB_QUEUED_OBJECTS_TABLE_NAME,
+        2,
+        &processor_id,
+        std::time::Duration::from_secs(60),
+    )
+    .await
+    .unwrap();
+    txn.commit().await.unwrap();
+    let mut queued: Vec<QueuedObject> = acquired.into_iter().map(|a| a.object).collect();
+    queued.sort_by(|a, b| a.object_id.cmp(&b.object_id));
+    assert_eq!(
+        queued,
+        vec![
+            QueuedObject {
+                object_id: "2".to_string(),
+                processed_by: Some(processor_id.clone()),
+                priority: QueuePriority::High,
+            },
+            QueuedObject {
+                object_id: "3".to_string(),
+                processed_by: Some(processor_id.clone()),
+                priority: QueuePriority::High,
+            },
+        ]
+    );
+
+    // Objects which are already processed keep their priority
+    let mut txn = pool.begin().await.unwrap();
+    let num_enqueued = controller::db::queue_objects_with_priority(
+        &mut txn,
+        TestStateControllerIO::DB_QUEUED_OBJECTS_TABLE_NAME,
+        &["0".to_string(), "2".to_string()],
+        QueuePriority::High,
+    )
+    .await
+    .unwrap();
+    assert_eq!(num_enqueued, 1);
+    let acquired = controller::db::acquire_queued_objects(
+        &mut txn,
+        TestStateControllerIO::DB_QUEUED_OBJECTS_TABLE_NAME,
+        1,
+        &processor_id,
+        std::time::Duration::from_secs(60),
+    )
+    .await
+    .unwrap();
+    txn.commit().await.unwrap();
+    assert_eq!(acquired.len(), 1);
+    assert_eq!(acquired[0].object.object_id, "0");
+    assert_eq!(acquired[0].object.priority, QueuePriority::High);
+
+    Ok(())
+}
+
 #[derive(Debug, Default)]
 struct TestStateControllerIO {}
 
@@ -472,7 +597,8 @@ async fn create_test_state_controller_tables(pool: &sqlx::PgPool) {
         "CREATE TABLE test_state_controller_queued_objects(
         object_id VARCHAR PRIMARY KEY,
         processed_by TEXT NULL,
-        processing_started_at timestamptz NOT NULL DEFAULT NOW()
+        processing_started_at timestamptz NOT NULL DEFAULT NOW(),
+        priority SMALLINT NOT NULL DEFAULT 0
     );",
     )
     .execute(&mut *txn)
@@ -980,6 +1106,7 @@ async fn test_state_controller_manual_enqueuing(pool: sqlx::PgPool) -> eyre::Res
         vec![QueuedObject {
             object_id: "test-obj-1".to_string(),
             processed_by: None,
+            priority: QueuePriority::High,
         },]
     );
     txn.commit().await.unwrap();
@@ -0,0 +1,240 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use carbide_uuid::vpc::VpcId;
+use rpc::forge::forge_server::Forge;
+use rpc::protos::dns::{
+    DnsResourceRecordLookupRequest, GetAllRecordsForDomainRequest, GetDomainInfoRequest,
+    TenantDnsRecord, TenantDnsRecordCreationRequest, TenantDnsRecordDeletionRequest,
+    TenantDnsRecordSearchFilter, TenantDnsRecordUpdateRequest, TenantDnsRecordsByIdsRequest,
+};
+use tonic::{Code, Request, Status};
+
+use crate::tests::common::api_fixtures::{TestEnv, create_test_env};
+
+const DOMAIN_NAME: &str = "dwrt1.com";
+
+async fn vpc_id(env: &TestEnv) -> VpcId {
+    db::vpc::find_by_name(&env.pool, "test vpc 1")
+        .await
+        .unwrap()
+        .into_iter()
+        .next()
+        .unwrap()
+        .id
+}
+
+async fn create_record(
+    env: &TestEnv,
+    vpc_id: VpcId,
+    qname: &str,
+    qtype: &str,
+    content: &str,
+) -> Result<TenantDnsRecord, Status> {
+    env.api
+        .create_tenant_dns_record(Request::new(TenantDnsRecordCreationRequest {
+            id: None,
+            vpc_id: Some(vpc_id),
+            qname: qname.to_string(),
+            qtype: qtype.to_string(),
+            ttl: None,
+            content: content.to_string(),
+        }))
+        .await
+        .map(|response| response.into_inner())
+}
+
+async fn lookup(env: &TestEnv, qname: &str, qtype: &str) -> Vec<(String, String)> {
+    env.api
+        .lookup_record(Request::new(DnsResourceRecordLookupRequest {
+            qtype: qtype.to_string(),
+            qname: qname.to_string(),
+            zone_id: "-1".to_string(),
+            local: None,
+            remote: None,
+            real_remote: None,
+        }))
+        .await
+        .unwrap()
+        .into_inner()
+        .records
+        .into_iter()
+        .map(|r| (r.qtype, r.content))
+        .collect()
+}
+
+#[crate::sqlx_test]
+async fn test_tenant_dns_record_lifecycle(pool: sqlx::PgPool) {
+    let env = create_test_env(pool).await;
+    env.create_vpc_and_tenant_segment().await;
+    let vpc_id = vpc_id(&env).await;
+
+    let serial_before = env
+        .api
+        .get_domain_info(Request::new(GetDomainInfoRequest {
+            name: DOMAIN_NAME.to_string(),
+        }))
+        .await
+        .unwrap()
+        .into_inner()
+        .result
+        .unwrap()
+        .serial;
+
+    let record = create_record(&env, vpc_id, "API.Cluster.dwrt1.com", "A", "192.0.2.100")
+        .await
+        .unwrap();
+    assert_eq!(record.qname, "api.cluster.dwrt1.com.");
+    assert_eq!(record.ttl, 300);
+    create_record(
+        &env,
+        vpc_id,
+        "_http._tcp.cluster.dwrt1.com.",
+        "SRV",
+        "10 5 80 api.cluster.dwrt1.com.",
+    )
+    .await
+    .unwrap();
+    create_record(&env, vpc_id, "cluster.dwrt1.com.", "TXT", "owner=team-a")
+        .await
+        .unwrap();
+
+    assert_eq!(
+        lookup(&env, "api.cluster.dwrt1.com.", "A").await,
+        vec![("A".to_string(), "192.0.2.100".to_string())]
+    );
+    assert_eq!(
+        lookup(&env, "cluster.dwrt1.com.", "ANY").await,
+        vec![("TXT".to_string(), "\"owner=team-a\"".to_string())]
+    );
+
+    // Tenant records are part of zone transfers and move the zone serial forward
+    let zone = env
+        .api
+        .get_all_records_for_domain(Request::new(GetAllRecordsForDomainRequest {
+            name: DOMAIN_NAME.to_string(),
+        }))
+        .await
+        .unwrap()
+        .into_inner()
+        .result;
+    assert!(
+        zone.iter()
+            .any(|r| r.qtype == "SRV" && r.content == "10 5 80 api.cluster.dwrt1.com.")
+    );
+    let serial_after = env
+        .api
+        .get_domain_info(Request::new(GetDomainInfoRequest {
+            name: DOMAIN_NAME.to_string(),
+        }))
+        .await
+        .unwrap()
+        .into_inner()
+        .result
+        .unwrap()
+        .serial;
+    assert!(serial_after > serial_before);
+
+    let updated = env
+        .api
+        .update_tenant_dns_record(Request::new(TenantDnsRecordUpdateRequest {
+            id: record.id,
+            ttl: Some(60),
+            content: "192.0.2.101".to_string(),
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(updated.ttl, 60);
+    assert_eq!(
+        lookup(&env, "api.cluster.dwrt1.com.", "A").await,
+        vec![("A".to_string(), "192.0.2.101".to_string())]
+    );
+
+    let record_ids = env
+        .api
+        .find_tenant_dns_record_ids(Request::new(TenantDnsRecordSearchFilter {
+            vpc_id: Some(vpc_id),
+        }))
+        .await
+        .unwrap()
+        .into_inner()
+        .record_ids;
+    assert_eq!(record_ids.len(), 3);
+    let records = env
+        .api
+        .find_tenant_dns_records_by_ids(Request::new(TenantDnsRecordsByIdsRequest { record_ids }))
+        .await
+        .unwrap()
+        .into_inner()
+        .records;
+    assert_eq!(records.len(), 3);
+
+    env.api
+        .delete_tenant_dns_record(Request::new(TenantDnsRecordDeletionRequest {
+            id: record.id,
+        }))
+        .await
+        .unwrap();
+    assert!(lookup(&env, "api.cluster.dwrt1.com.", "A").await.is_empty());
+}
+
+#[crate::sqlx_test]
+async fn test_tenant_dns_record_validation(pool: sqlx::PgPool) {
+    let env = create_test_env(pool).await;
+    env.create_vpc_and_tenant_segment().await;
+    let vpc_id = vpc_id(&env).await;
+
+    // Outside of the domains of the VPC
+    let err = create_record(&env, vpc_id, "api.example.org.", "A", "192.0.2.1")
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), Code::InvalidArgument);
+
+    // At the apex of the domain
+    let err = create_record(&env, vpc_id, "dwrt1.com.", "A", "192.0.2.1")
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), Code::InvalidArgument);
+
+    // Reserved record types and invalid content
+    for (qtype, content) in [
+        ("NS", "ns1.dwrt1.com."),
+        ("A", "fd00::1"),
+        ("SRV", "10 80 api.dwrt1.com."),
+    ] {
+        let err = create_record(&env, vpc_id, "svc.dwrt1.com.", qtype, content)
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), Code::InvalidArgument, "{qtype} {content}");
+    }
+
+    // A CNAME can not coexist with other records of the same name
+    create_record(&env, vpc_id, "svc.dwrt1.com.", "A", "192.0.2.1")
+        .await
+        .unwrap();
+    let err = create_record(&env, vpc_id, "svc.dwrt1.com.", "CNAME", "lb.dwrt1.com.")
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), Code::InvalidArgument);
+
+    // Duplicates are rejected
+    assert!(
+        create_record(&env, vpc_id, "svc.dwrt1.com.", "A", "192.0.2.1")
+            .await
+            .is_err()
+    );
+}
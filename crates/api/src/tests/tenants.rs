@@ -46,6 +46,8 @@ async fn test_tenant(pool: sqlx::PgPool) {
                 description: "".to_string(),
                 labels: vec![],
             }),
+            metadata_token_required: None,
+            metadata_token_hop_limit: None,
         }))
         .await
         .unwrap_err();
@@ -66,6 +68,8 @@ async fn test_tenant(pool: sqlx::PgPool) {
                     value: Some("bbb".to_string()),
                 }],
             }),
+            metadata_token_required: None,
+            metadata_token_hop_limit: None,
         }))
         .await
         .unwrap_err();
@@ -89,6 +93,8 @@ async fn test_tenant(pool: sqlx::PgPool) {
                 description: "".to_string(),
                 labels: vec![],
             }),
+            metadata_token_required: None,
+            metadata_token_hop_limit: None,
         }))
         .await
         .unwrap_err()
@@ -106,6 +112,8 @@ async fn test_tenant(pool: sqlx::PgPool) {
                 description: "".to_string(),
                 labels: vec![],
             }),
+            metadata_token_required: None,
+            metadata_token_hop_limit: None,
         }))
         .await
         .unwrap()
@@ -173,6 +181,8 @@ async fn test_tenant(pool: sqlx::PgPool) {
                 labels: vec![],
             }),
             if_version_match: Some(version.clone()),
+            metadata_token_required: None,
+            metadata_token_hop_limit: None,
         }))
         .await
         .unwrap_err();
@@ -194,6 +204,8 @@ async fn test_tenant(pool: sqlx::PgPool) {
                 }],
             }),
             if_version_match: Some(version.clone()),
+            metadata_token_required: None,
+            metadata_token_hop_limit: None,
         }))
         .await
         .unwrap_err();
@@ -230,6 +242,8 @@ async fn test_tenant(pool: sqlx::PgPool) {
                     labels: vec![],
                 }),
                 if_version_match: Some(version.clone()),
+                metadata_token_required: None,
+                metadata_token_hop_limit: None,
             }))
             .await
             .unwrap_err()
@@ -247,6 +261,8 @@ async fn test_tenant(pool: sqlx::PgPool) {
             metadata: None,
             routing_profile_type: None,
             if_version_match: Some(version.clone()),
+            metadata_token_required: None,
+            metadata_token_hop_limit: None,
         }))
         .await
         .unwrap_err();
@@ -268,6 +284,8 @@ async fn test_tenant(pool: sqlx::PgPool) {
                 labels: vec![],
             }),
             if_version_match: Some(version.clone()),
+            metadata_token_required: None,
+            metadata_token_hop_limit: None,
         }))
         .await
         .unwrap()
@@ -311,6 +329,8 @@ async fn test_tenant(pool: sqlx::PgPool) {
                 labels: vec![],
             }),
             if_version_match: Some(tenant.version),
+            metadata_token_required: None,
+            metadata_token_hop_limit: None,
         }))
         .await
         .unwrap();
@@ -327,6 +347,8 @@ async fn test_tenant(pool: sqlx::PgPool) {
                 description: "".to_string(),
                 labels: vec![],
             }),
+            metadata_token_required: None,
+            metadata_token_hop_limit: None,
         }))
         .await
         .unwrap()
@@ -356,6 +378,8 @@ async fn test_find_tenant_ids(pool: sqlx::PgPool) {
                     description: "".to_string(),
                     labels: vec![],
                 }),
+                metadata_token_required: None,
+                metadata_token_hop_limit: None,
             }))
             .await;
     }
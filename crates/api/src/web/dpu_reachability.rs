@@ -0,0 +1,290 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::cmp::min;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use askama::Template;
+use axum::Json;
+use axum::extract::{Query, State as AxumState};
+use axum::response::{Html, IntoResponse, Response};
+use carbide_uuid::machine::MachineId;
+use hyper::http::StatusCode;
+use model::dpu_reachability::{
+    self, DpuReachabilityReport, GroupReachability, PathHealth, ReachabilityFinding, UNKNOWN_GROUP,
+};
+use rpc::forge as forgerpc;
+use rpc::forge::forge_server::Forge;
+
+use super::filters;
+use crate::api::Api;
+
+// The ToR of a DPU is the switch its first uplink is connected to
+const TOR_PORT: &str = "p0";
+
+const DEFAULT_PAGE_RECORD_LIMIT: usize = 100;
+
+#[derive(Template)]
+#[template(path = "dpu_reachability.html")]
+struct DpuReachability {
+    vpc_id: String,
+    findings: Vec<FindingRow>,
+    groups: Vec<GroupReachability>,
+    /// The paths of the current page
+    paths: Vec<PathRow>,
+    path_count: usize,
+    current_page: usize,
+    previous: usize,
+    next: usize,
+    pages: usize,
+    page_range_start: usize,
+    page_range_end: usize,
+    limit: usize,
+}
+
+struct FindingRow {
+    kind: &'static str,
+    source: String,
+    destination: String,
+    description: String,
+}
+
+impl From<&ReachabilityFinding> for FindingRow {
+    fn from(finding: &ReachabilityFinding) -> Self {
+        let kind = match finding {
+            ReachabilityFinding::Asymmetric { .. } => "Asymmetric",
+            ReachabilityFinding::PartialLoss { .. } => "Partial loss",
+            ReachabilityFinding::Unreachable { .. } => "Unreachable",
+        };
+        FindingRow {
+            kind,
+            source: finding.source().to_string(),
+            destination: finding.destination().to_string(),
+            description: finding.to_string(),
+        }
+    }
+}
+
+#[derive(serde::Serialize)]
+struct PathRow {
+    source: String,
+    source_tor: String,
+    destination: String,
+    destination_tor: String,
+    probe: String,
+    target_ip: String,
+    health: PathHealth,
+    sent: u32,
+    received: u32,
+    loss_percent: String,
+    latency_p50_ms: String,
+    latency_p90_ms: String,
+    latency_p99_ms: String,
+    jitter_ms: String,
+    observed_at: String,
+}
+
+#[derive(serde::Serialize)]
+struct DpuReachabilityJson {
+    reports: Vec<DpuReachabilityReport>,
+    findings: Vec<ReachabilityFinding>,
+    groups: Vec<GroupReachability>,
+}
+
+fn format_ms(value: Option<f64>) -> String {
+    value.map(|v| format!("{v:.3}")).unwrap_or_default()
+}
+
+fn path_rows(reports: &[DpuReachabilityReport], tors: &HashMap<MachineId, String>) -> Vec<PathRow> {
+    let tor = |dpu_id: &MachineId| {
+        tors.get(dpu_id)
+            .cloned()
+            .unwrap_or_else(|| UNKNOWN_GROUP.to_string())
+    };
+
+    let mut rows: Vec<PathRow> = reports
+        .iter()
+        .flat_map(|report| {
+            report.results.iter().map(|result| PathRow {
+                source: report.dpu_id.to_string(),
+                source_tor: tor(&report.dpu_id),
+                destination: result.peer_dpu_id.to_string(),
+                destination_tor: tor(&result.peer_dpu_id),
+                probe: result.probe.to_string(),
+                target_ip: result.target_ip.to_string(),
+                health: result.health(),
+                sent: result.sent,
+                received: result.received,
+                loss_percent: format!("{:.0}", result.loss() * 100.0),
+                latency_p50_ms: format_ms(result.latency_p50_ms),
+                latency_p90_ms: format_ms(result.latency_p90_ms),
+                latency_p99_ms: format_ms(result.latency_p99_ms),
+                jitter_ms: format_ms(result.jitter_ms),
+                observed_at: report.observed_at.to_string(),
+            })
+        })
+        .collect();
+
+    // Problems first
+    rows.sort_by(|a, b| {
+        b.health
+            .cmp(&a.health)
+            .then_with(|| a.source.cmp(&b.source))
+            .then_with(|| a.destination.cmp(&b.destination))
+    });
+    rows
+}
+
+/// All reports, fetched page by page. The analysis needs the reports of both directions of a
+/// path, so it can't be limited to a page of reports.
+async fn fetch_reports(
+    api: &Arc<Api>,
+    vpc_id: Option<String>,
+) -> Result<Vec<DpuReachabilityReport>, tonic::Status> {
+    let mut reports = Vec::new();
+    let mut after_dpu_id = None;
+    loop {
+        let request = tonic::Request::new(forgerpc::DpuReachabilityMatrixRequest {
+            vpc_id: vpc_id.clone(),
+            after_dpu_id,
+            limit: None,
+        });
+        let matrix = api
+            .get_dpu_reachability_matrix(request)
+            .await
+            .map(|response| response.into_inner())?;
+
+        for report in matrix.reports {
+            reports.push(
+                DpuReachabilityReport::try_from(report)
+                    .map_err(|err| tonic::Status::internal(err.to_string()))?,
+            );
+        }
+
+        match matrix.next_dpu_id {
+            Some(next_dpu_id) => after_dpu_id = Some(next_dpu_id),
+            None => return Ok(reports),
+        }
+    }
+}
+
+/// The ToR of every DPU, from the LLDP data of its uplinks
+async fn fetch_tors(api: &Arc<Api>) -> Result<HashMap<MachineId, String>, tonic::Status> {
+    let request = tonic::Request::new(forgerpc::NetworkTopologyRequest { id: None });
+    let topology = api
+        .get_network_topology(request)
+        .await
+        .map(|response| response.into_inner())?;
+
+    let mut tors = HashMap::new();
+    for network_device in topology.network_devices {
+        for device in network_device.devices {
+            if device.local_port == TOR_PORT
+                && let Some(dpu_id) = device.id
+            {
+                tors.insert(dpu_id, network_device.name.clone());
+            }
+        }
+    }
+    Ok(tors)
+}
+
+pub async fn show_html(
+    AxumState(api): AxumState<Arc<Api>>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Response {
+    let vpc_id = params.get("vpc_id").filter(|v| !v.is_empty()).cloned();
+
+    let current_page = params
+        .get("current_page")
+        .map_or(0, |s| s.parse::<usize>().unwrap_or(0));
+
+    let limit: usize = params
+        .get("limit")
+        .and_then(|s| s.parse::<usize>().ok())
+        .filter(|limit| *limit > 0)
+        .map_or(DEFAULT_PAGE_RECORD_LIMIT, |limit| {
+            min(limit, DEFAULT_PAGE_RECORD_LIMIT)
+        });
+
+    let (reports, tors) =
+        match tokio::try_join!(fetch_reports(&api, vpc_id.clone()), fetch_tors(&api)) {
+            Ok(result) => result,
+            Err(err) => {
+                tracing::error!(%err, "fetch_dpu_reachability");
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Error loading DPU reachability",
+                )
+                    .into_response();
+            }
+        };
+
+    let paths = path_rows(&reports, &tors);
+    let path_count = paths.len();
+    let pages = path_count.div_ceil(limit);
+
+    let tmpl = DpuReachability {
+        vpc_id: vpc_id.unwrap_or_default(),
+        findings: dpu_reachability::analyze(&reports)
+            .iter()
+            .map(FindingRow::from)
+            .collect(),
+        groups: dpu_reachability::summarize_by_group(&reports, &tors),
+        paths: paths
+            .into_iter()
+            .skip(current_page.saturating_mul(limit))
+            .take(limit)
+            .collect(),
+        path_count,
+        current_page,
+        previous: current_page.saturating_sub(1),
+        next: current_page.saturating_add(1),
+        pages,
+        page_range_start: current_page.saturating_sub(3),
+        page_range_end: min(current_page.saturating_add(4), pages),
+        limit,
+    };
+    (StatusCode::OK, Html(tmpl.render().unwrap())).into_response()
+}
+
+pub async fn show_json(
+    AxumState(api): AxumState<Arc<Api>>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Response {
+    let vpc_id = params.get("vpc_id").filter(|v| !v.is_empty()).cloned();
+
+    let (reports, tors) = match tokio::try_join!(fetch_reports(&api, vpc_id), fetch_tors(&api)) {
+        Ok(result) => result,
+        Err(err) => {
+            tracing::error!(%err, "fetch_dpu_reachability");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Error loading DPU reachability",
+            )
+                .into_response();
+        }
+    };
+
+    let json = DpuReachabilityJson {
+        findings: dpu_reachability::analyze(&reports),
+        groups: dpu_reachability::summarize_by_group(&reports, &tors),
+        reports,
+    };
+    (StatusCode::OK, Json(json)).into_response()
+}
@@ -55,6 +55,7 @@ mod attestation;
 mod auth;
 mod domain;
 mod dpa;
+mod dpu_reachability;
 mod dpu_versions;
 mod expected_machine;
 mod explored_endpoint;
@@ -236,6 +237,8 @@ pub fn routes(api: Arc<Api>) -> eyre::Result<NormalizePath<Router>> {
             .route("/dpa/{dpa_id}", get(dpa::detail))
             .route("/dpu", get(machine::show_dpus_html))
             .route("/dpu.json", get(machine::show_dpus_json))
+            .route("/dpu/reachability", get(dpu_reachability::show_html))
+            .route("/dpu/reachability.json", get(dpu_reachability::show_json))
             .route("/dpu/versions", get(dpu_versions::list_html))
             .route("/dpu/versions.json", get(dpu_versions::list_json))
             .route(
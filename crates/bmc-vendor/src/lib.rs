@@ -27,6 +27,7 @@ use libredfish::model::service_root::RedfishVendor;
     Debug,
     Default,
     Eq,
+    Hash,
     PartialEq,
     clap::ValueEnum,
     clap::Parser,
@@ -31,6 +31,9 @@ pub struct Args {
 
     #[arg(short, long, value_enum, default_value_t=ServerMode::Dpu)]
     pub mode: ServerMode,
+
+    #[arg(long, help = "Also serve DHCPv6 on the given interfaces.")]
+    pub dhcpv6: bool,
 }
 
 #[derive(ValueEnum, Clone, Debug)]
@@ -0,0 +1,1095 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! DHCPv6 (RFC 8415) packet handling.
+//!
+//! This is the IPv6 counterpart of `packet_handler`. A received message is unwrapped from any
+//! Relay-forward layers, turned into the same `DhcpDiscovery` request the DHCPv4 path sends to
+//! the API, and answered with an Advertise/Reply which is wrapped back into Relay-reply layers.
+
+use std::net::{Ipv6Addr, SocketAddrV6};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use dhcproto::v6::{
+    DhcpOption, DhcpOptions, IAAddr, IANA, Message, MessageType, OptionCode, RelayMessage,
+    RelayMessageData, Status, StatusCode,
+};
+use dhcproto::{Decodable, Decoder, Encodable, Encoder};
+use ipnetwork::{IpNetwork, Ipv6Network};
+use lru::LruCache;
+use rpc::forge::{DhcpDiscovery, DhcpRecord};
+use tokio::net::UdpSocket;
+use tokio::sync::Mutex;
+
+use crate::cache::CacheEntry;
+use crate::errors::DhcpError;
+use crate::{Config, DhcpMode, util};
+
+/// Port DHCPv6 servers and relay agents listen on.
+pub const SERVER_PORT: u16 = 547;
+/// Port DHCPv6 clients listen on.
+pub const CLIENT_PORT: u16 = 546;
+/// All_DHCP_Relay_Agents_and_Servers multicast address (RFC 8415, section 7.1).
+pub const ALL_DHCP_RELAY_AGENTS_AND_SERVERS: Ipv6Addr = Ipv6Addr::new(0xff02, 0, 0, 0, 0, 0, 1, 2);
+/// msg-type and transaction-id. Anything shorter can not be a DHCPv6 message.
+pub const MINIMUM_DHCPV6_PKT_SIZE: usize = 4;
+
+const MSG_TYPE_RELAY_FORW: u8 = 12;
+
+const DUID_TYPE_LLT: u16 = 1;
+const DUID_TYPE_LL: u16 = 3;
+const HARDWARE_TYPE_ETHERNET: u16 = 1;
+
+pub struct DecodedPacketV6 {
+    /// Relay-forward layers, outermost first. Empty if the client talked to us directly.
+    relays: Vec<RelayMessage>,
+    message: Message,
+}
+
+impl DecodedPacketV6 {
+    pub fn decode(buf: &[u8]) -> Result<Self, DhcpError> {
+        if buf[0] != MSG_TYPE_RELAY_FORW {
+            let message = Message::decode(&mut Decoder::new(buf))?;
+            return Ok(Self {
+                relays: vec![],
+                message,
+            });
+        }
+
+        let mut relays = vec![];
+        let mut relay = RelayMessage::decode(&mut Decoder::new(buf))?;
+        loop {
+            let Some(DhcpOption::RelayMsg(inner)) = relay.opts().get(OptionCode::RelayMsg) else {
+                return Err(DhcpError::MissingClientMessage);
+            };
+            let inner = inner.clone();
+            relays.push(relay);
+
+            match inner {
+                RelayMessageData::Message(message) => return Ok(Self { relays, message }),
+                RelayMessageData::Relay(next) => relay = next,
+            }
+        }
+    }
+
+    /// The relay agent closest to the client. Its link-address identifies the client's link,
+    /// same as giaddr does for DHCPv4.
+    fn first_relay(&self) -> Option<&RelayMessage> {
+        self.relays.last()
+    }
+
+    pub fn is_relayed(&self) -> bool {
+        !self.relays.is_empty()
+    }
+
+    pub fn msg_type(&self) -> MessageType {
+        self.message.msg_type()
+    }
+
+    /// Interface-ID option added by the first relay. This is the DHCPv6 equivalent of the
+    /// DHCPv4 agent circuit id.
+    pub fn get_interface_id(&self) -> Option<String> {
+        let Some(DhcpOption::InterfaceId(interface_id)) =
+            self.first_relay()?.opts().get(OptionCode::InterfaceId)
+        else {
+            return None;
+        };
+        util::u8_to_hex_string(interface_id).ok()
+    }
+
+    fn client_id(&self) -> Option<&[u8]> {
+        match self.message.opts().get(OptionCode::ClientId) {
+            Some(DhcpOption::ClientId(client_id)) => Some(client_id),
+            _ => None,
+        }
+    }
+
+    fn server_id(&self) -> Option<&[u8]> {
+        match self.message.opts().get(OptionCode::ServerId) {
+            Some(DhcpOption::ServerId(server_id)) => Some(server_id),
+            _ => None,
+        }
+    }
+
+    fn get_iana(&self) -> Option<&IANA> {
+        match self.message.opts().get(OptionCode::IANA) {
+            Some(DhcpOption::IANA(iana)) => Some(iana),
+            _ => None,
+        }
+    }
+
+    /// Addresses the client asked for (or wants to confirm/release) in its IA_NA.
+    fn requested_addresses(&self) -> Vec<Ipv6Addr> {
+        self.get_iana()
+            .map(|iana| {
+                iana.opts
+                    .iter()
+                    .filter_map(|opt| match opt {
+                        DhcpOption::IAAddr(ia_addr) => Some(ia_addr.addr),
+                        _ => None,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// DHCPv6 has no chaddr. The MAC address is recovered from a link-layer based client DUID.
+    fn get_mac_address(&self) -> Result<String, DhcpError> {
+        let Some(client_id) = self.client_id() else {
+            return Err(DhcpError::MissingArgument(
+                "DHCPv6 client id is missing.".to_string(),
+            ));
+        };
+
+        mac_from_duid(client_id)
+            .map(util::u8_to_mac)
+            .ok_or_else(|| DhcpError::UnsupportedClientDuid(duid_to_string(client_id)))
+    }
+
+    fn is_this_for_us(&self, server_duid: &[u8]) -> Result<(), DhcpError> {
+        match self.server_id() {
+            Some(server_id) if server_id != server_duid => {
+                Err(DhcpError::NotMyPacket(duid_to_string(server_id)))
+            }
+            // No identifier sent by client (Solicit, Rebind, Confirm). It can be for us.
+            _ => Ok(()),
+        }
+    }
+
+    fn get_discovery_request(
+        &self,
+        handler: &dyn DhcpMode,
+        circuit_id: &str,
+    ) -> Result<DhcpDiscovery, DhcpError> {
+        let relay_address = self
+            .first_relay()
+            .map(|relay| relay.link_addr())
+            .unwrap_or(Ipv6Addr::UNSPECIFIED);
+
+        Ok(DhcpDiscovery {
+            mac_address: self.get_mac_address()?,
+            relay_address: relay_address.to_string(),
+            vendor_string: None,
+            link_address: None,
+            circuit_id: handler.get_circuit_id_v6(self, circuit_id),
+            remote_id: None,
+            desired_address: None,
+        })
+    }
+
+    /// A reply skeleton carrying the client's transaction id, client id and our server id.
+    fn reply(&self, msg_type: MessageType, server_duid: &[u8]) -> Message {
+        let mut msg = Message::new_with_id(msg_type, self.message.xid());
+        if let Some(client_id) = self.client_id() {
+            msg.opts_mut()
+                .insert(DhcpOption::ClientId(client_id.to_vec()));
+        }
+        msg.opts_mut()
+            .insert(DhcpOption::ServerId(server_duid.to_vec()));
+        msg
+    }
+
+    /// Wrap the reply in one Relay-reply per received Relay-forward, echoing the Interface-ID
+    /// option as required by RFC 8415, section 19.3.
+    fn encode_reply(&self, reply: Message) -> Result<Vec<u8>, DhcpError> {
+        let mut data = RelayMessageData::Message(reply);
+        for relay in self.relays.iter().rev() {
+            let mut opts = DhcpOptions::new();
+            if let Some(interface_id) = relay.opts().get(OptionCode::InterfaceId) {
+                opts.insert(interface_id.clone());
+            }
+            opts.insert(DhcpOption::RelayMsg(data));
+
+            data = RelayMessageData::Relay(RelayMessage {
+                msg_type: MessageType::RelayRepl,
+                hop_count: relay.hop_count(),
+                link_addr: relay.link_addr(),
+                peer_addr: relay.peer_addr(),
+                opts,
+            });
+        }
+
+        let mut encoded_packet = Vec::new();
+        let mut e = Encoder::new(&mut encoded_packet);
+        match data {
+            RelayMessageData::Message(msg) => msg.encode(&mut e)?,
+            RelayMessageData::Relay(relay) => relay.encode(&mut e)?,
+        }
+        Ok(encoded_packet)
+    }
+}
+
+pub struct PacketV6 {
+    encoded_packet: Vec<u8>,
+    pub dst_address: SocketAddrV6,
+}
+
+impl PacketV6 {
+    #[cfg(test)]
+    pub fn encoded_packet(&self) -> &Vec<u8> {
+        &self.encoded_packet
+    }
+
+    pub async fn send(&self, socket: Arc<UdpSocket>) -> Result<(), String> {
+        tracing::info!("Sending DHCPv6 packet to {:?}", self.dst_address);
+        socket
+            .send_to(&self.encoded_packet, self.dst_address)
+            .await
+            .map_err(|x| x.to_string())?;
+
+        Ok(())
+    }
+}
+
+pub async fn process_packet(
+    buf: &[u8],
+    src: SocketAddrV6,
+    config: &Config,
+    circuit_id: &str,
+    server_duid: &[u8],
+    handler: &dyn DhcpMode,
+    machine_cache: &mut Arc<Mutex<LruCache<String, CacheEntry>>>,
+) -> Result<PacketV6, DhcpError> {
+    let decoded_packet = DecodedPacketV6::decode(buf)?;
+    tracing::info!(
+        packet.received=?decoded_packet.message,
+        relay_hops = decoded_packet.relays.len(),
+        "Received DHCPv6 Packet"
+    );
+
+    if handler.should_be_relayed() && !decoded_packet.is_relayed() {
+        return Err(DhcpError::NonRelayedPacketV6(*src.ip()));
+    }
+    decoded_packet.is_this_for_us(server_duid)?;
+
+    let reply = match decoded_packet.msg_type() {
+        MessageType::Solicit | MessageType::Request | MessageType::Renew | MessageType::Rebind => {
+            let dhcp_response = handler
+                .discover_dhcp_v6(
+                    decoded_packet.get_discovery_request(handler, circuit_id)?,
+                    config,
+                    machine_cache,
+                )
+                .await?;
+            create_dhcpv6_reply_packet(&decoded_packet, dhcp_response, config, server_duid)?
+        }
+        MessageType::Confirm => {
+            let Some(dhcp_record) = handler
+                .find_dhcp_v6(
+                    decoded_packet.get_discovery_request(handler, circuit_id)?,
+                    config,
+                    machine_cache,
+                )
+                .await?
+            else {
+                // A server which can not tell whether the addresses are on-link must not
+                // reply (section 18.3.3).
+                return Err(DhcpError::DhcpV6ConfirmUnknownClient(
+                    decoded_packet.get_mac_address()?,
+                ));
+            };
+            create_confirm_reply(&decoded_packet, &dhcp_record, server_duid)?
+        }
+        MessageType::InformationRequest => {
+            let mut msg = decoded_packet.reply(MessageType::Reply, server_duid);
+            insert_nameservers(&mut msg, config);
+            msg
+        }
+        // Addresses are owned by the API and never go back to a pool, so there is nothing to
+        // release. The client still expects an acknowledgement.
+        MessageType::Release => {
+            let mut msg = decoded_packet.reply(MessageType::Reply, server_duid);
+            msg.opts_mut()
+                .insert(status_code(Status::Success, "Release received."));
+            msg
+        }
+        // The address is owned by the API, so there is no other address to hand out. Log it for
+        // the operator and acknowledge it, as the client waits for a Reply (section 18.3.8).
+        MessageType::Decline => {
+            tracing::warn!(
+                addresses = ?decoded_packet.requested_addresses(),
+                client_id = decoded_packet
+                    .client_id()
+                    .map(duid_to_string)
+                    .unwrap_or_default(),
+                "DHCPv6 client declined addresses"
+            );
+            let mut msg = decoded_packet.reply(MessageType::Reply, server_duid);
+            msg.opts_mut()
+                .insert(status_code(Status::Success, "Decline received."));
+            msg
+        }
+        msg_type => {
+            return Err(DhcpError::UnhandledMessageTypeV6(msg_type));
+        }
+    };
+    tracing::info!(packet.send=?reply, "Sending DHCPv6 Packet");
+
+    // Relayed replies go back to the relay agent on the server port, everything else straight
+    // to the client.
+    let dst_port = if decoded_packet.is_relayed() {
+        SERVER_PORT
+    } else {
+        CLIENT_PORT
+    };
+
+    Ok(PacketV6 {
+        encoded_packet: decoded_packet.encode_reply(reply)?,
+        dst_address: SocketAddrV6::new(*src.ip(), dst_port, src.flowinfo(), src.scope_id()),
+    })
+}
+
+fn create_dhcpv6_reply_packet(
+    src: &DecodedPacketV6,
+    forge_response: DhcpRecord,
+    config: &Config,
+    server_duid: &[u8],
+) -> Result<Message, DhcpError> {
+    let allocated_address = Ipv6Addr::from_str(&forge_response.address)
+        .map_err(|_| DhcpError::NotAnIpv6Record(forge_response.address.clone()))?;
+    // Not needed for the reply, but a record with an IPv4 prefix is not meant for DHCPv6.
+    parse_v6_prefix(&forge_response.prefix)?;
+
+    let rapid_commit = src.msg_type() == MessageType::Solicit
+        && src.message.opts().get(OptionCode::RapidCommit).is_some();
+    let reply_message_type = match src.msg_type() {
+        MessageType::Solicit if !rapid_commit => MessageType::Advertise,
+        _ => MessageType::Reply,
+    };
+
+    // https://www.rfc-editor.org/rfc/rfc8415
+    let mut msg = src.reply(reply_message_type, server_duid);
+    if rapid_commit {
+        msg.opts_mut().insert(DhcpOption::RapidCommit);
+    }
+
+    let Some(iana) = src.get_iana() else {
+        // IA_TA and IA_PD are not supported, only a single non-temporary address per interface.
+        msg.opts_mut().insert(status_code(
+            Status::NoAddrsAvail,
+            "Only IA_NA is supported.",
+        ));
+        return Ok(msg);
+    };
+
+    let mut ia_opts = DhcpOptions::new();
+    ia_opts.insert(DhcpOption::IAAddr(IAAddr {
+        addr: allocated_address,
+        preferred_life: config.dhcp_config.lease_time_secs,
+        valid_life: config.dhcp_config.lease_time_secs,
+        opts: DhcpOptions::new(),
+    }));
+    // This is the renew case with an address the API did not allocate. Tell the client to
+    // drop it by sending it back with zero lifetimes (section 18.3.4).
+    for requested in src.requested_addresses() {
+        if requested != allocated_address {
+            ia_opts.insert(DhcpOption::IAAddr(IAAddr {
+                addr: requested,
+                preferred_life: 0,
+                valid_life: 0,
+                opts: DhcpOptions::new(),
+            }));
+        }
+    }
+
+    msg.opts_mut().insert(DhcpOption::IANA(IANA {
+        id: iana.id,
+        t1: config.dhcp_config.renewal_time_secs,
+        t2: config.dhcp_config.rebinding_time_secs,
+        opts: ia_opts,
+    }));
+    insert_nameservers(&mut msg, config);
+
+    Ok(msg)
+}
+
+/// Confirm only asks whether the client's addresses are still on-link (section 18.3.3).
+fn create_confirm_reply(
+    src: &DecodedPacketV6,
+    dhcp_record: &DhcpRecord,
+    server_duid: &[u8],
+) -> Result<Message, DhcpError> {
+    let prefix = parse_v6_prefix(&dhcp_record.prefix)?;
+    let on_link = src
+        .requested_addresses()
+        .iter()
+        .all(|addr| prefix.contains(*addr));
+
+    let mut msg = src.reply(MessageType::Reply, server_duid);
+    msg.opts_mut().insert(if on_link {
+        status_code(Status::Success, "All addresses are on-link.")
+    } else {
+        status_code(Status::NotOnLink, "Addresses are not on-link.")
+    });
+    Ok(msg)
+}
+
+fn parse_v6_prefix(prefix: &str) -> Result<Ipv6Network, DhcpError> {
+    match prefix.parse::<IpNetwork>() {
+        Ok(IpNetwork::V6(prefix)) => Ok(prefix),
+        Ok(IpNetwork::V4(prefix)) => Err(DhcpError::GenericError(format!(
+            "Prefix ({prefix}) is an IPv4 network, which can not be used for DHCPv6."
+        ))),
+        Err(error) => Err(DhcpError::GenericError(format!(
+            "prefix value in deserialized protobuf is not an IP Network: {error}"
+        ))),
+    }
+}
+
+fn insert_nameservers(msg: &mut Message, config: &Config) {
+    if !config.dhcp_config.carbide_nameservers_ipv6.is_empty() {
+        msg.opts_mut().insert(DhcpOption::DomainNameServers(
+            config.dhcp_config.carbide_nameservers_ipv6.clone(),
+        ));
+    }
+}
+
+fn status_code(status: Status, msg: &str) -> DhcpOption {
+    DhcpOption::StatusCode(StatusCode {
+        status,
+        msg: msg.to_string(),
+    })
+}
+
+/// Extracts the MAC address from a DUID-LLT or DUID-LL (RFC 8415, sections 11.2 and 11.4).
+/// Other DUID types do not carry a link-layer address.
+fn mac_from_duid(duid: &[u8]) -> Option<&[u8]> {
+    let duid_type = u16::from_be_bytes([*duid.first()?, *duid.get(1)?]);
+    let hardware_type = u16::from_be_bytes([*duid.get(2)?, *duid.get(3)?]);
+    if hardware_type != HARDWARE_TYPE_ETHERNET {
+        return None;
+    }
+
+    let mac = match duid_type {
+        DUID_TYPE_LLT => duid.get(8..)?,
+        DUID_TYPE_LL => duid.get(4..)?,
+        _ => return None,
+    };
+    (mac.len() == 6).then_some(mac)
+}
+
+/// Builds a DUID-LL for the server from the MAC address of the interface it listens on.
+pub fn server_duid(mac: &[u8]) -> Vec<u8> {
+    let mut duid = Vec::with_capacity(4 + mac.len());
+    duid.extend_from_slice(&DUID_TYPE_LL.to_be_bytes());
+    duid.extend_from_slice(&HARDWARE_TYPE_ETHERNET.to_be_bytes());
+    duid.extend_from_slice(mac);
+    duid
+}
+
+fn duid_to_string(duid: &[u8]) -> String {
+    duid.iter().map(|x| format!("{x:02x}")).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use std::net::{Ipv4Addr, Ipv6Addr, SocketAddrV6};
+    use std::sync::Arc;
+
+    use dhcproto::v6::{
+        DhcpOption, DhcpOptions, IAAddr, IANA, Message, MessageType, OptionCode, RelayMessage,
+        RelayMessageData, Status,
+    };
+    use dhcproto::{Decodable, Decoder, Encodable, Encoder};
+    use lru::LruCache;
+    use rpc::forge::{DhcpDiscovery, DhcpRecord};
+    use tokio::sync::Mutex;
+    use tonic::async_trait;
+    use utils::models::dhcp::{DhcpConfig, HostConfig, InterfaceInfo};
+
+    use super::{DecodedPacketV6, mac_from_duid, process_packet, server_duid};
+    use crate::cache::{self, CacheEntry};
+    use crate::errors::DhcpError;
+    use crate::modes::controller::Controller;
+    use crate::modes::dpu::Dpu;
+    use crate::{Config, DhcpMode};
+
+    const SERVER_MAC: [u8; 6] = [0x02, 0x00, 0x00, 0x00, 0x00, 0x01];
+    const CLIENT_MAC: [u8; 6] = [0x00, 0x1b, 0x63, 0x84, 0x45, 0xe6];
+
+    /// Relay-forward captured from a relay agent on link 2001:db8:1::1 (Interface-ID
+    /// "vlan200"), carrying a Solicit with a DUID-LL client id, an IA_NA (IAID 1) and an ORO
+    /// asking for DNS servers.
+    const RELAYED_SOLICIT: [u8; 95] = [
+        0x0c, 0x00, 0x20, 0x01, 0x0d, 0xb8, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x01, 0xfe, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x1b, 0x63, 0xff,
+        0xfe, 0x84, 0x45, 0xe6, 0x00, 0x12, 0x00, 0x07, 0x76, 0x6c, 0x61, 0x6e, 0x32, 0x30, 0x30,
+        0x00, 0x09, 0x00, 0x2e, 0x01, 0x10, 0x20, 0x30, 0x00, 0x01, 0x00, 0x0a, 0x00, 0x03, 0x00,
+        0x01, 0x00, 0x1b, 0x63, 0x84, 0x45, 0xe6, 0x00, 0x08, 0x00, 0x02, 0x00, 0x00, 0x00, 0x03,
+        0x00, 0x0c, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x06, 0x00, 0x02, 0x00, 0x17,
+    ];
+
+    /// The Solicit from `RELAYED_SOLICIT`, as sent by a client on the local link.
+    const SOLICIT: [u8; 46] = [
+        0x01, 0x10, 0x20, 0x30, 0x00, 0x01, 0x00, 0x0a, 0x00, 0x03, 0x00, 0x01, 0x00, 0x1b, 0x63,
+        0x84, 0x45, 0xe6, 0x00, 0x08, 0x00, 0x02, 0x00, 0x00, 0x00, 0x03, 0x00, 0x0c, 0x00, 0x00,
+        0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x06, 0x00, 0x02, 0x00,
+        0x17,
+    ];
+
+    #[derive(Debug)]
+    struct TestV6 {}
+
+    impl TestV6 {
+        fn dhcp_record(discovery_request: &DhcpDiscovery) -> DhcpRecord {
+            assert_eq!(discovery_request.mac_address, "0:1b:63:84:45:e6");
+            assert_eq!(discovery_request.relay_address, "2001:db8:1::1");
+            assert_eq!(discovery_request.circuit_id.as_deref(), Some("vlan200"));
+
+            DhcpRecord {
+                machine_id: None,
+                machine_interface_id: Some("0fd6e9a3-06fc-4a22-ad29-aca299677b00".parse().unwrap()),
+                segment_id: Some("55a2d74e-f9e1-49d5-bf99-be05171a5d75".parse().unwrap()),
+                subdomain_id: None,
+                fqdn: "seventeen-connecticut.dev3.frg.nvidia.com".to_string(),
+                mac_address: "00:1b:63:84:45:e6".to_string(),
+                address: "2001:db8:1::2a".to_string(),
+                mtu: 1500,
+                prefix: "2001:db8:1::/64".to_string(),
+                gateway: None,
+                booturl: None,
+                last_invalidation_time: None,
+            }
+        }
+    }
+
+    #[async_trait]
+    impl DhcpMode for TestV6 {
+        async fn discover_dhcp(
+            &self,
+            discovery_request: DhcpDiscovery,
+            _config: &Config,
+            _machine_cache: &mut Arc<Mutex<LruCache<String, CacheEntry>>>,
+        ) -> Result<DhcpRecord, DhcpError> {
+            Ok(TestV6::dhcp_record(&discovery_request))
+        }
+
+        async fn find_dhcp_v6(
+            &self,
+            discovery_request: DhcpDiscovery,
+            _config: &Config,
+            _machine_cache: &mut Arc<Mutex<LruCache<String, CacheEntry>>>,
+        ) -> Result<Option<DhcpRecord>, DhcpError> {
+            Ok(Some(TestV6::dhcp_record(&discovery_request)))
+        }
+    }
+
+    fn test_config() -> Config {
+        Config {
+            dhcp_config: DhcpConfig {
+                carbide_dhcp_server: Ipv4Addr::new(10, 217, 126, 16),
+                carbide_nameservers_ipv6: vec!["2001:db8::53".parse().unwrap()],
+                ..Default::default()
+            },
+            host_config: None,
+        }
+    }
+
+    /// Host config as written by dpu-agent for a dual-stack tenant interface on "vlan200" and an
+    /// IPv4-only one on "vlan123".
+    fn dpu_config() -> Config {
+        let dual_stack = InterfaceInfo {
+            address: Ipv4Addr::new(10, 1, 0, 2),
+            gateway: Ipv4Addr::new(10, 1, 0, 1),
+            prefix: "10.1.0.0/24".to_string(),
+            fqdn: "chalie-failed.dev3.frg.nvidia.com".to_string(),
+            address_v6: Some("2001:db8:1::2a".parse().unwrap()),
+            prefix_v6: Some("2001:db8:1::/64".to_string()),
+            ..Default::default()
+        };
+        let ipv4_only = InterfaceInfo {
+            address: Ipv4Addr::new(10, 0, 0, 2),
+            gateway: Ipv4Addr::new(10, 0, 0, 1),
+            prefix: "10.0.0.0/24".to_string(),
+            ..Default::default()
+        };
+
+        Config {
+            host_config: Some(HostConfig {
+                host_interface_id: "6a3e76cd-e3f3-487a-aea0-04e56b049999".parse().unwrap(),
+                host_ip_addresses: [
+                    ("vlan200".to_string(), dual_stack),
+                    ("vlan123".to_string(), ipv4_only),
+                ]
+                .into(),
+            }),
+            ..test_config()
+        }
+    }
+
+    /// A client message as sent on the link between host and DPU, without any relay.
+    fn unrelayed(msg: Message) -> Vec<u8> {
+        let mut encoded_packet = Vec::new();
+        msg.encode(&mut Encoder::new(&mut encoded_packet)).unwrap();
+        encoded_packet
+    }
+
+    fn link_local_src() -> SocketAddrV6 {
+        SocketAddrV6::new("fe80::21b:63ff:fe84:45e6".parse().unwrap(), 546, 0, 2)
+    }
+
+    fn status(msg: &Message) -> Status {
+        let Some(DhcpOption::StatusCode(status)) = msg.opts().get(OptionCode::StatusCode) else {
+            panic!("Reply is missing a status code");
+        };
+        status.status
+    }
+
+    async fn process_dpu_packet(msg: Message, circuit_id: &str) -> Result<Message, DhcpError> {
+        let config = dpu_config();
+        let handler: Box<dyn DhcpMode> = Box::new(Dpu {});
+        let packet = process_packet(
+            &unrelayed(msg),
+            link_local_src(),
+            &config,
+            circuit_id,
+            &server_duid(&SERVER_MAC),
+            &*handler,
+            &mut machine_cache(),
+        )
+        .await?;
+
+        assert_eq!(packet.dst_address, link_local_src());
+        Ok(Message::decode(&mut Decoder::new(packet.encoded_packet())).unwrap())
+    }
+
+    fn machine_cache() -> Arc<Mutex<LruCache<String, CacheEntry>>> {
+        Arc::new(Mutex::new(LruCache::new(
+            std::num::NonZeroUsize::new(cache::MACHINE_CACHE_SIZE).unwrap(),
+        )))
+    }
+
+    fn relay_src() -> SocketAddrV6 {
+        SocketAddrV6::new("2001:db8:1::1".parse().unwrap(), 547, 0, 0)
+    }
+
+    fn relay_reply_inner(encoded: &[u8]) -> (RelayMessage, Message) {
+        let relay = RelayMessage::decode(&mut Decoder::new(encoded)).unwrap();
+        let Some(DhcpOption::RelayMsg(RelayMessageData::Message(msg))) =
+            relay.opts().get(OptionCode::RelayMsg)
+        else {
+            panic!("Relay-reply does not carry a client message");
+        };
+        let msg = msg.clone();
+        (relay, msg)
+    }
+
+    /// Wraps a client message the same way the captured relay agent did.
+    fn relayed(msg: Message) -> Vec<u8> {
+        let mut opts = DhcpOptions::new();
+        opts.insert(DhcpOption::InterfaceId(b"vlan200".to_vec()));
+        opts.insert(DhcpOption::RelayMsg(RelayMessageData::Message(msg)));
+        let relay = RelayMessage {
+            msg_type: MessageType::RelayForw,
+            hop_count: 0,
+            link_addr: "2001:db8:1::1".parse().unwrap(),
+            peer_addr: "fe80::21b:63ff:fe84:45e6".parse().unwrap(),
+            opts,
+        };
+
+        let mut encoded_packet = Vec::new();
+        relay
+            .encode(&mut Encoder::new(&mut encoded_packet))
+            .unwrap();
+        encoded_packet
+    }
+
+    fn client_message(msg_type: MessageType, requested: Option<Ipv6Addr>) -> Message {
+        let mut msg = Message::new_with_id(msg_type, [0x10, 0x20, 0x30]);
+        let mut client_id = vec![0x00, 0x03, 0x00, 0x01];
+        client_id.extend_from_slice(&CLIENT_MAC);
+        msg.opts_mut().insert(DhcpOption::ClientId(client_id));
+
+        let mut ia_opts = DhcpOptions::new();
+        if let Some(addr) = requested {
+            ia_opts.insert(DhcpOption::IAAddr(IAAddr {
+                addr,
+                preferred_life: 0,
+                valid_life: 0,
+                opts: DhcpOptions::new(),
+            }));
+        }
+        msg.opts_mut().insert(DhcpOption::IANA(IANA {
+            id: 1,
+            t1: 0,
+            t2: 0,
+            opts: ia_opts,
+        }));
+        msg
+    }
+
+    fn allocated_addresses(msg: &Message) -> Vec<(Ipv6Addr, u32)> {
+        let Some(DhcpOption::IANA(iana)) = msg.opts().get(OptionCode::IANA) else {
+            panic!("Reply does not carry an IA_NA");
+        };
+        iana.opts
+            .iter()
+            .filter_map(|opt| match opt {
+                DhcpOption::IAAddr(ia_addr) => Some((ia_addr.addr, ia_addr.valid_life)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_decode_relayed_solicit() {
+        let packet = DecodedPacketV6::decode(&RELAYED_SOLICIT).unwrap();
+        assert!(packet.is_relayed());
+        assert_eq!(packet.msg_type(), MessageType::Solicit);
+        assert_eq!(packet.get_interface_id().as_deref(), Some("vlan200"));
+        assert_eq!(packet.get_mac_address().unwrap(), "0:1b:63:84:45:e6");
+    }
+
+    #[test]
+    fn test_mac_from_duid() {
+        // DUID-LLT: type, hardware type, time, MAC
+        let llt = [
+            0x00, 0x01, 0x00, 0x01, 0x2b, 0x3c, 0x4d, 0x5e, 0x00, 0x1b, 0x63, 0x84, 0x45, 0xe6,
+        ];
+        assert_eq!(mac_from_duid(&llt), Some(&CLIENT_MAC[..]));
+        assert_eq!(
+            mac_from_duid(&server_duid(&CLIENT_MAC)),
+            Some(&CLIENT_MAC[..])
+        );
+        // DUID-UUID does not carry a MAC address
+        let uuid = [0x00, 0x04, 0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07];
+        assert_eq!(mac_from_duid(&uuid), None);
+        assert_eq!(mac_from_duid(&[0x00]), None);
+    }
+
+    #[tokio::test]
+    async fn test_relayed_solicit_advertise() {
+        let config = test_config();
+        let handler: Box<dyn DhcpMode> = Box::new(TestV6 {});
+        let packet = process_packet(
+            &RELAYED_SOLICIT,
+            relay_src(),
+            &config,
+            "eth0",
+            &server_duid(&SERVER_MAC),
+            &*handler,
+            &mut machine_cache(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(packet.dst_address, relay_src());
+
+        let (relay, msg) = relay_reply_inner(packet.encoded_packet());
+        assert_eq!(relay.msg_type(), MessageType::RelayRepl);
+        assert_eq!(
+            relay.link_addr(),
+            "2001:db8:1::1".parse::<Ipv6Addr>().unwrap()
+        );
+        assert_eq!(
+            relay.peer_addr(),
+            "fe80::21b:63ff:fe84:45e6".parse::<Ipv6Addr>().unwrap()
+        );
+        assert_eq!(
+            relay.opts().get(OptionCode::InterfaceId),
+            Some(&DhcpOption::InterfaceId(b"vlan200".to_vec()))
+        );
+
+        assert_eq!(msg.msg_type(), MessageType::Advertise);
+        assert_eq!(msg.xid(), [0x10, 0x20, 0x30]);
+        assert_eq!(
+            msg.opts().get(OptionCode::ServerId),
+            Some(&DhcpOption::ServerId(server_duid(&SERVER_MAC)))
+        );
+        assert_eq!(
+            allocated_addresses(&msg),
+            vec![("2001:db8:1::2a".parse().unwrap(), 604800)]
+        );
+        assert_eq!(
+            msg.opts().get(OptionCode::DomainNameServers),
+            Some(&DhcpOption::DomainNameServers(vec![
+                "2001:db8::53".parse().unwrap()
+            ]))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rapid_commit_solicit_reply() {
+        let mut solicit = client_message(MessageType::Solicit, None);
+        solicit.opts_mut().insert(DhcpOption::RapidCommit);
+
+        let config = test_config();
+        let handler: Box<dyn DhcpMode> = Box::new(TestV6 {});
+        let packet = process_packet(
+            &relayed(solicit),
+            relay_src(),
+            &config,
+            "eth0",
+            &server_duid(&SERVER_MAC),
+            &*handler,
+            &mut machine_cache(),
+        )
+        .await
+        .unwrap();
+
+        let (_, msg) = relay_reply_inner(packet.encoded_packet());
+        assert_eq!(msg.msg_type(), MessageType::Reply);
+        assert!(msg.opts().get(OptionCode::RapidCommit).is_some());
+    }
+
+    #[tokio::test]
+    async fn test_renew_with_stale_address() {
+        let mut renew = client_message(MessageType::Renew, Some("2001:db8:1::99".parse().unwrap()));
+        renew
+            .opts_mut()
+            .insert(DhcpOption::ServerId(server_duid(&SERVER_MAC)));
+
+        let config = test_config();
+        let handler: Box<dyn DhcpMode> = Box::new(TestV6 {});
+        let packet = process_packet(
+            &relayed(renew),
+            relay_src(),
+            &config,
+            "eth0",
+            &server_duid(&SERVER_MAC),
+            &*handler,
+            &mut machine_cache(),
+        )
+        .await
+        .unwrap();
+
+        let (_, msg) = relay_reply_inner(packet.encoded_packet());
+        assert_eq!(msg.msg_type(), MessageType::Reply);
+        let mut addresses = allocated_addresses(&msg);
+        addresses.sort();
+        assert_eq!(
+            addresses,
+            vec![
+                ("2001:db8:1::2a".parse().unwrap(), 604800),
+                ("2001:db8:1::99".parse().unwrap(), 0),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_confirm_not_on_link() {
+        let confirm = client_message(MessageType::Confirm, Some("2001:db8:2::5".parse().unwrap()));
+
+        let config = test_config();
+        let handler: Box<dyn DhcpMode> = Box::new(TestV6 {});
+        let packet = process_packet(
+            &relayed(confirm),
+            relay_src(),
+            &config,
+            "eth0",
+            &server_duid(&SERVER_MAC),
+            &*handler,
+            &mut machine_cache(),
+        )
+        .await
+        .unwrap();
+
+        let (_, msg) = relay_reply_inner(packet.encoded_packet());
+        let Some(DhcpOption::StatusCode(status)) = msg.opts().get(OptionCode::StatusCode) else {
+            panic!("Confirm reply is missing a status code");
+        };
+        assert_eq!(status.status, Status::NotOnLink);
+    }
+
+    #[tokio::test]
+    async fn test_request_for_other_server() {
+        let mut request = client_message(MessageType::Request, None);
+        request
+            .opts_mut()
+            .insert(DhcpOption::ServerId(server_duid(&[0x02, 0, 0, 0, 0, 0x02])));
+
+        let config = test_config();
+        let handler: Box<dyn DhcpMode> = Box::new(TestV6 {});
+        assert!(matches!(
+            process_packet(
+                &relayed(request),
+                relay_src(),
+                &config,
+                "eth0",
+                &server_duid(&SERVER_MAC),
+                &*handler,
+                &mut machine_cache(),
+            )
+            .await,
+            Err(DhcpError::NotMyPacket(..))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_non_relayed_packet() {
+        let config = test_config();
+        let handler: Box<dyn DhcpMode> = Box::new(TestV6 {});
+        assert!(matches!(
+            process_packet(
+                &SOLICIT,
+                SocketAddrV6::new("fe80::21b:63ff:fe84:45e6".parse().unwrap(), 546, 0, 2),
+                &config,
+                "eth0",
+                &server_duid(&SERVER_MAC),
+                &*handler,
+                &mut machine_cache(),
+            )
+            .await,
+            Err(DhcpError::NonRelayedPacketV6(..))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_dpu_information_request() {
+        let mut information_request =
+            Message::new_with_id(MessageType::InformationRequest, [0x01, 0x02, 0x03]);
+        information_request
+            .opts_mut()
+            .insert(DhcpOption::ClientId(server_duid(&CLIENT_MAC)));
+        let mut encoded_packet = Vec::new();
+        information_request
+            .encode(&mut Encoder::new(&mut encoded_packet))
+            .unwrap();
+
+        let src = SocketAddrV6::new("fe80::21b:63ff:fe84:45e6".parse().unwrap(), 546, 0, 2);
+        let config = test_config();
+        let handler: Box<dyn DhcpMode> = Box::new(Dpu {});
+        let packet = process_packet(
+            &encoded_packet,
+            src,
+            &config,
+            "vlan200",
+            &server_duid(&SERVER_MAC),
+            &*handler,
+            &mut machine_cache(),
+        )
+        .await
+        .unwrap();
+
+        // Not relayed: answered directly to the client, keeping the link-local scope.
+        assert_eq!(packet.dst_address, src);
+        let msg = Message::decode(&mut Decoder::new(packet.encoded_packet())).unwrap();
+        assert_eq!(msg.msg_type(), MessageType::Reply);
+        assert!(msg.opts().get(OptionCode::IANA).is_none());
+        assert!(msg.opts().get(OptionCode::DomainNameServers).is_some());
+    }
+    #[tokio::test]
+    async fn test_dpu_solicit_advertise() {
+        let msg = process_dpu_packet(client_message(MessageType::Solicit, None), "vlan200")
+            .await
+            .unwrap();
+
+        assert_eq!(msg.msg_type(), MessageType::Advertise);
+        assert_eq!(
+            allocated_addresses(&msg),
+            vec![("2001:db8:1::2a".parse().unwrap(), 604800)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dpu_request_reply() {
+        let mut request = client_message(MessageType::Request, None);
+        request
+            .opts_mut()
+            .insert(DhcpOption::ServerId(server_duid(&SERVER_MAC)));
+
+        let msg = process_dpu_packet(request, "vlan200").await.unwrap();
+
+        assert_eq!(msg.msg_type(), MessageType::Reply);
+        assert_eq!(
+            allocated_addresses(&msg),
+            vec![("2001:db8:1::2a".parse().unwrap(), 604800)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dpu_renew_reply() {
+        let mut renew = client_message(MessageType::Renew, Some("2001:db8:1::2a".parse().unwrap()));
+        renew
+            .opts_mut()
+            .insert(DhcpOption::ServerId(server_duid(&SERVER_MAC)));
+
+        let msg = process_dpu_packet(renew, "vlan200").await.unwrap();
+
+        assert_eq!(msg.msg_type(), MessageType::Reply);
+        assert_eq!(
+            allocated_addresses(&msg),
+            vec![("2001:db8:1::2a".parse().unwrap(), 604800)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dpu_confirm() {
+        let confirm = client_message(
+            MessageType::Confirm,
+            Some("2001:db8:1::2a".parse().unwrap()),
+        );
+        let msg = process_dpu_packet(confirm, "vlan200").await.unwrap();
+        assert_eq!(msg.msg_type(), MessageType::Reply);
+        assert_eq!(status(&msg), Status::Success);
+        assert!(msg.opts().get(OptionCode::IANA).is_none());
+
+        let confirm = client_message(MessageType::Confirm, Some("2001:db8:2::5".parse().unwrap()));
+        let msg = process_dpu_packet(confirm, "vlan200").await.unwrap();
+        assert_eq!(status(&msg), Status::NotOnLink);
+    }
+
+    #[tokio::test]
+    async fn test_dpu_solicit_without_ipv6_address() {
+        assert!(matches!(
+            process_dpu_packet(client_message(MessageType::Solicit, None), "vlan123").await,
+            Err(DhcpError::MissingArgument(..))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_decline_reply() {
+        let mut decline = client_message(
+            MessageType::Decline,
+            Some("2001:db8:1::2a".parse().unwrap()),
+        );
+        decline
+            .opts_mut()
+            .insert(DhcpOption::ServerId(server_duid(&SERVER_MAC)));
+
+        let msg = process_dpu_packet(decline, "vlan200").await.unwrap();
+
+        assert_eq!(msg.msg_type(), MessageType::Reply);
+        assert_eq!(status(&msg), Status::Success);
+    }
+
+    #[tokio::test]
+    async fn test_controller_confirm_unknown_client() {
+        // Nothing cached for the client. Asking the API could allocate, so there is no reply.
+        let confirm = client_message(
+            MessageType::Confirm,
+            Some("2001:db8:1::2a".parse().unwrap()),
+        );
+        let config = test_config();
+        let handler: Box<dyn DhcpMode> = Box::new(Controller {});
+        assert!(matches!(
+            process_packet(
+                &relayed(confirm),
+                relay_src(),
+                &config,
+                "eth0",
+                &server_duid(&SERVER_MAC),
+                &*handler,
+                &mut machine_cache(),
+            )
+            .await,
+            Err(DhcpError::DhcpV6ConfirmUnknownClient(..))
+        ));
+    }
+}
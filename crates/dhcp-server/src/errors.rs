@@ -15,7 +15,7 @@
  * limitations under the License.
  */
 use std::io;
-use std::net::{AddrParseError, Ipv4Addr};
+use std::net::{AddrParseError, Ipv4Addr, Ipv6Addr};
 use std::str::Utf8Error;
 
 use dhcproto::v4::relay::RelayCode;
@@ -78,6 +78,24 @@ pub enum DhcpError {
     #[error("Vendor class parse error: {0:?}")]
     VendorClassParseError(String),
 
+    #[error("Non relayed DHCPv6 packet received from: {0}. Dropping!")]
+    NonRelayedPacketV6(Ipv6Addr),
+
+    #[error("Unhandled DHCPv6 Message Type: {0:?}")]
+    UnhandledMessageTypeV6(dhcproto::v6::MessageType),
+
+    #[error("No address is known for DHCPv6 client {0}, can not answer its Confirm")]
+    DhcpV6ConfirmUnknownClient(String),
+
+    #[error("DHCPv6 message is missing the innermost client message")]
+    MissingClientMessage,
+
+    #[error("Could not derive a MAC address from the DHCPv6 client id: {0}")]
+    UnsupportedClientDuid(String),
+
+    #[error("DHCP record address {0} is not an IPv6 address")]
+    NotAnIpv6Record(String),
+
     #[error("Multiple interfaces are provided, but only 1 is supported: {0}")]
     MultipleInterfacesProvidedOneSupported(usize),
 }
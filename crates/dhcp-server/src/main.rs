@@ -17,6 +17,7 @@
 
 mod cache;
 mod command_line;
+mod dhcpv6;
 mod errors;
 mod modes;
 mod packet_handler;
@@ -25,7 +26,7 @@ mod util;
 mod vendor_class;
 
 use std::error::Error;
-use std::net::SocketAddr;
+use std::net::{Ipv6Addr, SocketAddr};
 use std::sync::Arc;
 
 use ::rpc::forge::{DhcpDiscovery, DhcpRecord};
@@ -45,7 +46,7 @@ use tracing_subscriber::EnvFilter;
 use tracing_subscriber::prelude::*;
 use utils::models::dhcp::{DhcpConfig, DhcpTimestamps, DhcpTimestampsFilePath, HostConfig};
 
-use crate::util::get_socket;
+use crate::util::{get_interface_index, get_interface_mac, get_socket};
 
 pub struct Server {
     socket: Arc<UdpSocket>,
@@ -111,6 +112,22 @@ async fn main() -> Result<(), Box<dyn Error>> {
     // Create a new socket for each interface.
     // In case of Controller, there will be only 1 interface.
     for interface in args.interfaces {
+        if args.dhcpv6 {
+            let mac = get_interface_mac(&interface).await.map_err(|err| {
+                DhcpError::InvalidInput(format!(
+                    "Cannot read MAC address of {interface} for the DHCPv6 server id: {err}"
+                ))
+            })?;
+            join_handles.push(tokio::spawn(listen_v6(
+                interface.clone(),
+                dhcpv6::server_duid(&mac),
+                config__.clone(),
+                args.mode.clone(),
+                dhcp_timestamps.clone(),
+                rate_limiter_.clone(),
+            )));
+        }
+
         let config_ = config__.clone();
         let args_mode = args.mode.clone();
         let dhcp_timestamps_ = dhcp_timestamps.clone();
@@ -203,6 +220,90 @@ async fn main() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+/// DHCPv6 counterpart of the per-interface listener loop in main. Runs next to the DHCPv4
+/// listener on the same interface and shares its rate limiter.
+async fn listen_v6(
+    interface: String,
+    server_duid: Vec<u8>,
+    config: Config,
+    args_mode: ServerMode,
+    dhcp_timestamps: Arc<Mutex<DhcpTimestamps>>,
+    rate_limiter: Arc<tokio::sync::Semaphore>,
+) {
+    let listen_address = SocketAddr::new(Ipv6Addr::UNSPECIFIED.into(), dhcpv6::SERVER_PORT);
+    let context = V6Context {
+        config,
+        handler: Arc::new(get_mode(&args_mode)),
+        interface,
+        server_duid: Arc::new(server_duid),
+        machine_cache: Arc::new(Mutex::new(LruCache::new(
+            std::num::NonZeroUsize::new(cache::MACHINE_CACHE_SIZE).unwrap(),
+        ))),
+        dhcp_timestamps,
+    };
+    let interface = &context.interface;
+
+    let mut server = Server {
+        socket: Arc::new(get_socket(listen_address, interface.clone()).await),
+    };
+    join_dhcpv6_multicast(&server.socket, interface).await;
+    tracing::info!(
+        "Listening on {:?} on interface: {}, mode: {:?}",
+        listen_address,
+        interface,
+        context.handler
+    );
+
+    loop {
+        let mut buf = [0; 1500];
+        let (len, addr) = match server.socket.recv_from(&mut buf).await {
+            Ok((len, addr)) => (len, addr),
+            Err(err) => {
+                tracing::error!("DHCPv6 socket recv failed with error: {err}");
+                drop(server.socket);
+                tracing::info!("Recreating the socket on {listen_address}, {interface}");
+                server.socket = Arc::new(get_socket(listen_address, interface.clone()).await);
+                join_dhcpv6_multicast(&server.socket, interface).await;
+                continue;
+            }
+        };
+
+        let Ok(permit) = rate_limiter.clone().try_acquire_owned() else {
+            // drop packet.
+            tracing::error!("Dropping DHCPv6 packet because of rate limiting.");
+            continue;
+        };
+
+        if len < dhcpv6::MINIMUM_DHCPV6_PKT_SIZE {
+            tracing::error!("Dropping DHCPv6 packet because it is smaller than min length.");
+            continue;
+        }
+
+        let context = context.clone();
+        let socket = server.socket.clone();
+
+        tokio::spawn(async move {
+            // Unlike DHCPv4, DHCPv6 has no end option, so trailing bytes would be parsed.
+            process_v6(addr, socket, &buf[..len], context).await;
+            drop(permit);
+        });
+    }
+}
+
+/// Clients which are not behind a relay send to All_DHCP_Relay_Agents_and_Servers.
+async fn join_dhcpv6_multicast(socket: &UdpSocket, interface: &str) {
+    let result = match get_interface_index(interface).await {
+        Ok(index) => socket
+            .join_multicast_v6(&dhcpv6::ALL_DHCP_RELAY_AGENTS_AND_SERVERS, index)
+            .map_err(DhcpError::from),
+        Err(err) => Err(err),
+    };
+    if let Err(err) = result {
+        // Relayed packets are unicast, so this only matters for directly attached clients.
+        tracing::warn!("Could not join DHCPv6 multicast group on {interface}: {err}");
+    }
+}
+
 fn get_mode(args_mode: &ServerMode) -> Box<dyn DhcpMode> {
     match args_mode {
         ServerMode::Dpu => Box::new(Dpu {}),
@@ -340,8 +441,57 @@ async fn process(
         }
     }
 
+    record_dhcp_timestamp(&config, dhcp_timestamps).await;
+}
+
+/// State of a DHCPv6 listener which every packet handling task gets a copy of.
+#[derive(Clone)]
+struct V6Context {
+    config: Config,
+    handler: Arc<Box<dyn DhcpMode>>,
+    interface: String,
+    server_duid: Arc<Vec<u8>>,
+    machine_cache: Arc<Mutex<LruCache<String, CacheEntry>>>,
+    dhcp_timestamps: Arc<Mutex<DhcpTimestamps>>,
+}
+
+#[tracing::instrument(skip_all)]
+async fn process_v6(addr: SocketAddr, socket: Arc<UdpSocket>, buf: &[u8], mut context: V6Context) {
+    let SocketAddr::V6(addr) = addr else {
+        tracing::error!("Dropping ipv4 packet received on DHCPv6 socket.");
+        return;
+    };
+
+    tracing::info!("Received DHCPv6 packet [{}] from {}", buf[0], addr);
+
+    let packet = match dhcpv6::process_packet(
+        buf,
+        addr,
+        &context.config,
+        &context.interface,
+        &context.server_duid,
+        &**context.handler,
+        &mut context.machine_cache,
+    )
+    .await
+    {
+        Ok(packet) => packet,
+        Err(err) => {
+            tracing::error!("Dropping DHCPv6 packet because of error: {}", err);
+            return;
+        }
+    };
+
+    if let Err(err) = packet.send(socket).await {
+        tracing::error!("DHCPv6 packet sending failed because of error: {}", err);
+    }
+
+    record_dhcp_timestamp(&context.config, context.dhcp_timestamps).await;
+}
+
+async fn record_dhcp_timestamp(config: &Config, dhcp_timestamps: Arc<Mutex<DhcpTimestamps>>) {
     // Tell forge-dpu-agent that an IP has been requested for this interface.
-    if let Some(host_config) = config.host_config {
+    if let Some(host_config) = &config.host_config {
         let mut dhcp_timestamps = dhcp_timestamps.lock().await;
         dhcp_timestamps.add_timestamp(host_config.host_interface_id, Utc::now().to_rfc3339());
         if let Err(e) = dhcp_timestamps.write() {
@@ -386,6 +536,7 @@ mod test {
                     .to_string(),
             ),
             mode: crate::command_line::ServerMode::Dpu,
+            dhcpv6: false,
         }
     }
 
@@ -33,6 +33,30 @@ use crate::vendor_class::VendorClass;
 #[derive(Debug)]
 pub struct Controller {}
 
+/// Link (or relay) address and vendor id of a request, which together with the other request
+/// fields make up the cache key.
+fn cache_key_parts(discovery_request: &DhcpDiscovery) -> Result<(IpAddr, String), DhcpError> {
+    let link_address = IpAddr::from_str(
+        discovery_request
+            .link_address
+            .as_ref()
+            .unwrap_or(&discovery_request.relay_address),
+    )?;
+
+    let vendor_id = match &discovery_request.vendor_string {
+        Some(vendor_string) => {
+            VendorClass::from_str(vendor_string)
+                .map_err(|e| {
+                    DhcpError::VendorClassParseError(format!("Vendor string parse error: {e:?}"))
+                })?
+                .id
+        }
+        None => String::new(),
+    };
+
+    Ok((link_address, vendor_id))
+}
+
 #[async_trait]
 impl DhcpMode for Controller {
     async fn discover_dhcp(
@@ -42,25 +66,7 @@ impl DhcpMode for Controller {
         machine_cache: &mut std::sync::Arc<Mutex<LruCache<String, CacheEntry>>>,
     ) -> Result<DhcpRecord, DhcpError> {
         // check if entry present in cache.
-        let link_address = IpAddr::from_str(
-            discovery_request
-                .link_address
-                .as_ref()
-                .unwrap_or(&discovery_request.relay_address),
-        )?;
-
-        let vendor_class = if let Some(vendor_string) = &discovery_request.vendor_string {
-            Some(VendorClass::from_str(vendor_string).map_err(|e| {
-                DhcpError::VendorClassParseError(format!("Vendor string parse error: {e:?}"))
-            })?)
-        } else {
-            None
-        };
-
-        let vendor_id = match &vendor_class {
-            Some(vc) => vc.id.as_str(),
-            None => "",
-        };
+        let (link_address, vendor_id) = cache_key_parts(&discovery_request)?;
 
         {
             let mut machine_cache = machine_cache.lock().await;
@@ -69,7 +75,7 @@ impl DhcpMode for Controller {
                 link_address,
                 &discovery_request.circuit_id,
                 &discovery_request.remote_id,
-                vendor_id,
+                &vendor_id,
                 &mut machine_cache,
             ) {
                 tracing::info!(
@@ -92,11 +98,31 @@ impl DhcpMode for Controller {
             link_address,
             discovery_request.circuit_id,
             discovery_request.remote_id,
-            vendor_id,
+            &vendor_id,
             record.clone(),
             &mut machine_cache,
         );
 
         Ok(record)
     }
+
+    /// Asking the API could allocate an address, so only answer from the cache.
+    async fn find_dhcp_v6(
+        &self,
+        discovery_request: DhcpDiscovery,
+        _config: &Config,
+        machine_cache: &mut std::sync::Arc<Mutex<LruCache<String, CacheEntry>>>,
+    ) -> Result<Option<DhcpRecord>, DhcpError> {
+        let (link_address, vendor_id) = cache_key_parts(&discovery_request)?;
+        let mut machine_cache = machine_cache.lock().await;
+        Ok(cache::get(
+            &discovery_request.mac_address,
+            link_address,
+            &discovery_request.circuit_id,
+            &discovery_request.remote_id,
+            &vendor_id,
+            &mut machine_cache,
+        )
+        .map(|cache_entry| cache_entry.dhcp_record))
+    }
 }
@@ -22,6 +22,7 @@ use utils::models::dhcp::InterfaceInfo;
 
 use super::DhcpMode;
 use crate::cache::CacheEntry;
+use crate::dhcpv6::DecodedPacketV6;
 use crate::errors::DhcpError;
 use crate::packet_handler::DecodedPacket;
 use crate::{Config, HostConfig};
@@ -47,6 +48,53 @@ fn from_host_conf(value: &InterfaceInfo, interface_id: MachineInterfaceId) -> Dh
     }
 }
 
+fn from_host_conf_v6(
+    value: &InterfaceInfo,
+    interface_id: MachineInterfaceId,
+    circuit_id: &str,
+) -> Result<DhcpRecord, DhcpError> {
+    let (Some(address), Some(prefix)) = (value.address_v6, &value.prefix_v6) else {
+        return Err(DhcpError::MissingArgument(format!(
+            "Could not find IPv6 details for {circuit_id}"
+        )));
+    };
+
+    // Hosts learn their IPv6 gateway from router advertisements, not from DHCPv6.
+    Ok(DhcpRecord {
+        address: address.to_string(),
+        prefix: prefix.clone(),
+        gateway: None,
+        ..from_host_conf(value, interface_id)
+    })
+}
+
+/// Find the host interface details for the circuit id (interface name) of a request.
+fn host_interface<'a>(
+    discovery_request: &DhcpDiscovery,
+    config: &'a Config,
+) -> Result<(&'a InterfaceInfo, MachineInterfaceId, String), DhcpError> {
+    let Some(circuit_id) = discovery_request.circuit_id.clone() else {
+        return Err(DhcpError::MissingArgument(
+            "Missing circuit id.".to_string(),
+        ));
+    };
+
+    let Some(host_config) = &config.host_config else {
+        return Err(DhcpError::MissingArgument(
+            "host_config is missing.".to_string(),
+        ));
+    };
+
+    let ip_details = host_config
+        .host_ip_addresses
+        .get(&circuit_id)
+        .ok_or_else(|| {
+            DhcpError::MissingArgument(format!("Could not find IP details for {circuit_id}"))
+        })?;
+
+    Ok((ip_details, host_config.host_interface_id, circuit_id))
+}
+
 #[async_trait]
 impl DhcpMode for Dpu {
     async fn discover_dhcp(
@@ -55,29 +103,30 @@ impl DhcpMode for Dpu {
         config: &Config,
         _machine_cache: &mut std::sync::Arc<tokio::sync::Mutex<LruCache<String, CacheEntry>>>,
     ) -> Result<DhcpRecord, DhcpError> {
-        let Some(circuit_id) = discovery_request.circuit_id else {
-            return Err(DhcpError::MissingArgument(
-                "Missing circuit id.".to_string(),
-            ));
-        };
-
-        let ip_details = config
-            .host_config
-            .as_ref()
-            .ok_or_else(|| DhcpError::InvalidInput("host input is invalid.".to_string()))?
-            .host_ip_addresses
-            .get(&circuit_id)
-            .ok_or_else(|| {
-                DhcpError::MissingArgument(format!("Could not find IP details for {circuit_id}"))
-            })?;
-
-        let Some(host_config) = &config.host_config else {
-            return Err(DhcpError::MissingArgument(
-                "host_config is missing.".to_string(),
-            ));
-        };
-
-        Ok(from_host_conf(ip_details, host_config.host_interface_id))
+        let (ip_details, interface_id, _) = host_interface(&discovery_request, config)?;
+        Ok(from_host_conf(ip_details, interface_id))
+    }
+
+    async fn discover_dhcp_v6(
+        &self,
+        discovery_request: DhcpDiscovery,
+        config: &Config,
+        _machine_cache: &mut std::sync::Arc<tokio::sync::Mutex<LruCache<String, CacheEntry>>>,
+    ) -> Result<DhcpRecord, DhcpError> {
+        let (ip_details, interface_id, circuit_id) = host_interface(&discovery_request, config)?;
+        from_host_conf_v6(ip_details, interface_id, &circuit_id)
+    }
+
+    /// The host config is only read, so this is the same lookup as `discover_dhcp_v6`.
+    async fn find_dhcp_v6(
+        &self,
+        discovery_request: DhcpDiscovery,
+        config: &Config,
+        machine_cache: &mut std::sync::Arc<tokio::sync::Mutex<LruCache<String, CacheEntry>>>,
+    ) -> Result<Option<DhcpRecord>, DhcpError> {
+        self.discover_dhcp_v6(discovery_request, config, machine_cache)
+            .await
+            .map(Some)
     }
 
     /// Here circuit is interface name. This is what dhcp-relay used to fill.
@@ -85,6 +134,10 @@ impl DhcpMode for Dpu {
         Some(circuit_id.to_string())
     }
 
+    fn get_circuit_id_v6(&self, _packet: &DecodedPacketV6, circuit_id: &str) -> Option<String> {
+        Some(circuit_id.to_string())
+    }
+
     fn should_be_relayed(&self) -> bool {
         false
     }
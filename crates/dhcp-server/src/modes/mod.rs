@@ -24,6 +24,7 @@ use tonic::async_trait;
 
 use crate::Config;
 use crate::cache::CacheEntry;
+use crate::dhcpv6::DecodedPacketV6;
 use crate::errors::DhcpError;
 use crate::packet_handler::{DecodedPacket, Packet};
 
@@ -39,6 +40,27 @@ pub trait DhcpMode: Send + Sync + std::fmt::Debug {
         config: &Config,
         machine_cache: &mut Arc<Mutex<LruCache<String, CacheEntry>>>,
     ) -> Result<DhcpRecord, DhcpError>;
+    /// Method to determine IPv6 address to be returned to a DHCPv6 client. The API picks the
+    /// address family from the relay address, so by default this is the same lookup.
+    async fn discover_dhcp_v6(
+        &self,
+        discovery_request: DhcpDiscovery,
+        config: &Config,
+        machine_cache: &mut Arc<Mutex<LruCache<String, CacheEntry>>>,
+    ) -> Result<DhcpRecord, DhcpError> {
+        self.discover_dhcp(discovery_request, config, machine_cache)
+            .await
+    }
+    /// Read-only lookup of the IPv6 address a DHCPv6 client already got, used to answer Confirm.
+    /// Must not allocate anything. Returns None if the assignment is not known.
+    async fn find_dhcp_v6(
+        &self,
+        _discovery_request: DhcpDiscovery,
+        _config: &Config,
+        _machine_cache: &mut Arc<Mutex<LruCache<String, CacheEntry>>>,
+    ) -> Result<Option<DhcpRecord>, DhcpError> {
+        Ok(None)
+    }
     /// And at what address?
     fn get_destination_address(&self, packet: &Packet) -> SocketAddrV4 {
         packet.dst_address()
@@ -47,6 +69,10 @@ pub trait DhcpMode: Send + Sync + std::fmt::Debug {
     fn get_circuit_id(&self, packet: &DecodedPacket, _circuit_id: &str) -> Option<String> {
         packet.get_circuit_id()
     }
+    /// Get circuit id for a DHCPv6 packet. Relays carry it in the Interface-ID option.
+    fn get_circuit_id_v6(&self, packet: &DecodedPacketV6, _circuit_id: &str) -> Option<String> {
+        packet.get_interface_id()
+    }
     /// Should be relayed? A controller mode will accept on relayed packet, while dpu with relay
     /// mode will never get a relayed packet.
     fn should_be_relayed(&self) -> bool {
@@ -82,13 +82,34 @@ pub fn machine_get_filename(
     url.into_bytes().to_vec()
 }
 
+/// Read the MAC address of an interface from sysfs.
+pub async fn get_interface_mac(interface: &str) -> Result<Vec<u8>, DhcpError> {
+    let path = format!("/sys/class/net/{interface}/address");
+    let mac = tokio::fs::read_to_string(&path).await?;
+    mac.trim()
+        .split(':')
+        .map(|x| u8::from_str_radix(x, 16))
+        .collect::<Result<Vec<u8>, _>>()
+        .map_err(|e| DhcpError::InvalidInput(format!("Invalid MAC address in {path}: {e}")))
+}
+
+/// Read the index of an interface from sysfs. Needed to join multicast groups on it.
+pub async fn get_interface_index(interface: &str) -> Result<u32, DhcpError> {
+    let path = format!("/sys/class/net/{interface}/ifindex");
+    let index = tokio::fs::read_to_string(&path).await?;
+    index
+        .trim()
+        .parse()
+        .map_err(|e| DhcpError::InvalidInput(format!("Invalid interface index in {path}: {e}")))
+}
+
 /// Create a UDP socket and set non_blocking, broadcast and other options flag on it.
 pub async fn get_socket(listen_address: core::net::SocketAddr, interface: String) -> UdpSocket {
     for retry in 0..10 {
         // Create a socket2.socket. std and tokio sockets do not support advance options like
         // reuseaddr to be set.
         let socket = match socket2::Socket::new(
-            socket2::Domain::IPV4,
+            socket2::Domain::for_address(listen_address),
             socket2::Type::DGRAM,
             Some(socket2::Protocol::UDP),
         ) {
@@ -102,9 +123,15 @@ pub async fn get_socket(listen_address: core::net::SocketAddr, interface: String
 
         socket_opr!(socket, socket.set_reuse_address(true), retry);
         socket_opr!(socket, socket.set_nonblocking(true), retry);
+        if listen_address.is_ipv6() {
+            // DHCPv4 has its own socket on the same interface.
+            socket_opr!(socket, socket.set_only_v6(true), retry);
+        }
         socket_opr!(socket, socket.bind(&listen_address.into()), retry);
-        // Not for listening, but allowed for sending.
-        socket_opr!(socket, socket.set_broadcast(true), retry);
+        if listen_address.is_ipv4() {
+            // Not for listening, but allowed for sending.
+            socket_opr!(socket, socket.set_broadcast(true), retry);
+        }
 
         let mut retries_left = 10;
         while retries_left > 0 && socket.bind_device(Some(interface.as_bytes())).is_err() {
@@ -72,6 +72,7 @@ pub enum DnsResourceRecordType {
     MX,
     TXT,
     PTR,
+    SRV,
     ANY,
 }
 
@@ -86,6 +87,7 @@ impl Display for DnsResourceRecordType {
             DnsResourceRecordType::MX => constants::DNS_TYPE_MX,
             DnsResourceRecordType::TXT => constants::DNS_TYPE_TXT,
             DnsResourceRecordType::PTR => constants::DNS_TYPE_PTR,
+            DnsResourceRecordType::SRV => constants::DNS_TYPE_SRV,
             DnsResourceRecordType::ANY => constants::DNS_TYPE_ANY,
         };
         write!(f, "{record_type}")
@@ -230,6 +232,7 @@ impl TryFrom<&str> for DnsResourceRecordType {
             constants::DNS_TYPE_MX => Ok(DnsResourceRecordType::MX),
             constants::DNS_TYPE_TXT => Ok(DnsResourceRecordType::TXT),
             constants::DNS_TYPE_PTR => Ok(DnsResourceRecordType::PTR),
+            constants::DNS_TYPE_SRV => Ok(DnsResourceRecordType::SRV),
             constants::DNS_TYPE_ANY => Ok(DnsResourceRecordType::ANY),
             _ => Err(format!("RecordType {value} not implement")),
         }
@@ -249,6 +252,7 @@ impl TryFrom<String> for DnsResourceRecordType {
             constants::DNS_TYPE_MX => Ok(DnsResourceRecordType::MX),
             constants::DNS_TYPE_TXT => Ok(DnsResourceRecordType::TXT),
             constants::DNS_TYPE_PTR => Ok(DnsResourceRecordType::PTR),
+            constants::DNS_TYPE_SRV => Ok(DnsResourceRecordType::SRV),
             constants::DNS_TYPE_ANY => Ok(DnsResourceRecordType::ANY),
             _ => Err(format!("RecordType {value} not implement")),
         }
@@ -266,6 +270,7 @@ impl From<DnsResourceRecordType> for String {
             DnsResourceRecordType::MX => constants::DNS_TYPE_MX.to_string(),
             DnsResourceRecordType::TXT => constants::DNS_TYPE_TXT.to_string(),
             DnsResourceRecordType::PTR => constants::DNS_TYPE_PTR.to_string(),
+            DnsResourceRecordType::SRV => constants::DNS_TYPE_SRV.to_string(),
             DnsResourceRecordType::ANY => constants::DNS_TYPE_ANY.to_string(),
         }
     }
@@ -433,6 +438,10 @@ mod tests {
             DnsResourceRecordType::try_from("PTR".to_string()).unwrap(),
             DnsResourceRecordType::PTR
         );
+        assert_eq!(
+            DnsResourceRecordType::try_from("SRV".to_string()).unwrap(),
+            DnsResourceRecordType::SRV
+        );
         assert_eq!(
             DnsResourceRecordType::try_from("ANY".to_string()).unwrap(),
             DnsResourceRecordType::ANY
@@ -455,6 +464,7 @@ mod tests {
         assert_eq!(String::from(DnsResourceRecordType::MX), "MX".to_string());
         assert_eq!(String::from(DnsResourceRecordType::TXT), "TXT".to_string());
         assert_eq!(String::from(DnsResourceRecordType::PTR), "PTR".to_string());
+        assert_eq!(String::from(DnsResourceRecordType::SRV), "SRV".to_string());
         assert_eq!(String::from(DnsResourceRecordType::ANY), "ANY".to_string());
     }
 
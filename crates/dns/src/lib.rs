@@ -26,6 +26,8 @@ use rpc::JsonDnsResourceRecord;
 use rpc::forge_tls_client::{ApiConfig, ForgeClientT, ForgeTlsClient};
 use rpc::protos::dns::{
     DnsResourceRecordLookupRequest, DomainMetadataRequest, GetAllDomainsRequest,
+    GetAllRecordsForDomainRequest, GetDomainInfoRequest, GetDomainKeysRequest,
+    GetUpdatedMastersRequest, SetNotifiedRequest,
 };
 use serde_json::{Value, json};
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
@@ -148,6 +150,69 @@ async fn handle_connection(
                     }
                 }
             }
+            "list" => {
+                match handle_list(&req, &client).await {
+                    Ok(response) => response,
+                    Err(e) => {
+                        tracing::error!(
+                            method = "list",
+                            error = %e,
+                            "Failed to process list - returning failure to PowerDNS"
+                        );
+                        // An empty result would be transferred as an empty zone, so fail instead
+                        PdnsResponse::from(Value::Bool(false))
+                    }
+                }
+            }
+
+            "getDomainInfo" => match handle_get_domain_info(&req, &client).await {
+                Ok(response) => response,
+                Err(e) => {
+                    tracing::error!(
+                        method = "getDomainInfo",
+                        error = %e,
+                        "Failed to process getDomainInfo - returning failure to PowerDNS"
+                    );
+                    PdnsResponse::from(Value::Bool(false))
+                }
+            },
+
+            "getDomainKeys" => match handle_get_domain_keys(&req, &client).await {
+                Ok(response) => response,
+                Err(e) => {
+                    tracing::error!(
+                        method = "getDomainKeys",
+                        error = %e,
+                        "Failed to process getDomainKeys - returning failure to PowerDNS"
+                    );
+                    PdnsResponse::from(Value::Bool(false))
+                }
+            },
+
+            "getUpdatedMasters" => match handle_get_updated_masters(&req, &client).await {
+                Ok(response) => response,
+                Err(e) => {
+                    tracing::error!(
+                        method = "getUpdatedMasters",
+                        error = %e,
+                        "Failed to process getUpdatedMasters - returning empty result to PowerDNS"
+                    );
+                    PdnsResponse::from(Vec::<Value>::new())
+                }
+            },
+
+            "setNotified" => match handle_set_notified(&req, &client).await {
+                Ok(response) => response,
+                Err(e) => {
+                    tracing::error!(
+                        method = "setNotified",
+                        error = %e,
+                        "Failed to process setNotified - returning failure to PowerDNS"
+                    );
+                    PdnsResponse::from(Value::Bool(false))
+                }
+            },
+
             "initialize" => {
                 let span = tracing::info_span!("initialize");
                 let _guard = span.enter();
@@ -310,6 +375,147 @@ async fn handle_lookup(
     Ok(response)
 }
 
+/// Handles `list`, which PowerDNS uses to serve AXFR requests for a zone
+async fn handle_list(
+    req: &PdnsRequest,
+    client: &Arc<Mutex<ForgeClientT>>,
+) -> Result<PdnsResponse, Report> {
+    let query: GetAllRecordsForDomainRequest = req.try_into()?;
+    let span = tracing::info_span!("list", zone = %query.name);
+    let _guard = span.enter();
+
+    tracing::info!(method = "list", zone = %query.name, "Processing list request");
+
+    let api_start = std::time::Instant::now();
+    let mut client = client.lock().await;
+    let records = client.get_all_records_for_domain(query).await?.into_inner();
+    let api_duration = api_start.elapsed();
+
+    let res = records
+        .result
+        .into_iter()
+        .map(|x| Value::from(JsonDnsResourceRecord(x)))
+        .collect::<Vec<_>>();
+
+    tracing::info!(
+        method = "list",
+        record_count = res.len(),
+        duration_ms = api_duration.as_millis(),
+        "list completed"
+    );
+
+    let response = PdnsResponse::from(res);
+    tracing::trace!(
+        method = "list",
+        response = ?response,
+        "Sending response"
+    );
+    Ok(response)
+}
+
+async fn handle_get_domain_info(
+    req: &PdnsRequest,
+    client: &Arc<Mutex<ForgeClientT>>,
+) -> Result<PdnsResponse, Report> {
+    let query: GetDomainInfoRequest = req.try_into()?;
+    let span = tracing::info_span!("get_domain_info", domain = %query.name);
+    let _guard = span.enter();
+
+    tracing::info!(
+        method = "getDomainInfo",
+        domain = %query.name,
+        "Processing getDomainInfo request"
+    );
+
+    let mut client = client.lock().await;
+    let info = client.get_domain_info(query).await?.into_inner();
+
+    // PowerDNS expects `false` for domains the backend is not authoritative for
+    let response = match info.result {
+        Some(info) => PdnsResponse::from(serde_json::to_value(info)?),
+        None => PdnsResponse::from(Value::Bool(false)),
+    };
+    tracing::trace!(
+        method = "getDomainInfo",
+        response = ?response,
+        "Sending response"
+    );
+    Ok(response)
+}
+
+/// Handles `getDomainKeys`, which PowerDNS uses to sign responses for a zone
+async fn handle_get_domain_keys(
+    req: &PdnsRequest,
+    client: &Arc<Mutex<ForgeClientT>>,
+) -> Result<PdnsResponse, Report> {
+    let query: GetDomainKeysRequest = req.try_into()?;
+    let span = tracing::info_span!("get_domain_keys", domain = %query.name);
+    let _guard = span.enter();
+
+    tracing::info!(
+        method = "getDomainKeys",
+        domain = %query.name,
+        "Processing getDomainKeys request"
+    );
+
+    let mut client = client.lock().await;
+    let keys = client.get_domain_keys(query).await?.into_inner();
+
+    let res = keys
+        .result
+        .into_iter()
+        .map(serde_json::to_value)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    tracing::info!(
+        method = "getDomainKeys",
+        key_count = res.len(),
+        "getDomainKeys completed"
+    );
+
+    Ok(PdnsResponse::from(res))
+}
+
+/// Handles `getUpdatedMasters`, which PowerDNS polls to find the zones it has
+/// to send a NOTIFY for to secondaries
+async fn handle_get_updated_masters(
+    req: &PdnsRequest,
+    client: &Arc<Mutex<ForgeClientT>>,
+) -> Result<PdnsResponse, Report> {
+    let query: GetUpdatedMastersRequest = req.try_into()?;
+
+    let mut client = client.lock().await;
+    let domains = client.get_updated_masters(query).await?.into_inner();
+
+    let res = domains
+        .result
+        .into_iter()
+        .map(serde_json::to_value)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    tracing::info!(
+        method = "getUpdatedMasters",
+        domain_count = res.len(),
+        "getUpdatedMasters completed"
+    );
+
+    Ok(PdnsResponse::from(res))
+}
+
+async fn handle_set_notified(
+    req: &PdnsRequest,
+    client: &Arc<Mutex<ForgeClientT>>,
+) -> Result<PdnsResponse, Report> {
+    let query: SetNotifiedRequest = req.try_into()?;
+    let span = tracing::info_span!("set_notified", serial = query.serial);
+    let _guard = span.enter();
+
+    let mut client = client.lock().await;
+    client.set_notified(query).await?;
+
+    Ok(PdnsResponse::from(Value::Bool(true)))
+}
+
 async fn send_response(
     writer: &mut tokio::net::unix::WriteHalf<'_>,
     response: PdnsResponse,
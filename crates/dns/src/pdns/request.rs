@@ -66,6 +66,115 @@ impl TryFrom<&PdnsRequest> for rpc::protos::dns::GetAllDomainsRequest {
     }
 }
 
+/// Reads a required, non-empty string parameter from a `PdnsRequest`
+fn required_name(request: &PdnsRequest, key: &str) -> Result<String, eyre::Report> {
+    match request.parameters.get(key).and_then(|v| v.as_str()) {
+        Some(name) if !name.is_empty() => Ok(name.to_string()),
+        _ => {
+            tracing::error!(
+                method = %request.method,
+                parameters = ?request.parameters,
+                "Missing or invalid '{}' parameter",
+                key
+            );
+            Err(eyre::eyre!("Missing or invalid '{}' parameter", key))
+        }
+    }
+}
+
+/// Converts a `list` request, which PowerDNS sends to serve AXFR requests.
+///
+/// PowerDNS also sends a `domain_id`, which is ignored as domains are looked up by name.
+impl TryFrom<&PdnsRequest> for rpc::protos::dns::GetAllRecordsForDomainRequest {
+    type Error = eyre::Report;
+
+    fn try_from(request: &PdnsRequest) -> Result<Self, Self::Error> {
+        let name = required_name(request, "zonename")?;
+
+        tracing::trace!(
+            method = %request.method,
+            zone = %name,
+            "Converted PdnsRequest to GetAllRecordsForDomainRequest"
+        );
+
+        Ok(rpc::protos::dns::GetAllRecordsForDomainRequest { name })
+    }
+}
+
+impl TryFrom<&PdnsRequest> for rpc::protos::dns::GetDomainInfoRequest {
+    type Error = eyre::Report;
+
+    fn try_from(request: &PdnsRequest) -> Result<Self, Self::Error> {
+        let name = required_name(request, "name")?;
+
+        tracing::trace!(
+            method = %request.method,
+            domain = %name,
+            "Converted PdnsRequest to GetDomainInfoRequest"
+        );
+
+        Ok(rpc::protos::dns::GetDomainInfoRequest { name })
+    }
+}
+
+impl TryFrom<&PdnsRequest> for rpc::protos::dns::GetDomainKeysRequest {
+    type Error = eyre::Report;
+
+    fn try_from(request: &PdnsRequest) -> Result<Self, Self::Error> {
+        let name = required_name(request, "name")?;
+
+        tracing::trace!(
+            method = %request.method,
+            domain = %name,
+            "Converted PdnsRequest to GetDomainKeysRequest"
+        );
+
+        Ok(rpc::protos::dns::GetDomainKeysRequest { name })
+    }
+}
+
+impl TryFrom<&PdnsRequest> for rpc::protos::dns::GetUpdatedMastersRequest {
+    type Error = eyre::Report;
+
+    fn try_from(request: &PdnsRequest) -> Result<Self, Self::Error> {
+        tracing::trace!(
+            method = %request.method,
+            "Converted PdnsRequest to GetUpdatedMastersRequest"
+        );
+        Ok(rpc::protos::dns::GetUpdatedMastersRequest {})
+    }
+}
+
+/// Converts a `setNotified` request, which PowerDNS sends after it sent a
+/// NOTIFY for a domain returned by `getUpdatedMasters`.
+impl TryFrom<&PdnsRequest> for rpc::protos::dns::SetNotifiedRequest {
+    type Error = eyre::Report;
+
+    fn try_from(request: &PdnsRequest) -> Result<Self, Self::Error> {
+        let id = required_name(request, "id")?
+            .parse::<carbide_uuid::domain::DomainId>()
+            .map_err(|e| eyre::eyre!("Invalid 'id' parameter: {e}"))?;
+        let serial = request
+            .parameters
+            .get("serial")
+            .and_then(|v| v.as_u64())
+            .and_then(|serial| u32::try_from(serial).ok())
+            .ok_or_else(|| eyre::eyre!("Missing or invalid 'serial' parameter"))?;
+
+        tracing::trace!(
+            method = %request.method,
+            %id,
+            serial,
+            "Converted PdnsRequest to SetNotifiedRequest"
+        );
+
+        Ok(rpc::protos::dns::SetNotifiedRequest {
+            id: Some(id),
+            serial,
+        })
+    }
+}
+
 /// Converts a `PdnsRequest` into a `DnsResourceRecordLookupRequest`.
 ///
 /// This conversion is used to handle DNS lookup requests from PowerDNS, typically
@@ -174,3 +283,76 @@ impl TryFrom<&PdnsRequest> for rpc::protos::dns::DnsResourceRecordLookupRequest
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use rpc::protos::dns::{
+        DnsResourceRecordLookupRequest, GetAllRecordsForDomainRequest, GetDomainInfoRequest,
+        GetDomainKeysRequest, SetNotifiedRequest,
+    };
+
+    use super::*;
+
+    // Requests as sent by the PowerDNS remote backend
+    const LIST_REQUEST: &str = r#"{"method":"list","parameters":{"zonename":"forge.example.com.","domain_id":-1,"include_disabled":false}}"#;
+    const GET_DOMAIN_INFO_REQUEST: &str =
+        r#"{"method":"getDomainInfo","parameters":{"name":"forge.example.com."}}"#;
+    const GET_DOMAIN_KEYS_REQUEST: &str =
+        r#"{"method":"getDomainKeys","parameters":{"name":"forge.example.com."}}"#;
+    const SET_NOTIFIED_REQUEST: &str = r#"{"method":"setNotified","parameters":{"id":"4a3b2c1d-0000-4000-8000-000000000001","serial":2026101701}}"#;
+    const LOOKUP_REQUEST: &str = r#"{"method":"lookup","parameters":{"qtype":"SOA","qname":"forge.example.com.","remote":"192.0.2.10","local":"192.0.2.1","real-remote":"192.0.2.10/32","zone-id":-1}}"#;
+
+    fn parse(raw: &str) -> PdnsRequest {
+        serde_json::from_str(raw).expect("recorded request should parse")
+    }
+
+    #[test]
+    fn test_list_request() {
+        let request = GetAllRecordsForDomainRequest::try_from(&parse(LIST_REQUEST)).unwrap();
+        assert_eq!(request.name, "forge.example.com.");
+    }
+
+    #[test]
+    fn test_get_domain_info_request() {
+        let request = GetDomainInfoRequest::try_from(&parse(GET_DOMAIN_INFO_REQUEST)).unwrap();
+        assert_eq!(request.name, "forge.example.com.");
+    }
+
+    #[test]
+    fn test_get_domain_keys_request() {
+        let request = GetDomainKeysRequest::try_from(&parse(GET_DOMAIN_KEYS_REQUEST)).unwrap();
+        assert_eq!(request.name, "forge.example.com.");
+    }
+
+    #[test]
+    fn test_set_notified_request() {
+        let request = SetNotifiedRequest::try_from(&parse(SET_NOTIFIED_REQUEST)).unwrap();
+        assert_eq!(
+            request.id.unwrap().to_string(),
+            "4a3b2c1d-0000-4000-8000-000000000001"
+        );
+        assert_eq!(request.serial, 2026101701);
+
+        let request = parse(
+            r#"{"method":"setNotified","parameters":{"id":"4a3b2c1d-0000-4000-8000-000000000001"}}"#,
+        );
+        assert!(SetNotifiedRequest::try_from(&request).is_err());
+    }
+
+    #[test]
+    fn test_lookup_request() {
+        let request = DnsResourceRecordLookupRequest::try_from(&parse(LOOKUP_REQUEST)).unwrap();
+        assert_eq!(request.qname, "forge.example.com.");
+        assert_eq!(request.zone_id, "-1");
+        assert_eq!(request.remote.as_deref(), Some("192.0.2.10"));
+    }
+
+    #[test]
+    fn test_missing_zone_name_is_rejected() {
+        let request = parse(r#"{"method":"list","parameters":{"zonename":"","domain_id":-1}}"#);
+        assert!(GetAllRecordsForDomainRequest::try_from(&request).is_err());
+
+        let request = parse(r#"{"method":"getDomainKeys","parameters":{}}"#);
+        assert!(GetDomainKeysRequest::try_from(&request).is_err());
+    }
+}
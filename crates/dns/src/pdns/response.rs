@@ -42,3 +42,34 @@ impl From<Vec<Value>> for PdnsResponse {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use rpc::protos::dns::DomainKey;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_domain_keys_response_format() {
+        let key = DomainKey {
+            id: 1,
+            flags: 257,
+            active: true,
+            published: true,
+            content: "Private-key-format: v1.2\n".to_string(),
+        };
+        let response = PdnsResponse::from(vec![serde_json::to_value(key).unwrap()]);
+
+        assert_eq!(
+            serde_json::to_value(response).unwrap(),
+            json!({"result": [{
+                "id": 1,
+                "flags": 257,
+                "active": true,
+                "published": true,
+                "content": "Private-key-format: v1.2\n",
+            }]})
+        );
+    }
+}
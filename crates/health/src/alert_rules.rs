@@ -0,0 +1,565 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Threshold rules over metric samples.
+//!
+//! Rules are evaluated per endpoint over each metric collection window
+//! (`MetricCollectionStart` .. `MetricCollectionEnd`). Once a condition has
+//! held for the configured duration, an alert is added to the next hardware
+//! health report of the endpoint.
+
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use health_report::{
+    HealthAlertClassification, HealthProbeAlert, HealthProbeId, HealthProbeSuccess, HealthReport,
+};
+
+use crate::HealthError;
+use crate::config::{AlertAggregate, AlertRuleConfig, AlertRulesConfig};
+use crate::sink::MetricSample;
+
+/// Windows of endpoints which did not report samples for this long are
+/// dropped, e.g. after the endpoint was removed or moved to another replica.
+const WINDOW_EXPIRY: Duration = Duration::from_secs(60 * 60);
+
+/// Minimum time between two scans for expired windows.
+const WINDOW_EVICTION_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Operator {
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    Eq,
+    Ne,
+}
+
+impl Operator {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Gt => ">",
+            Self::Ge => ">=",
+            Self::Lt => "<",
+            Self::Le => "<=",
+            Self::Eq => "==",
+            Self::Ne => "!=",
+        }
+    }
+}
+
+/// A comparison of a metric value against a threshold, e.g. `> 45`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Condition {
+    operator: Operator,
+    threshold: f64,
+}
+
+impl Condition {
+    pub fn matches(&self, value: f64) -> bool {
+        match self.operator {
+            Operator::Gt => value > self.threshold,
+            Operator::Ge => value >= self.threshold,
+            Operator::Lt => value < self.threshold,
+            Operator::Le => value <= self.threshold,
+            Operator::Eq => value == self.threshold,
+            Operator::Ne => value != self.threshold,
+        }
+    }
+}
+
+impl FromStr for Condition {
+    type Err = HealthError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        // Two character operators have to be checked first
+        let operator = [
+            (">=", Operator::Ge),
+            ("<=", Operator::Le),
+            ("==", Operator::Eq),
+            ("!=", Operator::Ne),
+            (">", Operator::Gt),
+            ("<", Operator::Lt),
+        ]
+        .into_iter()
+        .find(|(prefix, _)| s.starts_with(prefix));
+
+        let Some((prefix, operator)) = operator else {
+            return Err(HealthError::GenericError(format!(
+                "condition '{s}' must start with one of >, >=, <, <=, ==, !="
+            )));
+        };
+        let threshold = s[prefix.len()..].trim().parse::<f64>().map_err(|e| {
+            HealthError::GenericError(format!("condition '{s}' has an invalid threshold: {e}"))
+        })?;
+
+        Ok(Self {
+            operator,
+            threshold,
+        })
+    }
+}
+
+impl std::fmt::Display for Condition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.operator.as_str(), self.threshold)
+    }
+}
+
+struct AlertRule {
+    probe_id: HealthProbeId,
+    name: Option<String>,
+    metric_type: String,
+    labels: Vec<(String, String)>,
+    condition: Condition,
+    aggregate: AlertAggregate,
+    for_duration: Duration,
+    message: String,
+    classifications: Vec<HealthAlertClassification>,
+}
+
+impl AlertRule {
+    fn from_config(config: &AlertRuleConfig) -> Result<Self, HealthError> {
+        let probe_id = config
+            .id
+            .parse()
+            .map_err(|_| HealthError::GenericError("alert rule id must be set".to_string()))?;
+        if config.metric_type.is_empty() {
+            return Err(HealthError::GenericError(format!(
+                "alert rule {} must set metric_type",
+                config.id
+            )));
+        }
+        let classifications = config
+            .classifications
+            .iter()
+            .map(|c| {
+                c.parse().map_err(|_| {
+                    HealthError::GenericError(format!(
+                        "alert rule {} has an empty classification",
+                        config.id
+                    ))
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            probe_id,
+            name: config.name.clone(),
+            metric_type: config.metric_type.clone(),
+            labels: config
+                .labels
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+            condition: config.condition.parse()?,
+            aggregate: config.aggregate,
+            for_duration: config.for_duration,
+            message: config.message.clone(),
+            classifications,
+        })
+    }
+
+    fn matches(&self, sample: &MetricSample) -> bool {
+        sample.metric_type == self.metric_type
+            && self.name.as_ref().is_none_or(|name| *name == sample.name)
+            && self.labels.iter().all(|(key, value)| {
+                sample
+                    .labels
+                    .iter()
+                    .any(|(k, v)| k.as_ref() == key && v == value)
+            })
+    }
+}
+
+/// Target of a sample, the sensor name if available
+fn sample_target(sample: &MetricSample) -> String {
+    sample
+        .labels
+        .iter()
+        .find(|(k, _)| k.as_ref() == "sensor_name")
+        .map(|(_, v)| v.clone())
+        .unwrap_or_else(|| sample.key.clone())
+}
+
+type RuleTarget = (usize, Option<String>);
+
+struct Breach {
+    since: Instant,
+    value: f64,
+    unit: String,
+}
+
+struct WindowState {
+    /// Last time the window was started, updated or completed
+    last_seen: Instant,
+    /// Rule targets observed in the current window
+    observed: HashSet<RuleTarget>,
+    /// Values of aggregated rules observed in the current window
+    aggregated: HashMap<usize, (Vec<f64>, String)>,
+    /// Rule targets evaluated in the last completed window
+    evaluated: HashSet<RuleTarget>,
+    breaches: HashMap<RuleTarget, Breach>,
+}
+
+impl WindowState {
+    fn new(now: Instant) -> Self {
+        Self {
+            last_seen: now,
+            observed: HashSet::new(),
+            aggregated: HashMap::new(),
+            evaluated: HashSet::new(),
+            breaches: HashMap::new(),
+        }
+    }
+}
+
+/// Evaluates alert rules for the metric samples of many endpoints.
+///
+/// State is kept per window key, which identifies the endpoint and
+/// collector the samples come from. Windows which were not updated for
+/// [`WINDOW_EXPIRY`] are evicted.
+pub struct AlertRulesEngine {
+    rules: Vec<AlertRule>,
+    windows: HashMap<String, WindowState>,
+    last_eviction: Option<Instant>,
+}
+
+impl AlertRulesEngine {
+    pub fn new(config: &AlertRulesConfig) -> Result<Self, HealthError> {
+        let rules = config
+            .rules
+            .iter()
+            .map(AlertRule::from_config)
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            rules,
+            windows: HashMap::new(),
+            last_eviction: None,
+        })
+    }
+
+    fn touch_window<'a>(
+        windows: &'a mut HashMap<String, WindowState>,
+        window_key: &str,
+        now: Instant,
+    ) -> &'a mut WindowState {
+        let window = windows
+            .entry(window_key.to_string())
+            .or_insert_with(|| WindowState::new(now));
+        window.last_seen = now;
+        window
+    }
+
+    pub fn start_window(&mut self, window_key: &str, now: Instant) {
+        self.evict_expired(now);
+        let window = Self::touch_window(&mut self.windows, window_key, now);
+        window.observed.clear();
+        window.aggregated.clear();
+    }
+
+    /// Drops the windows of endpoints which stopped reporting samples.
+    fn evict_expired(&mut self, now: Instant) {
+        if self
+            .last_eviction
+            .is_some_and(|last| now.saturating_duration_since(last) < WINDOW_EVICTION_INTERVAL)
+        {
+            return;
+        }
+        self.last_eviction = Some(now);
+        self.windows
+            .retain(|_, window| now.saturating_duration_since(window.last_seen) < WINDOW_EXPIRY);
+    }
+
+    pub fn observe(&mut self, window_key: &str, sample: &MetricSample, now: Instant) {
+        let window = Self::touch_window(&mut self.windows, window_key, now);
+        for (idx, rule) in self.rules.iter().enumerate() {
+            if !rule.matches(sample) {
+                continue;
+            }
+            if rule.aggregate == AlertAggregate::Each {
+                let target = (idx, Some(sample_target(sample)));
+                window.observed.insert(target.clone());
+                Self::evaluate(window, rule, target, sample.value, &sample.unit, now);
+            } else {
+                window
+                    .aggregated
+                    .entry(idx)
+                    .or_insert_with(|| (Vec::new(), sample.unit.clone()))
+                    .0
+                    .push(sample.value);
+            }
+        }
+    }
+
+    pub fn end_window(&mut self, window_key: &str, now: Instant) {
+        let Some(window) = self.windows.get_mut(window_key) else {
+            return;
+        };
+        window.last_seen = now;
+
+        for (idx, rule) in self.rules.iter().enumerate() {
+            if rule.aggregate == AlertAggregate::Each {
+                continue;
+            }
+            let (values, unit) = window
+                .aggregated
+                .remove(&idx)
+                .unwrap_or_else(|| (Vec::new(), String::new()));
+            let value = match rule.aggregate {
+                AlertAggregate::Count => Some(values.len() as f64),
+                AlertAggregate::Min => values.iter().copied().reduce(f64::min),
+                AlertAggregate::Max => values.iter().copied().reduce(f64::max),
+                AlertAggregate::Avg => {
+                    (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64)
+                }
+                AlertAggregate::Each => None,
+            };
+            let target = (idx, None);
+            if let Some(value) = value {
+                window.observed.insert(target.clone());
+                Self::evaluate(window, rule, target, value, &unit, now);
+            }
+        }
+
+        // Sensors which disappeared can not be in breach anymore
+        let observed = std::mem::take(&mut window.observed);
+        window
+            .breaches
+            .retain(|target, _| observed.contains(target));
+        window.evaluated = observed;
+    }
+
+    fn evaluate(
+        window: &mut WindowState,
+        rule: &AlertRule,
+        target: RuleTarget,
+        value: f64,
+        unit: &str,
+        now: Instant,
+    ) {
+        if rule.condition.matches(value) {
+            let breach = window.breaches.entry(target).or_insert_with(|| Breach {
+                since: now,
+                value,
+                unit: unit.to_string(),
+            });
+            breach.value = value;
+        } else {
+            window.breaches.remove(&target);
+        }
+    }
+
+    /// Adds the alerts of all rules which are firing for the window, and
+    /// successes for all other evaluated rule targets, to the report.
+    pub fn apply_to_report(&self, window_key: &str, report: &mut HealthReport, now: Instant) {
+        let Some(window) = self.windows.get(window_key) else {
+            return;
+        };
+
+        let mut evaluated: Vec<&RuleTarget> = window.evaluated.iter().collect();
+        evaluated.sort();
+        for target in evaluated {
+            let rule = &self.rules[target.0];
+            match window.breaches.get(target) {
+                Some(breach) if now.duration_since(breach.since) >= rule.for_duration => {
+                    report.alerts.push(HealthProbeAlert {
+                        id: rule.probe_id.clone(),
+                        target: target.1.clone(),
+                        in_alert_since: None,
+                        message: format!(
+                            "{}: value {:.2}{} {} for {}",
+                            rule.message,
+                            breach.value,
+                            breach.unit,
+                            rule.condition,
+                            humantime::format_duration(rule.for_duration)
+                        ),
+                        tenant_message: None,
+                        classifications: rule.classifications.clone(),
+                    });
+                }
+                // Pending breaches are neither reported as success nor as alert
+                Some(_) => {}
+                None => report.successes.push(HealthProbeSuccess {
+                    id: rule.probe_id.clone(),
+                    target: target.1.clone(),
+                }),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    fn sample(sensor: &str, metric_type: &str, value: f64) -> MetricSample {
+        MetricSample {
+            key: format!("/redfish/v1/Chassis/1/Sensors/{sensor}"),
+            name: "hw_sensor".to_string(),
+            metric_type: metric_type.to_string(),
+            unit: "celsius".to_string(),
+            value,
+            labels: vec![
+                (Cow::Borrowed("sensor_name"), sensor.to_string()),
+                (Cow::Borrowed("physical_context"), "intake".to_string()),
+            ],
+        }
+    }
+
+    fn inlet_rule() -> AlertRuleConfig {
+        AlertRuleConfig {
+            id: "InletTemperatureHigh".to_string(),
+            metric_type: "temperature".to_string(),
+            labels: BTreeMap::from([("physical_context".to_string(), "intake".to_string())]),
+            condition: "> 45".to_string(),
+            for_duration: Duration::from_secs(300),
+            message: "Inlet temperature too high".to_string(),
+            classifications: vec!["Hardware".to_string(), "PreventAllocations".to_string()],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_condition() {
+        let condition: Condition = ">= 10.5".parse().unwrap();
+        assert!(condition.matches(10.5));
+        assert!(!condition.matches(10.4));
+
+        let condition: Condition = "<1000".parse().unwrap();
+        assert!(condition.matches(999.0));
+        assert_eq!(condition.to_string(), "< 1000");
+
+        assert!("=> 5".parse::<Condition>().is_err());
+        assert!("> hot".parse::<Condition>().is_err());
+    }
+
+    #[test]
+    fn test_alert_fires_after_duration() {
+        let mut engine = AlertRulesEngine::new(&AlertRulesConfig {
+            rules: vec![inlet_rule()],
+        })
+        .unwrap();
+        let start = Instant::now();
+
+        let run_window = |engine: &mut AlertRulesEngine, value: f64, offset: u64| {
+            let now = start + Duration::from_secs(offset);
+            engine.start_window("bmc", now);
+            engine.observe("bmc", &sample("Inlet", "temperature", value), now);
+            engine.observe("bmc", &sample("Inlet", "voltage", 100.0), now);
+            engine.end_window("bmc", now);
+            let mut report = HealthReport::empty("hardware-health".to_string());
+            engine.apply_to_report("bmc", &mut report, now);
+            report
+        };
+
+        let report = run_window(&mut engine, 40.0, 0);
+        assert!(report.alerts.is_empty());
+        assert_eq!(report.successes.len(), 1);
+
+        // Breach started, but did not last long enough yet
+        let report = run_window(&mut engine, 50.0, 60);
+        assert!(report.alerts.is_empty());
+        assert!(report.successes.is_empty());
+
+        let report = run_window(&mut engine, 51.0, 360);
+        assert_eq!(report.alerts.len(), 1);
+        let alert = &report.alerts[0];
+        assert_eq!(alert.id.as_str(), "InletTemperatureHigh");
+        assert_eq!(alert.target.as_deref(), Some("Inlet"));
+        assert!(alert.message.contains("51.00celsius > 45"));
+        assert!(
+            alert
+                .classifications
+                .contains(&HealthAlertClassification::prevent_allocations())
+        );
+
+        let report = run_window(&mut engine, 44.0, 420);
+        assert!(report.alerts.is_empty());
+        assert_eq!(report.successes.len(), 1);
+    }
+
+    #[test]
+    fn test_count_aggregate() {
+        let mut engine = AlertRulesEngine::new(&AlertRulesConfig {
+            rules: vec![AlertRuleConfig {
+                id: "PowerSupplyRedundancyLost".to_string(),
+                metric_type: "powersupply_capacity".to_string(),
+                condition: "< 2".to_string(),
+                aggregate: AlertAggregate::Count,
+                message: "Less than two power supplies report capacity".to_string(),
+                classifications: vec!["Hardware".to_string()],
+                ..Default::default()
+            }],
+        })
+        .unwrap();
+        let now = Instant::now();
+
+        engine.start_window("bmc", now);
+        engine.observe("bmc", &sample("PSU0", "powersupply_capacity", 2000.0), now);
+        engine.end_window("bmc", now);
+
+        let mut report = HealthReport::empty("hardware-health".to_string());
+        engine.apply_to_report("bmc", &mut report, now);
+        assert_eq!(report.alerts.len(), 1);
+        assert_eq!(report.alerts[0].target, None);
+    }
+
+    #[test]
+    fn test_invalid_rules_are_rejected() {
+        let mut rule = inlet_rule();
+        rule.condition = "45".to_string();
+        assert!(AlertRulesEngine::new(&AlertRulesConfig { rules: vec![rule] }).is_err());
+
+        let mut rule = inlet_rule();
+        rule.id = String::new();
+        assert!(AlertRulesEngine::new(&AlertRulesConfig { rules: vec![rule] }).is_err());
+    }
+
+    #[test]
+    fn test_expired_windows_are_evicted() {
+        let mut engine = AlertRulesEngine::new(&AlertRulesConfig {
+            rules: vec![inlet_rule()],
+        })
+        .unwrap();
+        let start = Instant::now();
+
+        for key in ["bmc-1", "bmc-2"] {
+            engine.start_window(key, start);
+            engine.observe(key, &sample("Inlet", "temperature", 50.0), start);
+            engine.end_window(key, start);
+        }
+        assert_eq!(engine.windows.len(), 2);
+
+        // Only bmc-1 keeps reporting
+        let later = start + WINDOW_EXPIRY / 2;
+        engine.start_window("bmc-1", later);
+        engine.end_window("bmc-1", later);
+
+        let expired = start + WINDOW_EXPIRY + WINDOW_EVICTION_INTERVAL;
+        engine.start_window("bmc-1", expired);
+        assert!(engine.windows.contains_key("bmc-1"));
+        assert!(!engine.windows.contains_key("bmc-2"));
+    }
+}
@@ -15,6 +15,7 @@
  * limitations under the License.
  */
 
+use std::collections::BTreeMap;
 use std::fmt::Debug;
 use std::net::SocketAddr;
 use std::path::Path;
@@ -36,6 +37,9 @@ pub struct Config {
 
     pub collectors: CollectorsConfig,
 
+    /// Threshold rules which turn sensor metrics into health alerts
+    pub alert_rules: Configurable<AlertRulesConfig>,
+
     pub metrics: MetricsConfig,
 
     /// Shard ordinal for this instance
@@ -44,6 +48,9 @@ pub struct Config {
     /// Total number of shards in the StatefulSet
     pub shards_count: usize,
 
+    /// Peer based sharding, replaces `shard` and `shards_count` when peers are configured
+    pub sharding: ShardingConfig,
+
     /// Maximum cache size per BMC, uses etags
     pub cache_size: usize,
 
@@ -58,9 +65,11 @@ impl Default for Config {
             sinks: SinksConfig::default(),
             rate_limit: Configurable::Enabled(RateLimitConfig::default()),
             collectors: CollectorsConfig::default(),
+            alert_rules: Configurable::Disabled,
             metrics: MetricsConfig::default(),
             shard: 0,
             shards_count: 1,
+            sharding: ShardingConfig::default(),
             cache_size: 100,
             bmc_proxy_url: None,
         }
@@ -122,6 +131,13 @@ pub struct SinksConfig {
     /// Health override sink: sends health override events to Carbide API.
     #[serde(alias = "carbide_override")]
     pub health_override: Configurable<CarbideApiConnectionConfig>,
+
+    /// OTLP sink: exports metric and log events to an OpenTelemetry collector over gRPC.
+    pub otlp: Configurable<OtlpSinkConfig>,
+
+    /// Event file sink: writes metric, log and firmware events as JSON lines to
+    /// rotated local files, in batches.
+    pub event_file: Configurable<EventFileSinkConfig>,
 }
 
 impl Default for SinksConfig {
@@ -130,6 +146,8 @@ impl Default for SinksConfig {
             tracing: Configurable::Enabled(TracingSinkConfig::default()),
             prometheus: Configurable::Enabled(PrometheusSinkConfig::default()),
             health_override: Configurable::Enabled(CarbideApiConnectionConfig::default()),
+            otlp: Configurable::Disabled,
+            event_file: Configurable::Disabled,
         }
     }
 }
@@ -142,6 +160,87 @@ pub struct TracingSinkConfig {}
 #[serde(default)]
 pub struct PrometheusSinkConfig {}
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OtlpSinkConfig {
+    /// OTLP gRPC collector endpoint.
+    pub endpoint: Url,
+
+    /// Value of the `service.name` resource attribute.
+    pub service_name: String,
+
+    /// Interval between metric exports.
+    #[serde(with = "humantime_serde")]
+    pub export_interval: Duration,
+
+    /// Timeout for a single export request.
+    #[serde(with = "humantime_serde")]
+    pub export_timeout: Duration,
+
+    /// Maximum number of events buffered before new events are dropped.
+    pub queue_capacity: usize,
+
+    /// Export metric events.
+    pub metrics: bool,
+
+    /// Export log events.
+    pub logs: bool,
+}
+
+impl Default for OtlpSinkConfig {
+    fn default() -> Self {
+        Self {
+            endpoint: Url::parse("http://localhost:4317").unwrap(),
+            service_name: "carbide-hw-health".to_string(),
+            export_interval: Duration::from_secs(60),
+            export_timeout: Duration::from_secs(10),
+            queue_capacity: 10000,
+            metrics: true,
+            logs: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EventFileSinkConfig {
+    /// Directory path for batch output files.
+    pub output_dir: String,
+
+    /// Name of the output file, without extension. Rotated files get a numeric suffix.
+    pub file_name: String,
+
+    /// Maximum number of events buffered before new events are dropped.
+    pub queue_capacity: usize,
+
+    /// Number of events written per batch.
+    pub batch_size: usize,
+
+    /// Maximum time an event waits before a partial batch is written.
+    #[serde(with = "humantime_serde")]
+    pub flush_interval: Duration,
+
+    /// Maximum output file size before rotation (in bytes).
+    pub max_file_size: u64,
+
+    /// Maximum number of rotated output files to keep.
+    pub max_backups: usize,
+}
+
+impl Default for EventFileSinkConfig {
+    fn default() -> Self {
+        Self {
+            output_dir: "/var/lib/hw-health/events".to_string(),
+            file_name: "hw-health-events".to_string(),
+            queue_capacity: 10000,
+            batch_size: 500,
+            flush_interval: Duration::from_secs(5),
+            max_file_size: 104857600,
+            max_backups: 5,
+        }
+    }
+}
+
 /// Shared Carbide API connection configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
@@ -170,6 +269,93 @@ impl Default for CarbideApiConnectionConfig {
     }
 }
 
+/// Rules evaluated over metric samples, raising alerts in the hardware health report.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct AlertRulesConfig {
+    pub rules: Vec<AlertRuleConfig>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct AlertRuleConfig {
+    /// Health probe ID of the raised alert, e.g. `InletTemperatureHigh`.
+    pub id: String,
+
+    /// Metric name to match, e.g. `hw_sensor`. Matches any name if unset.
+    pub name: Option<String>,
+
+    /// Metric type to match, e.g. `temperature`.
+    pub metric_type: String,
+
+    /// Labels the metric must carry with exactly these values.
+    pub labels: BTreeMap<String, String>,
+
+    /// Expression the value is checked against, e.g. `> 45` or `<= 1000`.
+    pub condition: String,
+
+    /// How matching samples of one collection are combined before the condition
+    /// is checked. `each` checks every sensor individually.
+    pub aggregate: AlertAggregate,
+
+    /// How long the condition has to hold before the alert is raised.
+    #[serde(rename = "for", with = "humantime_serde")]
+    pub for_duration: Duration,
+
+    /// Alert message, the observed value is appended.
+    pub message: String,
+
+    /// Alert classifications, e.g. `Hardware` or `PreventAllocations`.
+    pub classifications: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertAggregate {
+    #[default]
+    Each,
+    Count,
+    Min,
+    Max,
+    Avg,
+}
+
+/// Membership of the health service replicas which share the BMC endpoints.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ShardingConfig {
+    /// ID of this replica in the peer list. Defaults to the `HOSTNAME` environment variable.
+    pub member_id: Option<String>,
+
+    /// Fixed list of replica IDs.
+    pub peers: Vec<String>,
+
+    /// File with one replica ID per line, re-read on every discovery iteration.
+    /// Takes precedence over `peers`.
+    pub peers_file: Option<String>,
+
+    /// Directory shared by all replicas, used to hand over per-endpoint
+    /// collector state when an endpoint moves to another replica.
+    pub handoff_dir: Option<String>,
+
+    /// How long a replica gaining an endpoint waits for the previous owner to
+    /// hand off its state before starting without it.
+    #[serde(with = "humantime_serde")]
+    pub handoff_timeout: Duration,
+}
+
+impl Default for ShardingConfig {
+    fn default() -> Self {
+        Self {
+            member_id: None,
+            peers: Vec::new(),
+            peers_file: None,
+            handoff_dir: None,
+            handoff_timeout: Duration::from_secs(120),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct RateLimitConfig {
@@ -389,6 +575,29 @@ impl Config {
             );
         }
 
+        if let Configurable::Enabled(otlp) = &self.sinks.otlp
+            && otlp.queue_capacity == 0
+        {
+            return Err("otlp sink queue_capacity must be greater than 0".to_string());
+        }
+
+        if let Configurable::Enabled(event_file) = &self.sinks.event_file {
+            if event_file.queue_capacity == 0 || event_file.batch_size == 0 {
+                return Err(
+                    "event_file sink queue_capacity and batch_size must be greater than 0"
+                        .to_string(),
+                );
+            }
+            if event_file.flush_interval.is_zero() {
+                return Err("event_file sink flush_interval must be greater than 0".to_string());
+            }
+        }
+
+        if let Configurable::Enabled(alert_rules) = &self.alert_rules {
+            crate::alert_rules::AlertRulesEngine::new(alert_rules)
+                .map_err(|e| format!("Invalid alert rules: {e}"))?;
+        }
+
         self.metrics_addr()?;
 
         Ok(())
@@ -500,6 +709,9 @@ mod tests {
         assert!(config.collectors.logs.is_enabled());
         assert!(!config.sinks.tracing.is_enabled());
         assert!(config.sinks.prometheus.is_enabled());
+        assert!(!config.sinks.otlp.is_enabled());
+        assert!(!config.sinks.event_file.is_enabled());
+        assert!(config.alert_rules.is_enabled());
 
         if let Configurable::Enabled(ref sensors) = config.collectors.sensors {
             assert_eq!(sensors.rediscover_interval, Duration::from_secs(300));
@@ -596,6 +808,128 @@ cache_size = 50
         config.validate().expect("config should be valid");
     }
 
+    #[test]
+    fn test_otlp_and_event_file_sinks_config() {
+        let toml_content = r#"
+[sinks.otlp]
+endpoint = "http://otel-collector.monitoring:4317"
+export_interval = "30s"
+logs = false
+
+[sinks.event_file]
+output_dir = "/srv/hw-health/events"
+batch_size = 100
+flush_interval = "2s"
+"#;
+
+        let config: Config = Figment::new()
+            .merge(Toml::string(toml_content))
+            .extract()
+            .expect("failed to parse");
+
+        if let Configurable::Enabled(ref otlp) = config.sinks.otlp {
+            assert_eq!(
+                otlp.endpoint.as_str(),
+                "http://otel-collector.monitoring:4317/"
+            );
+            assert_eq!(otlp.export_interval, Duration::from_secs(30));
+            assert_eq!(otlp.queue_capacity, 10000);
+            assert!(otlp.metrics);
+            assert!(!otlp.logs);
+        } else {
+            panic!("otlp sink is disabled")
+        }
+
+        if let Configurable::Enabled(ref event_file) = config.sinks.event_file {
+            assert_eq!(event_file.output_dir, "/srv/hw-health/events");
+            assert_eq!(event_file.file_name, "hw-health-events");
+            assert_eq!(event_file.batch_size, 100);
+            assert_eq!(event_file.flush_interval, Duration::from_secs(2));
+        } else {
+            panic!("event_file sink is disabled")
+        }
+
+        config.validate().expect("config should be valid");
+
+        let default_config = Config::default();
+        assert!(!default_config.sinks.otlp.is_enabled());
+        assert!(!default_config.sinks.event_file.is_enabled());
+    }
+
+    #[test]
+    fn test_sharding_config() {
+        let toml_content = r#"
+[sharding]
+member_id = "carbide-hw-health-1"
+peers_file = "/etc/carbide-hw-health/peers"
+handoff_dir = "/var/lib/carbide-hw-health/handoff"
+handoff_timeout = "5m"
+"#;
+
+        let config: Config = Figment::new()
+            .merge(Serialized::defaults(Config::default()))
+            .merge(Toml::string(toml_content))
+            .extract()
+            .expect("failed to parse");
+
+        assert_eq!(
+            config.sharding.member_id.as_deref(),
+            Some("carbide-hw-health-1")
+        );
+        assert!(config.sharding.peers.is_empty());
+        assert_eq!(
+            config.sharding.peers_file.as_deref(),
+            Some("/etc/carbide-hw-health/peers")
+        );
+        assert_eq!(
+            config.sharding.handoff_dir.as_deref(),
+            Some("/var/lib/carbide-hw-health/handoff")
+        );
+        assert_eq!(config.sharding.handoff_timeout, Duration::from_secs(300));
+    }
+
+    #[test]
+    fn test_alert_rules_config() {
+        let toml_content = r#"
+[[alert_rules.rules]]
+id = "InletTemperatureHigh"
+metric_type = "temperature"
+labels = { physical_context = "intake" }
+condition = "> 45"
+for = "5m"
+message = "Inlet temperature above 45C"
+classifications = ["Hardware", "PreventAllocations"]
+
+[[alert_rules.rules]]
+id = "PowerSupplyRedundancyLost"
+metric_type = "powersupply_capacity"
+aggregate = "count"
+condition = "< 2"
+message = "Less than two power supplies report capacity"
+classifications = ["Hardware"]
+"#;
+
+        let config: Config = Figment::new()
+            .merge(Toml::string(toml_content))
+            .extract()
+            .expect("failed to parse");
+
+        let Configurable::Enabled(ref alert_rules) = config.alert_rules else {
+            panic!("alert rules are disabled")
+        };
+        assert_eq!(alert_rules.rules.len(), 2);
+        assert_eq!(alert_rules.rules[0].for_duration, Duration::from_secs(300));
+        assert_eq!(alert_rules.rules[0].aggregate, AlertAggregate::Each);
+        assert_eq!(
+            alert_rules.rules[0].labels.get("physical_context"),
+            Some(&"intake".to_string())
+        );
+        assert_eq!(alert_rules.rules[1].aggregate, AlertAggregate::Count);
+        assert_eq!(alert_rules.rules[1].for_duration, Duration::ZERO);
+
+        config.validate().expect("config should be valid");
+    }
+
     #[test]
     fn test_config_validation() {
         let mut config = Config::default();
@@ -616,6 +950,13 @@ cache_size = 50
             max_jitter: Duration::from_secs(0),
         });
         assert!(config.validate().is_err());
+
+        config.rate_limit = Configurable::Enabled(RateLimitConfig::default());
+        config.sinks.event_file = Configurable::Enabled(EventFileSinkConfig {
+            batch_size: 0,
+            ..Default::default()
+        });
+        assert!(config.validate().is_err());
     }
 
     #[test]
@@ -25,8 +25,20 @@ fn stop_collectors_for_keys(
     kind: CollectorKind,
     removed_keys: &HashSet<Cow<'static, str>>,
 ) {
-    let collectors = ctx.collectors.map_mut_for_kind(kind);
     for key in removed_keys {
+        // The logs collector state is handed off once the collector stopped
+        // writing it, so the replica taking over resumes where this one left off
+        let state_file = match kind {
+            CollectorKind::Logs => ctx.collectors.logs_state_files.remove(key),
+            _ => None,
+        };
+        // Only state of an endpoint claimed through the handoff is exported
+        let handoff = ctx
+            .state_handoff
+            .clone()
+            .zip(state_file.and_then(|state_file| Some((state_file.handoff_epoch?, state_file))));
+
+        let collectors = ctx.collectors.map_mut_for_kind(kind);
         if let Some(collector) = collectors.remove(key) {
             tracing::info!(
                 endpoint_key = %key,
@@ -36,6 +48,17 @@ fn stop_collectors_for_keys(
             );
             tokio::spawn(async move {
                 collector.stop().await;
+                if let Some((handoff, (epoch, state_file))) = handoff
+                    && let Err(error) = handoff
+                        .export(&state_file.endpoint_id, epoch, &state_file.path)
+                        .await
+                {
+                    tracing::warn!(
+                        ?error,
+                        endpoint_id = %state_file.endpoint_id,
+                        "Failed to hand off logs collector state"
+                    );
+                }
             });
         }
     }
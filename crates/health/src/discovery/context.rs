@@ -17,6 +17,7 @@
 
 use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use nv_redfish::bmc_http::HttpBmc;
@@ -34,6 +35,7 @@ use crate::config::{
 };
 use crate::limiter::RateLimiter;
 use crate::metrics::{MetricsManager, operation_duration_buckets_seconds};
+use crate::sharding::StateHandoff;
 
 pub(crate) type BmcClient = HttpBmc<ReqwestClient>;
 
@@ -63,11 +65,21 @@ impl CollectorKind {
     }
 }
 
+/// Location of the persistent state of a running logs collector.
+#[derive(Clone, Debug)]
+pub(super) struct LogsStateFile {
+    pub(super) endpoint_id: String,
+    pub(super) path: PathBuf,
+    /// Ownership epoch claimed through the state handoff, if any.
+    pub(super) handoff_epoch: Option<u64>,
+}
+
 pub(super) struct CollectorState {
     sensors: HashMap<Cow<'static, str>, Collector>,
     firmware: HashMap<Cow<'static, str>, Collector>,
     logs: HashMap<Cow<'static, str>, Collector>,
     nmxt: HashMap<Cow<'static, str>, Collector>,
+    pub(super) logs_state_files: HashMap<Cow<'static, str>, LogsStateFile>,
 }
 
 impl CollectorState {
@@ -77,6 +89,7 @@ impl CollectorState {
             firmware: HashMap::new(),
             logs: HashMap::new(),
             nmxt: HashMap::new(),
+            logs_state_files: HashMap::new(),
         }
     }
 
@@ -149,6 +162,7 @@ pub struct DiscoveryLoopContext {
     pub(crate) logs_config: Configurable<LogsCollectorOptions>,
     pub(crate) firmware_config: Configurable<FirmwareCollectorOptions>,
     pub(crate) nmxt_config: Configurable<NmxtCollectorOptions>,
+    pub(crate) state_handoff: Option<Arc<StateHandoff>>,
 }
 
 impl DiscoveryLoopContext {
@@ -156,6 +170,7 @@ impl DiscoveryLoopContext {
         limiter: Arc<dyn RateLimiter>,
         metrics_manager: Arc<MetricsManager>,
         config: Arc<Config>,
+        member_id: &str,
     ) -> Result<Self, HealthError> {
         let registry = metrics_manager.global_registry();
 
@@ -187,6 +202,13 @@ impl DiscoveryLoopContext {
         let logs_config = config.collectors.logs.clone();
         let firmware_config = config.collectors.firmware.clone();
         let nmxt_config = config.collectors.nmxt.clone();
+        let state_handoff = config.sharding.handoff_dir.as_ref().map(|dir| {
+            Arc::new(StateHandoff::new(
+                dir,
+                member_id,
+                config.sharding.handoff_timeout,
+            ))
+        });
 
         Ok(Self {
             collectors: CollectorState::new(),
@@ -200,6 +222,7 @@ impl DiscoveryLoopContext {
             logs_config,
             firmware_config,
             nmxt_config,
+            state_handoff,
         })
     }
 }
@@ -45,6 +45,13 @@ pub async fn run_discovery_iteration(
 ) -> Result<DiscoveryIterationStats, HealthError> {
     let iteration_start = Instant::now();
 
+    if let Err(error) = shard_manager.refresh_membership().await {
+        tracing::warn!(
+            ?error,
+            "Could not refresh shard membership, using previous members"
+        );
+    }
+
     let fetch_start = Instant::now();
     let endpoints = match endpoint_source.fetch_bmc_hosts().await {
         Ok(v) => v,
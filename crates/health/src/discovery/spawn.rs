@@ -18,7 +18,7 @@
 use std::path::PathBuf;
 use std::sync::Arc;
 
-use super::context::{BmcClient, CollectorKind, DiscoveryLoopContext};
+use super::context::{BmcClient, CollectorKind, DiscoveryLoopContext, LogsStateFile};
 use crate::HealthError;
 use crate::collectors::{
     Collector, FirmwareCollector, FirmwareCollectorConfig, LogsCollector, LogsCollectorConfig,
@@ -87,22 +87,56 @@ pub(super) async fn spawn_collectors_for_endpoint(
         let endpoint_id = endpoint.log_identity().into_owned();
         let state_file_path = logs_state_file_path(&logs_cfg.logs_state_file, &endpoint_id);
 
-        let log_writer = match create_log_file_writer(
-            PathBuf::from(&logs_cfg.logs_output_dir),
-            endpoint_id.clone(),
-            logs_cfg.logs_max_file_size,
-            logs_cfg.logs_max_backups,
-        )
-        .await
-        {
-            Ok(writer) => Some(Arc::new(tokio::sync::Mutex::new(writer))),
-            Err(error) => {
-                tracing::error!(
+        let mut handoff_epoch = None;
+        let mut awaiting_handoff = false;
+        if let Some(handoff) = &ctx.state_handoff {
+            match handoff.import(&endpoint_id, &state_file_path).await {
+                Ok(Some(claim)) => {
+                    if claim.imported {
+                        tracing::info!(
+                            endpoint_id = %endpoint_id,
+                            epoch = claim.epoch,
+                            "Imported logs collector state handed off by another replica"
+                        );
+                    }
+                    handoff_epoch = Some(claim.epoch);
+                }
+                // Retried on the next discovery iteration
+                Ok(None) => {
+                    tracing::debug!(
+                        endpoint_id = %endpoint_id,
+                        "Waiting for another replica to hand off logs collector state"
+                    );
+                    awaiting_handoff = true;
+                }
+                Err(error) => tracing::warn!(
                     ?error,
                     endpoint_id = %endpoint_id,
-                    "Failed to create log file writer, skipping logs collector"
-                );
-                None
+                    "Failed to import logs collector state handoff"
+                ),
+            }
+        }
+
+        let log_writer = if awaiting_handoff {
+            None
+        } else {
+            match create_log_file_writer(
+                PathBuf::from(&logs_cfg.logs_output_dir),
+                endpoint_id.clone(),
+                logs_cfg.logs_max_file_size,
+                logs_cfg.logs_max_backups,
+            )
+            .await
+            {
+                Ok(writer) => Some(Arc::new(tokio::sync::Mutex::new(writer))),
+                Err(error) => {
+                    tracing::error!(
+                        ?error,
+                        endpoint_id = %endpoint_id,
+                        "Failed to create log file writer, skipping logs collector"
+                    );
+                    None
+                }
             }
         };
 
@@ -117,7 +151,7 @@ pub(super) async fn spawn_collectors_for_endpoint(
                 ctx.limiter.clone(),
                 logs_cfg.logs_collection_interval,
                 LogsCollectorConfig {
-                    state_file_path,
+                    state_file_path: state_file_path.clone(),
                     service_refresh_interval: logs_cfg.state_refresh_interval,
                     log_writer,
                     data_sink: data_sink.clone(),
@@ -129,6 +163,14 @@ pub(super) async fn spawn_collectors_for_endpoint(
                 Ok(collector) => {
                     ctx.collectors
                         .insert(CollectorKind::Logs, key.clone(), collector);
+                    ctx.collectors.logs_state_files.insert(
+                        key.clone(),
+                        LogsStateFile {
+                            endpoint_id,
+                            path: state_file_path,
+                            handoff_epoch,
+                        },
+                    );
                     tracing::info!(
                         endpoint_key = %key,
                         total_collectors = ctx.collectors.len(CollectorKind::Logs),
@@ -293,7 +335,7 @@ mod tests {
 
         let limiter: Arc<dyn RateLimiter> = Arc::new(NoopLimiter);
         let metrics_manager = Arc::new(MetricsManager::new());
-        let mut ctx = DiscoveryLoopContext::new(limiter, metrics_manager, Arc::new(config))
+        let mut ctx = DiscoveryLoopContext::new(limiter, metrics_manager, Arc::new(config), "0")
             .expect("context should initialize");
 
         let endpoint = Arc::new(BmcEndpoint {
@@ -23,6 +23,7 @@ use std::time::Duration;
 use nv_redfish::bmc_http::reqwest::BmcError;
 use prometheus::{Gauge, GaugeVec, Opts};
 
+pub mod alert_rules;
 pub mod api_client;
 pub mod collectors;
 pub mod config;
@@ -42,7 +43,10 @@ use crate::endpoint::{CompositeEndpointSource, EndpointSource, StaticEndpointSou
 use crate::limiter::{BucketLimiter, NoopLimiter, RateLimiter};
 use crate::metrics::{MetricsManager, run_metrics_server};
 use crate::sharding::ShardManager;
-use crate::sink::{CompositeDataSink, DataSink, HealthOverrideSink, PrometheusSink, TracingSink};
+use crate::sink::{
+    AlertRulesSink, CompositeDataSink, DataSink, EventFileSink, HealthOverrideSink, OtlpSink,
+    PrometheusSink, TracingSink,
+};
 
 #[derive(thiserror::Error, Debug)]
 pub enum HealthError {
@@ -139,7 +143,7 @@ fn build_data_sink(
 
     if let Configurable::Enabled(_) = &config.sinks.prometheus {
         sinks.push(Arc::new(PrometheusSink::new(
-            metrics_manager,
+            metrics_manager.clone(),
             &config.metrics.prefix,
         )?));
     }
@@ -148,12 +152,35 @@ fn build_data_sink(
         sinks.push(Arc::new(HealthOverrideSink::new(sink_cfg)?));
     }
 
+    if let Configurable::Enabled(ref sink_cfg) = config.sinks.otlp {
+        sinks.push(Arc::new(OtlpSink::new(
+            sink_cfg,
+            metrics_manager.clone(),
+            &config.metrics.prefix,
+        )?));
+    }
+
+    if let Configurable::Enabled(ref sink_cfg) = config.sinks.event_file {
+        sinks.push(Arc::new(EventFileSink::new(
+            sink_cfg,
+            metrics_manager.clone(),
+            &config.metrics.prefix,
+        )?));
+    }
+
     let data_sink = match sinks.len() {
         0 => None,
         1 => Some(sinks.pop().expect("len() == 1 guarantees one element")),
         _ => Some(Arc::new(CompositeDataSink::new(sinks)) as Arc<dyn DataSink>),
     };
 
+    let data_sink = match (&config.alert_rules, data_sink) {
+        (Configurable::Enabled(rules_cfg), Some(inner)) => {
+            Some(Arc::new(AlertRulesSink::new(rules_cfg, inner)?) as Arc<dyn DataSink>)
+        }
+        (_, data_sink) => data_sink,
+    };
+
     Ok(data_sink)
 }
 
@@ -198,7 +225,7 @@ pub async fn run_service(config: Config) -> Result<(), HealthError> {
 
     let join_discovery: tokio::task::JoinHandle<Result<(), HealthError>> = tokio::spawn({
         let config = config_arc.clone();
-        let shard_manager = ShardManager::new(config.shard, config.shards_count);
+        let shard_manager = ShardManager::from_config(&config)?;
         let limiter: Arc<dyn RateLimiter> =
             if let Configurable::Enabled(rate_limit) = &config.rate_limit {
                 Arc::new(BucketLimiter::new(
@@ -215,7 +242,12 @@ pub async fn run_service(config: Config) -> Result<(), HealthError> {
         let endpoint_source = endpoint_source.clone();
         let data_sink = data_sink.clone();
 
-        let mut ctx = DiscoveryLoopContext::new(limiter, metrics_manager, config.clone())?;
+        let mut ctx = DiscoveryLoopContext::new(
+            limiter,
+            metrics_manager,
+            config.clone(),
+            shard_manager.member_id(),
+        )?;
 
         async move {
             loop {
@@ -15,19 +15,112 @@
  * limitations under the License.
  */
 
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+use crate::HealthError;
+use crate::config::Config;
 use crate::endpoint::BmcAddr;
 
+/// Where the set of health service replicas is taken from.
+#[derive(Debug, Clone)]
+enum MembershipSource {
+    /// Fixed member list, either from `sharding.peers` or derived from `shards_count`.
+    Static,
+    /// File with one member ID per line, re-read on every refresh.
+    PeersFile(PathBuf),
+}
+
+/// Assigns BMC endpoints to health service replicas using rendezvous
+/// (highest random weight) hashing.
+///
+/// Every replica computes a score for each `(member, endpoint)` pair and the
+/// member with the highest score owns the endpoint. When a member joins or
+/// leaves, only the endpoints it gains or loses move, roughly `1/N` of the
+/// total, so collectors on the remaining replicas keep running.
 pub struct ShardManager {
-    shard: usize,
-    shards_count: usize,
+    member_id: String,
+    members: RwLock<Vec<String>>,
+    source: MembershipSource,
 }
 
 impl ShardManager {
     pub fn new(shard: usize, shards_count: usize) -> Self {
         Self {
-            shard,
-            shards_count,
+            member_id: shard.to_string(),
+            members: RwLock::new((0..shards_count).map(|s| s.to_string()).collect()),
+            source: MembershipSource::Static,
+        }
+    }
+
+    pub fn from_config(config: &Config) -> Result<Self, HealthError> {
+        let sharding = &config.sharding;
+        if sharding.peers.is_empty() && sharding.peers_file.is_none() {
+            return Ok(Self::new(config.shard, config.shards_count));
+        }
+
+        let member_id = sharding
+            .member_id
+            .clone()
+            .or_else(|| std::env::var("HOSTNAME").ok())
+            .ok_or_else(|| {
+                HealthError::GenericError(
+                    "sharding.member_id must be set when peer based sharding is used".to_string(),
+                )
+            })?;
+
+        let (source, members) = match &sharding.peers_file {
+            Some(path) => (MembershipSource::PeersFile(PathBuf::from(path)), Vec::new()),
+            None => (
+                MembershipSource::Static,
+                normalize_members(sharding.peers.iter().cloned()),
+            ),
+        };
+
+        Ok(Self {
+            member_id,
+            members: RwLock::new(members),
+            source,
+        })
+    }
+
+    /// The ID of this replica.
+    pub fn member_id(&self) -> &str {
+        &self.member_id
+    }
+
+    /// The currently known replicas, sorted.
+    pub fn members(&self) -> Vec<String> {
+        self.members.read().expect("members lock poisoned").clone()
+    }
+
+    /// Re-reads the membership source. Returns `true` if the member set changed.
+    pub async fn refresh_membership(&self) -> Result<bool, HealthError> {
+        let MembershipSource::PeersFile(path) = &self.source else {
+            return Ok(false);
+        };
+
+        let members = read_peers_file(path).await?;
+        let mut current = self.members.write().expect("members lock poisoned");
+        if *current == members {
+            return Ok(false);
+        }
+
+        if !members.contains(&self.member_id) {
+            tracing::warn!(
+                member_id = %self.member_id,
+                "This replica is not part of the peer list and will not monitor any endpoints"
+            );
         }
+        tracing::info!(
+            previous = ?*current,
+            current = ?members,
+            "Health service membership changed, rebalancing endpoints"
+        );
+        *current = members;
+        Ok(true)
     }
 
     /// Check if this shard should monitor a BMC endpoint.
@@ -36,17 +129,28 @@ impl ShardManager {
     }
 
     pub fn should_monitor_key(&self, key: &str) -> bool {
-        if self.shards_count == 1 {
-            return true;
+        self.owner(key).as_deref() == Some(self.member_id.as_str())
+    }
+
+    /// The member which owns `key`, or `None` if there are no members.
+    pub fn owner(&self, key: &str) -> Option<String> {
+        let members = self.members.read().expect("members lock poisoned");
+        if members.len() == 1 {
+            return members.first().cloned();
         }
 
-        let hash = self.hash_key(key);
-        let assigned_shard = hash % self.shards_count;
-        assigned_shard == self.shard
+        members
+            .iter()
+            .max_by_key(|member| (self.score(member, key), *member))
+            .cloned()
+    }
+
+    fn score(&self, member: &str, key: &str) -> u64 {
+        mix64(self.hash_key(member) ^ self.hash_key(key).rotate_left(32))
     }
 
     /// FNV-1a 64-bit
-    fn hash_key(&self, key: &str) -> usize {
+    fn hash_key(&self, key: &str) -> u64 {
         const FNV_PRIME: u64 = 1099511628211;
         const FNV_OFFSET_BASIS: u64 = 14695981039346656037;
 
@@ -56,7 +160,289 @@ impl ShardManager {
             hash ^= *byte as u64;
         }
 
-        hash as usize
+        hash
+    }
+}
+
+/// SplitMix64 finalizer, spreads FNV hashes of similar strings across the full range.
+fn mix64(mut value: u64) -> u64 {
+    value = (value ^ (value >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    value = (value ^ (value >> 27)).wrapping_mul(0x94d049bb133111eb);
+    value ^ (value >> 31)
+}
+
+fn normalize_members(members: impl Iterator<Item = String>) -> Vec<String> {
+    let mut members: Vec<String> = members
+        .map(|m| m.trim().to_string())
+        .filter(|m| !m.is_empty() && !m.starts_with('#'))
+        .collect();
+    members.sort();
+    members.dedup();
+    members
+}
+
+async fn read_peers_file(path: &Path) -> Result<Vec<String>, HealthError> {
+    let contents = tokio::fs::read_to_string(path).await.map_err(|e| {
+        HealthError::GenericError(format!("Failed to read peers file {}: {e}", path.display()))
+    })?;
+    Ok(normalize_members(contents.lines().map(str::to_string)))
+}
+
+/// Hands per-endpoint collector state over between replicas through a
+/// directory shared by all of them.
+///
+/// The replica which stops collecting from an endpoint exports the state,
+/// and the replica which starts collecting imports it, so e.g. the logs
+/// collector resumes from the last seen log entry instead of starting over.
+///
+/// Every import claims the endpoint with a new ownership epoch, recorded next
+/// to the handoff. Exports carry the epoch of the replica which wrote them and
+/// are rejected once another replica claimed the endpoint, and imports ignore
+/// handoffs older than the current claim. A replica which lost an endpoint but
+/// exports late can therefore not overwrite the state of the current owner.
+///
+/// The replica gaining an endpoint usually notices before the one losing it
+/// exported. While another replica holds the claim, imports therefore wait for
+/// its handoff, for up to `handoff_timeout` after the first attempt, and only
+/// claim without it once the previous owner failed to export in time.
+pub struct StateHandoff {
+    dir: PathBuf,
+    member_id: String,
+    handoff_timeout: Duration,
+    /// First import attempt of every endpoint which is still waiting for a handoff
+    waiting_since: Mutex<HashMap<String, Instant>>,
+}
+
+/// Ownership of an endpoint taken over by [`StateHandoff::import`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HandoffClaim {
+    /// Ownership epoch which has to be passed to [`StateHandoff::export`].
+    pub epoch: u64,
+    /// Whether state handed off by the previous owner was imported.
+    pub imported: bool,
+}
+
+/// Latest claim of an endpoint, stored as `<endpoint>.epoch`.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+struct OwnershipEpoch {
+    epoch: u64,
+    member_id: String,
+}
+
+/// Exported state, stored as `<endpoint>.json`.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+struct HandoffEnvelope {
+    epoch: u64,
+    member_id: String,
+    state: String,
+}
+
+impl StateHandoff {
+    pub fn new(
+        dir: impl Into<PathBuf>,
+        member_id: impl Into<String>,
+        handoff_timeout: Duration,
+    ) -> Self {
+        Self {
+            dir: dir.into(),
+            member_id: member_id.into(),
+            handoff_timeout,
+            waiting_since: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn file_stem(endpoint_id: &str) -> String {
+        endpoint_id
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect()
+    }
+
+    fn handoff_path(&self, endpoint_id: &str) -> PathBuf {
+        self.dir
+            .join(format!("{}.json", Self::file_stem(endpoint_id)))
+    }
+
+    fn epoch_path(&self, endpoint_id: &str) -> PathBuf {
+        self.dir
+            .join(format!("{}.epoch", Self::file_stem(endpoint_id)))
+    }
+
+    async fn read_epoch(&self, endpoint_id: &str) -> Result<Option<OwnershipEpoch>, HealthError> {
+        let path = self.epoch_path(endpoint_id);
+        match tokio::fs::read(&path).await {
+            Ok(contents) => serde_json::from_slice(&contents).map(Some).map_err(|e| {
+                HealthError::GenericError(format!(
+                    "Failed to parse ownership epoch {}: {e}",
+                    path.display()
+                ))
+            }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(HealthError::GenericError(format!(
+                "Failed to read ownership epoch {}: {e}",
+                path.display()
+            ))),
+        }
+    }
+
+    /// Writes to a temporary file first so readers never see partial contents.
+    async fn write_atomic(&self, target: &Path, contents: &[u8]) -> Result<(), HealthError> {
+        tokio::fs::create_dir_all(&self.dir).await.map_err(|e| {
+            HealthError::GenericError(format!("Failed to create handoff directory: {e}"))
+        })?;
+        let mut tmp = target.as_os_str().to_owned();
+        tmp.push(format!(".{}.tmp", Self::file_stem(&self.member_id)));
+        let tmp = PathBuf::from(tmp);
+        tokio::fs::write(&tmp, contents).await.map_err(|e| {
+            HealthError::GenericError(format!("Failed to write {}: {e}", tmp.display()))
+        })?;
+        tokio::fs::rename(&tmp, target).await.map_err(|e| {
+            HealthError::GenericError(format!("Failed to publish {}: {e}", target.display()))
+        })
+    }
+
+    /// Publishes the local state file of an endpoint for the next owner.
+    ///
+    /// `epoch` is the epoch returned when this replica claimed the endpoint.
+    /// The export is rejected if another replica claimed it since.
+    pub async fn export(
+        &self,
+        endpoint_id: &str,
+        epoch: u64,
+        state_path: &Path,
+    ) -> Result<(), HealthError> {
+        let state = match tokio::fs::read_to_string(state_path).await {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => {
+                return Err(HealthError::GenericError(format!(
+                    "Failed to read state file {}: {e}",
+                    state_path.display()
+                )));
+            }
+        };
+
+        let claim = OwnershipEpoch {
+            epoch,
+            member_id: self.member_id.clone(),
+        };
+        if let Some(current) = self.read_epoch(endpoint_id).await?
+            && current != claim
+        {
+            return Err(HealthError::GenericError(format!(
+                "Stale handoff for {endpoint_id}: exported with epoch {epoch} by {}, \
+                 but the endpoint was claimed with epoch {} by {}",
+                self.member_id, current.epoch, current.member_id
+            )));
+        }
+
+        let envelope = HandoffEnvelope {
+            epoch,
+            member_id: self.member_id.clone(),
+            state,
+        };
+        let contents = serde_json::to_vec(&envelope)
+            .map_err(|e| HealthError::GenericError(format!("Failed to encode handoff: {e}")))?;
+        self.write_atomic(&self.handoff_path(endpoint_id), &contents)
+            .await
+    }
+
+    /// Whether the import of an endpoint claimed by another replica, which
+    /// didn't hand off its state yet, still has to wait for the handoff.
+    fn await_handoff(&self, endpoint_id: &str) -> bool {
+        let mut waiting_since = self.waiting_since.lock().unwrap();
+        let since = *waiting_since
+            .entry(endpoint_id.to_string())
+            .or_insert_with(Instant::now);
+        since.elapsed() < self.handoff_timeout
+    }
+
+    /// Claims an endpoint with a new ownership epoch and takes over the state
+    /// published for it, replacing the local state file.
+    ///
+    /// Returns `None` while the endpoint is claimed by another replica which
+    /// didn't hand off its state yet. The import has to be retried later.
+    pub async fn import(
+        &self,
+        endpoint_id: &str,
+        state_path: &Path,
+    ) -> Result<Option<HandoffClaim>, HealthError> {
+        let current = self.read_epoch(endpoint_id).await?;
+        let current_epoch = current
+            .as_ref()
+            .map(|current| current.epoch)
+            .unwrap_or_default();
+
+        let source = self.handoff_path(endpoint_id);
+        let envelope = match tokio::fs::read(&source).await {
+            Ok(contents) => Some(
+                serde_json::from_slice::<HandoffEnvelope>(&contents).map_err(|e| {
+                    HealthError::GenericError(format!(
+                        "Failed to parse handoff {}: {e}",
+                        source.display()
+                    ))
+                })?,
+            ),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => {
+                return Err(HealthError::GenericError(format!(
+                    "Failed to read handoff {}: {e}",
+                    source.display()
+                )));
+            }
+        };
+
+        let claimed_by_other = current.is_some_and(|current| current.member_id != self.member_id);
+        if envelope.is_none() && claimed_by_other {
+            if self.await_handoff(endpoint_id) {
+                return Ok(None);
+            }
+            tracing::warn!(
+                endpoint_id,
+                current_epoch,
+                "Previous owner did not hand off collector state in time, claiming without it"
+            );
+        }
+        self.waiting_since.lock().unwrap().remove(endpoint_id);
+
+        let mut imported = false;
+        let mut next_epoch = current_epoch + 1;
+        if let Some(envelope) = envelope {
+            if envelope.epoch < current_epoch {
+                tracing::warn!(
+                    endpoint_id,
+                    handoff_epoch = envelope.epoch,
+                    handoff_member_id = envelope.member_id,
+                    current_epoch,
+                    "Ignoring stale collector state handoff"
+                );
+            } else {
+                tokio::fs::write(state_path, envelope.state)
+                    .await
+                    .map_err(|e| {
+                        HealthError::GenericError(format!("Failed to write state: {e}"))
+                    })?;
+                next_epoch = envelope.epoch + 1;
+                imported = true;
+            }
+            tokio::fs::remove_file(&source)
+                .await
+                .map_err(|e| HealthError::GenericError(format!("Failed to remove handoff: {e}")))?;
+        }
+
+        let claim = OwnershipEpoch {
+            epoch: next_epoch,
+            member_id: self.member_id.clone(),
+        };
+        let contents = serde_json::to_vec(&claim)
+            .map_err(|e| HealthError::GenericError(format!("Failed to encode epoch: {e}")))?;
+        self.write_atomic(&self.epoch_path(endpoint_id), &contents)
+            .await?;
+
+        Ok(Some(HandoffClaim {
+            epoch: next_epoch,
+            imported,
+        }))
     }
 }
 
@@ -68,6 +454,8 @@ mod tests {
 
     use super::*;
 
+    const HANDOFF_TIMEOUT: Duration = Duration::from_secs(60);
+
     #[test]
     fn test_single_shard() {
         let manager = ShardManager::new(0, 1);
@@ -159,4 +547,224 @@ mod tests {
             manager.should_monitor_key(key)
         );
     }
+
+    #[test]
+    fn test_rebalancing_moves_only_keys_of_new_member() {
+        let keys: Vec<String> = (0..1000).map(|i| format!("aa:bb:cc:dd:{i:04x}")).collect();
+
+        let before = ShardManager::new(0, 3);
+        let after = ShardManager::new(0, 4);
+
+        let mut moved = 0;
+        for key in &keys {
+            let old_owner = before.owner(key).unwrap();
+            let new_owner = after.owner(key).unwrap();
+            if old_owner != new_owner {
+                assert_eq!(new_owner, "3", "keys should only move to the new member");
+                moved += 1;
+            }
+        }
+
+        // Ideally 1/4 of the keys move to the new member
+        assert!(moved > 150, "too few keys moved: {moved}");
+        assert!(moved < 350, "too many keys moved: {moved}");
+    }
+
+    #[tokio::test]
+    async fn test_refresh_membership_from_peers_file() {
+        let dir = std::env::temp_dir().join(format!("health_sharding_{}", std::process::id()));
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        let peers_file = dir.join("peers");
+        tokio::fs::write(&peers_file, "hw-health-1\nhw-health-0\n\n# comment\n")
+            .await
+            .unwrap();
+
+        let mut config = Config::default();
+        config.sharding.member_id = Some("hw-health-0".to_string());
+        config.sharding.peers_file = Some(peers_file.to_string_lossy().into_owned());
+        let manager = ShardManager::from_config(&config).unwrap();
+
+        // Nothing is owned until the peers file was read
+        assert!(!manager.should_monitor_key("AA:BB:CC:DD:EE:FF"));
+
+        assert!(manager.refresh_membership().await.unwrap());
+        assert_eq!(manager.members(), vec!["hw-health-0", "hw-health-1"]);
+        assert!(!manager.refresh_membership().await.unwrap());
+
+        tokio::fs::write(&peers_file, "hw-health-0\n")
+            .await
+            .unwrap();
+        assert!(manager.refresh_membership().await.unwrap());
+        assert!(manager.should_monitor_key("AA:BB:CC:DD:EE:FF"));
+
+        tokio::fs::write(&peers_file, "hw-health-1\n")
+            .await
+            .unwrap();
+        assert!(manager.refresh_membership().await.unwrap());
+        assert!(!manager.should_monitor_key("AA:BB:CC:DD:EE:FF"));
+
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+    }
+
+    #[tokio::test]
+    async fn test_state_handoff_roundtrip() {
+        let dir = std::env::temp_dir().join(format!("health_handoff_{}", std::process::id()));
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        let old_state = dir.join("old_state.json");
+        let new_state = dir.join("new_state.json");
+        tokio::fs::write(&old_state, r#"{"last_seen_ids":{}}"#)
+            .await
+            .unwrap();
+
+        let old_owner = StateHandoff::new(dir.join("handoff"), "hw-health-0", HANDOFF_TIMEOUT);
+        let new_owner = StateHandoff::new(dir.join("handoff"), "hw-health-1", HANDOFF_TIMEOUT);
+        let claim = old_owner
+            .import("endpoint-1", &old_state)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            claim,
+            HandoffClaim {
+                epoch: 1,
+                imported: false
+            }
+        );
+
+        old_owner
+            .export("endpoint-1", claim.epoch, &old_state)
+            .await
+            .unwrap();
+        let claim = new_owner
+            .import("endpoint-1", &new_state)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            claim,
+            HandoffClaim {
+                epoch: 2,
+                imported: true
+            }
+        );
+        assert_eq!(
+            tokio::fs::read_to_string(&new_state).await.unwrap(),
+            r#"{"last_seen_ids":{}}"#
+        );
+
+        // A handoff is consumed by the importer
+        let claim = new_owner
+            .import("endpoint-1", &new_state)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(!claim.imported);
+
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+    }
+
+    #[tokio::test]
+    async fn test_state_handoff_rejects_stale_exports() {
+        let dir = std::env::temp_dir().join(format!("health_stale_handoff_{}", std::process::id()));
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        let old_state = dir.join("old_state.json");
+        let new_state = dir.join("new_state.json");
+        tokio::fs::write(&old_state, r#"{"last_seen_ids":{"a":"1"}}"#)
+            .await
+            .unwrap();
+
+        let old_owner = StateHandoff::new(dir.join("handoff"), "hw-health-0", HANDOFF_TIMEOUT);
+        // Doesn't wait for the old owner, as if it failed to export in time
+        let new_owner = StateHandoff::new(dir.join("handoff"), "hw-health-1", Duration::ZERO);
+        let old_claim = old_owner
+            .import("endpoint-1", &old_state)
+            .await
+            .unwrap()
+            .unwrap();
+
+        // The new owner claims the endpoint before the old owner exported
+        let new_claim = new_owner
+            .import("endpoint-1", &new_state)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(!new_claim.imported);
+        assert!(new_claim.epoch > old_claim.epoch);
+
+        // The late export of the previous owner is rejected
+        assert!(
+            old_owner
+                .export("endpoint-1", old_claim.epoch, &old_state)
+                .await
+                .is_err()
+        );
+
+        // The current owner can still hand off its own state
+        tokio::fs::write(&new_state, r#"{"last_seen_ids":{"a":"2"}}"#)
+            .await
+            .unwrap();
+        new_owner
+            .export("endpoint-1", new_claim.epoch, &new_state)
+            .await
+            .unwrap();
+        let claim = old_owner
+            .import("endpoint-1", &old_state)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(claim.imported);
+        assert_eq!(
+            tokio::fs::read_to_string(&old_state).await.unwrap(),
+            r#"{"last_seen_ids":{"a":"2"}}"#
+        );
+
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+    }
+
+    #[tokio::test]
+    async fn test_state_handoff_waits_for_previous_owner() {
+        let dir = std::env::temp_dir().join(format!("health_wait_handoff_{}", std::process::id()));
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        let old_state = dir.join("old_state.json");
+        let new_state = dir.join("new_state.json");
+        tokio::fs::write(&old_state, r#"{"last_seen_ids":{"a":"1"}}"#)
+            .await
+            .unwrap();
+
+        let old_owner = StateHandoff::new(dir.join("handoff"), "hw-health-0", HANDOFF_TIMEOUT);
+        let new_owner = StateHandoff::new(dir.join("handoff"), "hw-health-1", HANDOFF_TIMEOUT);
+        let old_claim = old_owner
+            .import("endpoint-1", &old_state)
+            .await
+            .unwrap()
+            .unwrap();
+
+        // The new owner notices first, e.g. after a scale up, and waits
+        assert_eq!(
+            new_owner.import("endpoint-1", &new_state).await.unwrap(),
+            None
+        );
+
+        // The old owner's export is still accepted and taken over
+        old_owner
+            .export("endpoint-1", old_claim.epoch, &old_state)
+            .await
+            .unwrap();
+        let new_claim = new_owner
+            .import("endpoint-1", &new_state)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(new_claim.imported);
+        assert!(new_claim.epoch > old_claim.epoch);
+        assert_eq!(
+            tokio::fs::read_to_string(&new_state).await.unwrap(),
+            r#"{"last_seen_ids":{"a":"1"}}"#
+        );
+
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+    }
 }
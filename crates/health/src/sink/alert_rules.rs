@@ -0,0 +1,87 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use super::{CollectorEvent, DataSink, EventContext, HealthOverride};
+use crate::HealthError;
+use crate::alert_rules::AlertRulesEngine;
+use crate::config::AlertRulesConfig;
+
+/// Sink which evaluates alert rules over metric events and adds the
+/// resulting alerts to health override reports before passing all events
+/// on to the wrapped sink.
+pub struct AlertRulesSink {
+    inner: Arc<dyn DataSink>,
+    engine: Mutex<AlertRulesEngine>,
+}
+
+impl AlertRulesSink {
+    pub fn new(config: &AlertRulesConfig, inner: Arc<dyn DataSink>) -> Result<Self, HealthError> {
+        Ok(Self {
+            inner,
+            engine: Mutex::new(AlertRulesEngine::new(config)?),
+        })
+    }
+
+    fn window_key(context: &EventContext) -> String {
+        format!("{}::{}", context.endpoint_key(), context.collector_type)
+    }
+}
+
+impl DataSink for AlertRulesSink {
+    fn handle_event(&self, context: &EventContext, event: &CollectorEvent) {
+        let now = Instant::now();
+        match event {
+            CollectorEvent::MetricCollectionStart => {
+                self.engine
+                    .lock()
+                    .expect("alert rules lock poisoned")
+                    .start_window(&Self::window_key(context), now);
+            }
+            CollectorEvent::Metric(sample) => {
+                self.engine
+                    .lock()
+                    .expect("alert rules lock poisoned")
+                    .observe(&Self::window_key(context), sample, now);
+            }
+            CollectorEvent::MetricCollectionEnd => {
+                self.engine
+                    .lock()
+                    .expect("alert rules lock poisoned")
+                    .end_window(&Self::window_key(context), now);
+            }
+            CollectorEvent::HealthOverride(HealthOverride { machine_id, report }) => {
+                let mut report = report.as_ref().clone();
+                self.engine
+                    .lock()
+                    .expect("alert rules lock poisoned")
+                    .apply_to_report(&Self::window_key(context), &mut report, now);
+                let event = CollectorEvent::HealthOverride(HealthOverride {
+                    machine_id: *machine_id,
+                    report: Arc::new(report),
+                });
+                self.inner.handle_event(context, &event);
+                return;
+            }
+            CollectorEvent::Log(_) | CollectorEvent::Firmware(_) => {}
+        }
+
+        self.inner.handle_event(context, event);
+    }
+}
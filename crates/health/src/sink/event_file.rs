@@ -0,0 +1,321 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+use tokio::io::AsyncWriteExt;
+use tokio::time::MissedTickBehavior;
+
+use super::queue::{QueueReceiver, QueueSender, SinkQueueMetrics, bounded_queue};
+use super::{CollectorEvent, DataSink, EventContext};
+use crate::HealthError;
+use crate::config::EventFileSinkConfig;
+use crate::metrics::{MetricLabel, MetricsManager};
+
+/// A single event as written by the [`EventFileSink`], one JSON object per line.
+///
+/// `key` is the endpoint the event originates from, so consumers that
+/// partition the stream (e.g. a log shipper tailing the file) can keep
+/// per-endpoint ordering.
+#[derive(Debug, Serialize)]
+struct EventRecord {
+    #[serde(rename = "timeUnixNano")]
+    time_unix_nano: String,
+    key: String,
+    collector_type: &'static str,
+    endpoint_ip: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    machine_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    switch_serial: Option<String>,
+    #[serde(flatten)]
+    payload: EventPayload,
+    attributes: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum EventPayload {
+    Metric {
+        name: String,
+        metric_type: String,
+        unit: String,
+        value: f64,
+    },
+    Log {
+        severity: String,
+        body: String,
+    },
+    Firmware {
+        component: String,
+        version: String,
+    },
+}
+
+impl EventRecord {
+    fn from_event(context: &EventContext, event: &CollectorEvent) -> Option<Self> {
+        let (payload, labels) = match event {
+            CollectorEvent::Metric(sample) => (
+                EventPayload::Metric {
+                    name: sample.name.clone(),
+                    metric_type: sample.metric_type.clone(),
+                    unit: sample.unit.clone(),
+                    value: sample.value,
+                },
+                &sample.labels,
+            ),
+            CollectorEvent::Log(record) => (
+                EventPayload::Log {
+                    severity: record.severity.clone(),
+                    body: record.body.clone(),
+                },
+                &record.attributes,
+            ),
+            CollectorEvent::Firmware(info) => (
+                EventPayload::Firmware {
+                    component: info.component.clone(),
+                    version: info.version.clone(),
+                },
+                &info.attributes,
+            ),
+            CollectorEvent::MetricCollectionStart
+            | CollectorEvent::MetricCollectionEnd
+            | CollectorEvent::HealthOverride(_) => return None,
+        };
+
+        Some(Self {
+            time_unix_nano: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos()
+                .to_string(),
+            key: context.endpoint_key().to_string(),
+            collector_type: context.collector_type,
+            endpoint_ip: context.addr.ip.to_string(),
+            machine_id: context.machine_id().map(|id| id.to_string()),
+            switch_serial: context.switch_serial().map(str::to_string),
+            payload,
+            attributes: Self::attributes(labels),
+        })
+    }
+
+    fn attributes(labels: &[MetricLabel]) -> BTreeMap<String, String> {
+        labels
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.clone()))
+            .collect()
+    }
+}
+
+/// Appends batches of records to `{output_dir}/{file_name}.jsonl`, rotating the
+/// file once it grows beyond `max_file_size`.
+struct EventFileWriter {
+    output_dir: PathBuf,
+    file_name: String,
+    max_file_size: u64,
+    max_backups: usize,
+    current_file: Option<tokio::io::BufWriter<tokio::fs::File>>,
+    current_size: u64,
+}
+
+impl EventFileWriter {
+    fn new(config: &EventFileSinkConfig) -> Self {
+        Self {
+            output_dir: PathBuf::from(&config.output_dir),
+            file_name: config.file_name.clone(),
+            max_file_size: config.max_file_size,
+            max_backups: config.max_backups,
+            current_file: None,
+            current_size: 0,
+        }
+    }
+
+    fn current_path(&self) -> PathBuf {
+        self.output_dir.join(format!("{}.jsonl", self.file_name))
+    }
+
+    fn rotated_path(&self, index: usize) -> PathBuf {
+        self.output_dir
+            .join(format!("{}.{index}.jsonl", self.file_name))
+    }
+
+    async fn open_current_file(&mut self) -> Result<(), HealthError> {
+        let path = self.current_path();
+        let file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .await
+            .map_err(|e| {
+                HealthError::GenericError(format!("Failed to open {}: {e}", path.display()))
+            })?;
+        self.current_size = file.metadata().await.map(|m| m.len()).unwrap_or(0);
+        self.current_file = Some(tokio::io::BufWriter::new(file));
+        Ok(())
+    }
+
+    async fn rotate(&mut self) -> Result<(), HealthError> {
+        if let Some(mut file) = self.current_file.take()
+            && let Err(error) = file.flush().await
+        {
+            tracing::error!(?error, "Failed to flush batch file before rotation");
+        }
+
+        for i in (1..self.max_backups).rev() {
+            let from_path = self.rotated_path(i);
+            if tokio::fs::try_exists(&from_path).await.unwrap_or(false)
+                && let Err(error) = tokio::fs::rename(&from_path, self.rotated_path(i + 1)).await
+            {
+                tracing::warn!(?error, path = %from_path.display(), "Failed to rotate batch file");
+            }
+        }
+
+        if self.max_backups > 0 {
+            if let Err(error) = tokio::fs::rename(self.current_path(), self.rotated_path(1)).await {
+                tracing::warn!(?error, "Failed to rotate current batch file");
+            }
+        } else if let Err(error) = tokio::fs::remove_file(self.current_path()).await {
+            tracing::warn!(?error, "Failed to remove current batch file");
+        }
+
+        self.open_current_file().await
+    }
+
+    async fn write_batch(&mut self, records: &[EventRecord]) -> Result<(), HealthError> {
+        let mut buffer = Vec::new();
+        for record in records {
+            serde_json::to_writer(&mut buffer, record)?;
+            buffer.push(b'\n');
+        }
+
+        if self.current_file.is_none() {
+            self.open_current_file().await?;
+        } else if self.current_size > 0
+            && self.current_size + buffer.len() as u64 > self.max_file_size
+        {
+            self.rotate().await?;
+        }
+
+        let Some(file) = self.current_file.as_mut() else {
+            return Err(HealthError::GenericError(
+                "batch file is not open".to_string(),
+            ));
+        };
+        file.write_all(&buffer)
+            .await
+            .map_err(|e| HealthError::GenericError(format!("Failed to write batch: {e}")))?;
+        file.flush()
+            .await
+            .map_err(|e| HealthError::GenericError(format!("Failed to flush batch: {e}")))?;
+        self.current_size += buffer.len() as u64;
+
+        Ok(())
+    }
+
+    async fn flush_batch(&mut self, batch: &mut Vec<EventRecord>) {
+        if batch.is_empty() {
+            return;
+        }
+        if let Err(error) = self.write_batch(batch).await {
+            tracing::warn!(?error, records = batch.len(), "Failed to write event batch");
+        }
+        batch.clear();
+    }
+}
+
+/// Sink which buffers metric, log and firmware events in a bounded queue and
+/// writes them as JSON lines in batches.
+///
+/// Events are dropped instead of blocking collectors when the queue is full.
+pub struct EventFileSink {
+    sender: QueueSender<EventRecord>,
+}
+
+impl EventFileSink {
+    pub fn new(
+        config: &EventFileSinkConfig,
+        metrics_manager: Arc<MetricsManager>,
+        metrics_prefix: &str,
+    ) -> Result<Self, HealthError> {
+        let handle = tokio::runtime::Handle::try_current().map_err(|error| {
+            HealthError::GenericError(format!(
+                "event file sink requires active Tokio runtime: {error}"
+            ))
+        })?;
+
+        std::fs::create_dir_all(&config.output_dir).map_err(|e| {
+            HealthError::GenericError(format!("Failed to create batch output directory: {e}"))
+        })?;
+
+        let metrics = SinkQueueMetrics::new(
+            metrics_manager.global_registry(),
+            metrics_prefix,
+            "event_file",
+        )?;
+        let (sender, receiver) = bounded_queue(config.queue_capacity, metrics);
+
+        handle.spawn(run_worker(
+            receiver,
+            EventFileWriter::new(config),
+            config.batch_size,
+            config.flush_interval,
+        ));
+
+        Ok(Self { sender })
+    }
+}
+
+async fn run_worker(
+    mut receiver: QueueReceiver<EventRecord>,
+    mut writer: EventFileWriter,
+    batch_size: usize,
+    flush_interval: Duration,
+) {
+    let mut batch = Vec::with_capacity(batch_size);
+    let mut flush_timer = tokio::time::interval(flush_interval);
+    flush_timer.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    loop {
+        tokio::select! {
+            record = receiver.recv() => match record {
+                Some(record) => {
+                    batch.push(record);
+                    if batch.len() >= batch_size {
+                        writer.flush_batch(&mut batch).await;
+                    }
+                }
+                None => {
+                    writer.flush_batch(&mut batch).await;
+                    break;
+                }
+            },
+            _ = flush_timer.tick() => writer.flush_batch(&mut batch).await,
+        }
+    }
+}
+
+impl DataSink for EventFileSink {
+    fn handle_event(&self, context: &EventContext, event: &CollectorEvent) {
+        if let Some(record) = EventRecord::from_event(context, event) {
+            self.sender.try_enqueue(context.collector_type, record);
+        }
+    }
+}
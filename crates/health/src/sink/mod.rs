@@ -15,17 +15,24 @@
  * limitations under the License.
  */
 
+mod alert_rules;
 mod composite;
+mod event_file;
 mod events;
 mod health_override;
+mod otlp;
 mod prometheus;
+mod queue;
 mod tracing;
 
+pub use alert_rules::AlertRulesSink;
 pub use composite::CompositeDataSink;
+pub use event_file::EventFileSink;
 pub use events::{
     CollectorEvent, EventContext, FirmwareInfo, HealthOverride, LogRecord, MetricSample,
 };
 pub use health_override::HealthOverrideSink;
+pub use otlp::OtlpSink;
 pub use prometheus::PrometheusSink;
 pub use tracing::TracingSink;
 
@@ -43,9 +50,10 @@ mod tests {
     use mac_address::MacAddress;
 
     use super::{
-        CollectorEvent, CompositeDataSink, DataSink, EventContext, LogRecord, MetricSample,
-        PrometheusSink,
+        AlertRulesSink, CollectorEvent, CompositeDataSink, DataSink, EventContext, EventFileSink,
+        LogRecord, MetricSample, PrometheusSink,
     };
+    use crate::config::{AlertRuleConfig, AlertRulesConfig, EventFileSinkConfig};
     use crate::endpoint::{BmcAddr, EndpointMetadata, MachineData};
     use crate::metrics::MetricsManager;
 
@@ -59,6 +67,25 @@ mod tests {
         }
     }
 
+    struct ReportCapturingSink {
+        alerts: std::sync::Mutex<Vec<String>>,
+    }
+
+    impl DataSink for ReportCapturingSink {
+        fn handle_event(&self, _context: &EventContext, event: &CollectorEvent) {
+            if let CollectorEvent::HealthOverride(override_event) = event {
+                let mut alerts = self.alerts.lock().unwrap();
+                alerts.extend(
+                    override_event
+                        .report
+                        .alerts
+                        .iter()
+                        .map(|alert| alert.id.to_string()),
+                );
+            }
+        }
+    }
+
     struct NoopSink;
 
     impl DataSink for NoopSink {
@@ -214,4 +241,187 @@ mod tests {
         assert!(!second_export.contains("sensor=\"temp1\""));
         assert!(second_export.contains("sensor=\"temp2\""));
     }
+
+    fn event_file_sink_config(test_name: &str) -> EventFileSinkConfig {
+        let output_dir = std::env::temp_dir().join(format!(
+            "health_event_file_sink_{test_name}_{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&output_dir);
+        EventFileSinkConfig {
+            output_dir: output_dir.to_string_lossy().into_owned(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_event_file_sink_writes_json_lines() {
+        let metrics_manager = Arc::new(MetricsManager::new());
+        let config = event_file_sink_config("writes");
+        let sink = EventFileSink::new(&config, metrics_manager, "test_sink")
+            .expect("sink should initialize");
+
+        let context = EventContext {
+            endpoint_key: "42:9e:b1:bd:9d:dd".to_string(),
+            addr: BmcAddr {
+                ip: "10.0.0.1".parse().expect("valid ip"),
+                port: Some(443),
+                mac: MacAddress::from_str("42:9e:b1:bd:9d:dd").unwrap(),
+            },
+            collector_type: "sensor_collector",
+            metadata: None,
+        };
+
+        sink.handle_event(&context, &CollectorEvent::MetricCollectionStart);
+        sink.handle_event(
+            &context,
+            &CollectorEvent::Metric(MetricSample {
+                key: "s1".to_string(),
+                name: "hw_sensor".to_string(),
+                metric_type: "temperature".to_string(),
+                unit: "celsius".to_string(),
+                value: 42.0,
+                labels: vec![(Cow::Borrowed("sensor"), "temp1".to_string())],
+            }),
+        );
+        sink.handle_event(
+            &context,
+            &CollectorEvent::Log(LogRecord {
+                body: "PSU failure".to_string(),
+                severity: "WARN".to_string(),
+                attributes: Vec::new(),
+            }),
+        );
+        sink.handle_event(&context, &CollectorEvent::MetricCollectionEnd);
+
+        // Dropping the sink closes the queue, which flushes the pending batch
+        drop(sink);
+
+        let path = std::path::Path::new(&config.output_dir).join("hw-health-events.jsonl");
+        let mut lines = Vec::new();
+        for _ in 0..100 {
+            if let Ok(contents) = std::fs::read_to_string(&path)
+                && contents.lines().count() == 2
+            {
+                lines = contents.lines().map(str::to_string).collect();
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+        assert_eq!(lines.len(), 2, "expected one metric and one log record");
+
+        let metric: serde_json::Value = serde_json::from_str(&lines[0]).unwrap();
+        assert_eq!(metric["kind"], "metric");
+        assert_eq!(metric["key"], "42:9e:b1:bd:9d:dd");
+        assert_eq!(metric["value"], 42.0);
+        assert_eq!(metric["attributes"]["sensor"], "temp1");
+
+        let log: serde_json::Value = serde_json::from_str(&lines[1]).unwrap();
+        assert_eq!(log["kind"], "log");
+        assert_eq!(log["severity"], "WARN");
+
+        let _ = std::fs::remove_dir_all(&config.output_dir);
+    }
+
+    #[tokio::test]
+    async fn test_event_file_sink_drops_events_when_queue_is_full() {
+        let metrics_manager = Arc::new(MetricsManager::new());
+        let config = EventFileSinkConfig {
+            queue_capacity: 2,
+            ..event_file_sink_config("backpressure")
+        };
+        let sink = EventFileSink::new(&config, metrics_manager.clone(), "test_sink")
+            .expect("sink should initialize");
+
+        let context = EventContext {
+            endpoint_key: "42:9e:b1:bd:9d:dd".to_string(),
+            addr: BmcAddr {
+                ip: "10.0.0.1".parse().expect("valid ip"),
+                port: Some(443),
+                mac: MacAddress::from_str("42:9e:b1:bd:9d:dd").unwrap(),
+            },
+            collector_type: "logs_collector",
+            metadata: None,
+        };
+
+        // The worker does not get to run on the current-thread runtime
+        // until this test yields, so everything past the capacity is dropped.
+        for i in 0..5 {
+            sink.handle_event(
+                &context,
+                &CollectorEvent::Log(LogRecord {
+                    body: format!("entry {i}"),
+                    severity: "INFO".to_string(),
+                    attributes: Vec::new(),
+                }),
+            );
+        }
+
+        let export = metrics_manager
+            .export_all()
+            .expect("metrics export should work");
+        assert!(export.contains(
+            "test_sink_sink_events_dropped_total{collector_type=\"logs_collector\",reason=\"full\",sink=\"event_file\"} 3"
+        ));
+        assert!(export.contains("test_sink_sink_queue_depth{sink=\"event_file\"} 2"));
+
+        let _ = std::fs::remove_dir_all(&config.output_dir);
+    }
+
+    #[tokio::test]
+    async fn test_alert_rules_sink_adds_alerts_to_health_override() {
+        let inner = Arc::new(ReportCapturingSink {
+            alerts: std::sync::Mutex::new(Vec::new()),
+        });
+        let sink = AlertRulesSink::new(
+            &AlertRulesConfig {
+                rules: vec![AlertRuleConfig {
+                    id: "FanSpeedLow".to_string(),
+                    metric_type: "rotational".to_string(),
+                    condition: "< 1000".to_string(),
+                    message: "Fan speed below threshold".to_string(),
+                    classifications: vec!["Hardware".to_string()],
+                    ..Default::default()
+                }],
+            },
+            inner.clone(),
+        )
+        .expect("sink should initialize");
+
+        let context = EventContext {
+            endpoint_key: "42:9e:b1:bd:9d:dd".to_string(),
+            addr: BmcAddr {
+                ip: "10.0.0.1".parse().expect("valid ip"),
+                port: Some(443),
+                mac: MacAddress::from_str("42:9e:b1:bd:9d:dd").unwrap(),
+            },
+            collector_type: "sensor_collector",
+            metadata: None,
+        };
+
+        sink.handle_event(&context, &CollectorEvent::MetricCollectionStart);
+        sink.handle_event(
+            &context,
+            &CollectorEvent::Metric(MetricSample {
+                key: "fan1".to_string(),
+                name: "hw_sensor".to_string(),
+                metric_type: "rotational".to_string(),
+                unit: "rpm".to_string(),
+                value: 300.0,
+                labels: vec![(Cow::Borrowed("sensor_name"), "Fan1".to_string())],
+            }),
+        );
+        sink.handle_event(&context, &CollectorEvent::MetricCollectionEnd);
+        sink.handle_event(
+            &context,
+            &CollectorEvent::HealthOverride(super::HealthOverride {
+                machine_id: None,
+                report: Arc::new(health_report::HealthReport::empty(
+                    "hardware-health".to_string(),
+                )),
+            }),
+        );
+
+        assert_eq!(*inner.alerts.lock().unwrap(), vec!["FanSpeedLow"]);
+    }
 }
@@ -0,0 +1,395 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use opentelemetry::KeyValue;
+use opentelemetry::logs::{AnyValue, LogRecord as _, Logger as _, LoggerProvider as _, Severity};
+use opentelemetry::metrics::{Meter, MeterProvider as _, ObservableGauge};
+use opentelemetry_otlp::{LogExporter, MetricExporter, WithExportConfig};
+use opentelemetry_sdk::Resource;
+use opentelemetry_sdk::logs::SdkLoggerProvider;
+use opentelemetry_sdk::metrics::{PeriodicReader, SdkMeterProvider};
+
+use super::queue::{QueueReceiver, QueueSender, SinkQueueMetrics, bounded_queue};
+use super::{CollectorEvent, DataSink, EventContext, LogRecord, MetricSample};
+use crate::HealthError;
+use crate::config::OtlpSinkConfig;
+use crate::metrics::MetricsManager;
+
+const INSTRUMENTATION_SCOPE: &str = "carbide-hw-health";
+
+struct OtlpJob {
+    context: EventContext,
+    event: CollectorEvent,
+}
+
+/// Sink which exports metric and log events to an OpenTelemetry collector
+/// using OTLP over gRPC.
+///
+/// Metric samples are recorded as observable gauges and exported
+/// periodically. Series that were not reported during the latest collection
+/// of an endpoint are dropped, so removed sensors and alerts stop being
+/// exported. Log events are exported in batches. Events pass through a bounded queue and
+/// are dropped instead of blocking collectors when the queue is full.
+pub struct OtlpSink {
+    sender: QueueSender<OtlpJob>,
+    export_metrics: bool,
+    export_logs: bool,
+}
+
+impl OtlpSink {
+    pub fn new(
+        config: &OtlpSinkConfig,
+        metrics_manager: Arc<MetricsManager>,
+        metrics_prefix: &str,
+    ) -> Result<Self, HealthError> {
+        let handle = tokio::runtime::Handle::try_current().map_err(|error| {
+            HealthError::GenericError(format!("otlp sink requires active Tokio runtime: {error}"))
+        })?;
+
+        let resource = Resource::builder()
+            .with_attributes([KeyValue::new("service.name", config.service_name.clone())])
+            .build();
+
+        let meter_provider = if config.metrics {
+            let exporter = MetricExporter::builder()
+                .with_tonic()
+                .with_endpoint(config.endpoint.as_str())
+                .with_timeout(config.export_timeout)
+                .build()
+                .map_err(|e| {
+                    HealthError::GenericError(format!("Failed to build OTLP metric exporter: {e}"))
+                })?;
+            let reader = PeriodicReader::builder(exporter)
+                .with_interval(config.export_interval)
+                .build();
+            Some(
+                SdkMeterProvider::builder()
+                    .with_reader(reader)
+                    .with_resource(resource.clone())
+                    .build(),
+            )
+        } else {
+            None
+        };
+
+        let logger_provider = if config.logs {
+            let exporter = LogExporter::builder()
+                .with_tonic()
+                .with_endpoint(config.endpoint.as_str())
+                .with_timeout(config.export_timeout)
+                .build()
+                .map_err(|e| {
+                    HealthError::GenericError(format!("Failed to build OTLP log exporter: {e}"))
+                })?;
+            Some(
+                SdkLoggerProvider::builder()
+                    .with_batch_exporter(exporter)
+                    .with_resource(resource)
+                    .build(),
+            )
+        } else {
+            None
+        };
+
+        let metrics =
+            SinkQueueMetrics::new(metrics_manager.global_registry(), metrics_prefix, "otlp")?;
+        let (sender, receiver) = bounded_queue(config.queue_capacity, metrics);
+
+        handle.spawn(run_worker(receiver, meter_provider, logger_provider));
+
+        Ok(Self {
+            sender,
+            export_metrics: config.metrics,
+            export_logs: config.logs,
+        })
+    }
+}
+
+impl DataSink for OtlpSink {
+    fn handle_event(&self, context: &EventContext, event: &CollectorEvent) {
+        let accepted = match event {
+            CollectorEvent::MetricCollectionStart
+            | CollectorEvent::Metric(_)
+            | CollectorEvent::MetricCollectionEnd => self.export_metrics,
+            CollectorEvent::Log(_) => self.export_logs,
+            _ => false,
+        };
+        if accepted {
+            self.sender.try_enqueue(
+                context.collector_type,
+                OtlpJob {
+                    context: context.clone(),
+                    event: event.clone(),
+                },
+            );
+        }
+    }
+}
+
+async fn run_worker(
+    mut receiver: QueueReceiver<OtlpJob>,
+    meter_provider: Option<SdkMeterProvider>,
+    logger_provider: Option<SdkLoggerProvider>,
+) {
+    let meter = meter_provider
+        .as_ref()
+        .map(|provider| provider.meter(INSTRUMENTATION_SCOPE));
+    let logger = logger_provider
+        .as_ref()
+        .map(|provider| provider.logger(INSTRUMENTATION_SCOPE));
+    let store = GaugeStore::default();
+    let mut gauges: HashMap<GaugeKey, ObservableGauge<f64>> = HashMap::new();
+
+    while let Some(job) = receiver.recv().await {
+        match (&job.event, &meter, &logger) {
+            (CollectorEvent::MetricCollectionStart, Some(_), _) => {
+                store.begin_update(stream_key(&job.context));
+            }
+            (CollectorEvent::Metric(sample), Some(meter), _) => {
+                let gauge_key = (gauge_name(sample), sample.unit.clone());
+                if !gauges.contains_key(&gauge_key) {
+                    let gauge = create_gauge(meter, &gauge_key, store.clone());
+                    gauges.insert(gauge_key.clone(), gauge);
+                }
+                store.record(
+                    gauge_key,
+                    stream_key(&job.context),
+                    sample.key.clone(),
+                    metric_attributes(&job.context, sample),
+                    sample.value,
+                );
+            }
+            (CollectorEvent::MetricCollectionEnd, Some(_), _) => {
+                store.sweep_stale(&stream_key(&job.context));
+            }
+            (CollectorEvent::Log(record), _, Some(logger)) => {
+                let mut log_record = logger.create_log_record();
+                let (severity, severity_text) = otel_severity(record);
+                log_record.set_timestamp(SystemTime::now());
+                log_record.set_severity_number(severity);
+                log_record.set_severity_text(severity_text);
+                log_record.set_body(AnyValue::from(record.body.clone()));
+                log_record.add_attributes(log_attributes(&job.context, record));
+                logger.emit(log_record);
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(provider) = meter_provider
+        && let Err(error) = provider.shutdown()
+    {
+        tracing::warn!(?error, "Failed to shut down OTLP meter provider");
+    }
+    if let Some(provider) = logger_provider
+        && let Err(error) = provider.shutdown()
+    {
+        tracing::warn!(?error, "Failed to shut down OTLP logger provider");
+    }
+}
+
+/// Gauge name and unit.
+type GaugeKey = (String, String);
+
+/// Endpoint key and collector type of the stream which reported a series.
+type StreamKey = (String, &'static str);
+
+struct GaugeSeries {
+    attributes: Vec<KeyValue>,
+    value: f64,
+    generation: u64,
+}
+
+#[derive(Default)]
+struct GaugeStoreInner {
+    generations: HashMap<StreamKey, u64>,
+    series: HashMap<GaugeKey, HashMap<(StreamKey, String), GaugeSeries>>,
+}
+
+/// Latest value of every series, shared with the observable gauge callbacks.
+///
+/// Each collection of a stream bumps its generation, and series which were
+/// not recorded again by the end of the collection are removed.
+#[derive(Clone, Default)]
+struct GaugeStore {
+    inner: Arc<Mutex<GaugeStoreInner>>,
+}
+
+impl GaugeStore {
+    fn begin_update(&self, stream: StreamKey) {
+        let mut inner = self.inner.lock().expect("otlp gauge store lock poisoned");
+        *inner.generations.entry(stream).or_default() += 1;
+    }
+
+    fn record(
+        &self,
+        gauge: GaugeKey,
+        stream: StreamKey,
+        series_key: String,
+        attributes: Vec<KeyValue>,
+        value: f64,
+    ) {
+        let mut inner = self.inner.lock().expect("otlp gauge store lock poisoned");
+        let generation = inner.generations.get(&stream).copied().unwrap_or_default();
+        inner.series.entry(gauge).or_default().insert(
+            (stream, series_key),
+            GaugeSeries {
+                attributes,
+                value,
+                generation,
+            },
+        );
+    }
+
+    fn sweep_stale(&self, stream: &StreamKey) {
+        let mut inner = self.inner.lock().expect("otlp gauge store lock poisoned");
+        let generation = inner.generations.get(stream).copied().unwrap_or_default();
+        for series in inner.series.values_mut() {
+            series.retain(|(series_stream, _), data| {
+                series_stream != stream || data.generation == generation
+            });
+        }
+    }
+
+    fn observe(&self, gauge: &GaugeKey, mut observe: impl FnMut(f64, &[KeyValue])) {
+        let inner = self.inner.lock().expect("otlp gauge store lock poisoned");
+        if let Some(series) = inner.series.get(gauge) {
+            for data in series.values() {
+                observe(data.value, &data.attributes);
+            }
+        }
+    }
+}
+
+fn stream_key(context: &EventContext) -> StreamKey {
+    (context.endpoint_key().to_string(), context.collector_type)
+}
+
+fn gauge_name(sample: &MetricSample) -> String {
+    format!("{}_{}", sample.name, sample.metric_type)
+}
+
+fn create_gauge(meter: &Meter, gauge_key: &GaugeKey, store: GaugeStore) -> ObservableGauge<f64> {
+    let (name, unit) = gauge_key;
+    let callback_key = gauge_key.clone();
+    meter
+        .f64_observable_gauge(name.clone())
+        .with_description("Metrics forwarded through sink pipeline")
+        .with_unit(unit.clone())
+        .with_callback(move |observer| {
+            store.observe(&callback_key, |value, attributes| {
+                observer.observe(value, attributes)
+            });
+        })
+        .build()
+}
+
+fn context_attributes(context: &EventContext) -> Vec<KeyValue> {
+    let mut attributes = vec![
+        KeyValue::new("endpoint_key", context.endpoint_key().to_string()),
+        KeyValue::new("endpoint_mac", context.addr.mac.to_string()),
+        KeyValue::new("endpoint_ip", context.addr.ip.to_string()),
+        KeyValue::new("collector_type", context.collector_type),
+    ];
+    if let Some(machine_id) = context.machine_id() {
+        attributes.push(KeyValue::new("machine_id", machine_id.to_string()));
+    }
+    if let Some(serial) = context.switch_serial() {
+        attributes.push(KeyValue::new("switch_serial", serial.to_string()));
+    }
+    attributes
+}
+
+fn metric_attributes(context: &EventContext, sample: &MetricSample) -> Vec<KeyValue> {
+    let mut attributes = context_attributes(context);
+    attributes.extend(
+        sample
+            .labels
+            .iter()
+            .map(|(key, value)| KeyValue::new(key.to_string(), value.clone())),
+    );
+    attributes
+}
+
+fn log_attributes(context: &EventContext, record: &LogRecord) -> Vec<(String, AnyValue)> {
+    context_attributes(context)
+        .into_iter()
+        .map(|kv| (kv.key.to_string(), AnyValue::from(kv.value.to_string())))
+        .chain(
+            record
+                .attributes
+                .iter()
+                .map(|(key, value)| (key.to_string(), AnyValue::from(value.clone()))),
+        )
+        .collect()
+}
+
+/// Maps the severity text produced by the logs collector to an OTel severity.
+fn otel_severity(record: &LogRecord) -> (Severity, &'static str) {
+    match record.severity.as_str() {
+        "FATAL" => (Severity::Fatal, "FATAL"),
+        "ERROR" => (Severity::Error, "ERROR"),
+        "WARN" => (Severity::Warn, "WARN"),
+        "INFO" => (Severity::Info, "INFO"),
+        "DEBUG" => (Severity::Debug, "DEBUG"),
+        _ => (Severity::Trace, "TRACE"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn observed(store: &GaugeStore, gauge: &GaugeKey) -> Vec<f64> {
+        let mut values = Vec::new();
+        store.observe(gauge, |value, _| values.push(value));
+        values.sort_by(f64::total_cmp);
+        values
+    }
+
+    #[test]
+    fn test_gauge_store_sweeps_series_missing_from_latest_collection() {
+        let store = GaugeStore::default();
+        let gauge: GaugeKey = ("temperature_reading".to_string(), "celsius".to_string());
+        let stream: StreamKey = ("endpoint-1".to_string(), "sensor_collector");
+        let other_stream: StreamKey = ("endpoint-2".to_string(), "sensor_collector");
+
+        store.begin_update(stream.clone());
+        store.record(gauge.clone(), stream.clone(), "temp1".into(), vec![], 40.0);
+        store.record(gauge.clone(), stream.clone(), "temp2".into(), vec![], 50.0);
+        store.sweep_stale(&stream);
+        store.begin_update(other_stream.clone());
+        store.record(
+            gauge.clone(),
+            other_stream.clone(),
+            "temp1".into(),
+            vec![],
+            60.0,
+        );
+        store.sweep_stale(&other_stream);
+        assert_eq!(observed(&store, &gauge), vec![40.0, 50.0, 60.0]);
+
+        store.begin_update(stream.clone());
+        store.record(gauge.clone(), stream.clone(), "temp2".into(), vec![], 55.0);
+        store.sweep_stale(&stream);
+        assert_eq!(observed(&store, &gauge), vec![55.0, 60.0]);
+    }
+}
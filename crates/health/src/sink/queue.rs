@@ -0,0 +1,139 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use prometheus::{IntCounterVec, IntGauge, Opts, Registry};
+use tokio::sync::mpsc;
+
+use crate::HealthError;
+
+/// Backpressure metrics of a queued sink.
+#[derive(Clone)]
+pub(crate) struct SinkQueueMetrics {
+    depth: IntGauge,
+    enqueued: IntCounterVec,
+    dropped: IntCounterVec,
+}
+
+impl SinkQueueMetrics {
+    pub(crate) fn new(
+        registry: &Registry,
+        metrics_prefix: &str,
+        sink_name: &str,
+    ) -> Result<Self, HealthError> {
+        let depth = IntGauge::with_opts(
+            Opts::new(
+                format!("{metrics_prefix}_sink_queue_depth"),
+                "Number of events waiting in the sink queue",
+            )
+            .const_label("sink", sink_name),
+        )?;
+        let enqueued = IntCounterVec::new(
+            Opts::new(
+                format!("{metrics_prefix}_sink_events_enqueued_total"),
+                "Number of events accepted into the sink queue",
+            )
+            .const_label("sink", sink_name),
+            &["collector_type"],
+        )?;
+        let dropped = IntCounterVec::new(
+            Opts::new(
+                format!("{metrics_prefix}_sink_events_dropped_total"),
+                "Number of events dropped because the sink queue was full or closed",
+            )
+            .const_label("sink", sink_name),
+            &["collector_type", "reason"],
+        )?;
+
+        registry.register(Box::new(depth.clone()))?;
+        registry.register(Box::new(enqueued.clone()))?;
+        registry.register(Box::new(dropped.clone()))?;
+
+        Ok(Self {
+            depth,
+            enqueued,
+            dropped,
+        })
+    }
+}
+
+/// Creates a bounded queue which never blocks the producer.
+///
+/// Items are dropped when the queue is full, and accounted for in the
+/// `sink_events_dropped_total` metric.
+pub(crate) fn bounded_queue<T>(
+    capacity: usize,
+    metrics: SinkQueueMetrics,
+) -> (QueueSender<T>, QueueReceiver<T>) {
+    let (sender, receiver) = mpsc::channel(capacity);
+    (
+        QueueSender {
+            sender,
+            metrics: metrics.clone(),
+        },
+        QueueReceiver { receiver, metrics },
+    )
+}
+
+pub(crate) struct QueueSender<T> {
+    sender: mpsc::Sender<T>,
+    metrics: SinkQueueMetrics,
+}
+
+impl<T> QueueSender<T> {
+    /// Enqueues an item without waiting. Returns `false` if the item was dropped.
+    pub(crate) fn try_enqueue(&self, collector_type: &str, item: T) -> bool {
+        match self.sender.try_send(item) {
+            Ok(()) => {
+                self.metrics.depth.inc();
+                self.metrics
+                    .enqueued
+                    .with_label_values(&[collector_type])
+                    .inc();
+                true
+            }
+            Err(mpsc::error::TrySendError::Full(_)) => {
+                self.metrics
+                    .dropped
+                    .with_label_values(&[collector_type, "full"])
+                    .inc();
+                false
+            }
+            Err(mpsc::error::TrySendError::Closed(_)) => {
+                self.metrics
+                    .dropped
+                    .with_label_values(&[collector_type, "closed"])
+                    .inc();
+                false
+            }
+        }
+    }
+}
+
+pub(crate) struct QueueReceiver<T> {
+    receiver: mpsc::Receiver<T>,
+    metrics: SinkQueueMetrics,
+}
+
+impl<T> QueueReceiver<T> {
+    pub(crate) async fn recv(&mut self) -> Option<T> {
+        let item = self.receiver.recv().await;
+        if item.is_some() {
+            self.metrics.depth.dec();
+        }
+        item
+    }
+}
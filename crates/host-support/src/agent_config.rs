@@ -138,14 +138,25 @@ pub struct MachineConfig {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub struct MetadataServiceConfig {
     pub address: String,
+    /// cloud-init vendor-data served to tenant instances, e.g. a `#cloud-config` document
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vendor_data: Option<String>,
+    /// IP TTL, or IPv6 hop limit, of metadata token responses, unless the tenant or instance
+    /// configures one. A hop limit of 1 keeps tokens from reaching anything beyond the host,
+    /// e.g. containers behind a bridge.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hop_limit: Option<u32>,
 }
 
 impl Default for MetadataServiceConfig {
     fn default() -> Self {
         Self {
             address: INSTANCE_METADATA_SERVICE_ADDRESS.to_string(),
+            vendor_data: None,
+            hop_limit: None,
         }
     }
 }
@@ -316,6 +327,11 @@ interface-id = "91609f10-c91d-470d-a260-6293ea0c1200"
 
 [metadata-service]
 address = "0.0.0.0:7777"
+vendor-data = """
+#cloud-config
+timezone: UTC
+"""
+hop-limit = 1
 
 [telemetry]
 metrics-address = "0.0.0.0:8888"
@@ -350,6 +366,11 @@ addresses = ["168.254.169.254/30"]
         assert!(config.machine.is_fake_dpu);
 
         assert_eq!(config.metadata_service.address, "0.0.0.0:7777");
+        assert_eq!(
+            config.metadata_service.vendor_data.as_deref(),
+            Some("#cloud-config\ntimezone: UTC\n")
+        );
+        assert_eq!(config.metadata_service.hop_limit, Some(1));
         assert_eq!(config.telemetry.metrics_address, "0.0.0.0:8888");
 
         assert_eq!(config.hbn.root_dir, PathBuf::from("/tmp/hbn-root"));
@@ -330,6 +330,8 @@ impl ApiClient {
             tenant_organization_id: "Forge-simulation-tenant".to_string(),
             tenant_keyset_ids: vec![],
             hostname: None,
+            metadata_token_required: None,
+            metadata_token_hop_limit: None,
         };
 
         let instance_config = rpc::InstanceConfig {
@@ -502,7 +504,9 @@ impl ApiClient {
                 result: 0,
                 message: "".to_string(),
             }),
+            sata_sas: None,
             result: 0,
+            erasure_records: vec![],
         };
 
         self.0
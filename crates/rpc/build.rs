@@ -33,6 +33,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         )
         .extern_path(".google.protobuf.Duration", "crate::Duration")
         .extern_path(".google.protobuf.Timestamp", "crate::Timestamp")
+        .extern_path(".common.CapacityReservationId", "::carbide_uuid::capacity_reservation::CapacityReservationId")
         .extern_path(".common.DomainId", "::carbide_uuid::domain::DomainId")
         .extern_path(".common.DpaInterfaceId", "::carbide_uuid::dpa_interface::DpaInterfaceId")
         .extern_path(".common.IBPartitionId", "::carbide_uuid::infiniband::IBPartitionId")
@@ -48,6 +49,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .extern_path(".common.NVLinkDomainId", "::carbide_uuid::nvlink::NvLinkDomainId")
         .extern_path(".common.RemediationId", "carbide_uuid::dpu_remediations::RemediationId")
         .extern_path(".common.SwitchId", "::carbide_uuid::switch::SwitchId")
+        .extern_path(".common.TenantDnsRecordId", "::carbide_uuid::tenant_dns_record::TenantDnsRecordId")
         .extern_path(".common.VpcId", "::carbide_uuid::vpc::VpcId")
         .extern_path(".common.VpcPeeringId", "::carbide_uuid::vpc_peering::VpcPeeringId")
         .extern_path(".common.VpcPrefixId", "::carbide_uuid::vpc::VpcPrefixId")
@@ -422,6 +424,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             "#[derive(serde::Deserialize, serde::Serialize)]",
         )
         .type_attribute("StateSla", "#[derive(serde::Serialize)]")
+        .type_attribute("forge.StateHandlerOutcomeHistoryRecord", "#[derive(serde::Serialize)]")
         .type_attribute(
             "BuildInfo",
             "#[derive(serde::Deserialize, serde::Serialize)]",
@@ -575,6 +578,34 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             "forge.NetworkSecurityGroupPropagationObjectStatus",
             "#[derive(serde::Deserialize, serde::Serialize)]",
         )
+        .type_attribute(
+            "forge.NetworkSecurityGroupFlow",
+            "#[derive(serde::Deserialize, serde::Serialize)]",
+        )
+        .field_attribute(
+            "forge.NetworkSecurityGroupFlow.direction",
+            "#[serde(deserialize_with = \"NetworkSecurityGroupRuleDirection::from_string\", serialize_with = \"NetworkSecurityGroupRuleDirection::serialize_from_enum_i32\")]",
+        )
+        .field_attribute(
+            "forge.NetworkSecurityGroupFlow.protocol",
+            "#[serde(deserialize_with = \"NetworkSecurityGroupRuleProtocol::from_string\", serialize_with = \"NetworkSecurityGroupRuleProtocol::serialize_from_enum_i32\")]",
+        )
+        .type_attribute(
+            "forge.NetworkSecurityGroupFlowResult",
+            "#[derive(serde::Deserialize, serde::Serialize)]",
+        )
+        .field_attribute(
+            "forge.NetworkSecurityGroupFlowResult.action",
+            "#[serde(deserialize_with = \"NetworkSecurityGroupRuleAction::from_string\", serialize_with = \"NetworkSecurityGroupRuleAction::serialize_from_enum_i32\")]",
+        )
+        .type_attribute(
+            "forge.NetworkSecurityGroupRuleFinding",
+            "#[derive(serde::Deserialize, serde::Serialize)]",
+        )
+        .type_attribute(
+            "forge.SimulateNetworkSecurityGroupResponse",
+            "#[derive(serde::Deserialize, serde::Serialize)]",
+        )
         .type_attribute("Sku", "#[derive(serde::Serialize, serde::Deserialize)]")
         .field_attribute("Sku.schema_version", "#[serde(default)]")
         .field_attribute("Sku.associated_machine_ids", "#[serde(default)]")
@@ -656,6 +687,42 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             "forge.InstanceType",
             "#[derive(serde::Deserialize,serde::Serialize)]",
         )
+        .type_attribute(
+            "forge.CapacityReservation",
+            "#[derive(serde::Deserialize,serde::Serialize)]",
+        )
+        .type_attribute(
+            "forge.CapacityReservationStatus",
+            "#[derive(serde::Deserialize,serde::Serialize)]",
+        )
+        .type_attribute(
+            "forge.MachineUpdateRollout",
+            "#[derive(serde::Deserialize,serde::Serialize)]",
+        )
+        .type_attribute(
+            "forge.MachineUpdateRolloutHost",
+            "#[derive(serde::Deserialize,serde::Serialize)]",
+        )
+        .type_attribute(
+            "forge.MaintenanceWindow",
+            "#[derive(serde::Deserialize,serde::Serialize)]",
+        )
+        .type_attribute(
+            "forge.MaintenanceWindowList",
+            "#[derive(serde::Deserialize,serde::Serialize)]",
+        )
+        .type_attribute(
+            "forge.SshConsoleSession",
+            "#[derive(serde::Deserialize,serde::Serialize)]",
+        )
+        .type_attribute(
+            "forge.InstanceWebhookSubscription",
+            "#[derive(serde::Deserialize,serde::Serialize)]",
+        )
+        .type_attribute(
+            "forge.InstanceWebhookDelivery",
+            "#[derive(serde::Deserialize,serde::Serialize)]",
+        )
         .field_attribute(
             "forge.InstanceTypeMachineCapabilityFilterAttributes.capability_type",
             "#[serde(deserialize_with = \"MachineCapabilityType::from_string\", serialize_with = \"MachineCapabilityType::serialize_from_enum_i32\")]",
@@ -770,6 +837,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         extern_paths: vec![
             (".common.MachineId", "::carbide_uuid::machine::MachineId"),
             (".common.DomainId", "::carbide_uuid::domain::DomainId"),
+            (
+                ".common.CapacityReservationId",
+                "::carbide_uuid::capacity_reservation::CapacityReservationId",
+            ),
             (
                 ".common.RemediationId",
                 "::carbide_uuid::dpu_remediations::RemediationId",
@@ -778,6 +849,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 ".common.MachineInterfaceId",
                 "::carbide_uuid::machine::MachineInterfaceId",
             ),
+            (
+                ".common.TenantDnsRecordId",
+                "::carbide_uuid::tenant_dns_record::TenantDnsRecordId",
+            ),
             (".common.VpcId", "::carbide_uuid::vpc::VpcId"),
             (".common.VpcPrefixId", "::carbide_uuid::vpc::VpcPrefixId"),
             (
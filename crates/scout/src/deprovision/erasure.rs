@@ -0,0 +1,739 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Per-device records of the storage erasure during cleanup, which the API turns into a
+//! certificate-of-erasure report, and the erasure of SATA and SAS disks.
+
+use std::fs;
+use std::future::Future;
+use std::io::SeekFrom;
+
+use ::rpc::forge as rpc;
+use chrono::{DateTime, Utc};
+use scout::CarbideClientError;
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+use tracing::Instrument;
+
+use crate::deprovision::cmdrun;
+
+/// Number of blocks read back from every device to verify the erasure
+const VERIFICATION_SAMPLE_COUNT: u64 = 8;
+/// Size of every verification sample. Samples are aligned to it.
+const VERIFICATION_SAMPLE_SIZE: u32 = 4096;
+
+static BLOCKDEV_PROG: &str = "/usr/sbin/blockdev";
+static UDEVADM_PROG: &str = "/usr/bin/udevadm";
+static HDPARM_PROG: &str = "/usr/sbin/hdparm";
+static SG_SANITIZE_PROG: &str = "/usr/bin/sg_sanitize";
+
+// Setting a user password is required to issue SECURITY ERASE UNIT.
+// A successful erase clears it again, a failed one disables it explicitly.
+static ATA_SECURITY_PASSWORD: &str = "forge";
+
+/// Identity of an erased device as reported by the device
+#[derive(Debug, Default, Clone)]
+pub(super) struct DeviceIdentity {
+    pub serial_number: String,
+    pub model: String,
+    pub firmware_version: String,
+    pub capacity_bytes: u64,
+}
+
+/// Offsets of the verification samples of a device with the given capacity.
+/// Samples are spread evenly from the first to the last full block.
+fn sample_offsets(capacity_bytes: u64) -> Vec<u64> {
+    let size = VERIFICATION_SAMPLE_SIZE as u64;
+    let blocks = capacity_bytes / size;
+    if blocks == 0 {
+        return Vec::new();
+    }
+    let mut offsets: Vec<u64> = (0..VERIFICATION_SAMPLE_COUNT)
+        .map(|i| i * (blocks - 1) / (VERIFICATION_SAMPLE_COUNT - 1) * size)
+        .collect();
+    offsets.dedup();
+    offsets
+}
+
+fn is_zeroed(data: &[u8]) -> bool {
+    data.iter().all(|&byte| byte == 0)
+}
+
+fn block_device_capacity(block_device: &str) -> Result<u64, CarbideClientError> {
+    let name = block_device.trim_start_matches("/dev/");
+    let sectors = fs::read_to_string(format!("/sys/block/{name}/size"))
+        .map_err(|e| {
+            CarbideClientError::GenericError(format!("Failed to read size of {block_device}: {e}"))
+        })?
+        .trim()
+        .parse::<u64>()
+        .map_err(|e| {
+            CarbideClientError::GenericError(format!("Invalid size of {block_device}: {e}"))
+        })?;
+    // sysfs always counts 512 byte sectors, independent of the logical block size
+    Ok(sectors * 512)
+}
+
+/// Reads the verification samples of a block device.
+/// Buffers are flushed first, so that the blocks are read from the device instead of the page cache.
+async fn read_samples(block_device: &str) -> Result<Vec<(u64, Vec<u8>)>, CarbideClientError> {
+    cmdrun::run_prog(BLOCKDEV_PROG, ["--flushbufs", block_device]).await?;
+
+    let mut file = tokio::fs::File::open(block_device).await?;
+    let mut samples = Vec::new();
+    for offset in sample_offsets(block_device_capacity(block_device)?) {
+        let mut data = vec![0u8; VERIFICATION_SAMPLE_SIZE as usize];
+        file.seek(SeekFrom::Start(offset)).await?;
+        file.read_exact(&mut data).await?;
+        samples.push((offset, data));
+    }
+    Ok(samples)
+}
+
+async fn read_samples_or_log(block_device: Option<String>) -> Vec<(u64, Vec<u8>)> {
+    let Some(block_device) = block_device else {
+        return Vec::new();
+    };
+    match read_samples(&block_device).await {
+        Ok(samples) => samples,
+        Err(error) => {
+            tracing::warn!(%block_device, %error, "Failed to read verification samples");
+            Vec::new()
+        }
+    }
+}
+
+fn verification_samples(
+    before: &[(u64, Vec<u8>)],
+    after: &[(u64, Vec<u8>)],
+) -> Vec<rpc::StorageErasureVerificationSample> {
+    after
+        .iter()
+        .map(|(offset, data)| rpc::StorageErasureVerificationSample {
+            offset_bytes: *offset,
+            length_bytes: data.len() as u32,
+            zeroed: is_zeroed(data),
+            changed: before
+                .iter()
+                .find(|(before_offset, _)| before_offset == offset)
+                .is_some_and(|(_, before_data)| before_data != data),
+        })
+        .collect()
+}
+
+/// Builds the record of an erasure. An erasure which succeeded still fails if any of the
+/// verification samples still holds its content from before the erasure.
+fn erasure_record(
+    device: &str,
+    identity: DeviceIdentity,
+    method: rpc::StorageErasureMethod,
+    result: &Result<(), CarbideClientError>,
+    verification_samples: Vec<rpc::StorageErasureVerificationSample>,
+    started_at: DateTime<Utc>,
+) -> rpc::StorageErasureRecord {
+    let unverified: Vec<String> = verification_samples
+        .iter()
+        .filter(|sample| !sample.zeroed && !sample.changed)
+        .map(|sample| sample.offset_bytes.to_string())
+        .collect();
+    let error = match result {
+        Err(e) => Some(e.to_string()),
+        Ok(()) if !unverified.is_empty() => Some(format!(
+            "Blocks at offsets {} are unchanged after the erasure",
+            unverified.join(", ")
+        )),
+        Ok(()) => None,
+    };
+
+    rpc::StorageErasureRecord {
+        device: device.to_string(),
+        serial_number: identity.serial_number,
+        model: identity.model,
+        firmware_version: identity.firmware_version,
+        capacity_bytes: identity.capacity_bytes,
+        method: method as i32,
+        success: error.is_none(),
+        error,
+        verification_samples,
+        started_at: Some(started_at.into()),
+        completed_at: Some(Utc::now().into()),
+    }
+}
+
+/// Records that no device was erased, so that the erasure report shows why instead of
+/// listing no devices at all
+pub(super) fn skipped_record(reason: String) -> rpc::StorageErasureRecord {
+    let now = Utc::now();
+    rpc::StorageErasureRecord {
+        device: "/dev/*".to_string(),
+        method: rpc::StorageErasureMethod::Unspecified as i32,
+        success: false,
+        error: Some(format!("skipped: {reason}")),
+        started_at: Some(now.into()),
+        completed_at: Some(now.into()),
+        ..Default::default()
+    }
+}
+
+/// Erases a device and records the erasure. The erasure is verified by reading samples of the
+/// block device returned by `block_device` before and after erasing, where the block device
+/// is resolved again after the erasure as it might have been recreated.
+pub(super) async fn erase_and_record<E>(
+    device: &str,
+    identity: DeviceIdentity,
+    block_device: impl Fn() -> Option<String>,
+    erase: E,
+) -> (rpc::StorageErasureRecord, Result<(), CarbideClientError>)
+where
+    E: Future<Output = (rpc::StorageErasureMethod, Result<(), CarbideClientError>)>,
+{
+    let started_at = Utc::now();
+    let before = read_samples_or_log(block_device()).await;
+
+    let (method, result) = erase.await;
+
+    let after = if result.is_ok() {
+        // Devices recreated by the erasure show up once udev processed their events
+        if let Err(error) = cmdrun::run_prog(UDEVADM_PROG, ["settle"]).await {
+            tracing::warn!(%error, "udevadm settle failed");
+        }
+        read_samples_or_log(block_device()).await
+    } else {
+        Vec::new()
+    };
+
+    let record = erasure_record(
+        device,
+        identity,
+        method,
+        &result,
+        verification_samples(&before, &after),
+        started_at,
+    );
+    let result = match (result, &record.error) {
+        (Ok(()), Some(error)) => Err(CarbideClientError::GenericError(error.clone())),
+        (result, _) => result,
+    };
+    (record, result)
+}
+
+/// Whether the sysfs path of a disk belongs to a USB device, e.g. the virtual media of a BMC
+fn is_usb_device_path(sysfs_path: &str) -> bool {
+    sysfs_path.contains("/usb")
+}
+
+fn read_sysfs(path: &str) -> String {
+    fs::read_to_string(path)
+        .map(|value| value.trim().to_string())
+        .unwrap_or_default()
+}
+
+/// SATA and SAS disks, which are named `sdX` by the kernel. Removable and USB devices are skipped.
+fn sata_sas_disks() -> Vec<String> {
+    let Ok(entries) = fs::read_dir("/sys/block") else {
+        return Vec::new();
+    };
+    let mut disks: Vec<String> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if !name.starts_with("sd") {
+                return None;
+            }
+            if read_sysfs(&format!("/sys/block/{name}/removable")) == "1" {
+                tracing::info!(disk = %name, "Skipping removable disk");
+                return None;
+            }
+            let sysfs_path = fs::canonicalize(entry.path()).ok()?;
+            if is_usb_device_path(&sysfs_path.to_string_lossy()) {
+                tracing::info!(disk = %name, "Skipping USB disk");
+                return None;
+            }
+            Some(name)
+        })
+        .collect();
+    disks.sort();
+    disks
+}
+
+/// Parses the serial number from VPD page 0x80 (Unit Serial Number)
+fn parse_vpd_unit_serial(page: &[u8]) -> String {
+    if page.len() < 4 || page[1] != 0x80 {
+        return String::new();
+    }
+    let length = u16::from_be_bytes([page[2], page[3]]) as usize;
+    let serial = &page[4..page.len().min(4 + length)];
+    String::from_utf8_lossy(serial)
+        .trim_matches(|c: char| c.is_whitespace() || c == '\0')
+        .to_string()
+}
+
+fn sata_sas_identity(disk: &str) -> DeviceIdentity {
+    DeviceIdentity {
+        serial_number: fs::read(format!("/sys/block/{disk}/device/vpd_pg80"))
+            .map(|page| parse_vpd_unit_serial(&page))
+            .unwrap_or_default(),
+        model: read_sysfs(&format!("/sys/block/{disk}/device/model")),
+        firmware_version: read_sysfs(&format!("/sys/block/{disk}/device/rev")),
+        capacity_bytes: block_device_capacity(&format!("/dev/{disk}")).unwrap_or_default(),
+    }
+}
+
+/// The security feature set of an ATA drive as reported by `hdparm -I`
+#[derive(Debug, Default, PartialEq)]
+struct AtaSecurity {
+    supported: bool,
+    enabled: bool,
+    frozen: bool,
+    enhanced_erase: bool,
+}
+
+fn parse_hdparm_security(output: &str) -> AtaSecurity {
+    let mut security = AtaSecurity::default();
+    let lines = output
+        .lines()
+        .skip_while(|line| !line.starts_with("Security:"))
+        .skip(1)
+        .take_while(|line| line.starts_with(char::is_whitespace));
+    for line in lines {
+        match line.trim() {
+            "supported" => security.supported = true,
+            "enabled" => security.enabled = true,
+            "frozen" => security.frozen = true,
+            "supported: enhanced erase" => security.enhanced_erase = true,
+            _ => {}
+        }
+    }
+    security
+}
+
+async fn ata_secure_erase(
+    block_device: &str,
+) -> (rpc::StorageErasureMethod, Result<(), CarbideClientError>) {
+    let info = match cmdrun::run_prog(HDPARM_PROG, ["-I", block_device]).await {
+        Ok(info) => info,
+        Err(e) => return (rpc::StorageErasureMethod::AtaSecureErase, Err(e)),
+    };
+    let security = parse_hdparm_security(&info);
+    let (method, erase_arg) = if security.enhanced_erase {
+        (
+            rpc::StorageErasureMethod::AtaEnhancedSecureErase,
+            "--security-erase-enhanced",
+        )
+    } else {
+        (
+            rpc::StorageErasureMethod::AtaSecureErase,
+            "--security-erase",
+        )
+    };
+
+    let result = async {
+        if !security.supported {
+            return Err(CarbideClientError::GenericError(format!(
+                "{block_device} does not support the ATA security feature set"
+            )));
+        }
+        if security.frozen {
+            return Err(CarbideClientError::GenericError(format!(
+                "{block_device} is security frozen"
+            )));
+        }
+        if security.enabled {
+            // The password might have been left behind by an earlier erasure which didn't finish
+            cmdrun::run_prog(
+                HDPARM_PROG,
+                [
+                    "--user-master",
+                    "u",
+                    "--security-unlock",
+                    ATA_SECURITY_PASSWORD,
+                    block_device,
+                ],
+            )
+            .await
+            .map_err(|e| {
+                CarbideClientError::GenericError(format!(
+                    "{block_device} has an unknown ATA security password set: {e}"
+                ))
+            })?;
+        } else {
+            cmdrun::run_prog(
+                HDPARM_PROG,
+                [
+                    "--user-master",
+                    "u",
+                    "--security-set-pass",
+                    ATA_SECURITY_PASSWORD,
+                    block_device,
+                ],
+            )
+            .await?;
+        }
+        if let Err(error) = cmdrun::run_prog(
+            HDPARM_PROG,
+            [
+                "--user-master",
+                "u",
+                erase_arg,
+                ATA_SECURITY_PASSWORD,
+                block_device,
+            ],
+        )
+        .await
+        {
+            disable_ata_security(block_device).await;
+            return Err(error);
+        }
+        Ok(())
+    }
+    .await;
+    (method, result)
+}
+
+/// Clears the password set for the erasure, so that a drive which failed to erase isn't locked
+/// after its next power cycle
+async fn disable_ata_security(block_device: &str) {
+    if let Err(error) = cmdrun::run_prog(
+        HDPARM_PROG,
+        [
+            "--user-master",
+            "u",
+            "--security-disable",
+            ATA_SECURITY_PASSWORD,
+            block_device,
+        ],
+    )
+    .await
+    {
+        tracing::warn!(%error, "Failed to disable ATA security after a failed erase");
+    }
+}
+
+/// Sanitizes a SCSI disk with cryptographic erase, falling back to block erase if the disk
+/// doesn't support it
+async fn scsi_sanitize(
+    block_device: &str,
+) -> (rpc::StorageErasureMethod, Result<(), CarbideClientError>) {
+    let result = match cmdrun::run_prog(
+        SG_SANITIZE_PROG,
+        ["--quick", "--wait", "--crypto", block_device],
+    )
+    .await
+    {
+        Ok(_) => Ok(()),
+        Err(error) => {
+            tracing::info!(%error, "Cryptographic sanitize failed, falling back to block erase");
+            cmdrun::run_prog(
+                SG_SANITIZE_PROG,
+                ["--quick", "--wait", "--block", block_device],
+            )
+            .await
+            .map(|_| ())
+        }
+    };
+    (rpc::StorageErasureMethod::ScsiSanitize, result)
+}
+
+async fn clean_this_sata_sas(
+    disk: &str,
+) -> (rpc::StorageErasureRecord, Result<(), CarbideClientError>) {
+    let block_device = format!("/dev/{disk}");
+    // libata exposes SATA disks as SCSI disks with the vendor "ATA"
+    let is_ata = read_sysfs(&format!("/sys/block/{disk}/device/vendor")) == "ATA";
+
+    erase_and_record(
+        &block_device,
+        sata_sas_identity(disk),
+        || Some(block_device.clone()),
+        async {
+            if is_ata {
+                ata_secure_erase(&block_device).await
+            } else {
+                scsi_sanitize(&block_device).await
+            }
+        },
+    )
+    .await
+}
+
+/// Records the erasure of a disk whose cleanup task failed, e.g. by panicking, so that the
+/// disk still shows up as not erased in the erasure report
+fn failed_task_record(
+    disk: &str,
+    error: &tokio::task::JoinError,
+    started_at: DateTime<Utc>,
+) -> (rpc::StorageErasureRecord, Result<(), CarbideClientError>) {
+    let result = Err(CarbideClientError::GenericError(format!(
+        "SATA/SAS cleanup task failed: {error}"
+    )));
+    let record = erasure_record(
+        &format!("/dev/{disk}"),
+        sata_sas_identity(disk),
+        rpc::StorageErasureMethod::Unspecified,
+        &result,
+        Vec::new(),
+        started_at,
+    );
+    (record, result)
+}
+
+/// Erases all SATA and SAS disks, adding a record per disk to `records`
+pub(super) async fn all_sata_sas_cleanup(
+    records: &mut Vec<rpc::StorageErasureRecord>,
+) -> Result<(), CarbideClientError> {
+    let disks = sata_sas_disks();
+    let device_count = disks.len();
+    if device_count == 0 {
+        tracing::info!("No SATA/SAS disks found to clean");
+        return Ok(());
+    }
+
+    tracing::info!(device_count, "Starting SATA/SAS cleanup");
+    let started_at = Utc::now();
+    let cleanup_futures: Vec<_> = disks
+        .iter()
+        .map(|disk| {
+            let disk = disk.clone();
+            let span = tracing::info_span!("sata_sas_cleanup", device = %disk);
+            tokio::spawn(async move { clean_this_sata_sas(&disk).await }.instrument(span))
+        })
+        .collect();
+
+    let mut errors: Vec<String> = Vec::new();
+    let join_results = futures_util::future::join_all(cleanup_futures).await;
+    for (disk, join_result) in disks.iter().zip(join_results) {
+        let (record, result) =
+            join_result.unwrap_or_else(|error| failed_task_record(disk, &error, started_at));
+        if let Err(error) = result {
+            tracing::error!(device = %record.device, %error, "Cleanup failed");
+            errors.push(format!(
+                "SATA_SAS_CLEAN_ERROR (device: {}): {}",
+                record.device, error
+            ));
+        }
+        records.push(record);
+    }
+
+    tracing::info!(
+        device_count,
+        error_count = errors.len(),
+        "SATA/SAS cleanup completed"
+    );
+
+    if !errors.is_empty() {
+        return Err(CarbideClientError::GenericError(errors.join("\n")));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample_offsets() {
+        let offsets = sample_offsets(1_000_000_000_000);
+        assert_eq!(offsets.len(), VERIFICATION_SAMPLE_COUNT as usize);
+        assert_eq!(offsets[0], 0);
+        for offset in &offsets {
+            assert_eq!(offset % VERIFICATION_SAMPLE_SIZE as u64, 0);
+            assert!(offset + VERIFICATION_SAMPLE_SIZE as u64 <= 1_000_000_000_000);
+        }
+        // The last sample is the last full block
+        assert_eq!(
+            *offsets.last().unwrap(),
+            1_000_000_000_000 / 4096 * 4096 - 4096
+        );
+
+        // Small devices get fewer samples
+        assert_eq!(sample_offsets(3 * 4096), vec![0, 4096, 8192]);
+        assert_eq!(sample_offsets(4096), vec![0]);
+        assert!(sample_offsets(4095).is_empty());
+    }
+
+    #[test]
+    fn test_verification_samples() {
+        let before = vec![
+            (0, vec![1u8; 4]),
+            (4096, vec![0u8; 4]),
+            (8192, vec![7u8; 4]),
+        ];
+        let after = vec![
+            (0, vec![0u8; 4]),
+            (4096, vec![0u8; 4]),
+            (8192, vec![7u8; 4]),
+        ];
+        let samples = verification_samples(&before, &after);
+        assert_eq!(
+            samples
+                .iter()
+                .map(|sample| (sample.offset_bytes, sample.zeroed, sample.changed))
+                .collect::<Vec<_>>(),
+            vec![(0, true, true), (4096, true, false), (8192, false, false)]
+        );
+        assert_eq!(samples[0].length_bytes, 4);
+    }
+
+    #[test]
+    fn test_erasure_record() {
+        let sample = |offset_bytes, zeroed, changed| rpc::StorageErasureVerificationSample {
+            offset_bytes,
+            length_bytes: VERIFICATION_SAMPLE_SIZE,
+            zeroed,
+            changed,
+        };
+        let record = |result, samples| {
+            erasure_record(
+                "/dev/sda",
+                DeviceIdentity::default(),
+                rpc::StorageErasureMethod::ScsiSanitize,
+                &result,
+                samples,
+                Utc::now(),
+            )
+        };
+
+        let ok = record(
+            Ok(()),
+            vec![sample(0, true, false), sample(4096, false, true)],
+        );
+        assert!(ok.success);
+        assert_eq!(ok.error, None);
+        assert_eq!(ok.method, rpc::StorageErasureMethod::ScsiSanitize as i32);
+
+        let unverified = record(
+            Ok(()),
+            vec![sample(0, true, true), sample(4096, false, false)],
+        );
+        assert!(!unverified.success);
+        assert_eq!(
+            unverified.error.as_deref(),
+            Some("Blocks at offsets 4096 are unchanged after the erasure")
+        );
+
+        let failed = record(
+            Err(CarbideClientError::GenericError("frozen".to_string())),
+            vec![],
+        );
+        assert!(!failed.success);
+        assert!(failed.error.unwrap().contains("frozen"));
+    }
+
+    #[test]
+    fn test_failed_task_record() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let error = runtime.block_on(async {
+            let task = tokio::spawn(std::future::pending::<()>());
+            task.abort();
+            task.await.unwrap_err()
+        });
+
+        let (record, result) = failed_task_record("sdz", &error, Utc::now());
+        assert_eq!(record.device, "/dev/sdz");
+        assert_eq!(record.method, rpc::StorageErasureMethod::Unspecified as i32);
+        assert!(!record.success);
+        assert!(
+            record
+                .error
+                .unwrap()
+                .contains("SATA/SAS cleanup task failed")
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_is_usb_device_path() {
+        assert!(is_usb_device_path(
+            "/sys/devices/pci0000:00/0000:00:14.0/usb1/1-4/1-4:1.0/host6/target6:0:0/6:0:0:0/block/sdb"
+        ));
+        assert!(!is_usb_device_path(
+            "/sys/devices/pci0000:00/0000:00:17.0/ata1/host0/target0:0:0/0:0:0:0/block/sda"
+        ));
+    }
+
+    #[test]
+    fn test_parse_vpd_unit_serial() {
+        let mut page = vec![0x00, 0x80, 0x00, 0x0c];
+        page.extend_from_slice(b"  S3Z8NB0K12");
+        assert_eq!(parse_vpd_unit_serial(&page), "S3Z8NB0K12");
+        assert_eq!(parse_vpd_unit_serial(&[0x00, 0x83, 0x00, 0x00]), "");
+        assert_eq!(parse_vpd_unit_serial(&[]), "");
+    }
+
+    #[test]
+    fn test_parse_hdparm_security() {
+        let output = "\
+/dev/sda:
+
+ATA device, with non-removable media
+\tModel Number:       Samsung SSD 860 EVO 500GB
+Security:
+\tMaster password revision code = 65534
+\t\tsupported
+\tnot\tenabled
+\tnot\tlocked
+\tnot\tfrozen
+\tnot\texpired: security count
+\t\tsupported: enhanced erase
+\t2min for SECURITY ERASE UNIT. 2min for ENHANCED SECURITY ERASE UNIT.
+Logical Unit WWN Device Identifier: 5002538e40a1b2c3
+";
+        assert_eq!(
+            parse_hdparm_security(output),
+            AtaSecurity {
+                supported: true,
+                enabled: false,
+                frozen: false,
+                enhanced_erase: true,
+            }
+        );
+
+        let frozen = "\
+Security:
+\t\tsupported
+\tnot\tenabled
+\tnot\tlocked
+\t\tfrozen
+\tnot\texpired: security count
+\tnot\tsupported: enhanced erase
+";
+        assert_eq!(
+            parse_hdparm_security(frozen),
+            AtaSecurity {
+                supported: true,
+                enabled: false,
+                frozen: true,
+                enhanced_erase: false,
+            }
+        );
+
+        assert_eq!(parse_hdparm_security(""), AtaSecurity::default());
+    }
+
+    #[test]
+    fn test_skipped_record() {
+        let record = skipped_record("stdin is /dev/pts/0 instead of /dev/null".to_string());
+        assert!(!record.device.is_empty());
+        assert_eq!(record.method, rpc::StorageErasureMethod::Unspecified as i32);
+        assert!(!record.success);
+        assert_eq!(
+            record.error.as_deref(),
+            Some("skipped: stdin is /dev/pts/0 instead of /dev/null")
+        );
+    }
+}
@@ -15,6 +15,7 @@
  * limitations under the License.
  */
 mod cmdrun;
+mod erasure;
 mod scrabbing;
 pub(crate) use scrabbing::run;
 pub use scrabbing::run_no_api;
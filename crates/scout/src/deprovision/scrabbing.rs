@@ -28,7 +28,8 @@ use tracing::Instrument;
 
 use crate::cfg::Options;
 use crate::client::create_forge_client;
-use crate::deprovision::cmdrun;
+use crate::deprovision::erasure::DeviceIdentity;
+use crate::deprovision::{cmdrun, erasure};
 use crate::{CarbideClientResult, IN_QEMU_VM};
 
 fn check_memory_overwrite_efi_var() -> Result<(), CarbideClientError> {
@@ -62,6 +63,7 @@ fn check_memory_overwrite_efi_var() -> Result<(), CarbideClientError> {
 
 static NVME_CLI_PROG: &str = "/usr/sbin/nvme";
 static LENOVO_NVMI_CLI_PROG: &str = "/opt/forge/bin/mnv_cli";
+static LENOVO_RAID_KIT_MODEL: &str = "M.2 NVMe 2-Bay RAID Kit";
 
 lazy_static::lazy_static! {
     static ref NVME_NS_RE: Regex = Regex::new(r".*:(0x[0-9]+)").unwrap();
@@ -218,11 +220,15 @@ async fn get_best_lba_format(nvmename: &str) -> Result<(u8, u64), CarbideClientE
     }
 }
 
-async fn clean_this_nvme(nvmename: &String) -> Result<(), CarbideClientError> {
+/// Erases a NVMe device, returning the method which erased it
+async fn clean_this_nvme(
+    nvmename: &String,
+    nvme_drive_params: &NvmeParams,
+) -> Result<rpc::StorageErasureMethod, CarbideClientError> {
     tracing::debug!("cleaning {}", nvmename);
 
-    let nvme_drive_params = get_nvme_params(nvmename).await?;
-
+    // The Lenovo RAID kit passthru formats with SES=2 as well
+    let mut method = rpc::StorageErasureMethod::NvmeCryptoErase;
     let namespaces_supported = nvme_drive_params.oacs & 0x8 == 0x8;
 
     tracing::debug!(
@@ -237,7 +243,7 @@ async fn clean_this_nvme(nvmename: &String) -> Result<(), CarbideClientError> {
         nvme_drive_params.fr
     );
 
-    if nvme_drive_params.mn.trim() == "M.2 NVMe 2-Bay RAID Kit" {
+    if nvme_drive_params.mn.trim() == LENOVO_RAID_KIT_MODEL {
         if !std::path::Path::new(LENOVO_NVMI_CLI_PROG).exists() {
             return Err(CarbideClientError::GenericError(format!(
                 "Device {} is a Lenovo M.2 NVMe 2-Bay RAID Kit and requires {} for cleanup, \
@@ -319,6 +325,7 @@ async fn clean_this_nvme(nvmename: &String) -> Result<(), CarbideClientError> {
                     if namespaces_supported {
                         // format can fail if there is a wrong params for namespace. We delete it anyway.
                         tracing::debug!("nvme format error: {}", e);
+                        method = rpc::StorageErasureMethod::NvmeNamespaceRecreate;
                     } else {
                         return Err(e);
                     }
@@ -380,7 +387,71 @@ async fn clean_this_nvme(nvmename: &String) -> Result<(), CarbideClientError> {
         }
     }
     tracing::debug!("Cleanup completed for nvme device {}", nvmename);
-    Ok(())
+    Ok(method)
+}
+
+/// Whether `name` is the block device of a namespace of the NVMe controller `controller`,
+/// e.g. `nvme0n1` of `nvme0`
+fn is_nvme_namespace_of(controller: &str, name: &str) -> bool {
+    name.strip_prefix(controller)
+        .and_then(|rest| rest.strip_prefix('n'))
+        .is_some_and(|nsid| !nsid.is_empty() && nsid.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// The block device of the first namespace of a NVMe controller, which is read to verify the
+/// erasure
+fn nvme_namespace_block_device(nvmename: &str) -> Option<String> {
+    let controller = nvmename.trim_start_matches("/dev/");
+    let mut namespaces: Vec<String> = fs::read_dir(format!("/sys/class/nvme/{controller}"))
+        .ok()?
+        .flatten()
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .filter(|name| is_nvme_namespace_of(controller, name))
+        .collect();
+    namespaces.sort();
+    namespaces.first().map(|name| format!("/dev/{name}"))
+}
+
+async fn erase_nvme(
+    nvmename: &String,
+) -> (rpc::StorageErasureRecord, Result<(), CarbideClientError>) {
+    let nvme_drive_params = get_nvme_params(nvmename).await;
+    // The namespaces of the RAID kit are its virtual drives, which are gone after the cleanup
+    let verify = nvme_drive_params
+        .as_ref()
+        .is_ok_and(|params| params.mn.trim() != LENOVO_RAID_KIT_MODEL);
+    let identity = match &nvme_drive_params {
+        Ok(params) => DeviceIdentity {
+            serial_number: params.sn.trim().to_string(),
+            model: params.mn.trim().to_string(),
+            firmware_version: params.fr.trim().to_string(),
+            capacity_bytes: params.tnvmcap,
+        },
+        Err(_) => DeviceIdentity::default(),
+    };
+
+    erasure::erase_and_record(
+        nvmename,
+        identity,
+        || {
+            if verify {
+                nvme_namespace_block_device(nvmename)
+            } else {
+                None
+            }
+        },
+        async {
+            match nvme_drive_params {
+                Ok(params) => match clean_this_nvme(nvmename, &params).await {
+                    Ok(method) => (method, Ok(())),
+                    // Formatting with crypto erase is always attempted first
+                    Err(e) => (rpc::StorageErasureMethod::NvmeCryptoErase, Err(e)),
+                },
+                Err(e) => (rpc::StorageErasureMethod::Unspecified, Err(e)),
+            }
+        },
+    )
+    .await
 }
 
 /// Failed NVMe device cleanup with error context
@@ -390,7 +461,10 @@ struct CleanupFailure {
     error: CarbideClientError,
 }
 
-async fn all_nvme_cleanup() -> Result<(), CarbideClientError> {
+/// Erases all NVMe devices, adding a record per device to `records`
+async fn all_nvme_cleanup(
+    records: &mut Vec<rpc::StorageErasureRecord>,
+) -> Result<(), CarbideClientError> {
     let mut nvme_devicepaths: Vec<String> = Vec::new();
     if let Ok(paths) = fs::read_dir("/dev") {
         for entry in paths {
@@ -430,10 +504,10 @@ async fn all_nvme_cleanup() -> Result<(), CarbideClientError> {
                     let device_start = std::time::Instant::now();
 
                     tracing::info!("Starting cleanup");
-                    let result = clean_this_nvme(&nvmename).await;
+                    let (record, result) = erase_nvme(&nvmename).await;
                     let duration = device_start.elapsed();
 
-                    match result {
+                    let result = match result {
                         Ok(()) => {
                             tracing::info!(?duration, "Cleanup completed successfully");
                             Ok(())
@@ -446,7 +520,8 @@ async fn all_nvme_cleanup() -> Result<(), CarbideClientError> {
                                 error,
                             })
                         }
-                    }
+                    };
+                    (record, result)
                 }
                 .instrument(span),
             )
@@ -462,7 +537,8 @@ async fn all_nvme_cleanup() -> Result<(), CarbideClientError> {
     let mut success_count = 0;
 
     for join_result in results {
-        let cleanup_result = join_result.expect("nvme cleanup task panicked");
+        let (record, cleanup_result) = join_result.expect("nvme cleanup task panicked");
+        records.push(record);
         match cleanup_result {
             Ok(()) => success_count += 1,
             Err(failure) => errors.push(format!(
@@ -704,7 +780,9 @@ async fn do_cleanup(machine_id: &MachineId) -> CarbideClientResult<rpc::MachineC
         ram: None,
         mem_overwrite: None,
         ib: None,
+        sata_sas: None,
         result: rpc::machine_cleanup_info::CleanupResult::Ok as _,
+        erasure_records: Vec::new(),
     };
 
     // do nvme cleanup only if stdin is /dev/null. This is because we afraid to cleanum someone's nvme drive.
@@ -714,7 +792,7 @@ async fn do_cleanup(machine_id: &MachineId) -> CarbideClientResult<rpc::MachineC
     };
 
     if stdin_link == "/dev/null" {
-        match all_nvme_cleanup().await {
+        match all_nvme_cleanup(&mut cleanup_result.erasure_records).await {
             Ok(_) => {
                 cleanup_result.nvme = Some(rpc::machine_cleanup_info::CleanupStepResult {
                     result: rpc::machine_cleanup_info::CleanupResult::Ok as _,
@@ -730,8 +808,29 @@ async fn do_cleanup(machine_id: &MachineId) -> CarbideClientResult<rpc::MachineC
                 cleanup_result.result = rpc::machine_cleanup_info::CleanupResult::Error as _;
             }
         }
+        match erasure::all_sata_sas_cleanup(&mut cleanup_result.erasure_records).await {
+            Ok(_) => {
+                cleanup_result.sata_sas = Some(rpc::machine_cleanup_info::CleanupStepResult {
+                    result: rpc::machine_cleanup_info::CleanupResult::Ok as _,
+                    message: "OK".to_string(),
+                });
+            }
+            Err(e) => {
+                tracing::error!("{}", e);
+                cleanup_result.sata_sas = Some(rpc::machine_cleanup_info::CleanupStepResult {
+                    result: rpc::machine_cleanup_info::CleanupResult::Error as _,
+                    message: e.to_string(),
+                });
+                cleanup_result.result = rpc::machine_cleanup_info::CleanupResult::Error as _;
+            }
+        }
     } else {
-        tracing::info!("stdin == {}. Skip nvme cleanup.", stdin_link);
+        tracing::info!("stdin == {}. Skip nvme and SATA/SAS cleanup.", stdin_link);
+        cleanup_result
+            .erasure_records
+            .push(erasure::skipped_record(format!(
+                "stdin is {stdin_link} instead of /dev/null"
+            )));
     }
 
     match check_memory_overwrite_efi_var() {
@@ -832,12 +931,18 @@ pub async fn run_no_api() -> Result<(), CarbideClientError> {
     tracing::info!("stdin is {}", stdin_link);
 
     if stdin_link == "/dev/null" {
-        match all_nvme_cleanup().await {
+        // Without an API there is nobody to send the erasure records to
+        let mut records = Vec::new();
+        match all_nvme_cleanup(&mut records).await {
             Ok(_) => tracing::debug!("nvme cleanup OK"),
             Err(e) => tracing::error!("nvme cleanup error: {}", e),
         }
+        match erasure::all_sata_sas_cleanup(&mut records).await {
+            Ok(_) => tracing::debug!("SATA/SAS cleanup OK"),
+            Err(e) => tracing::error!("SATA/SAS cleanup error: {}", e),
+        }
     } else {
-        tracing::info!("stdin == {}. Skip nvme cleanup.", stdin_link);
+        tracing::info!("stdin == {}. Skip nvme and SATA/SAS cleanup.", stdin_link);
     }
 
     // P1 errors are propagated (fail startup), P2 errors are handled internally in reset_ib_devices()
@@ -994,6 +1099,16 @@ mod tests {
         assert_eq!(sectors_512 / sectors_4096, 8);
     }
 
+    #[test]
+    fn test_is_nvme_namespace_of() {
+        assert!(is_nvme_namespace_of("nvme0", "nvme0n1"));
+        assert!(is_nvme_namespace_of("nvme1", "nvme1n12"));
+        assert!(!is_nvme_namespace_of("nvme1", "nvme10n1"));
+        assert!(!is_nvme_namespace_of("nvme0", "nvme0c0n1"));
+        assert!(!is_nvme_namespace_of("nvme0", "nvme0n"));
+        assert!(!is_nvme_namespace_of("nvme0", "ng0n1"));
+    }
+
     #[test]
     fn test_lbaf_sector_size_calculation() {
         // ds=9 means 2^9 = 512 bytes
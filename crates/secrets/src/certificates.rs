@@ -16,6 +16,7 @@
  */
 use ::rpc::protos::forge::MachineCertificate;
 use async_trait::async_trait;
+use rand::Rng;
 
 use crate::SecretsError;
 
@@ -45,3 +46,21 @@ pub trait CertificateProvider: Send + Sync {
         ttl: Option<String>,
     ) -> Result<Certificate, SecretsError>;
 }
+
+/// The SPIFFE ID which is put into the URI SAN of the certificate of a machine:
+/// `spiffe://<trust_domain>/<namespace>/machine/<stable_machine_id>`
+pub fn machine_spiffe_id(unique_identifier: &str) -> String {
+    let trust_domain = "forge.local";
+    let namespace = "forge-system";
+    format!("spiffe://{trust_domain}/{namespace}/machine/{unique_identifier}")
+}
+
+/// The TTL for certificates which are requested without one, in hours.
+///
+/// This is to setup a baseline skew of between 60 - 100% of 30 days,
+/// so that not all boxes will renew (or expire) at the same time.
+pub fn default_ttl_hours() -> u64 {
+    let max_hours = 720; // 24 * 30
+    let min_hours = 432; // 24 * 30 * 0.6
+    rand::rng().random_range(min_hours..max_hours)
+}
@@ -0,0 +1,240 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Selection of the backends which hold credentials and issue certificates.
+//!
+//! Vault is the default for both. Sites which can't run Vault keep their credentials in an
+//! encrypted file and issue certificates from a local CA instead:
+//!
+//! ```toml
+//! [secrets.credentials]
+//! backend = "file"
+//! path = "/var/lib/carbide/credentials.json"
+//! kek = { source = "tpm_sealed", path = "/etc/carbide/kek.sealed.json" }
+//!
+//! [secrets.certificates]
+//! backend = "local_ca"
+//! cert_path = "/etc/carbide/ca/ca.crt"
+//! key_path = "/etc/carbide/ca/ca.key"
+//! ```
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use opentelemetry::metrics::Meter;
+use serde::{Deserialize, Serialize};
+
+use crate::SecretsError;
+use crate::certificates::CertificateProvider;
+use crate::credentials::CredentialProvider;
+use crate::file_credentials::FileCredentialProvider;
+use crate::forge_vault::{ForgeVaultClient, VaultConfig, create_vault_client};
+use crate::kek::KeyEncryptionKey;
+use crate::local_ca::LocalCertificateAuthority;
+use crate::migration::CredentialStore;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SecretsConfig {
+    #[serde(default)]
+    pub credentials: CredentialBackendConfig,
+    #[serde(default)]
+    pub certificates: CertificateBackendConfig,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "backend", rename_all = "snake_case")]
+pub enum CredentialBackendConfig {
+    /// The KV engine of Vault
+    #[default]
+    Vault,
+    /// An encrypted file, see [`FileCredentialProvider`]
+    File { path: PathBuf, kek: KekConfig },
+}
+
+/// Where the key encryption key of the file credential provider comes from
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "source", rename_all = "snake_case")]
+pub enum KekConfig {
+    /// A file holding the raw or base64 encoded key
+    File { path: PathBuf },
+    /// A key sealed to the TPM of the host, see [`KeyEncryptionKey::from_tpm_sealed_file`]
+    TpmSealed {
+        path: PathBuf,
+        #[serde(default = "KekConfig::default_tcti")]
+        tcti: String,
+    },
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "backend", rename_all = "snake_case")]
+pub enum CertificateBackendConfig {
+    /// The PKI engine of Vault
+    #[default]
+    Vault,
+    /// A CA whose key is stored on local disk, see [`LocalCertificateAuthority`]
+    LocalCa {
+        cert_path: PathBuf,
+        key_path: PathBuf,
+        /// Create a new self-signed CA if neither the certificate nor the key exist
+        #[serde(default)]
+        create_if_missing: bool,
+    },
+}
+
+/// The providers for credentials and certificates which are used by carbide-api
+pub struct SecretProviders {
+    pub credentials: Arc<dyn CredentialProvider>,
+    pub certificates: Arc<dyn CertificateProvider>,
+}
+
+impl SecretsConfig {
+    /// Creates the configured providers. A Vault client is only created if one of them uses
+    /// Vault.
+    pub async fn create_providers(
+        &self,
+        vault_config: &VaultConfig,
+        meter: Meter,
+    ) -> eyre::Result<SecretProviders> {
+        let mut vault_client: Option<Arc<ForgeVaultClient>> = None;
+        let mut vault = || -> eyre::Result<Arc<ForgeVaultClient>> {
+            if let Some(vault_client) = &vault_client {
+                return Ok(vault_client.clone());
+            }
+            let client = create_vault_client(vault_config, meter.clone())?;
+            vault_client = Some(client.clone());
+            Ok(client)
+        };
+
+        let credentials: Arc<dyn CredentialProvider> = match &self.credentials {
+            CredentialBackendConfig::Vault => vault()?,
+            CredentialBackendConfig::File { path, kek } => {
+                Arc::new(FileCredentialProvider::open(path.clone(), kek.load()?).await?)
+            }
+        };
+
+        let certificates: Arc<dyn CertificateProvider> = match &self.certificates {
+            CertificateBackendConfig::Vault => vault()?,
+            CertificateBackendConfig::LocalCa {
+                cert_path,
+                key_path,
+                create_if_missing,
+            } => Arc::new(if *create_if_missing {
+                LocalCertificateAuthority::load_or_create(cert_path, key_path, "Carbide Local CA")?
+            } else {
+                LocalCertificateAuthority::load(cert_path, key_path)?
+            }),
+        };
+
+        Ok(SecretProviders {
+            credentials,
+            certificates,
+        })
+    }
+}
+
+impl CredentialBackendConfig {
+    /// Creates the configured backend for copying credentials from or to it
+    pub async fn create_store(
+        &self,
+        vault_config: &VaultConfig,
+        meter: Meter,
+    ) -> eyre::Result<Arc<dyn CredentialStore>> {
+        let store: Arc<dyn CredentialStore> = match self {
+            CredentialBackendConfig::Vault => create_vault_client(vault_config, meter)?,
+            CredentialBackendConfig::File { path, kek } => {
+                Arc::new(FileCredentialProvider::open(path.clone(), kek.load()?).await?)
+            }
+        };
+        Ok(store)
+    }
+}
+
+impl KekConfig {
+    fn default_tcti() -> String {
+        // tpmrm0 is a tpm with an in-kernel resource manager
+        "device:/dev/tpmrm0".to_string()
+    }
+
+    pub fn load(&self) -> Result<KeyEncryptionKey, SecretsError> {
+        match self {
+            KekConfig::File { path } => KeyEncryptionKey::from_file(path),
+            #[cfg(feature = "tpm")]
+            KekConfig::TpmSealed { path, tcti } => {
+                KeyEncryptionKey::from_tpm_sealed_file(path, tcti)
+            }
+            #[cfg(not(feature = "tpm"))]
+            KekConfig::TpmSealed { .. } => Err(eyre::eyre!(
+                "TPM sealed key encryption keys are not supported by this build"
+            )
+            .into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Deserialize)]
+    struct Wrapper {
+        #[serde(default)]
+        secrets: SecretsConfig,
+    }
+
+    #[test]
+    fn test_deserialize_default() {
+        let config: Wrapper = toml::from_str("").unwrap();
+        assert_eq!(config.secrets, SecretsConfig::default());
+        assert_eq!(config.secrets.credentials, CredentialBackendConfig::Vault);
+    }
+
+    #[test]
+    fn test_deserialize_local_backends() {
+        let config: Wrapper = toml::from_str(
+            r#"
+            [secrets.credentials]
+            backend = "file"
+            path = "/var/lib/carbide/credentials.json"
+            kek = { source = "tpm_sealed", path = "/etc/carbide/kek.sealed.json" }
+
+            [secrets.certificates]
+            backend = "local_ca"
+            cert_path = "/etc/carbide/ca/ca.crt"
+            key_path = "/etc/carbide/ca/ca.key"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.secrets,
+            SecretsConfig {
+                credentials: CredentialBackendConfig::File {
+                    path: "/var/lib/carbide/credentials.json".into(),
+                    kek: KekConfig::TpmSealed {
+                        path: "/etc/carbide/kek.sealed.json".into(),
+                        tcti: "device:/dev/tpmrm0".to_string(),
+                    },
+                },
+                certificates: CertificateBackendConfig::LocalCa {
+                    cert_path: "/etc/carbide/ca/ca.crt".into(),
+                    key_path: "/etc/carbide/ca/ca.key".into(),
+                    create_if_missing: false,
+                },
+            }
+        );
+    }
+}
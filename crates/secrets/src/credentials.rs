@@ -21,6 +21,7 @@ use std::sync::atomic;
 use std::sync::atomic::AtomicU32;
 
 use async_trait::async_trait;
+use carbide_uuid::domain::DomainId;
 use carbide_uuid::machine::MachineId;
 use mac_address::MacAddress;
 use rand::Rng;
@@ -255,6 +256,8 @@ pub enum CredentialKey {
     NmxM { nmxm_id: String },
     RackFirmware { firmware_id: String },
     SwitchNvosAdmin { bmc_mac_address: MacAddress },
+    DnsDomainKey { domain_id: DomainId, key_id: u32 },
+    InstanceWebhookSecret { subscription_id: String },
 }
 
 impl CredentialKey {
@@ -334,6 +337,12 @@ impl CredentialKey {
             CredentialKey::SwitchNvosAdmin { bmc_mac_address } => {
                 Cow::from(format!("switch_nvos/{bmc_mac_address}/admin"))
             }
+            CredentialKey::DnsDomainKey { domain_id, key_id } => {
+                Cow::from(format!("dns/domains/{domain_id}/keys/{key_id}"))
+            }
+            CredentialKey::InstanceWebhookSecret { subscription_id } => {
+                Cow::from(format!("instance_webhooks/{subscription_id}/secret"))
+            }
         }
     }
 }
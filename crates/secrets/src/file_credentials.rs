@@ -0,0 +1,465 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! A credential provider which keeps credentials in a local file, for sites which can't run
+//! Vault.
+//!
+//! The file holds a JSON map from credential keys to [`EncryptedSecret`]s. Only the values are
+//! encrypted, the keys (which contain MAC addresses and machine IDs, but no secrets) are stored in
+//! plain text. Every change rewrites the whole file atomically.
+//!
+//! The file is the only source of truth: reads load it again, and changes hold an exclusive lock
+//! of a `.lock` file next to it while they re-read, modify and replace it. Several processes (e.g.
+//! carbide-api and a credential migration) can therefore share the file without losing writes.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+use eyre::{WrapErr, eyre};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+
+use crate::SecretsError;
+use crate::credentials::{CredentialKey, CredentialProvider, Credentials};
+use crate::kek::{EncryptedSecret, KeyEncryptionKey};
+use crate::migration::CredentialStore;
+
+const FILE_VERSION: u32 = 1;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CredentialsFile {
+    version: u32,
+    credentials: BTreeMap<String, EncryptedSecret>,
+}
+
+#[derive(Debug)]
+pub struct FileCredentialProvider {
+    path: PathBuf,
+    lock_path: PathBuf,
+    kek: KeyEncryptionKey,
+    /// Serializes the changes of this process. The lock file serializes them with other processes.
+    write_lock: Mutex<()>,
+}
+
+impl FileCredentialProvider {
+    /// Opens the credentials file at `path`. The file is created with the first write if it
+    /// doesn't exist yet.
+    pub async fn open(path: PathBuf, kek: KeyEncryptionKey) -> Result<Self, SecretsError> {
+        let mut lock_path = path.clone().into_os_string();
+        lock_path.push(".lock");
+
+        let provider = Self {
+            path,
+            lock_path: PathBuf::from(lock_path),
+            kek,
+            write_lock: Mutex::new(()),
+        };
+
+        // Fail right away if the file was encrypted with a different KEK
+        let credentials = provider.load().await?;
+        if let Some((key, secret)) = credentials.iter().next() {
+            provider.kek.decrypt(key, secret)?;
+        }
+
+        Ok(provider)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn decrypt(&self, key: &str, secret: &EncryptedSecret) -> Result<Credentials, SecretsError> {
+        let plaintext = self.kek.decrypt(key, secret)?;
+        Ok(serde_json::from_slice(&plaintext)
+            .wrap_err_with(|| format!("Invalid credentials stored for {key}"))?)
+    }
+
+    /// Reads the current contents of the file
+    async fn load(&self) -> Result<BTreeMap<String, EncryptedSecret>, SecretsError> {
+        let contents = match tokio::fs::read(&self.path).await {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(BTreeMap::new()),
+            Err(e) => {
+                return Err(eyre!(e)
+                    .wrap_err(format!(
+                        "Failed to read credentials file {}",
+                        self.path.display()
+                    ))
+                    .into());
+            }
+        };
+
+        let file: CredentialsFile = serde_json::from_slice(&contents)
+            .wrap_err_with(|| format!("Invalid credentials file {}", self.path.display()))?;
+        if file.version != FILE_VERSION {
+            return Err(eyre!(
+                "Unsupported version {} of credentials file {}",
+                file.version,
+                self.path.display()
+            )
+            .into());
+        }
+        Ok(file.credentials)
+    }
+
+    /// Takes the exclusive lock of the file, which is released when the returned file is dropped
+    async fn lock(&self) -> Result<std::fs::File, SecretsError> {
+        let lock_path = self.lock_path.clone();
+        let file = tokio::task::spawn_blocking(move || {
+            let mut options = std::fs::OpenOptions::new();
+            options.write(true).create(true).truncate(false);
+            #[cfg(unix)]
+            std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+            let file = options.open(&lock_path)?;
+            file.lock()?;
+            Ok::<_, std::io::Error>(file)
+        })
+        .await
+        .wrap_err("Credentials file lock task failed")?
+        .wrap_err_with(|| format!("Failed to lock {}", self.lock_path.display()))?;
+        Ok(file)
+    }
+
+    /// Applies `change` to the current contents of the file and stores the result, unless
+    /// `change` returns `false` because there is nothing to store
+    async fn modify<F>(&self, change: F) -> Result<(), SecretsError>
+    where
+        F: FnOnce(&mut BTreeMap<String, EncryptedSecret>) -> Result<bool, SecretsError>,
+    {
+        let _write_guard = self.write_lock.lock().await;
+        let _file_lock = self.lock().await?;
+
+        let mut credentials = self.load().await?;
+        if change(&mut credentials)? {
+            self.persist(&credentials).await?;
+        }
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Credentials>, SecretsError> {
+        let credentials = self.load().await?;
+        let Some(secret) = credentials.get(key) else {
+            return Ok(None);
+        };
+        match self.decrypt(key, secret)? {
+            // Empty passwords are treated as missing credentials, like the Vault provider does
+            Credentials::UsernamePassword { password, .. } if password.is_empty() => Ok(None),
+            credentials => Ok(Some(credentials)),
+        }
+    }
+
+    async fn set(
+        &self,
+        key: &str,
+        credentials: &Credentials,
+        allow_overwrite: bool,
+    ) -> Result<(), SecretsError> {
+        let plaintext = serde_json::to_vec(credentials).wrap_err("Failed to serialize")?;
+        let secret = self.kek.encrypt(key, &plaintext)?;
+
+        self.modify(|credentials| {
+            if !allow_overwrite && credentials.contains_key(key) {
+                return Err(eyre!("Secret already exists with key {key}").into());
+            }
+            credentials.insert(key.to_string(), secret);
+            Ok(true)
+        })
+        .await
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), SecretsError> {
+        self.modify(|credentials| Ok(credentials.remove(key).is_some()))
+            .await
+    }
+
+    /// Replaces the file through a rename, so that a crash never leaves a partially written file.
+    /// Must only be called while holding the file lock.
+    async fn persist(
+        &self,
+        credentials: &BTreeMap<String, EncryptedSecret>,
+    ) -> Result<(), SecretsError> {
+        let contents = serde_json::to_vec_pretty(&CredentialsFile {
+            version: FILE_VERSION,
+            credentials: credentials.clone(),
+        })
+        .wrap_err("Failed to serialize credentials file")?;
+
+        let mut tmp_path = self.path.clone().into_os_string();
+        tmp_path.push(".tmp");
+        let tmp_path = PathBuf::from(tmp_path);
+
+        let mut options = tokio::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        options.mode(0o600);
+        let mut file = options
+            .open(&tmp_path)
+            .await
+            .wrap_err_with(|| format!("Failed to create {}", tmp_path.display()))?;
+        tokio::io::AsyncWriteExt::write_all(&mut file, &contents)
+            .await
+            .wrap_err_with(|| format!("Failed to write {}", tmp_path.display()))?;
+        file.sync_all()
+            .await
+            .wrap_err_with(|| format!("Failed to sync {}", tmp_path.display()))?;
+        drop(file);
+
+        tokio::fs::rename(&tmp_path, &self.path)
+            .await
+            .wrap_err_with(|| format!("Failed to replace {}", self.path.display()))?;
+
+        // The rename only survives a crash once the directory entry is synced as well
+        let dir = match self.path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+        tokio::fs::File::open(dir)
+            .await
+            .wrap_err_with(|| format!("Failed to open {}", dir.display()))?
+            .sync_all()
+            .await
+            .wrap_err_with(|| format!("Failed to sync {}", dir.display()))?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl CredentialProvider for FileCredentialProvider {
+    async fn get_credentials(
+        &self,
+        key: &CredentialKey,
+    ) -> Result<Option<Credentials>, SecretsError> {
+        self.get(key.to_key_str().as_ref()).await
+    }
+
+    async fn set_credentials(
+        &self,
+        key: &CredentialKey,
+        credentials: &Credentials,
+    ) -> Result<(), SecretsError> {
+        self.set(key.to_key_str().as_ref(), credentials, true).await
+    }
+
+    async fn create_credentials(
+        &self,
+        key: &CredentialKey,
+        credentials: &Credentials,
+    ) -> Result<(), SecretsError> {
+        self.set(key.to_key_str().as_ref(), credentials, false)
+            .await
+    }
+
+    async fn delete_credentials(&self, key: &CredentialKey) -> Result<(), SecretsError> {
+        self.delete(key.to_key_str().as_ref()).await
+    }
+}
+
+#[async_trait]
+impl CredentialStore for FileCredentialProvider {
+    async fn list_credential_keys(&self) -> Result<Vec<String>, SecretsError> {
+        Ok(self.load().await?.into_keys().collect())
+    }
+
+    async fn get_credentials_by_key(&self, key: &str) -> Result<Option<Credentials>, SecretsError> {
+        self.get(key).await
+    }
+
+    async fn set_credentials_by_key(
+        &self,
+        key: &str,
+        credentials: &Credentials,
+    ) -> Result<(), SecretsError> {
+        self.set(key, credentials, true).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::credentials::BmcCredentialType;
+    use crate::migration::copy_credentials;
+
+    fn kek(bytes: &[u8]) -> KeyEncryptionKey {
+        KeyEncryptionKey::from_bytes(bytes).unwrap()
+    }
+
+    fn credentials(password: &str) -> Credentials {
+        Credentials::UsernamePassword {
+            username: "root".to_string(),
+            password: password.to_string(),
+        }
+    }
+
+    const SITE_ROOT: CredentialKey = CredentialKey::BmcCredentials {
+        credential_type: BmcCredentialType::SiteWideRoot,
+    };
+
+    #[tokio::test]
+    async fn test_file_credential_provider() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("credentials.json");
+        let key_bytes = KeyEncryptionKey::generate().unwrap();
+
+        let provider = FileCredentialProvider::open(path.clone(), kek(&key_bytes))
+            .await
+            .unwrap();
+        assert_eq!(provider.get_credentials(&SITE_ROOT).await.unwrap(), None);
+
+        provider
+            .create_credentials(&SITE_ROOT, &credentials("first"))
+            .await
+            .unwrap();
+        assert!(
+            provider
+                .create_credentials(&SITE_ROOT, &credentials("second"))
+                .await
+                .is_err()
+        );
+        provider
+            .set_credentials(&SITE_ROOT, &credentials("second"))
+            .await
+            .unwrap();
+
+        // The password is not stored in plain text
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert!(contents.contains("machines/bmc/site/root"));
+        assert!(!contents.contains("second"));
+
+        // Reopening the file sees the stored credentials
+        let provider = FileCredentialProvider::open(path.clone(), kek(&key_bytes))
+            .await
+            .unwrap();
+        assert_eq!(
+            provider.get_credentials(&SITE_ROOT).await.unwrap(),
+            Some(credentials("second"))
+        );
+
+        // A different KEK is rejected
+        let wrong_key = KeyEncryptionKey::generate().unwrap();
+        assert!(
+            FileCredentialProvider::open(path.clone(), kek(&wrong_key))
+                .await
+                .is_err()
+        );
+
+        provider.delete_credentials(&SITE_ROOT).await.unwrap();
+        assert_eq!(provider.get_credentials(&SITE_ROOT).await.unwrap(), None);
+        assert!(provider.list_credential_keys().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_shared_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("credentials.json");
+        let key_bytes = KeyEncryptionKey::generate().unwrap();
+
+        let first = FileCredentialProvider::open(path.clone(), kek(&key_bytes))
+            .await
+            .unwrap();
+        let second = FileCredentialProvider::open(path.clone(), kek(&key_bytes))
+            .await
+            .unwrap();
+
+        first
+            .set_credentials_by_key("ufm/fabric1/auth", &credentials("ufm"))
+            .await
+            .unwrap();
+        // Writes of one provider are neither lost nor overwritten by the other
+        assert!(
+            second
+                .set("ufm/fabric1/auth", &credentials("other"), false)
+                .await
+                .is_err()
+        );
+        second
+            .set_credentials(&SITE_ROOT, &credentials("root"))
+            .await
+            .unwrap();
+        assert_eq!(
+            first.get_credentials(&SITE_ROOT).await.unwrap(),
+            Some(credentials("root"))
+        );
+        assert_eq!(
+            first.list_credential_keys().await.unwrap(),
+            vec![
+                "machines/bmc/site/root".to_string(),
+                "ufm/fabric1/auth".to_string()
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_copy_credentials() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = FileCredentialProvider::open(
+            dir.path().join("source.json"),
+            kek(&KeyEncryptionKey::generate().unwrap()),
+        )
+        .await
+        .unwrap();
+        let target = FileCredentialProvider::open(
+            dir.path().join("target.json"),
+            kek(&KeyEncryptionKey::generate().unwrap()),
+        )
+        .await
+        .unwrap();
+
+        source
+            .set_credentials_by_key("ufm/fabric1/auth", &credentials("ufm"))
+            .await
+            .unwrap();
+        source
+            .set_credentials_by_key("machines/bmc/site/root", &credentials("new"))
+            .await
+            .unwrap();
+        target
+            .set_credentials_by_key("machines/bmc/site/root", &credentials("old"))
+            .await
+            .unwrap();
+
+        let report = copy_credentials(&source, &target, false, true)
+            .await
+            .unwrap();
+        assert_eq!(report.copied, vec!["ufm/fabric1/auth".to_string()]);
+        assert_eq!(
+            report.skipped_existing,
+            vec!["machines/bmc/site/root".to_string()]
+        );
+        // Dry runs don't write anything
+        assert_eq!(target.list_credential_keys().await.unwrap().len(), 1);
+
+        let report = copy_credentials(&source, &target, true, false)
+            .await
+            .unwrap();
+        assert_eq!(report.copied.len(), 2);
+        assert_eq!(
+            target
+                .get_credentials_by_key("machines/bmc/site/root")
+                .await
+                .unwrap(),
+            Some(credentials("new"))
+        );
+        assert_eq!(
+            target
+                .get_credentials_by_key("ufm/fabric1/auth")
+                .await
+                .unwrap(),
+            Some(credentials("ufm"))
+        );
+    }
+}
@@ -25,7 +25,6 @@ use async_trait::async_trait;
 use eyre::{ContextCompat, WrapErr, eyre};
 use opentelemetry::KeyValue;
 use opentelemetry::metrics::{Counter, Gauge, Histogram, Meter};
-use rand::Rng;
 use tokio::sync::mpsc::{Receiver, Sender};
 use tokio::time::sleep;
 use vaultrs::api::kv2::requests::SetSecretRequestOptions;
@@ -37,8 +36,9 @@ use vaultrs::error::ClientError;
 use vaultrs::{kv2, pki};
 
 use crate::SecretsError;
-use crate::certificates::{Certificate, CertificateProvider};
+use crate::certificates::{Certificate, CertificateProvider, default_ttl_hours, machine_spiffe_id};
 use crate::credentials::{CredentialKey, CredentialProvider, Credentials};
+use crate::migration::CredentialStore;
 
 #[derive(Clone, Debug)]
 enum ForgeVaultAuthenticationType {
@@ -311,7 +311,7 @@ trait VaultTask<T> {
 
 struct GetCredentialsHelper<'key, 'location> {
     pub kv_mount_location: &'location String,
-    pub key: &'key CredentialKey,
+    pub key: &'key str,
 }
 
 #[async_trait]
@@ -326,12 +326,8 @@ impl VaultTask<Option<Credentials>> for GetCredentialsHelper<'_, '_> {
             .add(1, &[KeyValue::new("request_type", "get_credentials")]);
 
         let time_started_vault_request = Instant::now();
-        let vault_response = kv2::read(
-            vault_client.deref(),
-            self.kv_mount_location,
-            self.key.to_key_str().as_ref(),
-        )
-        .await;
+        let vault_response =
+            kv2::read(vault_client.deref(), self.kv_mount_location, self.key).await;
         let elapsed_request_duration = time_started_vault_request.elapsed().as_millis() as u64;
         vault_metrics.vault_request_duration_histogram.record(
             elapsed_request_duration,
@@ -350,17 +346,11 @@ impl VaultTask<Option<Credentials>> for GetCredentialsHelper<'_, '_> {
                 match status_code {
                     Some(404) => {
                         // Not found errors are common and of no concern
-                        tracing::debug!(
-                            "Credentials not found for key ({})",
-                            self.key.to_key_str().as_ref()
-                        );
+                        tracing::debug!("Credentials not found for key ({})", self.key);
                         Ok(None)
                     }
                     _ => {
-                        tracing::error!(
-                            "Error getting credentials ({}). Error: {ce:?}",
-                            self.key.to_key_str().as_ref()
-                        );
+                        tracing::error!("Error getting credentials ({}). Error: {ce:?}", self.key);
                         Err(SecretsError::GenericError(ce.into()))
                     }
                 }
@@ -403,7 +393,7 @@ fn record_vault_client_error(
 
 struct SetCredentialsHelper<'key, 'location> {
     pub kv_mount_location: &'location String,
-    pub key: &'key CredentialKey,
+    pub key: &'key str,
     pub credentials: &'key Credentials,
     pub allow_overwrite: bool,
 }
@@ -425,7 +415,7 @@ impl VaultTask<()> for SetCredentialsHelper<'_, '_> {
             kv2::set(
                 vault_client.deref(),
                 self.kv_mount_location,
-                self.key.to_key_str().as_ref(),
+                self.key,
                 &self.credentials,
             )
             .await
@@ -439,7 +429,7 @@ impl VaultTask<()> for SetCredentialsHelper<'_, '_> {
             kv2::set_with_options(
                 vault_client.deref(),
                 self.kv_mount_location,
-                self.key.to_key_str().as_ref(),
+                self.key,
                 &self.credentials,
                 options,
             )
@@ -467,7 +457,7 @@ impl VaultTask<()> for SetCredentialsHelper<'_, '_> {
 
 struct DeleteCredentialsHelper<'key, 'location> {
     pub kv_mount_location: &'location String,
-    pub key: &'key CredentialKey,
+    pub key: &'key str,
 }
 
 #[async_trait]
@@ -482,12 +472,8 @@ impl VaultTask<()> for DeleteCredentialsHelper<'_, '_> {
             .add(1, &[KeyValue::new("request_type", "delete_credentials")]);
 
         let time_started_vault_request = Instant::now();
-        let vault_response = kv2::delete_metadata(
-            vault_client.deref(),
-            self.kv_mount_location,
-            self.key.to_key_str().as_ref(),
-        )
-        .await;
+        let vault_response =
+            kv2::delete_metadata(vault_client.deref(), self.kv_mount_location, self.key).await;
 
         let elapsed_request_duration = time_started_vault_request.elapsed().as_millis() as u64;
         vault_metrics.vault_request_duration_histogram.record(
@@ -517,7 +503,7 @@ impl CredentialProvider for ForgeVaultClient {
         let kv_mount_location = &self.vault_client_config.kv_mount_location;
         let get_credentials_helper = GetCredentialsHelper {
             kv_mount_location,
-            key,
+            key: &key.to_key_str(),
         };
         let vault_client = self.vault_client().await?;
         get_credentials_helper
@@ -532,7 +518,7 @@ impl CredentialProvider for ForgeVaultClient {
     ) -> Result<(), SecretsError> {
         let kv_mount_location = &self.vault_client_config.kv_mount_location;
         let set_credentials_helper = SetCredentialsHelper {
-            key,
+            key: &key.to_key_str(),
             credentials,
             kv_mount_location,
             allow_overwrite: true,
@@ -550,7 +536,7 @@ impl CredentialProvider for ForgeVaultClient {
     ) -> Result<(), SecretsError> {
         let kv_mount_location = &self.vault_client_config.kv_mount_location;
         let set_credentials_helper = SetCredentialsHelper {
-            key,
+            key: &key.to_key_str(),
             credentials,
             kv_mount_location,
             allow_overwrite: false,
@@ -564,7 +550,7 @@ impl CredentialProvider for ForgeVaultClient {
     async fn delete_credentials(&self, key: &CredentialKey) -> Result<(), SecretsError> {
         let kv_mount_location = &self.vault_client_config.kv_mount_location;
         let delete_credentials_helper = DeleteCredentialsHelper {
-            key,
+            key: &key.to_key_str(),
             kv_mount_location,
         };
         let vault_client = self.vault_client().await?;
@@ -574,6 +560,104 @@ impl CredentialProvider for ForgeVaultClient {
     }
 }
 
+struct ListCredentialKeysHelper<'location> {
+    pub kv_mount_location: &'location String,
+}
+
+#[async_trait]
+impl VaultTask<Vec<String>> for ListCredentialKeysHelper<'_> {
+    async fn execute(
+        &self,
+        vault_client: Arc<VaultClient>,
+        vault_metrics: &ForgeVaultMetrics,
+    ) -> Result<Vec<String>, SecretsError> {
+        // Vault only lists a single level, entries ending in `/` are folders
+        let mut keys = Vec::new();
+        let mut folders = vec![String::new()];
+        while let Some(folder) = folders.pop() {
+            vault_metrics
+                .vault_requests_total_counter
+                .add(1, &[KeyValue::new("request_type", "list_credentials")]);
+
+            let time_started_vault_request = Instant::now();
+            let vault_response =
+                kv2::list(vault_client.deref(), self.kv_mount_location, &folder).await;
+            let elapsed_request_duration = time_started_vault_request.elapsed().as_millis() as u64;
+            vault_metrics.vault_request_duration_histogram.record(
+                elapsed_request_duration,
+                &[KeyValue::new("request_type", "list_credentials")],
+            );
+
+            let entries = match vault_response {
+                Ok(entries) => entries,
+                Err(err) => {
+                    match record_vault_client_error(&err, "list_credentials", vault_metrics) {
+                        // An empty mount
+                        Some(404) => continue,
+                        _ => return Err(err.into()),
+                    }
+                }
+            };
+            vault_metrics
+                .vault_requests_succeeded_counter
+                .add(1, &[KeyValue::new("request_type", "list_credentials")]);
+
+            for entry in entries {
+                let path = format!("{folder}{entry}");
+                if entry.ends_with('/') {
+                    folders.push(path);
+                } else {
+                    keys.push(path);
+                }
+            }
+        }
+
+        keys.sort();
+        Ok(keys)
+    }
+}
+
+#[async_trait]
+impl CredentialStore for ForgeVaultClient {
+    async fn list_credential_keys(&self) -> Result<Vec<String>, SecretsError> {
+        let list_credential_keys_helper = ListCredentialKeysHelper {
+            kv_mount_location: &self.vault_client_config.kv_mount_location,
+        };
+        let vault_client = self.vault_client().await?;
+        list_credential_keys_helper
+            .execute(vault_client, &self.vault_metrics)
+            .await
+    }
+
+    async fn get_credentials_by_key(&self, key: &str) -> Result<Option<Credentials>, SecretsError> {
+        let get_credentials_helper = GetCredentialsHelper {
+            kv_mount_location: &self.vault_client_config.kv_mount_location,
+            key,
+        };
+        let vault_client = self.vault_client().await?;
+        get_credentials_helper
+            .execute(vault_client, &self.vault_metrics)
+            .await
+    }
+
+    async fn set_credentials_by_key(
+        &self,
+        key: &str,
+        credentials: &Credentials,
+    ) -> Result<(), SecretsError> {
+        let set_credentials_helper = SetCredentialsHelper {
+            key,
+            credentials,
+            kv_mount_location: &self.vault_client_config.kv_mount_location,
+            allow_overwrite: true,
+        };
+        let vault_client = self.vault_client().await?;
+        set_credentials_helper
+            .execute(vault_client, &self.vault_metrics)
+            .await
+    }
+}
+
 struct GetCertificateHelper {
     /// Used to form URI-type SANs for this certificate
     unique_identifier: String,
@@ -598,25 +682,12 @@ impl VaultTask<Certificate> for GetCertificateHelper {
             .vault_requests_total_counter
             .add(1, &[KeyValue::new("request_type", "get_certificate")]);
 
-        let trust_domain = "forge.local";
-        let namespace = "forge-system";
+        let spiffe_id = machine_spiffe_id(&self.unique_identifier);
 
-        // spiffe://<trust_domain>/<namespace>/machine/<stable_machine_id>
-        let spiffe_id = format!(
-            "spiffe://{}/{}/machine/{}",
-            trust_domain, namespace, self.unique_identifier,
-        );
-
-        let ttl = if let Some(ttl) = self.ttl.clone() {
-            ttl
-        } else {
-            // this is to setup a baseline skew of between 60 - 100% of 30 days,
-            // so that not all boxes will renew (or expire) at the same time.
-            let max_hours = 720; // 24 * 30
-            let min_hours = 432; // 24 * 30 * 0.6
-            let mut rng = rand::rng();
-            format!("{}h", rng.random_range(min_hours..max_hours))
-        };
+        let ttl = self
+            .ttl
+            .clone()
+            .unwrap_or_else(|| format!("{}h", default_ttl_hours()));
 
         let mut certificate_request_builder = GenerateCertificateRequest::builder();
         certificate_request_builder
@@ -0,0 +1,313 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Envelope encryption for secrets stored outside of Vault.
+//!
+//! Every secret is encrypted with its own randomly generated data encryption key (DEK). The DEK
+//! is in turn encrypted ("wrapped") with the key encryption key (KEK), which never touches the
+//! storage. Both layers use AES-256-GCM and authenticate the name of the secret as additional
+//! data, so that encrypted secrets can't be swapped between names.
+
+use std::path::Path;
+
+use base64::Engine;
+use base64::prelude::BASE64_STANDARD;
+use eyre::{WrapErr, eyre};
+use ring::aead::{AES_256_GCM, Aad, LessSafeKey, NONCE_LEN, Nonce, UnboundKey};
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+
+use crate::SecretsError;
+
+const KEY_LEN: usize = 32;
+
+/// A secret encrypted with a data encryption key, together with the wrapped key
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EncryptedSecret {
+    /// Base64 of the nonce followed by the DEK encrypted with the KEK
+    pub wrapped_key: String,
+    /// Base64 of the nonce followed by the secret encrypted with the DEK
+    pub ciphertext: String,
+}
+
+/// The key which wraps the data encryption keys of all secrets of a store
+pub struct KeyEncryptionKey {
+    key: LessSafeKey,
+    rng: SystemRandom,
+}
+
+impl std::fmt::Debug for KeyEncryptionKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KeyEncryptionKey").finish_non_exhaustive()
+    }
+}
+
+impl KeyEncryptionKey {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SecretsError> {
+        Ok(Self {
+            key: aes_key(bytes)?,
+            rng: SystemRandom::new(),
+        })
+    }
+
+    /// Loads the KEK from a file, which either holds the 32 raw key bytes or their base64
+    /// encoding.
+    pub fn from_file(path: &Path) -> Result<Self, SecretsError> {
+        let contents = std::fs::read(path)
+            .wrap_err_with(|| format!("Failed to read key encryption key {}", path.display()))?;
+        if contents.len() == KEY_LEN {
+            return Self::from_bytes(&contents);
+        }
+
+        let decoded = BASE64_STANDARD
+            .decode(contents.trim_ascii())
+            .wrap_err_with(|| {
+                format!(
+                    "Key encryption key {} is neither {KEY_LEN} raw bytes nor base64",
+                    path.display()
+                )
+            })?;
+        Self::from_bytes(&decoded)
+    }
+
+    /// Generates a new random KEK and returns its raw bytes, e.g. for writing it to a file
+    pub fn generate() -> Result<Vec<u8>, SecretsError> {
+        let mut bytes = vec![0; KEY_LEN];
+        SystemRandom::new()
+            .fill(&mut bytes)
+            .map_err(|_| eyre!("Failed to generate random key"))?;
+        Ok(bytes)
+    }
+
+    /// Encrypts a secret with a new DEK, which is wrapped with this KEK
+    pub fn encrypt(&self, name: &str, plaintext: &[u8]) -> Result<EncryptedSecret, SecretsError> {
+        let mut dek = vec![0; KEY_LEN];
+        self.rng
+            .fill(&mut dek)
+            .map_err(|_| eyre!("Failed to generate data encryption key"))?;
+
+        let ciphertext = seal(&aes_key(&dek)?, &self.rng, name, plaintext)?;
+        let wrapped_key = seal(&self.key, &self.rng, name, &dek)?;
+
+        Ok(EncryptedSecret {
+            wrapped_key: BASE64_STANDARD.encode(wrapped_key),
+            ciphertext: BASE64_STANDARD.encode(ciphertext),
+        })
+    }
+
+    /// Decrypts a secret which was encrypted under the given name
+    pub fn decrypt(&self, name: &str, secret: &EncryptedSecret) -> Result<Vec<u8>, SecretsError> {
+        let wrapped_key = BASE64_STANDARD
+            .decode(&secret.wrapped_key)
+            .wrap_err("Invalid base64 in wrapped key")?;
+        let ciphertext = BASE64_STANDARD
+            .decode(&secret.ciphertext)
+            .wrap_err("Invalid base64 in ciphertext")?;
+
+        let dek = open(&self.key, name, wrapped_key)
+            .wrap_err_with(|| format!("Failed to unwrap the key of secret {name}"))?;
+        Ok(open(&aes_key(&dek)?, name, ciphertext)
+            .wrap_err_with(|| format!("Failed to decrypt secret {name}"))?)
+    }
+
+    /// Unseals a KEK which was sealed to the TPM of this host.
+    ///
+    /// The file holds the JSON serialized [`TpmSealedKey`] of a sealed data object which was
+    /// created below the default RSA storage primary key of the owner hierarchy, e.g. with
+    /// `tpm2_createprimary -C o` and `tpm2_create -i kek.bin`. The object must not require
+    /// authorization beyond an empty password.
+    #[cfg(feature = "tpm")]
+    pub fn from_tpm_sealed_file(path: &Path, tcti: &str) -> Result<Self, SecretsError> {
+        let contents = std::fs::read(path)
+            .wrap_err_with(|| format!("Failed to read sealed key {}", path.display()))?;
+        let sealed: TpmSealedKey = serde_json::from_slice(&contents)
+            .wrap_err_with(|| format!("Invalid sealed key {}", path.display()))?;
+        Self::from_bytes(&tpm::unseal(&sealed, tcti)?)
+    }
+}
+
+/// A KEK sealed to a TPM
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TpmSealedKey {
+    /// Base64 of the marshalled TPMT_PUBLIC area of the sealed object
+    pub public: String,
+    /// Base64 of the TPM2B_PRIVATE buffer of the sealed object, without its size prefix
+    pub private: String,
+}
+
+fn aes_key(bytes: &[u8]) -> Result<LessSafeKey, SecretsError> {
+    if bytes.len() != KEY_LEN {
+        return Err(eyre!("Key must be {KEY_LEN} bytes, but is {} bytes", bytes.len()).into());
+    }
+    let key = UnboundKey::new(&AES_256_GCM, bytes).map_err(|_| eyre!("Invalid AES key"))?;
+    Ok(LessSafeKey::new(key))
+}
+
+/// Encrypts `plaintext` and returns the random nonce followed by the ciphertext and tag
+fn seal(
+    key: &LessSafeKey,
+    rng: &SystemRandom,
+    name: &str,
+    plaintext: &[u8],
+) -> Result<Vec<u8>, SecretsError> {
+    let mut nonce = [0; NONCE_LEN];
+    rng.fill(&mut nonce)
+        .map_err(|_| eyre!("Failed to generate nonce"))?;
+
+    let mut in_out = plaintext.to_vec();
+    key.seal_in_place_append_tag(
+        Nonce::assume_unique_for_key(nonce),
+        Aad::from(name.as_bytes()),
+        &mut in_out,
+    )
+    .map_err(|_| eyre!("Encryption failed"))?;
+
+    let mut sealed = nonce.to_vec();
+    sealed.extend_from_slice(&in_out);
+    Ok(sealed)
+}
+
+fn open(key: &LessSafeKey, name: &str, mut sealed: Vec<u8>) -> Result<Vec<u8>, eyre::Report> {
+    if sealed.len() < NONCE_LEN {
+        return Err(eyre!("Encrypted data is too short"));
+    }
+    let mut in_out = sealed.split_off(NONCE_LEN);
+    let nonce = Nonce::try_assume_unique_for_key(&sealed).map_err(|_| eyre!("Invalid nonce"))?;
+    let plaintext = key
+        .open_in_place(nonce, Aad::from(name.as_bytes()), &mut in_out)
+        .map_err(|_| eyre!("Authentication failed"))?;
+    Ok(plaintext.to_vec())
+}
+
+#[cfg(feature = "tpm")]
+mod tpm {
+    use std::str::FromStr;
+
+    use base64::Engine;
+    use base64::prelude::BASE64_STANDARD;
+    use eyre::WrapErr;
+    use tss_esapi::interface_types::key_bits::RsaKeyBits;
+    use tss_esapi::interface_types::resource_handles::Hierarchy;
+    use tss_esapi::structures::{Private, Public, RsaExponent, SymmetricDefinitionObject};
+    use tss_esapi::traits::UnMarshall;
+    use tss_esapi::utils::create_restricted_decryption_rsa_public;
+    use tss_esapi::{Context, TctiNameConf};
+
+    use super::TpmSealedKey;
+    use crate::SecretsError;
+
+    pub(super) fn unseal(sealed: &TpmSealedKey, tcti: &str) -> Result<Vec<u8>, SecretsError> {
+        let public = BASE64_STANDARD
+            .decode(&sealed.public)
+            .wrap_err("Invalid base64 in sealed public area")?;
+        let private = BASE64_STANDARD
+            .decode(&sealed.private)
+            .wrap_err("Invalid base64 in sealed private area")?;
+        let public = Public::unmarshall(&public).wrap_err("Invalid sealed public area")?;
+        let private = Private::try_from(private).wrap_err("Invalid sealed private area")?;
+
+        let mut context = Context::new(TctiNameConf::from_str(tcti).wrap_err("Invalid TCTI")?)
+            .wrap_err("Failed to open TPM")?;
+        // The same template which tpm2_createprimary uses by default
+        let primary_template = create_restricted_decryption_rsa_public(
+            SymmetricDefinitionObject::AES_128_CFB,
+            RsaKeyBits::Rsa2048,
+            RsaExponent::default(),
+        )
+        .wrap_err("Failed to create primary key template")?;
+
+        let kek = context
+            .execute_with_nullauth_session(|context| {
+                let primary = context.create_primary(
+                    Hierarchy::Owner,
+                    primary_template,
+                    None,
+                    None,
+                    None,
+                    None,
+                )?;
+                let sealed = context.load(primary.key_handle, private, public)?;
+                let kek = context.unseal(sealed.into());
+                context.flush_context(sealed.into())?;
+                context.flush_context(primary.key_handle.into())?;
+                kek
+            })
+            .wrap_err("Failed to unseal key encryption key")?;
+
+        Ok(kek.value().to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypt_decrypt() {
+        let kek = KeyEncryptionKey::from_bytes(&KeyEncryptionKey::generate().unwrap()).unwrap();
+        let secret = kek.encrypt("machines/bmc/site/root", b"hunter2").unwrap();
+        assert_eq!(
+            kek.decrypt("machines/bmc/site/root", &secret).unwrap(),
+            b"hunter2"
+        );
+
+        // Every encryption uses a new DEK and nonces
+        assert_ne!(
+            kek.encrypt("machines/bmc/site/root", b"hunter2").unwrap(),
+            secret
+        );
+
+        // The secret is bound to its name
+        assert!(kek.decrypt("machines/bmc/other/root", &secret).is_err());
+
+        // And to the KEK
+        let other_kek =
+            KeyEncryptionKey::from_bytes(&KeyEncryptionKey::generate().unwrap()).unwrap();
+        assert!(
+            other_kek
+                .decrypt("machines/bmc/site/root", &secret)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_from_file() {
+        let key = KeyEncryptionKey::generate().unwrap();
+        let dir = tempfile::tempdir().unwrap();
+
+        let raw_path = dir.path().join("kek.bin");
+        std::fs::write(&raw_path, &key).unwrap();
+        let base64_path = dir.path().join("kek.b64");
+        std::fs::write(&base64_path, format!("{}\n", BASE64_STANDARD.encode(&key))).unwrap();
+
+        let secret = KeyEncryptionKey::from_file(&raw_path)
+            .unwrap()
+            .encrypt("name", b"value")
+            .unwrap();
+        assert_eq!(
+            KeyEncryptionKey::from_file(&base64_path)
+                .unwrap()
+                .decrypt("name", &secret)
+                .unwrap(),
+            b"value"
+        );
+
+        let short_path = dir.path().join("short");
+        std::fs::write(&short_path, BASE64_STANDARD.encode(b"too short")).unwrap();
+        assert!(KeyEncryptionKey::from_file(&short_path).is_err());
+    }
+}
@@ -19,8 +19,13 @@ use std::fmt::Display;
 pub use crate::forge_vault::ForgeVaultClient;
 
 pub mod certificates;
+pub mod config;
 pub mod credentials;
+pub mod file_credentials;
 pub mod forge_vault;
+pub mod kek;
+pub mod local_ca;
+pub mod migration;
 
 #[derive(Debug)]
 pub enum SecretsError {
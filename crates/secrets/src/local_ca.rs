@@ -0,0 +1,290 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! A [`CertificateProvider`] which issues machine certificates from a CA whose key is kept on
+//! local disk, for sites which can't run the Vault PKI engine.
+//!
+//! Issued certificates look like the ones of the Vault PKI role: they carry the SPIFFE ID of the
+//! machine as URI SAN, the requested DNS names as DNS SANs, and can be used for TLS client and
+//! server authentication.
+
+use std::path::Path;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use eyre::{WrapErr, eyre};
+use rcgen::{
+    BasicConstraints, CertificateParams, DistinguishedName, DnType, ExtendedKeyUsagePurpose, IsCa,
+    KeyPair, KeyUsagePurpose, SanType,
+};
+use time::OffsetDateTime;
+
+use crate::SecretsError;
+use crate::certificates::{Certificate, CertificateProvider, default_ttl_hours, machine_spiffe_id};
+
+/// How far the validity of issued certificates reaches into the past, to tolerate clock skew
+const BACKDATE: Duration = Duration::from_secs(5 * 60);
+
+/// Validity of CA certificates created by [`LocalCertificateAuthority::load_or_create`]
+const CA_VALIDITY: Duration = Duration::from_secs(10 * 365 * 24 * 60 * 60);
+
+pub struct LocalCertificateAuthority {
+    ca_cert_pem: String,
+    ca_cert: rcgen::Certificate,
+    ca_key: KeyPair,
+}
+
+impl LocalCertificateAuthority {
+    /// Loads the CA from its PEM encoded certificate and private key
+    pub fn load(cert_path: &Path, key_path: &Path) -> Result<Self, SecretsError> {
+        let ca_cert_pem = std::fs::read_to_string(cert_path)
+            .wrap_err_with(|| format!("Failed to read CA certificate {}", cert_path.display()))?;
+        let ca_key_pem = std::fs::read_to_string(key_path)
+            .wrap_err_with(|| format!("Failed to read CA key {}", key_path.display()))?;
+        Self::from_pem(ca_cert_pem, &ca_key_pem)
+    }
+
+    /// Loads the CA, or creates a new self-signed CA if neither the certificate nor the key
+    /// exist yet
+    pub fn load_or_create(
+        cert_path: &Path,
+        key_path: &Path,
+        common_name: &str,
+    ) -> Result<Self, SecretsError> {
+        if cert_path.exists() || key_path.exists() {
+            return Self::load(cert_path, key_path);
+        }
+
+        let (ca_cert_pem, ca_key_pem) = Self::create_ca(common_name)?;
+        write_private(key_path, &ca_key_pem)?;
+        std::fs::write(cert_path, &ca_cert_pem)
+            .wrap_err_with(|| format!("Failed to write CA certificate {}", cert_path.display()))?;
+        tracing::info!(
+            cert_path = %cert_path.display(),
+            "Created new local certificate authority"
+        );
+
+        Self::from_pem(ca_cert_pem, &ca_key_pem)
+    }
+
+    pub fn from_pem(ca_cert_pem: String, ca_key_pem: &str) -> Result<Self, SecretsError> {
+        let ca_key = KeyPair::from_pem(ca_key_pem).wrap_err("Invalid CA key")?;
+        let ca_params =
+            CertificateParams::from_ca_cert_pem(&ca_cert_pem).wrap_err("Invalid CA certificate")?;
+        // Re-signing the parameters of the CA certificate yields an issuer with the same subject
+        // and key, which is all that is needed for signing
+        let ca_cert = ca_params
+            .self_signed(&ca_key)
+            .wrap_err("CA key does not match the CA certificate")?;
+
+        Ok(Self {
+            ca_cert_pem,
+            ca_cert,
+            ca_key,
+        })
+    }
+
+    /// Creates a new self-signed CA, returning the PEM encoded certificate and private key
+    pub fn create_ca(common_name: &str) -> Result<(String, String), SecretsError> {
+        let mut params = CertificateParams::default();
+        params.distinguished_name = DistinguishedName::new();
+        params
+            .distinguished_name
+            .push(DnType::CommonName, common_name);
+        params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        params.key_usages = vec![
+            KeyUsagePurpose::KeyCertSign,
+            KeyUsagePurpose::CrlSign,
+            KeyUsagePurpose::DigitalSignature,
+        ];
+        let now = OffsetDateTime::now_utc();
+        params.not_before = now - BACKDATE;
+        params.not_after = now + CA_VALIDITY;
+
+        let key = KeyPair::generate().wrap_err("Failed to generate CA key")?;
+        let cert = params
+            .self_signed(&key)
+            .wrap_err("Failed to create CA certificate")?;
+        Ok((cert.pem(), key.serialize_pem()))
+    }
+
+    fn issue(
+        &self,
+        unique_identifier: &str,
+        alt_names: Option<String>,
+        ttl: Option<String>,
+    ) -> Result<Certificate, SecretsError> {
+        let ttl = match ttl {
+            Some(ttl) => parse_ttl(&ttl)?,
+            None => Duration::from_secs(default_ttl_hours() * 60 * 60),
+        };
+
+        // Like Vault, alt_names is a comma separated list of DNS names
+        let dns_names: Vec<String> = alt_names
+            .iter()
+            .flat_map(|names| names.split(','))
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(str::to_string)
+            .collect();
+
+        let mut params = CertificateParams::new(dns_names).wrap_err("Invalid alternative names")?;
+        params.subject_alt_names.push(SanType::URI(
+            machine_spiffe_id(unique_identifier)
+                .try_into()
+                .wrap_err("Invalid SPIFFE ID")?,
+        ));
+        params.distinguished_name = DistinguishedName::new();
+        params
+            .distinguished_name
+            .push(DnType::CommonName, unique_identifier);
+        params.key_usages = vec![
+            KeyUsagePurpose::DigitalSignature,
+            KeyUsagePurpose::KeyEncipherment,
+        ];
+        params.extended_key_usages = vec![
+            ExtendedKeyUsagePurpose::ServerAuth,
+            ExtendedKeyUsagePurpose::ClientAuth,
+        ];
+        params.use_authority_key_identifier_extension = true;
+        let now = OffsetDateTime::now_utc();
+        params.not_before = now - BACKDATE;
+        params.not_after = now + ttl;
+
+        let key = KeyPair::generate().wrap_err("Failed to generate key")?;
+        let cert = params
+            .signed_by(&key, &self.ca_cert, &self.ca_key)
+            .wrap_err("Failed to sign certificate")?;
+
+        Ok(Certificate {
+            issuing_ca: self.ca_cert_pem.clone().into_bytes(),
+            public_key: cert.pem().into_bytes(),
+            private_key: key.serialize_pem().into_bytes(),
+        })
+    }
+}
+
+#[async_trait]
+impl CertificateProvider for LocalCertificateAuthority {
+    async fn get_certificate(
+        &self,
+        unique_identifier: &str,
+        alt_names: Option<String>,
+        ttl: Option<String>,
+    ) -> Result<Certificate, SecretsError> {
+        self.issue(unique_identifier, alt_names, ttl)
+    }
+}
+
+/// Parses a TTL in the subset of the Vault duration format which is used with certificates:
+/// a number of seconds, optionally followed by one of the units `s`, `m`, `h` or `d`.
+fn parse_ttl(ttl: &str) -> Result<Duration, SecretsError> {
+    let ttl = ttl.trim();
+    let (value, unit_secs) = match ttl.char_indices().last() {
+        Some((i, 's')) => (&ttl[..i], 1),
+        Some((i, 'm')) => (&ttl[..i], 60),
+        Some((i, 'h')) => (&ttl[..i], 60 * 60),
+        Some((i, 'd')) => (&ttl[..i], 24 * 60 * 60),
+        _ => (ttl, 1),
+    };
+    let value: u64 = value
+        .parse()
+        .map_err(|_| eyre!("Invalid certificate TTL: {ttl}"))?;
+    Ok(Duration::from_secs(value * unit_secs))
+}
+
+fn write_private(path: &Path, contents: &str) -> Result<(), SecretsError> {
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut file = options
+        .open(path)
+        .wrap_err_with(|| format!("Failed to create {}", path.display()))?;
+    std::io::Write::write_all(&mut file, contents.as_bytes())
+        .wrap_err_with(|| format!("Failed to write {}", path.display()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use x509_parser::extensions::GeneralName;
+    use x509_parser::pem::parse_x509_pem;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_ttl() {
+        assert_eq!(parse_ttl("90").unwrap(), Duration::from_secs(90));
+        assert_eq!(parse_ttl("90s").unwrap(), Duration::from_secs(90));
+        assert_eq!(parse_ttl("15m").unwrap(), Duration::from_secs(15 * 60));
+        assert_eq!(parse_ttl("720h").unwrap(), Duration::from_secs(720 * 3600));
+        assert_eq!(parse_ttl("2d").unwrap(), Duration::from_secs(2 * 86400));
+        assert!(parse_ttl("").is_err());
+        assert!(parse_ttl("1w").is_err());
+        assert!(parse_ttl("h").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_issue_certificate() {
+        let dir = tempfile::tempdir().unwrap();
+        let cert_path = dir.path().join("ca.crt");
+        let key_path = dir.path().join("ca.key");
+
+        LocalCertificateAuthority::load_or_create(&cert_path, &key_path, "test-ca").unwrap();
+        let ca_pem = std::fs::read_to_string(&cert_path).unwrap();
+        // The second call loads the CA created by the first
+        let ca =
+            LocalCertificateAuthority::load_or_create(&cert_path, &key_path, "other-ca").unwrap();
+
+        let certificate = ca
+            .get_certificate(
+                "fm100htest",
+                Some("host1.example.com, host2.example.com".to_string()),
+                Some("24h".to_string()),
+            )
+            .await
+            .unwrap();
+        assert_eq!(certificate.issuing_ca, ca_pem.as_bytes());
+        assert!(
+            String::from_utf8(certificate.private_key)
+                .unwrap()
+                .contains("PRIVATE KEY")
+        );
+
+        let (_, ca_pem) = parse_x509_pem(ca_pem.as_bytes()).unwrap();
+        let ca_cert = ca_pem.parse_x509().unwrap();
+        let (_, pem) = parse_x509_pem(&certificate.public_key).unwrap();
+        let cert = pem.parse_x509().unwrap();
+
+        assert_eq!(cert.issuer(), ca_cert.subject());
+        let validity = cert.validity().time_to_expiration().unwrap();
+        assert!(validity.whole_hours() <= 24 && validity.whole_hours() >= 23);
+
+        let sans = &cert
+            .subject_alternative_name()
+            .unwrap()
+            .unwrap()
+            .value
+            .general_names;
+        assert!(sans.contains(&GeneralName::DNSName("host1.example.com")));
+        assert!(sans.contains(&GeneralName::DNSName("host2.example.com")));
+        assert!(sans.contains(&GeneralName::URI(
+            "spiffe://forge.local/forge-system/machine/fm100htest"
+        )));
+    }
+}
@@ -0,0 +1,94 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Copying credentials between credential providers, e.g. when moving a site from Vault to the
+//! file credential provider.
+
+use async_trait::async_trait;
+
+use crate::SecretsError;
+use crate::credentials::Credentials;
+
+/// Raw access to the credentials of a provider by their key strings.
+///
+/// [`crate::credentials::CredentialKey`] can only be turned into key strings, not back, so
+/// enumerating the contents of a provider has to work on the key strings.
+#[async_trait]
+pub trait CredentialStore: Send + Sync {
+    /// Lists the keys of all stored credentials
+    async fn list_credential_keys(&self) -> Result<Vec<String>, SecretsError>;
+
+    async fn get_credentials_by_key(&self, key: &str) -> Result<Option<Credentials>, SecretsError>;
+
+    async fn set_credentials_by_key(
+        &self,
+        key: &str,
+        credentials: &Credentials,
+    ) -> Result<(), SecretsError>;
+}
+
+/// The outcome of [`copy_credentials`]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CopyReport {
+    /// Keys which were copied (or would have been, in a dry run)
+    pub copied: Vec<String>,
+    /// Keys which already existed in the target and were left alone
+    pub skipped_existing: Vec<String>,
+    /// Keys which exist in the source but don't hold credentials
+    pub skipped_unreadable: Vec<String>,
+}
+
+/// Copies all credentials from `source` to `target`.
+///
+/// Credentials which already exist in the target are only replaced with `overwrite`. With
+/// `dry_run`, nothing is written and the report shows what would have been copied.
+pub async fn copy_credentials(
+    source: &dyn CredentialStore,
+    target: &dyn CredentialStore,
+    overwrite: bool,
+    dry_run: bool,
+) -> Result<CopyReport, SecretsError> {
+    let mut report = CopyReport::default();
+
+    for key in source.list_credential_keys().await? {
+        // Vault holds a few entries which aren't credentials (like the token refresh marker)
+        let credentials = match source.get_credentials_by_key(&key).await {
+            Ok(Some(credentials)) => credentials,
+            Ok(None) => {
+                report.skipped_unreadable.push(key);
+                continue;
+            }
+            Err(error) => {
+                tracing::warn!(key, %error, "Skipping entry which doesn't hold credentials");
+                report.skipped_unreadable.push(key);
+                continue;
+            }
+        };
+
+        if !overwrite && target.get_credentials_by_key(&key).await?.is_some() {
+            report.skipped_existing.push(key);
+            continue;
+        }
+
+        if !dry_run {
+            target.set_credentials_by_key(&key, &credentials).await?;
+        }
+        report.copied.push(key);
+    }
+
+    Ok(report)
+}
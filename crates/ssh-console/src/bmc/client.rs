@@ -35,6 +35,7 @@ use crate::bmc::connection::{self, AtomicConnectionState, ConnectionDetails};
 use crate::bmc::message_proxy::{
     ConnectionChangeMessage, ExecReply, ToBmcMessage, ToFrontendMessage,
 };
+use crate::bmc::write_lock::WriteLock;
 use crate::config::Config;
 use crate::console_logger;
 use crate::shutdown_handle::ShutdownHandle;
@@ -60,6 +61,7 @@ pub fn spawn(
     dev_null(broadcast_to_frontend_rx);
 
     let connection_state = Arc::new(AtomicConnectionState::default());
+    let write_lock = Arc::new(WriteLock::new(broadcast_to_frontend_tx.downgrade()));
     let machine_id = connection_details.machine_id();
     let kind = connection_details.kind();
 
@@ -89,6 +91,7 @@ pub fn spawn(
         join_handle,
         connection_state,
         kind,
+        write_lock,
     }
 }
 
@@ -473,6 +476,8 @@ pub struct ClientHandle {
     shutdown_tx: oneshot::Sender<()>,
     join_handle: JoinHandle<()>,
     pub connection_state: Arc<AtomicConnectionState>, // pub for metrics gathering
+    // Shared by all frontends of this BMC
+    write_lock: Arc<WriteLock>,
 }
 
 impl ShutdownHandle<()> for ClientHandle {
@@ -496,6 +501,7 @@ impl ClientHandle {
             to_bmc_msg_tx: self.to_bmc_msg_tx.clone(),
             metrics,
             kind: self.kind,
+            write_lock: self.write_lock.clone(),
         }
     }
 }
@@ -507,6 +513,8 @@ pub struct BmcConnectionSubscription {
     pub to_frontend_msg_weak_tx: broadcast::WeakSender<ToFrontendMessage>,
     pub to_bmc_msg_tx: mpsc::Sender<ToBmcMessage>,
     pub kind: connection::Kind,
+    /// Which frontend may write to the console
+    pub write_lock: Arc<WriteLock>,
     // Not pub, to make sure we go through ClientHandle::subscribe() to build, so we get the
     // right metrics
     metrics: Arc<ServerMetrics>,
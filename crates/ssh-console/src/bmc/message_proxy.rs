@@ -24,6 +24,7 @@ use tokio::sync::oneshot::Sender;
 use tokio::sync::{broadcast, oneshot};
 use tokio::task::JoinHandle;
 
+use crate::bmc::write_lock::WriteLockChange;
 use crate::shutdown_handle::ShutdownHandle;
 
 /// Proxy messages from the BMC to the user's connection.
@@ -90,6 +91,8 @@ pub enum ToFrontendMessage {
     ConnectionChanged(ConnectionChangeMessage),
     /// A reply to the user pressing the Enter key when the BMC is disconnected
     InformDisconnectedSince(Option<DateTime<Utc>>),
+    /// An alert that a frontend acquired, took over or released the console write lock
+    WriteLockChanged(WriteLockChange),
 }
 
 #[derive(Clone)]
@@ -117,6 +120,10 @@ impl From<ToFrontendMessage> for Arc<ChannelMsg> {
                 let data: CryptoVec = b"--- Console not connected ---\r\n".to_vec().into();
                 Arc::new(ChannelMsg::Data { data })
             }
+            ToFrontendMessage::WriteLockChanged(change) => {
+                let data: CryptoVec = format!("\r\n--- {change} ---\r\n").into_bytes().into();
+                Arc::new(ChannelMsg::Data { data })
+            }
             ToFrontendMessage::Channel(msg) => msg,
         }
     }
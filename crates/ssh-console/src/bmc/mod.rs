@@ -22,3 +22,4 @@ mod connection_impl;
 pub mod message_proxy;
 mod pending_output_line;
 pub mod vendor;
+pub mod write_lock;
@@ -0,0 +1,268 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Exclusive write access to a BMC console.
+//!
+//! Any number of frontends can watch a BMC console, but only the frontend session holding the
+//! write lock can type into it. Every change of the lock is broadcast to all frontends (and the
+//! console log) so that everybody can see who holds it.
+
+use std::fmt;
+use std::sync::Mutex;
+
+use chrono::{DateTime, Utc};
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+use crate::bmc::message_proxy::ToFrontendMessage;
+
+/// The frontend session currently holding the write lock of a console
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LockHolder {
+    pub session_id: Uuid,
+    /// Who opened the session, for display to other frontends
+    pub user: String,
+    pub peer_addr: String,
+    pub since: DateTime<Utc>,
+}
+
+impl LockHolder {
+    pub fn new(session_id: Uuid, user: String, peer_addr: String) -> Self {
+        Self {
+            session_id,
+            user,
+            peer_addr,
+            since: Utc::now(),
+        }
+    }
+}
+
+impl fmt::Display for LockHolder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} ({}) since {}",
+            self.user,
+            self.peer_addr,
+            self.since.to_rfc2822()
+        )
+    }
+}
+
+/// A change of the write lock, broadcast to all frontends of a console
+#[derive(Debug, Clone)]
+pub enum WriteLockChange {
+    Acquired {
+        holder: LockHolder,
+    },
+    TakenOver {
+        holder: LockHolder,
+        previous: LockHolder,
+    },
+    Released {
+        previous: LockHolder,
+    },
+}
+
+impl fmt::Display for WriteLockChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WriteLockChange::Acquired { holder } => {
+                write!(f, "Console write lock acquired by {}", holder.user)
+            }
+            WriteLockChange::TakenOver { holder, previous } => write!(
+                f,
+                "Console write lock taken over by {} from {}",
+                holder.user, previous.user
+            ),
+            WriteLockChange::Released { previous } => {
+                write!(f, "Console write lock released by {}", previous.user)
+            }
+        }
+    }
+}
+
+/// The write lock of a single BMC console, shared by all of its frontends.
+pub struct WriteLock {
+    holder: Mutex<Option<LockHolder>>,
+    // Weak, so that holding on to the lock doesn't keep the broadcast channel open after the BMC
+    // client shuts down.
+    to_frontend_weak_tx: broadcast::WeakSender<ToFrontendMessage>,
+}
+
+impl WriteLock {
+    pub fn new(to_frontend_weak_tx: broadcast::WeakSender<ToFrontendMessage>) -> Self {
+        Self {
+            holder: Mutex::new(None),
+            to_frontend_weak_tx,
+        }
+    }
+
+    /// The session currently holding the lock, if any
+    pub fn holder(&self) -> Option<LockHolder> {
+        self.holder.lock().expect("lock poisoned").clone()
+    }
+
+    pub fn is_held_by(&self, session_id: Uuid) -> bool {
+        self.holder
+            .lock()
+            .expect("lock poisoned")
+            .as_ref()
+            .is_some_and(|holder| holder.session_id == session_id)
+    }
+
+    /// Acquire the lock if nobody holds it. Succeeds if the session already holds the lock,
+    /// otherwise returns the current holder.
+    pub fn try_acquire(&self, candidate: LockHolder) -> Result<(), LockHolder> {
+        let change = {
+            let mut holder = self.holder.lock().expect("lock poisoned");
+            match holder.as_ref() {
+                Some(current) if current.session_id == candidate.session_id => return Ok(()),
+                Some(current) => return Err(current.clone()),
+                None => {
+                    *holder = Some(candidate.clone());
+                    WriteLockChange::Acquired { holder: candidate }
+                }
+            }
+        };
+        self.notify(change);
+        Ok(())
+    }
+
+    /// Take the lock regardless of who holds it, returning the previous holder.
+    pub fn take_over(&self, candidate: LockHolder) -> Option<LockHolder> {
+        let previous = self
+            .holder
+            .lock()
+            .expect("lock poisoned")
+            .replace(candidate.clone());
+        match previous.clone() {
+            Some(previous) if previous.session_id != candidate.session_id => {
+                self.notify(WriteLockChange::TakenOver {
+                    holder: candidate,
+                    previous,
+                });
+            }
+            Some(_) => {}
+            None => self.notify(WriteLockChange::Acquired { holder: candidate }),
+        }
+        previous
+    }
+
+    /// Release the lock if it is held by the given session. Returns whether it was released.
+    pub fn release(&self, session_id: Uuid) -> bool {
+        let previous = {
+            let mut holder = self.holder.lock().expect("lock poisoned");
+            if !holder
+                .as_ref()
+                .is_some_and(|holder| holder.session_id == session_id)
+            {
+                return false;
+            }
+            holder.take()
+        };
+        if let Some(previous) = previous {
+            self.notify(WriteLockChange::Released { previous });
+        }
+        true
+    }
+
+    fn notify(&self, change: WriteLockChange) {
+        tracing::info!("{change}");
+        if let Some(tx) = self.to_frontend_weak_tx.upgrade() {
+            tx.send(ToFrontendMessage::WriteLockChanged(change)).ok();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn holder(user: &str) -> LockHolder {
+        LockHolder::new(
+            Uuid::new_v4(),
+            user.to_string(),
+            "127.0.0.1:1234".to_string(),
+        )
+    }
+
+    // The sender is returned so that it stays alive for the duration of the test
+    fn lock() -> (
+        WriteLock,
+        broadcast::Sender<ToFrontendMessage>,
+        broadcast::Receiver<ToFrontendMessage>,
+    ) {
+        let (tx, rx) = broadcast::channel(16);
+        (WriteLock::new(tx.downgrade()), tx, rx)
+    }
+
+    fn next_change(rx: &mut broadcast::Receiver<ToFrontendMessage>) -> String {
+        match rx.try_recv() {
+            Ok(ToFrontendMessage::WriteLockChanged(change)) => change.to_string(),
+            Ok(_) => panic!("unexpected message"),
+            Err(error) => panic!("no lock change: {error}"),
+        }
+    }
+
+    #[test]
+    fn test_acquire_and_release() {
+        let (lock, _tx, mut rx) = lock();
+        let alice = holder("alice");
+        let bob = holder("bob");
+
+        assert_eq!(lock.try_acquire(alice.clone()), Ok(()));
+        assert_eq!(next_change(&mut rx), "Console write lock acquired by alice");
+        // Acquiring again is a no-op
+        assert_eq!(lock.try_acquire(alice.clone()), Ok(()));
+        assert!(rx.try_recv().is_err());
+
+        assert_eq!(lock.try_acquire(bob.clone()), Err(alice.clone()));
+        assert!(lock.is_held_by(alice.session_id));
+        assert!(!lock.is_held_by(bob.session_id));
+
+        // Only the holder can release
+        assert!(!lock.release(bob.session_id));
+        assert!(lock.release(alice.session_id));
+        assert_eq!(next_change(&mut rx), "Console write lock released by alice");
+        assert_eq!(lock.holder(), None);
+
+        assert_eq!(lock.try_acquire(bob.clone()), Ok(()));
+        assert!(lock.is_held_by(bob.session_id));
+    }
+
+    #[test]
+    fn test_take_over() {
+        let (lock, _tx, mut rx) = lock();
+        let alice = holder("alice");
+        let bob = holder("bob");
+
+        assert_eq!(lock.take_over(alice.clone()), None);
+        assert_eq!(next_change(&mut rx), "Console write lock acquired by alice");
+
+        assert_eq!(lock.take_over(bob.clone()), Some(alice.clone()));
+        assert_eq!(
+            next_change(&mut rx),
+            "Console write lock taken over by bob from alice"
+        );
+        assert!(lock.is_held_by(bob.session_id));
+
+        // The previous holder can no longer release the lock
+        assert!(!lock.release(alice.session_id));
+        assert!(lock.is_held_by(bob.session_id));
+    }
+}
@@ -49,6 +49,8 @@ pub struct Config {
     pub carbide_uri: http::Uri,
     #[serde(default)]
     pub authorized_keys_path: Option<PathBuf>,
+    #[serde(default)]
+    pub authorized_keys_takeover: bool,
     #[serde(default, rename = "bmcs")]
     pub override_bmcs: Option<Vec<BmcConfig>>,
     #[serde(default = "Defaults::host_key_path", rename = "host_key")]
@@ -79,6 +81,10 @@ pub struct Config {
     pub openssh_certificate_ca_fingerprints: Vec<Fingerprint>,
     #[serde(default)]
     pub admin_certificate_role: Option<String>,
+    #[serde(default)]
+    pub observer_certificate_role: Option<String>,
+    #[serde(default)]
+    pub takeover_certificate_role: Option<String>,
     #[serde(
         default = "Defaults::api_poll_interval",
         serialize_with = "serialize_duration",
@@ -115,6 +121,18 @@ pub struct Config {
     pub log_rotate_max_rotated_files: usize,
     #[serde(default = "Defaults::cert_authorization")]
     pub openssh_certificate_authorization: CertAuthorization,
+    #[serde(default = "Defaults::session_recording_enabled")]
+    pub session_recording_enabled: bool,
+    #[serde(default = "Defaults::session_recordings_path")]
+    pub session_recordings_path: PathBuf,
+    #[serde(
+        default = "Defaults::session_recording_max_age",
+        serialize_with = "serialize_duration",
+        deserialize_with = "deserialize_duration"
+    )]
+    pub session_recording_max_age: Duration,
+    #[serde(default = "Defaults::session_recording_max_per_machine")]
+    pub session_recording_max_per_machine: usize,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
@@ -199,6 +217,7 @@ impl Config {
             listen_address,
             metrics_address,
             authorized_keys_path: _,
+            authorized_keys_takeover: _,
             override_bmcs: _,
             host_key_path,
             dpus,
@@ -213,6 +232,8 @@ impl Config {
             client_key_path,
             openssh_certificate_ca_fingerprints: _,
             admin_certificate_role: _,
+            observer_certificate_role: _,
+            takeover_certificate_role: _,
             api_poll_interval,
             console_logs_path,
             console_logging_enabled,
@@ -223,12 +244,17 @@ impl Config {
             log_rotate_max_size,
             log_rotate_max_rotated_files,
             openssh_certificate_authorization,
+            session_recording_enabled,
+            session_recordings_path,
+            session_recording_max_age,
+            session_recording_max_per_machine,
         } = self;
         let api_poll_interval = format!("{}s", api_poll_interval.as_secs());
         let reconnect_interval_base = format!("{}s", reconnect_interval_base.as_secs());
         let reconnect_interval_max = format!("{}s", reconnect_interval_max.as_secs());
         let successful_connection_minimum_duration =
             format!("{}s", successful_connection_minimum_duration.as_secs());
+        let session_recording_max_age = format!("{}s", session_recording_max_age.as_secs());
         let carbide_uri = carbide_uri.to_string();
         let listen_address = listen_address.to_string();
         let metrics_address = metrics_address.to_string();
@@ -311,6 +337,14 @@ insecure = {insecure}
 ## extracted from certs.)
 # admin_certificate_role = <group>
 
+## Role which grants read-only access to consoles: Logins with an openssh certificate containing this
+## role (but not the admin role) can only observe consoles, they can never type into them.
+# observer_certificate_role = <group>
+
+## Role required to take over the write lock of a console from another user (by logging in as
+## `<machine_id>:takeover`.) If unset, nobody can take over the lock with a certificate.
+# takeover_certificate_role = <group>
+
 ## If true, use insecure ciphers when connecting to IPMI, like SHA1. Useful for ipmi_sim.
 insecure_ipmi_ciphers = {insecure_ipmi_ciphers}
 
@@ -318,6 +352,10 @@ insecure_ipmi_ciphers = {insecure_ipmi_ciphers}
 ## for integration tests. For interactive use, consider using openssh certificates instead.
 # authorized_keys_path = <path>
 
+## Whether keys in authorized_keys_path may take over the write lock of a console from another
+## user. Unless set, they can only use interactive and observer mode.
+# authorized_keys_takeover = true
+
 ## How often to poll the carbide API server for what machines are available
 api_poll_interval = {api_poll_interval:?}
 
@@ -351,6 +389,23 @@ log_rotate_max_size = {log_rotate_max_size:?}
 ## When rotating console logs, how many old logs should we keep? (e.g. 3 means we keep .log, .log.0, .log.1, and .log.2)
 log_rotate_max_rotated_files = {log_rotate_max_rotated_files}
 
+## Whether to record each frontend shell session (user input, console output and timing) in
+## asciicast v2 format, along with the identity of the user who opened it. Finished recordings are
+## uploaded to carbide-api. Recordings include everything users type, including passwords, in
+## plaintext.
+session_recording_enabled = {session_recording_enabled:?}
+
+## Where to write session recordings, if enabled. Recordings are written to a subdirectory per
+## machine.
+session_recordings_path = {session_recordings_path:?}
+
+## Session recordings older than this are deleted
+session_recording_max_age = {session_recording_max_age:?}
+
+## How many session recordings to keep for each machine. Older recordings beyond this count are
+## deleted.
+session_recording_max_per_machine = {session_recording_max_per_machine}
+
 ## Configure how the role is extracted from an SSH certificate
 [openssh_certificate_authorization]
 ## How should roles be extracted from SSH certs? (Currently supported: "key_id")
@@ -465,12 +520,19 @@ impl Default for Config {
             override_bmc_ssh_port: None,
             override_ipmi_port: None,
             authorized_keys_path: None,
+            authorized_keys_takeover: false,
             override_bmcs: None,
             insecure: false,
             insecure_ipmi_ciphers: false,
             override_bmc_ssh_host: None,
             admin_certificate_role: None,
+            observer_certificate_role: None,
+            takeover_certificate_role: None,
             openssh_certificate_ca_fingerprints: vec![],
+            session_recording_enabled: Defaults::session_recording_enabled(),
+            session_recordings_path: Defaults::session_recordings_path(),
+            session_recording_max_age: Defaults::session_recording_max_age(),
+            session_recording_max_per_machine: Defaults::session_recording_max_per_machine(),
         }
     }
 }
@@ -553,6 +615,22 @@ impl Defaults {
         true
     }
 
+    pub fn session_recording_enabled() -> bool {
+        true
+    }
+
+    pub fn session_recordings_path() -> PathBuf {
+        "/var/log/consoles/sessions".into()
+    }
+
+    pub fn session_recording_max_age() -> Duration {
+        Duration::from_secs(30 * 24 * 60 * 60)
+    }
+
+    pub fn session_recording_max_per_machine() -> usize {
+        500
+    }
+
     pub fn reconnect_interval_base() -> Duration {
         Duration::from_secs(10)
     }
@@ -18,7 +18,7 @@ use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::str::FromStr;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use carbide_uuid::machine::MachineId;
 use lazy_static::lazy_static;
@@ -28,7 +28,7 @@ use russh::keys::ssh_key::AuthorizedKeys;
 use russh::keys::{Certificate, PublicKey, PublicKeyBase64};
 use russh::server::{Auth, Msg, Session};
 use russh::{Channel, ChannelId, ChannelMsg, MethodKind, MethodSet, Pty};
-use tokio::sync::oneshot;
+use tokio::sync::{mpsc, oneshot};
 use tonic::Code;
 use uuid::Uuid;
 
@@ -37,13 +37,17 @@ use crate::bmc::client_pool::{BmcConnectionStore, GetConnectionError};
 use crate::bmc::connection::Kind;
 use crate::bmc::message_proxy;
 use crate::bmc::message_proxy::{ExecReply, ToBmcMessage};
+use crate::bmc::write_lock::LockHolder;
 use crate::config::Config;
+use crate::session_recorder::{self, FrontendIdentity, RecordingEvent, SessionInfo};
 use crate::shutdown_handle::ShutdownHandle;
 use crate::ssh_cert_parsing::{certificate_contains_role, get_user_from_certificate};
 use crate::ssh_server::ServerMetrics;
 
 static EXEC_TIMEOUT: Duration = Duration::from_secs(10);
 
+static READ_ONLY_NOTICE_INTERVAL: Duration = Duration::from_secs(10);
+
 static BANNER_SSH_BMC: &str = "\
 +------------------------------------------------------------------------------+\r\n\
 |                NVIDIA Carbide SSH Serial Console (beta)                      |\r\n\
@@ -85,6 +89,10 @@ pub struct Handler {
     bmc_connection_store: BmcConnectionStore,
     /// The machine_id or instance_id the user is attempting to log into. Used as the username in the ssh command line (ie. ssh machine_id@ssh-console)
     authenticated_machine_string: Option<String>,
+    /// Who authenticated, recorded in session recordings
+    identity: FrontendIdentity,
+    /// How the authenticated user may interact with the console
+    session_mode: SessionMode,
     per_client_state: HashMap<ChannelId, PerClientState>,
     metrics: Arc<ServerMetrics>,
     last_auth_failure: Option<AuthFailureReason>,
@@ -96,6 +104,126 @@ struct PerClientState {
     bmc_connection: BmcConnectionSubscription,
     // Option so that it can be taken with .take() when we get a shell_request or exec_request
     client_channel: Option<Channel<Msg>>,
+    // Terminal type and (cols, rows) from the pty request, if any
+    term: Option<String>,
+    terminal_size: Option<(u32, u32)>,
+    // Set once a shell session is being recorded
+    recording_tx: Option<mpsc::UnboundedSender<RecordingEvent>>,
+    // Identifies this session as the holder of the console write lock
+    session_id: Uuid,
+    session_mode: SessionMode,
+    // Who opened this session, shown to other frontends when holding the write lock
+    user: String,
+    peer_addr: String,
+    // When the user was last told their input is being discarded
+    last_read_only_notice: Option<Instant>,
+}
+
+impl PerClientState {
+    fn record(&self, event: RecordingEvent) {
+        if let Some(recording_tx) = &self.recording_tx {
+            recording_tx.send(event).ok();
+        }
+    }
+
+    fn lock_holder(&self) -> LockHolder {
+        LockHolder::new(self.session_id, self.user.clone(), self.peer_addr.clone())
+    }
+
+    /// Check whether input from this session may be written to the console, acquiring the write
+    /// lock if nobody holds it.
+    fn acquire_write_access(&self) -> WriteAccess {
+        let write_lock = &self.bmc_connection.write_lock;
+        match self.session_mode {
+            SessionMode::Observe => WriteAccess::Observer,
+            _ if write_lock.is_held_by(self.session_id) => WriteAccess::Allowed,
+            _ => match write_lock.try_acquire(self.lock_holder()) {
+                Ok(()) => WriteAccess::Allowed,
+                Err(holder) => WriteAccess::LockedBy(holder),
+            },
+        }
+    }
+
+    /// Tell the user their input was discarded, at most once every [`READ_ONLY_NOTICE_INTERVAL`].
+    fn notify_read_only(
+        &mut self,
+        channel_id: ChannelId,
+        session: &mut Session,
+        write_access: WriteAccess,
+    ) {
+        if self
+            .last_read_only_notice
+            .is_some_and(|last| last.elapsed() < READ_ONLY_NOTICE_INTERVAL)
+        {
+            return;
+        }
+        self.last_read_only_notice = Some(Instant::now());
+
+        let notice = match write_access {
+            WriteAccess::Allowed => return,
+            WriteAccess::Observer => {
+                "\r\n--- This session is read-only (observer mode), input is discarded ---\r\n"
+                    .to_string()
+            }
+            WriteAccess::LockedBy(holder) => format!(
+                "\r\n--- Console is read-only: write lock held by {holder}, input is discarded ---\r\n"
+            ),
+        };
+        session.data(channel_id, notice.into_bytes().into()).ok();
+    }
+}
+
+/// Whether a session may write to the console
+enum WriteAccess {
+    Allowed,
+    Observer,
+    LockedBy(LockHolder),
+}
+
+/// How a frontend session may interact with the console. Requested with a suffix on the SSH
+/// username (e.g. `ssh <machine_id>:observe@ssh-console`), and limited by how the user
+/// authenticated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum SessionMode {
+    /// Read-only, never acquires the console write lock
+    Observe,
+    /// Acquires the console write lock if nobody else holds it, read-only otherwise
+    Interactive,
+    /// Takes over the console write lock from whoever holds it
+    TakeOver,
+}
+
+/// Split an SSH username into the machine_id or instance_id, and the requested session mode.
+/// Returns None for unknown session modes.
+fn parse_username(username: &str) -> Option<(&str, SessionMode)> {
+    match username.rsplit_once(':') {
+        None => Some((username, SessionMode::Interactive)),
+        Some((machine_string, "observe" | "ro")) => Some((machine_string, SessionMode::Observe)),
+        Some((machine_string, "takeover")) => Some((machine_string, SessionMode::TakeOver)),
+        Some(_) => None,
+    }
+}
+
+/// The most an admin is allowed to do. Taking over the console write lock from other users must be
+/// granted explicitly: By `takeover_certificate_role` for certificates, and by
+/// `authorized_keys_takeover` for authorized_keys.
+fn admin_session_mode(takeover_granted: bool) -> SessionMode {
+    if takeover_granted {
+        SessionMode::TakeOver
+    } else {
+        SessionMode::Interactive
+    }
+}
+
+/// Determine the mode of a session from what the user requested and the most they're allowed.
+/// Users who may only observe are downgraded to read-only, but taking over the write lock is
+/// refused (None) unless allowed.
+fn effective_session_mode(requested: SessionMode, allowed: SessionMode) -> Option<SessionMode> {
+    if requested == SessionMode::TakeOver && allowed < SessionMode::TakeOver {
+        None
+    } else {
+        Some(requested.min(allowed))
+    }
 }
 
 impl Handler {
@@ -112,6 +240,8 @@ impl Handler {
             forge_api_client,
             bmc_connection_store,
             authenticated_machine_string: None,
+            identity: FrontendIdentity::default(),
+            session_mode: SessionMode::Interactive,
             per_client_state: HashMap::new(),
             metrics,
             last_auth_failure: Default::default(),
@@ -143,6 +273,14 @@ impl Handler {
 impl Drop for Handler {
     fn drop(&mut self) {
         tracing::info!(peer_addr = self.peer_addr, "end frontend connection");
+        // Release the write lock right away when the connection goes away, instead of waiting for
+        // the proxy task to notice, so that the next session to the console can type immediately.
+        for client_state in self.per_client_state.values() {
+            client_state
+                .bmc_connection
+                .write_lock
+                .release(client_state.session_id);
+        }
         // All auth failure paths set self.last_auth_failure, but auth can still succeed (they may
         // be trying multiple pubkeys, etc.) So if authenticated_user is None but last_auth_failure
         // is Some, bump the metrics.
@@ -209,6 +347,14 @@ impl russh::server::Handler for Handler {
             PerClientState {
                 bmc_connection,
                 client_channel: Some(channel),
+                term: None,
+                terminal_size: None,
+                recording_tx: None,
+                session_id: Uuid::new_v4(),
+                session_mode: self.session_mode,
+                user: self.identity.display_name().to_owned(),
+                peer_addr: self.peer_addr.clone(),
+                last_read_only_notice: None,
             },
         );
 
@@ -235,22 +381,41 @@ impl russh::server::Handler for Handler {
 
     async fn auth_openssh_certificate(
         &mut self,
-        machine_string: &str,
+        username: &str,
         certificate: &Certificate,
     ) -> Result<Auth, Self::Error> {
         tracing::trace!(peer_addr = self.peer_addr, "auth_openssh_certificate");
-        let Some(admin_certificate_role) = self.config.admin_certificate_role.as_ref() else {
-            tracing::debug!("skipping ssh certificate auth, no admin role is configured");
+        if self.config.admin_certificate_role.is_none()
+            && self.config.observer_certificate_role.is_none()
+        {
+            tracing::debug!(
+                "skipping ssh certificate auth, no admin or observer role is configured"
+            );
             return Ok(Auth::Reject {
                 proceed_with_methods: None,
                 partial_success: false,
             });
-        };
+        }
 
         let user =
             get_user_from_certificate(certificate, &self.config.openssh_certificate_authorization)
                 .map(str::to_owned);
 
+        let Some((machine_string, requested_mode)) = parse_username(username) else {
+            tracing::warn!(
+                peer_addr = self.peer_addr,
+                "certificate auth failed for user {username}, unknown session mode"
+            );
+            self.last_auth_failure = Some(AuthFailureReason::Certificate {
+                user,
+                machine_string: username.to_owned(),
+            });
+            return Ok(Auth::Reject {
+                proceed_with_methods: None,
+                partial_success: false,
+            });
+        };
+
         let is_trusted = certificate
             .validate(&self.config.openssh_certificate_ca_fingerprints)
             .is_ok();
@@ -271,14 +436,20 @@ impl russh::server::Handler for Handler {
             });
         }
 
-        if !certificate_contains_role(
-            certificate,
-            admin_certificate_role,
-            &self.config.openssh_certificate_authorization,
-        ) {
+        let cert_authorization = &self.config.openssh_certificate_authorization;
+        let has_role = |role: &Option<String>| {
+            role.as_ref().is_some_and(|role| {
+                certificate_contains_role(certificate, role, cert_authorization)
+            })
+        };
+        let allowed_mode = if has_role(&self.config.admin_certificate_role) {
+            admin_session_mode(has_role(&self.config.takeover_certificate_role))
+        } else if has_role(&self.config.observer_certificate_role) {
+            SessionMode::Observe
+        } else {
             tracing::warn!(
                 peer_addr = self.peer_addr,
-                "certificate auth failed for user {machine_string}, not in role {admin_certificate_role}",
+                "certificate auth failed for user {machine_string}, not in admin or observer role",
             );
             self.last_auth_failure = Some(AuthFailureReason::Certificate {
                 user,
@@ -288,70 +459,121 @@ impl russh::server::Handler for Handler {
                 proceed_with_methods: None,
                 partial_success: false,
             });
-        }
+        };
+
+        let Some(session_mode) = effective_session_mode(requested_mode, allowed_mode) else {
+            tracing::warn!(
+                peer_addr = self.peer_addr,
+                "certificate auth failed for user {machine_string}, not allowed to take over the console write lock",
+            );
+            self.last_auth_failure = Some(AuthFailureReason::Certificate {
+                user,
+                machine_string: machine_string.to_owned(),
+            });
+            return Ok(Auth::Reject {
+                proceed_with_methods: None,
+                partial_success: false,
+            });
+        };
 
         if let Some(user) = &user {
             tracing::info!(
                 peer_addr = self.peer_addr,
-                "certificate auth succeeded for user {user} to machine {machine_string}, in role {admin_certificate_role}"
+                "certificate auth succeeded for user {user} to machine {machine_string}, in mode {session_mode:?}"
             );
         } else {
             tracing::info!(
                 peer_addr = self.peer_addr,
-                "certificate auth succeeded to machine {machine_string}, in role {admin_certificate_role}"
+                "certificate auth succeeded to machine {machine_string}, in mode {session_mode:?}"
             );
         }
+        self.identity = FrontendIdentity {
+            user,
+            certificate_key_id: Some(certificate.key_id().to_owned()),
+        };
+        self.session_mode = session_mode;
         self.authenticated_machine_string = Some(machine_string.to_owned());
         Ok(Auth::Accept)
     }
 
     async fn auth_publickey(
         &mut self,
-        machine_string: &str,
+        username: &str,
         public_key: &PublicKey,
     ) -> Result<Auth, Self::Error> {
         use HandlerError::*;
         tracing::trace!(peer_addr = self.peer_addr, "auth_publickey");
 
+        let Some((machine_string, requested_mode)) = parse_username(username) else {
+            tracing::debug!(
+                peer_addr = self.peer_addr,
+                "rejecting public key for user {username}, unknown session mode"
+            );
+            self.last_auth_failure = Some(AuthFailureReason::PubKey {
+                machine_string: username.to_owned(),
+            });
+            return Ok(Auth::Reject {
+                partial_success: false,
+                proceed_with_methods: None,
+            });
+        };
+
         // Authentication flow:
         // 1. If authorized_keys_path is set, check against file first
         // 2. If not found in file, validate via carbide-api
         // 3. If insecure mode is enabled, accept all connections
 
-        let success = if pubkey_auth_admin_authorized_keys(public_key, &self.config, machine_string)
-            .map_err(|error| PubkeyAuthAdminAuthorizedKeys {
-                machine_id: machine_string.to_owned(),
-                error,
-            })? {
-            true
-        } else if Uuid::from_str(machine_string).is_ok() {
-            // Only try tenant auth if the user is a valid-looking UUID.
-            pubkey_auth_tenant(machine_string, public_key, &self.forge_api_client)
-                .await
-                .map_err(|error| PubkeyAuthTenant {
-                    instance_id: machine_string.to_owned(),
+        // Admins can take over the console write lock if the config allows it, tenants can only
+        // acquire it when it's free.
+        let allowed_mode =
+            if pubkey_auth_admin_authorized_keys(public_key, &self.config, machine_string).map_err(
+                |error| PubkeyAuthAdminAuthorizedKeys {
+                    machine_id: machine_string.to_owned(),
                     error,
-                })?
-        } else {
-            tracing::debug!(
-                peer_addr = self.peer_addr,
-                machine_string,
-                "rejecting public key for user {machine_string}"
-            );
-            false
-        };
+                },
+            )? {
+                Some(admin_session_mode(self.config.authorized_keys_takeover))
+            } else if Uuid::from_str(machine_string).is_ok() {
+                // Only try tenant auth if the user is a valid-looking UUID.
+                pubkey_auth_tenant(machine_string, public_key, &self.forge_api_client)
+                    .await
+                    .map_err(|error| PubkeyAuthTenant {
+                        instance_id: machine_string.to_owned(),
+                        error,
+                    })?
+                    .then_some(SessionMode::Interactive)
+            } else {
+                tracing::debug!(
+                    peer_addr = self.peer_addr,
+                    machine_string,
+                    "rejecting public key for user {machine_string}"
+                );
+                None
+            };
 
-        let success = if !success && self.config.insecure {
+        let allowed_mode = if allowed_mode.is_none() && self.config.insecure {
             tracing::info!(
                 peer_addr = self.peer_addr,
                 "Overriding public-key rejection because we are in insecure (testing) mode"
             );
-            true
+            Some(SessionMode::Interactive)
         } else {
-            success
+            allowed_mode
         };
 
-        if success {
+        let session_mode = allowed_mode.and_then(|allowed_mode| {
+            let session_mode = effective_session_mode(requested_mode, allowed_mode);
+            if session_mode.is_none() {
+                tracing::warn!(
+                    peer_addr = self.peer_addr,
+                    "rejecting public key for user {machine_string}, not allowed to take over the console write lock"
+                );
+            }
+            session_mode
+        });
+
+        if let Some(session_mode) = session_mode {
+            self.session_mode = session_mode;
             self.authenticated_machine_string = Some(machine_string.to_owned());
             Ok(Auth::Accept)
         } else {
@@ -374,6 +596,12 @@ impl russh::server::Handler for Handler {
     ) -> Result<(), Self::Error> {
         tracing::trace!(peer_addr = self.peer_addr, "data");
         if let Some(client_state) = self.get_client_state_or_report_error(session, channel) {
+            let write_access = client_state.acquire_write_access();
+            if !matches!(write_access, WriteAccess::Allowed) {
+                client_state.notify_read_only(channel, session, write_access);
+                return Ok(());
+            }
+            client_state.record(RecordingEvent::Input(data.to_vec()));
             client_state
                 .bmc_connection
                 .to_bmc_msg_tx
@@ -396,6 +624,12 @@ impl russh::server::Handler for Handler {
     ) -> Result<(), Self::Error> {
         tracing::trace!(peer_addr = self.peer_addr, "extended_data");
         if let Some(client_state) = self.get_client_state_or_report_error(session, channel) {
+            let write_access = client_state.acquire_write_access();
+            if !matches!(write_access, WriteAccess::Allowed) {
+                client_state.notify_read_only(channel, session, write_access);
+                return Ok(());
+            }
+            client_state.record(RecordingEvent::Input(data.to_vec()));
             client_state
                 .bmc_connection
                 .to_bmc_msg_tx
@@ -414,15 +648,19 @@ impl russh::server::Handler for Handler {
     async fn pty_request(
         &mut self,
         channel: ChannelId,
-        _term: &str,
-        _col_width: u32,
-        _row_height: u32,
+        term: &str,
+        col_width: u32,
+        row_height: u32,
         _pix_width: u32,
         _pix_height: u32,
         _modes: &[(Pty, u32)],
         session: &mut Session,
     ) -> Result<(), Self::Error> {
         tracing::trace!(peer_addr = self.peer_addr, "pty_request");
+        if let Some(client_state) = self.per_client_state.get_mut(&channel) {
+            client_state.term = Some(term.to_owned());
+            client_state.terminal_size = Some((col_width, row_height));
+        }
         session.channel_success(channel)?;
         Ok(())
     }
@@ -434,6 +672,12 @@ impl russh::server::Handler for Handler {
     ) -> Result<(), Self::Error> {
         tracing::trace!(peer_addr = self.peer_addr, "shell_request");
         let peer_addr = self.peer_addr.clone();
+        let config = self.config.clone();
+        let identity = self.identity.clone();
+        let machine_string = self
+            .authenticated_machine_string
+            .clone()
+            .unwrap_or_default();
         let Some(client_state) = self.get_client_state_or_report_error(session, channel_id) else {
             return Ok(());
         };
@@ -458,14 +702,32 @@ impl russh::server::Handler for Handler {
             return Ok(());
         };
         let machine_id = client_state.bmc_connection.machine_id;
-        let Some(from_bmc_rx) = client_state
+        let Some(to_frontend_msg_tx) = client_state
             .bmc_connection
             .to_frontend_msg_weak_tx
             .upgrade()
-            .map(|tx| tx.subscribe())
         else {
             return Err(HandlerError::BmcDisconnectedBeforeSubscribe { machine_id })?;
         };
+        let from_bmc_rx = to_frontend_msg_tx.subscribe();
+
+        // Record the session (if enabled), with its own subscription to the BMC output
+        let recorder = session_recorder::spawn(
+            config,
+            self.forge_api_client.clone(),
+            SessionInfo {
+                session_id: client_state.session_id,
+                machine_id,
+                machine_string,
+                identity,
+                peer_addr: peer_addr.clone(),
+                term: client_state.term.clone(),
+                terminal_size: client_state.terminal_size,
+            },
+            to_frontend_msg_tx.subscribe(),
+        );
+        std::mem::drop(to_frontend_msg_tx);
+        client_state.recording_tx = recorder.as_ref().map(|recorder| recorder.event_tx());
 
         // Output the banner with instructions
         let banner = match client_state.bmc_connection.kind {
@@ -474,6 +736,35 @@ impl russh::server::Handler for Handler {
         };
         session.data(channel_id, banner.into()).ok();
 
+        // Tell the user who may write to the console
+        let write_lock = client_state.bmc_connection.write_lock.clone();
+        let session_id = client_state.session_id;
+        let lock_status = match client_state.session_mode {
+            SessionMode::Observe => Some(match write_lock.holder() {
+                Some(holder) => format!(
+                    "--- Observer mode: this session is read-only. Write lock held by {holder} ---"
+                ),
+                None => "--- Observer mode: this session is read-only ---".to_string(),
+            }),
+            SessionMode::Interactive => write_lock
+                .try_acquire(client_state.lock_holder())
+                .err()
+                .map(|holder| {
+                    format!(
+                        "--- Console write lock held by {holder}. This session is read-only until it is released ---"
+                    )
+                }),
+            SessionMode::TakeOver => {
+                write_lock.take_over(client_state.lock_holder());
+                None
+            }
+        };
+        if let Some(lock_status) = lock_status {
+            session
+                .data(channel_id, format!("{lock_status}\r\n").into_bytes().into())
+                .ok();
+        }
+
         // Tell the backend to return any "pending line": data since the last newline
         let (mut channel_rx, channel_tx) = channel.split();
         let (pending_line_reply_tx, pending_line_reply_rx) = oneshot::channel();
@@ -486,6 +777,7 @@ impl russh::server::Handler for Handler {
             .await
             .ok();
         if let Ok(pending_line) = pending_line_reply_rx.await {
+            client_state.record(RecordingEvent::Output(pending_line.clone()));
             channel_tx.data(pending_line.as_slice()).await.ok();
         }
 
@@ -505,6 +797,10 @@ impl russh::server::Handler for Handler {
                     }
                 }
                 proxy_handle.shutdown_and_wait().await;
+                write_lock.release(session_id);
+                if let Some(recorder) = recorder {
+                    recorder.shutdown_and_wait().await;
+                }
             }
         });
 
@@ -518,16 +814,12 @@ impl russh::server::Handler for Handler {
         session: &mut Session,
     ) -> Result<(), Self::Error> {
         tracing::trace!(peer_addr = self.peer_addr, "exec_request");
-        let Some(PerClientState {
-            client_channel,
-            bmc_connection,
-        }) = self.get_client_state_or_report_error(session, channel_id)
-        else {
+        let Some(client_state) = self.get_client_state_or_report_error(session, channel_id) else {
             return Ok(());
         };
 
         // Drop the client channel when we're done, so that it properly disconnects.
-        let Some(channel) = client_channel.take() else {
+        let Some(channel) = client_state.client_channel.take() else {
             tracing::error!(
                 peer_addr = self.peer_addr,
                 "Channel unavailable, cannot service exec request"
@@ -543,17 +835,48 @@ impl russh::server::Handler for Handler {
             return Ok(());
         };
 
+        // Commands like "power reset" affect everybody watching the console, so they hold the
+        // write lock while they run, the same as typing into it.
+        let write_lock = client_state.bmc_connection.write_lock.clone();
+        let session_id = client_state.session_id;
+        let held_before = write_lock.is_held_by(session_id);
+        if client_state.session_mode == SessionMode::TakeOver {
+            write_lock.take_over(client_state.lock_holder());
+        }
+        let denied = match client_state.acquire_write_access() {
+            WriteAccess::Allowed => None,
+            WriteAccess::Observer => {
+                Some("Error: this session is read-only (observer mode)\r\n".to_string())
+            }
+            WriteAccess::LockedBy(holder) => {
+                Some(format!("Error: console write lock held by {holder}\r\n"))
+            }
+        };
+        if let Some(denied) = denied {
+            channel.data(denied.as_bytes()).await.ok();
+            channel.exit_status(1).await.ok();
+            session.channel_success(channel_id).ok();
+            channel.close().await.ok();
+            return Ok(());
+        }
+
         let (reply_tx, reply_rx) = oneshot::channel();
-        bmc_connection
+        let sent = client_state
+            .bmc_connection
             .to_bmc_msg_tx
             .send(ToBmcMessage::Exec {
                 command: data.to_vec(),
                 reply_tx,
             })
-            .await
-            .map_err(|_| HandlerError::WritingToChannel {
+            .await;
+        if sent.is_err() {
+            if !held_before {
+                write_lock.release(session_id);
+            }
+            return Err(HandlerError::WritingToChannel {
                 what: "exec request",
-            })?;
+            });
+        }
 
         tokio::select! {
             _ = tokio::time::sleep(EXEC_TIMEOUT) => {
@@ -581,6 +904,11 @@ impl russh::server::Handler for Handler {
             }
         }
 
+        // A lock acquired just for this command is released once it finished
+        if !held_before {
+            write_lock.release(session_id);
+        }
+
         session.channel_success(channel_id).ok();
         channel.close().await.ok();
 
@@ -598,6 +926,18 @@ impl russh::server::Handler for Handler {
     ) -> Result<(), Self::Error> {
         tracing::trace!(peer_addr = self.peer_addr, "window_change_request");
         if let Some(client_state) = self.get_client_state_or_report_error(session, channel) {
+            client_state.record(RecordingEvent::Resize {
+                col_width,
+                row_height,
+            });
+            // Only the session writing to the console gets to resize it
+            if !client_state
+                .bmc_connection
+                .write_lock
+                .is_held_by(client_state.session_id)
+            {
+                return Ok(());
+            }
             client_state
                 .bmc_connection
                 .to_bmc_msg_tx
@@ -783,3 +1123,64 @@ async fn pubkey_auth_tenant(
 
     Ok(authorized)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MACHINE_ID: &str = "fm100hteau2jdt69qg575qld4lj05me09u2qp7ei38uv7volvprkck9enkg";
+
+    #[test]
+    fn test_parse_username() {
+        assert_eq!(
+            parse_username(MACHINE_ID),
+            Some((MACHINE_ID, SessionMode::Interactive))
+        );
+        assert_eq!(
+            parse_username(&format!("{MACHINE_ID}:observe")),
+            Some((MACHINE_ID, SessionMode::Observe))
+        );
+        assert_eq!(
+            parse_username(&format!("{MACHINE_ID}:ro")),
+            Some((MACHINE_ID, SessionMode::Observe))
+        );
+        assert_eq!(
+            parse_username(&format!("{MACHINE_ID}:takeover")),
+            Some((MACHINE_ID, SessionMode::TakeOver))
+        );
+        assert_eq!(parse_username(&format!("{MACHINE_ID}:bogus")), None);
+    }
+
+    #[test]
+    fn test_effective_session_mode() {
+        use SessionMode::*;
+        // Observers are downgraded to read-only
+        assert_eq!(effective_session_mode(Interactive, Observe), Some(Observe));
+        assert_eq!(effective_session_mode(Observe, TakeOver), Some(Observe));
+        assert_eq!(
+            effective_session_mode(Interactive, TakeOver),
+            Some(Interactive)
+        );
+        // Taking over the lock is refused unless allowed
+        assert_eq!(effective_session_mode(TakeOver, Interactive), None);
+        assert_eq!(effective_session_mode(TakeOver, Observe), None);
+        assert_eq!(effective_session_mode(TakeOver, TakeOver), Some(TakeOver));
+    }
+
+    #[test]
+    fn test_admin_takeover_requires_grant() {
+        use SessionMode::*;
+        assert_eq!(
+            effective_session_mode(TakeOver, admin_session_mode(false)),
+            None
+        );
+        assert_eq!(
+            effective_session_mode(Interactive, admin_session_mode(false)),
+            Some(Interactive)
+        );
+        assert_eq!(
+            effective_session_mode(TakeOver, admin_session_mode(true)),
+            Some(TakeOver)
+        );
+    }
+}
@@ -23,6 +23,7 @@ mod ssh_server;
 
 mod console_logger;
 mod frontend;
+mod session_recorder;
 
 // pub mods are only ones used by main.rs and integration tests
 pub mod config;
@@ -68,10 +69,16 @@ pub async fn spawn(config: Config) -> Result<SpawnHandle, SpawnError> {
     // 3) Start metrics server
     let metrics_handle = metrics::spawn(config.clone(), metrics).await?;
 
-    // 4) Wait for a shutdown signal, then shut down the above
+    // 4) Start cleaning up old session recordings
+    let retention_handle = session_recorder::spawn_retention(config.clone());
+
+    // 5) Wait for a shutdown signal, then shut down the above
     let (shutdown_tx, shutdown_rx) = oneshot::channel();
     let join_handle = tokio::spawn(async move {
         shutdown_rx.await.ok();
+        if let Some(retention_handle) = retention_handle {
+            retention_handle.shutdown_and_wait().await;
+        }
         metrics_handle.shutdown_and_wait().await;
         bmc_client_pool.shutdown_and_wait().await;
         server.shutdown_and_wait().await;
@@ -0,0 +1,744 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Records frontend console sessions in the [asciicast v2] format.
+//!
+//! Each shell session opened by a frontend user gets its own recording at
+//! `{session_recordings_path}/{machine_id}/{start_time}_{session_id}.cast`. The first line is a
+//! JSON header describing the session (including who opened it), and every following line is an
+//! event of the form `[seconds_since_start, code, data]`, where code is one of:
+//!
+//! - `"o"`: output sent to the user (from the BMC)
+//! - `"i"`: input typed by the user (sent to the BMC)
+//! - `"r"`: the user's terminal was resized, data is `"{cols}x{rows}"`
+//! - `"m"`: a marker, used to note gaps in the recording
+//!
+//! When the session ends, its recording is uploaded to carbide-api, from where it can be listed and
+//! replayed with `carbide-admin-cli ssh-console`. The local copy can be played back with
+//! `asciinema play`, and is deleted according to the retention policy.
+//!
+//! Recordings contain everything the user typed, including any passwords entered on the console,
+//! in plaintext. Recording files are only readable by the user ssh-console runs as.
+//!
+//! [asciicast v2]: https://docs.asciinema.org/manual/asciicast/v2/
+
+use std::collections::HashMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use carbide_uuid::machine::MachineId;
+use chrono::{DateTime, Utc};
+use rpc::forge::{SshConsoleSession, SshConsoleSessionRecording};
+use rpc::forge_api_client::ForgeApiClient;
+use russh::ChannelMsg;
+use serde::Serialize;
+use tokio::fs::OpenOptions;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::{broadcast, mpsc, oneshot};
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+use uuid::Uuid;
+
+use crate::bmc::message_proxy::ToFrontendMessage;
+use crate::config::Config;
+use crate::shutdown_handle::ShutdownHandle;
+
+/// File extension for session recordings
+pub static RECORDING_EXTENSION: &str = "cast";
+
+/// How often old recordings are cleaned up
+static RETENTION_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Terminal size to assume if the frontend never sent a pty request
+static DEFAULT_TERMINAL_SIZE: (u32, u32) = (80, 24);
+
+/// Recordings contain passwords typed by users, so only the owner may read them
+static RECORDING_FILE_MODE: u32 = 0o600;
+static RECORDING_DIR_MODE: u32 = 0o700;
+
+/// Who opened a frontend session, as determined during authentication.
+#[derive(Debug, Clone, Default)]
+pub struct FrontendIdentity {
+    /// The user, as extracted from the openssh certificate (either from its principals or its Key
+    /// ID.) None for public key authentication.
+    pub user: Option<String>,
+    /// The full Key ID of the openssh certificate used to authenticate, if any.
+    pub certificate_key_id: Option<String>,
+}
+
+impl FrontendIdentity {
+    /// How to refer to the user in messages to other frontends
+    pub fn display_name(&self) -> &str {
+        self.user
+            .as_deref()
+            .or(self.certificate_key_id.as_deref())
+            .unwrap_or("<public key user>")
+    }
+}
+
+/// Everything known about a frontend session at the time it starts.
+#[derive(Debug, Clone)]
+pub struct SessionInfo {
+    pub session_id: Uuid,
+    pub machine_id: MachineId,
+    /// The machine_id or instance_id the user used to log in.
+    pub machine_string: String,
+    pub identity: FrontendIdentity,
+    pub peer_addr: String,
+    pub term: Option<String>,
+    /// (cols, rows) from the pty request, if any
+    pub terminal_size: Option<(u32, u32)>,
+}
+
+/// Events the frontend reports to the recorder, in addition to the output it gets from the BMC.
+#[derive(Debug)]
+pub enum RecordingEvent {
+    /// Data typed by the user
+    Input(Vec<u8>),
+    /// Data sent to the user which doesn't go through the BMC broadcast channel
+    Output(Vec<u8>),
+    /// The user's terminal was resized
+    Resize { col_width: u32, row_height: u32 },
+}
+
+/// The header line of a recording. The fields after `env` are extensions to asciicast v2, which
+/// asciinema ignores.
+#[derive(Debug, Serialize)]
+struct RecordingHeader<'a> {
+    version: u8,
+    width: u32,
+    height: u32,
+    timestamp: i64,
+    title: String,
+    env: HashMap<&'static str, &'a str>,
+    session_id: Uuid,
+    machine_id: String,
+    machine_string: &'a str,
+    user: Option<&'a str>,
+    certificate_key_id: Option<&'a str>,
+    peer_addr: &'a str,
+}
+
+/// Spawn a background task which records a frontend session, or return None if session recording
+/// is disabled.
+pub fn spawn(
+    config: Arc<Config>,
+    forge_api_client: ForgeApiClient,
+    session: SessionInfo,
+    output_rx: broadcast::Receiver<ToFrontendMessage>,
+) -> Option<SessionRecorderHandle> {
+    if !config.session_recording_enabled {
+        return None;
+    }
+
+    let (shutdown_tx, shutdown_rx) = oneshot::channel();
+    let (event_tx, event_rx) = mpsc::unbounded_channel();
+    let recorder = SessionRecorder::new(&config, forge_api_client, session);
+    let join_handle = tokio::spawn(recorder.run(shutdown_rx, output_rx, event_rx));
+
+    Some(SessionRecorderHandle {
+        shutdown_tx,
+        join_handle,
+        event_tx,
+    })
+}
+
+pub struct SessionRecorderHandle {
+    shutdown_tx: oneshot::Sender<()>,
+    join_handle: JoinHandle<()>,
+    event_tx: mpsc::UnboundedSender<RecordingEvent>,
+}
+
+impl SessionRecorderHandle {
+    pub fn event_tx(&self) -> mpsc::UnboundedSender<RecordingEvent> {
+        self.event_tx.clone()
+    }
+}
+
+impl ShutdownHandle<()> for SessionRecorderHandle {
+    fn into_parts(self) -> (oneshot::Sender<()>, JoinHandle<()>) {
+        (self.shutdown_tx, self.join_handle)
+    }
+}
+
+struct SessionRecorder {
+    session: SessionInfo,
+    forge_api_client: ForgeApiClient,
+    started_at: DateTime<Utc>,
+    path: PathBuf,
+}
+
+impl SessionRecorder {
+    fn new(config: &Config, forge_api_client: ForgeApiClient, session: SessionInfo) -> Self {
+        let started_at = Utc::now();
+        let path = recording_path(
+            &config.session_recordings_path,
+            &session.machine_id,
+            started_at,
+            session.session_id,
+        );
+        Self {
+            session,
+            forge_api_client,
+            started_at,
+            path,
+        }
+    }
+
+    async fn run(
+        self,
+        mut shutdown_rx: oneshot::Receiver<()>,
+        mut output_rx: broadcast::Receiver<ToFrontendMessage>,
+        mut event_rx: mpsc::UnboundedReceiver<RecordingEvent>,
+    ) {
+        let machine_id = self.session.machine_id;
+        let file = match self.open().await {
+            Ok(file) => file,
+            Err(error) => {
+                tracing::error!(path = self.path.display().to_string(), %machine_id, %error, "could not open session recording for writing");
+                return;
+            }
+        };
+        tracing::info!(
+            path = self.path.display().to_string(),
+            %machine_id,
+            peer_addr = self.session.peer_addr,
+            user = self.session.identity.user.as_deref().unwrap_or("<unknown>"),
+            "recording frontend session",
+        );
+
+        let mut writer = RecordingWriter::new(file);
+        loop {
+            tokio::select! {
+                _ = &mut shutdown_rx => {
+                    break;
+                }
+
+                res = output_rx.recv() => match res {
+                    Ok(msg) => {
+                        let msg = Arc::<ChannelMsg>::from(msg);
+                        match msg.as_ref() {
+                            ChannelMsg::Data { data } | ChannelMsg::ExtendedData { data, .. } => {
+                                let data = writer.bmc_output.decode(data);
+                                writer.write_event("o", &data).await;
+                            }
+                            _ => {}
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Closed) => {
+                        writer.write_event("m", "BMC connection closed").await;
+                        break;
+                    }
+                    Err(broadcast::error::RecvError::Lagged(count)) => {
+                        tracing::warn!(%machine_id, "session recorder is lagged by {count} messages, data may be missing from recording");
+                        writer.write_event("m", &format!("recording lagged, {count} messages missing")).await;
+                    }
+                },
+
+                Some(event) = event_rx.recv() => {
+                    writer.write_recording_event(event).await;
+                }
+            }
+        }
+
+        // Record any input which arrived before the shutdown
+        while let Ok(event) = event_rx.try_recv() {
+            writer.write_recording_event(event).await;
+        }
+
+        let write_failures = writer.finish().await;
+        if write_failures > 0 {
+            tracing::error!(%machine_id, session_id = %self.session.session_id, write_failures, "events are missing from session recording");
+        }
+        tracing::debug!(%machine_id, session_id = %self.session.session_id, "finished recording frontend session");
+
+        self.upload(&writer).await;
+    }
+
+    async fn open(&self) -> io::Result<tokio::fs::File> {
+        if let Some(parent) = self.path.parent() {
+            tokio::fs::DirBuilder::new()
+                .recursive(true)
+                .mode(RECORDING_DIR_MODE)
+                .create(parent)
+                .await?;
+        }
+
+        let mut file = OpenOptions::new()
+            .create_new(true)
+            .write(true)
+            .mode(RECORDING_FILE_MODE)
+            .open(&self.path)
+            .await?;
+
+        let (width, height) = self.session.terminal_size.unwrap_or(DEFAULT_TERMINAL_SIZE);
+        let env = match self.session.term.as_deref() {
+            Some(term) => HashMap::from([("TERM", term)]),
+            None => HashMap::new(),
+        };
+        let header = RecordingHeader {
+            version: 2,
+            width,
+            height,
+            timestamp: self.started_at.timestamp(),
+            title: format!("{} console", self.session.machine_string),
+            env,
+            session_id: self.session.session_id,
+            machine_id: self.session.machine_id.to_string(),
+            machine_string: &self.session.machine_string,
+            user: self.session.identity.user.as_deref(),
+            certificate_key_id: self.session.identity.certificate_key_id.as_deref(),
+            peer_addr: &self.session.peer_addr,
+        };
+        let mut line = serde_json::to_string(&header).map_err(io::Error::other)?;
+        line.push('\n');
+        file.write_all(line.as_bytes()).await?;
+        Ok(file)
+    }
+
+    /// Upload the finished recording to carbide-api, so it can be replayed from anywhere. The local
+    /// copy is kept until the retention policy deletes it, so a failed upload loses nothing.
+    async fn upload(&self, writer: &RecordingWriter) {
+        let machine_id = self.session.machine_id;
+        let recording = match tokio::fs::read_to_string(&self.path).await {
+            Ok(recording) => recording,
+            Err(error) => {
+                tracing::error!(path = self.path.display().to_string(), %machine_id, %error, "could not read session recording for upload");
+                return;
+            }
+        };
+        let name = self
+            .path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        if let Err(error) = self
+            .forge_api_client
+            .record_ssh_console_session(SshConsoleSessionRecording {
+                session: Some(SshConsoleSession {
+                    session_id: self.session.session_id.to_string(),
+                    machine_id: Some(machine_id),
+                    name,
+                    started: Some(self.started_at.into()),
+                    user: self.session.identity.user.clone(),
+                    certificate_key_id: self.session.identity.certificate_key_id.clone(),
+                    peer_addr: self.session.peer_addr.clone(),
+                    duration_secs: writer.last_event.as_secs_f64(),
+                    input_events: writer.input_events,
+                    output_events: writer.output_events,
+                }),
+                recording,
+            })
+            .await
+        {
+            tracing::error!(path = self.path.display().to_string(), %machine_id, %error, "could not upload session recording to carbide-api");
+        }
+    }
+}
+
+/// Writes the events of a recording, keeping track of what was written.
+struct RecordingWriter {
+    file: tokio::fs::File,
+    start: Instant,
+    /// Output from the BMC can split multi-byte characters across messages
+    bmc_output: Utf8Decoder,
+    input: Utf8Decoder,
+    /// Time of the last event relative to the start of the session
+    last_event: Duration,
+    input_events: u32,
+    output_events: u32,
+    write_failures: u32,
+}
+
+impl RecordingWriter {
+    fn new(file: tokio::fs::File) -> Self {
+        Self {
+            file,
+            start: Instant::now(),
+            bmc_output: Utf8Decoder::default(),
+            input: Utf8Decoder::default(),
+            last_event: Duration::ZERO,
+            input_events: 0,
+            output_events: 0,
+            write_failures: 0,
+        }
+    }
+
+    async fn write_recording_event(&mut self, event: RecordingEvent) {
+        match event {
+            RecordingEvent::Input(data) => {
+                let data = self.input.decode(&data);
+                self.write_event("i", &data).await
+            }
+            RecordingEvent::Output(data) => {
+                self.write_event("o", &String::from_utf8_lossy(&data)).await
+            }
+            RecordingEvent::Resize {
+                col_width,
+                row_height,
+            } => {
+                self.write_event("r", &format!("{col_width}x{row_height}"))
+                    .await
+            }
+        }
+    }
+
+    /// Write an event, unless it has no data (which happens while a character is incomplete).
+    /// Failures are logged once and counted, so that a full disk doesn't flood the log.
+    async fn write_event(&mut self, code: &str, data: &str) {
+        if data.is_empty() {
+            return;
+        }
+        let elapsed = self.start.elapsed();
+        if let Err(error) = self
+            .file
+            .write_all(format_event(elapsed, code, data).as_bytes())
+            .await
+        {
+            if self.write_failures == 0 {
+                tracing::error!(%error, "error writing to session recording, further errors are only counted");
+            }
+            self.write_failures += 1;
+            return;
+        }
+        self.last_event = elapsed;
+        match code {
+            "i" => self.input_events += 1,
+            "o" => self.output_events += 1,
+            _ => {}
+        }
+    }
+
+    /// Write what's left of incomplete characters and flush the file, returning how many events
+    /// could not be written.
+    async fn finish(&mut self) -> u32 {
+        let output = self.bmc_output.finish();
+        self.write_event("o", &output).await;
+        let input = self.input.finish();
+        self.write_event("i", &input).await;
+        if let Err(error) = self.file.flush().await {
+            tracing::error!(%error, "error flushing session recording");
+            self.write_failures += 1;
+        }
+        self.write_failures
+    }
+}
+
+/// Decodes a stream of UTF-8 split into arbitrary chunks, holding back a character which is
+/// incomplete at the end of a chunk until the rest of it arrives. Invalid sequences are replaced
+/// with U+FFFD.
+#[derive(Debug, Default)]
+struct Utf8Decoder {
+    pending: Vec<u8>,
+}
+
+impl Utf8Decoder {
+    fn decode(&mut self, data: &[u8]) -> String {
+        self.pending.extend_from_slice(data);
+        let mut decoded = String::with_capacity(self.pending.len());
+        let mut rest = self.pending.as_slice();
+        loop {
+            match std::str::from_utf8(rest) {
+                Ok(valid) => {
+                    decoded.push_str(valid);
+                    rest = &[];
+                    break;
+                }
+                Err(error) => {
+                    let (valid, invalid) = rest.split_at(error.valid_up_to());
+                    decoded.push_str(
+                        std::str::from_utf8(valid).expect("BUG: valid_up_to is valid UTF-8"),
+                    );
+                    match error.error_len() {
+                        Some(len) => {
+                            decoded.push(char::REPLACEMENT_CHARACTER);
+                            rest = &invalid[len..];
+                        }
+                        // The character is incomplete, wait for the rest of it
+                        None => {
+                            rest = invalid;
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+        let consumed = self.pending.len() - rest.len();
+        self.pending.drain(..consumed);
+        decoded
+    }
+
+    /// Decode whatever is still pending, at the end of the stream
+    fn finish(&mut self) -> String {
+        let decoded = String::from_utf8_lossy(&self.pending).into_owned();
+        self.pending.clear();
+        decoded
+    }
+}
+
+/// Format a single asciicast event line, including the trailing newline.
+fn format_event(elapsed: Duration, code: &str, data: &str) -> String {
+    // A tuple serializes as a JSON array, which is what asciicast expects.
+    let mut line = serde_json::to_string(&(elapsed.as_secs_f64(), code, data))
+        .expect("BUG: serializing a tuple of primitives failed");
+    line.push('\n');
+    line
+}
+
+fn recording_path(
+    recordings_path: &Path,
+    machine_id: &MachineId,
+    started_at: DateTime<Utc>,
+    session_id: Uuid,
+) -> PathBuf {
+    recordings_path.join(machine_id.to_string()).join(format!(
+        "{}_{session_id}.{RECORDING_EXTENSION}",
+        started_at.format("%Y%m%dT%H%M%SZ")
+    ))
+}
+
+/// Spawn a background task which periodically deletes session recordings according to the
+/// configured retention policy. Returns None if session recording is disabled.
+pub fn spawn_retention(config: Arc<Config>) -> Option<RetentionHandle> {
+    if !config.session_recording_enabled {
+        return None;
+    }
+
+    let (shutdown_tx, mut shutdown_rx) = oneshot::channel::<()>();
+    let join_handle = tokio::spawn(async move {
+        let mut interval = tokio::time::interval(RETENTION_INTERVAL);
+        loop {
+            tokio::select! {
+                _ = &mut shutdown_rx => {
+                    break;
+                }
+                _ = interval.tick() => {
+                    let recordings_path = config.session_recordings_path.clone();
+                    let policy = RetentionPolicy {
+                        max_age: config.session_recording_max_age,
+                        max_per_machine: config.session_recording_max_per_machine,
+                    };
+                    match tokio::task::spawn_blocking(move || {
+                        prune_recordings(&recordings_path, &policy, SystemTime::now())
+                    })
+                    .await
+                    {
+                        Ok(Ok(0)) => {}
+                        Ok(Ok(deleted)) => {
+                            tracing::info!("deleted {deleted} session recordings past their retention");
+                        }
+                        Ok(Err(error)) => {
+                            tracing::error!(%error, "error applying session recording retention");
+                        }
+                        Err(error) => {
+                            tracing::error!(%error, "session recording retention task panicked");
+                        }
+                    }
+                }
+            }
+        }
+    });
+
+    Some(RetentionHandle {
+        shutdown_tx,
+        join_handle,
+    })
+}
+
+pub struct RetentionHandle {
+    shutdown_tx: oneshot::Sender<()>,
+    join_handle: JoinHandle<()>,
+}
+
+impl ShutdownHandle<()> for RetentionHandle {
+    fn into_parts(self) -> (oneshot::Sender<()>, JoinHandle<()>) {
+        (self.shutdown_tx, self.join_handle)
+    }
+}
+
+struct RetentionPolicy {
+    /// Recordings last modified longer ago than this are deleted
+    max_age: Duration,
+    /// Only the newest this many recordings are kept for each machine
+    max_per_machine: usize,
+}
+
+/// Delete recordings which fall outside the retention policy, returning how many were deleted.
+///
+/// Recordings are expected in one directory per machine. Within a directory, file names start with
+/// the session start time, so sorting by name sorts them from oldest to newest.
+fn prune_recordings(
+    recordings_path: &Path,
+    policy: &RetentionPolicy,
+    now: SystemTime,
+) -> io::Result<usize> {
+    let machine_dirs = match std::fs::read_dir(recordings_path) {
+        Ok(dirs) => dirs,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(0),
+        Err(error) => return Err(error),
+    };
+
+    let mut deleted = 0;
+    for machine_dir in machine_dirs {
+        let machine_dir = machine_dir?;
+        if !machine_dir.file_type()?.is_dir() {
+            continue;
+        }
+
+        let mut recordings = std::fs::read_dir(machine_dir.path())?
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|extension| extension == RECORDING_EXTENSION)
+            })
+            .collect::<Vec<_>>();
+        recordings.sort();
+
+        let excess = recordings.len().saturating_sub(policy.max_per_machine);
+        for (index, path) in recordings.iter().enumerate() {
+            let expired = index < excess
+                || std::fs::metadata(path)?
+                    .modified()
+                    .ok()
+                    .and_then(|modified| now.duration_since(modified).ok())
+                    .is_some_and(|age| age > policy.max_age);
+            if expired {
+                tracing::debug!("deleting session recording at {}", path.display());
+                std::fs::remove_file(path)?;
+                deleted += 1;
+            }
+        }
+    }
+
+    Ok(deleted)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use temp_dir::TempDir;
+
+    use super::*;
+
+    const MACHINE_ID: &str = "fm100hteau2jdt69qg575qld4lj05me09u2qp7ei38uv7volvprkck9enkg";
+
+    #[test]
+    fn test_format_event() {
+        assert_eq!(
+            format_event(Duration::from_millis(1500), "o", "hello\r\n"),
+            "[1.5,\"o\",\"hello\\r\\n\"]\n"
+        );
+        assert_eq!(
+            format_event(Duration::ZERO, "r", "120x40"),
+            "[0.0,\"r\",\"120x40\"]\n"
+        );
+    }
+
+    #[test]
+    fn test_utf8_decoder() {
+        let mut decoder = Utf8Decoder::default();
+        // "é" is 0xc3 0xa9, and "€" is 0xe2 0x82 0xac
+        assert_eq!(decoder.decode(b"caf\xc3"), "caf");
+        assert_eq!(decoder.decode(b"\xa9 \xe2"), "é ");
+        assert_eq!(decoder.decode(b"\x82"), "");
+        assert_eq!(decoder.decode(b"\xac!"), "€!");
+        // Invalid bytes are replaced without holding back what follows
+        assert_eq!(decoder.decode(b"a\xffb"), "a\u{fffd}b");
+        // An incomplete character at the end of the stream is replaced
+        assert_eq!(decoder.decode(b"\xe2\x82"), "");
+        assert_eq!(decoder.finish(), "\u{fffd}");
+        assert_eq!(decoder.finish(), "");
+    }
+
+    #[test]
+    fn test_recording_path() {
+        let machine_id = MachineId::from_str(MACHINE_ID).unwrap();
+        let session_id = Uuid::nil();
+        let started_at = DateTime::parse_from_rfc3339("2026-03-01T12:34:56Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(
+            recording_path(
+                Path::new("/recordings"),
+                &machine_id,
+                started_at,
+                session_id
+            ),
+            PathBuf::from(format!(
+                "/recordings/{MACHINE_ID}/20260301T123456Z_00000000-0000-0000-0000-000000000000.cast"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_prune_recordings() {
+        let dir = TempDir::new().unwrap();
+        let machine_dir = dir.path().join(MACHINE_ID);
+        std::fs::create_dir_all(&machine_dir).unwrap();
+        for name in [
+            "20260301T000000Z_a.cast",
+            "20260302T000000Z_b.cast",
+            "20260303T000000Z_c.cast",
+            "notes.txt",
+        ] {
+            std::fs::write(machine_dir.join(name), "{}\n").unwrap();
+        }
+
+        // Only the newest two recordings are kept, other files are left alone
+        let policy = RetentionPolicy {
+            max_age: Duration::from_secs(3600),
+            max_per_machine: 2,
+        };
+        assert_eq!(
+            prune_recordings(dir.path(), &policy, SystemTime::now()).unwrap(),
+            1
+        );
+        assert!(!machine_dir.join("20260301T000000Z_a.cast").exists());
+        assert!(machine_dir.join("20260302T000000Z_b.cast").exists());
+        assert!(machine_dir.join("notes.txt").exists());
+
+        // Everything is older than max_age a day from now
+        assert_eq!(
+            prune_recordings(
+                dir.path(),
+                &policy,
+                SystemTime::now() + Duration::from_secs(86400)
+            )
+            .unwrap(),
+            2
+        );
+        assert!(machine_dir.join("notes.txt").exists());
+    }
+
+    #[test]
+    fn test_prune_missing_recordings_path() {
+        let dir = TempDir::new().unwrap();
+        let policy = RetentionPolicy {
+            max_age: Duration::from_secs(3600),
+            max_per_machine: 2,
+        };
+        assert_eq!(
+            prune_recordings(&dir.path().join("missing"), &policy, SystemTime::now()).unwrap(),
+            0
+        );
+    }
+}
@@ -14,6 +14,7 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+use std::os::unix::fs::PermissionsExt;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
@@ -154,6 +155,41 @@ async fn test_ssh_console() -> eyre::Result<()> {
             log_path.display(),
             logs
         );
+
+        // Each frontend session should have been recorded
+        let recordings_path = logs_path
+            .join("sessions")
+            .join(mock_host.machine_id.to_string());
+        let recordings = std::fs::read_dir(&recordings_path)
+            .with_context(|| {
+                format!(
+                    "error reading session recordings at {}",
+                    recordings_path.display()
+                )
+            })?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<Result<Vec<_>, _>>()?;
+        assert!(
+            !recordings.is_empty(),
+            "did not see any session recordings at {}",
+            recordings_path.display()
+        );
+        for recording_path in recordings {
+            let recording = std::fs::read_to_string(&recording_path).with_context(|| {
+                format!(
+                    "error reading session recording at {}",
+                    recording_path.display()
+                )
+            })?;
+            let header: serde_json::Value =
+                serde_json::from_str(recording.lines().next().unwrap_or_default())
+                    .with_context(|| format!("invalid header in {}", recording_path.display()))?;
+            assert_eq!(header["version"], 2);
+            assert_eq!(header["machine_id"], mock_host.machine_id.to_string());
+            // Recordings contain passwords typed by users
+            let mode = std::fs::metadata(&recording_path)?.permissions().mode();
+            assert_eq!(mode & 0o777, 0o600, "{}", recording_path.display());
+        }
     }
 
     Ok(())
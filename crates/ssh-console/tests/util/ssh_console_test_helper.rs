@@ -78,6 +78,9 @@ pub async fn spawn(
         client_key_path: API_CLIENT_KEY.clone(),
         openssh_certificate_ca_fingerprints: vec![],
         admin_certificate_role: None,
+        observer_certificate_role: None,
+        takeover_certificate_role: None,
+        authorized_keys_takeover: false,
         api_poll_interval: Duration::from_secs(1),
         console_logging_enabled: true,
         console_logs_path: logs_dir.path().to_path_buf(),
@@ -100,6 +103,10 @@ pub async fn spawn(
         log_rotate_max_size: Size::from_kib(10),
         hosts: true,
         openssh_certificate_authorization: ssh_console::config::Defaults::cert_authorization(),
+        session_recording_enabled: true,
+        session_recordings_path: logs_dir.path().join("sessions"),
+        session_recording_max_age: Defaults::session_recording_max_age(),
+        session_recording_max_per_machine: Defaults::session_recording_max_per_machine(),
     };
 
     let spawn_handle = ssh_console::spawn(config).await?;
@@ -16,3 +16,4 @@
  */
 
 pub mod systemd;
+pub mod unit;
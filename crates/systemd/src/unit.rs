@@ -0,0 +1,122 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Managing systemd units through `systemctl`
+
+use eyre::WrapErr;
+use tokio::process::Command;
+
+/// The state of a unit, as reported by `systemctl show`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UnitState {
+    /// e.g. `loaded` or `not-found`
+    pub load_state: String,
+    /// e.g. `active`, `activating`, `inactive` or `failed`
+    pub active_state: String,
+    /// The unit type specific state, e.g. `running` or `dead` for services
+    pub sub_state: String,
+}
+
+impl UnitState {
+    /// Whether systemd knows about the unit
+    pub fn is_loaded(&self) -> bool {
+        self.load_state == "loaded"
+    }
+}
+
+/// Reload the systemd manager configuration, to pick up changed unit files
+pub async fn daemon_reload() -> eyre::Result<()> {
+    systemctl(&["daemon-reload"]).await.map(|_| ())
+}
+
+pub async fn start(unit: &str) -> eyre::Result<()> {
+    systemctl(&["start", unit]).await.map(|_| ())
+}
+
+pub async fn stop(unit: &str) -> eyre::Result<()> {
+    systemctl(&["stop", unit]).await.map(|_| ())
+}
+
+pub async fn restart(unit: &str) -> eyre::Result<()> {
+    systemctl(&["restart", unit]).await.map(|_| ())
+}
+
+/// Reset the failed state of a unit, including the counter of its start rate limit
+pub async fn reset_failed(unit: &str) -> eyre::Result<()> {
+    systemctl(&["reset-failed", unit]).await.map(|_| ())
+}
+
+/// Get the state of a unit. Units which systemd doesn't know about are reported with a
+/// `load_state` of `not-found`.
+pub async fn state(unit: &str) -> eyre::Result<UnitState> {
+    let output = systemctl(&["show", "--property=LoadState,ActiveState,SubState", unit]).await?;
+    Ok(parse_state(&output))
+}
+
+fn parse_state(output: &str) -> UnitState {
+    let mut state = UnitState::default();
+    for (key, value) in output.lines().filter_map(|line| line.split_once('=')) {
+        match key {
+            "LoadState" => state.load_state = value.to_string(),
+            "ActiveState" => state.active_state = value.to_string(),
+            "SubState" => state.sub_state = value.to_string(),
+            _ => {}
+        }
+    }
+    state
+}
+
+async fn systemctl(args: &[&str]) -> eyre::Result<String> {
+    let output = Command::new("systemctl")
+        .args(args)
+        .output()
+        .await
+        .wrap_err_with(|| format!("Failed to run systemctl {}", args.join(" ")))?;
+
+    if !output.status.success() {
+        eyre::bail!(
+            "systemctl {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_state() {
+        let state = parse_state("LoadState=loaded\nActiveState=active\nSubState=running\n");
+        assert_eq!(
+            state,
+            UnitState {
+                load_state: "loaded".to_string(),
+                active_state: "active".to_string(),
+                sub_state: "running".to_string(),
+            }
+        );
+        assert!(state.is_loaded());
+
+        let state = parse_state("LoadState=not-found\nActiveState=inactive\nSubState=dead\n");
+        assert!(!state.is_loaded());
+        assert_eq!(state.active_state, "inactive");
+    }
+}
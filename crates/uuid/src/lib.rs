@@ -17,6 +17,7 @@
 use std::error::Error;
 use std::fmt;
 
+pub mod capacity_reservation;
 pub mod domain;
 pub mod dpa_interface;
 pub mod dpu_remediations;
@@ -32,6 +33,7 @@ pub mod nvlink;
 pub mod power_shelf;
 pub mod rack;
 pub mod switch;
+pub mod tenant_dns_record;
 pub mod typed_uuids;
 pub mod vpc;
 pub mod vpc_peering;